@@ -3,3 +3,12 @@
 // Bezier curve intersection algorithm
 pub const F64PRECISE: f64 = f64::EPSILON * ((1 << 7) as f64); // ~= 2^(-45) - For f64 comparisons to allow for rounding error; note that f64::EPSILON ~= 2^(-52)
 pub const F64LOOSE: f64 = f64::EPSILON * ((1 << 20) as f64); // ~= 2^(-32) - For comparisons between values that are a result of complex computations where error accumulates
+
+// LOADING
+
+/// The largest document or SVG file that is read, in bytes. Larger files are refused rather than risking running out of memory while reading them.
+pub const MAX_FILE_SIZE: usize = 512 * 1024 * 1024;
+/// The deepest that folders may be nested within each other, and elements within an imported SVG. The layer tree is walked recursively,
+/// so deeper nesting from a malformed file could overflow the stack. Each folder also nests four levels deep in a saved document, which
+/// can't be read back beyond 128 levels, and the document's operation log and the node networks of its layers take up some of those.
+pub const MAX_FOLDER_DEPTH: usize = 20;
@@ -0,0 +1,238 @@
+use crate::document::{Document, LayerId};
+use crate::layers::folder_layer::FolderLayer;
+use crate::layers::layer_info::{Layer, LayerDataType};
+
+/// A property of a layer which differs between two versions of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LayerProperty {
+	Name,
+	/// The shape, the node graph, or for folders, the isolation and knockout options and the default style.
+	Content,
+	Transform,
+	Visibility,
+	/// Whether clicks pass through the layer.
+	ClickThrough,
+	/// Whether the layer inherits its style from the folders above it.
+	StyleInheritance,
+	BlendMode,
+	Opacity,
+	/// The order of the layers inside a folder.
+	Order,
+}
+
+impl LayerProperty {
+	pub fn label(&self) -> &'static str {
+		match self {
+			LayerProperty::Name => "name",
+			LayerProperty::Content => "content",
+			LayerProperty::Transform => "transform",
+			LayerProperty::Visibility => "visibility",
+			LayerProperty::ClickThrough => "click-through",
+			LayerProperty::StyleInheritance => "style inheritance",
+			LayerProperty::BlendMode => "blend mode",
+			LayerProperty::Opacity => "opacity",
+			LayerProperty::Order => "layer order",
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerChange {
+	Added,
+	Removed,
+	Changed(Vec<LayerProperty>),
+}
+
+/// A layer which differs between two versions of a document. Layers are matched by their path, so one moved to another folder is removed and added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayerDiff {
+	pub path: Vec<LayerId>,
+	/// The name of the layer in the newer version, or the older one if it was removed. `None` for unnamed layers and the root folder.
+	pub name: Option<String>,
+	pub change: LayerChange,
+}
+
+/// The differences between two versions of a document, from [Document::diff].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentDiff {
+	/// The differing layers, in the order they appear in the layer tree. The layers inside added or removed folders aren't listed separately.
+	pub layers: Vec<LayerDiff>,
+	pub text_styles_changed: bool,
+}
+
+impl DocumentDiff {
+	pub fn is_empty(&self) -> bool {
+		self.layers.is_empty() && !self.text_styles_changed
+	}
+
+	/// The number of layers added, removed, and changed.
+	pub fn counts(&self) -> (usize, usize, usize) {
+		self.layers.iter().fold((0, 0, 0), |(added, removed, changed), layer| match layer.change {
+			LayerChange::Added => (added + 1, removed, changed),
+			LayerChange::Removed => (added, removed + 1, changed),
+			LayerChange::Changed(_) => (added, removed, changed + 1),
+		})
+	}
+}
+
+impl Document {
+	/// Compares this document with a newer version of it, listing the layers that were added, removed, or changed on the way to the newer version.
+	pub fn diff(&self, newer: &Document) -> DocumentDiff {
+		let mut diff = DocumentDiff {
+			layers: Vec::new(),
+			text_styles_changed: self.text_styles != newer.text_styles,
+		};
+		diff_layers(&mut vec![], &self.root, &newer.root, &mut diff.layers);
+		diff
+	}
+}
+
+fn diff_layers(path: &mut Vec<LayerId>, older: &Layer, newer: &Layer, layers: &mut Vec<LayerDiff>) {
+	let mut properties = Vec::new();
+	if older.name != newer.name {
+		properties.push(LayerProperty::Name);
+	}
+	if !same_content(&older.data, &newer.data) {
+		properties.push(LayerProperty::Content);
+	}
+	if older.transform != newer.transform || older.pivot != newer.pivot {
+		properties.push(LayerProperty::Transform);
+	}
+	if older.visible != newer.visible || older.export_visible != newer.export_visible {
+		properties.push(LayerProperty::Visibility);
+	}
+	if older.click_through != newer.click_through {
+		properties.push(LayerProperty::ClickThrough);
+	}
+	if older.inherit_style != newer.inherit_style {
+		properties.push(LayerProperty::StyleInheritance);
+	}
+	if older.blend_mode != newer.blend_mode {
+		properties.push(LayerProperty::BlendMode);
+	}
+	if older.opacity != newer.opacity {
+		properties.push(LayerProperty::Opacity);
+	}
+
+	let folders = match (&older.data, &newer.data) {
+		(LayerDataType::Folder(older), LayerDataType::Folder(newer)) => Some((older, newer)),
+		_ => None,
+	};
+	if let Some((older_folder, newer_folder)) = folders {
+		if !same_order(older_folder, newer_folder) {
+			properties.push(LayerProperty::Order);
+		}
+	}
+
+	if !properties.is_empty() {
+		layers.push(LayerDiff {
+			path: path.clone(),
+			name: newer.name.clone(),
+			change: LayerChange::Changed(properties),
+		});
+	}
+
+	let Some((older_folder, newer_folder)) = folders else { return };
+
+	for (id, newer_layer) in newer_folder.layer_ids.iter().zip(newer_folder.layers()) {
+		path.push(*id);
+		match older_folder.layer(*id) {
+			Some(older_layer) => diff_layers(path, older_layer, newer_layer, layers),
+			None => layers.push(LayerDiff {
+				path: path.clone(),
+				name: newer_layer.name.clone(),
+				change: LayerChange::Added,
+			}),
+		}
+		path.pop();
+	}
+	for (id, older_layer) in older_folder.layer_ids.iter().zip(older_folder.layers()) {
+		if newer_folder.layer(*id).is_none() {
+			layers.push(LayerDiff {
+				path: [path.as_slice(), &[*id]].concat(),
+				name: older_layer.name.clone(),
+				change: LayerChange::Removed,
+			});
+		}
+	}
+}
+
+/// Whether the data of two layers is the same, not counting the layers inside folders, which are compared separately.
+fn same_content(older: &LayerDataType, newer: &LayerDataType) -> bool {
+	match (older, newer) {
+		(LayerDataType::Folder(older), LayerDataType::Folder(newer)) => older.isolated == newer.isolated && older.knockout == newer.knockout && older.default_style == newer.default_style,
+		(LayerDataType::Shape(older), LayerDataType::Shape(newer)) => older == newer,
+		(LayerDataType::Layer(older), LayerDataType::Layer(newer)) => older.network == newer.network,
+		_ => false,
+	}
+}
+
+/// Whether the layers kept in both versions of a folder are in the same order, ignoring those that were added or removed.
+fn same_order(older: &FolderLayer, newer: &FolderLayer) -> bool {
+	let kept_in_older = older.layer_ids.iter().filter(|id| newer.layer_ids.contains(id));
+	let kept_in_newer = newer.layer_ids.iter().filter(|id| older.layer_ids.contains(id));
+	kept_in_older.eq(kept_in_newer)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::operation::Operation;
+	use crate::test_utils::add_rect;
+
+	use glam::{DAffine2, DVec2};
+
+	/// A folder holding two rectangles, followed by another rectangle outside of it.
+	fn saved_document() -> Document {
+		let mut document = Document::default();
+		document.handle_operation(Operation::CreateFolder { path: vec![1], insert_index: -1 }).unwrap();
+		add_rect(&mut document, vec![1, 10]);
+		add_rect(&mut document, vec![1, 11]);
+		add_rect(&mut document, vec![2]);
+		document
+	}
+
+	#[test]
+	fn unchanged_document_has_no_differences() {
+		let saved = saved_document();
+		assert!(saved.diff(&saved.clone()).is_empty());
+	}
+
+	#[test]
+	fn added_removed_and_changed_layers_are_listed_in_tree_order() {
+		let saved = saved_document();
+		let mut edited = saved.clone();
+
+		edited.handle_operation(Operation::DeleteLayer { path: vec![1, 11] }).unwrap();
+		let name = "Moved".to_string();
+		edited.handle_operation(Operation::SetLayerName { path: vec![2], name }).unwrap();
+		let transform = DAffine2::from_translation(DVec2::new(10., 0.)).to_cols_array();
+		edited.handle_operation(Operation::TransformLayer { path: vec![2], transform }).unwrap();
+		add_rect(&mut edited, vec![3]);
+		// The layers inside an added folder aren't listed on their own
+		edited.handle_operation(Operation::CreateFolder { path: vec![4], insert_index: -1 }).unwrap();
+		add_rect(&mut edited, vec![4, 40]);
+
+		let diff = saved.diff(&edited);
+		let layer = |path: Vec<u64>, name: Option<&str>, change| LayerDiff {
+			path,
+			name: name.map(str::to_string),
+			change,
+		};
+		assert_eq!(
+			diff.layers,
+			vec![
+				layer(vec![1, 11], None, LayerChange::Removed),
+				layer(vec![2], Some("Moved"), LayerChange::Changed(vec![LayerProperty::Name, LayerProperty::Transform])),
+				layer(vec![3], None, LayerChange::Added),
+				layer(vec![4], None, LayerChange::Added),
+			]
+		);
+		assert_eq!(diff.counts(), (2, 1, 1));
+		assert!(!diff.text_styles_changed);
+
+		// Compared the other way around, the added layers are the removed ones
+		let (added, removed, changed) = edited.diff(&saved).counts();
+		assert_eq!((added, removed, changed), (1, 2, 1));
+	}
+}
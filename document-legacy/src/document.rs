@@ -1,9 +1,14 @@
+use crate::consts::MAX_FOLDER_DEPTH;
 use crate::intersection::Quad;
-use crate::layers::folder_layer::FolderLayer;
-use crate::layers::layer_info::{Layer, LayerData, LayerDataType, LayerDataTypeDiscriminant};
+use crate::layers::folder_layer::{push_tagged_layer_svg, FolderLayer};
+use crate::layers::folder_style::FolderStyle;
+use crate::layers::layer_info::{Layer, LayerData, LayerDataType, LayerDataTypeDiscriminant, RenderChange};
 use crate::layers::layer_layer::{CachedOutputData, LayerLayer};
 use crate::layers::shape_layer::ShapeLayer;
 use crate::layers::style::RenderData;
+use crate::layers::svg_defs::deduplicate_defs;
+use crate::layers::text_style::TextStyle;
+use crate::layers::SvgElementIds;
 use crate::{DocumentError, DocumentResponse, Operation};
 
 use glam::{DAffine2, DVec2};
@@ -30,6 +35,16 @@ pub struct Document {
 	pub document_network: graph_craft::document::NodeNetwork,
 	#[serde(default)]
 	pub commit_hash: String,
+	/// The named text styles which text layers can be linked to.
+	#[serde(default)]
+	pub text_styles: Vec<TextStyle>,
+	/// The deepest that folders may be nested within each other by adding or moving layers, which can't be more than [MAX_FOLDER_DEPTH].
+	#[serde(skip, default = "default_max_folder_depth")]
+	pub max_folder_depth: usize,
+}
+
+fn default_max_folder_depth() -> usize {
+	MAX_FOLDER_DEPTH
 }
 
 impl PartialEq for Document {
@@ -57,6 +72,8 @@ impl Default for Document {
 				network
 			},
 			commit_hash: String::new(),
+			text_styles: Vec::new(),
+			max_folder_depth: MAX_FOLDER_DEPTH,
 		}
 	}
 }
@@ -65,9 +82,7 @@ impl Document {
 	/// Wrapper around render, that returns the whole document as a Response.
 	pub fn render_root(&mut self, render_data: &RenderData) -> String {
 		// Render and append to the defs section
-		let mut svg_defs = String::from("<defs>");
-		self.root.render(&mut vec![], &mut svg_defs, render_data);
-		svg_defs.push_str("</defs>");
+		let mut svg_defs = render_with_defs(&mut self.root, render_data);
 
 		// Append the cached rendered SVG
 		svg_defs.push_str(&self.root.cache);
@@ -75,6 +90,37 @@ impl Document {
 		svg_defs
 	}
 
+	/// Renders the root like [render_root](Document::render_root), but returns the SVG defs and only the layers whose SVG changed since the previous render.
+	/// Each change is the path of a layer and its SVG as tagged within its folder, so it can replace that layer on the canvas.
+	/// An empty path means the root itself changed, holding the SVG from [canvas_root_svg](Document::canvas_root_svg).
+	pub fn render_root_changes(&mut self, render_data: &RenderData) -> (String, Vec<(Vec<LayerId>, String)>) {
+		let svg_defs = render_with_defs(&mut self.root, render_data);
+
+		let mut changes = Vec::new();
+		match self.root.render_change {
+			RenderChange::Whole => changes.push((Vec::new(), self.canvas_root_svg())),
+			RenderChange::Children => collect_render_changes(&self.root, &mut Vec::new(), &mut changes),
+			RenderChange::Unchanged => {}
+		}
+
+		(svg_defs, changes)
+	}
+
+	/// The cached SVG of the root with its group tagged by a `data-layer-root` attribute, under which the layers are found on the canvas.
+	pub fn canvas_root_svg(&self) -> String {
+		match self.root.cache.strip_prefix("<g") {
+			Some(rest) => format!("<g data-layer-root{rest}"),
+			None => self.root.cache.clone(),
+		}
+	}
+
+	/// The `id` given to each named layer by an export tagging the layers with their names, along with the layer's path, in document order.
+	pub fn exported_element_ids(&self) -> Vec<(Vec<LayerId>, String)> {
+		let mut element_ids = Vec::new();
+		collect_element_ids(&self.root, &mut Vec::new(), &SvgElementIds::default(), &mut element_ids);
+		element_ids
+	}
+
 	/// Renders everything below the given layer contained within its parent folder.
 	pub fn render_layers_below(&mut self, below_layer_path: &[LayerId], render_data: &RenderData) -> Option<String> {
 		// Split the path into the layer ID and its parent folder
@@ -89,9 +135,7 @@ impl Document {
 			folder.layers.truncate(count_of_layers_below);
 
 			// Render and append to the defs section
-			let mut svg_defs = String::from("<defs>");
-			temp_subset_folder.render(&mut vec![], &mut svg_defs, render_data);
-			svg_defs.push_str("</defs>");
+			let mut svg_defs = render_with_defs(&mut temp_subset_folder, render_data);
 
 			// Append the cached rendered SVG
 			svg_defs.push_str(&temp_subset_folder.cache);
@@ -108,9 +152,7 @@ impl Document {
 		let mut temp_clone = self.layer_mut(layer_path).ok()?.clone();
 
 		// Render and append to the defs section
-		let mut svg_defs = String::from("<defs>");
-		temp_clone.render(&mut vec![], &mut svg_defs, render_data);
-		svg_defs.push_str("</defs>");
+		let mut svg_defs = render_with_defs(&mut temp_clone, render_data);
 
 		// Append the cached rendered SVG
 		svg_defs.push_str(&temp_clone.cache);
@@ -118,6 +160,90 @@ impl Document {
 		Some(svg_defs)
 	}
 
+	/// Checks that the layer can be moved into the folder: that it isn't a folder being moved into itself or a folder within it, and that the
+	/// folders in it wouldn't end up nested deeper than [max_folder_depth](Document::max_folder_depth).
+	pub fn check_move(&self, layer: &[LayerId], folder_path: &[LayerId]) -> Result<(), DocumentError> {
+		if folder_path.starts_with(layer) {
+			return Err(DocumentError::MoveIntoItself);
+		}
+		self.check_nesting(self.layer(layer)?, folder_path)
+	}
+
+	/// Checks that adding the layer to the folder wouldn't nest the folders in it deeper than [max_folder_depth](Document::max_folder_depth).
+	pub fn check_nesting(&self, layer: &Layer, folder_path: &[LayerId]) -> Result<(), DocumentError> {
+		/// How many folders deep the layer reaches, counting itself, so a layer that isn't a folder reaches none.
+		fn folder_depth(layer: &Layer) -> usize {
+			match &layer.data {
+				LayerDataType::Folder(folder) => 1 + folder.layers().iter().map(folder_depth).max().unwrap_or_default(),
+				_ => 0,
+			}
+		}
+
+		let max_depth = self.max_folder_depth.min(MAX_FOLDER_DEPTH);
+		if folder_path.len() + folder_depth(layer) > max_depth {
+			return Err(DocumentError::NestingTooDeep(max_depth));
+		}
+		Ok(())
+	}
+
+	/// Checks that a document read from a file is consistent enough to be edited without panicking: every folder lists an ID for each of
+	/// its layers without repeating one, folders aren't nested more than [MAX_FOLDER_DEPTH] deep, and every transform is finite.
+	pub fn validate(&self) -> Result<(), DocumentError> {
+		fn validate_layer(layer: &Layer, depth: usize) -> Result<(), DocumentError> {
+			if !layer.transform.is_finite() {
+				return Err(DocumentError::InvalidFile("A layer has a transform that isn't a finite number".to_string()));
+			}
+			let LayerDataType::Folder(folder) = &layer.data else { return Ok(()) };
+
+			if depth > MAX_FOLDER_DEPTH {
+				return Err(DocumentError::InvalidFile(format!("Folders are nested more than {MAX_FOLDER_DEPTH} levels deep")));
+			}
+			if folder.layer_ids.len() != folder.layers.len() {
+				return Err(DocumentError::InvalidFile("A folder doesn't list an ID for each of its layers".to_string()));
+			}
+			let mut ids = folder.layer_ids.clone();
+			ids.sort_unstable();
+			if ids.windows(2).any(|pair| pair[0] == pair[1]) {
+				return Err(DocumentError::InvalidFile("A folder has two layers with the same ID".to_string()));
+			}
+
+			folder.layers.iter().try_for_each(|layer| validate_layer(layer, depth + 1))
+		}
+
+		validate_layer(&self.root, 0)
+	}
+
+	/// The translations, in the space of their folder, which move the layers of each folder with an auto layout to where it places them.
+	/// Layers already in place are left out, so there's nothing to do once the layouts are up to date.
+	pub fn auto_layout_changes(&self, render_data: &RenderData) -> Vec<(Vec<LayerId>, DVec2)> {
+		let mut changes = Vec::new();
+		collect_auto_layout_changes(&self.root, &mut Vec::new(), &mut changes, render_data);
+		changes
+	}
+
+	/// The fill and stroke that the layer takes when it inherits its style, each from the nearest folder above it with a default for it.
+	pub fn inherited_style(&self, path: &[LayerId]) -> FolderStyle {
+		let mut style = FolderStyle::default();
+		for depth in (0..path.len()).rev() {
+			let Ok(folder) = self.folder(&path[..depth]) else { continue };
+			style.fill = style.fill.or_else(|| folder.default_style.fill.clone());
+			style.stroke = style.stroke.or_else(|| folder.default_style.stroke.clone());
+		}
+		style
+	}
+
+	/// The layers which inherit their style at any depth within the folder, or the layer itself if it's not a folder and inherits its style.
+	/// These are restyled when the default style of the folder changes, or when the folder or layer is placed somewhere else.
+	pub fn layers_inheriting_style(&self, path: &[LayerId]) -> Vec<Vec<LayerId>> {
+		let mut layers = Vec::new();
+		if let Ok(folder) = self.folder(path) {
+			collect_layers_inheriting_style(folder, &mut path.to_vec(), &mut layers);
+		} else if self.layer(path).map_or(false, |layer| layer.inherit_style) {
+			layers.push(path.to_vec());
+		}
+		layers
+	}
+
 	pub fn current_state_identifier(&self) -> u64 {
 		self.state_identifier.finish()
 	}
@@ -628,6 +754,7 @@ impl Document {
 				duplicating,
 			} => {
 				let (folder_path, layer_id) = split_path(&destination_path)?;
+				self.check_nesting(&layer, if duplicating { &destination_path } else { folder_path })?;
 				let mut responses = vec![DocumentChanged];
 
 				// If we are duplicating, use the parent layer path as the folder we insert to
@@ -798,6 +925,8 @@ impl Document {
 				Some(vec![LayerChanged { path }])
 			}
 			Operation::CreateFolder { path, insert_index } => {
+				let (folder_path, _) = split_path(&path)?;
+				self.check_nesting(&Layer::from(FolderLayer::default()), folder_path)?;
 				self.set_layer(&path, Layer::new(LayerDataType::Folder(FolderLayer::default()), DAffine2::IDENTITY.to_cols_array()), insert_index)?;
 				self.mark_as_dirty(&path)?;
 
@@ -925,6 +1054,36 @@ impl Document {
 				layer.visible = visible;
 				Some([vec![DocumentChanged], update_thumbnails_upstream(&path)].concat())
 			}
+			Operation::SetLayerExportVisibility { path, export_visible } => {
+				let layer = self.layer_mut(&path)?;
+				layer.export_visible = export_visible;
+				Some(vec![LayerChanged { path }])
+			}
+			Operation::SetLayerClickThrough { path, click_through } => {
+				let layer = self.layer_mut(&path)?;
+				layer.click_through = click_through;
+				Some(vec![LayerChanged { path }])
+			}
+			Operation::SetLayerInheritStyle { path, inherit_style } => {
+				let layer = self.layer_mut(&path)?;
+				layer.inherit_style = inherit_style;
+				Some(vec![LayerChanged { path }])
+			}
+			Operation::SetLayerConstraints { path, constraints } => {
+				let layer = self.layer_mut(&path)?;
+				layer.constraints = constraints;
+				Some(vec![LayerChanged { path }])
+			}
+			Operation::SetLayerRasterResolution { path, raster_resolution } => {
+				let layer = self.layer_mut(&path)?;
+				layer.raster_resolution = raster_resolution;
+				Some(vec![LayerChanged { path }])
+			}
+			Operation::SetLayerTextStyle { path, text_style } => {
+				let layer = self.layer_mut(&path)?;
+				layer.text_style = text_style;
+				Some(vec![LayerChanged { path }])
+			}
 			Operation::SetLayerName { path, name } => {
 				self.mark_as_dirty(&path)?;
 				let layer = self.layer_mut(&path)?;
@@ -932,6 +1091,10 @@ impl Document {
 
 				Some(vec![LayerChanged { path }])
 			}
+			Operation::SetLayerCustomMetadata { path, key, value } => {
+				self.layer_mut(&path)?.set_custom_metadata(key, value);
+				Some(vec![LayerChanged { path }])
+			}
 			Operation::SetLayerBlendMode { path, blend_mode } => {
 				self.mark_as_dirty(&path)?;
 				self.layer_mut(&path)?.blend_mode = blend_mode;
@@ -944,6 +1107,28 @@ impl Document {
 
 				Some([vec![DocumentChanged], update_thumbnails_upstream(&path)].concat())
 			}
+			Operation::SetFolderIsolation { path, isolated } => {
+				self.mark_as_dirty(&path)?;
+				self.layer_mut(&path)?.as_folder_mut()?.isolated = isolated;
+
+				Some([vec![DocumentChanged], update_thumbnails_upstream(&path)].concat())
+			}
+			Operation::SetFolderKnockout { path, knockout } => {
+				self.mark_as_dirty(&path)?;
+				self.layer_mut(&path)?.as_folder_mut()?.knockout = knockout;
+
+				Some([vec![DocumentChanged], update_thumbnails_upstream(&path)].concat())
+			}
+			Operation::SetFolderDefaultStyle { path, default_style } => {
+				self.layer_mut(&path)?.as_folder_mut()?.default_style = default_style;
+
+				Some(vec![LayerChanged { path }])
+			}
+			Operation::SetFolderAutoLayout { path, auto_layout } => {
+				self.layer_mut(&path)?.as_folder_mut()?.auto_layout = auto_layout;
+
+				Some(vec![DocumentChanged, LayerChanged { path }])
+			}
 			Operation::SetLayerStyle { path, style } => {
 				let layer = self.layer_mut(&path)?;
 				match &mut layer.data {
@@ -965,11 +1150,102 @@ impl Document {
 				self.mark_as_dirty(&path)?;
 				Some([vec![DocumentChanged], update_thumbnails_upstream(&path)].concat())
 			}
+			Operation::SetTextStyle { text_style } => {
+				match self.text_styles.iter_mut().find(|existing| existing.id == text_style.id) {
+					Some(existing) => *existing = text_style,
+					None => self.text_styles.push(text_style),
+				}
+				Some(Vec::new())
+			}
+			Operation::DeleteTextStyle { id } => {
+				self.text_styles.retain(|text_style| text_style.id != id);
+				Some(Vec::new())
+			}
 		};
 		Ok(responses)
 	}
 }
 
+/// Adds the layers within the folder whose SVG changed in the previous render, leaving out those whose whole folder is already added.
+/// Renders the layer, returning the `<defs>` section gathered from it with each repeated definition only written once.
+fn render_with_defs(layer: &mut Layer, render_data: &RenderData) -> String {
+	let mut svg_defs = String::new();
+	layer.render(&mut vec![], &mut svg_defs, render_data);
+	format!("<defs>{}</defs>", deduplicate_defs(&svg_defs))
+}
+
+fn collect_render_changes(folder: &Layer, path: &mut Vec<LayerId>, changes: &mut Vec<(Vec<LayerId>, String)>) {
+	let LayerDataType::Folder(folder) = &folder.data else { return };
+
+	for (&id, layer) in folder.layer_ids.iter().zip(folder.layers()) {
+		path.push(id);
+		match layer.render_change {
+			RenderChange::Whole => {
+				let mut svg = String::new();
+				push_tagged_layer_svg(&mut svg, &layer.cache, id);
+				changes.push((path.clone(), svg));
+			}
+			RenderChange::Children => collect_render_changes(layer, path, changes),
+			RenderChange::Unchanged => {}
+		}
+		path.pop();
+	}
+}
+
+/// Claims the `id` of each layer within the folder which is exported, in the same order as rendering claims them, so they match the exported SVG.
+fn collect_element_ids(folder: &Layer, path: &mut Vec<LayerId>, claimed: &SvgElementIds, element_ids: &mut Vec<(Vec<LayerId>, String)>) {
+	let LayerDataType::Folder(folder) = &folder.data else { return };
+
+	for (&id, layer) in folder.layer_ids.iter().zip(folder.layers()).filter(|(_, layer)| layer.is_visible(true)) {
+		path.push(id);
+		if let Some(name) = layer.name.as_deref() {
+			element_ids.push((path.clone(), claimed.claim(name)));
+		}
+		collect_element_ids(layer, path, claimed, element_ids);
+		path.pop();
+	}
+}
+
+fn collect_auto_layout_changes(folder: &Layer, path: &mut Vec<LayerId>, changes: &mut Vec<(Vec<LayerId>, DVec2)>, render_data: &RenderData) {
+	let LayerDataType::Folder(folder) = &folder.data else { return };
+
+	if let Some(auto_layout) = &folder.auto_layout {
+		let arranged = folder
+			.layer_ids
+			.iter()
+			.zip(folder.layers())
+			.filter(|(_, layer)| layer.visible)
+			.filter_map(|(&id, layer)| Some((id, layer.data.bounding_box(layer.transform, render_data)?)))
+			.collect::<Vec<_>>();
+		let bounds = arranged.iter().map(|(_, bounds)| *bounds).collect::<Vec<_>>();
+
+		for ((id, _), translation) in arranged.iter().zip(auto_layout.arrange(&bounds)) {
+			// Ignore the rounding error left over after a layer was moved into place
+			if translation.abs().max_element() > 1e-6 {
+				changes.push(([path.as_slice(), &[*id]].concat(), translation));
+			}
+		}
+	}
+
+	for (&id, layer) in folder.layer_ids.iter().zip(folder.layers()) {
+		path.push(id);
+		collect_auto_layout_changes(layer, path, changes, render_data);
+		path.pop();
+	}
+}
+
+fn collect_layers_inheriting_style(folder: &FolderLayer, path: &mut Vec<LayerId>, layers: &mut Vec<Vec<LayerId>>) {
+	for (&id, layer) in folder.layer_ids.iter().zip(folder.layers()) {
+		path.push(id);
+		match &layer.data {
+			LayerDataType::Folder(folder) => collect_layers_inheriting_style(folder, path, layers),
+			_ if layer.inherit_style => layers.push(path.clone()),
+			_ => {}
+		}
+		path.pop();
+	}
+}
+
 fn split_path(path: &[LayerId]) -> Result<(&[LayerId], LayerId), DocumentError> {
 	let (id, path) = path.split_last().ok_or(DocumentError::InvalidPath)?;
 	Ok((path, *id))
@@ -990,3 +1266,222 @@ pub fn pick_layer_safe_imaginate_resolution(layer: &Layer, render_data: &RenderD
 
 	graphene_std::imaginate::pick_safe_imaginate_resolution(layer_bounds_size)
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::layers::style::ViewMode;
+	use crate::test_utils::add_rect;
+
+	use graphene_std::text::FontCache;
+
+	/// A document with folders nested along the given path, each inside the one before.
+	fn nested_folders(path: &[LayerId]) -> Document {
+		let mut document = Document::default();
+		for depth in 1..=path.len() {
+			document
+				.handle_operation(Operation::CreateFolder {
+					path: path[..depth].to_vec(),
+					insert_index: -1,
+				})
+				.unwrap();
+		}
+		document
+	}
+
+	#[test]
+	fn folders_cant_be_moved_into_themselves() {
+		let document = nested_folders(&[1, 2, 3]);
+
+		assert_eq!(document.check_move(&[1], &[1]), Err(DocumentError::MoveIntoItself));
+		assert_eq!(document.check_move(&[1], &[1, 2]), Err(DocumentError::MoveIntoItself));
+		assert_eq!(document.check_move(&[1, 2], &[1, 2, 3]), Err(DocumentError::MoveIntoItself));
+
+		assert_eq!(document.check_move(&[1, 2, 3], &[1]), Ok(()));
+		assert_eq!(document.check_move(&[1, 2], &[]), Ok(()));
+	}
+
+	#[test]
+	fn moves_cant_nest_folders_too_deep() {
+		let mut document = nested_folders(&[1, 2, 3]);
+		document.max_folder_depth = 4;
+		document.handle_operation(Operation::CreateFolder { path: vec![4], insert_index: -1 }).unwrap();
+		document.handle_operation(Operation::CreateFolder { path: vec![4, 5], insert_index: -1 }).unwrap();
+
+		// The folder being moved counts along with the folders within it
+		assert_eq!(document.check_move(&[1, 2], &[4, 5]), Ok(()));
+		assert_eq!(document.check_move(&[1], &[4, 5]), Err(DocumentError::NestingTooDeep(4)));
+	}
+
+	#[test]
+	fn created_folders_cant_be_nested_too_deep() {
+		let mut document = nested_folders(&[1, 2]);
+		document.max_folder_depth = 3;
+
+		assert!(document
+			.handle_operation(Operation::CreateFolder {
+				path: vec![1, 2, 3],
+				insert_index: -1
+			})
+			.is_ok());
+		let too_deep = document.handle_operation(Operation::CreateFolder {
+			path: vec![1, 2, 3, 4],
+			insert_index: -1,
+		});
+		assert_eq!(too_deep.err(), Some(DocumentError::NestingTooDeep(3)));
+		assert!(document.layer(&[1, 2, 3, 4]).is_err());
+
+		// The preference can't raise the limit past the deepest nesting documents are read with
+		document.max_folder_depth = usize::MAX;
+		let path = (1..=MAX_FOLDER_DEPTH as LayerId + 1).collect::<Vec<_>>();
+		let errors = (4..=path.len()).filter_map(|depth| {
+			document
+				.handle_operation(Operation::CreateFolder {
+					path: path[..depth].to_vec(),
+					insert_index: -1,
+				})
+				.err()
+		});
+		assert_eq!(errors.collect::<Vec<_>>(), [DocumentError::NestingTooDeep(MAX_FOLDER_DEPTH)]);
+	}
+
+	#[test]
+	fn inserted_layers_cant_nest_folders_too_deep() {
+		let mut document = nested_folders(&[1, 2, 3]);
+		document.max_folder_depth = 4;
+		let folders = Box::new(document.layer(&[1]).unwrap().clone());
+		let insert = |destination_path: Vec<LayerId>, duplicating: bool| Operation::InsertLayer {
+			layer: folders.clone(),
+			destination_path,
+			insert_index: -1,
+			duplicating,
+		};
+
+		assert!(document.handle_operation(insert(vec![1, 9], false)).is_ok());
+		assert_eq!(document.handle_operation(insert(vec![1, 2, 9], false)).err(), Some(DocumentError::NestingTooDeep(4)));
+
+		// When duplicating, the destination is the folder the layer is added to
+		assert!(document.handle_operation(insert(vec![1], true)).is_ok());
+		assert_eq!(document.handle_operation(insert(vec![1, 2], true)).err(), Some(DocumentError::NestingTooDeep(4)));
+	}
+
+	/// A folder holding two rectangles, followed by another rectangle outside of it, rendered once so the canvas holds all of it.
+	fn rendered_document(render_data: &RenderData) -> Document {
+		let mut document = Document::default();
+		document.handle_operation(Operation::CreateFolder { path: vec![1], insert_index: -1 }).unwrap();
+		add_rect(&mut document, vec![1, 10]);
+		add_rect(&mut document, vec![1, 11]);
+		add_rect(&mut document, vec![2]);
+
+		let (_, changes) = document.render_root_changes(render_data);
+		assert_eq!(changes, vec![(Vec::new(), document.canvas_root_svg())]);
+		assert!(changes[0].1.starts_with("<g data-layer-root"));
+		document
+	}
+
+	/// The paths of the layers replaced on the canvas by the next render, and the render change of each one.
+	fn render_changes(document: &mut Document, render_data: &RenderData) -> Vec<(Vec<LayerId>, RenderChange)> {
+		let (_, changes) = document.render_root_changes(render_data);
+		changes
+			.into_iter()
+			.map(|(path, svg)| {
+				let layer = document.layer(&path).unwrap();
+				if let Some(&id) = path.last() {
+					let mut tagged = String::new();
+					push_tagged_layer_svg(&mut tagged, &layer.cache, id);
+					assert_eq!(svg, tagged);
+				}
+				(path, layer.render_change)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn editing_a_nested_shape_only_rerenders_that_shape() {
+		let font_cache = FontCache::default();
+		let render_data = RenderData::new(&font_cache, ViewMode::Normal, None);
+		let mut document = rendered_document(&render_data);
+
+		// Nothing changed, so nothing is replaced
+		assert!(render_changes(&mut document, &render_data).is_empty());
+
+		let transform = DAffine2::from_translation(DVec2::new(10., 0.)).to_cols_array();
+		document.handle_operation(Operation::TransformLayer { path: vec![1, 10], transform }).unwrap();
+		assert_eq!(render_changes(&mut document, &render_data), vec![(vec![1, 10], RenderChange::Whole)]);
+		assert_eq!(document.root.render_change, RenderChange::Children);
+		assert_eq!(document.layer(&[1]).unwrap().render_change, RenderChange::Children);
+		assert_eq!(document.layer(&[1, 11]).unwrap().render_change, RenderChange::Unchanged);
+	}
+
+	#[test]
+	fn changing_a_folder_or_which_children_it_shows_rerenders_the_whole_folder() {
+		let font_cache = FontCache::default();
+		let render_data = RenderData::new(&font_cache, ViewMode::Normal, None);
+
+		// Hiding a child leaves the folder with different rendered children
+		let mut document = rendered_document(&render_data);
+		document.handle_operation(Operation::SetLayerVisibility { path: vec![1, 11], visible: false }).unwrap();
+		assert_eq!(render_changes(&mut document, &render_data), vec![(vec![1], RenderChange::Whole)]);
+		assert!(!document.layer(&[1]).unwrap().cache.contains(r#"data-layer="11""#));
+
+		// Showing it again brings it back, still replacing the whole folder
+		document.handle_operation(Operation::SetLayerVisibility { path: vec![1, 11], visible: true }).unwrap();
+		assert_eq!(render_changes(&mut document, &render_data), vec![(vec![1], RenderChange::Whole)]);
+
+		// Moving the folder changes its own attributes
+		let mut document = rendered_document(&render_data);
+		let transform = DAffine2::from_translation(DVec2::new(0., 10.)).to_cols_array();
+		document.handle_operation(Operation::TransformLayer { path: vec![1], transform }).unwrap();
+		assert_eq!(render_changes(&mut document, &render_data), vec![(vec![1], RenderChange::Whole)]);
+	}
+
+	#[test]
+	fn exported_layers_arent_tagged_for_the_canvas() {
+		let font_cache = FontCache::default();
+		let render_data = RenderData::new(&font_cache, ViewMode::Normal, None);
+		let mut document = rendered_document(&render_data);
+		assert!(document.root.cache.contains(r#"<g data-layer="10""#));
+
+		let mut exported = document.clone();
+		exported.root.clear_caches();
+		let svg = exported.render_root(&render_data.for_export());
+		assert!(svg.contains("<path"));
+		assert!(!svg.contains("data-layer"));
+	}
+
+	#[test]
+	fn exported_element_ids_match_the_exported_svg() {
+		let mut document = Document::default();
+		document.handle_operation(Operation::CreateFolder { path: vec![1], insert_index: -1 }).unwrap();
+		add_rect(&mut document, vec![1, 10]);
+		add_rect(&mut document, vec![1, 11]);
+		add_rect(&mut document, vec![2]);
+		add_rect(&mut document, vec![3]);
+		let rename = |path: Vec<LayerId>, name: &str| Operation::SetLayerName { path, name: name.to_string() };
+		document.handle_operation(rename(vec![1], "Buttons")).unwrap();
+		document.handle_operation(rename(vec![1, 10], "Button")).unwrap();
+		document.handle_operation(rename(vec![1, 11], "Button")).unwrap();
+		document.handle_operation(rename(vec![2], "Hidden")).unwrap();
+		document
+			.handle_operation(Operation::SetLayerExportVisibility {
+				path: vec![2],
+				export_visible: Some(false),
+			})
+			.unwrap();
+
+		// Layers left out of the export don't claim an ID, and unnamed layers aren't tagged
+		let element_ids = document.exported_element_ids();
+		assert_eq!(
+			element_ids,
+			vec![(vec![1], "Buttons".to_string()), (vec![1, 10], "Button".to_string()), (vec![1, 11], "Button-2".to_string())]
+		);
+
+		let font_cache = FontCache::default();
+		let claimed = SvgElementIds::default();
+		let svg = document.render_root(&RenderData::new(&font_cache, ViewMode::Normal, None).for_export().with_element_ids(&claimed));
+		for (_, id) in element_ids {
+			assert!(svg.contains(&format!(r#" id="{id}""#)));
+		}
+		assert!(!svg.contains("Hidden"));
+	}
+}
@@ -10,4 +10,8 @@ pub enum DocumentError {
 	NotShape,
 	NotLayer,
 	InvalidFile(String),
+	/// A folder can't be moved into itself or into a folder within it, which would make it contain itself.
+	MoveIntoItself,
+	/// Folders would be nested deeper than the document allows, which is the given depth.
+	NestingTooDeep(usize),
 }
@@ -65,6 +65,37 @@ impl Quad {
 	pub fn center(&self) -> DVec2 {
 		self.0.iter().sum::<DVec2>() / 4.
 	}
+
+	/// Grows the quad outward by the distance on every side, keeping each edge parallel to where it was.
+	pub fn inflate(&self, distance: f64) -> Self {
+		if distance <= 0. {
+			return *self;
+		}
+
+		let center = self.center();
+		// The unit normal of each edge, pointing away from the center whichever way round the vertices go
+		let normals: [DVec2; 4] = core::array::from_fn(|index| {
+			let (start, end) = (self.0[index], self.0[(index + 1) % 4]);
+			let normal = (end - start).perp().normalize_or_zero();
+			if normal.dot(start - center) < 0. {
+				-normal
+			} else {
+				normal
+			}
+		});
+
+		// Each vertex moves to where its two edges meet once they've been pushed out, which is along the sum of their normals
+		Self(core::array::from_fn(|index| {
+			let (before, after) = (normals[(index + 3) % 4], normals[index]);
+			let denominator = 1. + before.dot(after);
+			let offset = if denominator > f64::EPSILON {
+				(before + after) * distance / denominator
+			} else {
+				before * distance
+			};
+			self.0[index] + offset
+		}))
+	}
 }
 
 impl Mul<Quad> for DAffine2 {
@@ -901,6 +932,23 @@ fn line_curve_intersection() {
 	assert_eq!(intersections(&alpha, &beta).len(), 2);
 }
 
+// Tests that inflating a quad moves each edge out by the distance, including for a sheared quad whose corners aren't right angles
+#[test]
+fn quad_inflate() {
+	let square = Quad::from_box([DVec2::ZERO, DVec2::splat(10.)]);
+	assert_eq!(square.inflate(2.).bounding_box(), [DVec2::splat(-2.), DVec2::splat(12.)]);
+	assert_eq!(square.inflate(0.).bounding_box(), square.bounding_box());
+	assert_eq!(square.inflate(-1.).bounding_box(), square.bounding_box());
+
+	let sheared = DAffine2::from_cols_array(&[1., 0., 1., 1., 0., 0.]) * square;
+	let inflated = sheared.inflate(2.);
+	for (index, line) in inflated.lines().into_iter().enumerate() {
+		let (start, end) = (sheared.0[index], sheared.0[(index + 1) % 4]);
+		let distance = |point: Point| (DVec2::new(point.x, point.y) - start).perp_dot((end - start).normalize()).abs();
+		assert!((distance(line.p0) - 2.).abs() < 1e-9 && (distance(line.p1) - 2.).abs() < 1e-9);
+	}
+}
+
 /// Each of these tests have been visually, but not mathematically, verified.
 /// These tests are all ignored because each test looks for exact floating point comparisons, so isn't tolerant to small adjustments in the algorithm.
 mod tests {
@@ -0,0 +1,143 @@
+use glam::DVec2;
+use serde::{Deserialize, Serialize};
+
+/// Which way the layers of a folder with an [AutoLayout] follow one another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AutoLayoutDirection {
+	/// From left to right.
+	#[default]
+	Row,
+	/// From top to bottom.
+	Column,
+}
+
+/// Where the layers of a folder with an [AutoLayout] are placed across its direction, relative to the largest of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum AutoLayoutAlignment {
+	/// At the top of a row or the left of a column.
+	#[default]
+	Start,
+	Center,
+	/// At the bottom of a row or the right of a column.
+	End,
+}
+
+/// Arranges the layers of a folder one after another in a row or column, like a flexbox in CSS, so they don't need to be positioned by hand.
+/// The layers follow their stacking order, from the bottom layer to the top, and start from the origin of the folder offset by the padding.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct AutoLayout {
+	pub direction: AutoLayoutDirection,
+	/// The space between each layer and the next.
+	pub gap: f64,
+	/// The space between the origin of the folder and the first layer, on both axes.
+	pub padding: f64,
+	pub alignment: AutoLayoutAlignment,
+}
+
+impl Default for AutoLayout {
+	fn default() -> Self {
+		Self {
+			direction: AutoLayoutDirection::Row,
+			gap: 10.,
+			padding: 0.,
+			alignment: AutoLayoutAlignment::Start,
+		}
+	}
+}
+
+impl AutoLayout {
+	/// The translation moving each layer to its place in the layout, given the bounds of the layers in the space of the folder in layout order.
+	pub fn arrange(&self, bounds: &[[DVec2; 2]]) -> Vec<DVec2> {
+		let (along, across) = match self.direction {
+			AutoLayoutDirection::Row => (DVec2::X, DVec2::Y),
+			AutoLayoutDirection::Column => (DVec2::Y, DVec2::X),
+		};
+		let alignment = match self.alignment {
+			AutoLayoutAlignment::Start => 0.,
+			AutoLayoutAlignment::Center => 0.5,
+			AutoLayoutAlignment::End => 1.,
+		};
+		let cross_size = bounds.iter().map(|[min, max]| (*max - *min).dot(across)).fold(0., f64::max);
+
+		let mut position = self.padding;
+		bounds
+			.iter()
+			.map(|[min, max]| {
+				let size = *max - *min;
+				let cross_position = self.padding + (cross_size - size.dot(across)) * alignment;
+				let target = along * position + across * cross_position;
+				position += size.dot(along) + self.gap;
+				target - *min
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::document::Document;
+	use crate::layers::style::{RenderData, ViewMode};
+	use crate::operation::Operation;
+	use crate::test_utils::add_transformed_rect;
+
+	use graphene_std::text::FontCache;
+
+	use glam::DAffine2;
+
+	/// The bounds of a 20 by 10 layer at (50, 50) followed by a 10 by 30 layer at the origin.
+	const BOUNDS: [[DVec2; 2]; 2] = [[DVec2::new(50., 50.), DVec2::new(70., 60.)], [DVec2::ZERO, DVec2::new(10., 30.)]];
+
+	#[test]
+	fn row_places_layers_one_after_another() {
+		let layout = AutoLayout::default();
+		assert_eq!(layout.arrange(&BOUNDS), vec![DVec2::new(-50., -50.), DVec2::new(30., 0.)]);
+
+		let layout = AutoLayout {
+			alignment: AutoLayoutAlignment::End,
+			..AutoLayout::default()
+		};
+		assert_eq!(layout.arrange(&BOUNDS), vec![DVec2::new(-50., -30.), DVec2::new(30., 0.)]);
+	}
+
+	#[test]
+	fn column_places_layers_below_one_another_with_padding() {
+		let layout = AutoLayout {
+			direction: AutoLayoutDirection::Column,
+			gap: 2.,
+			padding: 5.,
+			alignment: AutoLayoutAlignment::Center,
+		};
+		assert_eq!(layout.arrange(&BOUNDS), vec![DVec2::new(-45., -45.), DVec2::new(10., 17.)]);
+		assert!(layout.arrange(&[]).is_empty());
+	}
+
+	#[test]
+	fn changes_move_layers_into_place_until_the_layout_is_up_to_date() {
+		let font_cache = FontCache::default();
+		let render_data = RenderData::new(&font_cache, ViewMode::Normal, None);
+
+		let mut document = Document::default();
+		document.handle_operation(Operation::CreateFolder { path: vec![1], insert_index: -1 }).unwrap();
+		add_transformed_rect(&mut document, vec![1, 10], DAffine2::from_scale_angle_translation(DVec2::new(20., 10.), 0., DVec2::new(50., 50.)));
+		add_transformed_rect(&mut document, vec![1, 11], DAffine2::from_scale_angle_translation(DVec2::new(10., 30.), 0., DVec2::new(30., 0.)));
+		add_transformed_rect(&mut document, vec![1, 12], DAffine2::from_scale_angle_translation(DVec2::ONE, 0., DVec2::new(200., 200.)));
+		document.handle_operation(Operation::SetLayerVisibility { path: vec![1, 12], visible: false }).unwrap();
+
+		// Folders without an auto layout are left as they are
+		assert!(document.auto_layout_changes(&render_data).is_empty());
+
+		let auto_layout = Some(AutoLayout::default());
+		document.handle_operation(Operation::SetFolderAutoLayout { path: vec![1], auto_layout }).unwrap();
+
+		// The hidden layer isn't part of the layout, and the layer already in place isn't moved
+		let changes = document.auto_layout_changes(&render_data);
+		assert_eq!(changes, vec![(vec![1, 10], DVec2::new(-50., -50.))]);
+
+		for (path, translation) in changes {
+			let transform = DAffine2::from_translation(translation).to_cols_array();
+			document.handle_operation(Operation::TransformLayer { path, transform }).unwrap();
+		}
+		assert!(document.auto_layout_changes(&render_data).is_empty());
+	}
+}
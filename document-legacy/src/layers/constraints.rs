@@ -0,0 +1,68 @@
+use glam::{DAffine2, DVec2};
+use serde::{Deserialize, Serialize};
+
+/// How a layer follows one axis of the artboard it's on when the artboard is resized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Constraint {
+	/// Keeps the distance to the left or top edge, without resizing.
+	#[default]
+	Start,
+	/// Keeps the distance to the right or bottom edge, without resizing.
+	End,
+	/// Keeps the distances to both edges, stretching with the artboard.
+	StartAndEnd,
+	/// Keeps the distance to the center, without resizing.
+	Center,
+	/// Keeps its position and size in proportion to the artboard.
+	Scale,
+}
+
+impl Constraint {
+	/// Where the span from `start` to `end` goes when its parent span changes from `old_parent` to `new_parent`.
+	fn resize(self, [start, end]: [f64; 2], old_parent: [f64; 2], new_parent: [f64; 2]) -> [f64; 2] {
+		let start_offset = new_parent[0] - old_parent[0];
+		let end_offset = new_parent[1] - old_parent[1];
+		match self {
+			Self::Start => [start + start_offset, end + start_offset],
+			Self::End => [start + end_offset, end + end_offset],
+			Self::StartAndEnd => [start + start_offset, end + end_offset],
+			Self::Center => {
+				let offset = (start_offset + end_offset) / 2.;
+				[start + offset, end + offset]
+			}
+			Self::Scale => {
+				let old_size = old_parent[1] - old_parent[0];
+				let scale = if old_size.abs() > f64::EPSILON { (new_parent[1] - new_parent[0]) / old_size } else { 1. };
+				[new_parent[0] + (start - old_parent[0]) * scale, new_parent[0] + (end - old_parent[0]) * scale]
+			}
+		}
+	}
+}
+
+/// How a layer is moved and resized along with the artboard it's on, so mockups adapt when the artboard is resized to try out other screen sizes.
+/// By default layers keep their place relative to the top left corner of the artboard, as if they weren't constrained.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LayerConstraints {
+	pub horizontal: Constraint,
+	pub vertical: Constraint,
+}
+
+impl LayerConstraints {
+	/// The transform, in the same space as the bounds, which moves and resizes a layer with the given bounds when its parent's bounds change.
+	pub fn resize(&self, bounds: [DVec2; 2], old_parent: [DVec2; 2], new_parent: [DVec2; 2]) -> DAffine2 {
+		let [x_start, x_end] = self
+			.horizontal
+			.resize([bounds[0].x, bounds[1].x], [old_parent[0].x, old_parent[1].x], [new_parent[0].x, new_parent[1].x]);
+		let [y_start, y_end] = self.vertical.resize([bounds[0].y, bounds[1].y], [old_parent[0].y, old_parent[1].y], [new_parent[0].y, new_parent[1].y]);
+		let (new_min, new_max) = (DVec2::new(x_start, y_start), DVec2::new(x_end, y_end));
+
+		let old_size = bounds[1] - bounds[0];
+		let new_size = new_max - new_min;
+		// Layers without any size along an axis, like a horizontal line, can only be moved along it
+		let scale = DVec2::new(
+			if old_size.x.abs() > f64::EPSILON { new_size.x / old_size.x } else { 1. },
+			if old_size.y.abs() > f64::EPSILON { new_size.y / old_size.y } else { 1. },
+		);
+		DAffine2::from_translation(new_min) * DAffine2::from_scale(scale) * DAffine2::from_translation(-bounds[0])
+	}
+}
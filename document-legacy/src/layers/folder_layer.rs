@@ -1,3 +1,5 @@
+use super::auto_layout::AutoLayout;
+use super::folder_style::FolderStyle;
 use super::layer_info::{Layer, LayerData, LayerDataType};
 use super::style::RenderData;
 use crate::intersection::Quad;
@@ -7,6 +9,7 @@ use graphene_core::uuid::generate_uuid;
 
 use glam::DVec2;
 use serde::{Deserialize, Serialize};
+use std::fmt::Write;
 
 /// A layer that encapsulates other layers, including potentially more folders.
 /// The contained layers are rendered in the same order they are
@@ -19,14 +22,38 @@ pub struct FolderLayer {
 	pub layer_ids: Vec<LayerId>,
 	/// The [Layer]s contained in the folder
 	pub layers: Vec<Layer>,
+	/// Whether the blend modes of the contained layers only mix with each other, rather than also with the artwork below the folder.
+	/// Folders with an opacity below 100% or a blend mode other than normal are always isolated.
+	#[serde(default)]
+	pub isolated: bool,
+	/// Whether each contained layer knocks out the layers below it in the folder where they overlap, instead of being blended with them,
+	/// so only the folder's backdrop shows through a translucent layer.
+	#[serde(default)]
+	pub knockout: bool,
+	/// When set, the visible layers in the folder are kept arranged in a row or column rather than positioned by hand.
+	#[serde(default)]
+	pub auto_layout: Option<AutoLayout>,
+	/// The fill and stroke taken by the layers within the folder which inherit their style.
+	#[serde(default)]
+	pub default_style: FolderStyle,
 }
 
+/// How far, as a portion of a layer's size, the area kept by its knockout mask extends past its bounds on every side, which leaves room for strokes.
+const KNOCKOUT_MASK_MARGIN: f64 = 0.5;
+
 impl LayerData for FolderLayer {
 	fn render(&mut self, svg: &mut String, svg_defs: &mut String, transforms: &mut Vec<glam::DAffine2>, render_data: &RenderData) -> bool {
+		if self.knockout {
+			return self.render_knockout(svg, svg_defs, transforms, render_data);
+		}
+
 		let mut any_child_requires_redraw = false;
-		for layer in &mut self.layers {
+		for (layer, &layer_id) in self.layers.iter_mut().zip(&self.layer_ids) {
 			let (svg_value, requires_redraw) = layer.render(transforms, svg_defs, render_data);
-			*svg += svg_value;
+			match render_data.exporting {
+				true => *svg += svg_value,
+				false => push_tagged_layer_svg(svg, svg_value, layer_id),
+			}
 			any_child_requires_redraw = any_child_requires_redraw || requires_redraw;
 		}
 		any_child_requires_redraw
@@ -48,7 +75,62 @@ impl LayerData for FolderLayer {
 	}
 }
 
+/// Appends the SVG of a layer, tagging its group with a `data-layer` attribute holding its ID so a single layer can be found and replaced on the canvas.
+pub fn push_tagged_layer_svg(svg: &mut String, layer_svg: &str, layer_id: LayerId) {
+	match layer_svg.strip_prefix("<g") {
+		Some(rest) => {
+			let _ = write!(svg, r#"<g data-layer="{layer_id}""#);
+			svg.push_str(rest);
+		}
+		None => svg.push_str(layer_svg),
+	}
+}
+
 impl FolderLayer {
+	/// Renders the contained layers so that each one hides the layers below it where they overlap. SVG has no knockout groups, so this is emulated
+	/// by masking every layer with the silhouettes of the layers above it, which carries over to exports and to the artwork rasterized below layers.
+	fn render_knockout(&mut self, svg: &mut String, svg_defs: &mut String, transforms: &mut Vec<glam::DAffine2>, render_data: &RenderData) -> bool {
+		let mut any_child_requires_redraw = false;
+		let mut rendered = Vec::with_capacity(self.layers.len());
+		for layer in self.layers.iter_mut() {
+			let (svg_value, requires_redraw) = layer.render(transforms, svg_defs, render_data);
+			rendered.push(svg_value.to_string());
+			any_child_requires_redraw = any_child_requires_redraw || requires_redraw;
+		}
+
+		// Anything with coverage, however translucent, is turned opaque black so it fully knocks out what is below it
+		let silhouette_id = generate_uuid();
+		let _ = write!(
+			svg_defs,
+			r#"<filter id="{silhouette_id}" color-interpolation-filters="sRGB"><feColorMatrix type="matrix" values="0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 255 0" /></filter>"#
+		);
+
+		for (index, (layer, &layer_id)) in self.layers.iter().zip(&self.layer_ids).enumerate() {
+			let above = rendered[index + 1..].concat();
+			let bounds = layer.data.bounding_box(layer.transform, render_data);
+			let layer_svg = match bounds {
+				Some([min, max]) if !above.is_empty() && !rendered[index].is_empty() => {
+					let margin = (max - min).max_element() * KNOCKOUT_MASK_MARGIN;
+					let (min, size) = (min - margin, max - min + 2. * margin);
+					let mask_id = generate_uuid();
+					let _ = write!(
+						svg_defs,
+						r##"<mask id="{mask_id}" maskUnits="userSpaceOnUse" x="{}" y="{}" width="{}" height="{}"><rect x="{}" y="{}" width="{}" height="{}" fill="white" /><g filter="url(#{silhouette_id})">{above}</g></mask>"##,
+						min.x, min.y, size.x, size.y, min.x, min.y, size.x, size.y,
+					);
+					format!(r##"<g mask="url(#{mask_id})">{}</g>"##, rendered[index])
+				}
+				_ => std::mem::take(&mut rendered[index]),
+			};
+			match render_data.exporting {
+				true => *svg += &layer_svg,
+				false => push_tagged_layer_svg(svg, &layer_svg, layer_id),
+			}
+		}
+
+		any_child_requires_redraw
+	}
+
 	/// When a insertion ID is provided, try to insert the layer with the given ID.
 	/// If that ID is already used, return `None`.
 	/// When no insertion ID is provided, search for the next free ID and insert it with that.
@@ -0,0 +1,83 @@
+use super::style::{Fill, Stroke};
+
+use serde::{Deserialize, Serialize};
+
+/// The fill and stroke that a folder gives to the layers within it which inherit their style, so grouped artwork can be restyled at once.
+/// A folder without a default for one of them leaves it to the folders above.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct FolderStyle {
+	pub fill: Option<Fill>,
+	pub stroke: Option<Stroke>,
+}
+
+impl FolderStyle {
+	pub fn is_empty(&self) -> bool {
+		self.fill.is_none() && self.stroke.is_none()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::document::{Document, LayerId};
+	use crate::operation::Operation;
+	use crate::test_utils::add_rect;
+
+	use graphene_core::raster::color::Color;
+
+	fn add_inheriting_rect(document: &mut Document, path: Vec<LayerId>, inherit_style: bool) {
+		add_rect(document, path.clone());
+		document.handle_operation(Operation::SetLayerInheritStyle { path, inherit_style }).unwrap();
+	}
+
+	fn set_default_style(document: &mut Document, path: Vec<u64>, fill: Option<Fill>, stroke: Option<Stroke>) {
+		let default_style = FolderStyle { fill, stroke };
+		document.handle_operation(Operation::SetFolderDefaultStyle { path, default_style }).unwrap();
+	}
+
+	/// A folder with a red fill and a black stroke, holding a folder overriding only the fill with blue.
+	fn nested_folders() -> Document {
+		let mut document = Document::default();
+		document.handle_operation(Operation::CreateFolder { path: vec![1], insert_index: -1 }).unwrap();
+		document.handle_operation(Operation::CreateFolder { path: vec![1, 2], insert_index: -1 }).unwrap();
+		set_default_style(&mut document, vec![1], Some(Fill::Solid(Color::RED)), Some(Stroke::new(Some(Color::BLACK), 2.)));
+		set_default_style(&mut document, vec![1, 2], Some(Fill::Solid(Color::BLUE)), None);
+		document
+	}
+
+	#[test]
+	fn each_part_of_the_style_comes_from_the_nearest_folder_setting_it() {
+		let mut document = nested_folders();
+		add_inheriting_rect(&mut document, vec![1, 10], true);
+		add_inheriting_rect(&mut document, vec![1, 2, 11], true);
+
+		let outer = document.inherited_style(&[1, 10]);
+		assert_eq!(outer.fill, Some(Fill::Solid(Color::RED)));
+		assert_eq!(outer.stroke, Some(Stroke::new(Some(Color::BLACK), 2.)));
+
+		// The inner folder only sets the fill, so the stroke still comes from the outer one
+		let inner = document.inherited_style(&[1, 2, 11]);
+		assert_eq!(inner.fill, Some(Fill::Solid(Color::BLUE)));
+		assert_eq!(inner.stroke, Some(Stroke::new(Some(Color::BLACK), 2.)));
+
+		// Outside of any folder with a default style there's nothing to inherit
+		add_inheriting_rect(&mut document, vec![12], true);
+		assert!(document.inherited_style(&[12]).is_empty());
+	}
+
+	#[test]
+	fn only_layers_marked_to_inherit_are_restyled() {
+		let mut document = nested_folders();
+		add_inheriting_rect(&mut document, vec![1, 10], true);
+		add_inheriting_rect(&mut document, vec![1, 11], false);
+		add_inheriting_rect(&mut document, vec![1, 2, 12], true);
+
+		assert_eq!(document.layers_inheriting_style(&[1]), vec![vec![1, 2, 12], vec![1, 10]]);
+		assert_eq!(document.layers_inheriting_style(&[1, 2]), vec![vec![1, 2, 12]]);
+
+		// A layer which isn't a folder is restyled itself if it inherits its style
+		assert_eq!(document.layers_inheriting_style(&[1, 10]), vec![vec![1, 10]]);
+		assert!(document.layers_inheriting_style(&[1, 11]).is_empty());
+		assert!(document.layers_inheriting_style(&[3]).is_empty());
+	}
+}
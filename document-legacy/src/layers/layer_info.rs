@@ -1,8 +1,11 @@
 use super::blend_mode::BlendMode;
+use super::constraints::LayerConstraints;
+use super::escape_attribute;
 use super::folder_layer::FolderLayer;
 use super::layer_layer::LayerLayer;
 use super::shape_layer::ShapeLayer;
 use super::style::{PathStyle, RenderData};
+use super::svg_defs::content_addressed_ids;
 use crate::intersection::Quad;
 use crate::DocumentError;
 use crate::LayerId;
@@ -13,6 +16,7 @@ use graphene_std::vector::subpath::Subpath;
 use core::fmt;
 use glam::{DAffine2, DMat2, DVec2};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::Write;
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
@@ -194,6 +198,18 @@ struct DAffine2Ref {
 	pub translation: DVec2,
 }
 
+/// How the [Cache](Layer::cache) of a layer changed in the most recent render, so the canvas can be updated one layer at a time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderChange {
+	/// The cache wasn't rerendered.
+	#[default]
+	Unchanged,
+	/// The whole cache was rerendered.
+	Whole,
+	/// The folder was rerendered but kept its attributes and the same rendered children, so only the changes within its children need updating.
+	Children,
+}
+
 /// Utility function for providing a default boolean value to serde.
 #[inline(always)]
 fn return_true() -> bool {
@@ -204,8 +220,33 @@ fn return_true() -> bool {
 pub struct Layer {
 	/// Whether the layer is currently visible or hidden.
 	pub visible: bool,
+	/// Whether the layer is included in exports, overriding [visible](Layer::visible) so guides can be kept out of exports or print marks out of the canvas.
+	/// When `None`, exports follow the canvas visibility.
+	#[serde(default)]
+	pub export_visible: Option<bool>,
+	/// Whether clicks and marquee selections pass through the layer to the layers below, while it is still shown and exported.
+	/// Useful for watermarks and background textures covering the whole canvas, which would otherwise keep getting selected.
+	#[serde(default)]
+	pub click_through: bool,
+	/// How the layer is moved and resized along with the artboard it's on when the artboard is resized.
+	#[serde(default)]
+	pub constraints: LayerConstraints,
+	/// How many pixels per document pixel the artwork below this layer is rasterized at before its raster effects are applied, such as a blur.
+	/// When `None`, the resolution set for the whole document is used.
+	#[serde(default)]
+	pub raster_resolution: Option<f64>,
+	/// The ID of the document's [TextStyle](super::text_style::TextStyle) that this text layer is linked to, if any.
+	#[serde(default)]
+	pub text_style: Option<u64>,
+	/// Whether the layer takes its fill and stroke from the default style of the nearest folders above it, see [FolderStyle](super::folder_style::FolderStyle).
+	#[serde(default)]
+	pub inherit_style: bool,
 	/// The user-given name of the layer.
 	pub name: Option<String>,
+	/// Data attached to the layer by whatever needs it, like hints for a game engine picked up by an exporter, kept by key.
+	/// The document doesn't look at these values itself, so new kinds of data can be attached without changing the document format.
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub custom_metadata: BTreeMap<String, serde_json::Value>,
 	/// The type of layer, such as folder or shape.
 	pub data: LayerDataType,
 	/// A transformation applied to the layer (translation, rotation, scaling, and shear).
@@ -229,6 +270,12 @@ pub struct Layer {
 	/// Whether or not the [Cache](Layer::cache) and [Thumbnail Cache](Layer::thumbnail_cache) need to be updated.
 	#[serde(skip, default = "return_true")]
 	pub cache_dirty: bool,
+	/// How the [Cache](Layer::cache) changed in the most recent render.
+	#[serde(skip)]
+	pub render_change: RenderChange,
+	/// The opening tag of the [Cache](Layer::cache) followed by the IDs of the rendered children, used to tell if only the children of a folder changed.
+	#[serde(skip)]
+	cache_shell: String,
 	/// The blend mode describing how this layer should composite with others underneath it.
 	pub blend_mode: BlendMode,
 	/// The opacity, in the range of 0 to 1.
@@ -239,7 +286,14 @@ impl Layer {
 	pub fn new(data: LayerDataType, transform: [f64; 6]) -> Self {
 		Self {
 			visible: true,
+			export_visible: None,
+			click_through: false,
+			constraints: LayerConstraints::default(),
+			raster_resolution: None,
+			text_style: None,
+			inherit_style: false,
 			name: None,
+			custom_metadata: BTreeMap::new(),
 			data,
 			transform: glam::DAffine2::from_cols_array(&transform),
 			preserve_aspect: true,
@@ -248,6 +302,8 @@ impl Layer {
 			thumbnail_cache: String::new(),
 			svg_defs_cache: String::new(),
 			cache_dirty: true,
+			render_change: RenderChange::Unchanged,
+			cache_shell: String::new(),
 			blend_mode: BlendMode::Normal,
 			opacity: 1.,
 		}
@@ -271,6 +327,23 @@ impl Layer {
 		Some(layer)
 	}
 
+	/// Gets the custom metadata attached to the layer under the key, if any.
+	pub fn custom_metadata(&self, key: &str) -> Option<&serde_json::Value> {
+		self.custom_metadata.get(key)
+	}
+
+	/// Attaches the custom metadata to the layer under the key, replacing what was there, or removes it when `None`.
+	pub fn set_custom_metadata(&mut self, key: String, value: Option<serde_json::Value>) {
+		match value {
+			Some(value) => {
+				self.custom_metadata.insert(key, value);
+			}
+			None => {
+				self.custom_metadata.remove(&key);
+			}
+		}
+	}
+
 	/// Iterate over the layers encapsulated by this layer.
 	/// If the [Layer type](Layer::data) is not a folder, the only item in the iterator will be the layer itself.
 	/// If the [Layer type](Layer::data) wraps a [Folder](LayerDataType::Folder), the iterator will recursively yield all the layers contained in the folder as well as potential sub-folders.
@@ -306,9 +379,34 @@ impl Layer {
 		LayerIter { stack: vec![self] }
 	}
 
+	/// Whether the layer is shown, either on the canvas or in exports.
+	pub fn is_visible(&self, exporting: bool) -> bool {
+		match exporting {
+			true => self.export_visible.unwrap_or(self.visible),
+			false => self.visible,
+		}
+	}
+
+	/// Frees the cached renders of the layer and its children, which are rebuilt the next time they are rendered.
+	pub fn clear_caches(&mut self) {
+		self.cache = String::new();
+		self.thumbnail_cache = String::new();
+		self.svg_defs_cache = String::new();
+		self.cache_shell = String::new();
+		self.cache_dirty = true;
+
+		if let LayerDataType::Folder(folder) = &mut self.data {
+			for layer in folder.layers_mut() {
+				layer.clear_caches();
+			}
+		}
+	}
+
 	/// Renders the layer, returning the result and if a redraw is required
 	pub fn render(&mut self, transforms: &mut Vec<DAffine2>, svg_defs: &mut String, render_data: &RenderData) -> (&str, bool) {
-		if !self.visible {
+		self.render_change = RenderChange::Unchanged;
+
+		if !self.is_visible(render_data.exporting) {
 			return ("", false);
 		}
 
@@ -322,6 +420,7 @@ impl Layer {
 				if !is_overlapping {
 					transforms.pop();
 					self.cache.clear();
+					self.cache_shell.clear();
 					self.cache_dirty = true;
 					return ("", true);
 				}
@@ -333,20 +432,49 @@ impl Layer {
 		if self.cache_dirty {
 			self.thumbnail_cache.clear();
 			self.svg_defs_cache.clear();
+			// Claim the ID before rendering the children so names are deduplicated in document order
+			let element_id = render_data
+				.element_ids
+				.zip(self.name.as_deref())
+				.map(|(element_ids, name)| (element_ids.claim(name), escape_attribute(name)));
 			requires_redraw = self.data.render(&mut self.thumbnail_cache, &mut self.svg_defs_cache, transforms, render_data);
+			content_addressed_ids(&mut self.svg_defs_cache, &mut self.thumbnail_cache);
 
 			self.cache.clear();
 			let _ = writeln!(self.cache, r#"<g transform="matrix("#);
 			self.transform.to_cols_array().iter().enumerate().for_each(|(i, f)| {
 				let _ = self.cache.write_str(&(f.to_string() + if i == 5 { "" } else { "," }));
 			});
-			let _ = write!(
-				self.cache,
-				r#")" style="mix-blend-mode: {}; opacity: {}">{}</g>"#,
-				self.blend_mode.to_svg_style_name(),
-				self.opacity,
-				self.thumbnail_cache.as_str()
-			);
+			let _ = self.cache.write_str(")\"");
+			if let Some((id, name)) = element_id {
+				let _ = write!(self.cache, r#" id="{id}" data-name="{name}""#);
+			}
+			let isolation = match &self.data {
+				LayerDataType::Folder(folder) if folder.isolated => "; isolation: isolate",
+				_ => "",
+			};
+			let _ = write!(self.cache, r#" style="mix-blend-mode: {}; opacity: {}{isolation}">"#, self.blend_mode.to_svg_style_name(), self.opacity);
+
+			// A folder that kept its attributes and rendered children only needs the changes within its children to be updated on the canvas
+			self.render_change = RenderChange::Whole;
+			if let LayerDataType::Folder(folder) = &self.data {
+				let mut shell = self.cache.clone();
+				let rendered_children = folder
+					.layer_ids
+					.iter()
+					.zip(folder.layers())
+					.filter(|(_, layer)| layer.is_visible(render_data.exporting) && !layer.cache.is_empty());
+				for (id, _) in rendered_children {
+					let _ = write!(shell, "{id},");
+				}
+				if shell == self.cache_shell {
+					self.render_change = RenderChange::Children;
+				}
+				self.cache_shell = shell;
+			}
+
+			self.cache.push_str(&self.thumbnail_cache);
+			self.cache.push_str("</g>");
 
 			self.cache_dirty = false;
 		}
@@ -363,11 +491,18 @@ impl Layer {
 	}
 
 	pub fn intersects_quad(&self, quad: Quad, path: &mut Vec<LayerId>, intersections: &mut Vec<Vec<LayerId>>, render_data: &RenderData) {
-		if !self.visible {
+		if !self.visible || self.click_through {
 			return;
 		}
 
-		let transformed_quad = self.transform.inverse() * quad;
+		// The stroke is drawn at its weight outside of the layer's transform, so the quad is grown by half of it before being brought into the layer's space
+		let stroke_reach = self
+			.style()
+			.ok()
+			.and_then(PathStyle::stroke)
+			.filter(|stroke| stroke.color.is_some())
+			.map_or(0., |stroke| stroke.weight / 2.);
+		let transformed_quad = self.transform.inverse() * quad.inflate(stroke_reach);
 		self.data.intersects_quad(transformed_quad, path, intersections, render_data)
 	}
 
@@ -510,7 +645,14 @@ impl Clone for Layer {
 	fn clone(&self) -> Self {
 		Self {
 			visible: self.visible,
+			export_visible: self.export_visible,
+			click_through: self.click_through,
+			constraints: self.constraints,
+			raster_resolution: self.raster_resolution,
+			text_style: self.text_style,
+			inherit_style: self.inherit_style,
 			name: self.name.clone(),
+			custom_metadata: self.custom_metadata.clone(),
 			data: self.data.clone(),
 			transform: self.transform,
 			preserve_aspect: self.preserve_aspect,
@@ -519,6 +661,8 @@ impl Clone for Layer {
 			thumbnail_cache: String::new(),
 			svg_defs_cache: String::new(),
 			cache_dirty: true,
+			render_change: RenderChange::Unchanged,
+			cache_shell: String::new(),
 			blend_mode: self.blend_mode,
 			opacity: self.opacity,
 		}
@@ -569,3 +713,97 @@ impl<'a> Iterator for LayerIter<'a> {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::document::Document;
+	use crate::layers::style::{Fill, Stroke, ViewMode};
+	use crate::operation::Operation;
+	use crate::test_utils::add_rect;
+
+	use graphene_core::raster::color::Color;
+	use graphene_std::text::FontCache;
+
+	use serde_json::{json, Value};
+
+	fn set_metadata(document: &mut Document, key: &str, value: Option<Value>) {
+		let key = key.to_string();
+		document.handle_operation(Operation::SetLayerCustomMetadata { path: vec![1], key, value }).unwrap();
+	}
+
+	#[test]
+	fn metadata_is_set_replaced_and_removed_by_key() {
+		let mut document = Document::default();
+		add_rect(&mut document, vec![1]);
+		set_metadata(&mut document, "collider", Some(json!("box")));
+		set_metadata(&mut document, "mass", Some(json!(2.5)));
+		set_metadata(&mut document, "collider", Some(json!({ "shape": "circle", "radius": 4 })));
+
+		let layer = document.layer(&[1]).unwrap();
+		assert_eq!(layer.custom_metadata("collider"), Some(&json!({ "shape": "circle", "radius": 4 })));
+		assert_eq!(layer.custom_metadata("mass"), Some(&json!(2.5)));
+		assert_eq!(layer.custom_metadata("missing"), None);
+
+		set_metadata(&mut document, "mass", None);
+		// Removing a key that was never set does nothing
+		set_metadata(&mut document, "missing", None);
+		let layer = document.layer(&[1]).unwrap();
+		assert_eq!(layer.custom_metadata("mass"), None);
+		assert_eq!(layer.custom_metadata.len(), 1);
+
+		// Layers which don't exist have nothing to attach the metadata to
+		let operation = Operation::SetLayerCustomMetadata {
+			path: vec![2],
+			key: "mass".to_string(),
+			value: Some(json!(1)),
+		};
+		assert!(document.handle_operation(operation).is_err());
+	}
+
+	#[test]
+	fn metadata_is_kept_when_saved_and_opened() {
+		let mut document = Document::default();
+		add_rect(&mut document, vec![1]);
+
+		// Layers without any metadata leave it out of the saved file
+		let layer = serde_json::to_value(document.layer(&[1]).unwrap()).unwrap();
+		assert!(layer.get("custom_metadata").is_none());
+
+		set_metadata(&mut document, "tags", Some(json!(["enemy", "boss"])));
+		let serialized = serde_json::to_string(&document).unwrap();
+		let opened: Document = serde_json::from_str(&serialized).unwrap();
+		assert_eq!(opened.layer(&[1]).unwrap().custom_metadata, document.layer(&[1]).unwrap().custom_metadata);
+		assert_eq!(opened.layer(&[1]).unwrap().custom_metadata("tags"), Some(&json!(["enemy", "boss"])));
+	}
+
+	/// Whether clicking the point, with a little tolerance around it, hits any layer of the document.
+	fn hits(document: &Document, point: DVec2) -> bool {
+		let font_cache = FontCache::default();
+		let render_data = RenderData::new(&font_cache, ViewMode::Normal, None);
+		let quad = Quad::from_box([point - DVec2::splat(0.1), point + DVec2::splat(0.1)]);
+		!document.intersects_quad_root(quad, &render_data).is_empty()
+	}
+
+	#[test]
+	fn unfilled_shape_is_hit_only_along_its_stroke() {
+		// A 100 by 10 rectangle, made by stretching the unit square unevenly, with a stroke 4 wide
+		let mut document = Document::default();
+		let operation = Operation::AddRect {
+			path: vec![1],
+			insert_index: -1,
+			transform: DAffine2::from_scale(DVec2::new(100., 10.)).to_cols_array(),
+			style: PathStyle::new(Some(Stroke::new(Some(Color::BLACK), 4.)), Fill::None),
+		};
+		document.handle_operation(operation).unwrap();
+
+		// The stroke reaches 2 beyond each edge, however much the layer stretches the square
+		assert!(hits(&document, DVec2::new(50., 11.5)));
+		assert!(hits(&document, DVec2::new(101.5, 5.)));
+		assert!(!hits(&document, DVec2::new(50., 13.)));
+		assert!(!hits(&document, DVec2::new(103., 5.)));
+
+		// The inside isn't painted
+		assert!(!hits(&document, DVec2::new(50., 5.)));
+	}
+}
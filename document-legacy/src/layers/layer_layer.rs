@@ -1,9 +1,13 @@
 use super::layer_info::LayerData;
 use super::style::{RenderData, ViewMode};
+use super::{embedded_font_family, escape_attribute};
 use crate::intersection::{intersect_quad_bez_path, intersect_quad_subpath, Quad};
 use crate::LayerId;
 
 use glam::{DAffine2, DMat2, DVec2};
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::NodeInput;
+use graphene_core::text::{Font, KERNING_UNITS_PER_EM};
 use graphene_core::vector::VectorData;
 use graphene_core::SurfaceId;
 use kurbo::{Affine, BezPath, Shape as KurboShape};
@@ -58,14 +62,24 @@ impl LayerData for LayerLayer {
 				let layer_bounds = vector_data.bounding_box().unwrap_or_default();
 				let transformed_bounds = vector_data.bounding_box_with_transform(transform).unwrap_or_default();
 
-				let _ = write!(svg, "<path d=\"");
-				for subpath in &vector_data.subpaths {
-					let _ = subpath.subpath_to_svg(svg, transform);
-				}
-				svg.push('"');
+				let style = vector_data.style.render(render_data.view_mode, svg_defs, transform, layer_bounds, transformed_bounds);
+
+				if let Some(text) = self.live_text(transform, &style, render_data) {
+					svg.push_str(&text);
+				} else {
+					let _ = write!(svg, "<path d=\"");
+					for subpath in &vector_data.subpaths {
+						let _ = subpath.subpath_to_svg(svg, transform);
+					}
+					svg.push('"');
+
+					svg.push_str(&style);
+					let _ = write!(svg, "/>");
 
-				svg.push_str(&vector_data.style.render(render_data.view_mode, svg_defs, transform, layer_bounds, transformed_bounds));
-				let _ = write!(svg, "/>");
+					if let Some(outline) = vector_data.style.render_width_profile(render_data.view_mode, &vector_data.subpaths, transform) {
+						svg.push_str(&outline);
+					}
+				}
 			}
 			CachedOutputData::BlobURL(blob_url) => {
 				// Render the image if it exists
@@ -126,8 +140,8 @@ impl LayerData for LayerLayer {
 
 	fn intersects_quad(&self, quad: Quad, path: &mut Vec<LayerId>, intersections: &mut Vec<Vec<LayerId>>, _render_data: &RenderData) {
 		if let CachedOutputData::VectorPath(vector_data) = &self.cached_output_data {
-			let filled_style = vector_data.style.fill().is_some();
-			if vector_data.subpaths.iter().any(|subpath| intersect_quad_subpath(quad, subpath, filled_style || subpath.closed())) {
+			// Only the painted parts can be clicked, so an unfilled shape is hit along its stroke (which the layer grew the quad to reach) and not inside it
+			if vector_data.subpaths.iter().any(|subpath| intersect_quad_subpath(quad, subpath, vector_data.style.fill().is_some())) {
 				intersections.push(path.clone());
 			}
 		} else if intersect_quad_bez_path(quad, &self.bounds(), true) {
@@ -145,6 +159,72 @@ impl LayerLayer {
 		transforms.iter().skip(start).cloned().reduce(|a, b| a * b).unwrap_or(DAffine2::IDENTITY)
 	}
 
+	/// The text, font, and font size of the "Text" node generating this layer, if there is one.
+	pub fn text_node_inputs(&self) -> Option<(&str, &Font, f64)> {
+		let node = self.network.nodes.values().find(|node| node.name == "Text")?;
+		let input = |index: usize| match node.inputs.get(index) {
+			Some(NodeInput::Value { tagged_value, .. }) => Some(tagged_value),
+			_ => None,
+		};
+		match (input(1)?, input(2)?, input(3)?) {
+			(TaggedValue::String(text), TaggedValue::Font(font), TaggedValue::F32(font_size)) => Some((text, font, *font_size as f64)),
+			_ => None,
+		}
+	}
+
+	/// The manual kerning added after each character of the text, in thousandths of an em, if this is a text layer with any.
+	pub fn text_kerning(&self) -> &[f32] {
+		let node = self.network.nodes.values().find(|node| node.name == "Text");
+		match node.and_then(|node| node.inputs.get(4)) {
+			Some(NodeInput::Value {
+				tagged_value: TaggedValue::VecF32(kerning),
+				..
+			}) => kerning,
+			_ => &[],
+		}
+	}
+
+	/// Writes a text layer as an SVG `<text>` element set in its embedded font, laid out with the same line positions as its outlines.
+	/// Returns `None` if live text isn't wanted, this isn't a text layer, or its font isn't loaded.
+	fn live_text(&self, transform: DAffine2, style: &str, render_data: &RenderData) -> Option<String> {
+		if !render_data.live_text || render_data.view_mode == ViewMode::Outline {
+			return None;
+		}
+		let (text, font, font_size) = self.text_node_inputs()?;
+		let font = render_data.font_cache.resolve_font(font)?;
+		let face = rustybuzz::Face::from_slice(render_data.font_cache.get(font)?, 0)?;
+		let ascender = face.ascender() as f64 / face.height() as f64 * font_size;
+
+		let matrix = transform.to_cols_array().map(|entry| entry.to_string()).join(",");
+		let mut svg = format!(
+			r#"<text transform="matrix({matrix})" font-family="'{}'" font-size="{font_size}" xml:space="preserve"{style}>"#,
+			escape_attribute(&embedded_font_family(font))
+		);
+		let kerning = self.text_kerning();
+		let mut line_start = 0;
+		for (index, line) in text.split('\n').enumerate() {
+			// The kerning after a character shifts the one following it, which is what each character's `dx` offset does
+			let character_count = line.chars().count();
+			let shifts = (0..character_count)
+				.map(|character| match character.checked_sub(1).and_then(|previous| kerning.get(line_start + previous)) {
+					Some(&adjustment) => adjustment as f64 * font_size / KERNING_UNITS_PER_EM,
+					None => 0.,
+				})
+				.collect::<Vec<_>>();
+			let dx = if shifts.iter().any(|&shift| shift != 0.) {
+				format!(r#" dx="{}""#, shifts.iter().map(|shift| shift.to_string()).collect::<Vec<_>>().join(" "))
+			} else {
+				String::new()
+			};
+
+			let _ = write!(svg, r#"<tspan x="0" y="{}"{dx}>{}</tspan>"#, ascender + index as f64 * font_size, escape_attribute(line));
+			line_start += character_count + 1;
+		}
+		svg.push_str("</text>");
+
+		Some(svg)
+	}
+
 	fn bounds(&self) -> BezPath {
 		kurbo::Rect::from_origin_size(kurbo::Point::ZERO, kurbo::Size::new(1., 1.)).to_path(0.)
 	}
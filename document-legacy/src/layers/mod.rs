@@ -13,11 +13,17 @@
 //! When different layers overlap, they are blended together according to the [BlendMode](blend_mode::BlendMode)
 //! using the CSS [`mix-blend-mode`](https://developer.mozilla.org/en-US/docs/Web/CSS/mix-blend-mode) property and the layer opacity.
 
+/// Contains the [AutoLayout](auto_layout::AutoLayout) type, which arranges the layers of a folder in a row or column.
+pub mod auto_layout;
 pub mod base64_serde;
 /// Different ways of combining overlapping SVG elements.
 pub mod blend_mode;
+/// Contains the [LayerConstraints](constraints::LayerConstraints) type, which moves and resizes layers along with the artboard they're on.
+pub mod constraints;
 /// Contains the [FolderLayer](folder_layer::FolderLayer) type that encapsulates other layers, including more folders.
 pub mod folder_layer;
+/// Contains the [FolderStyle](folder_style::FolderStyle) type, the default fill and stroke a folder gives to the layers within it which inherit their style.
+pub mod folder_style;
 /// Contains the base [Layer](layer_info::Layer) type, an abstraction over the different types of layers.
 pub mod layer_info;
 /// Contains the [LayerLayer](nodegraph_layer::LayerLayer) type that contains a node graph.
@@ -25,9 +31,13 @@ pub mod layer_layer;
 // TODO: Remove shape layers after rewriting the overlay system
 /// Contains the [ShapeLayer](shape_layer::ShapeLayer) type, a generic SVG element defined using Bezier paths.
 pub mod shape_layer;
+/// Keeps the IDs of the definitions in the rendered `<defs>` unique, and drops repeated definitions.
+pub mod svg_defs;
+/// Contains the [TextStyle](text_style::TextStyle) type, a named font and size which text layers can be linked to.
+pub mod text_style;
 
 mod render_data;
-pub use render_data::RenderData;
+pub use render_data::{embedded_font_family, escape_attribute, RenderData, SvgElementIds};
 
 pub mod style {
 	pub use super::RenderData;
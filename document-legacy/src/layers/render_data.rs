@@ -1,7 +1,10 @@
 use super::style::ViewMode;
-use graphene_std::text::FontCache;
+use super::svg_defs::GENERATED_ID_PREFIX;
+use graphene_std::text::{Font, FontCache};
 
 use glam::DVec2;
+use std::cell::RefCell;
+use std::collections::HashSet;
 
 /// Contains metadata for rendering the document as an svg
 #[derive(Debug, Clone, Copy)]
@@ -9,6 +12,12 @@ pub struct RenderData<'a> {
 	pub font_cache: &'a FontCache,
 	pub view_mode: ViewMode,
 	pub culling_bounds: Option<[DVec2; 2]>,
+	/// When present, each named layer is tagged with an `id` and `data-name` attribute so the exported SVG can be targeted by CSS or JS.
+	pub element_ids: Option<&'a SvgElementIds>,
+	/// Whether the render is for an export, so layers are shown according to their export visibility rather than their canvas visibility.
+	pub exporting: bool,
+	/// Whether text layers are written as SVG `<text>` elements using an embedded font, instead of being converted to outlines.
+	pub live_text: bool,
 }
 
 impl<'a> RenderData<'a> {
@@ -17,6 +26,65 @@ impl<'a> RenderData<'a> {
 			font_cache,
 			view_mode,
 			culling_bounds,
+			element_ids: None,
+			exporting: false,
+			live_text: false,
 		}
 	}
+
+	pub fn for_export(mut self) -> Self {
+		self.exporting = true;
+		self
+	}
+
+	pub fn with_live_text(mut self, live_text: bool) -> Self {
+		self.live_text = live_text;
+		self
+	}
+
+	pub fn with_element_ids(mut self, element_ids: &'a SvgElementIds) -> Self {
+		self.element_ids = Some(element_ids);
+		self
+	}
+}
+
+/// Hands out SVG element IDs derived from layer names, keeping them unique within a single render.
+#[derive(Debug, Default)]
+pub struct SvgElementIds {
+	used: RefCell<HashSet<String>>,
+}
+
+impl SvgElementIds {
+	/// Converts the layer name into a valid SVG `id`, adding a numeric suffix if another layer already claimed it.
+	/// Names that look like the IDs given to definitions are prefixed so they can't collide with them.
+	pub fn claim(&self, name: &str) -> String {
+		let mut base: String = name.trim().chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' }).collect();
+		if base.is_empty() {
+			base.push_str("layer");
+		} else if !base.starts_with(|c: char| c.is_ascii_alphabetic()) || base.starts_with(GENERATED_ID_PREFIX) {
+			base.insert_str(0, "layer-");
+		}
+
+		let mut used = self.used.borrow_mut();
+		let mut id = base.clone();
+		let mut suffix = 2;
+		while used.contains(&id) {
+			id = format!("{base}-{suffix}");
+			suffix += 1;
+		}
+		used.insert(id.clone());
+
+		id
+	}
+}
+
+/// The family name given to a font when it is embedded in an exported SVG, which is unique for each style of the family.
+/// Quotes and backslashes are left out so the name can be quoted in CSS.
+pub fn embedded_font_family(font: &Font) -> String {
+	format!("{} {}", font.font_family, font.font_style).replace(['\'', '"', '\\'], "")
+}
+
+/// Escapes text so it can be placed inside a double-quoted XML attribute.
+pub fn escape_attribute(text: &str) -> String {
+	text.replace('&', "&amp;").replace('"', "&quot;").replace('<', "&lt;").replace('>', "&gt;")
 }
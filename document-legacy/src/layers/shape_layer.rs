@@ -68,8 +68,8 @@ impl LayerData for ShapeLayer {
 	}
 
 	fn intersects_quad(&self, quad: Quad, path: &mut Vec<LayerId>, intersections: &mut Vec<Vec<LayerId>>, _render_data: &RenderData) {
-		let filled = self.style.fill().is_some() || self.shape.manipulator_groups().last().filter(|manipulator_group| manipulator_group.is_close()).is_some();
-		if intersect_quad_bez_path(quad, &(&self.shape).into(), filled) {
+		// Only the painted parts can be clicked, so an unfilled shape is hit along its stroke (which the layer grew the quad to reach) and not inside it
+		if intersect_quad_bez_path(quad, &(&self.shape).into(), self.style.fill().is_some()) {
 			intersections.push(path.clone());
 		}
 	}
@@ -0,0 +1,127 @@
+//! Layers are rendered to SVG fragments which are concatenated into one document, while the gradients, clip paths, masks, and filters they
+//! define are gathered into its `<defs>`. Those definitions are given IDs as they're rendered, which are then referenced from elsewhere in
+//! the SVG. For the IDs to never collide, whether between layers, between the documents shown on the same page, or between exported files
+//! inlined into the same web page, each definition's ID is derived from its content. Identical definitions then share an ID, so repeats can
+//! be dropped, and differing ones can't be mistaken for each other.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// The start of every ID given to a definition, which layer names are kept from starting with so they can't collide.
+pub const GENERATED_ID_PREFIX: &str = "def-";
+
+/// Gives each definition in `defs` an ID derived from its content, updating its references within the definitions and the rendered `svg`.
+/// Definitions which already have such an ID are left alone, so it can be done again for each folder as its layers are rendered.
+///
+/// # Example
+/// ```
+/// # use graphite_document_legacy::layers::svg_defs::content_addressed_ids;
+/// let mut defs = r#"<linearGradient id="42"><stop offset="0" /></linearGradient>"#.to_string();
+/// let mut svg = r#"<path fill="url(#42)" />"#.to_string();
+/// content_addressed_ids(&mut defs, &mut svg);
+///
+/// assert!(defs.starts_with(r#"<linearGradient id="def-"#));
+/// assert!(svg.starts_with(r#"<path fill="url(#def-"#));
+/// ```
+pub fn content_addressed_ids(defs: &mut String, svg: &mut String) {
+	if !defs.contains(" id=\"") {
+		return;
+	}
+
+	let mut elements = top_level_elements(defs).into_iter().map(str::to_string).collect::<Vec<_>>();
+	for index in 0..elements.len() {
+		let Some(old_id) = element_id(&elements[index]).filter(|id| !id.starts_with(GENERATED_ID_PREFIX)).map(str::to_string) else {
+			continue;
+		};
+
+		let blanked = elements[index].replacen(&format!(r#" id="{old_id}""#), r#" id="""#, 1);
+		let mut hasher = DefaultHasher::new();
+		blanked.hash(&mut hasher);
+		let new_id = format!("{GENERATED_ID_PREFIX}{:016x}", hasher.finish());
+		elements[index] = blanked.replacen(r#" id="""#, &format!(r#" id="{new_id}""#), 1);
+
+		// References from later definitions are updated before those are hashed, so a definition's ID follows from what it references
+		for element in elements.iter_mut().skip(index + 1) {
+			rename_references(element, &old_id, &new_id);
+		}
+		rename_references(svg, &old_id, &new_id);
+	}
+
+	*defs = elements.concat();
+}
+
+/// Drops each definition that repeats an earlier one, which happens when layers define the same gradient, filter, or style.
+///
+/// # Example
+/// ```
+/// # use graphite_document_legacy::layers::svg_defs::deduplicate_defs;
+/// let style = "<style>@keyframes march { }</style>";
+/// let defs = format!(r#"{style}<filter id="def-1"></filter>{style}"#);
+///
+/// assert_eq!(deduplicate_defs(&defs), format!(r#"{style}<filter id="def-1"></filter>"#));
+/// ```
+pub fn deduplicate_defs(defs: &str) -> String {
+	let mut written = HashSet::new();
+	top_level_elements(defs).into_iter().filter(|element| written.insert(*element)).collect()
+}
+
+/// Points each reference to the definition with the old ID at the new ID instead.
+fn rename_references(svg: &mut String, old_id: &str, new_id: &str) {
+	for (old, new) in [
+		(format!("url(#{old_id})"), format!("url(#{new_id})")),
+		(format!(r##"href="#{old_id}""##), format!(r##"href="#{new_id}""##)),
+	] {
+		if svg.contains(&old) {
+			*svg = svg.replace(&old, &new);
+		}
+	}
+}
+
+/// The ID given by the opening tag of the element.
+fn element_id(element: &str) -> Option<&str> {
+	let opening_tag = &element[..element.find('>')?];
+	let start = opening_tag.find(r#" id=""#)? + r#" id=""#.len();
+	let length = opening_tag[start..].find('"')?;
+	Some(&opening_tag[start..start + length])
+}
+
+/// Splits concatenated SVG elements into each of the outermost elements, including everything they contain.
+/// Text between the elements, like whitespace, is kept with the element after it.
+fn top_level_elements(svg: &str) -> Vec<&str> {
+	let mut elements = Vec::new();
+	let (mut element_start, mut depth, mut position) = (0, 0_usize, 0);
+
+	while let Some(offset) = svg[position..].find('<') {
+		let tag_start = position + offset;
+		let rest = &svg[tag_start..];
+
+		// Comments and character data can contain anything, including what looks like tags
+		let (tag_end, depth_change) = if let Some((_, closing)) = [("<!--", "-->"), ("<![CDATA[", "]]>")].into_iter().find(|(opening, _)| rest.starts_with(opening)) {
+			(rest.find(closing).map_or(svg.len(), |end| tag_start + end + closing.len()), 0)
+		} else {
+			let tag_end = rest.find('>').map_or(svg.len(), |end| tag_start + end + 1);
+			let tag = &svg[tag_start..tag_end];
+			let change = if tag.starts_with("</") {
+				-1
+			} else if tag.ends_with("/>") {
+				0
+			} else {
+				1
+			};
+			(tag_end, change)
+		};
+
+		depth = depth.saturating_add_signed(depth_change);
+		position = tag_end;
+		if depth == 0 {
+			elements.push(&svg[element_start..tag_end]);
+			element_start = tag_end;
+		}
+	}
+
+	if element_start < svg.len() {
+		elements.push(&svg[element_start..]);
+	}
+	elements
+}
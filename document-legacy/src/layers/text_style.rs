@@ -0,0 +1,13 @@
+use graphene_core::text::Font;
+
+use serde::{Deserialize, Serialize};
+
+/// A named font and size stored in the document which text layers can be linked to, so that editing it restyles all of the linked text at once.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TextStyle {
+	/// Identifies the text style to the layers linked to it, which stays the same when it's renamed.
+	pub id: u64,
+	pub name: String,
+	pub font: Font,
+	pub font_size: f64,
+}
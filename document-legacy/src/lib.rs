@@ -1,20 +1,68 @@
+//! The document engine behind the Graphite editor: a tree of layers changed by operations and rendered to SVG.
+//! It doesn't depend on the editor, so it can be embedded wherever documents need to be read, changed, or rendered, like on a server.
+//!
+//! # Public API
+//! - A [Document] holds the tree of [Layer]s under its root folder.
+//! - Every change to a document is made by handling an [Operation] with [Document::handle_operation], which describes what changed
+//!   by returning [DocumentResponse]s.
+//! - A document is rendered to an SVG string by [Document::render_root], given the [RenderData] describing how to render it.
+//! - SVG files are read into layers by [svg_import::import_svg].
+//!
+//! The items re-exported here at the root of the crate are the entry points meant for use outside of the editor. The crate isn't published yet,
+//! since its node graph dependencies aren't either, so it makes no semantic versioning promise: any of its API, even at the root, may still
+//! change along with the editor.
+//!
+//! # Example
+//! ```
+//! use graphite_document_legacy::layers::style::{Fill, PathStyle, ViewMode};
+//! use graphite_document_legacy::{Document, Operation, RenderData};
+//! use graphene_core::raster::color::Color;
+//! use graphene_std::text::FontCache;
+//! use glam::DAffine2;
+//!
+//! let mut document = Document::default();
+//! document
+//! 	.handle_operation(Operation::AddRect {
+//! 		path: vec![1],
+//! 		insert_index: -1,
+//! 		transform: DAffine2::from_scale(glam::DVec2::new(100., 50.)).to_cols_array(),
+//! 		style: PathStyle::new(None, Fill::Solid(Color::RED)),
+//! 	})
+//! 	.unwrap();
+//!
+//! let font_cache = FontCache::default();
+//! let svg = document.render_root(&RenderData::new(&font_cache, ViewMode::Normal, None));
+//! assert!(svg.contains("<path"));
+//! ```
+
 // `macro_use` puts the log macros (`error!`, `warn!`, `debug!`, `info!` and `trace!`) in scope for the crate
 #[macro_use]
 extern crate log;
 
+/// Combining shapes by their union, difference, intersection, and so on.
 pub mod boolean_ops;
 /// Contains constant values used by this crate.
 pub mod consts;
+/// Compares two versions of a document.
+pub mod diff;
+/// Contains the [Document] type, the tree of layers which operations are handled by.
 pub mod document;
 /// Defines errors that can occur when using this crate.
 pub mod error;
 /// Utilities for computing intersections.
 pub mod intersection;
 pub mod layers;
+/// Contains the [Operation] type, each describing a change to a document.
 pub mod operation;
+/// Contains the [DocumentResponse] type, describing how a document changed when handling an operation.
 pub mod response;
+pub mod svg_import;
+#[cfg(test)]
+mod test_utils;
 
-pub use document::LayerId;
+pub use document::{Document, LayerId};
 pub use error::DocumentError;
+pub use layers::layer_info::Layer;
+pub use layers::RenderData;
 pub use operation::Operation;
 pub use response::DocumentResponse;
@@ -1,6 +1,10 @@
+use crate::layers::auto_layout::AutoLayout;
 use crate::layers::blend_mode::BlendMode;
+use crate::layers::constraints::LayerConstraints;
+use crate::layers::folder_style::FolderStyle;
 use crate::layers::layer_info::Layer;
 use crate::layers::style::{self, Stroke};
+use crate::layers::text_style::TextStyle;
 use crate::LayerId;
 
 use graphene_std::vector::subpath::Subpath;
@@ -102,10 +106,42 @@ pub enum Operation {
 		path: Vec<LayerId>,
 		visible: bool,
 	},
+	SetLayerExportVisibility {
+		path: Vec<LayerId>,
+		export_visible: Option<bool>,
+	},
+	SetLayerClickThrough {
+		path: Vec<LayerId>,
+		click_through: bool,
+	},
+	/// Makes the layer take its fill and stroke from the default style of the folders above it, or keep the style it has when `false`.
+	SetLayerInheritStyle {
+		path: Vec<LayerId>,
+		inherit_style: bool,
+	},
+	SetLayerConstraints {
+		path: Vec<LayerId>,
+		constraints: LayerConstraints,
+	},
 	SetLayerName {
 		path: Vec<LayerId>,
 		name: String,
 	},
+	/// Attaches custom metadata to the layer under the key, or removes the metadata under the key when `value` is `None`.
+	SetLayerCustomMetadata {
+		path: Vec<LayerId>,
+		key: String,
+		value: Option<serde_json::Value>,
+	},
+	SetLayerRasterResolution {
+		path: Vec<LayerId>,
+		raster_resolution: Option<f64>,
+	},
+	/// Links the text layer to one of the document's text styles, or unlinks it when `None`.
+	SetLayerTextStyle {
+		path: Vec<LayerId>,
+		text_style: Option<u64>,
+	},
 	SetLayerPreserveAspect {
 		layer_path: Vec<LayerId>,
 		preserve_aspect: bool,
@@ -118,6 +154,24 @@ pub enum Operation {
 		path: Vec<LayerId>,
 		opacity: f64,
 	},
+	SetFolderIsolation {
+		path: Vec<LayerId>,
+		isolated: bool,
+	},
+	SetFolderKnockout {
+		path: Vec<LayerId>,
+		knockout: bool,
+	},
+	/// Sets the fill and stroke given to the layers within the folder which inherit their style. The layers themselves are restyled separately.
+	SetFolderDefaultStyle {
+		path: Vec<LayerId>,
+		default_style: FolderStyle,
+	},
+	/// Keeps the layers of the folder arranged by the auto layout, or lets them be positioned by hand again when `None`.
+	SetFolderAutoLayout {
+		path: Vec<LayerId>,
+		auto_layout: Option<AutoLayout>,
+	},
 	SetLayerFill {
 		path: Vec<LayerId>,
 		fill: style::Fill,
@@ -164,6 +218,14 @@ pub enum Operation {
 		path: Vec<LayerId>,
 		style: style::PathStyle,
 	},
+	/// Adds the text style to the document, or replaces the one with the same ID.
+	SetTextStyle {
+		text_style: TextStyle,
+	},
+	/// Removes the text style from the document. Layers still linked to it should be unlinked first.
+	DeleteTextStyle {
+		id: u64,
+	},
 }
 
 impl Operation {
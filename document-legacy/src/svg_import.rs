@@ -0,0 +1,958 @@
+//! Reading SVG files into folder and shape layers, so existing artwork can be opened and edited.
+//!
+//! Groups become folders and paths, rectangles, circles, ellipses, lines, polylines, and polygons become shapes, each keeping its
+//! `transform` as the layer transform. Solid fills and strokes are read from presentation attributes and `style` declarations.
+//! Elements that can't be represented, like text or images, are skipped with a warning rather than failing the whole import.
+
+use crate::consts::{MAX_FILE_SIZE, MAX_FOLDER_DEPTH};
+use crate::layers::folder_layer::FolderLayer;
+use crate::layers::layer_info::Layer;
+use crate::layers::shape_layer::ShapeLayer;
+use crate::layers::style::{Fill, LineCap, LineJoin, PathStyle, Stroke};
+use crate::DocumentError;
+
+use graphene_core::raster::color::Color;
+use graphene_core::uuid::ManipulatorGroupId;
+use graphene_std::vector::subpath::Subpath as LegacySubpath;
+
+use bezier_rs::{ManipulatorGroup, Subpath};
+use glam::{DAffine2, DVec2};
+use std::f64::consts::{PI, TAU};
+
+/// The layers read from an SVG file, listed from the bottom of the stacking order to the top.
+#[derive(Debug, Default)]
+pub struct SvgImport {
+	pub layers: Vec<Layer>,
+	/// The size of the SVG canvas, if it is given by the `width` and `height` or the `viewBox` of the root element.
+	pub size: Option<DVec2>,
+	/// Descriptions of the content that couldn't be imported, each listed once.
+	pub warnings: Vec<String>,
+}
+
+/// Parses an SVG file into layers. Fails only if the file isn't well-formed XML with an `<svg>` root element.
+pub fn import_svg(svg: &str) -> Result<SvgImport, DocumentError> {
+	if svg.len() > MAX_FILE_SIZE {
+		return Err(DocumentError::InvalidFile(format!("The file is larger than the limit of {} MB", MAX_FILE_SIZE / 1024 / 1024)));
+	}
+
+	let root = parse_xml(svg).map_err(DocumentError::InvalidFile)?;
+	if root.name != "svg" {
+		return Err(DocumentError::InvalidFile(format!("Expected an <svg> root element but found <{}>", root.name)));
+	}
+
+	let mut import = SvgImport::default();
+	let (size, root_transform) = viewport(&root);
+	import.size = size;
+
+	let style = PresentationStyle::default().inherit(&root, &mut import.warnings);
+	for child in &root.children {
+		if let Some(mut layer) = import_element(child, &style, &mut import.warnings) {
+			layer.transform = root_transform * layer.transform;
+			import.layers.push(layer);
+		}
+	}
+
+	Ok(import)
+}
+
+/// The size of the root element's canvas and the transform from its user space (set by the `viewBox`) into that canvas.
+fn viewport(root: &Element) -> (Option<DVec2>, DAffine2) {
+	let view_box = root.attribute("viewBox").map(parse_numbers).filter(|numbers| numbers.len() == 4 && numbers[2] > 0. && numbers[3] > 0.);
+	let view_box = view_box.map(|numbers| (DVec2::new(numbers[0], numbers[1]), DVec2::new(numbers[2], numbers[3])));
+
+	let width = root.attribute("width").and_then(parse_length).filter(|&width| width > 0.);
+	let height = root.attribute("height").and_then(parse_length).filter(|&height| height > 0.);
+	let size = match (width, height, view_box) {
+		(Some(width), Some(height), _) => Some(DVec2::new(width, height)),
+		(Some(width), None, Some((_, view_size))) => Some(DVec2::new(width, width * view_size.y / view_size.x)),
+		(None, Some(height), Some((_, view_size))) => Some(DVec2::new(height * view_size.x / view_size.y, height)),
+		(_, _, Some((_, view_size))) => Some(view_size),
+		_ => None,
+	};
+
+	let transform = match (size, view_box) {
+		(Some(size), Some((view_origin, view_size))) => {
+			let scale = size / view_size;
+			// Only stretching and the default of centering the view box while keeping its aspect ratio are supported
+			if root.attribute("preserveAspectRatio").map_or(false, |value| value.trim() == "none") {
+				DAffine2::from_scale(scale) * DAffine2::from_translation(-view_origin)
+			} else {
+				let scale = scale.min_element();
+				let offset = (size - view_size * scale) / 2.;
+				DAffine2::from_translation(offset) * DAffine2::from_scale(DVec2::splat(scale)) * DAffine2::from_translation(-view_origin)
+			}
+		}
+		_ => DAffine2::IDENTITY,
+	};
+
+	(size, transform)
+}
+
+/// Converts an element and its descendants into a layer, or returns `None` if it isn't rendered or can't be represented.
+fn import_element(element: &Element, parent_style: &PresentationStyle, warnings: &mut Vec<String>) -> Option<Layer> {
+	let style = parent_style.inherit(element, warnings);
+
+	let mut layer = match element.name.as_str() {
+		"g" | "a" | "svg" => {
+			let mut folder = FolderLayer::default();
+			for child in &element.children {
+				if let Some(child) = import_element(child, &style, warnings) {
+					folder.add_layer(child, None, -1);
+				}
+			}
+			if folder.layers.is_empty() {
+				return None;
+			}
+
+			let mut layer = Layer::from(folder);
+			// Nested `<svg>` elements are positioned by their `x` and `y` attributes
+			if element.name == "svg" {
+				let position = DVec2::new(element.length("x"), element.length("y"));
+				layer.transform = DAffine2::from_translation(position);
+			}
+			layer
+		}
+		"path" | "rect" | "circle" | "ellipse" | "line" | "polyline" | "polygon" => {
+			let subpaths = element_subpaths(element, warnings);
+			if subpaths.is_empty() {
+				return None;
+			}
+
+			// Lines are never filled, as there is no area to fill
+			let filled = !matches!(element.name.as_str(), "line");
+			let path_style = style.path_style(filled);
+			Layer::from(ShapeLayer::new(LegacySubpath::from_bezier_rs(&subpaths), path_style))
+		}
+		// Elements that describe the document or hold definitions aren't rendered themselves
+		"title" | "desc" | "metadata" | "defs" | "symbol" | "clipPath" | "mask" | "linearGradient" | "radialGradient" | "pattern" | "marker" => return None,
+		"style" => {
+			warn_once(warnings, "CSS style sheets aren't supported, so styles from <style> elements were ignored".to_string());
+			return None;
+		}
+		name => {
+			warn_once(warnings, format!("<{name}> elements aren't supported and were skipped"));
+			return None;
+		}
+	};
+
+	layer.name = element.attribute("inkscape:label").or_else(|| element.attribute("id")).map(str::to_string);
+	layer.transform = layer.transform * element.attribute("transform").map_or(DAffine2::IDENTITY, parse_transform);
+	layer.opacity = element.style_value("opacity").and_then(parse_opacity).unwrap_or(1.) as f64;
+	layer.visible = element.style_value("display").map_or(true, |display| display != "none") && style.visible;
+
+	Some(layer)
+}
+
+/// The geometry of a basic shape or path element, in its own coordinate space.
+fn element_subpaths(element: &Element, warnings: &mut Vec<String>) -> Vec<Subpath<ManipulatorGroupId>> {
+	match element.name.as_str() {
+		"path" => {
+			let (subpaths, complete) = parse_path_data(element.attribute("d").unwrap_or_default());
+			if !complete {
+				warn_once(warnings, "Some path data couldn't be read, so those paths were imported only up to the error".to_string());
+			}
+			subpaths
+		}
+		"rect" => {
+			let position = DVec2::new(element.length("x"), element.length("y"));
+			let size = DVec2::new(element.length("width"), element.length("height"));
+			if size.x <= 0. || size.y <= 0. {
+				return Vec::new();
+			}
+
+			// A missing corner radius in one direction matches the other
+			let rx = element.attribute("rx").and_then(parse_length);
+			let ry = element.attribute("ry").and_then(parse_length);
+			let radius = DVec2::new(rx.or(ry).unwrap_or(0.), ry.or(rx).unwrap_or(0.)).max(DVec2::ZERO).min(size / 2.);
+			if radius.x <= 0. || radius.y <= 0. {
+				return vec![Subpath::new_rect(position, position + size)];
+			}
+
+			let [left, top] = position.to_array();
+			let [right, bottom] = (position + size).to_array();
+			let mut path = PathBuilder::default();
+			path.move_to(DVec2::new(left + radius.x, top));
+			path.line_to(DVec2::new(right - radius.x, top));
+			path.arc_to(radius, 0., false, true, DVec2::new(right, top + radius.y));
+			path.line_to(DVec2::new(right, bottom - radius.y));
+			path.arc_to(radius, 0., false, true, DVec2::new(right - radius.x, bottom));
+			path.line_to(DVec2::new(left + radius.x, bottom));
+			path.arc_to(radius, 0., false, true, DVec2::new(left, bottom - radius.y));
+			path.line_to(DVec2::new(left, top + radius.y));
+			path.arc_to(radius, 0., false, true, DVec2::new(left + radius.x, top));
+			path.close();
+			path.finish()
+		}
+		"circle" | "ellipse" => {
+			let center = DVec2::new(element.length("cx"), element.length("cy"));
+			let radius = match element.name.as_str() {
+				"circle" => DVec2::splat(element.length("r")),
+				_ => DVec2::new(element.length("rx"), element.length("ry")),
+			};
+			if radius.x <= 0. || radius.y <= 0. {
+				return Vec::new();
+			}
+
+			vec![Subpath::new_ellipse(center - radius, center + radius)]
+		}
+		"line" => {
+			let start = DVec2::new(element.length("x1"), element.length("y1"));
+			let end = DVec2::new(element.length("x2"), element.length("y2"));
+			vec![Subpath::new_line(start, end)]
+		}
+		"polyline" | "polygon" => {
+			let numbers = parse_numbers(element.attribute("points").unwrap_or_default());
+			let points = numbers.chunks_exact(2).map(|point| DVec2::new(point[0], point[1])).collect::<Vec<_>>();
+			if points.len() < 2 {
+				return Vec::new();
+			}
+
+			vec![Subpath::from_anchors(points, element.name == "polygon")]
+		}
+		_ => Vec::new(),
+	}
+}
+
+fn warn_once(warnings: &mut Vec<String>, warning: String) {
+	if !warnings.contains(&warning) {
+		warnings.push(warning);
+	}
+}
+
+// =====================
+// Presentation styles
+// =====================
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Paint {
+	None,
+	Color(Color),
+}
+
+/// The inherited presentation attributes that determine how a shape is filled and stroked.
+#[derive(Clone, Debug)]
+struct PresentationStyle {
+	fill: Paint,
+	fill_opacity: f32,
+	stroke: Paint,
+	stroke_opacity: f32,
+	stroke_width: f64,
+	line_cap: LineCap,
+	line_join: LineJoin,
+	miter_limit: f64,
+	dash_lengths: String,
+	dash_offset: f64,
+	visible: bool,
+}
+
+impl Default for PresentationStyle {
+	fn default() -> Self {
+		Self {
+			fill: Paint::Color(Color::BLACK),
+			fill_opacity: 1.,
+			stroke: Paint::None,
+			stroke_opacity: 1.,
+			stroke_width: 1.,
+			line_cap: LineCap::Butt,
+			line_join: LineJoin::Miter,
+			miter_limit: 4.,
+			dash_lengths: String::new(),
+			dash_offset: 0.,
+			visible: true,
+		}
+	}
+}
+
+impl PresentationStyle {
+	/// The style of an element, which inherits this style of its parent except for the properties the element sets itself.
+	fn inherit(&self, element: &Element, warnings: &mut Vec<String>) -> Self {
+		let mut style = self.clone();
+
+		if let Some(fill) = element.style_value("fill").and_then(|value| parse_paint(value, warnings)) {
+			style.fill = fill;
+		}
+		if let Some(stroke) = element.style_value("stroke").and_then(|value| parse_paint(value, warnings)) {
+			style.stroke = stroke;
+		}
+		if let Some(opacity) = element.style_value("fill-opacity").and_then(parse_opacity) {
+			style.fill_opacity = opacity;
+		}
+		if let Some(opacity) = element.style_value("stroke-opacity").and_then(parse_opacity) {
+			style.stroke_opacity = opacity;
+		}
+		if let Some(width) = element.style_value("stroke-width").and_then(parse_length) {
+			style.stroke_width = width.max(0.);
+		}
+		match element.style_value("stroke-linecap") {
+			Some("butt") => style.line_cap = LineCap::Butt,
+			Some("round") => style.line_cap = LineCap::Round,
+			Some("square") => style.line_cap = LineCap::Square,
+			_ => {}
+		}
+		match element.style_value("stroke-linejoin") {
+			Some("miter") => style.line_join = LineJoin::Miter,
+			Some("round") => style.line_join = LineJoin::Round,
+			Some("bevel") => style.line_join = LineJoin::Bevel,
+			_ => {}
+		}
+		if let Some(limit) = element.style_value("stroke-miterlimit").and_then(|value| value.parse::<f64>().ok()) {
+			style.miter_limit = limit.max(1.);
+		}
+		if let Some(dash_lengths) = element.style_value("stroke-dasharray") {
+			style.dash_lengths = if dash_lengths == "none" { String::new() } else { dash_lengths.to_string() };
+		}
+		if let Some(offset) = element.style_value("stroke-dashoffset").and_then(parse_length) {
+			style.dash_offset = offset;
+		}
+		match element.style_value("visibility") {
+			Some("hidden" | "collapse") => style.visible = false,
+			Some("visible") => style.visible = true,
+			_ => {}
+		}
+
+		style
+	}
+
+	fn path_style(&self, filled: bool) -> PathStyle {
+		let fill = match self.fill {
+			Paint::Color(color) if filled => Fill::solid(color.with_alpha(color.a() * self.fill_opacity)),
+			_ => Fill::None,
+		};
+
+		let stroke = match self.stroke {
+			Paint::Color(color) if self.stroke_width > 0. => {
+				let color = Some(color.with_alpha(color.a() * self.stroke_opacity));
+				let stroke = Stroke::new(color, self.stroke_width)
+					.with_line_cap(self.line_cap)
+					.with_line_join(self.line_join)
+					.with_line_join_miter_limit(self.miter_limit)
+					.with_dash_offset(self.dash_offset);
+				Some(stroke.clone().with_dash_lengths(&self.dash_lengths).unwrap_or(stroke))
+			}
+			_ => None,
+		};
+
+		PathStyle::new(stroke, fill)
+	}
+}
+
+/// Reads a `fill` or `stroke` value, returning `None` if it should be inherited instead.
+fn parse_paint(value: &str, warnings: &mut Vec<String>) -> Option<Paint> {
+	match value {
+		"inherit" => None,
+		"none" | "transparent" => Some(Paint::None),
+		_ if value.starts_with("url(") => {
+			warn_once(warnings, "Gradient and pattern paints aren't supported, so shapes using them were left unpainted".to_string());
+			Some(Paint::None)
+		}
+		_ => match parse_color(value) {
+			Some(color) => Some(Paint::Color(color)),
+			None => {
+				warn_once(warnings, format!("The color \"{value}\" isn't supported, so shapes using it were left unpainted"));
+				Some(Paint::None)
+			}
+		},
+	}
+}
+
+fn parse_color(value: &str) -> Option<Color> {
+	let value = value.trim();
+
+	if let Some(hex) = value.strip_prefix('#') {
+		let expanded: String = match hex.len() {
+			3 | 4 => hex.chars().flat_map(|digit| [digit, digit]).collect(),
+			_ => hex.to_string(),
+		};
+		return match expanded.len() {
+			6 => Color::from_rgb_str(&expanded),
+			8 => Color::from_rgba_str(&expanded),
+			_ => None,
+		};
+	}
+
+	let function = value.strip_prefix("rgba(").or_else(|| value.strip_prefix("rgb(")).and_then(|rest| rest.strip_suffix(')'));
+	if let Some(arguments) = function {
+		let channels = arguments.split([',', ' ', '/']).filter(|argument| !argument.is_empty()).collect::<Vec<_>>();
+		if !(3..=4).contains(&channels.len()) {
+			return None;
+		}
+
+		let channel = |argument: &str| match argument.strip_suffix('%') {
+			Some(percent) => percent.parse::<f64>().ok().map(|percent| percent / 100. * 255.),
+			None => argument.parse::<f64>().ok(),
+		};
+		let [red, green, blue] = [channel(channels[0])?, channel(channels[1])?, channel(channels[2])?].map(|channel| channel.round().clamp(0., 255.) as u8);
+		let alpha = channels.get(3).map_or(Some(1.), |&alpha| parse_opacity(alpha))?;
+		return Some(Color::from_rgba8_srgb(red, green, blue, (alpha * 255.).round() as u8));
+	}
+
+	let hex = match value.to_ascii_lowercase().as_str() {
+		"black" => "000000",
+		"silver" => "c0c0c0",
+		"gray" | "grey" => "808080",
+		"white" => "ffffff",
+		"maroon" => "800000",
+		"red" => "ff0000",
+		"purple" => "800080",
+		"fuchsia" | "magenta" => "ff00ff",
+		"green" => "008000",
+		"lime" => "00ff00",
+		"olive" => "808000",
+		"yellow" => "ffff00",
+		"navy" => "000080",
+		"blue" => "0000ff",
+		"teal" => "008080",
+		"aqua" | "cyan" => "00ffff",
+		"orange" => "ffa500",
+		"pink" => "ffc0cb",
+		"brown" => "a52a2a",
+		"gold" => "ffd700",
+		"indigo" => "4b0082",
+		"violet" => "ee82ee",
+		"darkgray" | "darkgrey" => "a9a9a9",
+		"lightgray" | "lightgrey" => "d3d3d3",
+		_ => return None,
+	};
+	Color::from_rgb_str(hex)
+}
+
+/// Reads an opacity given as a number or a percentage, clamped to the range of 0 to 1.
+fn parse_opacity(value: &str) -> Option<f32> {
+	let value = value.trim();
+	let opacity = match value.strip_suffix('%') {
+		Some(percent) => percent.parse::<f32>().ok()? / 100.,
+		None => value.parse::<f32>().ok()?,
+	};
+	Some(opacity.clamp(0., 1.))
+}
+
+/// Reads a length in pixels, converting from absolute units. Relative units like percentages can't be resolved and return `None`.
+fn parse_length(value: &str) -> Option<f64> {
+	let value = value.trim();
+	let units = [("px", 1.), ("pt", 4. / 3.), ("pc", 16.), ("mm", 96. / 25.4), ("cm", 96. / 2.54), ("in", 96.)];
+	let (number, scale) = units.iter().find_map(|&(unit, scale)| value.strip_suffix(unit).map(|number| (number, scale))).unwrap_or((value, 1.));
+	number.trim().parse::<f64>().ok().filter(|length| length.is_finite()).map(|length| length * scale)
+}
+
+/// Reads a list of numbers separated by whitespace or commas, stopping at the first part that isn't a number.
+fn parse_numbers(value: &str) -> Vec<f64> {
+	let mut parser = PathDataParser::new(value);
+	std::iter::from_fn(|| parser.number()).collect()
+}
+
+/// Reads a `transform` attribute's list of transform functions, which are applied from right to left.
+fn parse_transform(value: &str) -> DAffine2 {
+	let mut transform = DAffine2::IDENTITY;
+
+	for function in value.split(')') {
+		let Some((name, arguments)) = function.split_once('(') else { continue };
+		let name = name.trim_matches(|character: char| character.is_whitespace() || character == ',');
+		let arguments = parse_numbers(arguments);
+		let argument = |index: usize| arguments.get(index).copied();
+
+		let function_transform = match (name, arguments.len()) {
+			("matrix", 6) => DAffine2::from_cols_array(&[arguments[0], arguments[1], arguments[2], arguments[3], arguments[4], arguments[5]]),
+			("translate", 1 | 2) => DAffine2::from_translation(DVec2::new(arguments[0], argument(1).unwrap_or(0.))),
+			("scale", 1 | 2) => DAffine2::from_scale(DVec2::new(arguments[0], argument(1).unwrap_or(arguments[0]))),
+			("rotate", 1 | 3) => {
+				let center = DVec2::new(argument(1).unwrap_or(0.), argument(2).unwrap_or(0.));
+				DAffine2::from_translation(center) * DAffine2::from_angle(arguments[0].to_radians()) * DAffine2::from_translation(-center)
+			}
+			("skewX", 1) => DAffine2::from_cols_array(&[1., 0., arguments[0].to_radians().tan(), 1., 0., 0.]),
+			("skewY", 1) => DAffine2::from_cols_array(&[1., arguments[0].to_radians().tan(), 0., 1., 0., 0.]),
+			_ => DAffine2::IDENTITY,
+		};
+		transform = transform * function_transform;
+	}
+
+	transform
+}
+
+// =====================
+// Path data
+// =====================
+
+/// Reads the numbers, flags, and command letters of path data, where separators between numbers are optional when unambiguous.
+struct PathDataParser<'a> {
+	data: &'a [u8],
+	position: usize,
+}
+
+impl<'a> PathDataParser<'a> {
+	fn new(data: &'a str) -> Self {
+		Self { data: data.as_bytes(), position: 0 }
+	}
+
+	fn skip_separators(&mut self) {
+		while self.data.get(self.position).map_or(false, |&byte| byte.is_ascii_whitespace() || byte == b',') {
+			self.position += 1;
+		}
+	}
+
+	fn command(&mut self) -> Option<u8> {
+		self.skip_separators();
+		let byte = *self.data.get(self.position)?;
+		if byte.is_ascii_alphabetic() && byte != b'e' && byte != b'E' {
+			self.position += 1;
+			return Some(byte);
+		}
+		None
+	}
+
+	fn at_end(&mut self) -> bool {
+		self.skip_separators();
+		self.position >= self.data.len()
+	}
+
+	fn number(&mut self) -> Option<f64> {
+		self.skip_separators();
+		let start = self.position;
+		let digits = |parser: &mut Self| {
+			let start = parser.position;
+			while parser.data.get(parser.position).map_or(false, u8::is_ascii_digit) {
+				parser.position += 1;
+			}
+			parser.position > start
+		};
+
+		if matches!(self.data.get(self.position), Some(b'+' | b'-')) {
+			self.position += 1;
+		}
+		let mut any_digits = digits(self);
+		if self.data.get(self.position) == Some(&b'.') {
+			self.position += 1;
+			any_digits |= digits(self);
+		}
+		if !any_digits {
+			self.position = start;
+			return None;
+		}
+		if matches!(self.data.get(self.position), Some(b'e' | b'E')) {
+			let mantissa_end = self.position;
+			self.position += 1;
+			if matches!(self.data.get(self.position), Some(b'+' | b'-')) {
+				self.position += 1;
+			}
+			if !digits(self) {
+				self.position = mantissa_end;
+			}
+		}
+
+		std::str::from_utf8(&self.data[start..self.position]).ok()?.parse().ok()
+	}
+
+	fn flag(&mut self) -> Option<bool> {
+		self.skip_separators();
+		let flag = match self.data.get(self.position)? {
+			b'0' => false,
+			b'1' => true,
+			_ => return None,
+		};
+		self.position += 1;
+		Some(flag)
+	}
+
+	fn point(&mut self) -> Option<DVec2> {
+		Some(DVec2::new(self.number()?, self.number()?))
+	}
+}
+
+/// Parses the `d` attribute of a path into subpaths. Also returns whether the whole path data was read, since it is drawn only up
+/// to the first error.
+pub fn parse_path_data(data: &str) -> (Vec<Subpath<ManipulatorGroupId>>, bool) {
+	let mut parser = PathDataParser::new(data);
+	let mut path = PathBuilder::default();
+	let mut command = None;
+	// The second control point of the previous curve, which smooth curves reflect to find their first control point
+	let mut previous_cubic_control = None;
+	let mut previous_quadratic_control = None;
+
+	let complete = loop {
+		if parser.at_end() {
+			break true;
+		}
+		if let Some(letter) = parser.command() {
+			command = Some(letter);
+		}
+		let Some(letter) = command else { break false };
+
+		let relative = letter.is_ascii_lowercase();
+		let origin = if relative { path.current } else { DVec2::ZERO };
+		let current = path.current;
+		let mut cubic_control = None;
+		let mut quadratic_control = None;
+
+		let parsed = match letter.to_ascii_uppercase() {
+			b'M' => parser.point().map(|point| {
+				path.move_to(origin + point);
+				// Additional coordinate pairs after a move are treated as lines
+				command = Some(if relative { b'l' } else { b'L' });
+			}),
+			b'L' => parser.point().map(|point| path.line_to(origin + point)),
+			b'H' => parser.number().map(|x| path.line_to(DVec2::new(origin.x + x, current.y))),
+			b'V' => parser.number().map(|y| path.line_to(DVec2::new(current.x, origin.y + y))),
+			b'C' => (|| Some((parser.point()?, parser.point()?, parser.point()?)))().map(|(first, second, end)| {
+				path.cubic_to(origin + first, origin + second, origin + end);
+				cubic_control = Some(origin + second);
+			}),
+			b'S' => (|| Some((parser.point()?, parser.point()?)))().map(|(second, end)| {
+				let first = previous_cubic_control.map_or(current, |control: DVec2| 2. * current - control);
+				path.cubic_to(first, origin + second, origin + end);
+				cubic_control = Some(origin + second);
+			}),
+			b'Q' => (|| Some((parser.point()?, parser.point()?)))().map(|(control, end)| {
+				path.quadratic_to(origin + control, origin + end);
+				quadratic_control = Some(origin + control);
+			}),
+			b'T' => parser.point().map(|end| {
+				let control = previous_quadratic_control.map_or(current, |control: DVec2| 2. * current - control);
+				path.quadratic_to(control, origin + end);
+				quadratic_control = Some(control);
+			}),
+			b'A' => (|| Some((parser.point()?, parser.number()?, parser.flag()?, parser.flag()?, parser.point()?)))().map(|(radii, rotation, large_arc, sweep, end)| {
+				path.arc_to(radii, rotation, large_arc, sweep, origin + end);
+			}),
+			b'Z' => {
+				path.close();
+				// A close command takes no arguments, so anything other than a new command after it is an error
+				command = None;
+				Some(())
+			}
+			_ => None,
+		};
+		if parsed.is_none() {
+			break false;
+		}
+
+		previous_cubic_control = cubic_control;
+		previous_quadratic_control = quadratic_control;
+	};
+
+	(path.finish(), complete)
+}
+
+/// Builds subpaths out of a sequence of drawing commands with absolute coordinates.
+#[derive(Default)]
+struct PathBuilder {
+	subpaths: Vec<Subpath<ManipulatorGroupId>>,
+	groups: Vec<ManipulatorGroup<ManipulatorGroupId>>,
+	start: DVec2,
+	current: DVec2,
+}
+
+impl PathBuilder {
+	fn move_to(&mut self, point: DVec2) {
+		self.finish_subpath(false);
+		self.start = point;
+		self.current = point;
+		self.groups.push(ManipulatorGroup::new_anchor(point));
+	}
+
+	/// Starts a new subpath at the current point when drawing continues after a subpath was closed without moving first.
+	fn ensure_started(&mut self) {
+		if self.groups.is_empty() {
+			self.groups.push(ManipulatorGroup::new_anchor(self.current));
+		}
+	}
+
+	fn line_to(&mut self, point: DVec2) {
+		self.ensure_started();
+		self.groups.push(ManipulatorGroup::new_anchor(point));
+		self.current = point;
+	}
+
+	fn cubic_to(&mut self, first: DVec2, second: DVec2, end: DVec2) {
+		self.ensure_started();
+		if let Some(previous) = self.groups.last_mut() {
+			previous.out_handle = Some(first);
+		}
+		self.groups.push(ManipulatorGroup::new(end, Some(second), None));
+		self.current = end;
+	}
+
+	fn quadratic_to(&mut self, control: DVec2, end: DVec2) {
+		let start = self.current;
+		self.cubic_to(start + (control - start) * (2. / 3.), end + (control - end) * (2. / 3.), end);
+	}
+
+	/// Draws an elliptical arc, converting the endpoint parameterization of SVG arcs to the center parameterization and then
+	/// approximating the arc with one cubic curve per quarter turn.
+	fn arc_to(&mut self, radii: DVec2, rotation: f64, large_arc: bool, sweep: bool, end: DVec2) {
+		let start = self.current;
+		let mut radii = radii.abs();
+		if start == end {
+			return;
+		}
+		if radii.x == 0. || radii.y == 0. {
+			self.line_to(end);
+			return;
+		}
+
+		let rotation = DAffine2::from_angle(rotation.to_radians());
+		let half_difference = rotation.inverse().transform_vector2((start - end) / 2.);
+
+		// Radii too small to span the endpoints are scaled up until the arc just fits
+		let lambda = (half_difference / radii).length_squared();
+		if lambda > 1. {
+			radii *= lambda.sqrt();
+		}
+
+		let [rx, ry] = radii.to_array();
+		let [x, y] = half_difference.to_array();
+		let numerator = rx * rx * ry * ry - rx * rx * y * y - ry * ry * x * x;
+		let denominator = rx * rx * y * y + ry * ry * x * x;
+		let mut coefficient = (numerator / denominator).max(0.).sqrt();
+		if large_arc == sweep {
+			coefficient = -coefficient;
+		}
+		let center_offset = DVec2::new(coefficient * rx * y / ry, -coefficient * ry * x / rx);
+		let center = rotation.transform_vector2(center_offset) + (start + end) / 2.;
+
+		let angle = |from: DVec2, to: DVec2| from.perp_dot(to).atan2(from.dot(to));
+		let start_angle = angle(DVec2::X, (half_difference - center_offset) / radii);
+		let mut sweep_angle = angle((half_difference - center_offset) / radii, (-half_difference - center_offset) / radii) % TAU;
+		if !sweep && sweep_angle > 0. {
+			sweep_angle -= TAU;
+		} else if sweep && sweep_angle < 0. {
+			sweep_angle += TAU;
+		}
+
+		let segments = (sweep_angle.abs() / (PI / 2.)).ceil().max(1.) as usize;
+		let segment_angle = sweep_angle / segments as f64;
+		let handle_length = 4. / 3. * (segment_angle / 4.).tan();
+		let point = |angle: f64| center + rotation.transform_vector2(radii * DVec2::new(angle.cos(), angle.sin()));
+		let tangent = |angle: f64| rotation.transform_vector2(radii * DVec2::new(-angle.sin(), angle.cos()));
+
+		for segment in 0..segments {
+			let from = start_angle + segment_angle * segment as f64;
+			let to = from + segment_angle;
+			let to_point = if segment + 1 == segments { end } else { point(to) };
+			self.cubic_to(point(from) + tangent(from) * handle_length, point(to) - tangent(to) * handle_length, to_point);
+		}
+	}
+
+	fn close(&mut self) {
+		self.finish_subpath(true);
+		self.current = self.start;
+	}
+
+	fn finish_subpath(&mut self, closed: bool) {
+		let mut groups = std::mem::take(&mut self.groups);
+
+		// The segment back to the start is implied by closing, so a final anchor placed on the start is merged into it
+		if closed && groups.len() > 2 && groups.last().map(|last| last.anchor.abs_diff_eq(groups[0].anchor, 1e-9)) == Some(true) {
+			let last = groups.pop().unwrap();
+			groups[0].in_handle = last.in_handle;
+		}
+
+		if groups.len() > 1 {
+			self.subpaths.push(Subpath::new(groups, closed));
+		}
+	}
+
+	fn finish(mut self) -> Vec<Subpath<ManipulatorGroupId>> {
+		self.finish_subpath(false);
+		self.subpaths
+	}
+}
+
+// =====================
+// XML
+// =====================
+
+/// An XML element with its attributes and child elements. Text content isn't kept, since only text elements would use it.
+#[derive(Debug, Default)]
+struct Element {
+	name: String,
+	attributes: Vec<(String, String)>,
+	children: Vec<Element>,
+}
+
+impl Element {
+	fn attribute(&self, name: &str) -> Option<&str> {
+		self.attributes.iter().find(|(attribute, _)| attribute == name).map(|(_, value)| value.as_str())
+	}
+
+	/// A presentation property, taken from the `style` attribute's declarations before falling back to the attribute of that name.
+	fn style_value(&self, property: &str) -> Option<&str> {
+		let declared = self.attribute("style").and_then(|style| {
+			style.split(';').filter_map(|declaration| declaration.split_once(':')).find_map(|(name, value)| {
+				let value = value.trim().trim_end_matches("!important").trim();
+				(name.trim() == property).then_some(value)
+			})
+		});
+		declared.or_else(|| self.attribute(property).map(str::trim))
+	}
+
+	fn length(&self, name: &str) -> f64 {
+		self.attribute(name).and_then(parse_length).unwrap_or(0.)
+	}
+}
+
+/// Parses XML into its root element. Comments, processing instructions, the document type declaration, and text are skipped.
+fn parse_xml(text: &str) -> Result<Element, String> {
+	let mut stack: Vec<Element> = Vec::new();
+	let mut root = None;
+	let mut rest = text;
+
+	while let Some(start) = rest.find('<') {
+		rest = &rest[start..];
+
+		let skip_to = |rest: &str, terminator: &str| rest.find(terminator).map(|index| index + terminator.len());
+		let skipped = if rest.starts_with("<!--") {
+			Some(skip_to(rest, "-->"))
+		} else if rest.starts_with("<![CDATA[") {
+			Some(skip_to(rest, "]]>"))
+		} else if rest.starts_with("<?") {
+			Some(skip_to(rest, "?>"))
+		} else if rest.starts_with("<!") {
+			// The document type declaration may contain an internal subset in brackets with `>` characters inside
+			let mut depth = 0;
+			Some(rest.char_indices().find_map(|(index, character)| {
+				match character {
+					'[' => depth += 1,
+					']' => depth -= 1,
+					'>' if depth == 0 => return Some(index + 1),
+					_ => {}
+				}
+				None
+			}))
+		} else {
+			None
+		};
+		if let Some(skipped) = skipped {
+			let length = skipped.ok_or("Unexpected end of file inside a comment or declaration")?;
+			rest = &rest[length..];
+			continue;
+		}
+
+		if let Some(closing) = rest.strip_prefix("</") {
+			let end = closing.find('>').ok_or("Unexpected end of file inside a closing tag")?;
+			let name = local_name(closing[..end].trim());
+			let element = stack.pop().ok_or_else(|| format!("Unexpected closing tag </{name}>"))?;
+			if element.name != name {
+				return Err(format!("Expected </{}> but found </{name}>", element.name));
+			}
+			match stack.last_mut() {
+				Some(parent) => parent.children.push(element),
+				None => root = root.or(Some(element)),
+			}
+			rest = &closing[end + 1..];
+			continue;
+		}
+
+		let (element, self_closing, length) = parse_start_tag(rest)?;
+		rest = &rest[length..];
+		if !self_closing {
+			// Each nested element becomes a nested folder, and both are handled recursively
+			if stack.len() >= MAX_FOLDER_DEPTH {
+				return Err(format!("Elements are nested more than {MAX_FOLDER_DEPTH} levels deep"));
+			}
+			stack.push(element);
+			continue;
+		}
+		match stack.last_mut() {
+			Some(parent) => parent.children.push(element),
+			None => root = root.or(Some(element)),
+		}
+	}
+
+	if let Some(element) = stack.last() {
+		return Err(format!("Unexpected end of file inside <{}>", element.name));
+	}
+	root.ok_or_else(|| "The file contains no elements".to_string())
+}
+
+/// Parses a start tag at the beginning of the text, returning the element, whether the tag closes itself, and the tag's length.
+fn parse_start_tag(text: &str) -> Result<(Element, bool, usize), String> {
+	let is_name_end = |character: char| character.is_whitespace() || character == '/' || character == '>';
+	let unexpected_end = || "Unexpected end of file inside a tag".to_string();
+
+	let mut position = 1;
+	let name_length = text[position..].find(is_name_end).ok_or_else(unexpected_end)?;
+	let mut element = Element {
+		name: local_name(&text[position..position + name_length]).to_string(),
+		..Default::default()
+	};
+	if element.name.is_empty() {
+		return Err("Found a tag without a name".to_string());
+	}
+	position += name_length;
+
+	loop {
+		let remaining = &text[position..];
+		let trimmed = remaining.trim_start();
+		position += remaining.len() - trimmed.len();
+
+		if trimmed.starts_with("/>") {
+			return Ok((element, true, position + 2));
+		}
+		if trimmed.starts_with('>') {
+			return Ok((element, false, position + 1));
+		}
+		if trimmed.is_empty() {
+			return Err(unexpected_end());
+		}
+
+		let equals = trimmed.find('=').ok_or_else(|| format!("Expected an attribute value in <{}>", element.name))?;
+		let name = trimmed[..equals].trim();
+		if name.is_empty() || name.contains(is_name_end) {
+			return Err(format!("Malformed attribute in <{}>", element.name));
+		}
+		let value_start = &trimmed[equals + 1..];
+		let quoted = value_start.trim_start();
+		let quote = quoted
+			.chars()
+			.next()
+			.filter(|&quote| quote == '"' || quote == '\'')
+			.ok_or_else(|| format!("Expected a quoted value for the {name} attribute"))?;
+		let value_length = quoted[1..].find(quote).ok_or_else(unexpected_end)?;
+
+		element.attributes.push((name.to_string(), decode_entities(&quoted[1..1 + value_length])));
+		position += equals + 1 + (value_start.len() - quoted.len()) + value_length + 2;
+	}
+}
+
+/// Removes the namespace prefix from an element name, so elements like `<svg:path>` are read like `<path>`.
+fn local_name(name: &str) -> &str {
+	name.rsplit_once(':').map_or(name, |(_, local)| local)
+}
+
+fn decode_entities(text: &str) -> String {
+	let mut decoded = String::with_capacity(text.len());
+	let mut rest = text;
+
+	while let Some(start) = rest.find('&') {
+		decoded.push_str(&rest[..start]);
+		rest = &rest[start..];
+
+		let Some(end) = rest.find(';') else { break };
+		let entity = &rest[1..end];
+		let character = match entity {
+			"amp" => Some('&'),
+			"lt" => Some('<'),
+			"gt" => Some('>'),
+			"quot" => Some('"'),
+			"apos" => Some('\''),
+			_ => entity
+				.strip_prefix("#x")
+				.map(|hex| u32::from_str_radix(hex, 16))
+				.or_else(|| entity.strip_prefix('#').map(str::parse))
+				.and_then(Result::ok)
+				.and_then(char::from_u32),
+		};
+		match character {
+			Some(character) => {
+				decoded.push(character);
+				rest = &rest[end + 1..];
+			}
+			None => {
+				decoded.push('&');
+				rest = &rest[1..];
+			}
+		}
+	}
+	decoded.push_str(rest);
+
+	decoded
+}
@@ -0,0 +1,21 @@
+use crate::document::{Document, LayerId};
+use crate::layers::style::PathStyle;
+use crate::operation::Operation;
+
+use glam::DAffine2;
+
+/// Adds an unstyled rectangle covering the unit square, placed by the given transform, at the end of its folder.
+pub fn add_transformed_rect(document: &mut Document, path: Vec<LayerId>, transform: DAffine2) {
+	let operation = Operation::AddRect {
+		path,
+		insert_index: -1,
+		transform: transform.to_cols_array(),
+		style: PathStyle::default(),
+	};
+	document.handle_operation(operation).unwrap();
+}
+
+/// Adds an unstyled rectangle covering the unit square at the end of its folder.
+pub fn add_rect(document: &mut Document, path: Vec<LayerId>) {
+	add_transformed_rect(document, path, DAffine2::IDENTITY);
+}
@@ -0,0 +1,167 @@
+//! Renders each SVG in `tests/svg_golden/corpus` to a document and compares the rendered SVG with the file of the same name in
+//! `tests/svg_golden/expected`, so changes to the renderer which alter its output are caught.
+//!
+//! Numbers are compared with a small tolerance, since changes in how floats are formatted or rounding in the math don't alter the artwork.
+//! Run with the `UPDATE_GOLDEN` environment variable set to write the expected files from the current output after checking it is right.
+//! An expected file which doesn't exist yet is written from the current output as well, and should be checked before it is committed.
+
+use graphite_document_legacy::document::Document;
+use graphite_document_legacy::layers::style::{RenderData, ViewMode};
+use graphite_document_legacy::operation::Operation;
+use graphite_document_legacy::svg_import::import_svg;
+
+use graphene_std::text::FontCache;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Numbers differing by less than this, relative to their size when above one, are considered equal.
+const NUMBER_TOLERANCE: f64 = 1e-6;
+
+fn golden_directory() -> PathBuf {
+	Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("svg_golden")
+}
+
+/// Imports the SVG into a new document and renders the document, giving the layers sequential IDs so the output is the same every time.
+fn render(svg: &str) -> String {
+	let import = import_svg(svg).expect("The corpus SVG should import");
+
+	let mut document = Document::default();
+	for (id, layer) in import.layers.into_iter().enumerate() {
+		document
+			.handle_operation(Operation::InsertLayer {
+				layer: Box::new(layer),
+				destination_path: vec![id as u64],
+				insert_index: -1,
+				duplicating: false,
+			})
+			.expect("The imported layer should be inserted");
+	}
+
+	let font_cache = FontCache::default();
+	document.render_root(&RenderData::new(&font_cache, ViewMode::Normal, None))
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+	Number(f64),
+	Character(char),
+}
+
+/// Splits the SVG into numbers and the other characters between them, leaving out whitespace so changes in indentation or line breaks
+/// don't matter.
+fn tokenize(svg: &str) -> Vec<Token> {
+	let mut tokens = Vec::new();
+	let mut rest = svg.trim_start();
+	while let Some(character) = rest.chars().next() {
+		let number_length = number_prefix_length(rest);
+		match rest[..number_length].parse() {
+			Ok(number) if number_length > 0 => {
+				tokens.push(Token::Number(number));
+				rest = &rest[number_length..];
+			}
+			_ => {
+				tokens.push(Token::Character(character));
+				rest = &rest[character.len_utf8()..];
+			}
+		}
+		rest = rest.trim_start();
+	}
+	tokens
+}
+
+/// The length of the number at the start of the text, like `-1.5e-3`, or zero if it doesn't start with one.
+fn number_prefix_length(text: &str) -> usize {
+	let bytes = text.as_bytes();
+	let mut length = 0;
+	if matches!(bytes.first(), Some(b'-' | b'+')) {
+		length += 1;
+	}
+	let digits_start = length;
+	while length < bytes.len() && (bytes[length].is_ascii_digit() || bytes[length] == b'.') {
+		length += 1;
+	}
+	if !bytes[digits_start..length].iter().any(u8::is_ascii_digit) {
+		return 0;
+	}
+
+	// An exponent is only part of the number if digits follow it
+	if matches!(bytes.get(length), Some(b'e' | b'E')) {
+		let mut exponent_length = length + 1;
+		if matches!(bytes.get(exponent_length), Some(b'-' | b'+')) {
+			exponent_length += 1;
+		}
+		let exponent_digits_start = exponent_length;
+		while exponent_length < bytes.len() && bytes[exponent_length].is_ascii_digit() {
+			exponent_length += 1;
+		}
+		if exponent_length > exponent_digits_start {
+			length = exponent_length;
+		}
+	}
+	length
+}
+
+/// Compares the rendered SVG with the expected one, describing the first difference found.
+fn compare(rendered: &str, expected: &str) -> Result<(), String> {
+	let (rendered, expected) = (tokenize(rendered), tokenize(expected));
+	for (index, (rendered_token, expected_token)) in rendered.iter().zip(&expected).enumerate() {
+		let equal = match (rendered_token, expected_token) {
+			(Token::Number(a), Token::Number(b)) => (a - b).abs() <= NUMBER_TOLERANCE * a.abs().max(b.abs()).max(1.),
+			(a, b) => a == b,
+		};
+		if !equal {
+			return Err(format!("Token {index} differs: rendered {rendered_token:?} but expected {expected_token:?}"));
+		}
+	}
+
+	if rendered.len() != expected.len() {
+		return Err(format!("Rendered {} tokens but expected {}", rendered.len(), expected.len()));
+	}
+	Ok(())
+}
+
+#[test]
+fn corpus_renders_as_expected() {
+	let directory = golden_directory();
+	let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+
+	let mut corpus = fs::read_dir(directory.join("corpus"))
+		.expect("The corpus directory should exist")
+		.map(|entry| entry.expect("The corpus entry should be readable").path())
+		.filter(|path| path.extension().map_or(false, |extension| extension == "svg"))
+		.collect::<Vec<_>>();
+	corpus.sort();
+	assert!(!corpus.is_empty(), "The corpus should contain SVG files");
+
+	let mut failures = Vec::new();
+	for source in corpus {
+		let name = source.file_name().unwrap().to_string_lossy().into_owned();
+		let rendered = render(&fs::read_to_string(&source).expect("The corpus SVG should be readable"));
+
+		let expected_path = directory.join("expected").join(&name);
+		match fs::read_to_string(&expected_path) {
+			Ok(expected) if !update => {
+				if let Err(difference) = compare(&rendered, &expected) {
+					failures.push(format!("{name}: {difference}"));
+				}
+			}
+			_ => {
+				fs::create_dir_all(directory.join("expected")).expect("The expected directory should be creatable");
+				fs::write(&expected_path, rendered).expect("The expected SVG should be writable");
+				eprintln!("Wrote the expected render of {name}, check it before committing it");
+			}
+		}
+	}
+
+	assert!(failures.is_empty(), "The render differs from the expected output:\n{}", failures.join("\n"));
+}
+
+#[test]
+fn comparison_tolerates_float_formatting() {
+	assert!(compare(r#"<path d="M0 0.30000000000000004 L1e-9,2" />"#, r#"<path d="M0 0.3 L0,2.0000000001" />"#).is_ok());
+	assert!(compare("<g>\n\t<rect />\n</g>", "<g><rect /></g>").is_ok());
+	assert!(compare(r#"<path d="M0 0.3" />"#, r#"<path d="M0 0.4" />"#).is_err());
+	assert!(compare(r#"<rect fill="red" />"#, r#"<rect fill="blue" />"#).is_err());
+	assert!(compare("<g></g>", "<g></g><g></g>").is_err());
+}
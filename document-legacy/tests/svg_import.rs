@@ -0,0 +1,64 @@
+use graphite_document_legacy::layers::layer_info::{Layer, LayerDataType};
+use graphite_document_legacy::layers::style::Fill;
+use graphite_document_legacy::svg_import::import_svg;
+
+use glam::{DAffine2, DVec2};
+
+fn shape_bounds(layer: &Layer) -> [DVec2; 2] {
+	let LayerDataType::Shape(shape) = &layer.data else { panic!("Expected a shape layer") };
+	shape.shape.bounding_box().expect("The shape should have geometry")
+}
+
+#[test]
+fn groups_become_folders_keeping_their_transforms() {
+	let import = import_svg(r##"<svg width="100" height="50"><g id="group" transform="translate(10 20)"><rect width="10" height="5" fill="#ff0000"/></g></svg>"##).unwrap();
+	assert_eq!(import.size, Some(DVec2::new(100., 50.)));
+	assert!(import.warnings.is_empty());
+
+	let [folder] = import.layers.as_slice() else { panic!("Expected one layer") };
+	assert_eq!(folder.name.as_deref(), Some("group"));
+	assert_eq!(folder.transform, DAffine2::from_translation(DVec2::new(10., 20.)));
+
+	let LayerDataType::Folder(folder) = &folder.data else { panic!("Expected a folder") };
+	let [rect] = folder.layers() else { panic!("Expected one layer in the folder") };
+	assert_eq!(shape_bounds(rect), [DVec2::ZERO, DVec2::new(10., 5.)]);
+	let LayerDataType::Shape(shape) = &rect.data else { unreachable!() };
+	assert!(matches!(shape.style.fill(), Fill::Solid(color) if color.to_rgba8_srgb() == [255, 0, 0, 255]));
+}
+
+#[test]
+fn view_box_scales_the_content_to_the_canvas() {
+	let import = import_svg(r#"<svg width="200" height="200" viewBox="0 0 100 100"><circle cx="50" cy="50" r="10"/></svg>"#).unwrap();
+	assert_eq!(import.size, Some(DVec2::new(200., 200.)));
+
+	let [circle] = import.layers.as_slice() else { panic!("Expected one layer") };
+	assert_eq!(circle.transform, DAffine2::from_scale(DVec2::splat(2.)));
+	let [min, max] = shape_bounds(circle);
+	assert!(min.abs_diff_eq(DVec2::splat(40.), 1e-6) && max.abs_diff_eq(DVec2::splat(60.), 1e-6));
+}
+
+#[test]
+fn path_data_and_stroke_attributes_are_read() {
+	let import = import_svg(r##"<svg><path d="M 0 0 l 10 0 v 10 z m 20 0 h 5" fill="none" style="stroke: #0000ff; stroke-width: 3"/></svg>"##).unwrap();
+	let [path] = import.layers.as_slice() else { panic!("Expected one layer") };
+	let LayerDataType::Shape(shape) = &path.data else { panic!("Expected a shape layer") };
+
+	assert_eq!(shape_bounds(path), [DVec2::ZERO, DVec2::new(25., 10.)]);
+	assert_eq!(shape.style.fill(), &Fill::None);
+	let stroke = shape.style.stroke().expect("The path should be stroked");
+	assert_eq!(stroke.weight(), 3.);
+	assert_eq!(stroke.color().map(|color| color.to_rgba8_srgb()), Some([0, 0, 255, 255]));
+}
+
+#[test]
+fn unsupported_elements_are_skipped_with_one_warning_each() {
+	let import = import_svg(r#"<svg><text>A</text><text>B</text><image href="a.png"/><rect width="1" height="1"/></svg>"#).unwrap();
+	assert_eq!(import.layers.len(), 1);
+	assert_eq!(import.warnings.len(), 2);
+}
+
+#[test]
+fn files_without_an_svg_root_fail() {
+	assert!(import_svg("<html></html>").is_err());
+	assert!(import_svg("<svg><rect").is_err());
+}
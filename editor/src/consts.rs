@@ -12,12 +12,32 @@ pub const VIEWPORT_ZOOM_LEVELS: [f64; 74] = [
 	128., 160., 200., 256., 320., 400., 512., 640., 800., 1024., 1280., 1600., 2048., 2560.,
 ];
 
+/// Milliseconds without any change to the view after which the view counts as settled, so "Previous Zoom" returns to it rather than to a step partway through a scroll or drag.
+pub const VIEWPORT_PREVIOUS_VIEW_SETTLE_TIME: f64 = 500.;
+
 pub const VIEWPORT_GRID_ROUNDING_BIAS: f64 = 0.002; // Helps push values that end in approximately half, plus or minus some floating point imprecision, towards the same side of the round() function
 
 pub const VIEWPORT_SCROLL_RATE: f64 = 0.6;
 
+/// Distance, in viewport pixels, from each edge of the viewport within which dragging automatically pans the canvas.
+pub const VIEWPORT_AUTO_SCROLL_MARGIN: f64 = 40.;
+/// Speed, in viewport pixels per second, at which the canvas is panned while dragging right at the edge of the viewport.
+/// It increases up to twice as fast as the pointer moves past the edge.
+pub const VIEWPORT_AUTO_SCROLL_SPEED: f64 = 600.;
+
 pub const VIEWPORT_ROTATE_SNAP_INTERVAL: f64 = 15.;
 
+/// Milliseconds over which zooming to fit, zooming to a set level, and resetting the rotation ease the view into place.
+pub const VIEWPORT_ANIMATION_DURATION: f64 = 200.;
+/// Speed, in viewport pixels per second, that the pointer must be moving when a pan is released for the canvas to keep drifting.
+pub const VIEWPORT_PAN_INERTIA_MIN_SPEED: f64 = 300.;
+/// Speed, in viewport pixels per second, below which the drifting canvas comes to a stop.
+pub const VIEWPORT_PAN_INERTIA_STOP_SPEED: f64 = 10.;
+/// Milliseconds over which the speed of the drifting canvas decays to about a third.
+pub const VIEWPORT_PAN_INERTIA_DECAY: f64 = 325.;
+/// Milliseconds that the pointer can rest before a pan is released and still set the canvas drifting.
+pub const VIEWPORT_PAN_INERTIA_MAX_REST: f64 = 50.;
+
 // Snapping axis
 pub const SNAP_AXIS_TOLERANCE: f64 = 3.;
 pub const SNAP_AXIS_OVERLAY_FADE_DISTANCE: f64 = 15.;
@@ -30,9 +50,18 @@ pub const SNAP_POINT_UNSNAPPED_OPACITY: f64 = 0.4;
 pub const SNAP_POINT_TOLERANCE: f64 = 5.;
 pub const SNAP_POINT_SIZE: f64 = 5.;
 
+// Snapping grid
+pub const SNAP_GRID_DEFAULT_SPACING: f64 = 10.;
+
+// Raster effects, rasterized at a number of pixels per document pixel
+pub const RASTER_RESOLUTION_MIN: f64 = 0.1;
+pub const RASTER_RESOLUTION_MAX: f64 = 8.;
+
 pub const DRAG_THRESHOLD: f64 = 1.;
 
 pub const PATH_OUTLINE_WEIGHT: f64 = 2.;
+/// How far, in viewport pixels, the outlines of selected and hovered layers may stray from their paths when simplified while zoomed out
+pub const PATH_OUTLINE_SIMPLIFICATION_TOLERANCE: f64 = 0.25;
 
 // Transforming layer
 pub const ROTATE_SNAP_ANGLE: f64 = 15.;
@@ -40,6 +69,12 @@ pub const SCALE_SNAP_INTERVAL: f64 = 0.1;
 pub const SLOWING_DIVISOR: f64 = 10.;
 pub const NUDGE_AMOUNT: f64 = 1.;
 pub const BIG_NUDGE_AMOUNT: f64 = 10.;
+// Kerning is nudged in thousandths of an em
+pub const KERNING_NUDGE_AMOUNT: f32 = 10.;
+pub const BIG_KERNING_NUDGE_AMOUNT: f32 = 50.;
+
+// Handles and hit targets below are in viewport pixels, read through `hit_targets` so the "Larger Handles" preference applies
+pub const LARGER_HANDLES_SCALE: f64 = 1.75;
 
 // Select tool
 pub const SELECTION_TOLERANCE: f64 = 5.;
@@ -47,6 +82,14 @@ pub const SELECTION_DRAG_ANGLE: f64 = 90.;
 pub const PIVOT_OUTER_OUTLINE_THICKNESS: f64 = 1.;
 pub const PIVOT_OUTER: f64 = 9.;
 pub const PIVOT_INNER: f64 = 3.;
+pub const CORNER_RADIUS_HANDLE_SIZE: f64 = 8.;
+pub const CORNER_RADIUS_HANDLE_MIN_INSET: f64 = 12.;
+/// Diameter, in viewport pixels, of the brush which paints the quick mask
+pub const QUICK_MASK_DEFAULT_DIAMETER: f64 = 40.;
+pub const QUICK_MASK_MIN_DIAMETER: f64 = 5.;
+/// Distance between the dabs of the quick mask brush, as a percentage of its diameter
+pub const QUICK_MASK_SPACING: f64 = 25.;
+pub const QUICK_MASK_OPACITY: f32 = 0.35;
 
 // Transformation cage
 pub const BOUNDS_SELECT_THRESHOLD: f64 = 10.;
@@ -63,9 +106,33 @@ pub const CREATE_CURVE_THRESHOLD: f64 = 5.;
 // Line tool
 pub const LINE_ROTATE_SNAP_ANGLE: f64 = 15.;
 
+// Freehand tool
+/// Farthest distance, in viewport pixels, that a drawn freehand line may move from the pointer samples when it is simplified
+pub const FREEHAND_SIMPLIFY_TOLERANCE: f64 = 1.;
+/// Pointer speed, in viewport pixels per millisecond, at which the speed dynamic thins the line by half of its strength
+pub const FREEHAND_SPEED_DYNAMIC_HALF_SPEED: f64 = 1.5;
+
 // Brush tool
 pub const BRUSH_SIZE_CHANGE_KEYBOARD: f64 = 5.;
 
+// Stabilizer of the drawing tools
+/// Number of pointer positions averaged by the moving average stabilizer at full strength
+pub const STABILIZER_MAX_AVERAGED_SAMPLES: usize = 32;
+/// Length, in viewport pixels, of the string of the pull string stabilizer at full strength
+pub const STABILIZER_MAX_STRING_LENGTH: f64 = 80.;
+
+// Pointer
+/// Time, in milliseconds, over which the pointer speed is smoothed so that one jittery event doesn't dominate it
+pub const POINTER_SPEED_SMOOTHING_TIME: f64 = 40.;
+
+// Key repeat
+/// Time, in milliseconds, that a key is held before its action starts repeating
+pub const KEY_REPEAT_DEFAULT_DELAY: f64 = 500.;
+/// Time, in milliseconds, between repeats of the action of a held key
+pub const KEY_REPEAT_DEFAULT_INTERVAL: f64 = 33.;
+/// The shortest time, in milliseconds, waited before a held key repeats, since shorter waits would flood the editor with repeats
+pub const KEY_REPEAT_MIN_TIME: f64 = 16.;
+
 // Scrollbars
 pub const SCROLLBAR_SPACING: f64 = 0.1;
 pub const ASYMPTOTIC_EFFECT: f64 = 0.5;
@@ -73,15 +140,39 @@ pub const SCALE_EFFECT: f64 = 0.5;
 
 // Colors
 pub const COLOR_ACCENT: Color = Color::from_rgbf32_unchecked(0x00 as f32 / 255., 0xA8 as f32 / 255., 0xFF as f32 / 255.);
+pub const COLOR_HIGH_CONTRAST_SELECTION: Color = Color::from_rgbf32_unchecked(0xFF as f32 / 255., 0x00 as f32 / 255., 0xFF as f32 / 255.);
+pub const COLOR_HIGH_CONTRAST_HANDLES: Color = Color::from_rgbf32_unchecked(0xFF as f32 / 255., 0xD0 as f32 / 255., 0x00 as f32 / 255.);
+pub const COLOR_HIGH_CONTRAST_SNAP: Color = Color::from_rgbf32_unchecked(0x00 as f32 / 255., 0xFF as f32 / 255., 0x60 as f32 / 255.);
+
+// Color picker
+pub const RECENT_COLORS_CAPACITY: usize = 16;
 
 // Fonts
 pub const DEFAULT_FONT_FAMILY: &str = "Merriweather";
 pub const DEFAULT_FONT_STYLE: &str = "Normal (400)";
 
+// Charts
+pub const DEFAULT_CHART_DATA: &str = "Label,Value\nA,3\nB,5\nC,2\nD,4";
+
+// Placeholders
+pub const PLACEHOLDER_TEXT_LINE_LENGTH: usize = 60;
+
+// Idle work
+pub const IDLE_INPUT_QUIET_PERIOD: f64 = 250.; // Milliseconds without any input before background work may begin
+
 // Document
 pub const GRAPHITE_DOCUMENT_VERSION: &str = "0.0.17"; // Remember to save a simple document and replace the test file `graphite-test-document.graphite`
 pub const DEFAULT_DOCUMENT_NAME: &str = "Untitled Document";
 pub const FILE_SAVE_SUFFIX: &str = ".graphite";
+pub const FILE_BACKUP_SUFFIX: &str = ".bak"; // Followed by the number of the backup, counting back from the latest save
+pub const MAX_DOCUMENT_BACKUPS: u32 = 20;
 pub const MAX_UNDO_HISTORY_LEN: usize = 100; // TODO: Add this to user preferences
+pub const DEFAULT_DUPLICATE_OFFSET: f64 = 10.; // Document pixels that a duplicate made with "Duplicate with Offset" is moved by
+pub const DEFAULT_RADIAL_ARRAY_COUNT: u32 = 6;
+pub const EXPORT_PREVIEW_SIZE: f64 = 256.; // Pixels along the longer side of the preview shown in the export dialog
+pub const FILE_THUMBNAIL_SIZE: f64 = 256.; // Pixels along the longer side of the thumbnail embedded in saved documents
+pub const MAX_EXPORT_HISTORY_LEN: usize = 10; // Recent exports remembered by each document, for repeating them
+pub const MAX_REPORTED_ELEMENT_IDS: usize = 10; // Changed element IDs listed after an export tagging layers with their names, before the rest are only counted
 
 pub const VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR: f32 = 1.05;
+pub const MAX_LAYER_RENDER_UPDATES: usize = 64; // When more layers than this change in one render, the whole artwork is resent instead of each layer
@@ -1,5 +1,7 @@
 use crate::consts::{DEFAULT_FONT_FAMILY, DEFAULT_FONT_STYLE};
 use crate::messages::debug::utility_types::MessageLoggingVerbosity;
+use crate::messages::input_mapper::utility_types::misc::MappingContext;
+use crate::messages::preferences::usage_statistics::UsageEvent;
 use crate::messages::prelude::*;
 
 use graphene_core::text::Font;
@@ -15,6 +17,7 @@ pub struct Dispatcher {
 #[derive(Debug, Default)]
 pub struct DispatcherMessageHandlers {
 	broadcast_message_handler: BroadcastMessageHandler,
+	color_picker_message_handler: ColorPickerMessageHandler,
 	debug_message_handler: DebugMessageHandler,
 	dialog_message_handler: DialogMessageHandler,
 	globals_message_handler: GlobalsMessageHandler,
@@ -24,6 +27,7 @@ pub struct DispatcherMessageHandlers {
 	pub portfolio_message_handler: PortfolioMessageHandler,
 	preferences_message_handler: PreferencesMessageHandler,
 	tool_message_handler: ToolMessageHandler,
+	tutorial_message_handler: TutorialMessageHandler,
 	workspace_message_handler: WorkspaceMessageHandler,
 }
 
@@ -42,6 +46,7 @@ const SIDE_EFFECT_FREE_MESSAGES: &[MessageDiscriminant] = &[
 	MessageDiscriminant::Frontend(FrontendMessageDiscriminant::TriggerFontLoad),
 	MessageDiscriminant::Broadcast(BroadcastMessageDiscriminant::TriggerEvent(BroadcastEventDiscriminant::DocumentIsDirty)),
 	MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::InputFrameRasterizeRegionBelowLayer)),
+	MessageDiscriminant::Tool(ToolMessageDiscriminant::RefreshToolStatus),
 ];
 
 impl Dispatcher {
@@ -63,7 +68,13 @@ impl Dispatcher {
 	pub fn handle_message<T: Into<Message>>(&mut self, message: T) {
 		use Message::*;
 
-		self.message_queues.push(VecDeque::from_iter([message.into()]));
+		let message = message.into();
+		if self.message_handlers.debug_message_handler.diagnostics.enabled() && matches!(message, InputPreprocessor(_)) {
+			if let Some(now) = now_milliseconds() {
+				self.message_handlers.debug_message_handler.diagnostics.record_input(now);
+			}
+		}
+		self.message_queues.push(VecDeque::from_iter([message]));
 
 		while let Some(message) = self.message_queues.last_mut().and_then(VecDeque::pop_front) {
 			// Skip processing of this message if it will be processed later (at the end of the shallowest level queue)
@@ -87,6 +98,16 @@ impl Dispatcher {
 			// Create a new queue for the child messages
 			let mut queue = VecDeque::new();
 
+			// Measure how long the message takes to process when recording performance diagnostics
+			let timing_start = self.message_handlers.debug_message_handler.diagnostics.enabled().then(now_milliseconds).flatten();
+			let discriminant = timing_start.map(|_| message.to_discriminant());
+
+			// Note what the message means for the personal usage statistics, if they have been turned on in the preferences
+			let usage_event = self.message_handlers.preferences_message_handler.usage_statistics.is_some().then(|| UsageEvent::of(&message));
+
+			// Note whether the message does what the current step of a running tutorial asks for, so the tutorial moves on once it's done
+			let completes_tutorial_step = self.message_handlers.tutorial_message_handler.completes_step(&message);
+
 			// Process the action by forwarding it to the relevant message handler, or saving the FrontendMessage to be sent to the frontend
 			#[remain::sorted]
 			match message {
@@ -107,6 +128,12 @@ impl Dispatcher {
 				}
 
 				Broadcast(message) => self.message_handlers.broadcast_message_handler.process_message(message, &mut queue, ()),
+				ColorPicker(message) => {
+					let active_tool = self.message_handlers.tool_message_handler.tool_state.tool_data.active_tool_type;
+					let primary_color = self.message_handlers.tool_message_handler.tool_state.document_tool_data.primary_color;
+
+					self.message_handlers.color_picker_message_handler.process_message(message, &mut queue, (active_tool, primary_color));
+				}
 				Debug(message) => {
 					self.message_handlers.debug_message_handler.process_message(message, &mut queue, ());
 				}
@@ -126,6 +153,10 @@ impl Dispatcher {
 						// Return early to avoid running the code after the match block
 						return;
 					} else {
+						if let (FrontendMessage::UpdateDocumentArtwork { .. }, Some(now)) = (&message, timing_start) {
+							self.message_handlers.debug_message_handler.diagnostics.record_frame(now);
+						}
+
 						// `FrontendMessage`s are saved and will be sent to the frontend after the message queue is done being processed
 						self.responses.push(message);
 					}
@@ -140,10 +171,17 @@ impl Dispatcher {
 				}
 				KeyMapping(message) => {
 					let actions = self.collect_actions();
+					let document = self.message_handlers.portfolio_message_handler.active_document();
+					let context = MappingContext {
+						// Tools are only active while a document is open
+						active_tool: document.map(|_| self.message_handlers.tool_message_handler.tool_state.tool_data.active_tool_type),
+						has_selection: document.map_or(false, |document| document.selected_layers().next().is_some()),
+						view_mode: document.map(|document| document.view_mode),
+					};
 
 					self.message_handlers
 						.key_mapping_message_handler
-						.process_message(message, &mut queue, (&self.message_handlers.input_preprocessor_message_handler, actions));
+						.process_message(message, &mut queue, (&self.message_handlers.input_preprocessor_message_handler, actions, context));
 				}
 				Layout(message) => {
 					let action_input_mapping = &|action_to_find: &MessageDiscriminant| self.message_handlers.key_mapping_message_handler.action_input_mapping(action_to_find);
@@ -177,11 +215,35 @@ impl Dispatcher {
 						warn!("Called ToolMessage without an active document.\nGot {:?}", message);
 					}
 				}
+				Tutorial(message) => {
+					self.message_handlers.tutorial_message_handler.process_message(message, &mut queue, ());
+				}
 				Workspace(message) => {
 					self.message_handlers.workspace_message_handler.process_message(message, &mut queue, ());
 				}
 			}
 
+			if completes_tutorial_step {
+				queue.add(TutorialMessage::NextStep);
+			}
+
+			if let (Some(start), Some(discriminant), Some(end)) = (timing_start, discriminant, now_milliseconds()) {
+				self.message_handlers.debug_message_handler.diagnostics.record_message(discriminant, end - start);
+			}
+
+			if let Some(usage_event) = usage_event {
+				let active_tool = self
+					.message_handlers
+					.portfolio_message_handler
+					.active_document()
+					.map(|_| self.message_handlers.tool_message_handler.tool_state.tool_data.active_tool_type);
+				let preferences = &mut self.message_handlers.preferences_message_handler;
+				let save = preferences.usage_statistics.as_mut().map_or(false, |statistics| statistics.record(usage_event, &queue, active_tool));
+				if save {
+					queue.add(FrontendMessage::TriggerSavePreferences { preferences: preferences.clone() });
+				}
+			}
+
 			// If there are child messages, append the queue to the list of queues
 			if !queue.is_empty() {
 				self.message_queues.push(queue);
@@ -198,11 +260,21 @@ impl Dispatcher {
 		list.extend(self.message_handlers.input_preprocessor_message_handler.actions());
 		list.extend(self.message_handlers.key_mapping_message_handler.actions());
 		list.extend(self.message_handlers.debug_message_handler.actions());
-		if self.message_handlers.portfolio_message_handler.active_document().is_some() {
+		list.extend(self.message_handlers.workspace_message_handler.actions());
+		// Tools are only active while a document is open, and not while it is being presented
+		if self
+			.message_handlers
+			.portfolio_message_handler
+			.active_document()
+			.map_or(false, |document| !document.presentation_handler.is_presenting())
+		{
 			list.extend(self.message_handlers.tool_message_handler.actions());
 		}
 		list.extend(self.message_handlers.portfolio_message_handler.actions());
-		list
+		list.extend(self.message_handlers.tutorial_message_handler.actions());
+
+		// A running tutorial only lets through the actions its current step needs
+		self.message_handlers.tutorial_message_handler.filter_actions(list)
 	}
 
 	pub fn poll_node_graph_evaluation(&mut self, responses: &mut VecDeque<Message>) {
@@ -545,6 +617,201 @@ mod test {
 		assert_eq!(all, non_selected.into_iter().chain(selected).collect::<Vec<_>>());
 	}
 
+	#[test]
+	/// - pan and zoom the canvas, which leaves the document unmodified
+	/// - draw a rectangle, which modifies the document
+	/// - save, which clears the modified state
+	fn modified_state_ignores_view_changes() {
+		init_logger();
+		let mut editor = Editor::create();
+		editor.new_document();
+		let is_saved = |editor: &Editor| editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap().is_saved();
+		assert!(is_saved(&editor));
+
+		editor.handle_message(NavigationMessage::TranslateCanvas { delta: (50., 30.).into() });
+		editor.handle_message(NavigationMessage::IncreaseCanvasZoom { center_on_mouse: false });
+		assert!(is_saved(&editor), "Changing the view should not modify the document");
+
+		editor.draw_rect(100., 200., 300., 400.);
+		assert!(!is_saved(&editor), "Drawing should modify the document");
+
+		editor.handle_message(DocumentMessage::SaveDocument);
+		assert!(is_saved(&editor));
+	}
+
+	#[test]
+	/// - change the preference for how deep folders may be nested while a document is open
+	/// - assert that the open document and the next new one both follow it
+	fn max_folder_depth_preference_applies_to_documents() {
+		init_logger();
+		let mut editor = Editor::create();
+		editor.new_document();
+		let max_folder_depth = |editor: &Editor| editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap().document_legacy.max_folder_depth;
+
+		editor.handle_message(PreferencesMessage::MaxFolderDepth { depth: 3 });
+		assert_eq!(max_folder_depth(&editor), 3);
+
+		editor.new_document();
+		assert_eq!(max_folder_depth(&editor), 3);
+	}
+
+	#[test]
+	#[cfg_attr(miri, ignore)]
+	/// - save a document with a vector layer made by the node graph, whose output isn't saved with it
+	/// - batch export the document
+	/// - assert that the export waits for the node graph to draw the layer
+	fn batch_export_waits_for_vector_layers() {
+		use crate::messages::frontend::utility_types::FileType;
+		use crate::messages::portfolio::batch::{BatchSource, BatchStep};
+		use crate::messages::portfolio::document::node_graph::new_vector_network;
+		use crate::messages::portfolio::document::utility_types::layer_panel::LayerMetadata;
+
+		use bezier_rs::Subpath;
+		use glam::{DAffine2, DVec2};
+
+		init_logger();
+		let mut editor = Editor::create();
+
+		let mut document = DocumentMessageHandler::default();
+		let add_frame = Operation::AddFrame {
+			path: vec![1],
+			insert_index: -1,
+			transform: DAffine2::from_scale(DVec2::splat(100.)).to_cols_array(),
+			network: new_vector_network(vec![Subpath::new_rect(DVec2::ZERO, DVec2::ONE)]),
+		};
+		document.document_legacy.handle_operation(add_frame).unwrap();
+		document.layer_metadata.insert(vec![1], LayerMetadata::new(false));
+
+		let source = BatchSource::Content {
+			name: "Rectangle.graphite".to_string(),
+			content: document.serialize_document(),
+		};
+		let step = BatchStep::Export {
+			file_type: FileType::Svg,
+			scale_factor: 1.,
+			transparent_background: true,
+		};
+		let mut frontend_messages = editor.handle_message(PortfolioMessage::ProcessBatch {
+			sources: vec![source],
+			steps: vec![step],
+		});
+		let exported = |messages: &[FrontendMessage]| {
+			messages.iter().find_map(|message| match message {
+				FrontendMessage::TriggerDownloadTextFile { document, .. } => Some(document.clone()),
+				_ => None,
+			})
+		};
+		assert!(exported(&frontend_messages).is_none(), "The export should wait for the layer to be evaluated");
+
+		// The frontend runs the node graph and polls for its output every frame, and the export is rendered on another thread
+		for _ in 0..100 {
+			if exported(&frontend_messages).is_some() {
+				break;
+			}
+			futures::executor::block_on(crate::node_graph_executor::run_node_graph());
+			let mut responses = VecDeque::new();
+			editor.poll_node_graph_evaluation(&mut responses);
+			for response in responses {
+				frontend_messages.extend(editor.handle_message(response));
+			}
+			std::thread::sleep(std::time::Duration::from_millis(10));
+		}
+
+		let svg = exported(&frontend_messages).expect("The document should be exported");
+		assert!(svg.contains("<path"), "The export should include the rectangle drawn by the node graph");
+	}
+
+	#[test]
+	/// - save a document, and open the saved file twice, as two windows would
+	/// - assert that both are locked under the same file ID, although they are open as different documents
+	/// - assert the same of a file saved before documents were given a file ID
+	fn same_file_opened_twice_is_locked_under_one_id() {
+		init_logger();
+		let mut editor = Editor::create();
+		editor.new_document();
+		editor.draw_rect(100., 200., 300., 400.);
+		let document = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap();
+		let saved_file_id = document.file_id;
+		let saved = document.serialize_document();
+
+		let mut without_file_id = serde_json::from_str::<serde_json::Value>(&saved).unwrap();
+		without_file_id.as_object_mut().unwrap().remove("file_id");
+		let older_file = without_file_id.to_string();
+
+		let open_in_new_window = |content: &str| {
+			let mut editor = Editor::create();
+			let responses = editor.handle_message(PortfolioMessage::OpenDocumentFile {
+				document_name: "Saved".into(),
+				document_serialized_content: content.to_string(),
+			});
+			responses
+				.into_iter()
+				.find_map(|response| match response {
+					FrontendMessage::TriggerAcquireDocumentLock { document_id, file_id, .. } => Some((document_id, file_id)),
+					_ => None,
+				})
+				.expect("Opening a document should lock its file")
+		};
+
+		let (first_document, first_file) = open_in_new_window(&saved);
+		let (second_document, second_file) = open_in_new_window(&saved);
+		assert_ne!(first_document, second_document);
+		assert_eq!(first_file, saved_file_id);
+		assert_eq!(second_file, saved_file_id);
+
+		let (_, first_older_file) = open_in_new_window(&older_file);
+		let (_, second_older_file) = open_in_new_window(&older_file);
+		assert_eq!(first_older_file, second_older_file);
+
+		editor.new_document();
+		let new_file_id = editor.dispatcher.message_handlers.portfolio_message_handler.active_document().unwrap().file_id;
+		assert_ne!(new_file_id, saved_file_id, "A new document is a different file");
+	}
+
+	#[test]
+	/// - nest folders as deep as they may be, with a vector layer made by the node graph in the deepest one
+	/// - log the edit adding them, which nests them deeper still in the saved document
+	/// - assert that the document can be saved and opened again
+	fn deepest_folders_can_be_saved_and_opened() {
+		use crate::messages::portfolio::document::node_graph::new_vector_network;
+		use crate::messages::portfolio::document::utility_types::operation_log::LoggedEdit;
+		use document_legacy::consts::MAX_FOLDER_DEPTH;
+		use document_legacy::document::Document as DocumentLegacy;
+
+		use bezier_rs::Subpath;
+		use glam::{DAffine2, DVec2};
+
+		let mut folders = DocumentLegacy::default();
+		let mut path = Vec::new();
+		for id in 0..MAX_FOLDER_DEPTH as LayerId {
+			path.push(id);
+			folders.handle_operation(Operation::CreateFolder { path: path.clone(), insert_index: -1 }).unwrap();
+		}
+		path.push(MAX_FOLDER_DEPTH as LayerId);
+		let add_frame = Operation::AddFrame {
+			path: path.clone(),
+			insert_index: -1,
+			transform: DAffine2::IDENTITY.to_cols_array(),
+			network: new_vector_network(vec![Subpath::new_rect(DVec2::ZERO, DVec2::ONE)]),
+		};
+		folders.handle_operation(add_frame).unwrap();
+
+		let mut document = DocumentMessageHandler::default();
+		let insert_layer = Operation::InsertLayer {
+			layer: Box::new(folders.layer(&[0]).unwrap().clone()),
+			destination_path: vec![0],
+			insert_index: -1,
+			duplicating: false,
+		};
+		assert!(document.operation_log.is_logging());
+		document.document_legacy.handle_operation(insert_layer.clone()).unwrap();
+		document.operation_log.record(LoggedEdit::Operation(insert_layer));
+
+		let reopened = DocumentMessageHandler::deserialize_document(&document.serialize_document()).expect("The document should open again");
+		assert!(reopened.document_legacy.layer(&path).is_ok());
+		assert_eq!(reopened.operation_log.len(), 1);
+	}
+
 	#[test]
 	/// If this test is failing take a look at `GRAPHITE_DOCUMENT_VERSION` in `editor/src/consts.rs`, it may need to be updated.
 	/// This test will fail when you make changes to the underlying serialization format for a document.
@@ -6,6 +6,9 @@ use serde::{Deserialize, Serialize};
 #[impl_message(Message, BroadcastMessage, TriggerEvent)]
 pub enum BroadcastEvent {
 	DocumentIsDirty,
+	ModifierKeysChanged,
+	PointerEnterViewport,
+	PointerLeaveViewport,
 	ToolAbort,
 	SelectionChanged,
 	WorkingColorChanged,
@@ -0,0 +1,27 @@
+use super::harmony::HarmonyRule;
+use super::utility_types::ColorPickerTarget;
+use crate::messages::prelude::*;
+
+use graphene_core::raster::color::Color;
+
+use serde::{Deserialize, Serialize};
+
+#[remain::sorted]
+#[impl_message(Message, ColorPicker)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum ColorPickerMessage {
+	// Messages
+	AddToPalette,
+	CancelSampling,
+	Close,
+	FinishSampling { color: Color },
+	GenerateHarmonyPalette { rule: HarmonyRule, hue_spread: f64, lightness_steps: u32 },
+	Open { target: ColorPickerTarget, color: Option<Color> },
+	RemoveFromPalette { index: usize },
+	SelectPalette { index: usize },
+	SelectPaletteColor { index: usize },
+	SelectRecentColor { index: usize },
+	SetColor { color: Option<Color> },
+	StartSampling,
+	SwapWithPrevious,
+}
@@ -0,0 +1,183 @@
+use super::harmony::harmony_palette;
+use super::utility_types::{ColorPalette, ColorPickerState, ColorPickerTarget};
+use crate::consts::RECENT_COLORS_CAPACITY;
+use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ToolType;
+
+use graphene_core::raster::color::Color;
+
+/// Holds the color picker's state in the editor so it is shared by every color input and survives switching tools.
+#[derive(Debug, Clone)]
+pub struct ColorPickerMessageHandler {
+	target: Option<ColorPickerTarget>,
+	current: Option<Color>,
+	previous: Option<Color>,
+	recent_colors: VecDeque<Color>,
+	palettes: Vec<ColorPalette>,
+	active_palette: usize,
+	/// The tool to return to once the Eyedropper tool has sampled a color for the picker.
+	sampling_from: Option<ToolType>,
+}
+
+impl Default for ColorPickerMessageHandler {
+	fn default() -> Self {
+		Self {
+			target: None,
+			current: None,
+			previous: None,
+			recent_colors: VecDeque::new(),
+			palettes: ColorPalette::defaults(),
+			active_palette: 0,
+			sampling_from: None,
+		}
+	}
+}
+
+impl MessageHandler<ColorPickerMessage, (ToolType, Color)> for ColorPickerMessageHandler {
+	#[remain::check]
+	fn process_message(&mut self, message: ColorPickerMessage, responses: &mut VecDeque<Message>, (active_tool, primary_color): (ToolType, Color)) {
+		use ColorPickerMessage::*;
+
+		#[remain::sorted]
+		match message {
+			// Messages
+			AddToPalette => {
+				let Some(color) = self.current else { return };
+				let Some(palette) = self.palettes.get_mut(self.active_palette) else { return };
+
+				if !palette.colors.contains(&color) {
+					palette.colors.push(color);
+				}
+			}
+			CancelSampling => {
+				// The Eyedropper tool was aborted, possibly by switching to another tool, so the user is left with whichever tool is now active
+				self.sampling_from = None;
+			}
+			Close => {
+				// Clicking the canvas to sample a color dismisses the frontend's picker, but the picker stays open here to receive the color
+				if self.sampling_from.is_some() {
+					return;
+				}
+				if self.target.take().is_none() {
+					return;
+				}
+
+				if let Some(color) = self.current.filter(|_| self.current != self.previous) {
+					self.recent_colors.retain(|&recent| recent != color);
+					self.recent_colors.push_front(color);
+					self.recent_colors.truncate(RECENT_COLORS_CAPACITY);
+				}
+			}
+			FinishSampling { color } => {
+				let Some(tool_type) = self.sampling_from.take() else { return };
+
+				self.set_color(Some(color), responses);
+				responses.add(ToolMessage::ActivateTool { tool_type });
+			}
+			GenerateHarmonyPalette { rule, hue_spread, lightness_steps } => {
+				let palette = harmony_palette(primary_color, rule, hue_spread, lightness_steps);
+
+				// Generating the same kind of harmony again replaces the last one, rather than piling up palettes
+				match self.palettes.iter().position(|existing| existing.name == palette.name) {
+					Some(index) => {
+						self.palettes[index] = palette;
+						self.active_palette = index;
+					}
+					None => {
+						self.palettes.push(palette);
+						self.active_palette = self.palettes.len() - 1;
+					}
+				}
+			}
+			Open { target, color } => {
+				self.sampling_from = None;
+
+				// Reopening the picker for the same target, like after sampling with the Eyedropper tool, carries on where it left off
+				if self.target != Some(target) {
+					self.target = Some(target);
+					self.current = color;
+					self.previous = color;
+				}
+			}
+			RemoveFromPalette { index } => {
+				let Some(palette) = self.palettes.get_mut(self.active_palette) else { return };
+
+				if index < palette.colors.len() {
+					palette.colors.remove(index);
+				}
+			}
+			SelectPalette { index } => {
+				if index < self.palettes.len() {
+					self.active_palette = index;
+				}
+			}
+			SelectPaletteColor { index } => {
+				let Some(&color) = self.palettes.get(self.active_palette).and_then(|palette| palette.colors.get(index)) else {
+					return;
+				};
+
+				self.set_color(Some(color), responses);
+			}
+			SelectRecentColor { index } => {
+				let Some(&color) = self.recent_colors.get(index) else { return };
+
+				self.set_color(Some(color), responses);
+			}
+			SetColor { color } => {
+				self.set_color(color, responses);
+			}
+			StartSampling => {
+				if self.target.is_none() || self.sampling_from.is_some() {
+					return;
+				}
+				self.sampling_from = Some(active_tool);
+
+				responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Eyedropper });
+				responses.add(EyedropperToolMessage::SampleForColorPicker);
+			}
+			SwapWithPrevious => {
+				let previous = self.previous;
+				self.previous = self.current;
+				self.set_color(previous, responses);
+			}
+		}
+
+		responses.add(FrontendMessage::UpdateColorPicker { state: self.state() });
+	}
+
+	advertise_actions!(ColorPickerMessageDiscriminant;
+	);
+}
+
+impl ColorPickerMessageHandler {
+	fn state(&self) -> ColorPickerState {
+		ColorPickerState {
+			target: self.target,
+			current: self.current,
+			previous: self.previous,
+			recent_colors: self.recent_colors.iter().copied().collect(),
+			palettes: self.palettes.clone(),
+			active_palette: self.active_palette,
+			sampling: self.sampling_from.is_some(),
+		}
+	}
+
+	/// Chooses a new color and applies it to whatever the picker is open for.
+	fn set_color(&mut self, color: Option<Color>, responses: &mut VecDeque<Message>) {
+		let Some(target) = self.target else { return };
+		self.current = color;
+
+		match target {
+			// The working colors can't be set to no color
+			ColorPickerTarget::PrimaryWorkingColor => responses.try_add(color.map(|color| ToolMessage::SelectPrimaryColor { color })),
+			ColorPickerTarget::SecondaryWorkingColor => responses.try_add(color.map(|color| ToolMessage::SelectSecondaryColor { color })),
+			ColorPickerTarget::Widget { layout_target, widget_id } => {
+				let value = match color {
+					Some(color) => serde_json::json!({ "none": false, "red": color.r(), "green": color.g(), "blue": color.b(), "alpha": color.a() }),
+					None => serde_json::json!({ "none": true }),
+				};
+				responses.add(LayoutMessage::UpdateLayout { layout_target, widget_id, value });
+			}
+		}
+	}
+}
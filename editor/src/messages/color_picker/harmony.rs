@@ -0,0 +1,202 @@
+//! Color harmonies: palettes whose hues are related by a rule from color theory, like being opposite each other on the color wheel.
+//!
+//! The hues are turned in the Oklch color space, where lightness and chroma are perceptual, so every generated color looks as light and
+//! as colorful as the base color it came from. Plain HSL would instead turn a yellow into a much darker looking blue of the same lightness.
+
+use super::utility_types::ColorPalette;
+
+use graphene_core::raster::color::Color;
+
+use serde::{Deserialize, Serialize};
+
+/// How far apart the lightness of neighboring shades in a harmony palette is, on Oklch's lightness scale from 0 to 1.
+const HARMONY_LIGHTNESS_STEP: f64 = 0.12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum HarmonyRule {
+	/// The base hue and the one opposite it.
+	Complementary,
+	/// The base hue between its neighbors on either side.
+	Analogous,
+	/// Three hues evenly spaced around the color wheel.
+	Triadic,
+	/// Two pairs of complementary hues.
+	Tetradic,
+}
+
+impl HarmonyRule {
+	pub fn name(self) -> &'static str {
+		match self {
+			HarmonyRule::Complementary => "Complementary",
+			HarmonyRule::Analogous => "Analogous",
+			HarmonyRule::Triadic => "Triadic",
+			HarmonyRule::Tetradic => "Tetradic",
+		}
+	}
+
+	/// The hues of the harmony, in degrees from the base hue. The spread is how far the neighbors of analogous harmonies are from the base hue,
+	/// and how far the second complementary pair of tetradic harmonies is from the first. The other rules have fixed angles.
+	pub fn hue_offsets(self, spread: f64) -> Vec<f64> {
+		match self {
+			HarmonyRule::Complementary => vec![0., 180.],
+			HarmonyRule::Analogous => vec![-spread, 0., spread],
+			HarmonyRule::Triadic => vec![0., 120., 240.],
+			HarmonyRule::Tetradic => vec![0., spread, 180., 180. + spread],
+		}
+	}
+}
+
+/// A color in the Oklch color space, with a perceptual lightness from 0 to 1, a chroma (colorfulness) from 0 up to about 0.37 for the most
+/// saturated sRGB colors, and a hue in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Oklch {
+	pub lightness: f64,
+	pub chroma: f64,
+	pub hue: f64,
+	pub alpha: f64,
+}
+
+impl Oklch {
+	/// Converts from an sRGB color, with gamma encoded channels like those chosen in the color picker.
+	pub fn from_color(color: Color) -> Self {
+		let linear = color.to_linear_srgb();
+		let [red, green, blue] = [linear.r(), linear.g(), linear.b()].map(f64::from);
+
+		let l = (0.4122214708 * red + 0.5363325363 * green + 0.0514459929 * blue).cbrt();
+		let m = (0.2119034982 * red + 0.6806995451 * green + 0.1073969566 * blue).cbrt();
+		let s = (0.0883024619 * red + 0.2817188376 * green + 0.6299787005 * blue).cbrt();
+
+		let lightness = 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s;
+		let a = 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s;
+		let b = 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s;
+
+		Self {
+			lightness,
+			chroma: a.hypot(b),
+			hue: b.atan2(a).to_degrees().rem_euclid(360.),
+			alpha: color.a() as f64,
+		}
+	}
+
+	/// Converts to an sRGB color. Colors outside of the sRGB gamut have their chroma reduced until they fit, keeping their lightness and hue.
+	pub fn to_color(self) -> Color {
+		let lightness = self.lightness.clamp(0., 1.);
+
+		let [red, green, blue] = match self.linear_srgb(lightness, self.chroma) {
+			Some(channels) => channels,
+			None => {
+				// Bisect the chroma between a gray, which is always in gamut, and the requested chroma
+				let (mut inside, mut outside) = (0., self.chroma);
+				for _ in 0..24 {
+					let chroma = (inside + outside) / 2.;
+					match self.linear_srgb(lightness, chroma) {
+						Some(_) => inside = chroma,
+						None => outside = chroma,
+					}
+				}
+				self.linear_srgb(lightness, inside).unwrap_or([lightness.powi(3); 3])
+			}
+		};
+
+		let [red, green, blue] = [red, green, blue].map(|channel| channel.clamp(0., 1.) as f32);
+		Color::from_rgbaf32_unchecked(red, green, blue, self.alpha.clamp(0., 1.) as f32).to_gamma_srgb()
+	}
+
+	/// The linear sRGB channels of this hue at the given lightness and chroma, if they are in gamut.
+	fn linear_srgb(&self, lightness: f64, chroma: f64) -> Option<[f64; 3]> {
+		let (a, b) = (chroma * self.hue.to_radians().cos(), chroma * self.hue.to_radians().sin());
+
+		let l = (lightness + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+		let m = (lightness - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+		let s = (lightness - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+		let channels = [
+			4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+			-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+			-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+		];
+
+		// A little leeway keeps rounding errors from pushing colors at the edge of the gamut out of it
+		const EPSILON: f64 = 1e-4;
+		channels.iter().all(|channel| (-EPSILON..=1. + EPSILON).contains(channel)).then_some(channels)
+	}
+}
+
+/// A palette of the harmony's hues around the base color, each as a ramp of lighter and darker shades.
+/// There are `lightness_steps` shades darker and lighter than the base color's lightness, as long as they don't go past black or white.
+pub fn harmony_palette(base: Color, rule: HarmonyRule, hue_spread: f64, lightness_steps: u32) -> ColorPalette {
+	let base = Oklch::from_color(base);
+	let steps = lightness_steps as i32;
+
+	let mut colors = Vec::new();
+	for offset in rule.hue_offsets(hue_spread) {
+		for step in -steps..=steps {
+			let lightness = base.lightness + step as f64 * HARMONY_LIGHTNESS_STEP;
+			if !(0. ..=1.).contains(&lightness) {
+				continue;
+			}
+
+			let color = Oklch {
+				lightness,
+				hue: (base.hue + offset).rem_euclid(360.),
+				..base
+			}
+			.to_color();
+			if !colors.contains(&color) {
+				colors.push(color);
+			}
+		}
+	}
+
+	ColorPalette {
+		name: format!("{} Harmony", rule.name()),
+		colors,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn assert_close(a: f64, b: f64, tolerance: f64) {
+		assert!((a - b).abs() <= tolerance, "{a} is not within {tolerance} of {b}");
+	}
+
+	#[test]
+	fn oklch_round_trips_srgb_colors() {
+		for color in [Color::from_rgbf32_unchecked(0.8, 0.2, 0.1), Color::from_rgbf32_unchecked(0.1, 0.5, 0.9), Color::WHITE, Color::BLACK] {
+			let round_trip = Oklch::from_color(color).to_color();
+			assert_close(round_trip.r() as f64, color.r() as f64, 1e-3);
+			assert_close(round_trip.g() as f64, color.g() as f64, 1e-3);
+			assert_close(round_trip.b() as f64, color.b() as f64, 1e-3);
+		}
+	}
+
+	#[test]
+	fn white_has_full_lightness_and_no_chroma() {
+		let white = Oklch::from_color(Color::WHITE);
+		assert_close(white.lightness, 1., 1e-3);
+		assert_close(white.chroma, 0., 1e-3);
+	}
+
+	#[test]
+	fn complementary_hues_are_opposite_with_the_same_lightness() {
+		let base = Color::from_rgbf32_unchecked(0.2, 0.4, 0.8);
+		let palette = harmony_palette(base, HarmonyRule::Complementary, 30., 0);
+		assert_eq!(palette.colors.len(), 2);
+
+		let (first, second) = (Oklch::from_color(palette.colors[0]), Oklch::from_color(palette.colors[1]));
+		assert_close(first.lightness, second.lightness, 1e-2);
+		assert_close((second.hue - first.hue).rem_euclid(360.), 180., 1.);
+	}
+
+	#[test]
+	fn lightness_steps_ramp_each_hue() {
+		let base = Color::from_rgbf32_unchecked(0.5, 0.45, 0.4);
+		let palette = harmony_palette(base, HarmonyRule::Triadic, 30., 1);
+		assert_eq!(palette.colors.len(), 9);
+
+		let ramp = palette.colors[..3].iter().map(|&color| Oklch::from_color(color).lightness).collect::<Vec<_>>();
+		assert!(ramp[0] < ramp[1] && ramp[1] < ramp[2]);
+	}
+}
@@ -0,0 +1,10 @@
+mod color_picker_message;
+mod color_picker_message_handler;
+
+pub mod harmony;
+pub mod utility_types;
+
+#[doc(inline)]
+pub use color_picker_message::{ColorPickerMessage, ColorPickerMessageDiscriminant};
+#[doc(inline)]
+pub use color_picker_message_handler::ColorPickerMessageHandler;
@@ -0,0 +1,68 @@
+use crate::messages::layout::utility_types::layout_widget::LayoutTarget;
+
+use graphene_core::raster::color::Color;
+
+use serde::{Deserialize, Serialize};
+
+/// What the color chosen in the color picker is applied to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum ColorPickerTarget {
+	PrimaryWorkingColor,
+	SecondaryWorkingColor,
+	/// A color input widget, which is updated just as if the user had edited its value directly
+	Widget {
+		#[serde(rename = "layoutTarget")]
+		layout_target: LayoutTarget,
+		#[serde(rename = "widgetId")]
+		widget_id: u64,
+	},
+}
+
+/// A named set of colors kept at hand in the color picker.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ColorPalette {
+	pub name: String,
+	pub colors: Vec<Color>,
+}
+
+impl ColorPalette {
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			colors: Vec::new(),
+		}
+	}
+
+	/// The built-in palettes: a fixed set of grays and pure hues, and an empty one for the user's own colors.
+	pub fn defaults() -> Vec<Self> {
+		let grays = [0., 0.25, 0.5, 0.75, 1.].map(|value| Color::from_rgbf32_unchecked(value, value, value));
+		let hues = [(1., 0., 0.), (1., 1., 0.), (0., 1., 0.), (0., 1., 1.), (0., 0., 1.), (1., 0., 1.)].map(|(r, g, b)| Color::from_rgbf32_unchecked(r, g, b));
+
+		vec![
+			Self {
+				name: "Basic".into(),
+				colors: grays.into_iter().chain(hues).collect(),
+			},
+			Self::new("Custom"),
+		]
+	}
+}
+
+/// Everything the frontend needs to draw the color picker, which keeps no color state of its own beyond what it is displaying.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ColorPickerState {
+	/// What the picker is open for, if it is open.
+	pub target: Option<ColorPickerTarget>,
+	/// The color being chosen, or `None` for no color.
+	pub current: Option<Color>,
+	/// The color from when the picker was opened, for comparison and to swap back to.
+	pub previous: Option<Color>,
+	/// The colors most recently chosen in the picker, newest first.
+	#[serde(rename = "recentColors")]
+	pub recent_colors: Vec<Color>,
+	pub palettes: Vec<ColorPalette>,
+	#[serde(rename = "activePalette")]
+	pub active_palette: usize,
+	/// Whether the Eyedropper tool is picking a color from the canvas on behalf of the picker.
+	pub sampling: bool,
+}
@@ -9,4 +9,6 @@ pub enum DebugMessage {
 	MessageOff,
 	MessageNames,
 	MessageContents,
+	ReportDiagnostics,
+	ToggleDiagnostics,
 }
@@ -1,9 +1,10 @@
-use super::utility_types::MessageLoggingVerbosity;
+use super::utility_types::{DiagnosticsRecorder, MessageLoggingVerbosity};
 use crate::messages::prelude::*;
 
 #[derive(Debug, Default)]
 pub struct DebugMessageHandler {
 	pub message_logging_verbosity: MessageLoggingVerbosity,
+	pub diagnostics: DiagnosticsRecorder,
 }
 
 impl MessageHandler<DebugMessage, ()> for DebugMessageHandler {
@@ -38,6 +39,23 @@ impl MessageHandler<DebugMessage, ()> for DebugMessageHandler {
 				// Refresh the checkmark beside the menu entry for this
 				responses.add(MenuBarMessage::SendLayout);
 			}
+			DebugMessage::ReportDiagnostics => {
+				let diagnostics = self.diagnostics.report();
+				let Ok(copy_text) = serde_json::to_string_pretty(&diagnostics) else { return };
+				info!("Performance diagnostics:\n{copy_text}");
+
+				// Copy the report so it can be pasted into a bug report
+				responses.add(FrontendMessage::TriggerTextCopy { copy_text });
+				responses.add(FrontendMessage::UpdateDiagnostics { diagnostics });
+			}
+			DebugMessage::ToggleDiagnostics => {
+				if now_milliseconds().is_none() {
+					warn!("Performance diagnostics can't be recorded because the platform didn't provide a clock");
+					return;
+				}
+				self.diagnostics.set_enabled(!self.diagnostics.enabled());
+				info!("Performance diagnostics recording {}", if self.diagnostics.enabled() { "started" } else { "stopped" });
+			}
 		}
 	}
 
@@ -46,5 +64,7 @@ impl MessageHandler<DebugMessage, ()> for DebugMessageHandler {
 		MessageOff,
 		MessageNames,
 		MessageContents,
+		ReportDiagnostics,
+		ToggleDiagnostics,
 	);
 }
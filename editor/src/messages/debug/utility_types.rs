@@ -1,3 +1,12 @@
+use crate::messages::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+/// How many of the most recent input latency and frame interval samples are kept for computing percentiles.
+const DIAGNOSTICS_SAMPLE_WINDOW: usize = 600;
+/// How many of the most expensive message types are listed in a report.
+const DIAGNOSTICS_REPORTED_MESSAGES: usize = 25;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub enum MessageLoggingVerbosity {
 	#[default]
@@ -5,3 +14,169 @@ pub enum MessageLoggingVerbosity {
 	Names,
 	Contents,
 }
+
+/// Accumulated processing time of one type of message.
+#[derive(Debug, Default, Clone, Copy)]
+struct MessageTiming {
+	count: u64,
+	total: f64,
+	max: f64,
+}
+
+/// Records performance measurements while enabled, so they can be summarized in a [DiagnosticsReport] for bug reports.
+/// All times are in milliseconds, read from the clock provided to [GLOBAL_CLOCK] by the platform.
+#[derive(Debug, Default)]
+pub struct DiagnosticsRecorder {
+	enabled: bool,
+	message_timings: HashMap<MessageDiscriminant, MessageTiming>,
+	input_latencies: VecDeque<f64>,
+	frame_intervals: VecDeque<f64>,
+	/// When the oldest input event still waiting for its effect to be rendered arrived.
+	pending_input: Option<f64>,
+	last_frame: Option<f64>,
+	started: f64,
+	stopped: Option<f64>,
+}
+
+impl DiagnosticsRecorder {
+	pub fn enabled(&self) -> bool {
+		self.enabled
+	}
+
+	/// Starts recording from scratch, or stops recording while keeping the measurements for reporting.
+	pub fn set_enabled(&mut self, enabled: bool) {
+		if enabled {
+			*self = Self {
+				enabled: true,
+				started: now_milliseconds().unwrap_or_default(),
+				..Default::default()
+			};
+		} else if self.enabled {
+			self.enabled = false;
+			self.stopped = now_milliseconds();
+		}
+	}
+
+	/// Adds the time spent by a message's handler, not including the child messages it queued up.
+	pub fn record_message(&mut self, discriminant: MessageDiscriminant, duration: f64) {
+		let timing = self.message_timings.entry(discriminant).or_default();
+		timing.count += 1;
+		timing.total += duration;
+		timing.max = timing.max.max(duration);
+	}
+
+	/// Notes the arrival of an input event, whose latency is measured until the next time the artwork is rendered.
+	pub fn record_input(&mut self, time: f64) {
+		self.pending_input.get_or_insert(time);
+	}
+
+	/// Notes that new artwork was sent to the frontend, finishing the latency measurement of any pending input.
+	pub fn record_frame(&mut self, time: f64) {
+		if let Some(input) = self.pending_input.take() {
+			push_sample(&mut self.input_latencies, time - input);
+		}
+		if let Some(last_frame) = self.last_frame.replace(time) {
+			push_sample(&mut self.frame_intervals, time - last_frame);
+		}
+	}
+
+	pub fn report(&self) -> DiagnosticsReport {
+		let mut messages: Vec<_> = self
+			.message_timings
+			.iter()
+			.map(|(discriminant, timing)| MessageTimingReport {
+				name: format!("{discriminant:?}"),
+				count: timing.count,
+				total_milliseconds: timing.total,
+				mean_milliseconds: timing.total / timing.count as f64,
+				max_milliseconds: timing.max,
+			})
+			.collect();
+		messages.sort_by(|a, b| b.total_milliseconds.total_cmp(&a.total_milliseconds));
+		messages.truncate(DIAGNOSTICS_REPORTED_MESSAGES);
+
+		let frame_intervals = SampleSummary::new(&self.frame_intervals);
+		let frames_per_second = if frame_intervals.mean_milliseconds > 0. { 1000. / frame_intervals.mean_milliseconds } else { 0. };
+
+		DiagnosticsReport {
+			recording: self.enabled,
+			duration_milliseconds: self.stopped.or_else(now_milliseconds).map_or(0., |end| end - self.started),
+			messages,
+			input_latency: SampleSummary::new(&self.input_latencies),
+			frame_intervals,
+			frames_per_second,
+		}
+	}
+}
+
+fn push_sample(samples: &mut VecDeque<f64>, sample: f64) {
+	if samples.len() == DIAGNOSTICS_SAMPLE_WINDOW {
+		samples.pop_front();
+	}
+	samples.push_back(sample);
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct MessageTimingReport {
+	pub name: String,
+	pub count: u64,
+	#[serde(rename = "totalMilliseconds")]
+	pub total_milliseconds: f64,
+	#[serde(rename = "meanMilliseconds")]
+	pub mean_milliseconds: f64,
+	#[serde(rename = "maxMilliseconds")]
+	pub max_milliseconds: f64,
+}
+
+/// The distribution of a set of recent timing samples.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct SampleSummary {
+	pub count: usize,
+	#[serde(rename = "meanMilliseconds")]
+	pub mean_milliseconds: f64,
+	#[serde(rename = "medianMilliseconds")]
+	pub median_milliseconds: f64,
+	#[serde(rename = "p95Milliseconds")]
+	pub p95_milliseconds: f64,
+	#[serde(rename = "maxMilliseconds")]
+	pub max_milliseconds: f64,
+}
+
+impl SampleSummary {
+	fn new(samples: &VecDeque<f64>) -> Self {
+		if samples.is_empty() {
+			return Self::default();
+		}
+
+		let mut sorted: Vec<f64> = samples.iter().copied().collect();
+		sorted.sort_by(|a, b| a.total_cmp(b));
+		let percentile = |fraction: f64| sorted[((sorted.len() - 1) as f64 * fraction).round() as usize];
+
+		Self {
+			count: sorted.len(),
+			mean_milliseconds: sorted.iter().sum::<f64>() / sorted.len() as f64,
+			median_milliseconds: percentile(0.5),
+			p95_milliseconds: percentile(0.95),
+			max_milliseconds: sorted[sorted.len() - 1],
+		}
+	}
+}
+
+/// A summary of the editor's responsiveness since diagnostics recording began, meant to be attached to bug reports about performance.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct DiagnosticsReport {
+	/// Whether measurements are still being recorded.
+	pub recording: bool,
+	#[serde(rename = "durationMilliseconds")]
+	pub duration_milliseconds: f64,
+	/// The message types that took the most time in total to process, most expensive first.
+	pub messages: Vec<MessageTimingReport>,
+	/// Time from an input event arriving to the resulting artwork being sent to the frontend.
+	#[serde(rename = "inputLatency")]
+	pub input_latency: SampleSummary,
+	/// Time between consecutive artwork updates sent to the frontend.
+	#[serde(rename = "frameIntervals")]
+	pub frame_intervals: SampleSummary,
+	#[serde(rename = "framesPerSecond")]
+	pub frames_per_second: f64,
+}
@@ -0,0 +1,27 @@
+use crate::messages::frontend::utility_types::FileType;
+use crate::messages::portfolio::batch::{BatchResult, BatchSource};
+use crate::messages::prelude::*;
+
+use graphene_core::Color;
+
+use serde::{Deserialize, Serialize};
+
+#[impl_message(Message, DialogMessage, BatchDialog)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum BatchDialogMessage {
+	Export(bool),
+	ExportFileType(FileType),
+	ExportScaleFactor(f64),
+	ExportTransparentBackground(bool),
+	Paths(String),
+	ReplaceColor(bool),
+	ReplaceColorFrom(Color),
+	ReplaceColorTo(Color),
+	Scale(bool),
+	ScaleFactor(f64),
+
+	ChooseFiles,
+	FilesChosen { sources: Vec<BatchSource> },
+	Progress { completed: usize, total: usize, results: Vec<BatchResult> },
+	SubmitPaths,
+}
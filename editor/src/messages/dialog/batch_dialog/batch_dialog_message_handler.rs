@@ -0,0 +1,265 @@
+use crate::messages::frontend::utility_types::FileType;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::batch::{BatchResult, BatchSource, BatchStep};
+use crate::messages::prelude::*;
+
+use graphene_core::Color;
+
+/// A dialog to choose the steps applied to each document of a batch, along with the documents, and to follow its progress.
+#[derive(Debug, Clone)]
+pub struct BatchDialogMessageHandler {
+	pub replace_color: bool,
+	pub replace_color_from: Color,
+	pub replace_color_to: Color,
+	pub scale: bool,
+	pub scale_factor: f64,
+	pub export: bool,
+	pub export_file_type: FileType,
+	pub export_scale_factor: f64,
+	pub export_transparent_background: bool,
+	/// File paths (in the desktop app) or URLs of the documents to process, one per line.
+	pub paths: String,
+	/// How many documents have been processed, and how many there are in all, once a batch has been started.
+	pub progress: Option<(usize, usize)>,
+	pub results: Vec<BatchResult>,
+}
+
+impl Default for BatchDialogMessageHandler {
+	fn default() -> Self {
+		Self {
+			replace_color: false,
+			replace_color_from: Color::BLACK,
+			replace_color_to: Color::WHITE,
+			scale: false,
+			scale_factor: 1.,
+			export: true,
+			export_file_type: FileType::Png,
+			export_scale_factor: 1.,
+			export_transparent_background: false,
+			paths: String::new(),
+			progress: None,
+			results: Vec::new(),
+		}
+	}
+}
+
+impl MessageHandler<BatchDialogMessage, ()> for BatchDialogMessageHandler {
+	fn process_message(&mut self, message: BatchDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			BatchDialogMessage::Export(export) => self.export = export,
+			BatchDialogMessage::ExportFileType(file_type) => self.export_file_type = file_type,
+			BatchDialogMessage::ExportScaleFactor(scale_factor) => self.export_scale_factor = scale_factor,
+			BatchDialogMessage::ExportTransparentBackground(transparent_background) => self.export_transparent_background = transparent_background,
+			BatchDialogMessage::Paths(paths) => self.paths = paths,
+			BatchDialogMessage::ReplaceColor(replace_color) => self.replace_color = replace_color,
+			BatchDialogMessage::ReplaceColorFrom(color) => self.replace_color_from = color,
+			BatchDialogMessage::ReplaceColorTo(color) => self.replace_color_to = color,
+			BatchDialogMessage::Scale(scale) => self.scale = scale,
+			BatchDialogMessage::ScaleFactor(scale_factor) => self.scale_factor = scale_factor,
+
+			BatchDialogMessage::ChooseFiles => responses.add(FrontendMessage::TriggerBatchChooseFiles),
+			BatchDialogMessage::FilesChosen { sources } => self.start(sources, responses),
+			BatchDialogMessage::Progress { completed, total, results } => {
+				self.progress = Some((completed, total));
+				self.results = results;
+			}
+			BatchDialogMessage::SubmitPaths => {
+				let sources = self
+					.paths
+					.lines()
+					.map(str::trim)
+					.filter(|path| !path.is_empty())
+					.map(|path| BatchSource::Path(path.to_string()))
+					.collect();
+				self.start(sources, responses);
+			}
+		}
+
+		self.send_layout(responses, LayoutTarget::DialogDetails);
+	}
+
+	advertise_actions! {BatchDialogUpdate;}
+}
+
+impl BatchDialogMessageHandler {
+	/// The steps chosen in the dialog, in the order they're applied.
+	pub fn steps(&self) -> Vec<BatchStep> {
+		let mut steps = Vec::new();
+		if self.replace_color {
+			steps.push(BatchStep::ReplaceColor {
+				from: self.replace_color_from,
+				to: self.replace_color_to,
+			});
+		}
+		if self.scale {
+			steps.push(BatchStep::Scale { factor: self.scale_factor });
+		}
+		if self.export {
+			steps.push(BatchStep::Export {
+				file_type: self.export_file_type,
+				scale_factor: self.export_scale_factor,
+				transparent_background: self.export_transparent_background,
+			});
+		}
+		steps
+	}
+
+	fn is_running(&self) -> bool {
+		self.progress.map_or(false, |(completed, total)| completed < total)
+	}
+
+	fn start(&mut self, sources: Vec<BatchSource>, responses: &mut VecDeque<Message>) {
+		if sources.is_empty() || self.is_running() {
+			return;
+		}
+
+		self.progress = Some((0, sources.len()));
+		self.results.clear();
+		responses.add(PortfolioMessage::ProcessBatch { sources, steps: self.steps() });
+	}
+}
+
+impl LayoutHolder for BatchDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let title = vec![TextLabel::new("Batch Process").bold(true).widget_holder()];
+
+		let description = vec![TextLabel::new(
+			"Each document is opened, has the chosen steps applied in order, and is closed again without being saved.\n\
+			Exports are named after their document.",
+		)
+		.multiline(true)
+		.widget_holder()];
+
+		let replace_color = vec![
+			CheckboxInput::new(self.replace_color)
+				.on_update(|checkbox_input: &CheckboxInput| BatchDialogMessage::ReplaceColor(checkbox_input.checked).into())
+				.widget_holder(),
+			TextLabel::new("Replace Color").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			ColorInput::new(Some(self.replace_color_from))
+				.allow_none(false)
+				.tooltip("The fill and stroke color to replace")
+				.on_update(|color_input: &ColorInput| BatchDialogMessage::ReplaceColorFrom(color_input.value.unwrap_or(Color::BLACK)).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			ColorInput::new(Some(self.replace_color_to))
+				.allow_none(false)
+				.tooltip("The color it's replaced with")
+				.on_update(|color_input: &ColorInput| BatchDialogMessage::ReplaceColorTo(color_input.value.unwrap_or(Color::WHITE)).into())
+				.widget_holder(),
+		];
+
+		let scale = vec![
+			CheckboxInput::new(self.scale)
+				.on_update(|checkbox_input: &CheckboxInput| BatchDialogMessage::Scale(checkbox_input.checked).into())
+				.widget_holder(),
+			TextLabel::new("Scale").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.scale_factor))
+				.unit("x")
+				.min(0.)
+				.disabled(!self.scale)
+				.tooltip("Scales the artwork and artboards about the origin of the document")
+				.on_update(|number_input: &NumberInput| BatchDialogMessage::ScaleFactor(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let entries = [
+			(FileType::Png, "PNG"),
+			(FileType::Jpg, "JPG"),
+			(FileType::Svg, "SVG"),
+			(FileType::Dxf, "DXF"),
+			(FileType::Gcode, "G-code"),
+			(FileType::Hpgl, "HPGL"),
+			(FileType::Ico, "ICO"),
+			(FileType::Icns, "ICNS"),
+			(FileType::Json, "JSON Scene Graph"),
+		]
+		.into_iter()
+		.map(|(file_type, name)| DropdownEntryData::new(name).on_update(move |_| BatchDialogMessage::ExportFileType(file_type).into()))
+		.collect();
+
+		let export = vec![
+			CheckboxInput::new(self.export)
+				.on_update(|checkbox_input: &CheckboxInput| BatchDialogMessage::Export(checkbox_input.checked).into())
+				.widget_holder(),
+			TextLabel::new("Export").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries])
+				.selected_index(Some(self.export_file_type as u32))
+				.disabled(!self.export)
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(self.export_scale_factor))
+				.unit("x")
+				.min(0.)
+				.disabled(!self.export || !self.export_file_type.is_rendered())
+				.tooltip("Scale factor of the exported image")
+				.on_update(|number_input: &NumberInput| BatchDialogMessage::ExportScaleFactor(number_input.value.unwrap()).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			CheckboxInput::new(self.export_transparent_background)
+				.disabled(!self.export || self.export_file_type == FileType::Jpg || !self.export_file_type.is_rendered())
+				.tooltip("Transparent Background")
+				.on_update(|checkbox_input: &CheckboxInput| BatchDialogMessage::ExportTransparentBackground(checkbox_input.checked).into())
+				.widget_holder(),
+		];
+
+		let paths = vec![TextAreaInput::new(&self.paths)
+			.tooltip("File paths (in the desktop app) or URLs of the documents to process, one per line")
+			.on_update(|text_area_input: &TextAreaInput| BatchDialogMessage::Paths(text_area_input.value.clone()).into())
+			.widget_holder()];
+
+		let mut rows = vec![
+			LayoutGroup::Row { widgets: title },
+			LayoutGroup::Row { widgets: description },
+			LayoutGroup::Row { widgets: replace_color },
+			LayoutGroup::Row { widgets: scale },
+			LayoutGroup::Row { widgets: export },
+			LayoutGroup::Row { widgets: paths },
+		];
+
+		if let Some((completed, total)) = self.progress {
+			let failures = self.results.iter().filter(|result| result.error.is_some()).count();
+			let summary = match failures {
+				0 => format!("Processed {completed} of {total} documents"),
+				_ => format!("Processed {completed} of {total} documents, {failures} of which failed:"),
+			};
+			rows.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new(summary).widget_holder()],
+			});
+
+			let errors = self
+				.results
+				.iter()
+				.filter_map(|result| result.error.as_ref().map(|error| format!("{}: {error}", result.name)))
+				.collect::<Vec<_>>();
+			if !errors.is_empty() {
+				rows.push(LayoutGroup::Row {
+					widgets: vec![TextLabel::new(errors.join("\n")).multiline(true).widget_holder()],
+				});
+			}
+		}
+
+		let cannot_start = self.steps().is_empty() || self.is_running();
+		let button_widgets = vec![
+			TextButton::new("Process Listed")
+				.min_width(96)
+				.emphasized(true)
+				.disabled(cannot_start || self.paths.trim().is_empty())
+				.tooltip("Process the documents at the paths or URLs listed above")
+				.on_update(|_| BatchDialogMessage::SubmitPaths.into())
+				.widget_holder(),
+			TextButton::new("Choose Files…")
+				.min_width(96)
+				.disabled(cannot_start)
+				.tooltip("Choose documents to process from this computer")
+				.on_update(|_| BatchDialogMessage::ChooseFiles.into())
+				.widget_holder(),
+			TextButton::new("Close").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+		rows.push(LayoutGroup::Row { widgets: button_widgets });
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
+	}
+}
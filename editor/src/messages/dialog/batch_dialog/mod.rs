@@ -0,0 +1,7 @@
+mod batch_dialog_message;
+mod batch_dialog_message_handler;
+
+#[doc(inline)]
+pub use batch_dialog_message::{BatchDialogMessage, BatchDialogMessageDiscriminant};
+#[doc(inline)]
+pub use batch_dialog_message_handler::BatchDialogMessageHandler;
@@ -0,0 +1,13 @@
+use crate::messages::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+#[impl_message(Message, DialogMessage, DataMergeDialog)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum DataMergeDialogMessage {
+	Data(String),
+	PerRow(f64),
+	Spacing(f64),
+
+	Submit,
+}
@@ -0,0 +1,96 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// A dialog to fill in the placeholders of the template on the first artboard with each row of pasted CSV data, making a new artboard for every row.
+#[derive(Debug, Clone, Default)]
+pub struct DataMergeDialogMessageHandler {
+	pub data: String,
+	/// How many artboards are placed side by side, counting the template, before wrapping onto the next row.
+	pub per_row: u32,
+	pub spacing: f64,
+	pub has_artboards: bool,
+}
+
+impl MessageHandler<DataMergeDialogMessage, ()> for DataMergeDialogMessageHandler {
+	fn process_message(&mut self, message: DataMergeDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			DataMergeDialogMessage::Data(data) => self.data = data,
+			DataMergeDialogMessage::PerRow(per_row) => self.per_row = per_row as u32,
+			DataMergeDialogMessage::Spacing(spacing) => self.spacing = spacing,
+
+			DataMergeDialogMessage::Submit => responses.add_front(DocumentMessage::DataMerge {
+				data: self.data.clone(),
+				per_row: self.per_row,
+				spacing: self.spacing,
+			}),
+		}
+
+		self.send_layout(responses, LayoutTarget::DialogDetails);
+	}
+
+	advertise_actions! {DataMergeDialogUpdate;}
+}
+
+impl LayoutHolder for DataMergeDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let title = vec![TextLabel::new("Data Merge").bold(true).widget_holder()];
+
+		let description = vec![TextLabel::new(
+			"The layers on the first artboard are the template. Write {{Column}} in its text, or name an image layer {{Column}}\n\
+			to fill in that column's text or image URL. Paste CSV data below, with a header row naming the columns.",
+		)
+		.multiline(true)
+		.widget_holder()];
+
+		let data = vec![TextAreaInput::new(&self.data)
+			.tooltip("One artboard is made for each row after the header")
+			.on_update(|text_area_input: &TextAreaInput| DataMergeDialogMessage::Data(text_area_input.value.clone()).into())
+			.widget_holder()];
+
+		let per_row = vec![
+			TextLabel::new("Per Row").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.per_row as f64))
+				.min(1.)
+				.is_integer(true)
+				.tooltip("How many artboards are placed side by side, counting the template, before starting a new row below")
+				.on_update(|number_input: &NumberInput| DataMergeDialogMessage::PerRow(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let spacing = vec![
+			TextLabel::new("Spacing").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.spacing))
+				.unit(" px")
+				.min(0.)
+				.tooltip("Distance between neighboring artboards")
+				.on_update(|number_input: &NumberInput| DataMergeDialogMessage::Spacing(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let button_widgets = vec![
+			TextButton::new("Merge")
+				.min_width(96)
+				.emphasized(true)
+				.disabled(!self.has_artboards || self.data.trim().is_empty())
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![DataMergeDialogMessage::Submit.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			LayoutGroup::Row { widgets: title },
+			LayoutGroup::Row { widgets: description },
+			LayoutGroup::Row { widgets: data },
+			LayoutGroup::Row { widgets: per_row },
+			LayoutGroup::Row { widgets: spacing },
+			LayoutGroup::Row { widgets: button_widgets },
+		]))
+	}
+}
@@ -0,0 +1,7 @@
+mod data_merge_dialog_message;
+mod data_merge_dialog_message_handler;
+
+#[doc(inline)]
+pub use data_merge_dialog_message::{DataMergeDialogMessage, DataMergeDialogMessageDiscriminant};
+#[doc(inline)]
+pub use data_merge_dialog_message_handler::DataMergeDialogMessageHandler;
@@ -9,13 +9,28 @@ pub enum DialogMessage {
 	// Sub-messages
 	#[remain::unsorted]
 	#[child]
+	BatchDialog(BatchDialogMessage),
+	#[remain::unsorted]
+	#[child]
+	DataMergeDialog(DataMergeDialogMessage),
+	#[remain::unsorted]
+	#[child]
 	ExportDialog(ExportDialogMessage),
 	#[remain::unsorted]
 	#[child]
+	HatchFillDialog(HatchFillDialogMessage),
+	#[remain::unsorted]
+	#[child]
 	NewDocumentDialog(NewDocumentDialogMessage),
 	#[remain::unsorted]
 	#[child]
 	PreferencesDialog(PreferencesDialogMessage),
+	#[remain::unsorted]
+	#[child]
+	ReplaceFontsDialog(ReplaceFontsDialogMessage),
+	#[remain::unsorted]
+	#[child]
+	SpriteSheetDialog(SpriteSheetDialogMessage),
 
 	// Messages
 	CloseAllDocumentsWithConfirmation,
@@ -30,10 +45,22 @@ pub enum DialogMessage {
 	RequestAboutGraphiteDialogWithLocalizedCommitDate {
 		localized_commit_date: String,
 	},
+	RequestBackupsDialog,
+	RequestBackupsDialogWithSaveTimes {
+		file_name: String,
+		save_times: Vec<String>,
+	},
+	RequestBatchDialog,
 	RequestComingSoonDialog {
 		issue: Option<i32>,
 	},
+	RequestDataMergeDialog,
 	RequestExportDialog,
+	RequestHatchFillDialog,
+	RequestMemoryReportDialog,
 	RequestNewDocumentDialog,
 	RequestPreferencesDialog,
+	RequestReplaceFontsDialog,
+	RequestSpriteSheetDialog,
+	RequestUsageStatisticsDialog,
 }
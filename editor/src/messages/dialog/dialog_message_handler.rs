@@ -1,12 +1,19 @@
-use super::simple_dialogs::{self, AboutGraphiteDialog, ComingSoonDialog};
+use super::simple_dialogs::{self, AboutGraphiteDialog, BackupsDialog, ComingSoonDialog};
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::sprite_sheet::SpriteSource;
+use crate::messages::portfolio::document::utility_types::svg_profile::svg_feature_uses;
 use crate::messages::prelude::*;
 
 #[derive(Debug, Default, Clone)]
 pub struct DialogMessageHandler {
+	batch_dialog: BatchDialogMessageHandler,
+	data_merge_dialog: DataMergeDialogMessageHandler,
 	export_dialog: ExportDialogMessageHandler,
+	hatch_fill_dialog: HatchFillDialogMessageHandler,
 	new_document_dialog: NewDocumentDialogMessageHandler,
 	preferences_dialog: PreferencesDialogMessageHandler,
+	replace_fonts_dialog: ReplaceFontsDialogMessageHandler,
+	sprite_sheet_dialog: SpriteSheetDialogMessageHandler,
 }
 
 impl MessageHandler<DialogMessage, (&PortfolioMessageHandler, &PreferencesMessageHandler)> for DialogMessageHandler {
@@ -14,12 +21,22 @@ impl MessageHandler<DialogMessage, (&PortfolioMessageHandler, &PreferencesMessag
 	fn process_message(&mut self, message: DialogMessage, responses: &mut VecDeque<Message>, (portfolio, preferences): (&PortfolioMessageHandler, &PreferencesMessageHandler)) {
 		#[remain::sorted]
 		match message {
+			#[remain::unsorted]
+			DialogMessage::BatchDialog(message) => self.batch_dialog.process_message(message, responses, ()),
+			#[remain::unsorted]
+			DialogMessage::DataMergeDialog(message) => self.data_merge_dialog.process_message(message, responses, ()),
 			#[remain::unsorted]
 			DialogMessage::ExportDialog(message) => self.export_dialog.process_message(message, responses, ()),
 			#[remain::unsorted]
+			DialogMessage::HatchFillDialog(message) => self.hatch_fill_dialog.process_message(message, responses, ()),
+			#[remain::unsorted]
 			DialogMessage::NewDocumentDialog(message) => self.new_document_dialog.process_message(message, responses, ()),
 			#[remain::unsorted]
 			DialogMessage::PreferencesDialog(message) => self.preferences_dialog.process_message(message, responses, preferences),
+			#[remain::unsorted]
+			DialogMessage::ReplaceFontsDialog(message) => self.replace_fonts_dialog.process_message(message, responses, ()),
+			#[remain::unsorted]
+			DialogMessage::SpriteSheetDialog(message) => self.sprite_sheet_dialog.process_message(message, responses, ()),
 
 			DialogMessage::CloseAllDocumentsWithConfirmation => {
 				let dialog = simple_dialogs::CloseAllDocumentsDialog;
@@ -48,6 +65,25 @@ impl MessageHandler<DialogMessage, (&PortfolioMessageHandler, &PreferencesMessag
 				about_graphite.send_layout(responses, LayoutTarget::DialogDetails);
 				responses.add(FrontendMessage::DisplayDialog { icon: "GraphiteLogo".to_string() });
 			}
+			DialogMessage::RequestBackupsDialog => {
+				// The backups are kept in the browser's storage, so the frontend looks them up and sends back when each was saved
+				if let Some(document) = portfolio.active_document() {
+					responses.add(FrontendMessage::TriggerLoadBackups { file_name: document.file_name() });
+				}
+			}
+			DialogMessage::RequestBackupsDialogWithSaveTimes { file_name, save_times } => {
+				let dialog = BackupsDialog {
+					file_name,
+					save_times,
+					backups_enabled: preferences.backup_count > 0,
+				};
+				dialog.send_layout(responses, LayoutTarget::DialogDetails);
+				responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
+			}
+			DialogMessage::RequestBatchDialog => {
+				self.batch_dialog.send_layout(responses, LayoutTarget::DialogDetails);
+				responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
+			}
 			DialogMessage::RequestComingSoonDialog { issue } => {
 				let coming_soon = ComingSoonDialog { issue };
 				coming_soon.send_layout(responses, LayoutTarget::DialogDetails);
@@ -79,19 +115,45 @@ impl MessageHandler<DialogMessage, (&PortfolioMessageHandler, &PreferencesMessag
 					self.export_dialog = ExportDialogMessageHandler {
 						file_name: document.name.clone(),
 						scale_factor: 1.,
+						custom_size: glam::DVec2::new(1920., 1080.),
 						artboards,
 						has_selection: document.selected_layers().next().is_some(),
+						svg_feature_uses: svg_feature_uses(&document.document_legacy),
 						..Default::default()
 					};
 					self.export_dialog.send_layout(responses, LayoutTarget::DialogDetails);
 					responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
+					responses.add(self.export_dialog.export_document(true));
+				}
+			}
+			DialogMessage::RequestHatchFillDialog => {
+				self.hatch_fill_dialog.send_layout(responses, LayoutTarget::DialogDetails);
+				responses.add(FrontendMessage::DisplayDialog { icon: "Layer".to_string() });
+			}
+			DialogMessage::RequestDataMergeDialog => {
+				if let Some(document) = portfolio.active_document() {
+					self.data_merge_dialog.has_artboards = !document.artboard_message_handler.artboard_ids.is_empty();
+					if self.data_merge_dialog.per_row == 0 {
+						self.data_merge_dialog.per_row = 4;
+						self.data_merge_dialog.spacing = 100.;
+					}
+					self.data_merge_dialog.send_layout(responses, LayoutTarget::DialogDetails);
+					responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
 				}
 			}
+			DialogMessage::RequestMemoryReportDialog => {
+				let dialog = simple_dialogs::MemoryReportDialog {
+					documents: portfolio.memory_reports(),
+				};
+				dialog.send_layout(responses, LayoutTarget::DialogDetails);
+				responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
+			}
 			DialogMessage::RequestNewDocumentDialog => {
 				self.new_document_dialog = NewDocumentDialogMessageHandler {
 					name: portfolio.generate_new_document_name(),
 					infinite: false,
 					dimensions: glam::UVec2::new(1920, 1080),
+					template: None,
 				};
 				self.new_document_dialog.send_layout(responses, LayoutTarget::DialogDetails);
 				responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
@@ -101,6 +163,40 @@ impl MessageHandler<DialogMessage, (&PortfolioMessageHandler, &PreferencesMessag
 				self.preferences_dialog.send_layout(responses, LayoutTarget::DialogDetails, preferences);
 				responses.add(FrontendMessage::DisplayDialog { icon: "Settings".to_string() });
 			}
+			DialogMessage::RequestReplaceFontsDialog => {
+				if let Some(document) = portfolio.active_document() {
+					let fonts = document.used_fonts();
+					self.replace_fonts_dialog = ReplaceFontsDialogMessageHandler {
+						replacements: fonts.iter().map(|(font, _)| font.clone()).collect(),
+						fonts,
+					};
+					self.replace_fonts_dialog.send_layout(responses, LayoutTarget::DialogDetails);
+					responses.add(FrontendMessage::DisplayDialog { icon: "NodeText".to_string() });
+				}
+			}
+			DialogMessage::RequestSpriteSheetDialog => {
+				if let Some(document) = portfolio.active_document() {
+					let has_artboards = !document.artboard_message_handler.artboard_ids.is_empty();
+					self.sprite_sheet_dialog = SpriteSheetDialogMessageHandler {
+						file_name: document.name.clone(),
+						source: if has_artboards { SpriteSource::Artboards } else { SpriteSource::SelectedLayers },
+						scale_factor: 1.,
+						spacing: 2,
+						transparent_background: true,
+						has_artboards,
+						has_selection: document.selected_layers().next().is_some(),
+					};
+					self.sprite_sheet_dialog.send_layout(responses, LayoutTarget::DialogDetails);
+					responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
+				}
+			}
+			DialogMessage::RequestUsageStatisticsDialog => {
+				let dialog = simple_dialogs::UsageStatisticsDialog {
+					statistics: preferences.usage_statistics.clone(),
+				};
+				dialog.send_layout(responses, LayoutTarget::DialogDetails);
+				responses.add(FrontendMessage::DisplayDialog { icon: "Settings".to_string() });
+			}
 		}
 	}
 
@@ -1,6 +1,9 @@
 use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::portfolio::document::utility_types::dxf::DxfUnits;
+use crate::messages::portfolio::document::utility_types::svg_profile::SvgProfile;
 use crate::messages::prelude::*;
 
+use glam::DVec2;
 use serde::{Deserialize, Serialize};
 
 #[impl_message(Message, DialogMessage, ExportDialog)]
@@ -11,6 +14,22 @@ pub enum ExportDialogMessage {
 	ScaleFactor(f64),
 	TransparentBackground(bool),
 	ExportBounds(ExportBounds),
+	CustomOrigin(DVec2),
+	CustomSize(DVec2),
+	Padding(f64),
+	LayerNames(bool),
+	EmbedFonts(bool),
+	SvgProfile(SvgProfile),
+	DxfTolerance(f64),
+	DxfUnits(DxfUnits),
+	DxfArcs(bool),
+	PlotterTolerance(f64),
+	PlotterFeedRate(f64),
+	PlotterOptimizeTravel(bool),
+	PlotterRemoveHiddenLines(bool),
+	Bleed(f64),
+	CropMarks(bool),
+	RegistrationMarks(bool),
 
 	Submit,
 }
@@ -1,8 +1,13 @@
 use crate::messages::frontend::utility_types::{ExportBounds, FileType};
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::dxf::{DxfOptions, DxfUnits};
+use crate::messages::portfolio::document::utility_types::plotter::PlotterOptions;
+use crate::messages::portfolio::document::utility_types::print_marks::PrintOptions;
+use crate::messages::portfolio::document::utility_types::svg_profile::{unsupported_feature_uses, FeatureUse, SvgProfile};
 use crate::messages::prelude::*;
 
 use document_legacy::LayerId;
+use glam::DVec2;
 
 /// A dialog to allow users to customize their file export.
 #[derive(Debug, Clone, Default)]
@@ -11,35 +16,137 @@ pub struct ExportDialogMessageHandler {
 	pub file_type: FileType,
 	pub scale_factor: f64,
 	pub bounds: ExportBounds,
+	/// Top left corner of the export rectangle in document space, used when the bounds are [ExportBounds::Custom].
+	pub custom_origin: DVec2,
+	/// Size of the export rectangle in document space, used when the bounds are [ExportBounds::Custom].
+	pub custom_size: DVec2,
+	/// Extra space added around the computed bounds on every side.
+	pub padding: f64,
 	pub transparent_background: bool,
+	pub layer_names: bool,
+	/// Whether text is kept as live text set in embedded fonts, subset to the glyphs used, rather than converted to outlines.
+	pub embed_fonts: bool,
+	/// The profile of SVG the exported SVG is checked against, which only affects the warnings shown and not the export itself.
+	pub svg_profile: SvgProfile,
+	/// The features used by the document which not every profile of SVG supports, found when the dialog was opened.
+	pub svg_feature_uses: Vec<FeatureUse>,
+	pub dxf_options: DxfOptions,
+	pub plotter_options: PlotterOptions,
+	pub print_options: PrintOptions,
 	pub artboards: HashMap<LayerId, String>,
 	pub has_selection: bool,
 }
 
 impl MessageHandler<ExportDialogMessage, ()> for ExportDialogMessageHandler {
 	fn process_message(&mut self, message: ExportDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		// The file name and SVG profile don't change how the export looks, and there's no preview to update once the dialog closes to export
+		let update_preview = !matches!(message, ExportDialogMessage::FileName(_) | ExportDialogMessage::SvgProfile(_) | ExportDialogMessage::Submit);
+
 		match message {
 			ExportDialogMessage::FileName(name) => self.file_name = name,
 			ExportDialogMessage::FileType(export_type) => self.file_type = export_type,
 			ExportDialogMessage::ScaleFactor(factor) => self.scale_factor = factor,
 			ExportDialogMessage::TransparentBackground(transparent_background) => self.transparent_background = transparent_background,
 			ExportDialogMessage::ExportBounds(export_area) => self.bounds = export_area,
+			ExportDialogMessage::CustomOrigin(origin) => self.custom_origin = origin,
+			ExportDialogMessage::CustomSize(size) => self.custom_size = size,
+			ExportDialogMessage::Padding(padding) => self.padding = padding,
+			ExportDialogMessage::LayerNames(layer_names) => self.layer_names = layer_names,
+			ExportDialogMessage::EmbedFonts(embed_fonts) => self.embed_fonts = embed_fonts,
+			ExportDialogMessage::SvgProfile(svg_profile) => self.svg_profile = svg_profile,
+			ExportDialogMessage::DxfTolerance(tolerance) => self.dxf_options.tolerance = tolerance,
+			ExportDialogMessage::DxfUnits(units) => self.dxf_options.units = units,
+			ExportDialogMessage::DxfArcs(arcs) => self.dxf_options.arcs = arcs,
+			ExportDialogMessage::PlotterTolerance(tolerance) => self.plotter_options.tolerance = tolerance,
+			ExportDialogMessage::PlotterFeedRate(feed_rate) => self.plotter_options.feed_rate = feed_rate,
+			ExportDialogMessage::PlotterOptimizeTravel(optimize_travel) => self.plotter_options.optimize_travel = optimize_travel,
+			ExportDialogMessage::PlotterRemoveHiddenLines(remove_hidden_lines) => self.plotter_options.remove_hidden_lines = remove_hidden_lines,
+			ExportDialogMessage::Bleed(bleed) => self.print_options.bleed = bleed,
+			ExportDialogMessage::CropMarks(crop_marks) => self.print_options.crop_marks = crop_marks,
+			ExportDialogMessage::RegistrationMarks(registration_marks) => self.print_options.registration_marks = registration_marks,
 
-			ExportDialogMessage::Submit => responses.add_front(DocumentMessage::ExportDocument {
-				file_name: self.file_name.clone(),
-				file_type: self.file_type,
-				scale_factor: self.scale_factor,
-				bounds: self.bounds,
-				transparent_background: self.file_type != FileType::Jpg && self.transparent_background,
-			}),
+			ExportDialogMessage::Submit => responses.add_front(self.export_document(false)),
 		}
 
 		self.send_layout(responses, LayoutTarget::DialogDetails);
+		if update_preview {
+			responses.add(self.export_document(true));
+		}
 	}
 
 	advertise_actions! {ExportDialogUpdate;}
 }
 
+impl ExportDialogMessageHandler {
+	/// The message which exports the document with the chosen settings, or only updates the preview shown in this dialog.
+	pub fn export_document(&self, preview: bool) -> DocumentMessage {
+		DocumentMessage::ExportDocument {
+			file_name: self.file_name.clone(),
+			file_type: self.file_type,
+			scale_factor: self.scale_factor,
+			bounds: self.bounds,
+			custom_bounds: [self.custom_origin, self.custom_origin + self.custom_size],
+			padding: self.padding,
+			transparent_background: self.file_type != FileType::Jpg && self.transparent_background,
+			layer_names: self.file_type == FileType::Svg && self.layer_names,
+			embed_fonts: self.file_type == FileType::Svg && self.embed_fonts,
+			dxf_options: self.dxf_options,
+			plotter_options: self.plotter_options,
+			print_options: self.print_options,
+			preview,
+		}
+	}
+}
+
+impl ExportDialogMessageHandler {
+	/// The most features listed before the rest are summarized, to keep the dialog from growing past the screen.
+	const MAX_LISTED_FEATURE_USES: usize = 8;
+
+	/// A row for each use of a feature the chosen SVG profile doesn't support, with a button to select the layer using it.
+	fn unsupported_feature_rows(&self) -> Vec<LayoutGroup> {
+		let unsupported = unsupported_feature_uses(&self.svg_feature_uses, self.svg_profile).collect::<Vec<_>>();
+		if unsupported.is_empty() {
+			return vec![LayoutGroup::Row {
+				widgets: vec![TextLabel::new(format!("Fully supported by {}", self.svg_profile.label())).italic(true).widget_holder()],
+			}];
+		}
+
+		let mut rows = unsupported
+			.iter()
+			.take(Self::MAX_LISTED_FEATURE_USES)
+			.map(|feature_use| {
+				let layer = feature_use.layer.clone();
+				LayoutGroup::Row {
+					widgets: vec![
+						IconLabel::new("Warning").widget_holder(),
+						Separator::new(SeparatorType::Related).widget_holder(),
+						TextLabel::new(format!("{} unsupported", feature_use.feature.label())).widget_holder(),
+						Separator::new(SeparatorType::Unrelated).widget_holder(),
+						TextButton::new(&feature_use.layer_name)
+							.tooltip("Close the dialog and select this layer")
+							.on_update(move |_| {
+								DialogMessage::CloseDialogAndThen {
+									followups: vec![DocumentMessage::SetSelectedLayers {
+										replacement_selected_layers: vec![layer.clone()],
+									}
+									.into()],
+								}
+								.into()
+							})
+							.widget_holder(),
+					],
+				}
+			})
+			.collect::<Vec<_>>();
+		if unsupported.len() > Self::MAX_LISTED_FEATURE_USES {
+			rows.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new(format!("And {} more", unsupported.len() - Self::MAX_LISTED_FEATURE_USES)).italic(true).widget_holder()],
+			});
+		}
+		rows
+	}
+}
+
 impl LayoutHolder for ExportDialogMessageHandler {
 	fn layout(&self) -> Layout {
 		let file_name = vec![
@@ -50,15 +157,25 @@ impl LayoutHolder for ExportDialogMessageHandler {
 				.widget_holder(),
 		];
 
-		let entries = [(FileType::Png, "PNG"), (FileType::Jpg, "JPG"), (FileType::Svg, "SVG")]
-			.into_iter()
-			.map(|(val, name)| RadioEntryData::new(name).on_update(move |_| ExportDialogMessage::FileType(val).into()))
-			.collect();
+		let entries = [
+			(FileType::Png, "PNG"),
+			(FileType::Jpg, "JPG"),
+			(FileType::Svg, "SVG"),
+			(FileType::Dxf, "DXF"),
+			(FileType::Gcode, "G-code"),
+			(FileType::Hpgl, "HPGL"),
+			(FileType::Ico, "ICO"),
+			(FileType::Icns, "ICNS"),
+			(FileType::Json, "JSON Scene Graph"),
+		]
+		.into_iter()
+		.map(|(val, name)| DropdownEntryData::new(name).on_update(move |_| ExportDialogMessage::FileType(val).into()))
+		.collect();
 
 		let export_type = vec![
 			TextLabel::new("File Type").table_align(true).widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
-			RadioInput::new(entries).selected_index(self.file_type as u32).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(Some(self.file_type as u32)).widget_holder(),
 		];
 
 		let artboards = self.artboards.iter().map(|(&val, name)| (ExportBounds::Artboard(val), name.to_string(), false));
@@ -67,6 +184,7 @@ impl LayoutHolder for ExportDialogMessageHandler {
 			(ExportBounds::Selection, "Selection".to_string(), !self.has_selection),
 		];
 		export_area_options.extend(artboards);
+		export_area_options.push((ExportBounds::Custom, "Custom".to_string(), false));
 		let index = export_area_options.iter().position(|(val, _, _)| val == &self.bounds).unwrap();
 		let entries = vec![export_area_options
 			.into_iter()
@@ -79,26 +197,213 @@ impl LayoutHolder for ExportDialogMessageHandler {
 			DropdownInput::new(entries).selected_index(Some(index as u32)).widget_holder(),
 		];
 
+		let custom = self.bounds == ExportBounds::Custom;
+		let custom_origin = self.custom_origin;
+		let custom_size = self.custom_size;
+		let custom_position = vec![
+			TextLabel::new("Position").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(custom_origin.x))
+				.label("X")
+				.unit(" px")
+				.min_width(100)
+				.on_update(move |number_input: &NumberInput| ExportDialogMessage::CustomOrigin(DVec2::new(number_input.value.unwrap(), custom_origin.y)).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(custom_origin.y))
+				.label("Y")
+				.unit(" px")
+				.min_width(100)
+				.on_update(move |number_input: &NumberInput| ExportDialogMessage::CustomOrigin(DVec2::new(custom_origin.x, number_input.value.unwrap())).into())
+				.widget_holder(),
+		];
+		let custom_dimensions = vec![
+			TextLabel::new("Dimensions").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(custom_size.x))
+				.label("W")
+				.unit(" px")
+				.min(1.)
+				.min_width(100)
+				.on_update(move |number_input: &NumberInput| ExportDialogMessage::CustomSize(DVec2::new(number_input.value.unwrap(), custom_size.y)).into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(custom_size.y))
+				.label("H")
+				.unit(" px")
+				.min(1.)
+				.min_width(100)
+				.on_update(move |number_input: &NumberInput| ExportDialogMessage::CustomSize(DVec2::new(custom_size.x, number_input.value.unwrap())).into())
+				.widget_holder(),
+		];
+
+		let padding = vec![
+			TextLabel::new("Padding").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.padding))
+				.unit(" px")
+				.min(0.)
+				.disabled(custom)
+				.tooltip("Extra space added around the bounds on every side")
+				.on_update(|number_input: &NumberInput| ExportDialogMessage::Padding(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
 		let transparent_background = vec![
 			TextLabel::new("Transparency").table_align(true).widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
 			CheckboxInput::new(self.transparent_background)
-				.disabled(self.file_type == FileType::Jpg)
+				.disabled(self.file_type == FileType::Jpg || !self.file_type.is_rendered())
 				.on_update(move |value: &CheckboxInput| ExportDialogMessage::TransparentBackground(value.checked).into())
 				.widget_holder(),
 		];
 
+		let layer_names = vec![
+			TextLabel::new("Layer Names").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.layer_names)
+				.disabled(self.file_type != FileType::Svg)
+				.tooltip("Tag each named layer and folder with a matching id and data-name attribute so it can be targeted from CSS or JS")
+				.on_update(move |value: &CheckboxInput| ExportDialogMessage::LayerNames(value.checked).into())
+				.widget_holder(),
+		];
+
+		let embed_fonts = vec![
+			TextLabel::new("Embed Fonts").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.embed_fonts)
+				.disabled(self.file_type != FileType::Svg)
+				.tooltip("Keep text as selectable text by embedding its fonts, reduced to only the characters used, instead of converting it to outlines")
+				.on_update(move |value: &CheckboxInput| ExportDialogMessage::EmbedFonts(value.checked).into())
+				.widget_holder(),
+		];
+
+		let profiles = [SvgProfile::Browsers, SvgProfile::Svg11, SvgProfile::SvgTiny12, SvgProfile::NoFilters];
+		let profile_entries = profiles
+			.into_iter()
+			.map(|profile| DropdownEntryData::new(profile.label()).on_update(move |_| ExportDialogMessage::SvgProfile(profile).into()))
+			.collect();
+		let svg_profile = vec![
+			TextLabel::new("Target Profile").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![profile_entries])
+				.selected_index(profiles.iter().position(|&profile| profile == self.svg_profile).map(|index| index as u32))
+				.tooltip("Check the document for features which viewers limited to this profile of SVG won't show correctly")
+				.widget_holder(),
+		];
+		let unsupported_features = self.unsupported_feature_rows();
+
 		let resolution = vec![
 			TextLabel::new("Scale Factor").table_align(true).widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
 			NumberInput::new(Some(self.scale_factor))
 				.unit(" ")
 				.min(0.)
-				.disabled(self.file_type == FileType::Svg)
+				.disabled(self.file_type.is_text() || self.file_type.is_icon())
 				.on_update(|number_input: &NumberInput| ExportDialogMessage::ScaleFactor(number_input.value.unwrap()).into())
 				.widget_holder(),
 		];
 
+		let dxf_tolerance = vec![
+			TextLabel::new("Tolerance").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.dxf_options.tolerance))
+				.unit(" px")
+				.min(0.001)
+				.tooltip("The furthest the exported lines and arcs may stray from the original curves")
+				.on_update(|number_input: &NumberInput| ExportDialogMessage::DxfTolerance(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let unit_entries = [(DxfUnits::Millimeters, "Millimeters"), (DxfUnits::Inches, "Inches"), (DxfUnits::Pixels, "Pixels")]
+			.into_iter()
+			.map(|(val, name)| RadioEntryData::new(name).on_update(move |_| ExportDialogMessage::DxfUnits(val).into()))
+			.collect();
+		let dxf_units = vec![
+			TextLabel::new("Units").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(unit_entries).selected_index(self.dxf_options.units as u32).widget_holder(),
+		];
+
+		let curve_entries = [(false, "Polylines"), (true, "Arcs")]
+			.into_iter()
+			.map(|(val, name)| RadioEntryData::new(name).on_update(move |_| ExportDialogMessage::DxfArcs(val).into()))
+			.collect();
+		let dxf_curves = vec![
+			TextLabel::new("Curves").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(curve_entries).selected_index(self.dxf_options.arcs as u32).widget_holder(),
+		];
+
+		let plotter_tolerance = vec![
+			TextLabel::new("Tolerance").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.plotter_options.tolerance))
+				.unit(" px")
+				.min(0.001)
+				.tooltip("The furthest the plotted lines may stray from the original curves")
+				.on_update(|number_input: &NumberInput| ExportDialogMessage::PlotterTolerance(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let plotter_feed_rate = vec![
+			TextLabel::new("Feed Rate").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.plotter_options.feed_rate))
+				.unit(" mm/min")
+				.min(1.)
+				.disabled(self.file_type != FileType::Gcode)
+				.on_update(|number_input: &NumberInput| ExportDialogMessage::PlotterFeedRate(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let plotter_optimize_travel = vec![
+			TextLabel::new("Optimize Travel").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.plotter_options.optimize_travel)
+				.tooltip("Reorder and reverse paths to minimize the distance traveled with the pen lifted")
+				.on_update(|value: &CheckboxInput| ExportDialogMessage::PlotterOptimizeTravel(value.checked).into())
+				.widget_holder(),
+		];
+
+		let plotter_remove_hidden_lines = vec![
+			TextLabel::new("Remove Hidden Lines").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.plotter_options.remove_hidden_lines)
+				.tooltip("Cut away the parts of lines covered by opaque filled shapes above them in the layer stack")
+				.on_update(|value: &CheckboxInput| ExportDialogMessage::PlotterRemoveHiddenLines(value.checked).into())
+				.widget_holder(),
+		];
+
+		let bleed = vec![
+			TextLabel::new("Bleed").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.print_options.bleed))
+				.unit(" px")
+				.min(0.)
+				.tooltip("How far the artwork and artboard backgrounds extend past the trimmed edge, so printing can be cut without leaving blank slivers")
+				.on_update(|number_input: &NumberInput| ExportDialogMessage::Bleed(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let crop_marks = vec![
+			TextLabel::new("Crop Marks").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.print_options.crop_marks)
+				.tooltip("Add lines outside each corner showing where the printed sheet is cut")
+				.on_update(|value: &CheckboxInput| ExportDialogMessage::CropMarks(value.checked).into())
+				.widget_holder(),
+		];
+
+		let registration_marks = vec![
+			TextLabel::new("Registration Marks").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.print_options.registration_marks)
+				.tooltip("Add targets outside each edge for lining up the printing plates of each ink")
+				.on_update(|value: &CheckboxInput| ExportDialogMessage::RegistrationMarks(value.checked).into())
+				.widget_holder(),
+		];
+
 		let button_widgets = vec![
 			TextButton::new("Export")
 				.min_width(96)
@@ -113,7 +418,7 @@ impl LayoutHolder for ExportDialogMessageHandler {
 			TextButton::new("Cancel").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
 		];
 
-		Layout::WidgetLayout(WidgetLayout::new(vec![
+		let mut rows = vec![
 			LayoutGroup::Row {
 				widgets: vec![TextLabel::new("Export").bold(true).widget_holder()],
 			},
@@ -121,8 +426,39 @@ impl LayoutHolder for ExportDialogMessageHandler {
 			LayoutGroup::Row { widgets: export_type },
 			LayoutGroup::Row { widgets: resolution },
 			LayoutGroup::Row { widgets: export_area },
+		];
+		if custom {
+			rows.push(LayoutGroup::Row { widgets: custom_position });
+			rows.push(LayoutGroup::Row { widgets: custom_dimensions });
+		}
+		rows.extend([
+			LayoutGroup::Row { widgets: padding },
 			LayoutGroup::Row { widgets: transparent_background },
-			LayoutGroup::Row { widgets: button_widgets },
-		]))
+			LayoutGroup::Row { widgets: layer_names },
+			LayoutGroup::Row { widgets: embed_fonts },
+		]);
+		if self.file_type == FileType::Svg {
+			rows.push(LayoutGroup::Row { widgets: svg_profile });
+			rows.extend(unsupported_features);
+		}
+		if self.file_type == FileType::Dxf {
+			rows.push(LayoutGroup::Row { widgets: dxf_tolerance });
+			rows.push(LayoutGroup::Row { widgets: dxf_units });
+			rows.push(LayoutGroup::Row { widgets: dxf_curves });
+		}
+		if matches!(self.file_type, FileType::Gcode | FileType::Hpgl) {
+			rows.push(LayoutGroup::Row { widgets: plotter_tolerance });
+			rows.push(LayoutGroup::Row { widgets: plotter_feed_rate });
+			rows.push(LayoutGroup::Row { widgets: plotter_optimize_travel });
+			rows.push(LayoutGroup::Row { widgets: plotter_remove_hidden_lines });
+		}
+		if self.file_type.is_rendered() && !self.file_type.is_icon() {
+			rows.push(LayoutGroup::Row { widgets: bleed });
+			rows.push(LayoutGroup::Row { widgets: crop_marks });
+			rows.push(LayoutGroup::Row { widgets: registration_marks });
+		}
+		rows.push(LayoutGroup::Row { widgets: button_widgets });
+
+		Layout::WidgetLayout(WidgetLayout::new(rows))
 	}
 }
@@ -0,0 +1,14 @@
+use crate::messages::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+#[impl_message(Message, DialogMessage, HatchFillDialog)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum HatchFillDialogMessage {
+	Angle(f64),
+	Spacing(f64),
+	Inset(f64),
+	CrossHatch(bool),
+
+	Submit,
+}
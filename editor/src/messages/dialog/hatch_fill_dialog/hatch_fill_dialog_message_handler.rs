@@ -0,0 +1,97 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::hatch::HatchOptions;
+use crate::messages::prelude::*;
+
+/// A dialog to convert the fills of the selected layers into hatch lines. Its options are remembered between uses.
+#[derive(Debug, Clone, Default)]
+pub struct HatchFillDialogMessageHandler {
+	pub options: HatchOptions,
+}
+
+impl MessageHandler<HatchFillDialogMessage, ()> for HatchFillDialogMessageHandler {
+	fn process_message(&mut self, message: HatchFillDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			HatchFillDialogMessage::Angle(angle) => self.options.angle = angle,
+			HatchFillDialogMessage::Spacing(spacing) => self.options.spacing = spacing,
+			HatchFillDialogMessage::Inset(inset) => self.options.inset = inset,
+			HatchFillDialogMessage::CrossHatch(cross_hatch) => self.options.cross_hatch = cross_hatch,
+
+			HatchFillDialogMessage::Submit => responses.add_front(DocumentMessage::HatchFillSelectedLayers { options: self.options }),
+		}
+
+		self.send_layout(responses, LayoutTarget::DialogDetails);
+	}
+
+	advertise_actions! {HatchFillDialogUpdate;}
+}
+
+impl LayoutHolder for HatchFillDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let title = vec![TextLabel::new("Hatch Fill").bold(true).widget_holder()];
+
+		let angle = vec![
+			TextLabel::new("Angle").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.options.angle))
+				.unit("°")
+				.min(-180.)
+				.max(180.)
+				.on_update(|number_input: &NumberInput| HatchFillDialogMessage::Angle(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let spacing = vec![
+			TextLabel::new("Spacing").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.options.spacing))
+				.unit(" px")
+				.min(0.1)
+				.tooltip("Distance between neighboring hatch lines")
+				.on_update(|number_input: &NumberInput| HatchFillDialogMessage::Spacing(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let inset = vec![
+			TextLabel::new("Inset").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.options.inset))
+				.unit(" px")
+				.min(0.)
+				.tooltip("Space kept clear between the hatch lines and the outline of the shape")
+				.on_update(|number_input: &NumberInput| HatchFillDialogMessage::Inset(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let cross_hatch = vec![
+			TextLabel::new("Cross-Hatch").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.options.cross_hatch)
+				.tooltip("Add a second set of lines perpendicular to the first")
+				.on_update(|checkbox_input: &CheckboxInput| HatchFillDialogMessage::CrossHatch(checkbox_input.checked).into())
+				.widget_holder(),
+		];
+
+		let button_widgets = vec![
+			TextButton::new("OK")
+				.min_width(96)
+				.emphasized(true)
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![HatchFillDialogMessage::Submit.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			LayoutGroup::Row { widgets: title },
+			LayoutGroup::Row { widgets: angle },
+			LayoutGroup::Row { widgets: spacing },
+			LayoutGroup::Row { widgets: inset },
+			LayoutGroup::Row { widgets: cross_hatch },
+			LayoutGroup::Row { widgets: button_widgets },
+		]))
+	}
+}
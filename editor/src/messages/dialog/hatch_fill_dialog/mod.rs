@@ -0,0 +1,7 @@
+mod hatch_fill_dialog_message;
+mod hatch_fill_dialog_message_handler;
+
+#[doc(inline)]
+pub use hatch_fill_dialog_message::{HatchFillDialogMessage, HatchFillDialogMessageDiscriminant};
+#[doc(inline)]
+pub use hatch_fill_dialog_message_handler::HatchFillDialogMessageHandler;
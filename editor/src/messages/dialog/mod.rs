@@ -8,10 +8,15 @@
 mod dialog_message;
 mod dialog_message_handler;
 
+pub mod batch_dialog;
+pub mod data_merge_dialog;
 pub mod export_dialog;
+pub mod hatch_fill_dialog;
 pub mod new_document_dialog;
 pub mod preferences_dialog;
+pub mod replace_fonts_dialog;
 pub mod simple_dialogs;
+pub mod sprite_sheet_dialog;
 
 #[doc(inline)]
 pub use dialog_message::{DialogMessage, DialogMessageDiscriminant};
@@ -1,5 +1,6 @@
 mod new_document_dialog_message;
 mod new_document_dialog_message_handler;
+pub mod templates;
 
 #[doc(inline)]
 pub use new_document_dialog_message::{NewDocumentDialogMessage, NewDocumentDialogMessageDiscriminant};
@@ -9,6 +9,7 @@ pub enum NewDocumentDialogMessage {
 	Infinite(bool),
 	DimensionsX(f64),
 	DimensionsY(f64),
+	Template(Option<usize>),
 
 	Submit,
 }
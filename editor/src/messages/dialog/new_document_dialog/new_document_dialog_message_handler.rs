@@ -1,3 +1,4 @@
+use super::templates::document_templates;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
 
@@ -11,20 +12,42 @@ pub struct NewDocumentDialogMessageHandler {
 	pub name: String,
 	pub infinite: bool,
 	pub dimensions: UVec2,
+	/// The index of the built-in template to start from, which sets up the artboards and safe area guides in place of the dimensions.
+	pub template: Option<usize>,
 }
 
 impl MessageHandler<NewDocumentDialogMessage, ()> for NewDocumentDialogMessageHandler {
 	fn process_message(&mut self, message: NewDocumentDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
 		match message {
 			NewDocumentDialogMessage::Name(name) => self.name = name,
-			NewDocumentDialogMessage::Infinite(infinite) => self.infinite = infinite,
-			NewDocumentDialogMessage::DimensionsX(x) => self.dimensions.x = x as u32,
-			NewDocumentDialogMessage::DimensionsY(y) => self.dimensions.y = y as u32,
+			NewDocumentDialogMessage::Infinite(infinite) => {
+				self.infinite = infinite;
+				self.template = None;
+			}
+			NewDocumentDialogMessage::DimensionsX(x) => {
+				self.dimensions.x = x as u32;
+				self.template = None;
+			}
+			NewDocumentDialogMessage::DimensionsY(y) => {
+				self.dimensions.y = y as u32;
+				self.template = None;
+			}
+			NewDocumentDialogMessage::Template(template) => {
+				self.template = template;
+				if let Some(artboard) = template.and_then(|index| document_templates().get(index)).and_then(|template| template.artboards.first()) {
+					self.infinite = false;
+					self.dimensions = UVec2::new(artboard.width, artboard.height);
+				}
+			}
 
 			NewDocumentDialogMessage::Submit => {
 				responses.add(PortfolioMessage::NewDocumentWithName { name: self.name.clone() });
 
-				if !self.infinite && self.dimensions.x > 0 && self.dimensions.y > 0 {
+				if let Some(template) = self.template.and_then(|index| document_templates().get(index)) {
+					template.instantiate(responses);
+					responses.add(DocumentMessage::DeselectAllLayers);
+					responses.add(DocumentMessage::ZoomCanvasToFitAll);
+				} else if !self.infinite && self.dimensions.x > 0 && self.dimensions.y > 0 {
 					let id = generate_uuid();
 					responses.add(ArtboardMessage::AddArtboard {
 						id: Some(id),
@@ -60,6 +83,37 @@ impl LayoutHolder for NewDocumentDialogMessageHandler {
 				.widget_holder(),
 		];
 
+		// Templates are listed under a heading for each of their categories, in the order the categories first appear
+		let mut categories: Vec<(&str, Vec<usize>)> = Vec::new();
+		for (index, template) in document_templates().iter().enumerate() {
+			match categories.iter_mut().find(|(category, _)| *category == template.category) {
+				Some((_, indices)) => indices.push(index),
+				None => categories.push((&template.category, vec![index])),
+			}
+		}
+		let sections = std::iter::once(vec![None])
+			.chain(categories.into_iter().map(|(_, indices)| indices.into_iter().map(Some).collect()))
+			.collect::<Vec<Vec<_>>>();
+		let selected_template = sections.iter().flatten().position(|&index| index == self.template);
+		let template_entries = sections
+			.into_iter()
+			.map(|section| {
+				section
+					.into_iter()
+					.map(|index| {
+						let label = index.and_then(|index| document_templates().get(index)).map_or("None", |template| template.name.as_str());
+						DropdownEntryData::new(label).on_update(move |_| NewDocumentDialogMessage::Template(index).into())
+					})
+					.collect()
+			})
+			.collect();
+
+		let template = vec![
+			TextLabel::new("Template").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(template_entries).selected_index(selected_template.map(|index| index as u32)).widget_holder(),
+		];
+
 		let infinite = vec![
 			TextLabel::new("Infinite Canvas").table_align(true).widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
@@ -109,6 +163,7 @@ impl LayoutHolder for NewDocumentDialogMessageHandler {
 		Layout::WidgetLayout(WidgetLayout::new(vec![
 			LayoutGroup::Row { widgets: title },
 			LayoutGroup::Row { widgets: name },
+			LayoutGroup::Row { widgets: template },
 			LayoutGroup::Row { widgets: infinite },
 			LayoutGroup::Row { widgets: scale },
 			LayoutGroup::Row { widgets: button_widgets },
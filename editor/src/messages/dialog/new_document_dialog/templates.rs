@@ -0,0 +1,190 @@
+//! The built-in templates offered when creating a new document, for common social media, video, and print sizes. Each is a set of artboards
+//! with guide layers marking their safe areas, which show where content stays clear of cropping, trimming, or an app's interface.
+//!
+//! The templates are kept as data in `templates.json` next to this file, so adding or adjusting one doesn't require touching the code.
+
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils;
+
+use graphene_core::uuid::generate_uuid;
+use graphene_core::vector::style::{Fill, Stroke};
+use graphene_core::Color;
+
+use glam::{DVec2, IVec2};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+/// Space left between the artboards of templates with more than one, such as the slides of a carousel.
+pub const TEMPLATE_ARTBOARD_SPACING: f64 = 100.;
+
+/// Stroke of the safe area guide layers, a dashed magenta that stands out from most artwork.
+const SAFE_AREA_GUIDE_COLOR: Color = Color::from_rgbf32_unchecked(1., 0., 1.);
+const SAFE_AREA_GUIDE_WEIGHT: f64 = 2.;
+const SAFE_AREA_GUIDE_DASH: f32 = 12.;
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DocumentTemplate {
+	pub name: String,
+	/// The heading the template is listed under, like "Social Media" or "Print".
+	pub category: String,
+	/// Laid out from left to right in the new document.
+	pub artboards: Vec<TemplateArtboard>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct TemplateArtboard {
+	pub name: String,
+	pub width: u32,
+	pub height: u32,
+	#[serde(default)]
+	pub safe_areas: Vec<SafeArea>,
+}
+
+/// A region of an artboard, relative to its top left corner, outlined by a guide layer.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SafeArea {
+	pub name: String,
+	pub x: f64,
+	pub y: f64,
+	pub width: f64,
+	pub height: f64,
+}
+
+const TEMPLATES_JSON: &str = include_str!("templates.json");
+
+static DOCUMENT_TEMPLATES: Lazy<Vec<DocumentTemplate>> = Lazy::new(|| {
+	serde_json::from_str(TEMPLATES_JSON).unwrap_or_else(|error| {
+		warn!("The built-in document templates could not be read: {error}");
+		Vec::new()
+	})
+});
+
+pub fn document_templates() -> &'static [DocumentTemplate] {
+	&DOCUMENT_TEMPLATES
+}
+
+impl DocumentTemplate {
+	/// The top left corner of each artboard, placed side by side with their tops aligned.
+	pub fn artboard_positions(&self) -> Vec<DVec2> {
+		let mut x = 0.;
+		self.artboards
+			.iter()
+			.map(|artboard| {
+				let position = DVec2::new(x, 0.);
+				x += artboard.width as f64 + TEMPLATE_ARTBOARD_SPACING;
+				position
+			})
+			.collect()
+	}
+
+	/// Adds the artboards and safe area guides of the template to the active document, which is expected to be newly created.
+	pub fn instantiate(&self, responses: &mut VecDeque<Message>) {
+		let positions = self.artboard_positions();
+
+		for (artboard, &position) in self.artboards.iter().zip(&positions) {
+			let id = generate_uuid();
+			let size = IVec2::new(artboard.width as i32, artboard.height as i32);
+			responses.add(ArtboardMessage::AddArtboard {
+				id: Some(id),
+				position: position.into(),
+				size: size.as_dvec2().into(),
+			});
+			responses.add(GraphOperationMessage::NewArtboard {
+				id,
+				artboard: graphene_core::Artboard::new(position.as_ivec2(), size),
+			});
+		}
+
+		if self.artboards.iter().all(|artboard| artboard.safe_areas.is_empty()) {
+			return;
+		}
+
+		// The guides are kept together in a folder which can be hidden, or deleted once the design is done
+		let folder_path = vec![generate_uuid()];
+		responses.add(DocumentOperation::CreateFolder {
+			path: folder_path.clone(),
+			insert_index: -1,
+		});
+		responses.add(DocumentOperation::SetLayerName {
+			path: folder_path.clone(),
+			name: "Safe Areas".to_string(),
+		});
+		// The guides are only there to design around, so they're left out of exports
+		responses.add(DocumentOperation::SetLayerExportVisibility {
+			path: folder_path.clone(),
+			export_visible: Some(false),
+		});
+
+		let stroke = Stroke {
+			dash_lengths: vec![SAFE_AREA_GUIDE_DASH, SAFE_AREA_GUIDE_DASH],
+			..Stroke::new(Some(SAFE_AREA_GUIDE_COLOR), SAFE_AREA_GUIDE_WEIGHT)
+		};
+		for (artboard, &position) in self.artboards.iter().zip(&positions) {
+			for safe_area in &artboard.safe_areas {
+				let path = [folder_path.as_slice(), &[generate_uuid()]].concat();
+				let min = position + DVec2::new(safe_area.x, safe_area.y);
+				let max = min + DVec2::new(safe_area.width, safe_area.height);
+
+				graph_modification_utils::new_vector_layer(vec![bezier_rs::Subpath::new_rect(min, max)], path.clone(), responses);
+				responses.add(GraphOperationMessage::FillSet {
+					layer: path.clone(),
+					fill: Fill::None,
+				});
+				responses.add(GraphOperationMessage::StrokeSet {
+					layer: path.clone(),
+					stroke: stroke.clone(),
+				});
+
+				let name = match self.artboards.len() {
+					1 => safe_area.name.clone(),
+					_ => format!("{}: {}", artboard.name, safe_area.name),
+				};
+				responses.add(DocumentOperation::SetLayerName { path: path.clone(), name });
+				responses.add(DocumentOperation::SetLayerExportVisibility { path, export_visible: Some(false) });
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn templates_deserialize_against_the_current_format() {
+		let templates: Vec<DocumentTemplate> = serde_json::from_str(TEMPLATES_JSON).expect("the built-in templates should match the template format");
+		assert!(!templates.is_empty());
+		assert_eq!(document_templates().len(), templates.len());
+
+		for template in &templates {
+			assert!(!template.artboards.is_empty(), "{} has no artboards", template.name);
+			assert_eq!(templates.iter().filter(|other| other.name == template.name).count(), 1, "{} is listed more than once", template.name);
+
+			for artboard in &template.artboards {
+				assert!(artboard.width > 0 && artboard.height > 0, "{}: {} is empty", template.name, artboard.name);
+
+				for safe_area in &artboard.safe_areas {
+					let inside = safe_area.x >= 0.
+						&& safe_area.y >= 0.
+						&& safe_area.width > 0.
+						&& safe_area.height > 0.
+						&& safe_area.x + safe_area.width <= artboard.width as f64
+						&& safe_area.y + safe_area.height <= artboard.height as f64;
+					assert!(inside, "{}: {} has its {} outside of it", template.name, artboard.name, safe_area.name);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn artboards_are_placed_side_by_side() {
+		let template = document_templates().iter().find(|template| template.artboards.len() > 1).unwrap();
+		let positions = template.artboard_positions();
+
+		assert_eq!(positions[0], DVec2::ZERO);
+		assert_eq!(positions[1], DVec2::new(template.artboards[0].width as f64 + TEMPLATE_ARTBOARD_SPACING, 0.));
+	}
+}
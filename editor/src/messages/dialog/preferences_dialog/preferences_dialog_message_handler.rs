@@ -1,6 +1,15 @@
+use crate::consts::{KEY_REPEAT_MIN_TIME, MAX_DOCUMENT_BACKUPS};
+use crate::messages::input_mapper::utility_types::input_keyboard::Key;
+use crate::messages::input_mapper::utility_types::misc::{MiddleDragBehavior, PointerRole, RoleTrigger};
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::overlays::utility_types::OverlayColors;
+use crate::messages::portfolio::document::utility_types::misc::NewLayerPlacement;
+use crate::messages::preferences::utility_types::{format_zoom_levels, parse_zoom_levels, NumberLocale};
 use crate::messages::prelude::*;
 
+use document_legacy::consts::MAX_FOLDER_DEPTH;
+use graphene_core::raster::color::Color;
+
 /// A dialog to allow users to customize Graphite editor options
 #[derive(Debug, Clone, Default)]
 pub struct PreferencesDialogMessageHandler {}
@@ -41,6 +50,311 @@ impl PreferencesDialogMessageHandler {
 				.widget_holder(),
 		];
 
+		let middle_drag_entries = [("Pan", MiddleDragBehavior::Pan), ("Rotate", MiddleDragBehavior::Rotate), ("Zoom", MiddleDragBehavior::Zoom)]
+			.into_iter()
+			.map(|(label, middle_drag)| RadioEntryData::new(label).on_update(move |_| PreferencesMessage::MiddleDrag { middle_drag }.into()))
+			.collect();
+		let middle_drag = vec![
+			TextLabel::new("").min_width(60).widget_holder(),
+			TextLabel::new("Middle Drag").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(middle_drag_entries)
+				.selected_index(preferences.middle_drag as u32)
+				.tooltip("What dragging with the middle mouse button does to the canvas, which swaps places with the shortcut that otherwise does it (Ctrl to rotate, Shift to zoom)")
+				.widget_holder(),
+		];
+
+		let pointer_role = |role: PointerRole, label: &str, tooltip: &str| {
+			let key_name = |key: Key| match key {
+				Key::Control => "Ctrl",
+				Key::Command => "Cmd",
+				Key::Alt => "Alt",
+				Key::Shift => "Shift",
+				_ => "Space",
+			};
+			let clicks = RoleTrigger::CLICK_KEYS.map(RoleTrigger::Click);
+			let holds = RoleTrigger::HOLD_KEYS.map(RoleTrigger::Hold);
+			let triggers = [RoleTrigger::None].into_iter().chain(clicks).chain(holds).collect::<Vec<_>>();
+			let selected_index = triggers.iter().position(|&trigger| trigger == preferences.pointer_roles.get(role)).map(|index| index as u32);
+
+			let entry = |trigger: RoleTrigger| {
+				let label = match trigger {
+					RoleTrigger::None => "Mouse Button Only".to_string(),
+					RoleTrigger::Click(key) => format!("{} + Click", key_name(key)),
+					RoleTrigger::Hold(key) => format!("Hold {}", key_name(key)),
+				};
+				DropdownEntryData::new(label).on_update(move |_| PreferencesMessage::PointerRole { role, trigger }.into())
+			};
+			let entries = vec![vec![entry(RoleTrigger::None)], clicks.into_iter().map(entry).collect(), holds.into_iter().map(entry).collect()];
+
+			vec![
+				TextLabel::new("").min_width(60).widget_holder(),
+				TextLabel::new(label).table_align(true).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				DropdownInput::new(entries).selected_index(selected_index).tooltip(tooltip).widget_holder(),
+			]
+		};
+		let pan_trigger = pointer_role(
+			PointerRole::Pan,
+			"Pan Trigger",
+			"What else pans the canvas like middle dragging: clicking with a key held, or holding a key alone so a trackpad pans without pressing",
+		);
+		let zoom_trigger = pointer_role(
+			PointerRole::Zoom,
+			"Zoom Trigger",
+			"What else zooms the canvas like Shift + middle dragging: clicking with a key held, or holding a key alone so a trackpad zooms without pressing",
+		);
+		let context_menu_trigger = pointer_role(
+			PointerRole::ContextMenu,
+			"Right Click Trigger",
+			"What else acts as a right click, which cancels and confirms what the tools are doing: clicking with a key held, or pressing a key alone",
+		);
+
+		let key_repeat = vec![
+			TextLabel::new("").min_width(60).widget_holder(),
+			TextLabel::new("Key Repeat").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.key_repeat_delay))
+				.label("Delay")
+				.unit(" ms")
+				.min(KEY_REPEAT_MIN_TIME)
+				.is_integer(true)
+				.tooltip("How long a key is held before its action starts repeating, for actions like nudging that repeat")
+				.on_update(|number_input: &NumberInput| PreferencesMessage::KeyRepeatDelay { delay: number_input.value.unwrap() }.into())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(preferences.key_repeat_interval))
+				.label("Interval")
+				.unit(" ms")
+				.min(KEY_REPEAT_MIN_TIME)
+				.is_integer(true)
+				.tooltip("How long between repeats of the action of a held key")
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::KeyRepeatInterval {
+						interval: number_input.value.unwrap(),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let repeat_undo_redo = vec![
+			TextLabel::new("").min_width(60).widget_holder(),
+			TextLabel::new("Repeat Undo/Redo").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.repeat_undo_redo)
+				.tooltip("Keep undoing or redoing while the shortcut is held, rather than once for each press")
+				.on_update(|checkbox_input: &CheckboxInput| {
+					PreferencesMessage::RepeatUndoRedo {
+						repeat_undo_redo: checkbox_input.checked,
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let smooth_navigation = vec![
+			TextLabel::new("").min_width(60).widget_holder(),
+			TextLabel::new("Smooth Navigation").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.smooth_navigation)
+				.tooltip("Animate zooming to fit, zooming to a set level, and resetting the rotation, and let the canvas keep drifting when a quick pan is released")
+				.on_update(|checkbox_input: &CheckboxInput| {
+					PreferencesMessage::SmoothNavigation {
+						smooth_navigation: checkbox_input.checked,
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let zoom_levels = vec![
+			TextLabel::new("").min_width(60).widget_holder(),
+			TextLabel::new("Zoom Levels").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(format_zoom_levels(&preferences.zoom_levels))
+				.min_width(200)
+				.tooltip("The zoom percentages that zooming in and out steps between, separated by semicolons (clear to restore the defaults)")
+				.on_update(|text_input: &TextInput| {
+					PreferencesMessage::ZoomLevels {
+						zoom_levels: parse_zoom_levels(&text_input.value),
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let css_pixel_zoom = vec![
+			TextLabel::new("").min_width(60).widget_holder(),
+			TextLabel::new("Zoom in CSS Pixels").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.css_pixel_zoom)
+				.tooltip("Make 100% zoom show one document pixel per CSS pixel instead of per physical screen pixel, so the canvas appears larger on high-DPI displays")
+				.on_update(|checkbox_input: &CheckboxInput| {
+					PreferencesMessage::CssPixelZoom {
+						css_pixel_zoom: checkbox_input.checked,
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let overlay_colors = preferences.overlay_colors;
+		let high_contrast_overlays = vec![
+			TextLabel::new("Overlays").min_width(60).italic(true).widget_holder(),
+			TextLabel::new("High Contrast").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(overlay_colors.is_high_contrast())
+				.tooltip("Draw selection outlines, handles, and snap guides in colors that stand out against most artwork")
+				.on_update(|checkbox_input: &CheckboxInput| {
+					let overlay_colors = if checkbox_input.checked { OverlayColors::HIGH_CONTRAST } else { OverlayColors::DEFAULT };
+					PreferencesMessage::OverlayColors { overlay_colors }.into()
+				})
+				.widget_holder(),
+		];
+
+		let larger_handles = vec![
+			TextLabel::new("").min_width(60).widget_holder(),
+			TextLabel::new("Larger Handles").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.larger_handles)
+				.tooltip("Draw bigger anchor points, handles, and transform controls, and widen the area around them that responds to clicks (useful for touch input)")
+				.on_update(|checkbox_input: &CheckboxInput| {
+					PreferencesMessage::LargerHandles {
+						larger_handles: checkbox_input.checked,
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let overlay_color_row = |label: &str, tooltip: &str, color: Color, modify: fn(&mut OverlayColors, Color)| {
+			vec![
+				TextLabel::new("").min_width(60).widget_holder(),
+				TextLabel::new(label).table_align(true).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				ColorInput::new(Some(color))
+					.allow_none(false)
+					.tooltip(tooltip)
+					.on_update(move |color_input: &ColorInput| {
+						let mut overlay_colors = overlay_colors;
+						modify(&mut overlay_colors, color_input.value.unwrap_or(color));
+						PreferencesMessage::OverlayColors { overlay_colors }.into()
+					})
+					.widget_holder(),
+			]
+		};
+		let selection_outline_color = overlay_color_row(
+			"Selection Outline",
+			"Color of the outlines drawn around selected and hovered layers",
+			overlay_colors.selection_outline,
+			|overlay_colors, color| overlay_colors.selection_outline = color,
+		);
+		let handles_color = overlay_color_row(
+			"Handles",
+			"Color of the anchor points, handles, and transform controls",
+			overlay_colors.handles,
+			|overlay_colors, color| overlay_colors.handles = color,
+		);
+		let snap_guides_color = overlay_color_row(
+			"Snap Guides",
+			"Color of the alignment lines and points shown while snapping",
+			overlay_colors.snap_guides,
+			|overlay_colors, color| overlay_colors.snap_guides = color,
+		);
+
+		let new_layer_placement_entries = [
+			("Top", NewLayerPlacement::TopOfDocument),
+			("Above Selection", NewLayerPlacement::AboveSelection),
+			("Into Folder", NewLayerPlacement::IntoSelectedFolder),
+		]
+		.into_iter()
+		.map(|(label, placement)| RadioEntryData::new(label).on_update(move |_| PreferencesMessage::NewLayerPlacement { placement }.into()))
+		.collect();
+		let new_layer_placement = vec![
+			TextLabel::new("Layers").min_width(60).italic(true).widget_holder(),
+			TextLabel::new("New Layer Placement").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(new_layer_placement_entries)
+				.selected_index(preferences.new_layer_placement as u32)
+				.tooltip("Where drawn layers are inserted: at the top of the document, directly above the topmost selected layer, or at the top of the selected folder (or the folder containing the selection)")
+				.widget_holder(),
+		];
+
+		let duplicate_offset = vec![
+			TextLabel::new("").min_width(60).widget_holder(),
+			TextLabel::new("Duplicate Offset").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.duplicate_offset))
+				.unit(" px")
+				.min_width(200)
+				.tooltip("Distance down and to the right that \"Duplicate with Offset\" moves the duplicated layers")
+				.on_update(|number_input: &NumberInput| PreferencesMessage::DuplicateOffset { offset: number_input.value.unwrap() }.into())
+				.widget_holder(),
+		];
+
+		let max_folder_depth = vec![
+			TextLabel::new("").min_width(60).widget_holder(),
+			TextLabel::new("Max Folder Depth").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.max_folder_depth as f64))
+				.min(1.)
+				.max(MAX_FOLDER_DEPTH as f64)
+				.is_integer(true)
+				.min_width(200)
+				.tooltip("How many folders deep layers may be nested by grouping, moving, and pasting them")
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::MaxFolderDepth {
+						depth: number_input.value.unwrap() as u32,
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let number_locale_entries = [("1.5", NumberLocale::Point), ("1,5", NumberLocale::Comma)]
+			.into_iter()
+			.map(|(label, locale)| RadioEntryData::new(label).on_update(move |_| PreferencesMessage::NumberLocale { locale }.into()))
+			.collect();
+		let number_locale = vec![
+			TextLabel::new("Numbers").min_width(60).italic(true).widget_holder(),
+			TextLabel::new("Decimal Separator").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(number_locale_entries)
+				.selected_index(preferences.number_locale as u32)
+				.tooltip("Whether numbers in the readouts, rulers, and number inputs are written with a decimal point or a decimal comma. Either one is accepted when typing numbers.")
+				.widget_holder(),
+		];
+
+		let backup_count = vec![
+			TextLabel::new("Files").min_width(60).italic(true).widget_holder(),
+			TextLabel::new("Backups Kept on Save").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(preferences.backup_count as f64))
+				.min(0.)
+				.max(MAX_DOCUMENT_BACKUPS as f64)
+				.is_integer(true)
+				.min_width(200)
+				.tooltip("How many of the previous saves of each document are kept in the browser's storage, to be restored with File > Restore Backup if a save is regretted. None are kept when 0.")
+				.on_update(|number_input: &NumberInput| {
+					PreferencesMessage::BackupCount {
+						count: number_input.value.unwrap() as u32,
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let usage_statistics = vec![
+			TextLabel::new("Privacy").min_width(60).italic(true).widget_holder(),
+			TextLabel::new("Record Usage Statistics").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(preferences.usage_statistics.is_some())
+				.tooltip("Keep statistics of the time spent with each tool, the operations done, and the documents worked on, shown in Help > Usage Statistics. They stay on this device and are never sent anywhere. Turning this off deletes them.")
+				.on_update(|checkbox_input: &CheckboxInput| PreferencesMessage::UsageStatistics { enabled: checkbox_input.checked }.into())
+				.widget_holder(),
+		];
+
 		let imaginate_server_hostname = vec![
 			TextLabel::new("Imaginate").min_width(60).italic(true).widget_holder(),
 			TextLabel::new("Server Hostname").table_align(true).widget_holder(),
@@ -85,6 +399,26 @@ impl PreferencesDialogMessageHandler {
 				widgets: vec![TextLabel::new("Editor Preferences").bold(true).widget_holder()],
 			},
 			LayoutGroup::Row { widgets: zoom_with_scroll },
+			LayoutGroup::Row { widgets: middle_drag },
+			LayoutGroup::Row { widgets: pan_trigger },
+			LayoutGroup::Row { widgets: zoom_trigger },
+			LayoutGroup::Row { widgets: context_menu_trigger },
+			LayoutGroup::Row { widgets: key_repeat },
+			LayoutGroup::Row { widgets: repeat_undo_redo },
+			LayoutGroup::Row { widgets: smooth_navigation },
+			LayoutGroup::Row { widgets: zoom_levels },
+			LayoutGroup::Row { widgets: css_pixel_zoom },
+			LayoutGroup::Row { widgets: high_contrast_overlays },
+			LayoutGroup::Row { widgets: larger_handles },
+			LayoutGroup::Row { widgets: selection_outline_color },
+			LayoutGroup::Row { widgets: handles_color },
+			LayoutGroup::Row { widgets: snap_guides_color },
+			LayoutGroup::Row { widgets: new_layer_placement },
+			LayoutGroup::Row { widgets: duplicate_offset },
+			LayoutGroup::Row { widgets: max_folder_depth },
+			LayoutGroup::Row { widgets: number_locale },
+			LayoutGroup::Row { widgets: backup_count },
+			LayoutGroup::Row { widgets: usage_statistics },
 			LayoutGroup::Row { widgets: imaginate_server_hostname },
 			LayoutGroup::Row { widgets: imaginate_refresh_frequency },
 			LayoutGroup::Row { widgets: button_widgets },
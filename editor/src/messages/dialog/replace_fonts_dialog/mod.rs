@@ -0,0 +1,7 @@
+mod replace_fonts_dialog_message;
+mod replace_fonts_dialog_message_handler;
+
+#[doc(inline)]
+pub use replace_fonts_dialog_message::{ReplaceFontsDialogMessage, ReplaceFontsDialogMessageDiscriminant};
+#[doc(inline)]
+pub use replace_fonts_dialog_message_handler::ReplaceFontsDialogMessageHandler;
@@ -0,0 +1,13 @@
+use crate::messages::prelude::*;
+
+use graphene_core::text::Font;
+
+use serde::{Deserialize, Serialize};
+
+#[impl_message(Message, DialogMessage, ReplaceFontsDialog)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum ReplaceFontsDialogMessage {
+	Replacement { index: usize, font: Font },
+
+	Submit,
+}
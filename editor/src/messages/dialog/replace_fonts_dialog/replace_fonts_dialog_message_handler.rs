@@ -0,0 +1,92 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+use graphene_core::text::Font;
+
+/// A dialog listing every font used by the document's text layers, where each can be swapped for another font across all of them at once.
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceFontsDialogMessageHandler {
+	/// The fonts used in the document along with how many text layers use each.
+	pub fonts: Vec<(Font, usize)>,
+	/// The font chosen to replace each of the used fonts, which starts out as the font itself.
+	pub replacements: Vec<Font>,
+}
+
+impl MessageHandler<ReplaceFontsDialogMessage, ()> for ReplaceFontsDialogMessageHandler {
+	fn process_message(&mut self, message: ReplaceFontsDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			ReplaceFontsDialogMessage::Replacement { index, font } => {
+				if let Some(replacement) = self.replacements.get_mut(index) {
+					responses.add(FrontendMessage::TriggerFontLoad {
+						font: font.clone(),
+						is_default: false,
+					});
+					*replacement = font;
+				}
+			}
+
+			ReplaceFontsDialogMessage::Submit => {
+				let replacements = self.fonts.iter().map(|(font, _)| font.clone()).zip(self.replacements.iter().cloned()).collect();
+				responses.add_front(DocumentMessage::ReplaceFonts { replacements });
+			}
+		}
+
+		self.send_layout(responses, LayoutTarget::DialogDetails);
+	}
+
+	advertise_actions! {ReplaceFontsDialogUpdate;}
+}
+
+impl LayoutHolder for ReplaceFontsDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let title = vec![TextLabel::new("Replace Fonts").bold(true).widget_holder()];
+
+		let font_rows = self.fonts.iter().zip(&self.replacements).enumerate().map(|(index, ((font, layer_count), replacement))| {
+			let plural = if *layer_count == 1 { "" } else { "s" };
+			let on_update = move |font_input: &FontInput| -> Message {
+				let font = Font::new(font_input.font_family.clone(), font_input.font_style.clone());
+				ReplaceFontsDialogMessage::Replacement { index, font }.into()
+			};
+
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new(format!("{} {} ({layer_count} layer{plural})", font.font_family, font.font_style))
+						.table_align(true)
+						.widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					FontInput::new(replacement.font_family.clone(), replacement.font_style.clone()).on_update(on_update).widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					FontInput::new(replacement.font_family.clone(), replacement.font_style.clone())
+						.is_style_picker(true)
+						.on_update(on_update)
+						.widget_holder(),
+				],
+			}
+		});
+
+		let empty_notice = self.fonts.is_empty().then(|| LayoutGroup::Row {
+			widgets: vec![TextLabel::new("The document has no text layers").italic(true).widget_holder()],
+		});
+
+		let button_widgets = vec![
+			TextButton::new("OK")
+				.min_width(96)
+				.emphasized(true)
+				.disabled(self.fonts.is_empty())
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![ReplaceFontsDialogMessage::Submit.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		let mut layout = vec![LayoutGroup::Row { widgets: title }];
+		layout.extend(font_rows);
+		layout.extend(empty_notice);
+		layout.push(LayoutGroup::Row { widgets: button_widgets });
+		Layout::WidgetLayout(WidgetLayout::new(layout))
+	}
+}
@@ -0,0 +1,124 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::utility_types::restored_backup_name;
+use crate::messages::prelude::*;
+
+use document_legacy::diff::{DocumentDiff, LayerChange};
+
+/// The most differences listed before the rest are summarized as a count, keeping the dialog from growing taller than the window.
+const MAX_LISTED_DIFFERENCES: usize = 12;
+
+/// A dialog shown when a document is recovered from its auto-save with changes newer than its last save, summarizing how the two differ so either can be kept.
+pub struct AutoSaveRecoveryDialog {
+	pub document_id: u64,
+	pub document_name: String,
+	pub file_name: String,
+	/// When the document was last saved, already formatted for the user's locale.
+	pub saved_at: String,
+	/// The differences from the saved version to the recovered one.
+	pub diff: DocumentDiff,
+	pub saved_document: String,
+}
+
+/// Describes the differences between two versions of a document, one line each, for listing them in a dialog.
+pub fn difference_lines(diff: &DocumentDiff) -> Vec<String> {
+	let mut lines = diff
+		.layers
+		.iter()
+		.take(MAX_LISTED_DIFFERENCES)
+		.map(|layer| {
+			let name = match (&layer.name, layer.path.is_empty()) {
+				(_, true) => "Top level".to_string(),
+				(Some(name), false) if !name.is_empty() => format!("\"{name}\""),
+				_ => "Unnamed layer".to_string(),
+			};
+			match &layer.change {
+				LayerChange::Added => format!("Added {name}"),
+				LayerChange::Removed => format!("Removed {name}"),
+				LayerChange::Changed(properties) => {
+					let properties = properties.iter().map(|property| property.label()).collect::<Vec<_>>().join(", ");
+					format!("Changed the {properties} of {name}")
+				}
+			}
+		})
+		.collect::<Vec<_>>();
+
+	if diff.layers.len() > MAX_LISTED_DIFFERENCES {
+		lines.push(format!("…and {} more layers", diff.layers.len() - MAX_LISTED_DIFFERENCES));
+	}
+	if diff.text_styles_changed {
+		lines.push("Changed the text styles".to_string());
+	}
+
+	lines
+}
+
+impl LayoutHolder for AutoSaveRecoveryDialog {
+	fn layout(&self) -> Layout {
+		let document_id = self.document_id;
+		let document_name = self.document_name.clone();
+		let saved_document = self.saved_document.clone();
+		// The saved version is the latest backup, so it is named like one when opened alongside the recovered document
+		let (saved_name, saved_copy) = (restored_backup_name(&self.file_name, 1), self.saved_document.clone());
+
+		let (added, removed, changed) = self.diff.counts();
+		let summary = format!(
+			"\"{}\" was recovered with changes made since it was last saved {}.\nCompared to the saved version, {added} layer(s) were added, {removed} removed, and {changed} changed:",
+			self.document_name, self.saved_at
+		);
+
+		let button_widgets = vec![
+			TextButton::new("Keep Recovered")
+				.min_width(96)
+				.emphasized(true)
+				.tooltip("Keep the recovered changes, which are saved with the next save")
+				.on_update(|_| FrontendMessage::DisplayDialogDismiss.into())
+				.widget_holder(),
+			TextButton::new("Keep Saved")
+				.min_width(96)
+				.tooltip("Discard the recovered changes and reopen the document as it was last saved")
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![
+							PortfolioMessage::CloseDocument { document_id }.into(),
+							PortfolioMessage::OpenDocumentFile {
+								document_name: document_name.clone(),
+								document_serialized_content: saved_document.clone(),
+							}
+							.into(),
+						],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Keep Both")
+				.min_width(96)
+				.tooltip("Also open the saved version as a separate document, to compare it with the recovered one")
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![PortfolioMessage::OpenDocumentFile {
+							document_name: saved_name.clone(),
+							document_serialized_content: saved_copy.clone(),
+						}
+						.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+		];
+
+		let mut layout = vec![
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new("Recovered Unsaved Changes").bold(true).widget_holder()],
+			},
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new(summary).multiline(true).widget_holder()],
+			},
+		];
+		layout.extend(difference_lines(&self.diff).into_iter().map(|line| LayoutGroup::Row {
+			widgets: vec![TextLabel::new(line).widget_holder()],
+		}));
+		layout.push(LayoutGroup::Row { widgets: button_widgets });
+
+		Layout::WidgetLayout(WidgetLayout::new(layout))
+	}
+}
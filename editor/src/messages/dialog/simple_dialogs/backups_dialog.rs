@@ -0,0 +1,66 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::utility_types::{backup_file_name, restored_backup_name};
+use crate::messages::prelude::*;
+
+/// A dialog listing the backups kept from the previous saves of the active document, any of which can be opened as a separate document.
+pub struct BackupsDialog {
+	pub file_name: String,
+	/// When each backup was saved, starting from the most recent, already formatted for the user's locale.
+	pub save_times: Vec<String>,
+	pub backups_enabled: bool,
+}
+
+impl LayoutHolder for BackupsDialog {
+	fn layout(&self) -> Layout {
+		let mut layout = vec![LayoutGroup::Row {
+			widgets: vec![TextLabel::new("Restore Backup").bold(true).widget_holder()],
+		}];
+
+		for (index, save_time) in self.save_times.iter().enumerate() {
+			let generation = index + 1;
+			let file_name = self.file_name.clone();
+			let open = TextButton::new("Open")
+				.tooltip("Open this backup as a separate document, leaving the original file as it is")
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![FrontendMessage::TriggerOpenBackup {
+							file_name: file_name.clone(),
+							generation,
+							document_name: restored_backup_name(&file_name, generation),
+						}
+						.into()],
+					}
+					.into()
+				})
+				.widget_holder();
+
+			layout.push(LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new(backup_file_name(&self.file_name, generation)).table_align(true).min_width(200).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextLabel::new(format!("Saved {save_time}")).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					open,
+				],
+			});
+		}
+
+		if self.save_times.is_empty() {
+			let message = match self.backups_enabled {
+				true => format!(
+					"No backups of \"{}\" have been kept yet.\nThe previous save is kept each time the document is saved again.",
+					self.file_name
+				),
+				false => "Backups aren't being kept. Choose how many to keep with the\n\"Backups Kept on Save\" setting in the preferences.".to_string(),
+			};
+			layout.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new(message).multiline(true).widget_holder()],
+			});
+		}
+
+		let close = TextButton::new("Close").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder();
+		layout.push(LayoutGroup::Row { widgets: vec![close] });
+
+		Layout::WidgetLayout(WidgetLayout::new(layout))
+	}
+}
@@ -0,0 +1,44 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// A dialog warning that an operation may keep the editor busy for a long time, asking whether to go ahead with it.
+pub struct CostlyOperationDialog {
+	pub title: String,
+	pub description: String,
+	/// The operation, which runs without being estimated again once the user chooses to proceed.
+	pub operation: DocumentMessage,
+}
+
+impl LayoutHolder for CostlyOperationDialog {
+	fn layout(&self) -> Layout {
+		let operation = Box::new(self.operation.clone());
+
+		let button_widgets = vec![
+			TextButton::new("Proceed")
+				.min_width(96)
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![DocumentMessage::ProceedWithCostlyOperation { operation: operation.clone() }.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			// Cancelling is the safer choice, so it's the one focused when the dialog opens
+			TextButton::new("Cancel")
+				.min_width(96)
+				.emphasized(true)
+				.on_update(|_| FrontendMessage::DisplayDialogDismiss.into())
+				.widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new(&self.title).bold(true).widget_holder()],
+			},
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new(&self.description).multiline(true).widget_holder()],
+			},
+			LayoutGroup::Row { widgets: button_widgets },
+		]))
+	}
+}
@@ -0,0 +1,43 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+/// A dialog explaining that a document is open read-only because another window holds its lock, offering to take over the lock and save anyway.
+pub struct DocumentLockedDialog {
+	pub document_name: String,
+	pub document_id: u64,
+}
+
+impl LayoutHolder for DocumentLockedDialog {
+	fn layout(&self) -> Layout {
+		let document_id = self.document_id;
+
+		let button_widgets = vec![
+			TextButton::new("Override and Save")
+				.min_width(96)
+				.emphasized(true)
+				.on_update(move |_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![PortfolioMessage::OverrideDocumentLock { document_id }.into(), DocumentMessage::SaveDocument.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new("This document is open in another window").bold(true).widget_holder()],
+			},
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new(format!(
+					"\"{}\" was opened read-only because it is locked by another window or instance of Graphite.\nOverriding the lock lets this window save it, but changes made in the other window may then be lost.",
+					self.document_name
+				))
+				.multiline(true)
+				.widget_holder()],
+			},
+			LayoutGroup::Row { widgets: button_widgets },
+		]))
+	}
+}
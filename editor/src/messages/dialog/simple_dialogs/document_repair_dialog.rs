@@ -0,0 +1,65 @@
+use super::auto_save_recovery_dialog::difference_lines;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+use document_legacy::diff::DocumentDiff;
+
+/// A dialog reporting what repairing a document from its operation log changed, or why it couldn't be repaired.
+pub struct DocumentRepairDialog {
+	pub document_name: String,
+	/// The differences from the document as it was to the repaired one, or `None` if the operation log doesn't go back to when the document was created.
+	pub diff: Option<DocumentDiff>,
+	pub replayed_operations: usize,
+	/// How many of the logged operations couldn't be applied again, which are left out of the repaired document.
+	pub failed_operations: usize,
+}
+
+impl LayoutHolder for DocumentRepairDialog {
+	fn layout(&self) -> Layout {
+		let summary = match &self.diff {
+			None => format!(
+				"\"{}\" can't be checked for damage because its edits weren't recorded since it was created.\nThis is the case for files saved before edits were recorded, and for documents with too many edits to record.",
+				self.document_name
+			),
+			Some(diff) if diff.is_empty() => format!(
+				"\"{}\" was checked by redoing its {} edits since it was created, and no damage was found.",
+				self.document_name, self.replayed_operations
+			),
+			Some(diff) => {
+				let (added, removed, changed) = diff.counts();
+				format!(
+					"\"{}\" was repaired by redoing its {} edits since it was created. This can be undone.\n{added} layer(s) were added, {removed} removed, and {changed} changed:",
+					self.document_name, self.replayed_operations
+				)
+			}
+		};
+
+		let mut layout = vec![
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new("Repair Document").bold(true).widget_holder()],
+			},
+			LayoutGroup::Row {
+				widgets: vec![TextLabel::new(summary).multiline(true).widget_holder()],
+			},
+		];
+		if let Some(diff) = &self.diff {
+			layout.extend(difference_lines(diff).into_iter().map(|line| LayoutGroup::Row {
+				widgets: vec![TextLabel::new(line).widget_holder()],
+			}));
+		}
+		if self.failed_operations > 0 {
+			layout.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new(format!("{} edit(s) could no longer be applied and were left out.", self.failed_operations)).widget_holder()],
+			});
+		}
+		layout.push(LayoutGroup::Row {
+			widgets: vec![TextButton::new("OK")
+				.min_width(96)
+				.emphasized(true)
+				.on_update(|_| FrontendMessage::DisplayDialogDismiss.into())
+				.widget_holder()],
+		});
+
+		Layout::WidgetLayout(WidgetLayout::new(layout))
+	}
+}
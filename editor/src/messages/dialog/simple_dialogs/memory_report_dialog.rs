@@ -0,0 +1,57 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::memory_report::{format_bytes, MemoryReport};
+use crate::messages::prelude::*;
+
+/// A dialog estimating the memory held by each open document, with a button to reclaim it by purging their caches and undo history.
+pub struct MemoryReportDialog {
+	pub documents: Vec<(String, MemoryReport)>,
+}
+
+impl LayoutHolder for MemoryReportDialog {
+	fn layout(&self) -> Layout {
+		let row = |label: &str, bytes: usize| LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new(label).table_align(true).min_width(120).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(format_bytes(bytes)).widget_holder(),
+			],
+		};
+
+		let mut layout = vec![LayoutGroup::Row {
+			widgets: vec![TextLabel::new("Memory Usage").bold(true).widget_holder()],
+		}];
+		for (name, report) in &self.documents {
+			layout.extend([
+				LayoutGroup::Row {
+					widgets: vec![TextLabel::new(name).italic(true).widget_holder()],
+				},
+				row("Layer Caches", report.layer_caches),
+				row("Thumbnails", report.thumbnails),
+				row("Undo History", report.undo_history),
+				row("Path Data", report.path_data),
+				row("Total", report.total()),
+			]);
+		}
+		if self.documents.is_empty() {
+			layout.push(LayoutGroup::Row {
+				widgets: vec![TextLabel::new("No documents are open.").widget_holder()],
+			});
+		}
+
+		let purge = TextButton::new("Purge Caches & History")
+			.emphasized(true)
+			.tooltip("Free the rendered caches and the undo history of every open document, which can't be undone")
+			.disabled(self.documents.is_empty())
+			.on_update(|_| {
+				DialogMessage::CloseDialogAndThen {
+					followups: vec![PortfolioMessage::PurgeCachesAndHistory.into()],
+				}
+				.into()
+			})
+			.widget_holder();
+		let close = TextButton::new("Close").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder();
+		layout.push(LayoutGroup::Row { widgets: vec![purge, close] });
+
+		Layout::WidgetLayout(WidgetLayout::new(layout))
+	}
+}
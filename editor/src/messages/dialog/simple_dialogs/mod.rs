@@ -1,11 +1,25 @@
 mod about_graphite_dialog;
+mod auto_save_recovery_dialog;
+mod backups_dialog;
 mod close_all_documents_dialog;
 mod close_document_dialog;
 mod coming_soon_dialog;
+mod costly_operation_dialog;
+mod document_locked_dialog;
+mod document_repair_dialog;
 mod error_dialog;
+mod memory_report_dialog;
+mod usage_statistics_dialog;
 
 pub use about_graphite_dialog::AboutGraphiteDialog;
+pub use auto_save_recovery_dialog::AutoSaveRecoveryDialog;
+pub use backups_dialog::BackupsDialog;
 pub use close_all_documents_dialog::CloseAllDocumentsDialog;
 pub use close_document_dialog::CloseDocumentDialog;
 pub use coming_soon_dialog::ComingSoonDialog;
+pub use costly_operation_dialog::CostlyOperationDialog;
+pub use document_locked_dialog::DocumentLockedDialog;
+pub use document_repair_dialog::DocumentRepairDialog;
 pub use error_dialog::ErrorDialog;
+pub use memory_report_dialog::MemoryReportDialog;
+pub use usage_statistics_dialog::UsageStatisticsDialog;
@@ -0,0 +1,90 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::preferences::usage_statistics::{format_duration, UsageStatistics};
+use crate::messages::prelude::*;
+
+/// A personal dashboard of the usage statistics recorded on this device, or an offer to start recording them if they are turned off.
+pub struct UsageStatisticsDialog {
+	pub statistics: Option<UsageStatistics>,
+}
+
+impl LayoutHolder for UsageStatisticsDialog {
+	fn layout(&self) -> Layout {
+		let row = |label: &str, value: String| LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new(label).table_align(true).min_width(160).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextLabel::new(value).widget_holder(),
+			],
+		};
+		let heading = |text: &str| LayoutGroup::Row {
+			widgets: vec![TextLabel::new(text).italic(true).widget_holder()],
+		};
+		let close = TextButton::new("Close").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder();
+
+		let mut layout = vec![LayoutGroup::Row {
+			widgets: vec![TextLabel::new("Usage Statistics").bold(true).widget_holder()],
+		}];
+
+		let Some(statistics) = &self.statistics else {
+			layout.push(LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Recording is off. Statistics are only ever kept on this device, for your own insight, and are never sent anywhere.")
+						.multiline(true)
+						.widget_holder(),
+				],
+			});
+			let turn_on = TextButton::new("Start Recording")
+				.emphasized(true)
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![PreferencesMessage::UsageStatistics { enabled: true }.into(), DialogMessage::RequestUsageStatisticsDialog.into()],
+					}
+					.into()
+				})
+				.widget_holder();
+			layout.push(LayoutGroup::Row { widgets: vec![turn_on, close] });
+			return Layout::WidgetLayout(WidgetLayout::new(layout));
+		};
+
+		let days = unix_time_milliseconds().map_or(0., |now| ((now - statistics.since) / 86_400_000.).max(0.)).ceil();
+		layout.push(row("Recorded Over", format!("{days} {}", if days == 1. { "day" } else { "days" })));
+		layout.push(row("Active Time", format_duration(statistics.active_milliseconds)));
+
+		layout.push(heading("Time per Tool"));
+		let tools = statistics.tools_by_time();
+		if tools.is_empty() {
+			layout.push(row("", "No tools used yet".to_string()));
+		}
+		layout.extend(tools.into_iter().map(|(tool, milliseconds)| row(tool, format_duration(milliseconds))));
+
+		layout.push(heading("Documents"));
+		let documents = statistics.documents;
+		layout.extend([
+			row("Created", documents.created.to_string()),
+			row("Opened", documents.opened.to_string()),
+			row("Saved", documents.saved.to_string()),
+			row("Exported", documents.exported.to_string()),
+			row("Closed", documents.closed.to_string()),
+		]);
+
+		layout.push(heading("Most Frequent Operations"));
+		let operations = statistics.top_operations();
+		if operations.is_empty() {
+			layout.push(row("", "No operations done yet".to_string()));
+		}
+		layout.extend(operations.into_iter().map(|(operation, count)| row(operation, count.to_string())));
+
+		let copy_text = serde_json::to_string_pretty(statistics).unwrap_or_default();
+		let copy = TextButton::new("Copy as JSON")
+			.tooltip("Copy the statistics to the clipboard, to keep or look into elsewhere")
+			.on_update(move |_| FrontendMessage::TriggerTextCopy { copy_text: copy_text.clone() }.into())
+			.widget_holder();
+		let reset = TextButton::new("Reset")
+			.tooltip("Clear the statistics recorded so far and start over")
+			.on_update(|_| PreferencesMessage::ResetUsageStatistics.into())
+			.widget_holder();
+		layout.push(LayoutGroup::Row { widgets: vec![copy, reset, close] });
+
+		Layout::WidgetLayout(WidgetLayout::new(layout))
+	}
+}
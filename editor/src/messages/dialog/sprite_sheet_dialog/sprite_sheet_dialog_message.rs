@@ -0,0 +1,16 @@
+use crate::messages::portfolio::document::utility_types::sprite_sheet::SpriteSource;
+use crate::messages::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+#[impl_message(Message, DialogMessage, SpriteSheetDialog)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum SpriteSheetDialogMessage {
+	FileName(String),
+	Source(SpriteSource),
+	ScaleFactor(f64),
+	Spacing(f64),
+	TransparentBackground(bool),
+
+	Submit,
+}
@@ -0,0 +1,122 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::sprite_sheet::SpriteSource;
+use crate::messages::prelude::*;
+
+/// A dialog to pack artboards or selected layers into a sprite sheet image with a JSON atlas describing where each one is.
+#[derive(Debug, Clone, Default)]
+pub struct SpriteSheetDialogMessageHandler {
+	pub file_name: String,
+	pub source: SpriteSource,
+	pub scale_factor: f64,
+	/// Empty pixels left between neighboring sprites.
+	pub spacing: u32,
+	pub transparent_background: bool,
+	pub has_artboards: bool,
+	pub has_selection: bool,
+}
+
+impl MessageHandler<SpriteSheetDialogMessage, ()> for SpriteSheetDialogMessageHandler {
+	fn process_message(&mut self, message: SpriteSheetDialogMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		match message {
+			SpriteSheetDialogMessage::FileName(name) => self.file_name = name,
+			SpriteSheetDialogMessage::Source(source) => self.source = source,
+			SpriteSheetDialogMessage::ScaleFactor(factor) => self.scale_factor = factor,
+			SpriteSheetDialogMessage::Spacing(spacing) => self.spacing = spacing as u32,
+			SpriteSheetDialogMessage::TransparentBackground(transparent_background) => self.transparent_background = transparent_background,
+
+			SpriteSheetDialogMessage::Submit => responses.add_front(DocumentMessage::ExportSpriteSheet {
+				file_name: self.file_name.clone(),
+				source: self.source,
+				scale_factor: self.scale_factor,
+				spacing: self.spacing,
+				transparent_background: self.transparent_background,
+			}),
+		}
+
+		self.send_layout(responses, LayoutTarget::DialogDetails);
+	}
+
+	advertise_actions! {SpriteSheetDialogUpdate;}
+}
+
+impl LayoutHolder for SpriteSheetDialogMessageHandler {
+	fn layout(&self) -> Layout {
+		let title = vec![TextLabel::new("Export Sprite Sheet").bold(true).widget_holder()];
+
+		let file_name = vec![
+			TextLabel::new("File Name").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextInput::new(&self.file_name)
+				.on_update(|text_input: &TextInput| SpriteSheetDialogMessage::FileName(text_input.value.clone()).into())
+				.widget_holder(),
+		];
+
+		let entries = [(SpriteSource::Artboards, "Artboards"), (SpriteSource::SelectedLayers, "Selected Layers")]
+			.into_iter()
+			.map(|(val, name)| RadioEntryData::new(name).on_update(move |_| SpriteSheetDialogMessage::Source(val).into()))
+			.collect();
+		let source = vec![
+			TextLabel::new("Sprites").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			RadioInput::new(entries).selected_index(self.source as u32).widget_holder(),
+		];
+
+		let scale_factor = vec![
+			TextLabel::new("Scale Factor").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.scale_factor))
+				.unit(" ")
+				.min(0.)
+				.on_update(|number_input: &NumberInput| SpriteSheetDialogMessage::ScaleFactor(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let spacing = vec![
+			TextLabel::new("Spacing").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(self.spacing as f64))
+				.unit(" px")
+				.min(0.)
+				.is_integer(true)
+				.tooltip("Empty pixels left between neighboring sprites, which prevents texture filtering from bleeding between them")
+				.on_update(|number_input: &NumberInput| SpriteSheetDialogMessage::Spacing(number_input.value.unwrap()).into())
+				.widget_holder(),
+		];
+
+		let transparent_background = vec![
+			TextLabel::new("Transparency").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			CheckboxInput::new(self.transparent_background)
+				.on_update(|value: &CheckboxInput| SpriteSheetDialogMessage::TransparentBackground(value.checked).into())
+				.widget_holder(),
+		];
+
+		let button_widgets = vec![
+			TextButton::new("Export")
+				.min_width(96)
+				.emphasized(true)
+				.disabled(match self.source {
+					SpriteSource::Artboards => !self.has_artboards,
+					SpriteSource::SelectedLayers => !self.has_selection,
+				})
+				.on_update(|_| {
+					DialogMessage::CloseDialogAndThen {
+						followups: vec![SpriteSheetDialogMessage::Submit.into()],
+					}
+					.into()
+				})
+				.widget_holder(),
+			TextButton::new("Cancel").min_width(96).on_update(|_| FrontendMessage::DisplayDialogDismiss.into()).widget_holder(),
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![
+			LayoutGroup::Row { widgets: title },
+			LayoutGroup::Row { widgets: file_name },
+			LayoutGroup::Row { widgets: source },
+			LayoutGroup::Row { widgets: scale_factor },
+			LayoutGroup::Row { widgets: spacing },
+			LayoutGroup::Row { widgets: transparent_background },
+			LayoutGroup::Row { widgets: button_widgets },
+		]))
+	}
+}
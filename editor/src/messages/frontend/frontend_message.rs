@@ -1,9 +1,17 @@
 use super::utility_types::{FrontendDocumentDetails, FrontendImageData, MouseCursorIcon};
+use crate::messages::color_picker::utility_types::ColorPickerState;
+use crate::messages::debug::utility_types::DiagnosticsReport;
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::batch::BatchResult;
 use crate::messages::portfolio::document::node_graph::{FrontendNode, FrontendNodeLink, FrontendNodeType};
+use crate::messages::portfolio::document::utility_types::clipboards::PasteMode;
+use crate::messages::portfolio::document::utility_types::find_replace::{TextMatch, TextQuery};
 use crate::messages::portfolio::document::utility_types::layer_panel::{JsRawBuffer, LayerPanelEntry, RawBuffer};
+use crate::messages::portfolio::document::utility_types::outline::OutlineEntry;
 use crate::messages::prelude::*;
-use crate::messages::tool::utility_types::HintData;
+use crate::messages::tool::utility_types::{HintData, ToolStatus};
+use crate::messages::tutorial::utility_types::TutorialStepDetails;
+use crate::messages::workspace::utility_types::WorkspaceLayout;
 
 use document_legacy::LayerId;
 use graph_craft::document::NodeId;
@@ -47,6 +55,23 @@ pub enum FrontendMessage {
 		#[serde(rename = "commitDate")]
 		commit_date: String,
 	},
+	/// Locks the file of an open document, so it's read-only in other windows which open the same file
+	TriggerAcquireDocumentLock {
+		#[serde(rename = "documentId")]
+		document_id: u64,
+		#[serde(rename = "fileId")]
+		file_id: u64,
+		force: bool,
+	},
+	TriggerAnimationFrame,
+	/// Asks the user to choose the documents to process with the batch dialog.
+	TriggerBatchChooseFiles,
+	/// Closes the capture of the window shown for sampling a color from it.
+	TriggerCancelWindowColorSampling,
+	TriggerCopyRaster {
+		svg: String,
+		size: (f64, f64),
+	},
 	TriggerCopyToClipboardBlobUrl {
 		#[serde(rename = "blobUrl")]
 		blob_url: String,
@@ -57,6 +82,12 @@ pub enum FrontendMessage {
 		#[serde(rename = "blobUrl")]
 		blob_url: String,
 	},
+	TriggerDownloadIcon {
+		svg: String,
+		name: String,
+		mime: String,
+		sizes: Vec<u32>,
+	},
 	TriggerDownloadRaster {
 		svg: String,
 		name: String,
@@ -67,25 +98,84 @@ pub enum FrontendMessage {
 		document: String,
 		name: String,
 	},
+	TriggerFetchDocument {
+		source: String,
+	},
+	TriggerFetchMergedImage {
+		#[serde(rename = "documentId")]
+		document_id: u64,
+		#[serde(rename = "layerPath")]
+		layer_path: Vec<LayerId>,
+		url: String,
+	},
 	TriggerFontLoad {
 		font: Font,
 		#[serde(rename = "isDefault")]
 		is_default: bool,
 	},
+	TriggerIdleWork,
 	TriggerImport,
 	TriggerIndexedDbRemoveDocument {
 		#[serde(rename = "documentId")]
 		document_id: u64,
 	},
+	TriggerIndexedDbWriteBackup {
+		#[serde(rename = "fileName")]
+		file_name: String,
+		document: String,
+		/// How many of the saves before this one to keep as backups, with the oldest beyond this being removed
+		keep: u32,
+	},
 	TriggerIndexedDbWriteDocument {
 		document: String,
 		details: FrontendDocumentDetails,
 		version: String,
 	},
+	/// Waits for the given time in milliseconds before telling the editor to repeat the held key with this ID.
+	TriggerKeyRepeat {
+		#[serde(rename = "repeatId")]
+		repeat_id: u64,
+		delay: f64,
+	},
 	TriggerLoadAutoSaveDocuments,
+	TriggerLoadBackups {
+		#[serde(rename = "fileName")]
+		file_name: String,
+	},
 	TriggerLoadPreferences,
+	/// Looks up the version a file was last saved as, to compare it with the recovered auto-save of the document.
+	TriggerLoadSavedVersion {
+		#[serde(rename = "documentId")]
+		document_id: u64,
+		#[serde(rename = "fileName")]
+		file_name: String,
+	},
+	TriggerOpenBackup {
+		#[serde(rename = "fileName")]
+		file_name: String,
+		/// Counting back from the latest save, starting at 1
+		generation: usize,
+		#[serde(rename = "documentName")]
+		document_name: String,
+	},
 	TriggerOpenDocument,
-	TriggerPaste,
+	TriggerPaste {
+		mode: PasteMode,
+	},
+	TriggerRasterizeFileThumbnail {
+		#[serde(rename = "documentId")]
+		document_id: u64,
+		svg: String,
+		size: glam::DVec2,
+	},
+	TriggerRasterizeLayerEffects {
+		#[serde(rename = "documentId")]
+		document_id: u64,
+		#[serde(rename = "layerPath")]
+		layer_path: Vec<LayerId>,
+		svg: String,
+		size: glam::DVec2,
+	},
 	TriggerRasterizeRegionBelowLayer {
 		#[serde(rename = "documentId")]
 		document_id: u64,
@@ -95,9 +185,19 @@ pub enum FrontendMessage {
 		size: glam::DVec2,
 	},
 	TriggerRefreshBoundsOfViewports,
+	TriggerReleaseDocumentLock {
+		#[serde(rename = "documentId")]
+		document_id: u64,
+	},
 	TriggerRevokeBlobUrl {
 		url: String,
 	},
+	/// Captures the window and shows the capture for the user to click the color to sample from it, which is sent back as its pixel.
+	TriggerSampleWindowColor,
+	/// The user's changes to the keyboard shortcuts, serialized as JSON, to be saved and given back with `SetMapping` when the editor next starts.
+	TriggerSaveKeymap {
+		keymap: String,
+	},
 	TriggerSavePreferences {
 		preferences: PreferencesMessageHandler,
 	},
@@ -116,6 +216,25 @@ pub enum FrontendMessage {
 		#[serde(rename = "documentId")]
 		document_id: u64,
 	},
+	/// How far a batch has got, with the result of each document it's processed so far
+	UpdateBatchProgress {
+		completed: usize,
+		total: usize,
+		results: Vec<BatchResult>,
+	},
+	/// The zoom of the active document's canvas and the zoom levels to choose from, both as percentages
+	UpdateCanvasZoom {
+		#[serde(rename = "zoomPercentage")]
+		zoom_percentage: f64,
+		#[serde(rename = "zoomLevels")]
+		zoom_levels: Vec<f64>,
+	},
+	UpdateColorPicker {
+		state: ColorPickerState,
+	},
+	UpdateDiagnostics {
+		diagnostics: DiagnosticsReport,
+	},
 	UpdateDialogDetails {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
@@ -135,6 +254,11 @@ pub enum FrontendMessage {
 	UpdateDocumentLayerDetails {
 		data: LayerPanelEntry,
 	},
+	UpdateDocumentLayerRender {
+		#[serde(rename = "layerPath")]
+		layer_path: Vec<LayerId>,
+		svg: String,
+	},
 	UpdateDocumentLayerTreeStructure {
 		#[serde(rename = "dataBuffer")]
 		data_buffer: RawBuffer,
@@ -143,6 +267,12 @@ pub enum FrontendMessage {
 		#[serde(rename = "dataBuffer")]
 		data_buffer: JsRawBuffer,
 	},
+	UpdateDocumentLoadingProgress {
+		#[serde(rename = "documentId")]
+		document_id: u64,
+		loaded: usize,
+		total: usize,
+	},
 	UpdateDocumentModeLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
@@ -151,6 +281,10 @@ pub enum FrontendMessage {
 	UpdateDocumentNodeRender {
 		svg: String,
 	},
+	UpdateDocumentOutline {
+		query: String,
+		entries: Vec<OutlineEntry>,
+	},
 	UpdateDocumentOverlays {
 		svg: String,
 	},
@@ -158,12 +292,21 @@ pub enum FrontendMessage {
 		origin: (f64, f64),
 		spacing: f64,
 		interval: f64,
+		/// Whether the view is mirrored horizontally, so the horizontal ruler's numbers decrease from left to right.
+		flipped: bool,
+		#[serde(rename = "decimalSeparator")]
+		decimal_separator: char,
 	},
 	UpdateDocumentScrollbars {
 		position: (f64, f64),
 		size: (f64, f64),
 		multiplier: (f64, f64),
 	},
+	UpdateDocumentThumbnail {
+		#[serde(rename = "documentId")]
+		document_id: u64,
+		thumbnail: String,
+	},
 	UpdateDocumentTransform {
 		transform: String,
 	},
@@ -177,6 +320,12 @@ pub enum FrontendMessage {
 		#[serde(rename = "setColorChoice")]
 		set_color_choice: Option<String>,
 	},
+	/// A small rasterized preview of the export, or an empty `svg` when the file type can't be previewed.
+	UpdateExportPreview {
+		svg: String,
+		mime: String,
+		size: (f64, f64),
+	},
 	UpdateImageData {
 		#[serde(rename = "documentId")]
 		document_id: u64,
@@ -187,6 +336,12 @@ pub enum FrontendMessage {
 		#[serde(rename = "hintData")]
 		hint_data: HintData,
 	},
+	/// The user's changes to the keyboard shortcuts, serialized as JSON, along with the problems that keep them from being applied and the shortcuts they take over.
+	UpdateKeymap {
+		keymap: String,
+		errors: Vec<String>,
+		conflicts: Vec<String>,
+	},
 	UpdateLayerTreeOptionsLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
@@ -220,6 +375,10 @@ pub enum FrontendMessage {
 		#[serde(rename = "openDocuments")]
 		open_documents: Vec<FrontendDocumentDetails>,
 	},
+	/// Whether the active document is being presented, in which case only its canvas is shown
+	UpdatePresentationMode {
+		presenting: bool,
+	},
 	UpdatePropertyPanelOptionsLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
@@ -230,6 +389,12 @@ pub enum FrontendMessage {
 		layout_target: LayoutTarget,
 		diff: Vec<WidgetDiff>,
 	},
+	/// The text layers of every open document matching a find query, or why the query couldn't be searched for.
+	UpdateTextSearchResults {
+		query: TextQuery,
+		matches: Vec<TextMatch>,
+		error: Option<String>,
+	},
 	UpdateToolOptionsLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
@@ -240,11 +405,21 @@ pub enum FrontendMessage {
 		layout_target: LayoutTarget,
 		diff: Vec<WidgetDiff>,
 	},
+	UpdateToolStatus {
+		status: ToolStatus,
+	},
+	/// The step of the running tutorial to show to the user, or `None` once the tutorial is over.
+	UpdateTutorialStep {
+		step: Option<TutorialStepDetails>,
+	},
 	UpdateWorkingColorsLayout {
 		#[serde(rename = "layoutTarget")]
 		layout_target: LayoutTarget,
 		diff: Vec<WidgetDiff>,
 	},
+	UpdateWorkspaceLayout {
+		layout: WorkspaceLayout,
+	},
 	UpdateZoomWithScroll {
 		#[serde(rename = "zoomWithScroll")]
 		zoom_with_scroll: bool,
@@ -1,4 +1,7 @@
+use crate::messages::prelude::FrontendMessage;
+
 use document_legacy::LayerId;
+use glam::DVec2;
 use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize, specta::Type)]
@@ -7,6 +10,8 @@ pub struct FrontendDocumentDetails {
 	pub is_auto_saved: bool,
 	#[serde(rename = "isSaved")]
 	pub is_saved: bool,
+	#[serde(rename = "isReadOnly")]
+	pub is_read_only: bool,
 	pub name: String,
 	pub id: u64,
 }
@@ -46,6 +51,12 @@ pub enum FileType {
 	Png,
 	Jpg,
 	Svg,
+	Dxf,
+	Gcode,
+	Hpgl,
+	Ico,
+	Icns,
+	Json,
 }
 
 impl FileType {
@@ -54,6 +65,60 @@ impl FileType {
 			FileType::Png => "image/png",
 			FileType::Jpg => "image/jpeg",
 			FileType::Svg => "image/svg+xml",
+			FileType::Dxf => "image/vnd.dxf",
+			FileType::Gcode => "text/x-gcode",
+			FileType::Hpgl => "application/vnd.hp-hpgl",
+			FileType::Ico => "image/x-icon",
+			FileType::Icns => "image/icns",
+			FileType::Json => "application/json",
+		}
+	}
+
+	/// Whether this format describes geometry for fabrication machines, which is exported from the vector paths rather than the rendered SVG.
+	pub fn is_fabrication(self) -> bool {
+		matches!(self, FileType::Dxf | FileType::Gcode | FileType::Hpgl)
+	}
+
+	/// Whether this format is made from the rendered artwork, rather than from the layers' paths and styles like fabrication formats and the JSON scene graph.
+	pub fn is_rendered(self) -> bool {
+		!self.is_fabrication() && self != FileType::Json
+	}
+
+	/// The square sizes, in pixels, rendered into this format's icon container, or an empty list if it isn't an icon format.
+	pub fn icon_sizes(self) -> &'static [u32] {
+		match self {
+			// ICO directory entries can't describe images larger than 256 pixels
+			FileType::Ico => &[16, 24, 32, 48, 64, 128, 256],
+			FileType::Icns => &[16, 32, 64, 128, 256, 512],
+			_ => &[],
+		}
+	}
+
+	pub fn is_icon(self) -> bool {
+		!self.icon_sizes().is_empty()
+	}
+
+	/// Whether this format is downloaded as text rather than rasterized.
+	pub fn is_text(self) -> bool {
+		self == FileType::Svg || !self.is_rendered()
+	}
+
+	/// The message which has the frontend save an exported document in this format, rasterizing it to the given size first if needed.
+	pub fn download(self, document: String, name: String, size: DVec2) -> FrontendMessage {
+		if self.is_text() {
+			FrontendMessage::TriggerDownloadTextFile { document, name }
+		} else if self.is_icon() {
+			let mime = self.to_mime().to_string();
+			let sizes = self.icon_sizes().to_vec();
+			FrontendMessage::TriggerDownloadIcon { svg: document, name, mime, sizes }
+		} else {
+			let mime = self.to_mime().to_string();
+			FrontendMessage::TriggerDownloadRaster {
+				svg: document,
+				name,
+				mime,
+				size: size.into(),
+			}
 		}
 	}
 }
@@ -64,4 +129,5 @@ pub enum ExportBounds {
 	AllArtwork,
 	Selection,
 	Artboard(LayerId),
+	Custom,
 }
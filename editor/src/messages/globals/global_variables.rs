@@ -1,5 +1,69 @@
+use crate::messages::portfolio::document::overlays::utility_types::OverlayColors;
+use crate::messages::portfolio::document::utility_types::misc::NewLayerPlacement;
 use crate::messages::portfolio::utility_types::Platform;
+use crate::messages::preferences::utility_types::NumberLocale;
+use crate::messages::tool::common_functionality::shape_presets::ShapePreset;
 
 use once_cell::sync::OnceCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
 
 pub static GLOBAL_PLATFORM: OnceCell<Platform> = OnceCell::new();
+
+/// Reads a monotonic clock in milliseconds. WASM has no clock of its own, so the platform provides this upon initialization.
+pub static GLOBAL_CLOCK: OnceCell<fn() -> f64> = OnceCell::new();
+
+/// Reads the date as milliseconds since the Unix epoch, provided by the platform like [GLOBAL_CLOCK]. Unlike that clock, it can be compared between sessions.
+pub static GLOBAL_DATE: OnceCell<fn() -> f64> = OnceCell::new();
+
+pub static GLOBAL_OVERLAY_COLORS: RwLock<OverlayColors> = RwLock::new(OverlayColors::DEFAULT);
+
+pub static GLOBAL_LARGER_HANDLES: AtomicBool = AtomicBool::new(false);
+
+pub static GLOBAL_NEW_LAYER_PLACEMENT: RwLock<NewLayerPlacement> = RwLock::new(NewLayerPlacement::IntoSelectedFolder);
+
+pub static GLOBAL_NUMBER_LOCALE: RwLock<NumberLocale> = RwLock::new(NumberLocale::Point);
+
+pub static GLOBAL_VIEWPORT_SCALE_FACTOR: RwLock<f64> = RwLock::new(1.);
+
+pub static GLOBAL_SHAPE_PRESETS: RwLock<Vec<ShapePreset>> = RwLock::new(Vec::new());
+
+/// The overlay colors currently chosen in the preferences, read by the tools when they draw their overlays.
+pub fn overlay_colors() -> OverlayColors {
+	GLOBAL_OVERLAY_COLORS.read().map(|colors| *colors).unwrap_or_default()
+}
+
+/// Whether the preferences ask for larger handles and hit targets, which are easier to use with touch input.
+pub fn larger_handles() -> bool {
+	GLOBAL_LARGER_HANDLES.load(Ordering::Relaxed)
+}
+
+/// Where the preferences ask for the tools to insert the layers they draw.
+pub fn new_layer_placement() -> NewLayerPlacement {
+	GLOBAL_NEW_LAYER_PLACEMENT.read().map(|placement| *placement).unwrap_or_default()
+}
+
+/// How the preferences ask for numbers to be written in the readouts shown to the user.
+pub fn number_locale() -> NumberLocale {
+	GLOBAL_NUMBER_LOCALE.read().map(|locale| *locale).unwrap_or_default()
+}
+
+/// The shape presets saved in the preferences, listed in the options of the shape tools.
+pub fn shape_presets() -> Vec<ShapePreset> {
+	GLOBAL_SHAPE_PRESETS.read().map(|presets| presets.clone()).unwrap_or_default()
+}
+
+/// The number of viewport units per logical pixel of the viewport, which the sizes and distances measured on screen are multiplied by.
+pub fn viewport_scale_factor() -> f64 {
+	GLOBAL_VIEWPORT_SCALE_FACTOR.read().map(|scale_factor| *scale_factor).unwrap_or(1.)
+}
+
+/// The current time in milliseconds according to [GLOBAL_CLOCK], if the platform has provided one.
+pub fn now_milliseconds() -> Option<f64> {
+	GLOBAL_CLOCK.get().map(|clock| clock())
+}
+
+/// The current date in milliseconds since the Unix epoch according to [GLOBAL_DATE], if the platform has provided one.
+pub fn unix_time_milliseconds() -> Option<f64> {
+	GLOBAL_DATE.get().map(|date| date())
+}
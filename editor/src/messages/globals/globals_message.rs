@@ -1,10 +1,20 @@
+use crate::messages::portfolio::document::overlays::utility_types::OverlayColors;
+use crate::messages::portfolio::document::utility_types::misc::NewLayerPlacement;
 use crate::messages::portfolio::utility_types::Platform;
+use crate::messages::preferences::utility_types::NumberLocale;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::shape_presets::ShapePreset;
 
 use serde::{Deserialize, Serialize};
 
 #[impl_message(Message, Globals)]
-#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum GlobalsMessage {
+	SetLargerHandles { larger_handles: bool },
+	SetNewLayerPlacement { placement: NewLayerPlacement },
+	SetNumberLocale { locale: NumberLocale },
+	SetOverlayColors { overlay_colors: OverlayColors },
 	SetPlatform { platform: Platform },
+	SetShapePresets { presets: Vec<ShapePreset> },
+	SetViewportScaleFactor { scale_factor: f64 },
 }
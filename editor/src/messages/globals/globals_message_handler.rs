@@ -7,11 +7,39 @@ impl MessageHandler<GlobalsMessage, ()> for GlobalsMessageHandler {
 	#[remain::check]
 	fn process_message(&mut self, message: GlobalsMessage, _responses: &mut VecDeque<Message>, _data: ()) {
 		match message {
+			GlobalsMessage::SetLargerHandles { larger_handles } => {
+				GLOBAL_LARGER_HANDLES.store(larger_handles, std::sync::atomic::Ordering::Relaxed);
+			}
+			GlobalsMessage::SetNewLayerPlacement { placement } => {
+				if let Ok(mut global_placement) = GLOBAL_NEW_LAYER_PLACEMENT.write() {
+					*global_placement = placement;
+				}
+			}
+			GlobalsMessage::SetNumberLocale { locale } => {
+				if let Ok(mut global_locale) = GLOBAL_NUMBER_LOCALE.write() {
+					*global_locale = locale;
+				}
+			}
+			GlobalsMessage::SetOverlayColors { overlay_colors } => {
+				if let Ok(mut global_overlay_colors) = GLOBAL_OVERLAY_COLORS.write() {
+					*global_overlay_colors = overlay_colors;
+				}
+			}
 			GlobalsMessage::SetPlatform { platform } => {
 				if GLOBAL_PLATFORM.get() != Some(&platform) {
 					GLOBAL_PLATFORM.set(platform).expect("Failed to set GLOBAL_PLATFORM");
 				}
 			}
+			GlobalsMessage::SetShapePresets { presets } => {
+				if let Ok(mut global_presets) = GLOBAL_SHAPE_PRESETS.write() {
+					*global_presets = presets;
+				}
+			}
+			GlobalsMessage::SetViewportScaleFactor { scale_factor } => {
+				if let Ok(mut global_scale_factor) = GLOBAL_VIEWPORT_SCALE_FACTOR.write() {
+					*global_scale_factor = scale_factor;
+				}
+			}
 		}
 	}
 
@@ -1,28 +1,45 @@
-use crate::consts::{BIG_NUDGE_AMOUNT, BRUSH_SIZE_CHANGE_KEYBOARD, NUDGE_AMOUNT};
+use crate::consts::{BIG_KERNING_NUDGE_AMOUNT, BIG_NUDGE_AMOUNT, BRUSH_SIZE_CHANGE_KEYBOARD, KERNING_NUDGE_AMOUNT, NUDGE_AMOUNT};
 use crate::messages::input_mapper::key_mapping::MappingVariant;
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeyStates};
 use crate::messages::input_mapper::utility_types::macros::*;
-use crate::messages::input_mapper::utility_types::misc::MappingEntry;
-use crate::messages::input_mapper::utility_types::misc::{KeyMappingEntries, Mapping};
+use crate::messages::input_mapper::utility_types::misc::{
+	BindingCondition, BindingScope, KeyMappingEntries, KeyRepeat, Mapping, MappingEntry, MiddleDragBehavior, PointerRole, PointerRoles, RoleTrigger,
+};
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
 use crate::messages::prelude::*;
 use crate::messages::tool::tool_messages::brush_tool::BrushToolMessageOptionsUpdate;
+use crate::messages::workspace::utility_types::WorkspacePreset;
 
+use document_legacy::boolean_ops::BooleanOperation;
 use glam::DVec2;
 
 impl From<MappingVariant> for Mapping {
 	fn from(value: MappingVariant) -> Self {
-		match value {
-			MappingVariant::Default => default_mapping(),
-			MappingVariant::ZoomWithScroll => zoom_with_scroll(),
-		}
+		built_in_mapping(&value, MiddleDragBehavior::default(), &PointerRoles::default())
 	}
 }
 
+/// The mapping that the user's changes are made to, given the scroll, middle drag, and pointer role preferences.
+pub fn built_in_mapping(variant: &MappingVariant, middle_drag: MiddleDragBehavior, roles: &PointerRoles) -> Mapping {
+	let mut mapping = match variant {
+		MappingVariant::Default => default_mapping(),
+		MappingVariant::ZoomWithScroll => zoom_with_scroll(),
+	};
+	apply_middle_drag_behavior(&mut mapping, middle_drag);
+	apply_pointer_roles(&mut mapping, roles);
+	mapping
+}
+
+/// The mapping before the pointer roles are also given to the inputs chosen in the preferences.
 pub fn default_mapping() -> Mapping {
 	use InputMapperMessage::*;
 	use Key::*;
 
+	// The buttons the pointer roles are bound to, whose entries are copied to other inputs by `apply_pointer_roles`
+	const PAN: Key = PointerRole::Pan.button();
+	const ZOOM: Key = PointerRole::Zoom.button();
+	const CONTEXT_MENU: Key = PointerRole::ContextMenu.button();
+
 	// NOTICE:
 	// If a new mapping you added here isn't working (and perhaps another lower-precedence one is instead), make sure to advertise
 	// it as an available action in the respective message handler file (such as the bottom of `document_message_handler.rs`).
@@ -38,6 +55,18 @@ pub fn default_mapping() -> Mapping {
 		),
 		// NORMAL PRIORITY:
 		//
+		// PresentationMessage
+		entry!(KeyDown(F5); action_dispatch=PresentationMessage::Enter),
+		entry!(KeyDown(Escape); action_dispatch=PresentationMessage::Exit),
+		entry!(KeyDown(Lmb); action_dispatch=PresentationMessage::Click),
+		entry!(KeyDown(ArrowRight); action_dispatch=PresentationMessage::NextArtboard),
+		entry!(KeyDown(ArrowDown); action_dispatch=PresentationMessage::NextArtboard),
+		entry!(KeyDown(PageDown); action_dispatch=PresentationMessage::NextArtboard),
+		entry!(KeyDown(Space); action_dispatch=PresentationMessage::NextArtboard),
+		entry!(KeyDown(ArrowLeft); action_dispatch=PresentationMessage::PreviousArtboard),
+		entry!(KeyDown(ArrowUp); action_dispatch=PresentationMessage::PreviousArtboard),
+		entry!(KeyDown(PageUp); action_dispatch=PresentationMessage::PreviousArtboard),
+		//
 		// NodeGraphMessage
 		entry!(KeyDown(Delete); action_dispatch=NodeGraphMessage::DeleteSelectedNodes),
 		entry!(KeyDown(Backspace); action_dispatch=NodeGraphMessage::DeleteSelectedNodes),
@@ -50,10 +79,10 @@ pub fn default_mapping() -> Mapping {
 		entry!(KeyDown(Enter); action_dispatch=TransformLayerMessage::ApplyTransformOperation),
 		entry!(KeyDown(Lmb); action_dispatch=TransformLayerMessage::ApplyTransformOperation),
 		entry!(KeyDown(Escape); action_dispatch=TransformLayerMessage::CancelTransformOperation),
-		entry!(KeyDown(Rmb); action_dispatch=TransformLayerMessage::CancelTransformOperation),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=TransformLayerMessage::CancelTransformOperation),
 		entry!(KeyDown(KeyX); action_dispatch=TransformLayerMessage::ConstrainX),
 		entry!(KeyDown(KeyY); action_dispatch=TransformLayerMessage::ConstrainY),
-		entry!(KeyDown(Backspace); action_dispatch=TransformLayerMessage::TypeBackspace),
+		entry!(KeyDown(Backspace); repeat=Always, action_dispatch=TransformLayerMessage::TypeBackspace),
 		entry!(KeyDown(Minus); action_dispatch=TransformLayerMessage::TypeNegate),
 		entry!(KeyDown(Comma); action_dispatch=TransformLayerMessage::TypeDecimalPoint),
 		entry!(KeyDown(Period); action_dispatch=TransformLayerMessage::TypeDecimalPoint),
@@ -63,10 +92,15 @@ pub fn default_mapping() -> Mapping {
 		entry!(PointerMove; refresh_keys=[Control, Shift, Alt], action_dispatch=SelectToolMessage::PointerMove { axis_align: Shift, snap_angle: Control, center: Alt, duplicate: Alt }),
 		entry!(KeyDown(Lmb); action_dispatch=SelectToolMessage::DragStart { add_to_selection: Shift, select_deepest: Accel }),
 		entry!(KeyUp(Lmb); action_dispatch=SelectToolMessage::DragStop { remove_from_selection: Shift }),
-		entry!(KeyDown(Enter); action_dispatch=SelectToolMessage::Enter),
 		entry!(DoubleClick; action_dispatch=SelectToolMessage::EditLayer),
-		entry!(KeyDown(Rmb); action_dispatch=SelectToolMessage::Abort),
-		entry!(KeyDown(Escape); action_dispatch=SelectToolMessage::Abort),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=SelectToolMessage::Abort),
+		entry!(KeyDown(Backspace); repeat=Always, action_dispatch=SelectToolMessage::TypeBackspace),
+		entry!(KeyDown(Minus); action_dispatch=SelectToolMessage::TypeNegate),
+		entry!(KeyDown(Comma); action_dispatch=SelectToolMessage::TypeDecimalPoint),
+		entry!(KeyDown(Period); action_dispatch=SelectToolMessage::TypeDecimalPoint),
+		entry!(KeyDown(KeyQ); action_dispatch=SelectToolMessage::ToggleQuickMask),
+		entry!(KeyDown(BracketLeft); repeat=Always, action_dispatch=SelectToolMessage::ResizeQuickMaskBrush { grow: false }),
+		entry!(KeyDown(BracketRight); repeat=Always, action_dispatch=SelectToolMessage::ResizeQuickMaskBrush { grow: true }),
 		//
 		// ArtboardToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=ArtboardToolMessage::PointerDown),
@@ -74,54 +108,59 @@ pub fn default_mapping() -> Mapping {
 		entry!(KeyUp(Lmb); action_dispatch=ArtboardToolMessage::PointerUp),
 		entry!(KeyDown(Delete); action_dispatch=ArtboardToolMessage::DeleteSelected),
 		entry!(KeyDown(Backspace); action_dispatch=ArtboardToolMessage::DeleteSelected),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: 0., delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: 0., delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: 0., delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: 0., delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: 0. }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: 0., delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: 0., delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -BIG_NUDGE_AMOUNT, delta_y: 0. }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: BIG_NUDGE_AMOUNT, delta_y: 0. }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: 0., delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: 0., delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: -NUDGE_AMOUNT, delta_y: 0. }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); repeat=Always, action_dispatch=ArtboardToolMessage::NudgeSelected { delta_x: NUDGE_AMOUNT, delta_y: 0. }),
 		//
 		// NavigateToolMessage
 		entry!(KeyUp(Lmb); modifiers=[Shift], action_dispatch=NavigateToolMessage::ClickZoom { zoom_in: false }),
 		entry!(KeyUp(Lmb); action_dispatch=NavigateToolMessage::ClickZoom { zoom_in: true }),
 		entry!(PointerMove; refresh_keys=[Control], action_dispatch=NavigateToolMessage::PointerMove { snap_angle: Control, snap_zoom: Control }),
-		entry!(KeyDown(Mmb); action_dispatch=NavigateToolMessage::TranslateCanvasBegin),
-		entry!(KeyDown(Rmb); action_dispatch=NavigateToolMessage::RotateCanvasBegin),
+		entry!(KeyDown(PAN); action_dispatch=NavigateToolMessage::TranslateCanvasBegin),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=NavigateToolMessage::RotateCanvasBegin),
 		entry!(KeyDown(Lmb); action_dispatch=NavigateToolMessage::ZoomCanvasBegin),
-		entry!(KeyUp(Rmb); action_dispatch=NavigateToolMessage::TransformCanvasEnd),
+		entry!(KeyUp(CONTEXT_MENU); action_dispatch=NavigateToolMessage::TransformCanvasEnd),
 		entry!(KeyUp(Lmb); action_dispatch=NavigateToolMessage::TransformCanvasEnd),
-		entry!(KeyUp(Mmb); action_dispatch=NavigateToolMessage::TransformCanvasEnd),
+		entry!(KeyUp(PAN); action_dispatch=NavigateToolMessage::TransformCanvasEnd),
 		//
 		// EyedropperToolMessage
 		entry!(PointerMove; action_dispatch=EyedropperToolMessage::PointerMove),
 		entry!(KeyDown(Lmb); action_dispatch=EyedropperToolMessage::LeftPointerDown),
-		entry!(KeyDown(Rmb); action_dispatch=EyedropperToolMessage::RightPointerDown),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=EyedropperToolMessage::RightPointerDown),
 		entry!(KeyUp(Lmb); action_dispatch=EyedropperToolMessage::LeftPointerUp),
-		entry!(KeyUp(Rmb); action_dispatch=EyedropperToolMessage::RightPointerUp),
-		entry!(KeyDown(Escape); action_dispatch=EyedropperToolMessage::Abort),
+		entry!(KeyUp(CONTEXT_MENU); action_dispatch=EyedropperToolMessage::RightPointerUp),
 		//
 		// TextToolMessage
 		entry!(KeyUp(Lmb); action_dispatch=TextToolMessage::Interact),
-		entry!(KeyDown(Escape); action_dispatch=TextToolMessage::Abort),
 		entry!(KeyDown(Enter); modifiers=[Accel], action_dispatch=TextToolMessage::CommitText),
+		entry!(KeyDown(KeyK); modifiers=[Alt], action_dispatch=TextToolMessage::StartKerning),
+		entry!(KeyDown(ArrowLeft); action_dispatch=TextToolMessage::SelectKerningPair { delta: -1 }),
+		entry!(KeyDown(ArrowRight); action_dispatch=TextToolMessage::SelectKerningPair { delta: 1 }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Alt], repeat=Always, action_dispatch=TextToolMessage::AdjustKerning { delta: -KERNING_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); modifiers=[Alt], repeat=Always, action_dispatch=TextToolMessage::AdjustKerning { delta: KERNING_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Alt, Shift], repeat=Always, action_dispatch=TextToolMessage::AdjustKerning { delta: -BIG_KERNING_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); modifiers=[Alt, Shift], repeat=Always, action_dispatch=TextToolMessage::AdjustKerning { delta: BIG_KERNING_NUDGE_AMOUNT }),
 		//
 		// GradientToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=GradientToolMessage::PointerDown),
@@ -134,44 +173,43 @@ pub fn default_mapping() -> Mapping {
 		// RectangleToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=RectangleToolMessage::DragStart),
 		entry!(KeyUp(Lmb); action_dispatch=RectangleToolMessage::DragStop),
-		entry!(KeyDown(Rmb); action_dispatch=RectangleToolMessage::Abort),
-		entry!(KeyDown(Escape); action_dispatch=RectangleToolMessage::Abort),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=RectangleToolMessage::Abort),
 		entry!(PointerMove; refresh_keys=[Alt, Shift], action_dispatch=RectangleToolMessage::Resize { center: Alt, lock_ratio: Shift }),
 		//
 		// ImaginateToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=ImaginateToolMessage::DragStart),
 		entry!(KeyUp(Lmb); action_dispatch=ImaginateToolMessage::DragStop),
-		entry!(KeyDown(Rmb); action_dispatch=ImaginateToolMessage::Abort),
-		entry!(KeyDown(Escape); action_dispatch=ImaginateToolMessage::Abort),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=ImaginateToolMessage::Abort),
 		entry!(PointerMove; refresh_keys=[Alt, Shift], action_dispatch=ImaginateToolMessage::Resize { center: Alt, lock_ratio: Shift }),
 		//
 		// FrameToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=FrameToolMessage::DragStart),
 		entry!(KeyUp(Lmb); action_dispatch=FrameToolMessage::DragStop),
-		entry!(KeyDown(Rmb); action_dispatch=FrameToolMessage::Abort),
-		entry!(KeyDown(Escape); action_dispatch=FrameToolMessage::Abort),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=FrameToolMessage::Abort),
 		entry!(PointerMove; refresh_keys=[Alt, Shift], action_dispatch=FrameToolMessage::Resize { center: Alt, lock_ratio: Shift }),
 		//
 		// EllipseToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=EllipseToolMessage::DragStart),
 		entry!(KeyUp(Lmb); action_dispatch=EllipseToolMessage::DragStop),
-		entry!(KeyDown(Rmb); action_dispatch=EllipseToolMessage::Abort),
-		entry!(KeyDown(Escape); action_dispatch=EllipseToolMessage::Abort),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=EllipseToolMessage::Abort),
 		entry!(PointerMove; refresh_keys=[Alt, Shift], action_dispatch=EllipseToolMessage::Resize { center: Alt, lock_ratio: Shift }),
 		//
 		// PolygonToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=PolygonToolMessage::DragStart),
 		entry!(KeyUp(Lmb); action_dispatch=PolygonToolMessage::DragStop),
-		entry!(KeyDown(Rmb); action_dispatch=PolygonToolMessage::Abort),
-		entry!(KeyDown(Escape); action_dispatch=PolygonToolMessage::Abort),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=PolygonToolMessage::Abort),
 		entry!(PointerMove; refresh_keys=[Alt, Shift], action_dispatch=PolygonToolMessage::Resize { center: Alt, lock_ratio: Shift }),
 		//
 		// LineToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=LineToolMessage::DragStart),
 		entry!(KeyUp(Lmb); action_dispatch=LineToolMessage::DragStop),
-		entry!(KeyDown(Rmb); action_dispatch=LineToolMessage::Abort),
-		entry!(KeyDown(Escape); action_dispatch=LineToolMessage::Abort),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=LineToolMessage::Abort),
 		entry!(PointerMove; refresh_keys=[Alt, Shift, Control], action_dispatch=LineToolMessage::Redraw { center: Alt, lock_angle: Control, snap_angle: Shift }),
+		entry!(KeyDown(Tab); action_dispatch=LineToolMessage::CycleTypedField),
+		entry!(KeyDown(Backspace); repeat=Always, action_dispatch=LineToolMessage::TypeBackspace),
+		entry!(KeyDown(Minus); action_dispatch=LineToolMessage::TypeNegate),
+		entry!(KeyDown(Comma); action_dispatch=LineToolMessage::TypeDecimalPoint),
+		entry!(KeyDown(Period); action_dispatch=LineToolMessage::TypeDecimalPoint),
 		//
 		// PathToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=PathToolMessage::DragStart { add_to_selection: Shift }),
@@ -179,42 +217,40 @@ pub fn default_mapping() -> Mapping {
 		entry!(KeyDown(Delete); action_dispatch=PathToolMessage::Delete),
 		entry!(KeyDown(Backspace); action_dispatch=PathToolMessage::Delete),
 		entry!(KeyUp(Lmb); action_dispatch=PathToolMessage::DragStop { shift_mirror_distance: Shift }),
-		entry!(KeyDown(Enter); action_dispatch=PathToolMessage::Enter {
-			add_to_selection: Shift
-		}),
 		entry!(DoubleClick; action_dispatch=PathToolMessage::InsertPoint),
-		entry!(KeyDown(ArrowRight); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: 0. }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(BracketRight); action_dispatch=PathToolMessage::GrowPointSelection),
+		entry!(KeyDown(BracketLeft); action_dispatch=PathToolMessage::ShrinkPointSelection),
+		entry!(KeyDown(KeyI); modifiers=[Accel, Shift], action_dispatch=PathToolMessage::InvertPointSelection),
+		entry!(KeyDown(ArrowRight); repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: 0. }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: 0. }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: 0. }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: 0. }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: 0., delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], repeat=Always, action_dispatch=PathToolMessage::NudgeSelectedPoints { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT }),
 		//
 		// PenToolMessage
 		entry!(PointerMove; refresh_keys=[Shift, Control], action_dispatch=PenToolMessage::PointerMove { snap_angle: Shift, break_handle: Alt, lock_angle: Control}),
 		entry!(KeyDown(Lmb); action_dispatch=PenToolMessage::DragStart),
 		entry!(KeyUp(Lmb); action_dispatch=PenToolMessage::DragStop),
-		entry!(KeyDown(Rmb); action_dispatch=PenToolMessage::Confirm),
-		entry!(KeyDown(Escape); action_dispatch=PenToolMessage::Confirm),
-		entry!(KeyDown(Enter); action_dispatch=PenToolMessage::Confirm),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=PenToolMessage::Confirm),
 		//
 		// FreehandToolMessage
 		entry!(PointerMove; action_dispatch=FreehandToolMessage::PointerMove),
@@ -225,22 +261,24 @@ pub fn default_mapping() -> Mapping {
 		entry!(PointerMove; action_dispatch=SplineToolMessage::PointerMove),
 		entry!(KeyDown(Lmb); action_dispatch=SplineToolMessage::DragStart),
 		entry!(KeyUp(Lmb); action_dispatch=SplineToolMessage::DragStop),
-		entry!(KeyDown(Rmb); action_dispatch=SplineToolMessage::Confirm),
-		entry!(KeyDown(Escape); action_dispatch=SplineToolMessage::Confirm),
-		entry!(KeyDown(Enter); action_dispatch=SplineToolMessage::Confirm),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=SplineToolMessage::Confirm),
 		//
 		// FillToolMessage
 		entry!(KeyDown(Lmb); action_dispatch=FillToolMessage::LeftPointerDown),
-		entry!(KeyDown(Rmb); action_dispatch=FillToolMessage::RightPointerDown),
+		entry!(KeyDown(CONTEXT_MENU); action_dispatch=FillToolMessage::RightPointerDown),
 		//
 		// BrushToolMessage
 		entry!(PointerMove; action_dispatch=BrushToolMessage::PointerMove),
 		entry!(KeyDown(Lmb); action_dispatch=BrushToolMessage::DragStart),
 		entry!(KeyUp(Lmb); action_dispatch=BrushToolMessage::DragStop),
-		entry!(KeyDown(BracketLeft); action_dispatch=BrushToolMessage::UpdateOptions(BrushToolMessageOptionsUpdate::ChangeDiameter(-BRUSH_SIZE_CHANGE_KEYBOARD))),
-		entry!(KeyDown(BracketRight); action_dispatch=BrushToolMessage::UpdateOptions(BrushToolMessageOptionsUpdate::ChangeDiameter(BRUSH_SIZE_CHANGE_KEYBOARD))),
+		entry!(KeyDown(PenEraser); action_dispatch=BrushToolMessage::EraseStart),
+		entry!(KeyUp(PenEraser); action_dispatch=BrushToolMessage::DragStop),
+		entry!(KeyDown(BracketLeft); repeat=Always, action_dispatch=BrushToolMessage::UpdateOptions(BrushToolMessageOptionsUpdate::ChangeDiameter(-BRUSH_SIZE_CHANGE_KEYBOARD))),
+		entry!(KeyDown(BracketRight); repeat=Always, action_dispatch=BrushToolMessage::UpdateOptions(BrushToolMessageOptionsUpdate::ChangeDiameter(BRUSH_SIZE_CHANGE_KEYBOARD))),
 		//
 		// ToolMessage
+		entry!(KeyDown(Escape); action_dispatch=ToolMessage::AbortCurrent),
+		entry!(KeyDown(Enter); action_dispatch=ToolMessage::ConfirmCurrent),
 		entry!(KeyDown(KeyV); action_dispatch=ToolMessage::ActivateToolSelect),
 		entry!(KeyDown(KeyZ); action_dispatch=ToolMessage::ActivateToolNavigate),
 		entry!(KeyDown(KeyI); action_dispatch=ToolMessage::ActivateToolEyedropper),
@@ -256,54 +294,75 @@ pub fn default_mapping() -> Mapping {
 		entry!(KeyDown(KeyY); action_dispatch=ToolMessage::ActivateToolPolygon),
 		entry!(KeyDown(KeyB); action_dispatch=ToolMessage::ActivateToolBrush),
 		entry!(KeyDown(KeyX); modifiers=[Shift, Accel], action_dispatch=ToolMessage::ResetColors),
-		entry!(KeyDown(KeyX); modifiers=[Shift], action_dispatch=ToolMessage::SwapColors),
+		// With nothing selected, Shift+X falls through to swapping the working colors instead
+		entry!(KeyDown(KeyX); modifiers=[Shift], action_dispatch=DocumentMessage::SwapSelectedFillAndStroke),
+		entry!(KeyDown(KeyX); action_dispatch=ToolMessage::SwapColors),
+		entry!(KeyDown(Slash); modifiers=[Shift], action_dispatch=DocumentMessage::ClearSelectedStroke),
+		entry!(KeyDown(Slash); action_dispatch=DocumentMessage::ClearSelectedFill),
 		entry!(KeyDown(KeyC); modifiers=[Alt], action_dispatch=ToolMessage::SelectRandomPrimaryColor),
 		//
 		// DocumentMessage
-		entry!(KeyDown(Delete); action_dispatch=DocumentMessage::DeleteSelectedLayers),
-		entry!(KeyDown(Backspace); action_dispatch=DocumentMessage::DeleteSelectedLayers),
+		entry!(KeyDown(Delete); condition=BindingCondition::HasSelection, action_dispatch=DocumentMessage::DeleteSelectedLayers),
+		entry!(KeyDown(Backspace); condition=BindingCondition::HasSelection, action_dispatch=DocumentMessage::DeleteSelectedLayers),
 		entry!(KeyDown(KeyP); modifiers=[Alt], action_dispatch=DocumentMessage::DebugPrintDocument),
-		entry!(KeyDown(KeyZ); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::Redo),
-		entry!(KeyDown(KeyZ); modifiers=[Accel], action_dispatch=DocumentMessage::Undo),
+		entry!(KeyDown(KeyZ); modifiers=[Accel, Shift], repeat=WhenEnabled, action_dispatch=DocumentMessage::Redo),
+		entry!(KeyDown(KeyZ); modifiers=[Accel], repeat=WhenEnabled, action_dispatch=DocumentMessage::Undo),
+		entry!(KeyDown(MouseBack); repeat=WhenEnabled, action_dispatch=DocumentMessage::Undo),
+		entry!(KeyDown(MouseForward); repeat=WhenEnabled, action_dispatch=DocumentMessage::Redo),
 		entry!(KeyDown(KeyA); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::DeselectAllLayers),
 		entry!(KeyDown(KeyA); modifiers=[Accel], action_dispatch=DocumentMessage::SelectAllLayers),
 		entry!(KeyDown(KeyS); modifiers=[Accel], action_dispatch=DocumentMessage::SaveDocument),
+		entry!(KeyDown(KeyC); modifiers=[Accel, Alt, Shift], action_dispatch=DocumentMessage::CopySelectedLayersAsPng { scale_factor: 2. }),
+		entry!(KeyDown(KeyC); modifiers=[Accel, Alt], action_dispatch=DocumentMessage::CopySelectedLayersAsPng { scale_factor: 1. }),
+		entry!(KeyDown(KeyD); modifiers=[Accel, Alt], action_dispatch=DocumentMessage::DuplicateSelectedLayersWithOffset),
+		entry!(KeyDown(KeyD); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::TransformSelectedLayersAgain),
 		entry!(KeyDown(KeyD); modifiers=[Accel], action_dispatch=DocumentMessage::DuplicateSelectedLayers),
 		entry!(KeyDown(KeyJ); modifiers=[Accel], action_dispatch=DocumentMessage::DuplicateSelectedLayers),
+		entry!(KeyDown(KeyV); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::PasteInto),
+		entry!(KeyDown(KeyV); modifiers=[Accel, Alt], action_dispatch=DocumentMessage::PasteAtPointer),
+		entry!(KeyDown(KeyV); modifiers=[Accel], action_dispatch=DocumentMessage::PasteInPlace),
+		entry!(KeyDown(KeyT); modifiers=[Accel, Alt], action_dispatch=DocumentMessage::TidySelectedLayers),
 		entry!(KeyDown(KeyG); modifiers=[Accel], action_dispatch=DocumentMessage::GroupSelectedLayers),
 		entry!(KeyDown(KeyG); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::UngroupSelectedLayers),
 		entry!(KeyDown(KeyN); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::CreateEmptyFolder { container_path: vec![] }),
+		entry!(KeyDown(KeyU); modifiers=[Alt, Shift], action_dispatch=DocumentMessage::BooleanOperation(BooleanOperation::Union)),
+		entry!(KeyDown(KeyS); modifiers=[Alt, Shift], action_dispatch=DocumentMessage::BooleanOperation(BooleanOperation::SubtractFront)),
+		entry!(KeyDown(KeyB); modifiers=[Alt, Shift], action_dispatch=DocumentMessage::BooleanOperation(BooleanOperation::SubtractBack)),
+		entry!(KeyDown(KeyI); modifiers=[Alt, Shift], action_dispatch=DocumentMessage::BooleanOperation(BooleanOperation::Intersection)),
+		entry!(KeyDown(KeyE); modifiers=[Alt, Shift], action_dispatch=DocumentMessage::BooleanOperation(BooleanOperation::Difference)),
 		entry!(KeyDown(Digit0); modifiers=[Accel], action_dispatch=DocumentMessage::ZoomCanvasToFitAll),
 		entry!(KeyDown(Digit1); modifiers=[Accel], action_dispatch=DocumentMessage::ZoomCanvasTo100Percent),
 		entry!(KeyDown(Digit2); modifiers=[Accel], action_dispatch=DocumentMessage::ZoomCanvasTo200Percent),
+		entry!(KeyDown(Backslash); modifiers=[Accel], action_dispatch=NavigationMessage::ZoomToPreviousView),
+		entry!(KeyDown(KeyY); modifiers=[Accel], action_dispatch=DocumentMessage::ToggleCmykPreview),
 		entry!(KeyDown(BracketLeft); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::SelectedLayersLowerToBack),
 		entry!(KeyDown(BracketRight); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::SelectedLayersRaiseToFront),
 		entry!(KeyDown(BracketLeft); modifiers=[Accel], action_dispatch=DocumentMessage::SelectedLayersLower),
 		entry!(KeyDown(BracketRight); modifiers=[Accel], action_dispatch=DocumentMessage::SelectedLayersRaise),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[Shift], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowUp); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowDown); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowLeft); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
-		entry!(KeyDown(ArrowRight); action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowLeft], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift, ArrowRight], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[Shift], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowLeft], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift, ArrowRight], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[Shift], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowUp], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift, ArrowDown], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[Shift], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -BIG_NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowUp], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: -BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift, ArrowDown], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: BIG_NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[Shift], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: BIG_NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowLeft], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); modifiers=[ArrowRight], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowUp); repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowLeft], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); modifiers=[ArrowRight], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowDown); repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: 0., delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowUp], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); modifiers=[ArrowDown], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowLeft); repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: -NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowUp], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: -NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); modifiers=[ArrowDown], repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: NUDGE_AMOUNT, resize: Alt, resize_opposite_corner: Control }),
+		entry!(KeyDown(ArrowRight); repeat=Always, action_dispatch=DocumentMessage::NudgeSelectedLayers { delta_x: NUDGE_AMOUNT, delta_y: 0., resize: Alt, resize_opposite_corner: Control }),
 		//
 		// TransformLayerMessage
 		entry!(KeyDown(KeyG); action_dispatch=TransformLayerMessage::BeginGrab),
@@ -311,23 +370,22 @@ pub fn default_mapping() -> Mapping {
 		entry!(KeyDown(KeyS); action_dispatch=TransformLayerMessage::BeginScale),
 		//
 		// NavigationMessage
-		entry!(KeyDown(Mmb); modifiers=[Control], action_dispatch=NavigationMessage::RotateCanvasBegin),
-		entry!(KeyDown(Mmb); modifiers=[Shift], action_dispatch=NavigationMessage::ZoomCanvasBegin),
-		entry!(KeyDown(Mmb); action_dispatch=NavigationMessage::TranslateCanvasBegin),
-		entry!(KeyUp(Mmb); action_dispatch=NavigationMessage::TransformCanvasEnd),
-		entry!(KeyDown(Lmb); modifiers=[Space], action_dispatch=NavigationMessage::TranslateCanvasBegin),
-		entry!(KeyUp(Lmb); modifiers=[Space], action_dispatch=NavigationMessage::TransformCanvasEnd),
-		entry!(KeyDown(NumpadAdd); modifiers=[Accel], action_dispatch=NavigationMessage::IncreaseCanvasZoom { center_on_mouse: false }),
-		entry!(KeyDown(Equal); modifiers=[Accel], action_dispatch=NavigationMessage::IncreaseCanvasZoom { center_on_mouse: false }),
-		entry!(KeyDown(Minus); modifiers=[Accel], action_dispatch=NavigationMessage::DecreaseCanvasZoom { center_on_mouse: false }),
+		entry!(KeyDown(PAN); modifiers=[Control], action_dispatch=NavigationMessage::RotateCanvasBegin),
+		entry!(KeyDown(ZOOM); modifiers=[Shift], action_dispatch=NavigationMessage::ZoomCanvasBegin),
+		entry!(KeyDown(PAN); action_dispatch=NavigationMessage::TranslateCanvasBegin),
+		entry!(KeyUp(PAN); action_dispatch=NavigationMessage::TransformCanvasEnd),
+		entry!(KeyDown(NumpadAdd); modifiers=[Accel], repeat=Always, action_dispatch=NavigationMessage::IncreaseCanvasZoom { center_on_mouse: false }),
+		entry!(KeyDown(Equal); modifiers=[Accel], repeat=Always, action_dispatch=NavigationMessage::IncreaseCanvasZoom { center_on_mouse: false }),
+		entry!(KeyDown(Minus); modifiers=[Accel], repeat=Always, action_dispatch=NavigationMessage::DecreaseCanvasZoom { center_on_mouse: false }),
 		entry!(WheelScroll; modifiers=[Control], action_dispatch=NavigationMessage::WheelCanvasZoom),
 		entry!(WheelScroll; modifiers=[Shift], action_dispatch=NavigationMessage::WheelCanvasTranslate { use_y_as_x: true }),
 		entry!(WheelScroll; action_dispatch=NavigationMessage::WheelCanvasTranslate { use_y_as_x: false }),
-		entry!(KeyDown(PageUp); modifiers=[Shift], action_dispatch=NavigationMessage::TranslateCanvasByViewportFraction { delta: DVec2::new(1., 0.) }),
-		entry!(KeyDown(PageDown); modifiers=[Shift], action_dispatch=NavigationMessage::TranslateCanvasByViewportFraction { delta: DVec2::new(-1., 0.) }),
-		entry!(KeyDown(PageUp); action_dispatch=NavigationMessage::TranslateCanvasByViewportFraction { delta: DVec2::new(0., 1.) }),
-		entry!(KeyDown(PageDown); action_dispatch=NavigationMessage::TranslateCanvasByViewportFraction { delta: DVec2::new(0., -1.) }),
+		entry!(KeyDown(PageUp); modifiers=[Shift], repeat=Always, action_dispatch=NavigationMessage::TranslateCanvasByViewportFraction { delta: DVec2::new(1., 0.) }),
+		entry!(KeyDown(PageDown); modifiers=[Shift], repeat=Always, action_dispatch=NavigationMessage::TranslateCanvasByViewportFraction { delta: DVec2::new(-1., 0.) }),
+		entry!(KeyDown(PageUp); repeat=Always, action_dispatch=NavigationMessage::TranslateCanvasByViewportFraction { delta: DVec2::new(0., 1.) }),
+		entry!(KeyDown(PageDown); repeat=Always, action_dispatch=NavigationMessage::TranslateCanvasByViewportFraction { delta: DVec2::new(0., -1.) }),
 		entry!(KeyDown(Period); action_dispatch=NavigationMessage::FitViewportToSelection),
+		entry!(KeyDown(KeyH); modifiers=[Shift], action_dispatch=NavigationMessage::ToggleCanvasFlip),
 		//
 		// PortfolioMessage
 		entry!(KeyDown(KeyO); modifiers=[Accel], action_dispatch=PortfolioMessage::OpenDocument),
@@ -337,14 +395,19 @@ pub fn default_mapping() -> Mapping {
 		entry!(KeyDown(KeyW); modifiers=[Accel], action_dispatch=PortfolioMessage::CloseActiveDocumentWithConfirmation),
 		entry!(KeyDown(KeyX); modifiers=[Accel], action_dispatch=PortfolioMessage::Cut { clipboard: Clipboard::Device }),
 		entry!(KeyDown(KeyC); modifiers=[Accel], action_dispatch=PortfolioMessage::Copy { clipboard: Clipboard::Device }),
-		entry!(KeyDown(KeyV); modifiers=[Accel], action_dispatch=FrontendMessage::TriggerPaste),
 		//
 		// DialogMessage
 		entry!(KeyDown(KeyW); modifiers=[Accel, Alt], action_dispatch=DialogMessage::CloseAllDocumentsWithConfirmation),
+		entry!(KeyDown(KeyE); modifiers=[Accel, Shift], action_dispatch=DocumentMessage::RepeatLastExport),
 		entry!(KeyDown(KeyE); modifiers=[Accel], action_dispatch=DialogMessage::RequestExportDialog),
 		entry!(KeyDown(KeyN); modifiers=[Accel], action_dispatch=DialogMessage::RequestNewDocumentDialog),
 		entry!(KeyDown(Comma); modifiers=[Accel], action_dispatch=DialogMessage::RequestPreferencesDialog),
 		//
+		// WorkspaceMessage
+		entry!(KeyDown(Digit1); modifiers=[Accel, Shift], action_dispatch=WorkspaceMessage::ApplyPreset { preset: WorkspacePreset::Design }),
+		entry!(KeyDown(Digit2); modifiers=[Accel, Shift], action_dispatch=WorkspaceMessage::ApplyPreset { preset: WorkspacePreset::Debug }),
+		entry!(KeyDown(Digit3); modifiers=[Accel, Shift], action_dispatch=WorkspaceMessage::ApplyPreset { preset: WorkspacePreset::Minimal }),
+		//
 		// DebugMessage
 		entry!(KeyDown(KeyT); modifiers=[Alt], action_dispatch=DebugMessage::ToggleTraceLogs),
 		entry!(KeyDown(Digit0); modifiers=[Alt], action_dispatch=DebugMessage::MessageOff),
@@ -362,10 +425,51 @@ pub fn default_mapping() -> Mapping {
 				action: TransformLayerMessage::TypeDigit { digit: i as u8 }.into(),
 				input: InputMapperMessage::KeyDown(*key),
 				modifiers: modifiers!(),
+				scope: BindingScope::Global,
+				condition: None,
+				repeat: KeyRepeat::Never,
+			},
+		);
+		key_down[*key as usize].0.insert(
+			0,
+			MappingEntry {
+				action: SelectToolMessage::TypeDigit { digit: i as u8 }.into(),
+				input: InputMapperMessage::KeyDown(*key),
+				modifiers: modifiers!(),
+				scope: BindingScope::Global,
+				condition: None,
+				repeat: KeyRepeat::Never,
+			},
+		);
+		key_down[*key as usize].0.insert(
+			0,
+			MappingEntry {
+				action: LineToolMessage::TypeDigit { digit: i as u8 }.into(),
+				input: InputMapperMessage::KeyDown(*key),
+				modifiers: modifiers!(),
+				scope: BindingScope::Global,
+				condition: None,
+				repeat: KeyRepeat::Never,
 			},
 		);
 	}
 
+	// The barrel button of a pen acts as a right click, unless the user's mapping binds it to something else like panning
+	for list in [&mut key_down, &mut key_up] {
+		let barrel_entries: Vec<_> = list[CONTEXT_MENU as usize]
+			.0
+			.iter()
+			.map(|entry| MappingEntry {
+				input: match entry.input {
+					KeyDown(_) => KeyDown(PenBarrel),
+					_ => KeyUp(PenBarrel),
+				},
+				..entry.clone()
+			})
+			.collect();
+		list[PenBarrel as usize].0.extend(barrel_entries);
+	}
+
 	let sort = |list: &mut KeyMappingEntries| list.0.sort_by(|u, v| v.modifiers.ones().cmp(&u.modifiers.ones()));
 	for list in [&mut key_up, &mut key_down] {
 		for sublist in list {
@@ -408,6 +512,72 @@ pub fn zoom_with_scroll() -> Mapping {
 	mapping
 }
 
+/// Makes dragging with the middle mouse button rotate or zoom the canvas, swapping it with the shortcut that does so to pan instead.
+pub fn apply_middle_drag_behavior(mapping: &mut Mapping, behavior: MiddleDragBehavior) {
+	let pan: Message = NavigationMessage::TranslateCanvasBegin.into();
+	let swapped: Message = match behavior {
+		MiddleDragBehavior::Pan => return,
+		MiddleDragBehavior::Rotate => NavigationMessage::RotateCanvasBegin.into(),
+		MiddleDragBehavior::Zoom => NavigationMessage::ZoomCanvasBegin.into(),
+	};
+
+	for entry in &mut mapping.key_down[PointerRole::Pan.button() as usize].0 {
+		if entry.action == pan {
+			entry.action = swapped.clone();
+		} else if entry.action == swapped {
+			entry.action = pan.clone();
+		}
+	}
+}
+
+/// Gives each pointer role to the input chosen for it by copying the entries of the role's mouse button, so pressing and releasing
+/// that input does whatever pressing and releasing the button does. The copies take precedence over other entries for the same input.
+pub fn apply_pointer_roles(mapping: &mut Mapping, roles: &PointerRoles) {
+	for role in [PointerRole::Pan, PointerRole::Zoom, PointerRole::ContextMenu] {
+		let (input_key, held_key) = match roles.get(role) {
+			RoleTrigger::None => continue,
+			RoleTrigger::Click(key) => (Key::Lmb, Some(key)),
+			RoleTrigger::Hold(key) if RoleTrigger::HOLD_KEYS.contains(&key) => (key, None),
+			// Holding a modifier key alone would press the mouse button whenever a shortcut using that modifier is pressed
+			RoleTrigger::Hold(_) => continue,
+		};
+
+		// Panning and zooming share the middle button, told apart by the modifiers held with it, which the middle drag preference may swap
+		let button = role.button();
+		let role_action: Option<Message> = match role {
+			PointerRole::Pan => Some(NavigationMessage::TranslateCanvasBegin.into()),
+			PointerRole::Zoom => Some(NavigationMessage::ZoomCanvasBegin.into()),
+			PointerRole::ContextMenu => None,
+		};
+		let role_modifiers = match role_action {
+			Some(action) => match mapping.key_down[button as usize].0.iter().find(|entry| entry.action == action) {
+				Some(entry) => entry.modifiers,
+				None => continue,
+			},
+			None => KeyStates::new(),
+		};
+
+		let pressed = mapping.key_down[button as usize].0.iter().filter(|entry| entry.modifiers == role_modifiers).map(|entry| MappingEntry {
+			input: InputMapperMessage::KeyDown(input_key),
+			modifiers: KeyStates::new(),
+			..entry.clone()
+		});
+		let released = mapping.key_up[button as usize].0.iter().map(|entry| MappingEntry {
+			input: InputMapperMessage::KeyUp(input_key),
+			..entry.clone()
+		});
+		let copies: Vec<_> = pressed.chain(released).collect();
+
+		// Each copy is added ahead of the last, so they're added in reverse to keep the order of the button's entries
+		for mut entry in copies.into_iter().rev() {
+			if let Some(key) = held_key {
+				entry.modifiers.set(key as usize);
+			}
+			mapping.add_with_precedence(entry);
+		}
+	}
+}
+
 fn apply_mapping_patch<'a, const N: usize, const M: usize, const X: usize, const Y: usize>(
 	mapping: &mut Mapping,
 	remove: impl IntoIterator<Item = &'a [&'a [MappingEntry; N]; M]>,
@@ -1,11 +1,12 @@
 use crate::messages::input_mapper::utility_types::input_keyboard::Key;
+use crate::messages::input_mapper::utility_types::mapping_definition::MappingDefinition;
 use crate::messages::prelude::*;
 
 use serde::{Deserialize, Serialize};
 
 #[remain::sorted]
 #[impl_message(Message, KeyMappingMessage, Lookup)]
-#[derive(PartialEq, Eq, Clone, Debug, Hash, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum InputMapperMessage {
 	// Sub-messages
 	#[remain::unsorted]
@@ -17,6 +18,12 @@ pub enum InputMapperMessage {
 
 	// Messages
 	DoubleClick,
+	/// A held key repeating, which repeats the action its key down is bound to if that action is one that repeats.
+	KeyRepeat(Key),
 	PointerMove,
+	/// Rebuilds the active mapping from the built-in one and the user's current [MappingDefinition], reporting its status to the frontend.
+	ReloadMapping,
+	/// Replaces the user's changes to the built-in mapping, unless the definition is invalid, in which case the current mapping is kept.
+	SetMapping(MappingDefinition),
 	WheelScroll,
 }
@@ -1,27 +1,109 @@
+use super::default_mapping::built_in_mapping;
 use super::utility_types::input_keyboard::KeysGroup;
-use super::utility_types::misc::Mapping;
+use super::utility_types::mapping_definition::MappingDefinition;
+use super::utility_types::misc::{Mapping, MappingContext, MiddleDragBehavior, PointerRoles};
+use crate::messages::input_mapper::key_mapping::MappingVariant;
 use crate::messages::input_mapper::utility_types::input_keyboard::{self, Key};
 use crate::messages::prelude::*;
 
 use std::fmt::Write;
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct InputMapperMessageHandler {
 	mapping: Mapping,
+	/// The built-in mapping that the user's changes are made to.
+	variant: MappingVariant,
+	middle_drag: MiddleDragBehavior,
+	pointer_roles: PointerRoles,
+	definition: MappingDefinition,
+	/// Whether holding the keys of undo and redo, whose bindings repeat only when enabled, repeats them.
+	repeat_undo_redo: bool,
 }
 
-impl MessageHandler<InputMapperMessage, (&InputPreprocessorMessageHandler, ActionList)> for InputMapperMessageHandler {
-	fn process_message(&mut self, message: InputMapperMessage, responses: &mut VecDeque<Message>, (input, actions): (&InputPreprocessorMessageHandler, ActionList)) {
-		if let Some(message) = self.mapping.match_input_message(message, &input.keyboard, actions) {
-			responses.add(message);
+impl Default for InputMapperMessageHandler {
+	fn default() -> Self {
+		Self {
+			mapping: Mapping::default(),
+			variant: MappingVariant::default(),
+			middle_drag: MiddleDragBehavior::default(),
+			pointer_roles: PointerRoles::default(),
+			definition: MappingDefinition::default(),
+			repeat_undo_redo: true,
+		}
+	}
+}
+
+/// The input preprocessor, the currently available actions, and the state of the editor the bindings are matched against.
+impl MessageHandler<InputMapperMessage, (&InputPreprocessorMessageHandler, ActionList, MappingContext)> for InputMapperMessageHandler {
+	fn process_message(&mut self, message: InputMapperMessage, responses: &mut VecDeque<Message>, (input, actions, context): (&InputPreprocessorMessageHandler, ActionList, MappingContext)) {
+		match message {
+			InputMapperMessage::KeyRepeat(key) => {
+				if let Some(message) = self.mapping.match_key_repeat(key, &input.keyboard, actions, context, self.repeat_undo_redo) {
+					responses.add(message);
+				}
+			}
+			InputMapperMessage::ReloadMapping => self.rebuild_mapping(responses),
+			InputMapperMessage::SetMapping(definition) => {
+				match definition.build(self.built_in_mapping()) {
+					Ok((mapping, conflicts)) => {
+						self.mapping = mapping;
+						self.definition = definition;
+						send_mapping_status(&self.definition, Vec::new(), conflicts.iter().map(ToString::to_string).collect(), responses);
+						// Only changes the user made are saved, since the mapping is also rebuilt while the saved keymap hasn't been loaded yet
+						let keymap = serde_json::to_string(&self.definition).expect("Failed to serialize the keymap");
+						responses.add(FrontendMessage::TriggerSaveKeymap { keymap });
+					}
+					// Keep using the current mapping until the user fixes the problems
+					Err(errors) => send_mapping_status(&definition, errors.iter().map(ToString::to_string).collect(), Vec::new(), responses),
+				}
+			}
+			message => {
+				if let Some(message) = self.mapping.match_input_message(message, &input.keyboard, actions, context) {
+					responses.add(message);
+				}
+			}
 		}
 	}
 	advertise_actions!();
 }
 
 impl InputMapperMessageHandler {
-	pub fn set_mapping(&mut self, mapping: Mapping) {
-		self.mapping = mapping;
+	pub fn set_variant(&mut self, variant: MappingVariant, responses: &mut VecDeque<Message>) {
+		self.variant = variant;
+		self.rebuild_mapping(responses);
+	}
+
+	pub fn set_middle_drag_behavior(&mut self, middle_drag: MiddleDragBehavior, responses: &mut VecDeque<Message>) {
+		self.middle_drag = middle_drag;
+		self.rebuild_mapping(responses);
+	}
+
+	pub fn set_pointer_roles(&mut self, pointer_roles: PointerRoles, responses: &mut VecDeque<Message>) {
+		self.pointer_roles = pointer_roles;
+		self.rebuild_mapping(responses);
+	}
+
+	pub fn set_repeat_undo_redo(&mut self, repeat_undo_redo: bool) {
+		self.repeat_undo_redo = repeat_undo_redo;
+	}
+
+	/// The mapping that the user's changes are made to, given the scroll, middle drag, and pointer role preferences.
+	fn built_in_mapping(&self) -> Mapping {
+		built_in_mapping(&self.variant, self.middle_drag, &self.pointer_roles)
+	}
+
+	/// Applies the user's changes to the built-in mapping, falling back to the built-in mapping alone if they no longer apply to it.
+	fn rebuild_mapping(&mut self, responses: &mut VecDeque<Message>) {
+		match self.definition.build(self.built_in_mapping()) {
+			Ok((mapping, conflicts)) => {
+				self.mapping = mapping;
+				send_mapping_status(&self.definition, Vec::new(), conflicts.iter().map(ToString::to_string).collect(), responses);
+			}
+			Err(errors) => {
+				self.mapping = self.built_in_mapping();
+				send_mapping_status(&self.definition, errors.iter().map(ToString::to_string).collect(), Vec::new(), responses);
+			}
+		}
 	}
 
 	pub fn hints(&self, actions: ActionList) -> String {
@@ -97,3 +179,14 @@ impl InputMapperMessageHandler {
 			.collect::<Vec<_>>()
 	}
 }
+
+fn send_mapping_status(definition: &MappingDefinition, errors: Vec<String>, conflicts: Vec<String>, responses: &mut VecDeque<Message>) {
+	for error in &errors {
+		warn!("Keyboard shortcuts not applied: {error}");
+	}
+
+	let keymap = serde_json::to_string(definition).expect("Failed to serialize the keymap");
+	responses.add(FrontendMessage::UpdateKeymap { keymap, errors, conflicts });
+	// Show the new shortcuts in the hints for the active tool
+	responses.add(ToolMessage::UpdateHints);
+}
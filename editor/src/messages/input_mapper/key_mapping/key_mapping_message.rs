@@ -1,15 +1,25 @@
+use crate::messages::input_mapper::utility_types::misc::{MiddleDragBehavior, PointerRoles};
 use crate::messages::prelude::*;
 
 use serde::{Deserialize, Serialize};
 
 #[remain::sorted]
 #[impl_message(Message, KeyMapping)]
-#[derive(PartialEq, Eq, Clone, Debug, Hash, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum KeyMappingMessage {
 	#[child]
 	Lookup(InputMapperMessage),
 	#[child]
 	ModifyMapping(MappingVariant),
+	SetMiddleDragBehavior {
+		middle_drag: MiddleDragBehavior,
+	},
+	SetPointerRoles {
+		pointer_roles: PointerRoles,
+	},
+	SetRepeatUndoRedo {
+		repeat_undo_redo: bool,
+	},
 }
 
 #[remain::sorted]
@@ -1,4 +1,5 @@
 use crate::messages::input_mapper::utility_types::input_keyboard::KeysGroup;
+use crate::messages::input_mapper::utility_types::misc::MappingContext;
 use crate::messages::prelude::*;
 
 #[derive(Debug, Default)]
@@ -6,11 +7,14 @@ pub struct KeyMappingMessageHandler {
 	mapping_handler: InputMapperMessageHandler,
 }
 
-impl MessageHandler<KeyMappingMessage, (&InputPreprocessorMessageHandler, ActionList)> for KeyMappingMessageHandler {
-	fn process_message(&mut self, message: KeyMappingMessage, responses: &mut VecDeque<Message>, data: (&InputPreprocessorMessageHandler, ActionList)) {
+impl MessageHandler<KeyMappingMessage, (&InputPreprocessorMessageHandler, ActionList, MappingContext)> for KeyMappingMessageHandler {
+	fn process_message(&mut self, message: KeyMappingMessage, responses: &mut VecDeque<Message>, data: (&InputPreprocessorMessageHandler, ActionList, MappingContext)) {
 		match message {
 			KeyMappingMessage::Lookup(input) => self.mapping_handler.process_message(input, responses, data),
-			KeyMappingMessage::ModifyMapping(new_layout) => self.mapping_handler.set_mapping(new_layout.into()),
+			KeyMappingMessage::ModifyMapping(new_layout) => self.mapping_handler.set_variant(new_layout, responses),
+			KeyMappingMessage::SetMiddleDragBehavior { middle_drag } => self.mapping_handler.set_middle_drag_behavior(middle_drag, responses),
+			KeyMappingMessage::SetPointerRoles { pointer_roles } => self.mapping_handler.set_pointer_roles(pointer_roles, responses),
+			KeyMappingMessage::SetRepeatUndoRedo { repeat_undo_redo } => self.mapping_handler.set_repeat_undo_redo(repeat_undo_redo),
 		}
 	}
 	advertise_actions!();
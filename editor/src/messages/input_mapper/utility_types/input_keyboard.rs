@@ -206,6 +206,10 @@ pub enum Key {
 	Lmb,
 	Rmb,
 	Mmb,
+	PenEraser,
+	PenBarrel,
+	MouseBack,
+	MouseForward,
 
 	// This has to be the last element in the enum
 	NumKeys,
@@ -289,6 +293,10 @@ impl fmt::Display for Key {
 				KeyboardPlatformLayout::Standard => "Ctrl",
 				KeyboardPlatformLayout::Mac => "⌘",
 			},
+			Self::PenEraser => "Pen Eraser",
+			Self::PenBarrel => "Pen Barrel Button",
+			Self::MouseBack => "Mouse Back Button",
+			Self::MouseForward => "Mouse Forward Button",
 
 			_ => key_name.as_str(),
 		};
@@ -1,5 +1,5 @@
-use crate::consts::DRAG_THRESHOLD;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::hit_targets;
 
 use bitflags::bitflags;
 use glam::DVec2;
@@ -65,6 +65,10 @@ pub struct MouseState {
 	pub position: ViewportPosition,
 	pub mouse_keys: MouseKeys,
 	pub scroll_delta: ScrollDelta,
+	/// The pressure of the pen or touch from 0 to 1, or 0.5 for a mouse while a button is held and 0 otherwise.
+	pub pressure: f64,
+	/// When the event happened, in milliseconds from the frontend's clock, or 0 when it isn't known.
+	pub time: f64,
 }
 
 impl MouseState {
@@ -77,6 +81,8 @@ impl MouseState {
 			position: (x, y).into(),
 			mouse_keys: MouseKeys::default(),
 			scroll_delta: ScrollDelta::default(),
+			pressure: 0.,
+			time: 0.,
 		}
 	}
 
@@ -87,11 +93,13 @@ impl MouseState {
 			position,
 			mouse_keys,
 			scroll_delta: ScrollDelta::default(),
+			pressure: 0.,
+			time: 0.,
 		}
 	}
 
 	pub fn finish_transaction(&self, drag_start: DVec2, responses: &mut VecDeque<Message>) {
-		match drag_start.distance(self.position) <= DRAG_THRESHOLD {
+		match drag_start.distance(self.position) <= hit_targets::drag_threshold() {
 			true => responses.add(DocumentMessage::AbortTransaction),
 			false => responses.add(DocumentMessage::CommitTransaction),
 		}
@@ -103,6 +111,8 @@ pub struct EditorMouseState {
 	pub editor_position: EditorPosition,
 	pub mouse_keys: MouseKeys,
 	pub scroll_delta: ScrollDelta,
+	pub pressure: f64,
+	pub time: f64,
 }
 
 impl EditorMouseState {
@@ -115,6 +125,8 @@ impl EditorMouseState {
 			editor_position: (x, y).into(),
 			mouse_keys: MouseKeys::default(),
 			scroll_delta: ScrollDelta::default(),
+			pressure: 0.,
+			time: 0.,
 		}
 	}
 
@@ -125,6 +137,8 @@ impl EditorMouseState {
 			editor_position,
 			mouse_keys,
 			scroll_delta: ScrollDelta::default(),
+			pressure: 0.,
+			time: 0.,
 		}
 	}
 
@@ -133,6 +147,8 @@ impl EditorMouseState {
 			position: self.editor_position - active_viewport_bounds.top_left,
 			mouse_keys: self.mouse_keys,
 			scroll_delta: self.scroll_delta,
+			pressure: self.pressure,
+			time: self.time,
 		}
 	}
 }
@@ -144,5 +160,9 @@ bitflags! {
 		const LEFT   = 0b0000_0001;
 		const RIGHT  = 0b0000_0010;
 		const MIDDLE = 0b0000_0100;
+		const ERASER = 0b0000_1000;
+		const BARREL = 0b0001_0000;
+		const BACK   = 0b0010_0000;
+		const FORWARD = 0b0100_0000;
 	}
 }
@@ -10,27 +10,43 @@ macro_rules! modifiers {
 	}};
 }
 
+/// Constructs the `KeyRepeat` of an entry from the name of its variant, or `KeyRepeat::Never` if none is given.
+macro_rules! repeat {
+	() => {
+		KeyRepeat::Never
+	};
+	($repeat:ident) => {
+		KeyRepeat::$repeat
+	};
+}
+
 /// Builds a slice of `MappingEntry` struct(s) that are used to:
 /// - ...dispatch the given `action_dispatch` as an output `Message` if its discriminant is a currently available action
 /// - ...when the `InputMapperMessage` enum variant, as specified at the start and followed by a semicolon, is received
 /// - ...while the optional `modifiers` being pressed.
+/// - ...and only while the optional `condition` holds.
+/// - ...and again as the key is held, if the optional `repeat` says the action repeats (see `KeyRepeat`).
 ///
 /// Syntax:
 /// ```rs
-/// entry_for_layout!(Key; modifiers?: Key[], refresh_keys?: Key[], action_dispatch: Message)
+/// entry_for_layout!(Key; modifiers?: Key[], refresh_keys?: Key[], repeat?: KeyRepeat, action_dispatch: Message)
+/// entry_for_layout!(Key; modifiers?: Key[], repeat?: KeyRepeat, condition: BindingCondition, action_dispatch: Message)
 /// ```
 ///
 /// The actions system controls which actions are currently available. Those are provided by the different message handlers based on the current application state and context.
 /// Each handler adds or removes actions in the form of message discriminants. Here, we tie an input condition (such as a hotkey) to an action's full message.
 /// When an action is currently available, and the user enters that input, the action's message is dispatched on the message bus.
 macro_rules! entry {
-	($input:expr; $(modifiers=[$($modifier:ident),*],)? $(refresh_keys=[$($refresh:ident),* $(,)?],)? action_dispatch=$action_dispatch:expr$(,)?) => {
+	($input:expr; $(modifiers=[$($modifier:ident),*],)? $(refresh_keys=[$($refresh:ident),* $(,)?],)? $(repeat=$repeat:ident,)? action_dispatch=$action_dispatch:expr$(,)?) => {
 		&[&[
 			// Cause the `action_dispatch` message to be sent when the specified input occurs.
 			MappingEntry {
 				action: $action_dispatch.into(),
 				input: $input,
 				modifiers: modifiers!($($($modifier),*)?),
+				scope: BindingScope::Global,
+				condition: None,
+				repeat: repeat!($($repeat)?),
 			},
 
 			// Also cause the `action_dispatch` message to be sent when any of the specified refresh keys change.
@@ -44,16 +60,36 @@ macro_rules! entry {
 				action: $action_dispatch.into(),
 				input: InputMapperMessage::KeyDown(Key::$refresh),
 				modifiers: modifiers!(),
+				scope: BindingScope::Global,
+				condition: None,
+				repeat: KeyRepeat::Never,
 			},
 			MappingEntry {
 				action: $action_dispatch.into(),
 				input: InputMapperMessage::KeyUp(Key::$refresh),
 				modifiers: modifiers!(),
+				scope: BindingScope::Global,
+				condition: None,
+				repeat: KeyRepeat::Never,
 			},
 			)*
 			)*
 		]]
 	};
+
+	($input:expr; $(modifiers=[$($modifier:ident),*],)? $(repeat=$repeat:ident,)? condition=$condition:expr, action_dispatch=$action_dispatch:expr$(,)?) => {
+		&[&[
+			// Cause the `action_dispatch` message to be sent when the specified input occurs while the condition holds.
+			MappingEntry {
+				action: $action_dispatch.into(),
+				input: $input,
+				modifiers: modifiers!($($($modifier),*)?),
+				scope: BindingScope::Global,
+				condition: Some($condition),
+				repeat: repeat!($($repeat)?),
+			},
+		]]
+	};
 }
 
 /// Constructs a `KeyMappingEntries` list for each input type and inserts every given entry into the list corresponding to its input type.
@@ -80,6 +116,7 @@ macro_rules! mapping {
 					InputMapperMessage::DoubleClick => &mut double_click,
 					InputMapperMessage::WheelScroll => &mut wheel_scroll,
 					InputMapperMessage::PointerMove => &mut pointer_move,
+					InputMapperMessage::KeyRepeat(_) | InputMapperMessage::ReloadMapping | InputMapperMessage::SetMapping(_) => continue,
 				};
 				// Push each entry to the corresponding `KeyMappingEntries` list for its input type
 				corresponding_list.push(entry.clone());
@@ -102,3 +139,4 @@ pub(crate) use action_keys;
 pub(crate) use entry;
 pub(crate) use mapping;
 pub(crate) use modifiers;
+pub(crate) use repeat;
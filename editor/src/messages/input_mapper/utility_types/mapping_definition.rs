@@ -0,0 +1,582 @@
+use super::input_keyboard::{Key, KeyStates, KeysGroup};
+use super::misc::{BindingCondition, BindingScope, KeyRepeat, Mapping, MappingEntry};
+use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::try_tool_message_to_tool_type;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A user's changes to the built-in keyboard shortcuts, in a form that the frontend can save and push back to the editor.
+///
+/// The `unbind` bindings are removed from the built-in mapping before the `bind` bindings are added, so a shortcut is rebound
+/// by unbinding its built-in binding and binding the same action to another input. For example, swapping two tools' shortcuts
+/// or moving shortcuts to the keys at the same positions on an AZERTY keyboard.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MappingDefinition {
+	#[serde(default)]
+	pub unbind: Vec<BindingDefinition>,
+	/// These take precedence over any built-in bindings of the same input and modifiers.
+	#[serde(default)]
+	pub bind: Vec<BindingDefinition>,
+}
+
+/// An input, made while holding down some modifier keys, and the message it dispatches when its action is available.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BindingDefinition {
+	pub action: Message,
+	pub input: InputMapperMessage,
+	#[serde(default)]
+	pub modifiers: Vec<Key>,
+	/// Where the binding applies. Left out of the keymap for bindings that apply everywhere.
+	#[serde(default, skip_serializing_if = "BindingScope::is_global")]
+	pub scope: BindingScope,
+	/// The state the editor must be in for the binding to apply. Left out of the keymap for bindings that apply regardless.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub condition: Option<BindingCondition>,
+	/// Whether holding the key repeats the action. Left out of the keymap for bindings that repeat like the built-in bindings of their action.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub repeat: Option<KeyRepeat>,
+}
+
+impl BindingDefinition {
+	/// The entry for the binding, which repeats like the built-in entries of the same action in `mapping` unless the binding says otherwise.
+	fn to_entry(&self, mapping: &Mapping) -> MappingEntry {
+		let mut modifiers = KeyStates::new();
+		for &key in &self.modifiers {
+			modifiers.set(key as usize);
+		}
+
+		let built_in_repeat = || {
+			mapping
+				.key_down
+				.iter()
+				.flat_map(|list| list.0.iter())
+				.find(|entry| entry.action == self.action)
+				.map(|entry| entry.repeat)
+		};
+
+		MappingEntry {
+			action: self.action.clone(),
+			input: self.input.clone(),
+			modifiers,
+			scope: self.scope,
+			condition: self.condition,
+			repeat: self.repeat.or_else(built_in_repeat).unwrap_or_default(),
+		}
+	}
+}
+
+impl From<&MappingEntry> for BindingDefinition {
+	fn from(entry: &MappingEntry) -> Self {
+		Self {
+			action: entry.action.clone(),
+			input: entry.input.clone(),
+			modifiers: entry.modifiers.iter().filter_map(|index| (index as u8).try_into().ok()).collect(),
+			scope: entry.scope,
+			condition: entry.condition,
+			repeat: Some(entry.repeat),
+		}
+	}
+}
+
+impl std::fmt::Display for BindingDefinition {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let mut keys = self.modifiers.clone();
+		let input = match self.input {
+			InputMapperMessage::KeyDown(key) => {
+				keys.push(key);
+				None
+			}
+			InputMapperMessage::KeyUp(key) => {
+				keys.push(key);
+				Some("(release)".to_string())
+			}
+			ref input => Some(format!("{:?}", input.to_discriminant())),
+		};
+
+		let keys = KeysGroup(keys).to_string();
+		let trigger = [keys, input.unwrap_or_default()].into_iter().filter(|part| !part.is_empty()).collect::<Vec<_>>().join(" ");
+		write!(f, "{trigger} → {:?}", self.action.to_discriminant())?;
+		if !self.scope.is_global() {
+			write!(f, " ({})", self.scope)?;
+		}
+		if let Some(condition) = self.condition {
+			write!(f, " (when {condition})")?;
+		}
+		Ok(())
+	}
+}
+
+/// A problem that keeps a [MappingDefinition] from being used, in which case the previous mapping stays active.
+#[derive(Clone, Debug, PartialEq, Error)]
+pub enum MappingDefinitionError {
+	#[error("\"{0}\" is not triggered by a user input")]
+	NotAnInput(BindingDefinition),
+	#[error("\"{0}\" can't be unbound because it is not one of the built-in shortcuts")]
+	UnknownBinding(BindingDefinition),
+	#[error("\"{0}\" is bound more than once")]
+	DuplicateBinding(BindingDefinition),
+}
+
+/// A binding from a [MappingDefinition] that is triggered by the same input and modifiers as another binding whose action may be
+/// available at the same time. The newly bound action takes precedence, so the other one is no longer reachable by that shortcut
+/// (or only outside of the new binding's scope or condition, if the new binding applies to a narrower scope or has a condition).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MappingConflict {
+	pub binding: BindingDefinition,
+	pub shadowed: BindingDefinition,
+}
+
+impl std::fmt::Display for MappingConflict {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "\"{}\" takes precedence over \"{}\"", self.binding, self.shadowed)
+	}
+}
+
+impl MappingDefinition {
+	pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+		serde_json::from_str(json)
+	}
+
+	/// Builds the mapping for this definition on top of the built-in `mapping`, along with the conflicts among its bindings.
+	pub fn build(&self, mut mapping: Mapping) -> Result<(Mapping, Vec<MappingConflict>), Vec<MappingDefinitionError>> {
+		let mut errors = Vec::new();
+
+		for binding in self.unbind.iter().chain(&self.bind) {
+			if !is_user_input(&binding.input) {
+				errors.push(MappingDefinitionError::NotAnInput(binding.clone()));
+			}
+		}
+		for (index, binding) in self.bind.iter().enumerate() {
+			if self.bind[..index].contains(binding) {
+				errors.push(MappingDefinitionError::DuplicateBinding(binding.clone()));
+			}
+		}
+		if !errors.is_empty() {
+			return Err(errors);
+		}
+
+		// The built-in entries are looked up before any are unbound, since rebinding an action usually moves it from its built-in key
+		let entries = self.bind.iter().map(|binding| binding.to_entry(&mapping)).collect::<Vec<_>>();
+
+		for binding in &self.unbind {
+			if !mapping.remove(&binding.to_entry(&mapping)) {
+				errors.push(MappingDefinitionError::UnknownBinding(binding.clone()));
+			}
+		}
+		if !errors.is_empty() {
+			return Err(errors);
+		}
+
+		let mut conflicts = Vec::new();
+		for (binding, entry) in self.bind.iter().zip(entries) {
+			let shadowed = mapping
+				.entries(&entry.input)
+				.filter(|other| other.modifiers == entry.modifiers && !other.scope.excludes(&entry.scope) && !exclusive_conditions(other, &entry) && !exclusive_actions(&other.action, &entry.action))
+				.map(|other| MappingConflict {
+					binding: binding.clone(),
+					shadowed: other.into(),
+				});
+			conflicts.extend(shadowed);
+
+			mapping.add_with_precedence(entry);
+		}
+
+		Ok((mapping, conflicts))
+	}
+}
+
+fn is_user_input(input: &InputMapperMessage) -> bool {
+	// A key repeating is matched against the bindings of its key down, rather than being bound itself
+	!matches!(input, InputMapperMessage::KeyRepeat(_) | InputMapperMessage::ReloadMapping | InputMapperMessage::SetMapping(_))
+}
+
+/// Bindings whose conditions can never hold at the same time never compete for a shortcut.
+fn exclusive_conditions(a: &MappingEntry, b: &MappingEntry) -> bool {
+	match (a.condition, b.condition) {
+		(Some(a), Some(b)) => a.excludes(&b),
+		_ => false,
+	}
+}
+
+/// Actions belonging to two different tools never compete for a shortcut, since only the active tool's actions are available.
+fn exclusive_actions(a: &Message, b: &Message) -> bool {
+	match (a, b) {
+		(Message::Tool(a), Message::Tool(b)) => match (try_tool_message_to_tool_type(a), try_tool_message_to_tool_type(b)) {
+			(Some(a), Some(b)) => a != b,
+			_ => false,
+		},
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::messages::input_mapper::default_mapping::{apply_middle_drag_behavior, built_in_mapping};
+	use crate::messages::input_mapper::key_mapping::MappingVariant;
+	use crate::messages::input_mapper::utility_types::misc::{MappingContext, MiddleDragBehavior, PointerRoles, RoleTrigger};
+	use crate::messages::tool::utility_types::ToolType;
+
+	use document_legacy::layers::style::ViewMode;
+
+	fn binding(action: impl Into<Message>, key: Key, modifiers: &[Key]) -> BindingDefinition {
+		BindingDefinition {
+			action: action.into(),
+			input: InputMapperMessage::KeyDown(key),
+			modifiers: modifiers.to_vec(),
+			scope: BindingScope::Global,
+			condition: None,
+			repeat: None,
+		}
+	}
+
+	#[test]
+	fn empty_definition_is_the_built_in_mapping() {
+		let (mapping, conflicts) = MappingDefinition::default().build(MappingVariant::Default.into()).unwrap();
+		let built_in: Mapping = MappingVariant::Default.into();
+
+		assert!(conflicts.is_empty());
+		assert_eq!(
+			mapping.entries(&InputMapperMessage::KeyDown(Key::KeyV)).count(),
+			built_in.entries(&InputMapperMessage::KeyDown(Key::KeyV)).count()
+		);
+	}
+
+	#[test]
+	fn swapping_tool_shortcuts() {
+		let definition = MappingDefinition {
+			unbind: vec![binding(ToolMessage::ActivateToolSelect, Key::KeyV, &[]), binding(ToolMessage::ActivateToolRectangle, Key::KeyM, &[])],
+			bind: vec![binding(ToolMessage::ActivateToolSelect, Key::KeyM, &[]), binding(ToolMessage::ActivateToolRectangle, Key::KeyV, &[])],
+		};
+		let (mapping, conflicts) = definition.build(MappingVariant::Default.into()).unwrap();
+
+		assert!(conflicts.is_empty(), "{conflicts:?}");
+		let first_action = |key| {
+			mapping
+				.entries(&InputMapperMessage::KeyDown(key))
+				.find(|entry| entry.modifiers.is_empty())
+				.map(|entry| entry.action.clone())
+		};
+		assert_eq!(first_action(Key::KeyM), Some(ToolMessage::ActivateToolSelect.into()));
+		assert_eq!(first_action(Key::KeyV), Some(ToolMessage::ActivateToolRectangle.into()));
+	}
+
+	#[test]
+	fn conflicting_binding_takes_precedence() {
+		let definition = MappingDefinition {
+			unbind: Vec::new(),
+			bind: vec![binding(ToolMessage::ActivateToolRectangle, Key::KeyV, &[])],
+		};
+		let (mapping, conflicts) = definition.build(MappingVariant::Default.into()).unwrap();
+
+		assert_eq!(conflicts.len(), 1);
+		assert_eq!(conflicts[0].shadowed.action, ToolMessage::ActivateToolSelect.into());
+		let first_action = mapping
+			.entries(&InputMapperMessage::KeyDown(Key::KeyV))
+			.find(|entry| entry.modifiers.is_empty())
+			.map(|entry| entry.action.clone());
+		assert_eq!(first_action, Some(ToolMessage::ActivateToolRectangle.into()));
+	}
+
+	#[test]
+	fn pen_barrel_button_is_remappable() {
+		let built_in: Mapping = MappingVariant::Default.into();
+		let actions = |mapping: &Mapping, key| mapping.entries(&InputMapperMessage::KeyDown(key)).map(|entry| entry.action.clone()).collect::<Vec<_>>();
+		assert_eq!(actions(&built_in, Key::PenBarrel), actions(&built_in, Key::Rmb), "the barrel button acts as a right click by default");
+
+		let definition = MappingDefinition {
+			unbind: Vec::new(),
+			bind: vec![binding(NavigationMessage::TranslateCanvasBegin, Key::PenBarrel, &[])],
+		};
+		let (mapping, conflicts) = definition.build(MappingVariant::Default.into()).unwrap();
+
+		assert!(!conflicts.is_empty());
+		let first_action = mapping
+			.entries(&InputMapperMessage::KeyDown(Key::PenBarrel))
+			.find(|entry| entry.modifiers.is_empty())
+			.map(|entry| entry.action.clone());
+		assert_eq!(first_action, Some(NavigationMessage::TranslateCanvasBegin.into()));
+	}
+
+	#[test]
+	fn middle_drag_behavior_swaps_with_panning() {
+		let mut mapping: Mapping = MappingVariant::Default.into();
+		apply_middle_drag_behavior(&mut mapping, MiddleDragBehavior::Rotate);
+
+		let mut control = KeyStates::new();
+		control.set(Key::Control as usize);
+		let actions = |modifiers: KeyStates| {
+			mapping
+				.entries(&InputMapperMessage::KeyDown(Key::Mmb))
+				.filter(|entry| entry.modifiers == modifiers)
+				.map(|entry| entry.action.clone())
+				.collect::<Vec<_>>()
+		};
+		assert!(actions(KeyStates::new()).contains(&NavigationMessage::RotateCanvasBegin.into()));
+		assert!(!actions(KeyStates::new()).contains(&NavigationMessage::TranslateCanvasBegin.into()));
+		assert!(actions(control).contains(&NavigationMessage::TranslateCanvasBegin.into()));
+	}
+
+	#[test]
+	fn pointer_roles_are_given_to_other_inputs() {
+		let mut modifiers = KeyStates::new();
+		let actions = |mapping: &Mapping, input, modifiers: &KeyStates| {
+			mapping
+				.entries(&input)
+				.filter(|entry| entry.modifiers == *modifiers)
+				.map(|entry| entry.action.clone())
+				.collect::<Vec<_>>()
+		};
+
+		// By default, Space + click pans like middle dragging
+		let mapping = built_in_mapping(&MappingVariant::Default, MiddleDragBehavior::default(), &PointerRoles::default());
+		modifiers.set(Key::Space as usize);
+		assert!(actions(&mapping, InputMapperMessage::KeyDown(Key::Lmb), &modifiers).contains(&NavigationMessage::TranslateCanvasBegin.into()));
+		assert!(actions(&mapping, InputMapperMessage::KeyUp(Key::Lmb), &modifiers).contains(&NavigationMessage::TransformCanvasEnd.into()));
+
+		// Zooming follows the middle drag preference to the modifiers it's moved to, and holding a key alone acts as the right button
+		let roles = PointerRoles {
+			pan: RoleTrigger::None,
+			zoom: RoleTrigger::Click(Key::Alt),
+			context_menu: RoleTrigger::Hold(Key::Space),
+		};
+		let mapping = built_in_mapping(&MappingVariant::Default, MiddleDragBehavior::Zoom, &roles);
+		assert!(actions(&mapping, InputMapperMessage::KeyDown(Key::Lmb), &modifiers).is_empty());
+
+		let mut alt = KeyStates::new();
+		alt.set(Key::Alt as usize);
+		assert!(actions(&mapping, InputMapperMessage::KeyDown(Key::Lmb), &alt).contains(&NavigationMessage::ZoomCanvasBegin.into()));
+		assert!(!actions(&mapping, InputMapperMessage::KeyDown(Key::Lmb), &alt).contains(&NavigationMessage::TranslateCanvasBegin.into()));
+
+		let right_click = actions(&mapping, InputMapperMessage::KeyDown(Key::Rmb), &KeyStates::new());
+		let held_space = actions(&mapping, InputMapperMessage::KeyDown(Key::Space), &KeyStates::new());
+		assert_eq!(held_space[..right_click.len()], right_click[..]);
+
+		// A modifier key held alone isn't given a role, even if one was saved for it
+		let roles = PointerRoles {
+			context_menu: RoleTrigger::Hold(Key::Control),
+			..roles
+		};
+		let mapping = built_in_mapping(&MappingVariant::Default, MiddleDragBehavior::Zoom, &roles);
+		let held_control = actions(&mapping, InputMapperMessage::KeyDown(Key::Control), &KeyStates::new());
+		assert!(right_click.iter().all(|action| !held_control.contains(action)), "{held_control:?}");
+	}
+
+	#[test]
+	fn zoom_with_scroll_swaps_modifiers() {
+		let actions = vec![vec![
+			Message::from(NavigationMessage::WheelCanvasZoom).to_discriminant(),
+			Message::from(NavigationMessage::WheelCanvasTranslate { use_y_as_x: false }).to_discriminant(),
+		]];
+		let mut control = KeyStates::new();
+		control.set(Key::Control as usize);
+		let matched = |variant: MappingVariant, modifiers: &KeyStates| {
+			let mapping: Mapping = variant.into();
+			mapping.match_input_message(InputMapperMessage::WheelScroll, modifiers, actions.clone(), MappingContext::default())
+		};
+
+		assert_eq!(
+			matched(MappingVariant::Default, &KeyStates::new()),
+			Some(NavigationMessage::WheelCanvasTranslate { use_y_as_x: false }.into())
+		);
+		assert_eq!(matched(MappingVariant::Default, &control), Some(NavigationMessage::WheelCanvasZoom.into()));
+		assert_eq!(matched(MappingVariant::ZoomWithScroll, &KeyStates::new()), Some(NavigationMessage::WheelCanvasZoom.into()));
+		assert_eq!(
+			matched(MappingVariant::ZoomWithScroll, &control),
+			Some(NavigationMessage::WheelCanvasTranslate { use_y_as_x: true }.into())
+		);
+	}
+
+	#[test]
+	fn invalid_definitions_are_rejected() {
+		let unknown = binding(ToolMessage::ActivateToolSelect, Key::KeyQ, &[Key::Alt, Key::Shift]);
+		let definition = MappingDefinition {
+			unbind: vec![unknown.clone()],
+			bind: Vec::new(),
+		};
+		assert_eq!(definition.build(MappingVariant::Default.into()).unwrap_err(), [MappingDefinitionError::UnknownBinding(unknown)]);
+
+		let not_an_input = BindingDefinition {
+			input: InputMapperMessage::ReloadMapping,
+			..binding(ToolMessage::ActivateToolSelect, Key::KeyV, &[])
+		};
+		let duplicate = binding(ToolMessage::ActivateToolSelect, Key::KeyQ, &[]);
+		let definition = MappingDefinition {
+			unbind: Vec::new(),
+			bind: vec![not_an_input.clone(), duplicate.clone(), duplicate.clone()],
+		};
+		assert_eq!(
+			definition.build(MappingVariant::Default.into()).unwrap_err(),
+			[MappingDefinitionError::NotAnInput(not_an_input), MappingDefinitionError::DuplicateBinding(duplicate)]
+		);
+	}
+
+	#[test]
+	fn scoped_binding_takes_precedence_only_in_its_scope() {
+		let global = binding(ToolMessage::ActivateToolSelect, Key::KeyQ, &[]);
+		let scoped = BindingDefinition {
+			scope: BindingScope::ActiveTool(ToolType::Pen),
+			..binding(ToolMessage::ActivateToolRectangle, Key::KeyQ, &[])
+		};
+		let definition = MappingDefinition {
+			unbind: Vec::new(),
+			bind: vec![scoped, global],
+		};
+		let (mapping, _) = definition.build(MappingVariant::Default.into()).unwrap();
+
+		let actions = vec![vec![
+			Message::from(ToolMessage::ActivateToolSelect).to_discriminant(),
+			Message::from(ToolMessage::ActivateToolRectangle).to_discriminant(),
+		]];
+		let matched = |active_tool| {
+			let context = MappingContext { active_tool, ..Default::default() };
+			mapping.match_input_message(InputMapperMessage::KeyDown(Key::KeyQ), &KeyStates::new(), actions.clone(), context)
+		};
+		assert_eq!(matched(Some(ToolType::Pen)), Some(ToolMessage::ActivateToolRectangle.into()));
+		assert_eq!(matched(Some(ToolType::Select)), Some(ToolMessage::ActivateToolSelect.into()));
+		assert_eq!(matched(None), Some(ToolMessage::ActivateToolSelect.into()));
+	}
+
+	#[test]
+	fn bindings_for_different_tools_do_not_conflict() {
+		let scoped = |tool, action: ToolMessage| BindingDefinition {
+			scope: BindingScope::ActiveTool(tool),
+			..binding(action, Key::KeyQ, &[Key::Alt, Key::Shift])
+		};
+		let definition = MappingDefinition {
+			unbind: Vec::new(),
+			bind: vec![scoped(ToolType::Pen, ToolMessage::ActivateToolSelect), scoped(ToolType::Path, ToolMessage::ActivateToolRectangle)],
+		};
+		let (_, conflicts) = definition.build(MappingVariant::Default.into()).unwrap();
+
+		assert!(conflicts.is_empty(), "{conflicts:?}");
+	}
+
+	#[test]
+	fn definition_round_trips_through_json() {
+		let definition = MappingDefinition {
+			unbind: vec![binding(ToolMessage::ActivateToolSelect, Key::KeyV, &[])],
+			bind: vec![
+				binding(ToolMessage::ActivateToolSelect, Key::KeyQ, &[Key::Shift]),
+				BindingDefinition {
+					scope: BindingScope::ActiveTool(ToolType::Pen),
+					..binding(ToolMessage::ActivateToolRectangle, Key::KeyQ, &[Key::Shift])
+				},
+			],
+		};
+		let json = serde_json::to_string(&definition).unwrap();
+
+		assert!(json.contains(r#""scope":"active-tool:Pen""#), "{json}");
+		assert!(!json.contains("condition"), "{json}");
+		assert_eq!(MappingDefinition::from_json(&json).unwrap(), definition);
+		assert!(MappingDefinition::from_json(&json.replace("active-tool:Pen", "active-tool:Quill")).is_err());
+	}
+
+	#[test]
+	fn conditional_binding_applies_only_while_its_condition_holds() {
+		let conditional = BindingDefinition {
+			condition: Some(BindingCondition::ViewMode(ViewMode::Outline)),
+			..binding(ToolMessage::ActivateToolRectangle, Key::KeyQ, &[])
+		};
+		let definition = MappingDefinition {
+			unbind: Vec::new(),
+			bind: vec![conditional.clone(), binding(ToolMessage::ActivateToolSelect, Key::KeyQ, &[])],
+		};
+		let (mapping, _) = definition.build(MappingVariant::Default.into()).unwrap();
+
+		let actions = vec![vec![
+			Message::from(ToolMessage::ActivateToolSelect).to_discriminant(),
+			Message::from(ToolMessage::ActivateToolRectangle).to_discriminant(),
+		]];
+		let matched = |view_mode| {
+			let context = MappingContext { view_mode, ..Default::default() };
+			mapping.match_input_message(InputMapperMessage::KeyDown(Key::KeyQ), &KeyStates::new(), actions.clone(), context)
+		};
+		// The unconditional binding was added last so it comes first, but the conditional one takes precedence while its condition holds
+		assert_eq!(matched(Some(ViewMode::Outline)), Some(ToolMessage::ActivateToolRectangle.into()));
+		assert_eq!(matched(Some(ViewMode::Normal)), Some(ToolMessage::ActivateToolSelect.into()));
+		assert_eq!(matched(None), Some(ToolMessage::ActivateToolSelect.into()));
+
+		let json = serde_json::to_string(&conditional).unwrap();
+		assert!(json.contains(r#""condition":"view-mode:Outline""#), "{json}");
+		assert_eq!(serde_json::from_str::<BindingDefinition>(&json).unwrap(), conditional);
+	}
+
+	#[test]
+	fn bindings_with_exclusive_conditions_do_not_conflict() {
+		let conditional = |condition, action: ToolMessage| BindingDefinition {
+			condition: Some(condition),
+			..binding(action, Key::KeyQ, &[Key::Alt, Key::Shift])
+		};
+		let definition = MappingDefinition {
+			unbind: Vec::new(),
+			bind: vec![
+				conditional(BindingCondition::HasSelection, ToolMessage::ActivateToolSelect),
+				conditional(BindingCondition::NoSelection, ToolMessage::ActivateToolRectangle),
+			],
+		};
+		let (_, conflicts) = definition.build(MappingVariant::Default.into()).unwrap();
+
+		assert!(conflicts.is_empty(), "{conflicts:?}");
+	}
+
+	#[test]
+	fn conditional_built_in_bindings_do_not_override_other_handlers() {
+		let mapping: Mapping = MappingVariant::Default.into();
+		let delete_layers = Message::from(DocumentMessage::DeleteSelectedLayers);
+		let matched = |other: Message, active_tool| {
+			let actions = vec![vec![delete_layers.to_discriminant(), other.to_discriminant()]];
+			let context = MappingContext {
+				active_tool,
+				has_selection: true,
+				..Default::default()
+			};
+			[Key::Delete, Key::Backspace].map(|key| mapping.match_input_message(InputMapperMessage::KeyDown(key), &KeyStates::new(), actions.clone(), context))
+		};
+
+		// Deleting the selected layers only applies while the Path tool or the node graph don't claim the key for themselves
+		let delete_points = Message::from(PathToolMessage::Delete);
+		assert_eq!(matched(delete_points.clone(), Some(ToolType::Path)), [Some(delete_points.clone()), Some(delete_points)]);
+		let delete_nodes = Message::from(NodeGraphMessage::DeleteSelectedNodes);
+		assert_eq!(matched(delete_nodes.clone(), Some(ToolType::Select)), [Some(delete_nodes.clone()), Some(delete_nodes)]);
+		let other = Message::from(ToolMessage::ActivateToolSelect);
+		assert_eq!(matched(other, Some(ToolType::Select)), [Some(delete_layers.clone()), Some(delete_layers)]);
+	}
+
+	#[test]
+	fn held_keys_repeat_only_actions_that_repeat() {
+		let definition = MappingDefinition {
+			unbind: Vec::new(),
+			bind: vec![binding(DocumentMessage::Undo, Key::KeyU, &[])],
+		};
+		let (mapping, _) = definition.build(MappingVariant::Default.into()).unwrap();
+
+		let nudge = DocumentMessage::NudgeSelectedLayers {
+			delta_x: 0.,
+			delta_y: 0.,
+			resize: Key::Alt,
+			resize_opposite_corner: Key::Control,
+		};
+		let undo = Message::from(DocumentMessage::Undo).to_discriminant();
+		let actions = vec![vec![
+			Message::from(nudge).to_discriminant(),
+			Message::from(DocumentMessage::DeleteSelectedLayers).to_discriminant(),
+			undo.clone(),
+		]];
+		let context = MappingContext {
+			has_selection: true,
+			..Default::default()
+		};
+		let repeated = |key, repeat_when_enabled| {
+			let message = mapping.match_key_repeat(key, &KeyStates::new(), actions.clone(), context, repeat_when_enabled);
+			message.map(|message| message.to_discriminant())
+		};
+
+		assert_eq!(repeated(Key::ArrowUp, false), Some(actions[0][0].clone()));
+		assert_eq!(repeated(Key::Delete, true), None);
+		// The user's binding repeats like the built-in bindings of undo, since it doesn't say otherwise
+		assert_eq!(repeated(Key::KeyU, true), Some(undo));
+		assert_eq!(repeated(Key::KeyU, false), None);
+	}
+}
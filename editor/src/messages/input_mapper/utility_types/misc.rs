@@ -1,9 +1,13 @@
 use super::input_keyboard::{all_required_modifiers_pressed, KeysGroup, LayoutKeysGroup};
 use crate::messages::input_mapper::key_mapping::MappingVariant;
-use crate::messages::input_mapper::utility_types::input_keyboard::{KeyStates, NUMBER_OF_KEYS};
+use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeyStates, NUMBER_OF_KEYS};
 use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ToolType;
+
+use document_legacy::layers::style::ViewMode;
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub struct Mapping {
@@ -21,38 +25,66 @@ impl Default for Mapping {
 }
 
 impl Mapping {
-	pub fn match_input_message(&self, message: InputMapperMessage, keyboard_state: &KeyStates, actions: ActionList) -> Option<Message> {
-		let list = self.associated_entries(&message);
-		list.match_mapping(keyboard_state, actions)
+	pub fn match_input_message(&self, message: InputMapperMessage, keyboard_state: &KeyStates, actions: ActionList, context: MappingContext) -> Option<Message> {
+		let list = self.associated_entries(&message)?;
+		list.match_mapping(keyboard_state, actions, context)
 	}
 
-	pub fn remove(&mut self, target_entry: &MappingEntry) {
-		let list = self.associated_entries_mut(&target_entry.input);
+	/// The message for a held key repeating, which is that of the entry its key down matches if that entry's action repeats.
+	/// An entry that doesn't repeat isn't passed over for one that does, so holding a key never does something that pressing it wouldn't.
+	pub fn match_key_repeat(&self, key: Key, keyboard_state: &KeyStates, actions: ActionList, context: MappingContext, repeat_when_enabled: bool) -> Option<Message> {
+		let entry = self.key_down[key as usize].matching_entry(keyboard_state, actions, context)?;
+		entry.repeat.repeats(repeat_when_enabled).then(|| entry.action.clone())
+	}
+
+	/// Removes the entry, returning whether it was part of the mapping.
+	pub fn remove(&mut self, target_entry: &MappingEntry) -> bool {
+		let Some(list) = self.associated_entries_mut(&target_entry.input) else { return false };
+		let length = list.0.len();
 		list.remove(target_entry);
+		list.0.len() != length
 	}
 
 	pub fn add(&mut self, new_entry: MappingEntry) {
-		let list = self.associated_entries_mut(&new_entry.input);
-		list.push(new_entry);
+		if let Some(list) = self.associated_entries_mut(&new_entry.input) {
+			list.push(new_entry);
+		}
+	}
+
+	/// Adds the entry ahead of the existing entries for its input that require the same number of modifiers or fewer,
+	/// so it is matched before any of them while entries with more specific modifiers keep being matched first.
+	pub fn add_with_precedence(&mut self, new_entry: MappingEntry) {
+		if let Some(list) = self.associated_entries_mut(&new_entry.input) {
+			let modifier_count = new_entry.modifiers.ones();
+			let index = list.0.iter().position(|entry| entry.modifiers.ones() <= modifier_count).unwrap_or(list.0.len());
+			list.0.insert(index, new_entry);
+		}
+	}
+
+	/// The entries triggered by an input, in the order they are matched.
+	pub fn entries(&self, input: &InputMapperMessage) -> impl Iterator<Item = &MappingEntry> {
+		self.associated_entries(input).into_iter().flat_map(|list| list.0.iter())
 	}
 
-	fn associated_entries(&self, message: &InputMapperMessage) -> &KeyMappingEntries {
+	fn associated_entries(&self, message: &InputMapperMessage) -> Option<&KeyMappingEntries> {
 		match message {
-			InputMapperMessage::KeyDown(key) => &self.key_down[*key as usize],
-			InputMapperMessage::KeyUp(key) => &self.key_up[*key as usize],
-			InputMapperMessage::DoubleClick => &self.double_click,
-			InputMapperMessage::WheelScroll => &self.wheel_scroll,
-			InputMapperMessage::PointerMove => &self.pointer_move,
+			InputMapperMessage::KeyDown(key) => Some(&self.key_down[*key as usize]),
+			InputMapperMessage::KeyUp(key) => Some(&self.key_up[*key as usize]),
+			InputMapperMessage::DoubleClick => Some(&self.double_click),
+			InputMapperMessage::WheelScroll => Some(&self.wheel_scroll),
+			InputMapperMessage::PointerMove => Some(&self.pointer_move),
+			InputMapperMessage::KeyRepeat(_) | InputMapperMessage::ReloadMapping | InputMapperMessage::SetMapping(_) => None,
 		}
 	}
 
-	fn associated_entries_mut(&mut self, message: &InputMapperMessage) -> &mut KeyMappingEntries {
+	fn associated_entries_mut(&mut self, message: &InputMapperMessage) -> Option<&mut KeyMappingEntries> {
 		match message {
-			InputMapperMessage::KeyDown(key) => &mut self.key_down[*key as usize],
-			InputMapperMessage::KeyUp(key) => &mut self.key_up[*key as usize],
-			InputMapperMessage::DoubleClick => &mut self.double_click,
-			InputMapperMessage::WheelScroll => &mut self.wheel_scroll,
-			InputMapperMessage::PointerMove => &mut self.pointer_move,
+			InputMapperMessage::KeyDown(key) => Some(&mut self.key_down[*key as usize]),
+			InputMapperMessage::KeyUp(key) => Some(&mut self.key_up[*key as usize]),
+			InputMapperMessage::DoubleClick => Some(&mut self.double_click),
+			InputMapperMessage::WheelScroll => Some(&mut self.wheel_scroll),
+			InputMapperMessage::PointerMove => Some(&mut self.pointer_move),
+			InputMapperMessage::KeyRepeat(_) | InputMapperMessage::ReloadMapping | InputMapperMessage::SetMapping(_) => None,
 		}
 	}
 }
@@ -61,18 +93,36 @@ impl Mapping {
 pub struct KeyMappingEntries(pub Vec<MappingEntry>);
 
 impl KeyMappingEntries {
-	pub fn match_mapping(&self, keyboard_state: &KeyStates, actions: ActionList) -> Option<Message> {
-		for mapping in self.0.iter() {
-			// Skip this entry if any of the required modifiers are missing
-			if all_required_modifiers_pressed(keyboard_state, &mapping.modifiers) {
+	/// The message of the first entry whose scope and condition apply, whose modifiers are pressed, and whose action is available.
+	/// Among the matching entries requiring the same modifiers as that one, an entry bound to a narrower scope is chosen instead,
+	/// or within the same scope, an entry with a condition whose action is handled by the same message handler.
+	pub fn match_mapping(&self, keyboard_state: &KeyStates, actions: ActionList, context: MappingContext) -> Option<Message> {
+		self.matching_entry(keyboard_state, actions, context).map(|entry| entry.action.clone())
+	}
+
+	/// The entry whose message [KeyMappingEntries::match_mapping] dispatches.
+	pub fn matching_entry(&self, keyboard_state: &KeyStates, actions: ActionList, context: MappingContext) -> Option<&MappingEntry> {
+		let mut matching = self.0.iter().filter(|mapping| {
+			// Skip this entry if it doesn't apply to the current context or any of the required modifiers are missing
+			mapping.scope.applies(context.active_tool)
+				&& mapping.condition.map_or(true, |condition| condition.holds(context))
+				&& all_required_modifiers_pressed(keyboard_state, &mapping.modifiers)
 				// Search for the action in the list of available actions to see if it's currently available to activate
-				let matching_action_found = actions.iter().flatten().any(|action| mapping.action.to_discriminant() == *action);
-				if matching_action_found {
-					return Some(mapping.action.clone());
-				}
+				&& actions.iter().flatten().any(|action| mapping.action.to_discriminant() == *action)
+		});
+
+		let first = matching.next()?;
+		let chosen = matching.filter(|mapping| mapping.modifiers == first.modifiers).fold(first, |chosen, mapping| {
+			let precedence = mapping.scope.precedence().cmp(&chosen.scope.precedence());
+			// A condition only decides between actions of the same handler, so it can't override another handler that made its action available
+			let conditional = mapping.condition.is_some() && chosen.condition.is_none() && message_handler(&mapping.action) == message_handler(&chosen.action);
+			if precedence.is_gt() || (precedence.is_eq() && conditional) {
+				mapping
+			} else {
+				chosen
 			}
-		}
-		None
+		});
+		Some(chosen)
 	}
 
 	pub fn push(&mut self, entry: MappingEntry) {
@@ -80,7 +130,7 @@ impl KeyMappingEntries {
 	}
 
 	pub fn remove(&mut self, target_entry: &MappingEntry) {
-		self.0.retain(|entry| entry != target_entry);
+		self.0.retain(|entry| !entry.same_binding(target_entry));
 	}
 
 	pub const fn new() -> Self {
@@ -93,6 +143,12 @@ impl KeyMappingEntries {
 	}
 }
 
+/// The name of the message handler which handles the message, such as `Portfolio.Document` for a [DocumentMessage].
+fn message_handler(message: &Message) -> String {
+	let name = message.to_discriminant().local_name();
+	name.rsplit_once('.').map_or(String::new(), |(handler, _)| handler.to_string())
+}
+
 #[derive(PartialEq, Clone, Debug)]
 pub struct MappingEntry {
 	/// Serves two purposes:
@@ -103,6 +159,281 @@ pub struct MappingEntry {
 	pub input: InputMapperMessage,
 	/// Any additional keys that must be also pressed for this input mapping to match
 	pub modifiers: KeyStates,
+	/// The context in which this input mapping applies
+	pub scope: BindingScope,
+	/// The state the editor must additionally be in for this input mapping to match, checked when the input occurs
+	pub condition: Option<BindingCondition>,
+	/// Whether holding the key down repeats the action
+	pub repeat: KeyRepeat,
+}
+
+impl MappingEntry {
+	/// Whether the two entries bind the same action to the same input, however they repeat.
+	pub fn same_binding(&self, other: &MappingEntry) -> bool {
+		self.action == other.action && self.input == other.input && self.modifiers == other.modifiers && self.scope == other.scope && self.condition == other.condition
+	}
+}
+
+/// Whether holding down the key of a binding repeats its action, after the delay and at the interval chosen in the preferences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyRepeat {
+	/// The action happens once for each press, like deleting, which would quickly do more than intended if it repeated.
+	#[default]
+	Never,
+	/// The action repeats, like nudging, which moves the selection further the longer the key is held.
+	Always,
+	/// The action repeats if the user has chosen for it to in the preferences, like undoing and redoing.
+	WhenEnabled,
+}
+
+impl KeyRepeat {
+	pub fn repeats(self, enabled: bool) -> bool {
+		match self {
+			KeyRepeat::Never => false,
+			KeyRepeat::Always => true,
+			KeyRepeat::WhenEnabled => enabled,
+		}
+	}
+}
+
+/// The state of the editor which bindings are matched against when an input occurs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MappingContext {
+	/// The active tool, or `None` while no document is open
+	pub active_tool: Option<ToolType>,
+	/// Whether any layers are selected in the open document
+	pub has_selection: bool,
+	/// How the open document is rendered, or `None` while no document is open
+	pub view_mode: Option<ViewMode>,
+}
+
+/// What dragging the canvas with the middle mouse button does without modifiers. The built-in shortcut for that
+/// action is given panning instead, so for example Ctrl + middle drag pans when middle dragging rotates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum MiddleDragBehavior {
+	#[default]
+	Pan,
+	Rotate,
+	Zoom,
+}
+
+/// The roles given to the middle and right mouse buttons, which the built-in shortcuts for them are derived from, so each role can
+/// also be given to another input for trackpads, pens, and one-button mice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum PointerRole {
+	/// Dragging the canvas to pan it, which is middle dragging by default
+	Pan,
+	/// Dragging the canvas to zoom it, which is Shift + middle dragging by default
+	Zoom,
+	/// The right click, which cancels and confirms what the tools are doing, or samples and fills with the secondary color
+	ContextMenu,
+}
+
+impl PointerRole {
+	/// The mouse button that the built-in shortcuts for the role are bound to.
+	pub const fn button(self) -> Key {
+		match self {
+			PointerRole::Pan | PointerRole::Zoom => Key::Mmb,
+			PointerRole::ContextMenu => Key::Rmb,
+		}
+	}
+}
+
+/// Another input that acts out a pointer role, in addition to the mouse button it's bound to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum RoleTrigger {
+	/// Only the mouse button acts out the role.
+	#[default]
+	None,
+	/// Pressing the primary button while holding the key acts as pressing the mouse button.
+	Click(Key),
+	/// Holding the key alone acts as holding the mouse button, so the role can be acted out without pressing any button, as from a keyboard and trackpad.
+	Hold(Key),
+}
+
+impl RoleTrigger {
+	/// The keys offered to trigger a role with a click, which are those that are easy to hold while using the pointer.
+	pub const CLICK_KEYS: [Key; 5] = [Key::Space, Key::Alt, Key::Control, Key::Shift, Key::Command];
+	/// The keys offered to trigger a role by holding them alone. Modifier keys aren't offered since they are held for the shortcuts they modify.
+	pub const HOLD_KEYS: [Key; 1] = [Key::Space];
+}
+
+/// Which input, besides its mouse button, acts out each pointer role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct PointerRoles {
+	pub pan: RoleTrigger,
+	pub zoom: RoleTrigger,
+	pub context_menu: RoleTrigger,
+}
+
+impl Default for PointerRoles {
+	fn default() -> Self {
+		Self {
+			pan: RoleTrigger::Click(Key::Space),
+			zoom: RoleTrigger::None,
+			context_menu: RoleTrigger::None,
+		}
+	}
+}
+
+impl PointerRoles {
+	pub fn get(&self, role: PointerRole) -> RoleTrigger {
+		match role {
+			PointerRole::Pan => self.pan,
+			PointerRole::Zoom => self.zoom,
+			PointerRole::ContextMenu => self.context_menu,
+		}
+	}
+
+	pub fn set(&mut self, role: PointerRole, trigger: RoleTrigger) {
+		match role {
+			PointerRole::Pan => self.pan = trigger,
+			PointerRole::Zoom => self.zoom = trigger,
+			PointerRole::ContextMenu => self.context_menu = trigger,
+		}
+	}
+}
+
+/// Where a binding applies, so the same input can be bound to different actions depending on the context, such as the active tool.
+/// In a keymap, it is written as `"global"`, `"document"`, or `"active-tool:<ToolType>"` (for example, `"active-tool:Pen"`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum BindingScope {
+	/// Applies everywhere.
+	#[default]
+	Global,
+	/// Applies while a document is open.
+	Document,
+	/// Applies only while the tool is active in an open document.
+	ActiveTool(ToolType),
+}
+
+impl BindingScope {
+	/// Whether bindings in this scope apply while the given tool is active, where `None` means no document is open.
+	pub fn applies(&self, active_tool: Option<ToolType>) -> bool {
+		match self {
+			BindingScope::Global => true,
+			BindingScope::Document => active_tool.is_some(),
+			BindingScope::ActiveTool(tool) => active_tool == Some(*tool),
+		}
+	}
+
+	/// Bindings in narrower scopes take precedence over bindings in broader scopes with the same input and modifiers.
+	fn precedence(&self) -> u8 {
+		match self {
+			BindingScope::Global => 0,
+			BindingScope::Document => 1,
+			BindingScope::ActiveTool(_) => 2,
+		}
+	}
+
+	/// Whether bindings in the two scopes can never apply at the same time.
+	pub fn excludes(&self, other: &BindingScope) -> bool {
+		matches!((self, other), (BindingScope::ActiveTool(a), BindingScope::ActiveTool(b)) if a != b)
+	}
+
+	pub fn is_global(&self) -> bool {
+		*self == BindingScope::Global
+	}
+}
+
+impl fmt::Display for BindingScope {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			BindingScope::Global => write!(f, "global"),
+			BindingScope::Document => write!(f, "document"),
+			BindingScope::ActiveTool(tool) => write!(f, "active-tool:{tool:?}"),
+		}
+	}
+}
+
+impl From<BindingScope> for String {
+	fn from(scope: BindingScope) -> Self {
+		scope.to_string()
+	}
+}
+
+impl TryFrom<String> for BindingScope {
+	type Error = String;
+
+	fn try_from(scope: String) -> Result<Self, Self::Error> {
+		match scope.as_str() {
+			"global" => Ok(BindingScope::Global),
+			"document" => Ok(BindingScope::Document),
+			_ => {
+				let tool = scope.strip_prefix("active-tool:").ok_or_else(|| format!("\"{scope}\" is not a binding scope"))?;
+				serde_json::from_value(serde_json::Value::String(tool.to_string()))
+					.map(BindingScope::ActiveTool)
+					.map_err(|_| format!("\"{tool}\" is not a tool"))
+			}
+		}
+	}
+}
+
+/// State of the editor a binding can require on top of its scope, so an input can be given a different meaning in that state without the
+/// message handlers having to add and remove actions for it. In a keymap, it is written as `"has-selection"`, `"no-selection"`, or
+/// `"view-mode:<ViewMode>"` (for example, `"view-mode:Outline"`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum BindingCondition {
+	/// At least one layer is selected in the open document.
+	HasSelection,
+	/// No layers are selected, or no document is open.
+	NoSelection,
+	/// The open document is rendered in the view mode.
+	ViewMode(ViewMode),
+}
+
+impl BindingCondition {
+	pub fn holds(&self, context: MappingContext) -> bool {
+		match self {
+			BindingCondition::HasSelection => context.has_selection,
+			BindingCondition::NoSelection => !context.has_selection,
+			BindingCondition::ViewMode(view_mode) => context.view_mode == Some(*view_mode),
+		}
+	}
+
+	/// Whether the two conditions can never hold at the same time.
+	pub fn excludes(&self, other: &BindingCondition) -> bool {
+		match (self, other) {
+			(BindingCondition::HasSelection, BindingCondition::NoSelection) | (BindingCondition::NoSelection, BindingCondition::HasSelection) => true,
+			(BindingCondition::ViewMode(a), BindingCondition::ViewMode(b)) => a != b,
+			_ => false,
+		}
+	}
+}
+
+impl fmt::Display for BindingCondition {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			BindingCondition::HasSelection => write!(f, "has-selection"),
+			BindingCondition::NoSelection => write!(f, "no-selection"),
+			BindingCondition::ViewMode(view_mode) => write!(f, "view-mode:{view_mode:?}"),
+		}
+	}
+}
+
+impl From<BindingCondition> for String {
+	fn from(condition: BindingCondition) -> Self {
+		condition.to_string()
+	}
+}
+
+impl TryFrom<String> for BindingCondition {
+	type Error = String;
+
+	fn try_from(condition: String) -> Result<Self, Self::Error> {
+		match condition.as_str() {
+			"has-selection" => Ok(BindingCondition::HasSelection),
+			"no-selection" => Ok(BindingCondition::NoSelection),
+			_ => {
+				let view_mode = condition.strip_prefix("view-mode:").ok_or_else(|| format!("\"{condition}\" is not a binding condition"))?;
+				serde_json::from_value(serde_json::Value::String(view_mode.to_string()))
+					.map(BindingCondition::ViewMode)
+					.map_err(|_| format!("\"{view_mode}\" is not a view mode"))
+			}
+		}
+	}
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, specta::Type)]
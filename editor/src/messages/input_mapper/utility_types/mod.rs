@@ -1,4 +1,5 @@
 pub mod input_keyboard;
 pub mod input_mouse;
 pub mod macros;
+pub mod mapping_definition;
 pub mod misc;
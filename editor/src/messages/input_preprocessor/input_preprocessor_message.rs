@@ -11,11 +11,21 @@ use serde::{Deserialize, Serialize};
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum InputPreprocessorMessage {
 	BoundsOfViewports { bounds_of_viewports: Vec<ViewportBounds> },
+	DevicePixelRatio { ratio: f64 },
 	DoubleClick { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
+	/// The editor lost focus to another window, which won't report the release of the keys and buttons held at the time.
+	FocusLost,
 	KeyDown { key: Key, modifier_keys: ModifierKeys },
+	/// The time the editor asked to wait, with [FrontendMessage::TriggerKeyRepeat], has passed for the key held down with this ID.
+	KeyRepeat { repeat_id: u64 },
 	KeyUp { key: Key, modifier_keys: ModifierKeys },
 	PointerDown { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
 	PointerMove { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
 	PointerUp { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
+	/// The number of viewport units per logical pixel of each viewport, in the same order as [InputPreprocessorMessage::BoundsOfViewports].
+	/// Native windows report positions in physical pixels, so this changes as a window moves between monitors of different DPI.
+	ScaleFactorsOfViewports { scale_factors_of_viewports: Vec<f64> },
+	/// How long, in milliseconds, a key is held before its action repeats, and how long between repeats after that.
+	SetKeyRepeat { delay: f64, interval: f64 },
 	WheelScroll { editor_mouse_state: EditorMouseState, modifier_keys: ModifierKeys },
 }
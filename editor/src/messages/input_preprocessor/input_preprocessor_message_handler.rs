@@ -1,3 +1,4 @@
+use crate::consts::{KEY_REPEAT_DEFAULT_DELAY, KEY_REPEAT_DEFAULT_INTERVAL, KEY_REPEAT_MIN_TIME, POINTER_SPEED_SMOOTHING_TIME};
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeyStates, ModifierKeys};
 use crate::messages::input_mapper::utility_types::input_mouse::{MouseKeys, MouseState, ViewportBounds};
 use crate::messages::portfolio::utility_types::KeyboardPlatformLayout;
@@ -8,16 +9,65 @@ pub use document_legacy::Operation;
 
 use glam::DVec2;
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct InputPreprocessorMessageHandler {
 	pub keyboard: KeyStates,
 	pub mouse: MouseState,
 	pub viewport_bounds: ViewportBounds,
+	/// The number of physical screen pixels per CSS pixel of the viewport, which is above 1 on high-DPI displays.
+	pub device_pixel_ratio: f64,
+	/// When the user last pressed a key or moved, clicked, or scrolled the pointer, in milliseconds from the platform's clock.
+	pub last_input_time: f64,
+	/// Whether the pointer was over the viewport when it was last reported, including while a drag continues outside of it.
+	pub pointer_in_viewport: bool,
+	/// How fast the pointer is moving, in logical pixels per millisecond, measured from the timestamps of the pointer events and smoothed
+	/// over [POINTER_SPEED_SMOOTHING_TIME]. It starts again from 0 each time a button is pressed.
+	pub pointer_speed: f64,
+	/// The number of viewport units per logical pixel, which is 1 in the browser since it reports positions in CSS pixels.
+	pub viewport_scale_factor: f64,
+	/// Time, in milliseconds, that a key is held before its action repeats.
+	pub key_repeat_delay: f64,
+	/// Time, in milliseconds, between repeats of the action of a held key.
+	pub key_repeat_interval: f64,
+	/// The key which repeats while it's held, along with the ID of its latest wait, so the waits of keys released since are ignored.
+	/// The editor times the repeats itself because browsers and platforms repeat keys inconsistently, and some not at all.
+	repeating_key: Option<(Key, u64)>,
+	next_repeat_id: u64,
+}
+
+impl Default for InputPreprocessorMessageHandler {
+	fn default() -> Self {
+		Self {
+			keyboard: KeyStates::default(),
+			mouse: MouseState::default(),
+			viewport_bounds: ViewportBounds::default(),
+			device_pixel_ratio: 1.,
+			last_input_time: 0.,
+			pointer_in_viewport: false,
+			pointer_speed: 0.,
+			viewport_scale_factor: 1.,
+			key_repeat_delay: KEY_REPEAT_DEFAULT_DELAY,
+			key_repeat_interval: KEY_REPEAT_DEFAULT_INTERVAL,
+			repeating_key: None,
+			next_repeat_id: 0,
+		}
+	}
 }
 
 impl MessageHandler<InputPreprocessorMessage, KeyboardPlatformLayout> for InputPreprocessorMessageHandler {
 	#[remain::check]
 	fn process_message(&mut self, message: InputPreprocessorMessage, responses: &mut VecDeque<Message>, keyboard_platform: KeyboardPlatformLayout) {
+		if !matches!(
+			message,
+			InputPreprocessorMessage::BoundsOfViewports { .. }
+				| InputPreprocessorMessage::DevicePixelRatio { .. }
+				| InputPreprocessorMessage::KeyRepeat { .. }
+				| InputPreprocessorMessage::ScaleFactorsOfViewports { .. }
+				| InputPreprocessorMessage::SetKeyRepeat { .. }
+		) {
+			self.last_input_time = now_milliseconds().unwrap_or_default();
+		}
+
 		#[remain::sorted]
 		match message {
 			InputPreprocessorMessage::BoundsOfViewports { bounds_of_viewports } => {
@@ -29,6 +79,16 @@ impl MessageHandler<InputPreprocessorMessage, KeyboardPlatformLayout> for InputP
 
 					responses.add(NavigationMessage::TranslateCanvas { delta: DVec2::ZERO });
 					responses.add(FrontendMessage::TriggerViewportResize);
+					// Hiding the panels around the canvas when presenting resizes the viewport, so the artboard has to be fitted to it again
+					responses.add(PresentationMessage::FitArtboard);
+				}
+			}
+			InputPreprocessorMessage::DevicePixelRatio { ratio } => {
+				if ratio > 0. && ratio.is_finite() && ratio != self.device_pixel_ratio {
+					self.device_pixel_ratio = ratio;
+
+					// Update the scale of the canvas so the zoom stays in step with the physical pixels
+					responses.add(NavigationMessage::TranslateCanvas { delta: DVec2::ZERO });
 				}
 			}
 			InputPreprocessorMessage::DoubleClick { editor_mouse_state, modifier_keys } => {
@@ -39,42 +99,115 @@ impl MessageHandler<InputPreprocessorMessage, KeyboardPlatformLayout> for InputP
 
 				responses.add(InputMapperMessage::DoubleClick);
 			}
+			InputPreprocessorMessage::FocusLost => {
+				// Release everything held so that drags end and modifiers don't stay stuck down after returning to the editor
+				let held_keys = self.keyboard.iter().filter_map(|index| Key::try_from(index as u8).ok()).collect::<Vec<_>>();
+				let modifier_released = held_keys.iter().any(|key| matches!(key, Key::Shift | Key::Alt | Key::Control | Key::Meta | Key::Command | Key::Accel));
+				self.repeating_key = None;
+				for key in held_keys {
+					self.keyboard.unset(key as usize);
+					responses.add(InputMapperMessage::KeyUp(key));
+				}
+
+				let released = MouseState {
+					mouse_keys: MouseKeys::empty(),
+					pressure: 0.,
+					..self.mouse
+				};
+				self.translate_mouse_event(released, false, responses);
+
+				if modifier_released {
+					responses.add(BroadcastEvent::ModifierKeysChanged);
+				}
+			}
 			InputPreprocessorMessage::KeyDown { key, modifier_keys } => {
 				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
 				self.keyboard.set(key as usize);
 				responses.add(InputMapperMessage::KeyDown(key));
+
+				// Only the latest key pressed repeats, like a held key stops repeating when another is pressed while typing
+				if !is_modifier_or_button(key) {
+					self.wait_to_repeat(key, self.key_repeat_delay, responses);
+				}
+			}
+			InputPreprocessorMessage::KeyRepeat { repeat_id } => {
+				let Some((key, current_id)) = self.repeating_key else { return };
+				if repeat_id != current_id {
+					return;
+				}
+				if !self.keyboard.get(key as usize) {
+					self.repeating_key = None;
+					return;
+				}
+
+				responses.add(InputMapperMessage::KeyRepeat(key));
+				self.wait_to_repeat(key, self.key_repeat_interval, responses);
 			}
 			InputPreprocessorMessage::KeyUp { key, modifier_keys } => {
 				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
 				self.keyboard.unset(key as usize);
 				responses.add(InputMapperMessage::KeyUp(key));
+
+				if self.repeating_key.map(|(repeating, _)| repeating) == Some(key) {
+					self.repeating_key = None;
+				}
 			}
 			InputPreprocessorMessage::PointerDown { editor_mouse_state, modifier_keys } => {
 				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
 
 				let mouse_state = editor_mouse_state.to_mouse_state(&self.viewport_bounds);
 				self.mouse.position = mouse_state.position;
+				self.mouse.pressure = mouse_state.pressure;
+				self.mouse.time = mouse_state.time;
+				self.pointer_speed = 0.;
 
 				self.translate_mouse_event(mouse_state, true, responses);
+				self.update_pointer_in_viewport(responses);
 			}
 			InputPreprocessorMessage::PointerMove { editor_mouse_state, modifier_keys } => {
 				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
 
 				let mouse_state = editor_mouse_state.to_mouse_state(&self.viewport_bounds);
+				self.update_pointer_speed(&mouse_state);
 				self.mouse.position = mouse_state.position;
+				self.mouse.pressure = mouse_state.pressure;
+				self.mouse.time = mouse_state.time;
 
 				responses.add(InputMapperMessage::PointerMove);
 
 				// While any pointer button is already down, additional button down events are not reported, but they are sent as `pointermove` events
 				self.translate_mouse_event(mouse_state, false, responses);
+				self.update_pointer_in_viewport(responses);
 			}
 			InputPreprocessorMessage::PointerUp { editor_mouse_state, modifier_keys } => {
 				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
 
 				let mouse_state = editor_mouse_state.to_mouse_state(&self.viewport_bounds);
+				self.update_pointer_speed(&mouse_state);
 				self.mouse.position = mouse_state.position;
+				self.mouse.pressure = mouse_state.pressure;
+				self.mouse.time = mouse_state.time;
 
 				self.translate_mouse_event(mouse_state, false, responses);
+				self.update_pointer_in_viewport(responses);
+			}
+			InputPreprocessorMessage::ScaleFactorsOfViewports { scale_factors_of_viewports } => {
+				assert_eq!(scale_factors_of_viewports.len(), 1, "Only one viewport is currently supported");
+
+				for scale_factor in scale_factors_of_viewports {
+					// TODO: Extend this to multiple viewports once the bounds of each are kept, since handles and tolerances then depend on the viewport under the pointer
+					if scale_factor > 0. && scale_factor.is_finite() && scale_factor != self.viewport_scale_factor {
+						self.viewport_scale_factor = scale_factor;
+
+						responses.add(GlobalsMessage::SetViewportScaleFactor { scale_factor });
+						// Redraw the overlays of the active tool at the new size
+						responses.add(BroadcastEvent::SelectionChanged);
+					}
+				}
+			}
+			InputPreprocessorMessage::SetKeyRepeat { delay, interval } => {
+				self.key_repeat_delay = delay.max(KEY_REPEAT_MIN_TIME);
+				self.key_repeat_interval = interval.max(KEY_REPEAT_MIN_TIME);
 			}
 			InputPreprocessorMessage::WheelScroll { editor_mouse_state, modifier_keys } => {
 				self.update_states_of_modifier_keys(modifier_keys, keyboard_platform, responses);
@@ -94,9 +227,47 @@ impl MessageHandler<InputPreprocessorMessage, KeyboardPlatformLayout> for InputP
 	advertise_actions!();
 }
 
+/// Modifiers change what a held key does rather than doing anything themselves, and mouse buttons drag rather than repeat.
+fn is_modifier_or_button(key: Key) -> bool {
+	matches!(
+		key,
+		Key::Shift | Key::Alt | Key::Control | Key::Meta | Key::Command | Key::Accel | Key::Lmb | Key::Rmb | Key::Mmb | Key::PenEraser | Key::PenBarrel | Key::MouseBack | Key::MouseForward
+	)
+}
+
 impl InputPreprocessorMessageHandler {
+	/// Asks the frontend to wait before the key repeats, replacing any wait for a key pressed before it.
+	fn wait_to_repeat(&mut self, key: Key, delay: f64, responses: &mut VecDeque<Message>) {
+		self.next_repeat_id += 1;
+		let repeat_id = self.next_repeat_id;
+		self.repeating_key = Some((key, repeat_id));
+		responses.add(FrontendMessage::TriggerKeyRepeat { repeat_id, delay });
+	}
+
+	/// Eases the pointer speed towards how fast the pointer moved since the last event, by more the longer it has been since then.
+	fn update_pointer_speed(&mut self, mouse_state: &MouseState) {
+		// Events without timestamps, or coalesced into the same instant, don't say anything about the speed
+		let elapsed = mouse_state.time - self.mouse.time;
+		if elapsed <= 0. || !elapsed.is_finite() {
+			return;
+		}
+
+		let speed = mouse_state.position.distance(self.mouse.position) / self.viewport_scale_factor / elapsed;
+		let weight = 1. - (-elapsed / POINTER_SPEED_SMOOTHING_TIME).exp();
+		self.pointer_speed += (speed - self.pointer_speed) * weight;
+	}
+
 	fn translate_mouse_event(&mut self, mut new_state: MouseState, allow_first_button_down: bool, responses: &mut VecDeque<Message>) {
-		for (bit_flag, key) in [(MouseKeys::LEFT, Key::Lmb), (MouseKeys::RIGHT, Key::Rmb), (MouseKeys::MIDDLE, Key::Mmb)] {
+		let buttons = [
+			(MouseKeys::LEFT, Key::Lmb),
+			(MouseKeys::RIGHT, Key::Rmb),
+			(MouseKeys::MIDDLE, Key::Mmb),
+			(MouseKeys::ERASER, Key::PenEraser),
+			(MouseKeys::BARREL, Key::PenBarrel),
+			(MouseKeys::BACK, Key::MouseBack),
+			(MouseKeys::FORWARD, Key::MouseForward),
+		];
+		for (bit_flag, key) in buttons {
 			// Calculate the intersection between the two key states
 			let old_down = self.mouse.mouse_keys & bit_flag == bit_flag;
 			let new_down = new_state.mouse_keys & bit_flag == bit_flag;
@@ -116,38 +287,62 @@ impl InputPreprocessorMessageHandler {
 		self.mouse = new_state;
 	}
 
+	/// Lets the tools know when the pointer crosses the edge of the viewport, since they keep receiving its movement outside while a drag continues.
+	fn update_pointer_in_viewport(&mut self, responses: &mut VecDeque<Message>) {
+		let position = self.mouse.position;
+		let in_viewport = position.cmpge(DVec2::ZERO).all() && position.cmple(self.viewport_bounds.size()).all();
+		if in_viewport == self.pointer_in_viewport {
+			return;
+		}
+
+		self.pointer_in_viewport = in_viewport;
+		match in_viewport {
+			true => responses.add(BroadcastEvent::PointerEnterViewport),
+			false => responses.add(BroadcastEvent::PointerLeaveViewport),
+		}
+	}
+
 	fn update_states_of_modifier_keys(&mut self, pressed_modifier_keys: ModifierKeys, keyboard_platform: KeyboardPlatformLayout, responses: &mut VecDeque<Message>) {
 		let is_key_pressed = |key_to_check: ModifierKeys| pressed_modifier_keys.contains(key_to_check);
 
 		// Update the state of the concrete modifier keys based on the source state
-		self.update_modifier_key(Key::Shift, is_key_pressed(ModifierKeys::SHIFT), responses);
-		self.update_modifier_key(Key::Alt, is_key_pressed(ModifierKeys::ALT), responses);
-		self.update_modifier_key(Key::Control, is_key_pressed(ModifierKeys::CONTROL), responses);
+		let mut changed = self.update_modifier_key(Key::Shift, is_key_pressed(ModifierKeys::SHIFT), responses);
+		changed |= self.update_modifier_key(Key::Alt, is_key_pressed(ModifierKeys::ALT), responses);
+		changed |= self.update_modifier_key(Key::Control, is_key_pressed(ModifierKeys::CONTROL), responses);
 
 		// Update the state of either the concrete Meta or the Command keys based on which one is applicable for this platform
 		let meta_or_command = match keyboard_platform {
 			KeyboardPlatformLayout::Mac => Key::Command,
 			KeyboardPlatformLayout::Standard => Key::Meta,
 		};
-		self.update_modifier_key(meta_or_command, is_key_pressed(ModifierKeys::META_OR_COMMAND), responses);
+		changed |= self.update_modifier_key(meta_or_command, is_key_pressed(ModifierKeys::META_OR_COMMAND), responses);
 
 		// Update the state of the virtual Accel key (the primary accelerator key) based on the source state of the Control or Command key, whichever is relevant on this platform
 		let accel_virtual_key_state = match keyboard_platform {
 			KeyboardPlatformLayout::Mac => is_key_pressed(ModifierKeys::META_OR_COMMAND),
 			KeyboardPlatformLayout::Standard => is_key_pressed(ModifierKeys::CONTROL),
 		};
-		self.update_modifier_key(Key::Accel, accel_virtual_key_state, responses);
+		changed |= self.update_modifier_key(Key::Accel, accel_virtual_key_state, responses);
+
+		if changed {
+			responses.add(BroadcastEvent::ModifierKeysChanged);
+		}
 	}
 
-	fn update_modifier_key(&mut self, key: Key, key_is_down: bool, responses: &mut VecDeque<Message>) {
+	/// Returns whether the key changed state.
+	fn update_modifier_key(&mut self, key: Key, key_is_down: bool, responses: &mut VecDeque<Message>) -> bool {
 		let key_was_down = self.keyboard.get(key as usize);
 
 		if key_was_down && !key_is_down {
 			self.keyboard.unset(key as usize);
 			responses.add(InputMapperMessage::KeyUp(key));
+			true
 		} else if !key_was_down && key_is_down {
 			self.keyboard.set(key as usize);
 			responses.add(InputMapperMessage::KeyDown(key));
+			true
+		} else {
+			false
 		}
 	}
 
@@ -246,4 +441,34 @@ mod test {
 		assert!(responses.contains(&InputMapperMessage::KeyDown(Key::Control).into()));
 		assert!(responses.contains(&InputMapperMessage::KeyDown(Key::Control).into()));
 	}
+
+	#[test]
+	fn held_key_repeats_until_released() {
+		let mut input_preprocessor = InputPreprocessorMessageHandler::default();
+		let mut responses = VecDeque::new();
+		let modifier_keys = ModifierKeys::empty();
+
+		input_preprocessor.process_message(InputPreprocessorMessage::KeyDown { key: Key::ArrowUp, modifier_keys }, &mut responses, KeyboardPlatformLayout::Standard);
+		let Some(Message::Frontend(FrontendMessage::TriggerKeyRepeat { repeat_id, .. })) = responses.pop_back() else {
+			panic!("Pressing a key should wait to repeat it")
+		};
+
+		input_preprocessor.process_message(InputPreprocessorMessage::KeyRepeat { repeat_id }, &mut responses, KeyboardPlatformLayout::Standard);
+		assert!(responses.contains(&InputMapperMessage::KeyRepeat(Key::ArrowUp).into()));
+		let Some(Message::Frontend(FrontendMessage::TriggerKeyRepeat { repeat_id: next_id, delay })) = responses.pop_back() else {
+			panic!("A repeating key should wait to repeat again")
+		};
+		assert_eq!(delay, input_preprocessor.key_repeat_interval);
+
+		// The wait from before the key was released is ignored
+		responses.clear();
+		input_preprocessor.process_message(InputPreprocessorMessage::KeyUp { key: Key::ArrowUp, modifier_keys }, &mut responses, KeyboardPlatformLayout::Standard);
+		responses.clear();
+		input_preprocessor.process_message(InputPreprocessorMessage::KeyRepeat { repeat_id: next_id }, &mut responses, KeyboardPlatformLayout::Standard);
+		assert!(responses.is_empty());
+
+		// Modifiers don't repeat
+		input_preprocessor.process_message(InputPreprocessorMessage::KeyDown { key: Key::Shift, modifier_keys }, &mut responses, KeyboardPlatformLayout::Standard);
+		assert!(!responses.iter().any(|message| matches!(message, Message::Frontend(FrontendMessage::TriggerKeyRepeat { .. }))));
+	}
 }
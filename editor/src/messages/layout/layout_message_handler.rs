@@ -148,7 +148,11 @@ impl<F: Fn(&MessageDiscriminant) -> Vec<KeysGroup>> MessageHandler<LayoutMessage
 						responses.add(callback_message);
 					}
 					Widget::IconButton(icon_button) => {
-						let callback_message = (icon_button.on_update.callback)(icon_button);
+						// Pointer entering or leaving the button is reported separately from clicks
+						let callback_message = match value.get("hovered").and_then(|hovered| hovered.as_bool()) {
+							Some(hovered) => (icon_button.on_hover.callback)(&hovered),
+							None => (icon_button.on_update.callback)(icon_button),
+						};
 						responses.add(callback_message);
 					}
 					Widget::IconLabel(_) => {}
@@ -25,10 +25,18 @@ pub struct IconButton {
 	#[serde(skip)]
 	pub tooltip_shortcut: Option<ActionKeys>,
 
+	/// Whether the frontend reports the pointer entering and leaving the button, which `on_hover` is called with.
+	#[serde(rename = "reportsHover")]
+	pub reports_hover: bool,
+
 	// Callbacks
 	#[serde(skip)]
 	#[derivative(Debug = "ignore", PartialEq = "ignore")]
 	pub on_update: WidgetCallback<IconButton>,
+
+	#[serde(skip)]
+	#[derivative(Debug = "ignore", PartialEq = "ignore")]
+	pub on_hover: WidgetCallback<bool>,
 }
 
 #[derive(Clone, Serialize, Deserialize, Derivative, WidgetBuilder, specta::Type)]
@@ -223,6 +223,11 @@ pub struct NumberInput {
 
 	pub unit: String,
 
+	/// Written in place of the decimal point, following the number locale chosen in the preferences.
+	#[serde(rename = "decimalSeparator")]
+	#[derivative(Default(value = "crate::messages::globals::global_variables::number_locale().decimal_separator()"))]
+	pub decimal_separator: char,
+
 	#[serde(rename = "unitIsHiddenWhenEditing")]
 	#[derivative(Default(value = "true"))]
 	pub unit_is_hidden_when_editing: bool,
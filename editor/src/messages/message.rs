@@ -16,6 +16,8 @@ pub enum Message {
 	#[child]
 	Broadcast(BroadcastMessage),
 	#[child]
+	ColorPicker(ColorPickerMessage),
+	#[child]
 	Debug(DebugMessage),
 	#[child]
 	Dialog(DialogMessage),
@@ -36,6 +38,8 @@ pub enum Message {
 	#[child]
 	Tool(ToolMessage),
 	#[child]
+	Tutorial(TutorialMessage),
+	#[child]
 	Workspace(WorkspaceMessage),
 }
 
@@ -1,6 +1,7 @@
 //! The root-level messages forming the first layer of the message system architecture.
 
 pub mod broadcast;
+pub mod color_picker;
 pub mod debug;
 pub mod dialog;
 pub mod frontend;
@@ -13,4 +14,5 @@ pub mod portfolio;
 pub mod preferences;
 pub mod prelude;
 pub mod tool;
+pub mod tutorial;
 pub mod workspace;
@@ -0,0 +1,196 @@
+//! Batch processing applies the same steps, like recoloring, scaling, and exporting, to many documents in turn. Each document is opened,
+//! has the steps applied as messages once the node graph has worked out the output of its layers, and is closed again before the next one
+//! is opened. Nothing in between waits on the user, so a batch can be run headless by sending [PortfolioMessage::ProcessBatch](crate::messages::portfolio::PortfolioMessage::ProcessBatch) as well as
+//! from the batch dialog.
+
+use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::portfolio::document::utility_types::dxf::DxfOptions;
+use crate::messages::portfolio::document::utility_types::plotter::PlotterOptions;
+use crate::messages::portfolio::document::utility_types::print_marks::PrintOptions;
+use crate::messages::prelude::*;
+
+use document_legacy::LayerId;
+use graphene_core::Color;
+
+use glam::DVec2;
+use serde::{Deserialize, Serialize};
+
+/// A document to process, given either by where it's read from or by its content.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BatchSource {
+	/// A file path (in the desktop app) or URL (on the web), which the frontend reads the document from
+	Path(String),
+	Content {
+		name: String,
+		content: String,
+	},
+}
+
+/// One step of a batch, applied in order to each of its documents.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BatchStep {
+	/// Changes the fills and strokes of one color to another.
+	ReplaceColor { from: Color, to: Color },
+	/// Scales the artwork and artboards about the origin of the document.
+	Scale { factor: f64 },
+	/// Exports all the artwork, named after the document.
+	Export { file_type: FileType, scale_factor: f64, transparent_background: bool },
+}
+
+impl BatchStep {
+	/// The message which applies the step to the document with the given name.
+	pub fn message(&self, document_name: &str) -> DocumentMessage {
+		match *self {
+			Self::ReplaceColor { from, to } => DocumentMessage::ReplaceColor { from, to },
+			Self::Scale { factor } => DocumentMessage::ScaleDocument { factor },
+			Self::Export {
+				file_type,
+				scale_factor,
+				transparent_background,
+			} => DocumentMessage::ExportDocument {
+				file_name: document_name.to_string(),
+				file_type,
+				scale_factor,
+				bounds: ExportBounds::AllArtwork,
+				custom_bounds: [DVec2::ZERO; 2],
+				padding: 0.,
+				transparent_background: file_type != FileType::Jpg && transparent_background,
+				layer_names: false,
+				embed_fonts: false,
+				dxf_options: DxfOptions::default(),
+				plotter_options: PlotterOptions::default(),
+				print_options: PrintOptions::default(),
+				preview: false,
+			},
+		}
+	}
+}
+
+/// How processing one document of a batch went, with the reason it failed if it did.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct BatchResult {
+	pub name: String,
+	pub error: Option<String>,
+}
+
+/// A batch being processed, one document at a time.
+#[derive(Clone, Debug, Default)]
+pub struct Batch {
+	steps: Vec<BatchStep>,
+	queued: VecDeque<BatchSource>,
+	total: usize,
+	/// The path the frontend is reading the next document from
+	fetching: Option<String>,
+	/// The document being processed, opened under this ID, and what its result is once its steps are done
+	open_document: Option<(u64, BatchResult)>,
+	/// The node graph layers of the open document which the steps wait on to be evaluated, since their output isn't saved with the document
+	awaiting_evaluation: HashSet<Vec<LayerId>>,
+	results: Vec<BatchResult>,
+}
+
+impl Batch {
+	pub fn new(sources: Vec<BatchSource>, steps: Vec<BatchStep>) -> Self {
+		Self {
+			steps,
+			total: sources.len(),
+			queued: sources.into(),
+			..Default::default()
+		}
+	}
+
+	pub fn steps(&self) -> &[BatchStep] {
+		&self.steps
+	}
+
+	pub fn results(&self) -> &[BatchResult] {
+		&self.results
+	}
+
+	/// How many documents have been processed so far, and how many there are in all.
+	pub fn progress(&self) -> (usize, usize) {
+		(self.results.len(), self.total)
+	}
+
+	/// Takes the next document to process, once the document before it is finished.
+	pub fn next_source(&mut self) -> Option<BatchSource> {
+		self.queued.pop_front()
+	}
+
+	pub fn start_fetching(&mut self, source: String) {
+		self.fetching = Some(source);
+	}
+
+	/// Whether the frontend was asked to read this path for the batch, in which case it no longer waits on it.
+	pub fn finish_fetching(&mut self, source: &str) -> bool {
+		let fetching = self.fetching.as_deref() == Some(source);
+		if fetching {
+			self.fetching = None;
+		}
+		fetching
+	}
+
+	/// Remembers the document which is open to have the steps applied, the result it gets once they are, and the node graph layers to
+	/// evaluate before they can be. Returns whether the steps can be applied right away.
+	pub fn open(&mut self, document_id: u64, result: BatchResult, awaiting_evaluation: HashSet<Vec<LayerId>>) -> bool {
+		self.open_document = Some((document_id, result));
+		self.awaiting_evaluation = awaiting_evaluation;
+		self.awaiting_evaluation.is_empty()
+	}
+
+	/// Records that a layer was evaluated, returning whether it was the last layer of the open document that the steps waited on.
+	pub fn finish_evaluation(&mut self, document_id: u64, layer_path: &[LayerId]) -> bool {
+		let open = self.open_document.as_ref().map_or(false, |(open_id, _)| *open_id == document_id);
+		open && self.awaiting_evaluation.remove(layer_path) && self.awaiting_evaluation.is_empty()
+	}
+
+	/// Records the result of the open document, returning its ID so it can be closed.
+	pub fn close(&mut self) -> Option<u64> {
+		let (document_id, result) = self.open_document.take()?;
+		self.results.push(result);
+		Some(document_id)
+	}
+
+	pub fn fail(&mut self, name: String, error: String) {
+		self.results.push(BatchResult { name, error: Some(error) });
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn results_are_recorded_in_order() {
+		let sources = vec![
+			BatchSource::Path("missing.graphite".to_string()),
+			BatchSource::Content {
+				name: "a.graphite".to_string(),
+				content: String::new(),
+			},
+		];
+		let mut batch = Batch::new(sources, vec![BatchStep::Scale { factor: 2. }]);
+		assert_eq!(batch.progress(), (0, 2));
+
+		let Some(BatchSource::Path(path)) = batch.next_source() else {
+			panic!("The path should be processed first")
+		};
+		batch.start_fetching(path.clone());
+		assert!(!batch.finish_fetching("other.graphite"));
+		assert!(batch.finish_fetching(&path));
+		batch.fail(path, "Not found".to_string());
+
+		assert!(matches!(batch.next_source(), Some(BatchSource::Content { .. })));
+		let awaiting_evaluation = [vec![1], vec![2, 3]].into_iter().collect();
+		assert!(!batch.open(7, BatchResult { name: "a".to_string(), error: None }, awaiting_evaluation));
+		assert!(!batch.finish_evaluation(8, &[1]));
+		assert!(!batch.finish_evaluation(7, &[1]));
+		assert!(!batch.finish_evaluation(7, &[1]));
+		assert!(batch.finish_evaluation(7, &[2, 3]));
+		assert_eq!(batch.close(), Some(7));
+		assert_eq!(batch.close(), None);
+
+		assert_eq!(batch.next_source(), None);
+		assert_eq!(batch.progress(), (2, 2));
+		assert_eq!(batch.results().iter().map(|result| result.error.is_some()).collect::<Vec<_>>(), [true, false]);
+	}
+}
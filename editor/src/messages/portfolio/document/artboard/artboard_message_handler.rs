@@ -9,7 +9,7 @@ use document_legacy::LayerId;
 use document_legacy::Operation as DocumentOperation;
 use graphene_core::raster::color::Color;
 
-use glam::DAffine2;
+use glam::{DAffine2, DVec2};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
@@ -97,9 +97,17 @@ impl MessageHandler<ArtboardMessage, &PersistentData> for ArtboardMessageHandler
 					size.1 = size.1.signum();
 				}
 
+				let transform = DAffine2::from_scale_angle_translation(size.into(), 0., position.into());
+				if let Ok(old_transform) = self.artboards_document.layer(&[artboard]).map(|layer| layer.transform) {
+					responses.add(DocumentMessage::ApplyArtboardConstraints {
+						old_bounds: artboard_bounds(old_transform),
+						new_bounds: artboard_bounds(transform),
+					});
+				}
+
 				responses.add(ArtboardMessage::DispatchOperation(Box::new(DocumentOperation::SetLayerTransform {
 					path: vec![artboard],
-					transform: DAffine2::from_scale_angle_translation(size.into(), 0., position.into()).to_cols_array(),
+					transform: transform.to_cols_array(),
 				})));
 
 				responses.add(DocumentMessage::RenderDocument);
@@ -112,6 +120,12 @@ impl MessageHandler<ArtboardMessage, &PersistentData> for ArtboardMessageHandler
 	}
 }
 
+/// The bounds of an artboard in document space from its transform, which maps the unit square to the artboard's rectangle.
+fn artboard_bounds(transform: DAffine2) -> [DVec2; 2] {
+	let (a, b) = (transform.transform_point2(DVec2::ZERO), transform.transform_point2(DVec2::ONE));
+	[a.min(b), a.max(b)]
+}
+
 impl ArtboardMessageHandler {
 	pub fn is_infinite_canvas(&self) -> bool {
 		self.artboard_ids.is_empty()
@@ -1,17 +1,34 @@
 use crate::messages::frontend::utility_types::{ExportBounds, FileType};
 use crate::messages::input_mapper::utility_types::input_keyboard::Key;
+use crate::messages::portfolio::document::utility_types::boolean_operations::StyledShape;
+use crate::messages::portfolio::document::utility_types::design_tokens::DesignTokenFormat;
+use crate::messages::portfolio::document::utility_types::dxf::DxfOptions;
+use crate::messages::portfolio::document::utility_types::guide_grid::GuideGrid;
+use crate::messages::portfolio::document::utility_types::hatch::HatchOptions;
+use crate::messages::portfolio::document::utility_types::isometric::IsometricPlane;
 use crate::messages::portfolio::document::utility_types::layer_panel::LayerMetadata;
 use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis};
+use crate::messages::portfolio::document::utility_types::plotter::PlotterOptions;
+use crate::messages::portfolio::document::utility_types::print_marks::PrintOptions;
+use crate::messages::portfolio::document::utility_types::prototype_links::PrototypeLink;
+use crate::messages::portfolio::document::utility_types::sprite_sheet::SpriteSource;
 use crate::messages::prelude::*;
 
+use document_legacy::boolean_ops::BooleanOperation;
 use document_legacy::document::Document as DocumentLegacy;
 use document_legacy::layers::blend_mode::BlendMode;
+use document_legacy::layers::folder_style::FolderStyle;
 use document_legacy::layers::style::ViewMode;
+use document_legacy::layers::text_style::TextStyle;
 use document_legacy::LayerId;
 use document_legacy::Operation as DocumentOperation;
 use graph_craft::document::NodeId;
 use graphene_core::raster::Image;
+use graphene_core::text::Font;
+use graphene_core::vector::width_profile::WidthPoint;
 use graphene_core::Color;
+
+use glam::{DAffine2, DVec2};
 use serde::{Deserialize, Serialize};
 
 #[remain::sorted]
@@ -32,6 +49,9 @@ pub enum DocumentMessage {
 	Overlays(OverlaysMessage),
 	#[remain::unsorted]
 	#[child]
+	Presentation(PresentationMessage),
+	#[remain::unsorted]
+	#[child]
 	PropertiesPanel(PropertiesPanelMessage),
 	#[remain::unsorted]
 	#[child]
@@ -49,49 +69,123 @@ pub enum DocumentMessage {
 		axis: AlignAxis,
 		aggregate: AlignAggregate,
 	},
+	ApplyArtboardConstraints {
+		old_bounds: [DVec2; 2],
+		new_bounds: [DVec2; 2],
+	},
+	/// Restyles the layers with the fill and stroke they inherit from the default styles of the folders above them. Folders among them
+	/// restyle the inheriting layers within them, and layers which don't inherit their style are left as they are.
+	ApplyInheritedStyle {
+		layers: Vec<Vec<LayerId>>,
+	},
+	ApplyTextStyle {
+		id: u64,
+	},
 	BackupDocument {
 		document: DocumentLegacy,
 		artboard: Box<ArtboardMessageHandler>,
 		layer_metadata: HashMap<Vec<LayerId>, LayerMetadata>,
 	},
+	BooleanOperation(BooleanOperation),
+	BooleanPreviewComputed {
+		job_id: u64,
+		result: Option<StyledShape>,
+	},
 	ClearLayerTree,
+	ClearSelectedFill,
+	ClearSelectedStroke,
+	CloseSelectedPaths,
 	CommitTransaction,
+	CopySelectedLayersAsPng {
+		scale_factor: f64,
+	},
 	CopyToClipboardLayerImageOutput {
 		layer_path: Vec<LayerId>,
 	},
 	CreateEmptyFolder {
 		container_path: Vec<LayerId>,
 	},
+	CreateTextStyle,
+	DataMerge {
+		data: String,
+		per_row: u32,
+		spacing: f64,
+	},
 	DebugPrintDocument,
 	DeleteLayer {
 		layer_path: Vec<LayerId>,
 	},
 	DeleteSelectedLayers,
+	DeleteTextStyle {
+		id: u64,
+	},
 	DeselectAllLayers,
+	DetachTextStyle,
 	DirtyRenderDocument,
 	DirtyRenderDocumentInOutlineView,
 	DocumentHistoryBackward,
 	DocumentHistoryForward,
 	DocumentStructureChanged,
+	DocumentThumbnailRendered {
+		job_id: u64,
+		thumbnail: String,
+	},
+	DownloadDocument {
+		thumbnail: Option<String>,
+	},
 	DownloadLayerImageOutput {
 		layer_path: Vec<LayerId>,
 	},
 	DuplicateSelectedLayers,
+	DuplicateSelectedLayersWithOffset,
+	ExportDesignTokens {
+		format: DesignTokenFormat,
+	},
 	ExportDocument {
 		file_name: String,
 		file_type: FileType,
 		scale_factor: f64,
 		bounds: ExportBounds,
+		custom_bounds: [DVec2; 2],
+		padding: f64,
+		transparent_background: bool,
+		layer_names: bool,
+		embed_fonts: bool,
+		dxf_options: DxfOptions,
+		plotter_options: PlotterOptions,
+		print_options: PrintOptions,
+		/// Whether to only show a small preview of the export in the export dialog, rather than download the file.
+		preview: bool,
+	},
+	ExportSpriteSheet {
+		file_name: String,
+		source: SpriteSource,
+		scale_factor: f64,
+		spacing: u32,
 		transparent_background: bool,
 	},
+	ExtrudeSelectedLayersToIsometricBox,
+	FlattenSelectedLayerEffects {
+		scale_factor: f64,
+	},
+	FlattenedLayerEffectsRasterized {
+		layer_path: Vec<LayerId>,
+		image: Image<Color>,
+	},
 	FlipSelectedLayers {
 		flip_axis: FlipAxis,
 	},
 	FolderChanged {
 		affected_folder_path: Vec<LayerId>,
 	},
+	FollowPrototypeLink {
+		layer: Vec<LayerId>,
+	},
 	FrameClear,
 	GroupSelectedLayers,
+	HatchFillSelectedLayers {
+		options: HatchOptions,
+	},
 	ImaginateClear {
 		layer_path: Vec<LayerId>,
 	},
@@ -106,24 +200,66 @@ pub enum DocumentMessage {
 	InputFrameRasterizeRegionBelowLayer {
 		layer_path: Vec<LayerId>,
 	},
+	InsertPlaceholderText {
+		words: usize,
+	},
+	JoinSelectedPaths,
 	LayerChanged {
 		affected_layer_path: Vec<LayerId>,
 	},
+	LayerThumbnailRendered {
+		job_id: u64,
+		layer_path: Vec<LayerId>,
+		thumbnail: String,
+	},
+	LinkSelectedLayerTransforms,
 	MoveSelectedLayersTo {
 		folder_path: Vec<LayerId>,
 		insert_index: isize,
 		reverse_index: bool,
 	},
+	/// Keeps the transform links of a layer which was moved to another path, along with those of the layers inside it.
+	MoveTransformLinks {
+		from: Vec<LayerId>,
+		to: Vec<LayerId>,
+	},
 	NudgeSelectedLayers {
 		delta_x: f64,
 		delta_y: f64,
 		resize: Key,
 		resize_opposite_corner: Key,
 	},
+	PasteAtPointer,
 	PasteImage {
 		image: Image<Color>,
 		mouse: Option<(f64, f64)>,
 	},
+	PasteInPlace,
+	PasteInto,
+	PerformIdleWork {
+		deadline: f64,
+	},
+	PreviewBooleanOperation {
+		operation: Option<BooleanOperation>,
+	},
+	/// Runs an operation the user confirmed despite the warning that it may take a long time.
+	ProceedWithCostlyOperation {
+		operation: Box<DocumentMessage>,
+	},
+	ProjectSelectedLayersIsometric {
+		plane: IsometricPlane,
+	},
+	QueueLayerThumbnails {
+		layer_paths: Vec<Vec<LayerId>>,
+	},
+	RadialArraySelectedLayers {
+		count: u32,
+		angle: f64,
+	},
+	/// Remembers a transform just applied to the selection about its pivot, in viewport space, so it can be repeated with [DocumentMessage::TransformSelectedLayersAgain].
+	RecordSelectionTransform {
+		viewport_transform: DAffine2,
+	},
 	Redo,
 	RenameDocument {
 		new_name: String,
@@ -133,8 +269,37 @@ pub enum DocumentMessage {
 		new_name: String,
 	},
 	RenderDocument,
+	RenderFullDocument,
+	/// Replays the operation log onto an empty document and replaces the document with the result if it differs, reporting the differences.
+	RepairDocument,
+	/// Exports the document again with the settings of its most recent export, or opens the export dialog if it hasn't been exported yet.
+	RepeatLastExport,
+	/// Changes the fills and strokes of one color to another throughout the document, as a single undo step.
+	ReplaceColor {
+		from: Color,
+		to: Color,
+	},
+	/// Changes every text layer using the first font of a pair to use the second font, as a single undo step.
+	ReplaceFonts {
+		replacements: Vec<(Font, Font)>,
+	},
+	/// Replaces the geometry of the selected shapes with the geometry set by [SetGeometrySource](DocumentMessage::SetGeometrySource), fitted within
+	/// the bounds of each shape's current geometry so their transforms and styles are kept.
+	ReplaceSelectedLayersGeometry,
+	RevealLayer {
+		layer_path: Vec<LayerId>,
+	},
 	RollbackTransaction,
 	SaveDocument,
+	/// Saves the style and size of the first selected shape as a shape preset named after its layer.
+	SaveSelectionAsShapePreset,
+	/// Scales all the artwork and artboards about the origin of the document, as a single undo step.
+	ScaleDocument {
+		factor: f64,
+	},
+	SearchOutline {
+		query: String,
+	},
 	SelectAllLayers,
 	SelectedLayersLower,
 	SelectedLayersLowerToBack,
@@ -151,12 +316,34 @@ pub enum DocumentMessage {
 	SetBlendModeForSelectedLayers {
 		blend_mode: BlendMode,
 	},
+	/// Sets the default style of the folder and restyles the layers within it which inherit their style.
+	SetFolderDefaultStyle {
+		path: Vec<LayerId>,
+		default_style: FolderStyle,
+	},
+	/// Sets whether the selected folders keep the blend modes of their layers from mixing with the artwork below them.
+	SetFolderIsolationForSelectedLayers {
+		isolated: bool,
+	},
+	/// Sets whether the layers in the selected folders hide the layers below them in the folder where they overlap.
+	SetFolderKnockoutForSelectedLayers {
+		knockout: bool,
+	},
+	/// Keeps the geometry of the first selected shape for replacing the geometry of other shapes.
+	SetGeometrySource,
+	SetGuideGrid {
+		guide_grid: GuideGrid,
+	},
 	SetImageBlobUrl {
 		layer_path: Vec<LayerId>,
 		blob_url: String,
 		resolution: (f64, f64),
 		document_id: u64,
 	},
+	/// Makes the selected layers take their style from the folders above them, or detaches them so they keep the style they have.
+	SetInheritStyleForSelectedLayers {
+		inherit_style: bool,
+	},
 	SetLayerExpansion {
 		layer_path: Vec<LayerId>,
 		set_expanded: bool,
@@ -171,6 +358,13 @@ pub enum DocumentMessage {
 	SetOverlaysVisibility {
 		visible: bool,
 	},
+	SetPrototypeLink {
+		layer: Vec<LayerId>,
+		link: Option<PrototypeLink>,
+	},
+	SetRasterResolution {
+		raster_resolution: f64,
+	},
 	SetSelectedLayers {
 		replacement_selected_layers: Vec<Vec<LayerId>>,
 	},
@@ -178,27 +372,53 @@ pub enum DocumentMessage {
 		snapping_enabled: Option<bool>,
 		bounding_box_snapping: Option<bool>,
 		node_snapping: Option<bool>,
+		grid_snapping: Option<bool>,
+		grid_spacing: Option<f64>,
 	},
 	SetViewMode {
 		view_mode: ViewMode,
 	},
 	StartTransaction,
+	SwapSelectedFillAndStroke,
+	SymmetrizeSelectedLayers {
+		flip_axis: FlipAxis,
+	},
+	/// Varies the stroke width of the selected layers along their paths, or makes it constant again if the profile is empty.
+	TaperSelectedStrokes {
+		profile: Vec<WidthPoint>,
+	},
+	/// Lines the selected layers up in the rows and columns they roughly sit in, evening out the gaps between them.
+	TidySelectedLayers,
+	ToggleCmykPreview,
 	ToggleLayerExpansion {
 		layer_path: Vec<LayerId>,
 	},
 	ToggleLayerVisibility {
 		layer_path: Vec<LayerId>,
 	},
+	ToggleSelectedLayersAspectLock,
+	TransformSelectedLayersAgain,
+	TranslateSelectedLayers {
+		delta: DVec2,
+	},
 	Undo,
 	UndoFinished,
 	UngroupLayers {
 		folder_path: Vec<LayerId>,
 	},
 	UngroupSelectedLayers,
+	UnlinkSelectedLayerTransforms,
 	UpdateLayerMetadata {
 		layer_path: Vec<LayerId>,
 		layer_metadata: LayerMetadata,
 	},
+	UpdateTextStyle {
+		text_style: TextStyle,
+	},
+	/// Exports the design tokens again whenever they change, or stops doing so if no format is given.
+	WatchDesignTokens {
+		format: Option<DesignTokenFormat>,
+	},
 	ZoomCanvasTo100Percent,
 	ZoomCanvasTo200Percent,
 	ZoomCanvasToFitAll,
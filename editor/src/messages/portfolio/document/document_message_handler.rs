@@ -1,49 +1,133 @@
 use super::utility_types::error::EditorError;
 use super::utility_types::misc::{DocumentRenderMode, SnappingOptions, SnappingState};
 use crate::application::generate_uuid;
-use crate::consts::{ASYMPTOTIC_EFFECT, DEFAULT_DOCUMENT_NAME, FILE_SAVE_SUFFIX, GRAPHITE_DOCUMENT_VERSION, SCALE_EFFECT, SCROLLBAR_SPACING, VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR};
+use crate::consts::{
+	ASYMPTOTIC_EFFECT, DEFAULT_DOCUMENT_NAME, EXPORT_PREVIEW_SIZE, FILE_SAVE_SUFFIX, FILE_THUMBNAIL_SIZE, GRAPHITE_DOCUMENT_VERSION, MAX_LAYER_RENDER_UPDATES, MAX_REPORTED_ELEMENT_IDS,
+	PATH_OUTLINE_WEIGHT, PLACEHOLDER_TEXT_LINE_LENGTH, RASTER_RESOLUTION_MAX, RASTER_RESOLUTION_MIN, SCALE_EFFECT, SCROLLBAR_SPACING, VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR,
+};
+use crate::messages::dialog::simple_dialogs;
 use crate::messages::frontend::utility_types::ExportBounds;
 use crate::messages::frontend::utility_types::FileType;
 use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::node_graph::transform_utils::nonzero_subpath_bounds;
+use crate::messages::portfolio::document::node_graph::{add_missing_inputs, VectorDataModification};
+use crate::messages::portfolio::document::presentation::PresentationMessageHandlerData;
 use crate::messages::portfolio::document::properties_panel::utility_types::PropertiesPanelMessageHandlerData;
-use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
+use crate::messages::portfolio::document::utility_types::boolean_operations::{combine_shapes, BooleanPreview, StyledShape, BOOLEAN_PREVIEW_MAX_POINTS};
+use crate::messages::portfolio::document::utility_types::clipboards::{Clipboard, PasteMode};
+use crate::messages::portfolio::document::utility_types::data_merge::{fill_layer_placeholders, merge_offset, MergeTable};
+use crate::messages::portfolio::document::utility_types::design_tokens::{DesignTokenFormat, DesignTokens};
+use crate::messages::portfolio::document::utility_types::dxf::shapes_to_dxf;
+use crate::messages::portfolio::document::utility_types::export_history::{ExportHistory, ExportRecord};
+use crate::messages::portfolio::document::utility_types::font_subset::font_face_rule;
+use crate::messages::portfolio::document::utility_types::guide_grid::{GuideGrid, GuideGridType, GUIDE_GRID_OVERLAY_OPACITY};
+use crate::messages::portfolio::document::utility_types::hatch::hatch_subpaths;
+use crate::messages::portfolio::document::utility_types::idle_work::IdleWork;
+use crate::messages::portfolio::document::utility_types::isometric::{isometric_box_over, IsometricPlane};
 use crate::messages::portfolio::document::utility_types::layer_panel::{LayerMetadata, LayerPanelEntry, RawBuffer};
-use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, DocumentMode, DocumentSave, FlipAxis};
+use crate::messages::portfolio::document::utility_types::memory_report::MemoryReport;
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, DocumentMode, DocumentSave, FlipAxis, NewLayerPlacement};
+use crate::messages::portfolio::document::utility_types::operation_cost::CostlyOperation;
+use crate::messages::portfolio::document::utility_types::operation_log::{LoggedEdit, OperationLog};
+use crate::messages::portfolio::document::utility_types::outline::{document_outline, layer_font, layer_font_size, layer_text, used_fonts};
+use crate::messages::portfolio::document::utility_types::path_joining::{close_subpath, join_subpaths, nearest_ends, PATH_JOIN_TOLERANCE};
+use crate::messages::portfolio::document::utility_types::placeholder::placeholder_text;
+use crate::messages::portfolio::document::utility_types::plotter::{shapes_to_toolpaths, toolpaths_to_gcode, toolpaths_to_hpgl};
+use crate::messages::portfolio::document::utility_types::print_marks::compose_print_sheet;
+use crate::messages::portfolio::document::utility_types::progressive_loading::{ProgressiveLoading, PROGRESSIVE_LOADING_LAYER_COUNT};
+use crate::messages::portfolio::document::utility_types::prototype_links::{PrototypeLinks, PrototypeTransition};
+use crate::messages::portfolio::document::utility_types::recovery::{drop_unreadable_layers, DroppedLayer};
+use crate::messages::portfolio::document::utility_types::scene_graph::{scene_graph, scene_graph_json};
+use crate::messages::portfolio::document::utility_types::soft_proof::soft_proof_svg;
+use crate::messages::portfolio::document::utility_types::sprite_sheet::{pack_sprites, unique_sprite_names, AtlasFrame, AtlasMeta, AtlasRect, AtlasSize, SpriteAtlas, SpriteSource};
+use crate::messages::portfolio::document::utility_types::symmetry::{symmetrize, SYMMETRY_TOLERANCE};
+use crate::messages::portfolio::document::utility_types::tidy::tidy;
+use crate::messages::portfolio::document::utility_types::transform_links::{viewport_change, TransformLinks};
+use crate::messages::portfolio::document::utility_types::transformation::{OriginalTransforms, Selected};
+use crate::messages::portfolio::document::utility_types::vector_export::collect_shapes;
 use crate::messages::portfolio::document::utility_types::vectorize_layer_metadata;
+use crate::messages::portfolio::render_workers::{RenderOutput, RenderWorkers};
 use crate::messages::portfolio::utility_types::PersistentData;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::color_selector::ColorSlot;
+use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::common_functionality::shape_presets::ShapePreset;
 use crate::messages::tool::utility_types::ToolType;
 use crate::node_graph_executor::NodeGraphExecutor;
 
+use document_legacy::consts::MAX_FILE_SIZE;
+use document_legacy::diff::LayerChange;
 use document_legacy::document::Document as DocumentLegacy;
 use document_legacy::layers::blend_mode::BlendMode;
 use document_legacy::layers::folder_layer::FolderLayer;
 use document_legacy::layers::layer_info::{LayerDataType, LayerDataTypeDiscriminant};
 use document_legacy::layers::layer_layer::CachedOutputData;
 use document_legacy::layers::style::{RenderData, ViewMode};
+use document_legacy::layers::text_style::TextStyle;
+use document_legacy::layers::{embedded_font_family, SvgElementIds};
 use document_legacy::{DocumentError, DocumentResponse, LayerId, Operation as DocumentOperation};
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{NodeInput, NodeNetwork};
-use graphene_core::raster::ImageFrame;
-use graphene_core::text::Font;
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::text::{glyph_ids, load_face, Font, FontCache};
+use graphene_core::uuid::ManipulatorGroupId;
+use graphene_core::vector::style::{Fill, PathStyle, Stroke};
+use graphene_core::vector::{ManipulatorPointId, SelectedType, Subpath};
+use graphene_core::Color;
 
 use glam::{DAffine2, DVec2};
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DocumentMessageHandler {
+	/// A small PNG of the artwork as of the last save, as a data URL. It comes first in the file so it's found quickly by anything reading only the thumbnail.
+	#[serde(default)]
+	pub thumbnail: Option<String>,
 	pub document_legacy: DocumentLegacy,
 	pub saved_document_identifier: u64,
 	pub auto_saved_document_identifier: u64,
+	/// Identifies the edit that brought the document to its current state, see [`Self::current_identifier`]
+	#[serde(skip)]
+	edit_identifier: u64,
 	pub name: String,
 	pub version: String,
+	/// Identifies the file this document is saved as, which stays the same each time it's opened, unlike the ID of the open document.
+	/// Files saved before it was added are identified by their content instead, see [`Self::deserialize_document`].
+	#[serde(default)]
+	pub file_id: u64,
+	/// Set while another window or instance of the editor holds the lock on this document, which prevents saving over its changes until the lock is overridden
+	#[serde(skip)]
+	pub read_only: bool,
 
 	pub document_mode: DocumentMode,
 	pub view_mode: ViewMode,
-	#[serde(skip)]
+	/// Previews how the colors of the artwork look when printed with CMYK inks, without changing the colors themselves
+	#[serde(default)]
+	pub cmyk_preview: bool,
+	/// Pixels per document pixel at which the artwork below a layer is rasterized before its raster effects are applied, unless the layer overrides it
+	#[serde(default = "default_raster_resolution")]
+	pub raster_resolution: f64,
+	#[serde(default)]
 	pub snapping_state: SnappingState,
+	/// The isometric or perspective guides drawn over the canvas, which line and pen segments can snap to
+	#[serde(default)]
+	pub guide_grid: GuideGrid,
 	pub overlays_visible: bool,
+	/// The settings of the most recent exports, so the last one can be repeated
+	#[serde(default)]
+	pub export_history: ExportHistory,
+	/// The layers whose transforms are linked, so transforming one of them transforms the others along with it
+	#[serde(default)]
+	pub transform_links: TransformLinks,
+	/// The links from layers to artboards which are followed when the layers are clicked, for clicking through the artboards as a prototype
+	#[serde(default)]
+	pub prototype_links: PrototypeLinks,
+	/// The edits made to the document since it was created, which are replayed to repair the document if it was damaged by a bug
+	#[serde(default)]
+	pub operation_log: OperationLog,
 
 	#[serde(skip)]
 	pub document_undo_history: VecDeque<DocumentSave>,
@@ -52,6 +136,9 @@ pub struct DocumentMessageHandler {
 	/// Don't allow aborting transactions whilst undoing to avoid #559
 	#[serde(skip)]
 	undo_in_progress: bool,
+	/// The last translation, rotation, or scale applied to the selection, in document space about the pivot of the selection, which "Transform Again" repeats
+	#[serde(skip)]
+	last_selection_transform: Option<DAffine2>,
 
 	#[serde(with = "vectorize_layer_metadata")]
 	pub layer_metadata: HashMap<Vec<LayerId>, LayerMetadata>,
@@ -60,10 +147,41 @@ pub struct DocumentMessageHandler {
 	navigation_handler: NavigationMessageHandler,
 	#[serde(skip)]
 	overlays_message_handler: OverlaysMessageHandler,
+	#[serde(skip)]
+	pub presentation_handler: PresentationMessageHandler,
+	/// The overlay layer showing the guide grid, while it is visible
+	#[serde(skip)]
+	guide_grid_overlay: Option<Vec<LayerId>>,
 	pub artboard_message_handler: ArtboardMessageHandler,
 	properties_panel_message_handler: PropertiesPanelMessageHandler,
 	#[serde(skip)]
 	node_graph_handler: NodeGraphMessageHandler,
+	#[serde(skip)]
+	idle_work: IdleWork,
+	#[serde(skip)]
+	boolean_preview: BooleanPreview,
+	/// Whether the user chose to go ahead with the next operation despite the warning that it may take a long time
+	#[serde(skip)]
+	costly_operation_confirmed: bool,
+	/// The geometry which the geometry of the selected shapes is replaced with by "Replace Geometry"
+	#[serde(skip)]
+	geometry_source: Option<Vec<bezier_rs::Subpath<ManipulatorGroupId>>>,
+	/// The folder contents of a very large document which are still being attached after it was opened
+	#[serde(skip)]
+	progressive_loading: ProgressiveLoading,
+	/// The format the design tokens are exported in again whenever they change, while watching for changes
+	#[serde(skip)]
+	design_token_watch: Option<DesignTokenFormat>,
+	/// The design tokens as last exported, which are compared with the current ones while watching so unchanged tokens aren't exported again
+	#[serde(skip)]
+	exported_design_tokens: Option<DesignTokens>,
+	/// The SVG defs of the artwork shown in the viewport, or `None` if the viewport needs the whole artwork on the next render rather than only the changed layers
+	#[serde(skip)]
+	rendered_artwork_defs: Option<String>,
+}
+
+fn default_raster_resolution() -> f64 {
+	1.
 }
 
 impl Default for DocumentMessageHandler {
@@ -73,29 +191,51 @@ impl Default for DocumentMessageHandler {
 			..Default::default()
 		};
 		Self {
+			thumbnail: None,
 			document_legacy,
 			saved_document_identifier: 0,
 			auto_saved_document_identifier: 0,
+			edit_identifier: 0,
 			name: String::from("Untitled Document"),
 			version: GRAPHITE_DOCUMENT_VERSION.to_string(),
+			file_id: generate_uuid(),
+			read_only: false,
 
 			document_mode: DocumentMode::DesignMode,
 			view_mode: ViewMode::default(),
+			cmyk_preview: false,
+			raster_resolution: default_raster_resolution(),
 			snapping_state: SnappingState::default(),
+			guide_grid: GuideGrid::default(),
 			overlays_visible: true,
+			export_history: ExportHistory::default(),
+			transform_links: TransformLinks::default(),
+			prototype_links: PrototypeLinks::default(),
+			operation_log: OperationLog::new(),
 
 			document_undo_history: VecDeque::new(),
 			document_redo_history: VecDeque::new(),
 			undo_in_progress: false,
+			last_selection_transform: None,
 
 			layer_metadata: vec![(vec![], LayerMetadata::new(true))].into_iter().collect(),
 			layer_range_selection_reference: Vec::new(),
 
 			navigation_handler: NavigationMessageHandler::default(),
 			overlays_message_handler: OverlaysMessageHandler::default(),
+			presentation_handler: PresentationMessageHandler::default(),
+			guide_grid_overlay: None,
 			artboard_message_handler: ArtboardMessageHandler::default(),
 			properties_panel_message_handler: PropertiesPanelMessageHandler::default(),
 			node_graph_handler: Default::default(),
+			idle_work: IdleWork::default(),
+			boolean_preview: BooleanPreview::default(),
+			costly_operation_confirmed: false,
+			geometry_source: None,
+			progressive_loading: ProgressiveLoading::default(),
+			design_token_watch: None,
+			exported_design_tokens: None,
+			rendered_artwork_defs: None,
 		}
 	}
 }
@@ -117,7 +257,13 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 			// Sub-messages
 			#[remain::unsorted]
 			DispatchOperation(op) => {
-				match self.document_legacy.handle_operation(*op) {
+				let logged_operation = (OperationLog::logs(&op) && self.operation_log.is_logging()).then(|| (*op).clone());
+				let result = self.document_legacy.handle_operation(*op);
+				if let (Ok(_), Some(operation)) = (&result, logged_operation) {
+					self.operation_log.record(LoggedEdit::Operation(operation));
+				}
+
+				match result {
 					Ok(Some(document_responses)) => {
 						for response in document_responses {
 							match &response {
@@ -127,6 +273,8 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 								}),
 								DocumentResponse::DeletedLayer { path } => {
 									self.layer_metadata.remove(path);
+									self.idle_work.forget(path);
+									self.queue_document_thumbnail(responses);
 								}
 								DocumentResponse::LayerChanged { path } => responses.add(LayerChanged { affected_layer_path: path.clone() }),
 								DocumentResponse::MoveSelectedLayersTo {
@@ -145,6 +293,8 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 									self.layer_metadata.insert(path.clone(), LayerMetadata::new(false));
 
 									responses.add(LayerChanged { affected_layer_path: path.clone() });
+									// Layers pasted, moved, grouped or duplicated into a folder take the style it passes down to them
+									responses.add(ApplyInheritedStyle { layers: vec![path.clone()] });
 									self.layer_range_selection_reference = path.clone();
 
 									if *is_selected {
@@ -172,6 +322,7 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					Err(e) => error!("DocumentError: {:?}", e),
 					Ok(_) => (),
 				}
+				self.queue_design_tokens(responses);
 			}
 			#[remain::unsorted]
 			Artboard(message) => {
@@ -183,7 +334,15 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				self.navigation_handler.process_message(
 					message,
 					responses,
-					(&self.document_legacy, document_bounds, ipp, self.selected_visible_layers_bounding_box(&render_data)),
+					(
+						&self.document_legacy,
+						document_bounds,
+						ipp,
+						self.selected_visible_layers_bounding_box(&render_data),
+						preferences.zoom_pixel_ratio(ipp),
+						preferences.smooth_navigation,
+						&preferences.zoom_levels,
+					),
 				);
 			}
 			#[remain::unsorted]
@@ -191,12 +350,28 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				self.overlays_message_handler.process_message(message, responses, (self.overlays_visible, persistent_data, ipp));
 			}
 			#[remain::unsorted]
+			Presentation(message) => {
+				let presentation_data = PresentationMessageHandlerData {
+					document: &self.document_legacy,
+					artboards_document: &self.artboard_message_handler.artboards_document,
+					artboard_ids: &self.artboard_message_handler.artboard_ids,
+					prototype_links: &self.prototype_links,
+					document_bounds: self.document_bounds(&render_data),
+					overlays_visible: self.overlays_visible,
+					ipp,
+					render_data: &render_data,
+				};
+				self.presentation_handler.process_message(message, responses, presentation_data);
+			}
+			#[remain::unsorted]
 			PropertiesPanel(message) => {
 				let properties_panel_message_handler_data = PropertiesPanelMessageHandlerData {
 					document_name: self.name.as_str(),
 					artwork_document: &self.document_legacy,
 					artboard_document: &self.artboard_message_handler.artboards_document,
+					raster_resolution: self.raster_resolution,
 					selected_layers: &mut self.layer_metadata.iter().filter_map(|(path, data)| data.selected.then_some(path.as_slice())),
+					prototype_links: &self.prototype_links,
 					node_graph_message_handler: &self.node_graph_handler,
 					executor,
 				};
@@ -205,11 +380,44 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 			}
 			#[remain::unsorted]
 			NodeGraph(message) => {
+				// Edits made in the node graph are logged as the network they leave behind, since the messages depend on what the node graph shows
+				let edits_network = !matches!(
+					message,
+					NodeGraphMessage::CloseNodeGraph
+						| NodeGraphMessage::Copy
+						| NodeGraphMessage::DoubleClickNode { .. }
+						| NodeGraphMessage::ExitNestedNetwork { .. }
+						| NodeGraphMessage::OpenNodeGraph { .. }
+						| NodeGraphMessage::RunDocumentGraph
+						| NodeGraphMessage::SelectNodes { .. }
+						| NodeGraphMessage::SendGraph { .. }
+						| NodeGraphMessage::UpdateNewNodeGraph
+				);
 				self.node_graph_handler
 					.process_message(message, responses, (&mut self.document_legacy, executor, document_id, self.name.as_str()));
+				if edits_network && self.operation_log.is_logging() {
+					let layer = self.node_graph_handler.layer_path.clone();
+					let network = match &layer {
+						Some(path) => self.document_legacy.layer(path).and_then(|layer| layer.as_layer_network()).ok(),
+						None => Some(&self.document_legacy.document_network),
+					};
+					if let Some(network) = network {
+						let network = network.clone();
+						self.operation_log.record(LoggedEdit::SetNetwork { layer, network });
+					}
+				}
 			}
 			#[remain::unsorted]
-			GraphOperation(message) => GraphOperationMessageHandler.process_message(message, responses, (&mut self.document_legacy, &mut self.node_graph_handler)),
+			GraphOperation(message) => {
+				// The changes are worked out first, since setting a transform replaces the one the change is measured from
+				let linked_changes = self.linked_transform_changes(&message);
+				let logged_message = self.operation_log.is_logging().then(|| message.clone());
+				GraphOperationMessageHandler.process_message(message, responses, (&mut self.document_legacy, &mut self.node_graph_handler));
+				if let Some(message) = logged_message {
+					self.operation_log.record(LoggedEdit::GraphOperation(message));
+				}
+				responses.extend(linked_changes.into_iter().map(Message::from));
+			}
 
 			// Messages
 			AbortTransaction => {
@@ -265,7 +473,139 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					responses.add(BroadcastEvent::DocumentIsDirty);
 				}
 			}
+			ApplyArtboardConstraints { old_bounds, new_bounds } => {
+				// The layers on the artboard are the ones at the root of the document centered within it, whose parent space is the document space the artboard is in
+				let root = self.document_legacy.root.as_folder().expect("Root is always a folder");
+				for (&id, layer) in root.layer_ids.iter().zip(root.layers()) {
+					let Some(bounds) = layer.aabb(&render_data) else { continue };
+					let center = (bounds[0] + bounds[1]) / 2.;
+					if center.cmplt(old_bounds[0]).any() || center.cmpgt(old_bounds[1]).any() {
+						continue;
+					}
+
+					let transform = layer.constraints.resize(bounds, old_bounds, new_bounds);
+					if transform != DAffine2::IDENTITY {
+						responses.add(GraphOperationMessage::TransformChange {
+							layer: vec![id],
+							transform,
+							transform_in: TransformIn::Local,
+							skip_rerender: false,
+						});
+					}
+				}
+			}
+			ApplyInheritedStyle { layers } => {
+				let layers = layers.iter().flat_map(|path| self.document_legacy.layers_inheriting_style(path)).collect::<Vec<_>>();
+				if layers.is_empty() {
+					return;
+				}
+
+				for layer in layers {
+					let style = self.document_legacy.inherited_style(&layer);
+					if let Some(fill) = style.fill {
+						responses.add(GraphOperationMessage::FillSet { layer: layer.clone(), fill });
+					}
+					if let Some(stroke) = style.stroke {
+						responses.add(GraphOperationMessage::StrokeSet { layer, stroke });
+					}
+				}
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			ApplyTextStyle { id } => {
+				let Some(text_style) = self.text_style(id).cloned() else { return };
+				let layers = self.selected_text_layers();
+				if layers.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for layer in layers {
+					responses.add(DocumentOperation::SetLayerTextStyle {
+						path: layer.clone(),
+						text_style: Some(id),
+					});
+					responses.add(GraphOperationMessage::TextStyleSet {
+						layer,
+						font: text_style.font.clone(),
+						font_size: text_style.font_size,
+					});
+				}
+				responses.add(FrontendMessage::TriggerFontLoad {
+					font: text_style.font,
+					is_default: false,
+				});
+			}
 			BackupDocument { document, artboard, layer_metadata } => self.backup_with_document(document, *artboard, layer_metadata, responses),
+			BooleanOperation(operation) => {
+				self.clear_boolean_preview(responses);
+
+				let document_transform_inverse = self.document_legacy.root.transform.inverse();
+				let (shapes, source_layers) = self.boolean_operation_shapes();
+
+				let Some(front_path) = source_layers.first().cloned() else { return };
+				let points = shapes.iter().flat_map(|(subpaths, _)| subpaths).map(|subpath| subpath.len()).sum::<usize>();
+				let cost = CostlyOperation::BooleanOperation { points };
+				if !self.confirm_costly_operation(cost, "This boolean operation may take a long time", DocumentMessage::BooleanOperation(operation), responses) {
+					return;
+				}
+
+				let (subpaths, style) = match combine_shapes(operation, shapes) {
+					Ok(result) => result,
+					Err(error) => {
+						warn!("The {operation:?} boolean operation could not be completed: {error:?}");
+						return;
+					}
+				};
+
+				self.backup(responses);
+
+				// Replace the shapes with the result, placed where the frontmost shape was
+				let Some((&front_id, parent_path)) = front_path.split_last() else { return };
+				let Ok(parent_to_document) = self.document_legacy.multiply_transforms(parent_path).map(|transform| document_transform_inverse * transform) else {
+					return;
+				};
+				let subpaths = subpaths
+					.into_iter()
+					.map(|mut subpath| {
+						subpath.apply_transform(parent_to_document.inverse());
+						subpath
+					})
+					.collect();
+				let insert_index = self
+					.document_legacy
+					.folder(parent_path)
+					.and_then(|folder| folder.position_of_layer(front_id))
+					.map_or(-1, |index| index as isize + 1);
+				let mut new_path = parent_path.to_vec();
+				new_path.push(generate_uuid());
+
+				graph_modification_utils::new_vector_layer_at(subpaths, new_path.clone(), insert_index, responses);
+				responses.add(GraphOperationMessage::FillSet {
+					layer: new_path.clone(),
+					fill: style.fill().clone(),
+				});
+				responses.add(GraphOperationMessage::StrokeSet {
+					layer: new_path.clone(),
+					stroke: style.stroke().unwrap_or_else(|| Stroke::new(None, 0.)),
+				});
+				if let Some(name) = self.document_legacy.layer(&front_path).ok().and_then(|layer| layer.name.clone()) {
+					responses.add(DocumentMessage::SetLayerName { layer_path: new_path.clone(), name });
+				}
+				for layer_path in source_layers {
+					responses.add(DocumentOperation::DeleteLayer { path: layer_path });
+				}
+
+				responses.add(DocumentMessage::SetSelectedLayers {
+					replacement_selected_layers: vec![new_path],
+				});
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			BooleanPreviewComputed { job_id, result } => {
+				if self.boolean_preview.job == Some(job_id) {
+					self.boolean_preview.job = None;
+					self.show_boolean_preview(result, responses);
+				}
+			}
 			ClearLayerTree => {
 				// Send an empty layer tree
 				let data_buffer: RawBuffer = Self::default().serialize_root().as_slice().into();
@@ -277,6 +617,50 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					layout_target: LayoutTarget::LayerTreeOptions,
 				});
 			}
+			ClearSelectedFill => self.clear_selected_style(ColorSlot::Fill, responses),
+			ClearSelectedStroke => self.clear_selected_style(ColorSlot::Stroke, responses),
+			CloseSelectedPaths => {
+				let mut closed_layers = Vec::new();
+				for path in self.selected_layers() {
+					let Ok(layer) = self.document_legacy.layer(path) else { continue };
+					let Some(vector_data) = layer.as_vector_data().filter(|_| layer_text(layer).is_none()) else {
+						continue;
+					};
+					if vector_data.subpaths.iter().all(|subpath| subpath.closed() || subpath.len() < 2) {
+						continue;
+					}
+
+					// The ends are compared in viewport space so the tolerance is the same at any zoom level
+					let transform = self.document_legacy.multiply_transforms(path).unwrap_or_default();
+					let subpaths = vector_data
+						.subpaths
+						.iter()
+						.map(|subpath| {
+							if subpath.closed() || subpath.len() < 2 {
+								return subpath.clone();
+							}
+							let mut subpath = subpath.clone();
+							subpath.apply_transform(transform);
+							let mut closed = close_subpath(&subpath, PATH_JOIN_TOLERANCE);
+							closed.apply_transform(transform.inverse());
+							closed
+						})
+						.collect();
+					closed_layers.push((path.to_vec(), subpaths));
+				}
+				if closed_layers.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for (layer, subpaths) in closed_layers {
+					responses.add(GraphOperationMessage::Vector {
+						layer,
+						modification: VectorDataModification::UpdateSubpaths { subpaths },
+					});
+				}
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
 			CommitTransaction => (),
 			CopyToClipboardLayerImageOutput { layer_path } => {
 				let layer = self.document_legacy.layer(&layer_path).ok();
@@ -287,6 +671,33 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					responses.add(FrontendMessage::TriggerCopyToClipboardBlobUrl { blob_url });
 				}
 			}
+			CopySelectedLayersAsPng { scale_factor } => {
+				self.finish_loading(responses);
+
+				// Layers inside selected folders are already drawn with their folder
+				let selected = self.selected_layers_sorted();
+				let layer_paths = selected
+					.iter()
+					.filter(|path| !selected.iter().any(|other| other.len() < path.len() && path.starts_with(other)))
+					.filter(|path| self.document_legacy.layer(path).map_or(false, |layer| layer.is_visible(true)))
+					.map(|path| path.to_vec())
+					.collect::<Vec<_>>();
+
+				let old_transforms = self.remove_document_transform();
+				let bounds = self.selected_exported_layers_bounding_box(&render_data);
+				let rendered = bounds.filter(|[min, max]| (*max - *min).min_element() > 0.).map(|bounds| {
+					let size = bounds[1] - bounds[0];
+					let transform = (DAffine2::from_translation(bounds[0]) * DAffine2::from_scale(size)).inverse();
+					let svg = self.render_document(size, transform, true, false, false, persistent_data, DocumentRenderMode::Layers(&layer_paths));
+					(svg, size)
+				});
+				self.restore_document_transform(old_transforms);
+
+				if let Some((svg, size)) = rendered {
+					let size = (size * scale_factor).round().max(DVec2::ONE).into();
+					responses.add(FrontendMessage::TriggerCopyRaster { svg, size });
+				}
+			}
 			CreateEmptyFolder { mut container_path } => {
 				let id = generate_uuid();
 				container_path.push(id);
@@ -300,6 +711,130 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					set_expanded: true,
 				});
 			}
+			CreateTextStyle => {
+				// The new text style takes the font and size of the first selected text layer, and the rest of the selection is restyled to match
+				let layers = self.selected_text_layers();
+				let Some(first_layer) = layers.first().and_then(|path| self.document_legacy.layer(path).ok()) else {
+					return;
+				};
+				let (Some(font), Some(font_size)) = (layer_font(first_layer), layer_font_size(first_layer)) else {
+					return;
+				};
+				let text_style = TextStyle {
+					id: generate_uuid(),
+					name: format!("Text Style {}", self.document_legacy.text_styles.len() + 1),
+					font: font.clone(),
+					font_size,
+				};
+
+				self.backup(responses);
+				for (index, layer) in layers.into_iter().enumerate() {
+					responses.add(DocumentOperation::SetLayerTextStyle {
+						path: layer.clone(),
+						text_style: Some(text_style.id),
+					});
+					if index > 0 {
+						responses.add(GraphOperationMessage::TextStyleSet {
+							layer,
+							font: text_style.font.clone(),
+							font_size,
+						});
+					}
+				}
+				responses.add(DocumentOperation::SetTextStyle { text_style });
+				responses.add(PropertiesPanelMessage::ResendActiveProperties);
+			}
+			DataMerge { data, per_row, spacing } => {
+				let error = |description: &str, responses: &mut VecDeque<Message>| {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Data merge failed".to_string(),
+						description: description.to_string(),
+					})
+				};
+
+				let Some(table) = MergeTable::from_csv(&data).filter(|table| !table.rows.is_empty()) else {
+					error("The data needs a header row naming the columns, followed by at least one row of values.", responses);
+					return;
+				};
+
+				// Measure the template without the zoom and pan of the viewport
+				let render_data = RenderData::new(&persistent_data.font_cache, ViewMode::Normal, None);
+				let template_bounds = self
+					.artboard_message_handler
+					.artboard_ids
+					.first()
+					.and_then(|&id| self.artboard_message_handler.artboards_document.layer(&[id]).ok())
+					.and_then(|artboard| artboard.aabb(&render_data));
+				let Some([min, max]) = template_bounds else {
+					error("The first artboard is used as the template, but this document has no artboards.", responses);
+					return;
+				};
+
+				// The template is made up of the top-level layers centered on the artboard
+				let root = self.document_legacy.root.as_folder().expect("Root is always a folder");
+				let template = root
+					.layer_ids
+					.iter()
+					.zip(root.layers())
+					.filter(|(_, layer)| {
+						layer.aabb(&render_data).map_or(false, |[layer_min, layer_max]| {
+							let center = (layer_min + layer_max) / 2.;
+							center.cmpge(min).all() && center.cmple(max).all()
+						})
+					})
+					.collect::<Vec<_>>();
+
+				responses.add(DocumentMessage::StartTransaction);
+				responses.add(DocumentMessage::DeselectAllLayers);
+
+				let size = max - min;
+				for (index, row) in table.rows.iter().enumerate() {
+					let offset = merge_offset(index, per_row as usize, size, spacing);
+
+					let id = generate_uuid();
+					let position = min + offset;
+					responses.add(ArtboardMessage::AddArtboard {
+						id: Some(id),
+						position: position.into(),
+						size: size.into(),
+					});
+					responses.add(GraphOperationMessage::NewArtboard {
+						id,
+						artboard: graphene_core::Artboard::new(position.round().as_ivec2(), size.round().as_ivec2()),
+					});
+
+					for &(&template_id, template_layer) in &template {
+						let mut layer = template_layer.clone();
+						layer.transform = DAffine2::from_translation(offset) * layer.transform;
+
+						let layer_path = vec![generate_uuid()];
+						let mut images = Vec::new();
+						fill_layer_placeholders(&mut layer, &table, row, &mut layer_path.clone(), &mut images);
+
+						let layer_metadata = LayerMetadata {
+							selected: false,
+							..self.layer_metadata.get(&[template_id][..]).copied().unwrap_or(LayerMetadata::new(false))
+						};
+						responses.add(DocumentOperation::InsertLayer {
+							layer: Box::new(layer.clone()),
+							destination_path: layer_path.clone(),
+							insert_index: -1,
+							duplicating: false,
+						});
+						responses.add(DocumentMessage::UpdateLayerMetadata {
+							layer_path: layer_path.clone(),
+							layer_metadata,
+						});
+						self.load_layer_resources(responses, &layer.data, layer_path, document_id);
+
+						for (layer_path, url) in images {
+							responses.add(FrontendMessage::TriggerFetchMergedImage { document_id, layer_path, url });
+						}
+					}
+				}
+
+				responses.add(DocumentMessage::CommitTransaction);
+			}
 			DebugPrintDocument => {
 				info!("{:#?}\n{:#?}", self.document_legacy, self.layer_metadata);
 			}
@@ -318,10 +853,39 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				responses.add_front(BroadcastEvent::SelectionChanged);
 				responses.add(BroadcastEvent::DocumentIsDirty);
 			}
+			DeleteTextStyle { id } => {
+				if self.text_style(id).is_none() {
+					return;
+				}
+
+				// The linked layers keep the font and size they were given by the text style
+				self.backup(responses);
+				for path in self.layers_with_text_style(id) {
+					responses.add(DocumentOperation::SetLayerTextStyle { path, text_style: None });
+				}
+				responses.add(DocumentOperation::DeleteTextStyle { id });
+				responses.add(PropertiesPanelMessage::ResendActiveProperties);
+			}
 			DeselectAllLayers => {
 				responses.add_front(SetSelectedLayers { replacement_selected_layers: vec![] });
 				self.layer_range_selection_reference.clear();
 			}
+			DetachTextStyle => {
+				let layers = self
+					.selected_text_layers()
+					.into_iter()
+					.filter(|path| self.document_legacy.layer(path).map_or(false, |layer| layer.text_style.is_some()))
+					.collect::<Vec<_>>();
+				if layers.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for path in layers {
+					responses.add(DocumentOperation::SetLayerTextStyle { path, text_style: None });
+				}
+				responses.add(PropertiesPanelMessage::ResendActiveProperties);
+			}
 			DirtyRenderDocument => {
 				// Mark all non-overlay caches as dirty
 				DocumentLegacy::mark_children_as_dirty(&mut self.document_legacy.root);
@@ -338,6 +902,24 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				let data_buffer: RawBuffer = self.serialize_root().as_slice().into();
 				responses.add(FrontendMessage::UpdateDocumentLayerTreeStructure { data_buffer })
 			}
+			DocumentThumbnailRendered { job_id, thumbnail } => {
+				if self.idle_work.finish_document_thumbnail(job_id) {
+					responses.add(FrontendMessage::UpdateDocumentThumbnail { document_id, thumbnail });
+				}
+			}
+			DownloadDocument { thumbnail } => {
+				self.thumbnail = thumbnail;
+				responses.add(PortfolioMessage::AutoSaveActiveDocument);
+
+				let document = self.serialize_document();
+				// The save is kept even without any backups, so recovered changes can be compared with it
+				responses.add(FrontendMessage::TriggerIndexedDbWriteBackup {
+					file_name: self.file_name(),
+					document: document.clone(),
+					keep: preferences.backup_count,
+				});
+				responses.add(FrontendMessage::TriggerDownloadTextFile { document, name: self.file_name() })
+			}
 			DownloadLayerImageOutput { layer_path } => {
 				let layer = self.document_legacy.layer(&layer_path).ok();
 
@@ -357,115 +939,608 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					responses.add(DocumentOperation::DuplicateLayer { path: path.to_vec() });
 				}
 			}
+			DuplicateSelectedLayersWithOffset => {
+				// The duplicates are selected once they are created, so they are the layers moved afterwards
+				let delta = self.document_legacy.root.transform.transform_vector2(DVec2::splat(preferences.duplicate_offset));
+				responses.add(DuplicateSelectedLayers);
+				responses.add(TranslateSelectedLayers { delta });
+			}
+			ExportDesignTokens { format } => {
+				self.finish_loading(responses);
+				let tokens = self.design_tokens();
+				self.download_design_tokens(tokens, format, responses);
+			}
 			ExportDocument {
 				file_name,
 				file_type,
 				scale_factor,
 				bounds,
+				custom_bounds,
+				padding,
 				transparent_background,
+				layer_names,
+				embed_fonts,
+				dxf_options,
+				plotter_options,
+				print_options,
+				preview,
 			} => {
+				self.finish_loading(responses);
+
+				// Fabrication files and scene graphs are text describing the artwork, so they have no image to preview
+				if preview && !file_type.is_rendered() {
+					responses.add(FrontendMessage::UpdateExportPreview {
+						svg: String::new(),
+						mime: String::new(),
+						size: (0., 0.),
+					});
+					return;
+				}
+
+				// The settings are remembered before the bounds are resolved, so repeating the export follows the artwork if it has since changed
+				let record = (!preview).then(|| ExportRecord {
+					file_name: file_name.clone(),
+					file_type,
+					scale_factor,
+					bounds,
+					custom_bounds,
+					padding,
+					transparent_background,
+					layer_names,
+					embed_fonts,
+					dxf_options,
+					plotter_options,
+					print_options,
+					exported_at: unix_time_milliseconds(),
+				});
+
 				let old_transforms = self.remove_document_transform();
+				let only_selected = bounds == ExportBounds::Selection;
 
 				// Calculate the bounding box of the region to be exported
 				let bounds = match bounds {
 					ExportBounds::AllArtwork => self.all_layer_bounds(&render_data),
-					ExportBounds::Selection => self.selected_visible_layers_bounding_box(&render_data),
+					ExportBounds::Selection => self.selected_exported_layers_bounding_box(&render_data),
 					ExportBounds::Artboard(id) => self.artboard_message_handler.artboards_document.layer(&[id]).ok().and_then(|layer| layer.aabb(&render_data)),
+					// Custom bounds are used exactly as entered, so no padding is added around them
+					ExportBounds::Custom => Some(custom_bounds),
 				}
+				.map(|[min, max]| match bounds {
+					ExportBounds::Custom => [min, max],
+					_ => [min - padding, max + padding],
+				})
 				.unwrap_or_default();
-				let size = bounds[1] - bounds[0];
-				let transform = (DAffine2::from_translation(bounds[0]) * DAffine2::from_scale(size)).inverse();
+				// Icons are square, so the shorter side of the bounds is extended equally in both directions
+				let bounds = match file_type.is_icon() {
+					true => {
+						let center = (bounds[0] + bounds[1]) / 2.;
+						let half_size = DVec2::splat((bounds[1] - bounds[0]).max_element() / 2.);
+						[center - half_size, center + half_size]
+					}
+					false => bounds,
+				};
+				let mut size = bounds[1] - bounds[0];
+				let print_sheet = print_options.is_enabled() && file_type.is_rendered() && !file_type.is_icon();
+				let mut background_render = None;
+
+				// Rasterizing a huge image can keep the editor busy for minutes, so the user is asked first (icons are always small)
+				if let Some(record) = record.as_ref().filter(|_| !file_type.is_text() && !file_type.is_icon()) {
+					let output_size = if print_sheet { print_options.sheet_size(bounds) } else { size } * scale_factor;
+					let cost = CostlyOperation::RasterExport { size: output_size };
+					if !self.confirm_costly_operation(cost, "This export may take a long time", record.export_message(), responses) {
+						self.restore_document_transform(old_transforms);
+						return;
+					}
+				}
 
-				let document = self.render_document(size, transform, transparent_background, persistent_data, DocumentRenderMode::Root);
+				let document = if file_type == FileType::Json {
+					let selected = only_selected.then(|| self.selected_layers().map(|path| path.to_vec()).collect::<Vec<_>>());
+					scene_graph_json(&scene_graph(&self.document_legacy, bounds, selected.as_deref()))
+				} else if file_type.is_fabrication() {
+					let selected = only_selected.then(|| self.selected_layers().map(|path| path.to_vec()).collect::<Vec<_>>());
+					let shapes = collect_shapes(&self.document_legacy, selected.as_deref());
+					match file_type {
+						FileType::Gcode => toolpaths_to_gcode(&shapes_to_toolpaths(&shapes, bounds, plotter_options), plotter_options),
+						FileType::Hpgl => toolpaths_to_hpgl(&shapes_to_toolpaths(&shapes, bounds, plotter_options)),
+						_ => shapes_to_dxf(&shapes, bounds, dxf_options),
+					}
+				} else if print_sheet {
+					// The artboards are drawn by the print sheet instead, so their backgrounds can be extended into the bleed
+					let backgrounds = self
+						.artboard_message_handler
+						.artboard_ids
+						.iter()
+						.filter_map(|&id| {
+							let artboard = self.artboard_message_handler.artboards_document.layer(&[id]).ok()?;
+							Some((artboard.aabb(&render_data)?, artboard.style().ok()?.fill().color()))
+						})
+						.collect::<Vec<_>>();
+
+					let bleed_bounds = print_options.bleed_bounds(bounds);
+					let bleed_size = bleed_bounds[1] - bleed_bounds[0];
+					let transform = (DAffine2::from_translation(bleed_bounds[0]) * DAffine2::from_scale(bleed_size)).inverse();
+					let artwork = self.render_document(bleed_size, transform, true, layer_names, embed_fonts, persistent_data, DocumentRenderMode::Root);
+
+					size = print_options.sheet_size(bounds);
+					compose_print_sheet(&artwork, bounds, &backgrounds, &print_options, transparent_background)
+				} else if !preview && RenderWorkers::available() {
+					// Rendered by a render worker from a copy of the document, and downloaded once that's done, so large exports don't stall the editor
+					let transform = (DAffine2::from_translation(bounds[0]) * DAffine2::from_scale(size)).inverse();
+					background_render = Some(self.render_document_job(size, transform, transparent_background, layer_names, embed_fonts, persistent_data));
+					String::new()
+				} else {
+					let transform = (DAffine2::from_translation(bounds[0]) * DAffine2::from_scale(size)).inverse();
+					self.render_document(size, transform, transparent_background, layer_names, embed_fonts, persistent_data, DocumentRenderMode::Root)
+				};
 
 				self.restore_document_transform(old_transforms);
 
+				if preview {
+					// Icons are previewed as one of their square images, and other files at their output size shrunk to fit the dialog
+					let output_size = if file_type.is_icon() { size } else { size * scale_factor };
+					let size = output_size * (EXPORT_PREVIEW_SIZE / output_size.max_element()).min(1.);
+					if size.min_element() >= 1. {
+						// Transparency is kept in the preview unless the file is a JPG, which is shown on white like its export
+						let mime = if file_type == FileType::Jpg { "image/jpeg" } else { "image/png" }.to_string();
+						let size = size.round().into();
+						responses.add(FrontendMessage::UpdateExportPreview { svg: document, mime, size });
+					} else {
+						// There's nothing to show for empty bounds, so the preview of the previous settings is cleared instead of lingering
+						responses.add(FrontendMessage::UpdateExportPreview {
+							svg: String::new(),
+							mime: String::new(),
+							size: (0., 0.),
+						});
+					}
+					return;
+				}
+
+				if let Some(record) = record {
+					self.export_history.record(record);
+				}
+				if layer_names {
+					self.report_broken_element_ids(responses);
+				}
+
 				let file_suffix = &format!(".{file_type:?}").to_lowercase();
 				let name = match file_name.ends_with(FILE_SAVE_SUFFIX) {
 					true => file_name.replace(FILE_SAVE_SUFFIX, file_suffix),
 					false => file_name + file_suffix,
 				};
 
-				if file_type == FileType::Svg {
-					responses.add(FrontendMessage::TriggerDownloadTextFile { document, name });
-				} else {
-					let mime = file_type.to_mime().to_string();
-					let size = (size * scale_factor).into();
-					responses.add(FrontendMessage::TriggerDownloadRaster { svg: document, name, mime, size });
-				}
-			}
-			FlipSelectedLayers { flip_axis } => {
-				self.backup(responses);
-				let scale = match flip_axis {
-					FlipAxis::X => DVec2::new(-1., 1.),
-					FlipAxis::Y => DVec2::new(1., -1.),
-				};
-				if let Some([min, max]) = self.document_legacy.combined_viewport_bounding_box(self.selected_layers(), &render_data) {
-					let center = (max + min) / 2.;
-					let bbox_trans = DAffine2::from_translation(-center);
-					for path in self.selected_layers() {
-						responses.add(GraphOperationMessage::TransformChange {
-							layer: path.to_vec(),
-							transform: DAffine2::from_scale(scale),
-							transform_in: TransformIn::Scope { scope: bbox_trans },
-							skip_rerender: false,
-						});
+				let size = size * scale_factor;
+				match background_render {
+					Some(render) => {
+						persistent_data.render_workers.submit(Box::new(move |font_cache| RenderOutput::Export {
+							svg: render(font_cache),
+							name,
+							file_type,
+							size,
+						}));
 					}
-					responses.add(BroadcastEvent::DocumentIsDirty);
+					None => responses.add(file_type.download(document, name, size)),
 				}
 			}
-			FolderChanged { affected_folder_path } => {
-				let affected_layer_path = affected_folder_path;
-				responses.extend([LayerChanged { affected_layer_path }.into(), DocumentStructureChanged.into()]);
-			}
-			FrameClear => {
-				let mut selected_frame_layers = self.selected_layers_with_type(LayerDataTypeDiscriminant::Layer);
-				// Get what is hopefully the only selected Layer layer
-				let layer_path = selected_frame_layers.next();
-				// Abort if we didn't have any Layer layer, or if there are additional ones also selected
-				if layer_path.is_none() || selected_frame_layers.next().is_some() {
-					return;
-				}
-				let layer_path = layer_path.unwrap();
+			ExportSpriteSheet {
+				file_name,
+				source,
+				scale_factor,
+				spacing,
+				transparent_background,
+			} => {
+				self.finish_loading(responses);
+				let old_transforms = self.remove_document_transform();
 
-				let layer = self.document_legacy.layer(layer_path).expect("Clearing Layer image for invalid layer");
-				let previous_blob_url = match &layer.data {
-					LayerDataType::Layer(layer) => layer.as_blob_url(),
-					x => panic!("Cannot find blob url for layer type {}", LayerDataTypeDiscriminant::from(x)),
+				// Find the name, layer, and bounds of each sprite
+				let sprites: Vec<(String, Vec<LayerId>, [DVec2; 2])> = match source {
+					SpriteSource::Artboards => {
+						let artboards = &self.artboard_message_handler;
+						artboards
+							.artboard_ids
+							.iter()
+							.enumerate()
+							.filter_map(|(index, &id)| {
+								let layer = artboards.artboards_document.layer(&[id]).ok()?;
+								let name = layer.name.clone().unwrap_or_else(|| format!("Artboard {}", index + 1));
+								Some((name, vec![id], layer.aabb(&render_data)?))
+							})
+							.collect()
+					}
+					SpriteSource::SelectedLayers => self
+						.selected_layers()
+						.filter_map(|path| {
+							let layer = self.document_legacy.layer(path).ok().filter(|layer| layer.is_visible(true))?;
+							let name = layer.name.clone().unwrap_or_else(|| "Layer".to_string());
+							Some((name, path.to_vec(), self.document_legacy.viewport_bounding_box(path, &render_data).ok().flatten()?))
+						})
+						.collect(),
 				};
 
-				if let Some(url) = previous_blob_url {
-					responses.add(FrontendMessage::TriggerRevokeBlobUrl { url: url.clone() });
+				if sprites.is_empty() || scale_factor <= 0. {
+					self.restore_document_transform(old_transforms);
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Nothing to export".to_string(),
+						description: "There are no visible artboards or selected layers to pack into a sprite sheet.".to_string(),
+					});
+					return;
 				}
-				responses.add(DocumentOperation::ClearBlobURL { path: layer_path.into() });
-			}
-			GroupSelectedLayers => {
-				// TODO: Add code that changes the insert index of the new folder based on the selected layer
-				let mut new_folder_path = self.document_legacy.shallowest_common_folder(self.selected_layers()).unwrap_or(&[]).to_vec();
 
-				// Required for grouping parent folders with their own children
-				if !new_folder_path.is_empty() && self.selected_layers_contains(&new_folder_path) {
-					new_folder_path.remove(new_folder_path.len() - 1);
+				let sizes: Vec<_> = sprites.iter().map(|(_, _, [min, max])| ((*max - *min) * scale_factor).ceil().max(DVec2::ONE).as_uvec2()).collect();
+				let (positions, sheet_size) = pack_sprites(&sizes, spacing);
+				let names = unique_sprite_names(sprites.iter().map(|(name, _, _)| name.clone()));
+
+				let mut sheet = String::new();
+				for ((_, path, [min, _]), (&size, &position)) in sprites.iter().zip(sizes.iter().zip(&positions)) {
+					// Round the region up to whole pixels so the sprite isn't stretched
+					let region_size = size.as_dvec2() / scale_factor;
+					let transform = (DAffine2::from_translation(*min) * DAffine2::from_scale(region_size)).inverse();
+					let render_mode = match source {
+						SpriteSource::Artboards => DocumentRenderMode::Root,
+						SpriteSource::SelectedLayers => DocumentRenderMode::LayerCutout(path, Color::WHITE),
+					};
+					let sprite = self.render_document(size.as_dvec2(), transform, transparent_background, false, false, persistent_data, render_mode);
+					sheet.push_str(&format!(r#"<g transform="translate({},{})">{sprite}</g>"#, position.x, position.y));
 				}
 
-				new_folder_path.push(generate_uuid());
+				self.restore_document_transform(old_transforms);
 
-				responses.add(PortfolioMessage::Copy { clipboard: Clipboard::Internal });
-				responses.add(DocumentMessage::DeleteSelectedLayers);
-				responses.add(DocumentOperation::CreateFolder {
-					path: new_folder_path.clone(),
-					insert_index: -1,
+				let base_name = file_name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(&file_name).to_string();
+				let image_name = format!("{base_name}.png");
+				let atlas = SpriteAtlas {
+					frames: names
+						.into_iter()
+						.zip(sizes.iter().zip(&positions))
+						.map(|(name, (size, position))| {
+							let frame = AtlasRect {
+								x: position.x,
+								y: position.y,
+								w: size.x,
+								h: size.y,
+							};
+							let source_size = AtlasSize { w: size.x, h: size.y };
+							(name, AtlasFrame { frame, source_size })
+						})
+						.collect(),
+					meta: AtlasMeta {
+						image: image_name.clone(),
+						size: AtlasSize { w: sheet_size.x, h: sheet_size.y },
+						scale: scale_factor,
+					},
+				};
+
+				let svg = format!(
+					r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">{sheet}</svg>"#,
+					sheet_size.x, sheet_size.y
+				);
+				responses.add(FrontendMessage::TriggerDownloadRaster {
+					svg,
+					name: image_name,
+					mime: FileType::Png.to_mime().to_string(),
+					size: sheet_size.as_dvec2().into(),
 				});
-				responses.add(DocumentMessage::ToggleLayerExpansion { layer_path: new_folder_path.clone() });
-				responses.add(PortfolioMessage::PasteIntoFolder {
-					clipboard: Clipboard::Internal,
-					folder_path: new_folder_path.clone(),
-					insert_index: -1,
+				responses.add(FrontendMessage::TriggerDownloadTextFile {
+					document: serde_json::to_string_pretty(&atlas).unwrap_or_default(),
+					name: format!("{base_name}.json"),
+				});
+			}
+			ExtrudeSelectedLayersToIsometricBox => {
+				self.backup(responses);
+
+				let document_transform_inverse = self.document_legacy.root.transform.inverse();
+				let selected_layers = self.selected_layers().map(|path| path.to_vec()).collect::<Vec<_>>();
+				let mut new_folders = Vec::new();
+				for layer_path in selected_layers {
+					let Ok(layer) = self.document_legacy.layer(&layer_path) else { continue };
+					let Some(vector_data) = layer.as_vector_data() else { continue };
+					let color = match vector_data.style.fill() {
+						Fill::Solid(color) => *color,
+						Fill::None | Fill::Gradient(_) => Color::from_rgbf32_unchecked(0.5, 0.5, 0.5),
+					};
+					let Some((&layer_id, parent_path)) = layer_path.split_last() else { continue };
+					let Ok(to_document) = self.document_legacy.multiply_transforms(&layer_path).map(|transform| document_transform_inverse * transform) else {
+						continue;
+					};
+					let Ok(parent_to_document) = self.document_legacy.multiply_transforms(parent_path).map(|transform| document_transform_inverse * transform) else {
+						continue;
+					};
+
+					let Some(faces) = vector_data.bounding_box_with_transform(to_document).and_then(isometric_box_over) else {
+						continue;
+					};
+
+					// Place the box directly above the flat layer
+					let insert_index = self
+						.document_legacy
+						.folder(parent_path)
+						.and_then(|folder| folder.position_of_layer(layer_id))
+						.map_or(-1, |index| index as isize + 1);
+					let mut folder_path = parent_path.to_vec();
+					folder_path.push(generate_uuid());
+					let name = format!("{} Isometric Box", layer.name.as_deref().filter(|name| !name.is_empty()).unwrap_or("Layer"));
+					responses.add(DocumentOperation::CreateFolder {
+						path: folder_path.clone(),
+						insert_index,
+					});
+					responses.add(DocumentMessage::SetLayerName {
+						layer_path: folder_path.clone(),
+						name,
+					});
+
+					for (plane, corners) in faces {
+						let mut face = Subpath::from_anchors(corners, true);
+						face.apply_transform(parent_to_document.inverse());
+
+						let mut face_path = folder_path.clone();
+						face_path.push(generate_uuid());
+						graph_modification_utils::new_vector_layer_at(vec![face], face_path.clone(), -1, responses);
+						responses.add(GraphOperationMessage::FillSet {
+							layer: face_path.clone(),
+							fill: Fill::Solid(plane.shade(color)),
+						});
+						let name = match plane {
+							IsometricPlane::Top => "Top",
+							IsometricPlane::Left => "Left",
+							IsometricPlane::Right => "Right",
+						};
+						responses.add(DocumentMessage::SetLayerName {
+							layer_path: face_path,
+							name: name.into(),
+						});
+					}
+					new_folders.push(folder_path);
+				}
+
+				if !new_folders.is_empty() {
+					responses.add(DocumentMessage::SetSelectedLayers {
+						replacement_selected_layers: new_folders,
+					});
+					responses.add(BroadcastEvent::DocumentIsDirty);
+				}
+			}
+			FlattenSelectedLayerEffects { scale_factor } => {
+				// Layers inside selected folders are flattened along with their folder
+				let selected = self.selected_layers_sorted();
+				let layer_paths = selected
+					.iter()
+					.filter(|path| !selected.iter().any(|other| other.len() < path.len() && path.starts_with(other)))
+					.filter(|path| self.document_legacy.layer(path).map_or(false, |layer| layer.is_visible(true)))
+					.map(|path| path.to_vec())
+					.collect::<Vec<_>>();
+
+				let old_transforms = self.remove_document_transform();
+				for layer_path in layer_paths {
+					let Some([min, max]) = self.document_legacy.viewport_bounding_box(&layer_path, &render_data).ok().flatten() else {
+						continue;
+					};
+					let size = max - min;
+					if size.min_element() <= 0. {
+						continue;
+					}
+
+					let transform = (DAffine2::from_translation(min) * DAffine2::from_scale(size)).inverse();
+					let svg = self.render_document(size, transform, true, false, false, persistent_data, DocumentRenderMode::Layers(&[layer_path.clone()]));
+					let size = (size * scale_factor).round().max(DVec2::ONE);
+
+					// Once JS asynchronously rasterizes the SVG, it will call `DocumentMessage::FlattenedLayerEffectsRasterized` with the image
+					responses.add(FrontendMessage::TriggerRasterizeLayerEffects { document_id, layer_path, svg, size });
+				}
+				self.restore_document_transform(old_transforms);
+			}
+			FlattenedLayerEffectsRasterized { layer_path, image } => {
+				let Some((&layer_id, parent_path)) = layer_path.split_last() else { return };
+				let Ok(layer) = self.document_legacy.layer(&layer_path) else { return };
+				let name = format!("{} (Flattened)", layer.name.as_deref().filter(|name| !name.is_empty()).unwrap_or("Layer"));
+
+				// Cover the same area as the layer did when it was rasterized
+				let old_transforms = self.remove_document_transform();
+				let bounds = self.document_legacy.viewport_bounding_box(&layer_path, &render_data).ok().flatten();
+				let parent_transform = self.document_legacy.multiply_transforms(parent_path).unwrap_or_default();
+				self.restore_document_transform(old_transforms);
+				let Some([min, max]) = bounds else { return };
+				let transform = parent_transform.inverse() * DAffine2::from_scale_angle_translation(max - min, 0., min);
+
+				let Some(network) = image_layer_network(image) else { return };
+
+				responses.add(DocumentMessage::StartTransaction);
+
+				// Place the image directly above the original layer, which is kept but hidden so it can still be edited and flattened again
+				let insert_index = self
+					.document_legacy
+					.folder(parent_path)
+					.and_then(|folder| folder.position_of_layer(layer_id))
+					.map_or(-1, |index| index as isize + 1);
+				let mut path = parent_path.to_vec();
+				path.push(generate_uuid());
+				responses.add(DocumentOperation::AddFrame {
+					path: path.clone(),
+					insert_index,
+					transform: DAffine2::ZERO.to_cols_array(),
+					network,
+				});
+				responses.add(GraphOperationMessage::TransformSet {
+					layer: path.clone(),
+					transform,
+					transform_in: TransformIn::Local,
+					skip_rerender: false,
+				});
+				responses.add(DocumentMessage::SetLayerName { layer_path: path.clone(), name });
+				responses.add(DocumentOperation::SetLayerVisibility { path: layer_path, visible: false });
+				responses.add(DocumentMessage::SetSelectedLayers {
+					replacement_selected_layers: vec![path.clone()],
+				});
+				responses.add(DocumentMessage::InputFrameRasterizeRegionBelowLayer { layer_path: path });
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			FlipSelectedLayers { flip_axis } => {
+				self.backup(responses);
+				let scale = match flip_axis {
+					FlipAxis::X => DVec2::new(-1., 1.),
+					FlipAxis::Y => DVec2::new(1., -1.),
+				};
+				if let Some([min, max]) = self.document_legacy.combined_viewport_bounding_box(self.selected_layers(), &render_data) {
+					let center = (max + min) / 2.;
+					let bbox_trans = DAffine2::from_translation(-center);
+					for path in self.selected_layers() {
+						responses.add(GraphOperationMessage::TransformChange {
+							layer: path.to_vec(),
+							transform: DAffine2::from_scale(scale),
+							transform_in: TransformIn::Scope { scope: bbox_trans },
+							skip_rerender: false,
+						});
+					}
+					responses.add(BroadcastEvent::DocumentIsDirty);
+				}
+			}
+			FolderChanged { affected_folder_path } => {
+				let affected_layer_path = affected_folder_path;
+				responses.extend([LayerChanged { affected_layer_path }.into(), DocumentStructureChanged.into()]);
+			}
+			FollowPrototypeLink { layer } => {
+				let Some(link) = self.prototype_links.hotspot_link(&layer) else { return };
+				let Ok(Some(bounds)) = self.artboard_message_handler.artboards_document.viewport_bounding_box(&[link.target], &render_data) else {
+					return;
+				};
+
+				// The transition of the link decides whether the view eases over to the artboard, rather than the navigation preference
+				let document_bounds = self.document_bounds(&render_data);
+				self.navigation_handler.process_message(
+					NavigationMessage::FitViewportToBounds {
+						bounds,
+						padding_scale_factor: Some(VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR),
+						prevent_zoom_past_100: false,
+					},
+					responses,
+					(
+						&self.document_legacy,
+						document_bounds,
+						ipp,
+						self.selected_visible_layers_bounding_box(&render_data),
+						preferences.zoom_pixel_ratio(ipp),
+						link.transition == PrototypeTransition::Smooth,
+						&preferences.zoom_levels,
+					),
+				);
+			}
+			FrameClear => {
+				let mut selected_frame_layers = self.selected_layers_with_type(LayerDataTypeDiscriminant::Layer);
+				// Get what is hopefully the only selected Layer layer
+				let layer_path = selected_frame_layers.next();
+				// Abort if we didn't have any Layer layer, or if there are additional ones also selected
+				if layer_path.is_none() || selected_frame_layers.next().is_some() {
+					return;
+				}
+				let layer_path = layer_path.unwrap();
+
+				let layer = self.document_legacy.layer(layer_path).expect("Clearing Layer image for invalid layer");
+				let previous_blob_url = match &layer.data {
+					LayerDataType::Layer(layer) => layer.as_blob_url(),
+					x => panic!("Cannot find blob url for layer type {}", LayerDataTypeDiscriminant::from(x)),
+				};
+
+				if let Some(url) = previous_blob_url {
+					responses.add(FrontendMessage::TriggerRevokeBlobUrl { url: url.clone() });
+				}
+				responses.add(DocumentOperation::ClearBlobURL { path: layer_path.into() });
+			}
+			GroupSelectedLayers => {
+				// TODO: Add code that changes the insert index of the new folder based on the selected layer
+				let mut new_folder_path = self.document_legacy.shallowest_common_folder(self.selected_layers()).unwrap_or(&[]).to_vec();
+
+				// Required for grouping parent folders with their own children
+				if !new_folder_path.is_empty() && self.selected_layers_contains(&new_folder_path) {
+					new_folder_path.remove(new_folder_path.len() - 1);
+				}
+
+				new_folder_path.push(generate_uuid());
+
+				if !self.check_selection_move(&new_folder_path, responses) {
+					return;
+				}
+
+				responses.add(PortfolioMessage::Copy { clipboard: Clipboard::Internal });
+				responses.add(DocumentMessage::DeleteSelectedLayers);
+				responses.add(DocumentOperation::CreateFolder {
+					path: new_folder_path.clone(),
+					insert_index: -1,
+				});
+				responses.add(DocumentMessage::ToggleLayerExpansion { layer_path: new_folder_path.clone() });
+				responses.add(PortfolioMessage::PasteIntoFolder {
+					clipboard: Clipboard::Internal,
+					folder_path: new_folder_path.clone(),
+					insert_index: -1,
 				});
 				responses.add(DocumentMessage::SetSelectedLayers {
 					replacement_selected_layers: vec![new_folder_path],
 				});
 			}
+			HatchFillSelectedLayers { options } => {
+				self.backup(responses);
+
+				let document_transform_inverse = self.document_legacy.root.transform.inverse();
+				let selected_layers = self.selected_layers().map(|path| path.to_vec()).collect::<Vec<_>>();
+				let mut new_layers = Vec::new();
+				for layer_path in selected_layers {
+					let Ok(layer) = self.document_legacy.layer(&layer_path) else { continue };
+					let Some(vector_data) = layer.as_vector_data() else { continue };
+					let color = match vector_data.style.fill() {
+						Fill::None => continue,
+						Fill::Solid(color) => *color,
+						Fill::Gradient(_) => Color::BLACK,
+					};
+					let Some((&layer_id, parent_path)) = layer_path.split_last() else { continue };
+					let Ok(to_document) = self.document_legacy.multiply_transforms(&layer_path).map(|transform| document_transform_inverse * transform) else {
+						continue;
+					};
+					let Ok(parent_to_document) = self.document_legacy.multiply_transforms(parent_path).map(|transform| document_transform_inverse * transform) else {
+						continue;
+					};
+
+					// Hatch in document space so the spacing and angle don't depend on the layer's transform
+					let subpaths = vector_data
+						.subpaths
+						.iter()
+						.map(|subpath| {
+							let mut subpath = subpath.clone();
+							subpath.apply_transform(to_document);
+							subpath
+						})
+						.collect::<Vec<_>>();
+					let mut lines = hatch_subpaths(&subpaths, options);
+					if lines.is_empty() {
+						continue;
+					}
+					lines.iter_mut().for_each(|line| line.apply_transform(parent_to_document.inverse()));
+
+					// Place the hatch lines directly above the filled layer
+					let insert_index = self
+						.document_legacy
+						.folder(parent_path)
+						.and_then(|folder| folder.position_of_layer(layer_id))
+						.map_or(-1, |index| index as isize + 1);
+					let mut new_path = parent_path.to_vec();
+					new_path.push(generate_uuid());
+					let name = format!("{} Hatch", layer.name.as_deref().filter(|name| !name.is_empty()).unwrap_or("Layer"));
+
+					graph_modification_utils::new_vector_layer_at(lines, new_path.clone(), insert_index, responses);
+					responses.add(GraphOperationMessage::FillSet {
+						layer: new_path.clone(),
+						fill: Fill::None,
+					});
+					responses.add(GraphOperationMessage::StrokeSet {
+						layer: new_path.clone(),
+						stroke: Stroke::new(Some(color), 1.),
+					});
+					responses.add(DocumentMessage::SetLayerName { layer_path: new_path.clone(), name });
+					new_layers.push(new_path);
+				}
+
+				if !new_layers.is_empty() {
+					responses.add(DocumentMessage::SetSelectedLayers {
+						replacement_selected_layers: new_layers,
+					});
+					responses.add(BroadcastEvent::DocumentIsDirty);
+				}
+			}
 			ImaginateClear { layer_path } => responses.add(InputFrameRasterizeRegionBelowLayer { layer_path }),
 			ImaginateGenerate { layer_path } => {
 				if let Some(message) = self.rasterize_region_below_layer(document_id, layer_path, preferences, persistent_data) {
@@ -501,25 +1576,124 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					responses.add(message);
 				}
 			}
+			InsertPlaceholderText { words } => {
+				let layers = self.selected_text_layers();
+				if layers.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for layer in layers {
+					let text = placeholder_text(words, PLACEHOLDER_TEXT_LINE_LENGTH);
+					responses.add(GraphOperationMessage::TextSet { layer, text });
+				}
+			}
+			JoinSelectedPaths => {
+				// Every open path in the selected shapes, in viewport space so the tolerance is the same at any zoom level
+				let mut open_paths = Vec::new();
+				for path in self.selected_layers() {
+					let Ok(layer) = self.document_legacy.layer(path) else { continue };
+					let Some(vector_data) = layer.as_vector_data().filter(|_| layer_text(layer).is_none()) else {
+						continue;
+					};
+					let transform = self.document_legacy.multiply_transforms(path).unwrap_or_default();
+					for (index, subpath) in vector_data.subpaths.iter().enumerate().filter(|(_, subpath)| !subpath.closed() && !subpath.is_empty()) {
+						let mut subpath = subpath.clone();
+						subpath.apply_transform(transform);
+						open_paths.push((path.to_vec(), transform, index, subpath));
+					}
+				}
+
+				// The two paths with the nearest ends are joined
+				let nearest = (0..open_paths.len())
+					.flat_map(|first| (first + 1..open_paths.len()).map(move |second| (first, second)))
+					.filter_map(|(first, second)| Some((first, second, nearest_ends(&open_paths[first].3, &open_paths[second].3)?.2)))
+					.min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+				let Some((first, second, _)) = nearest else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Nothing to join".to_string(),
+						description: "Joining needs at least two open paths among the selected shapes.".to_string(),
+					});
+					return;
+				};
+				let (first_layer, first_transform, first_index, first_path) = open_paths[first].clone();
+				let (second_layer, _, second_index, second_path) = open_paths[second].clone();
+				let Some(mut joined) = join_subpaths(&first_path, &second_path, PATH_JOIN_TOLERANCE) else {
+					return;
+				};
+				joined.apply_transform(first_transform.inverse());
+
+				let subpaths_of = |layer: &[LayerId]| {
+					self.document_legacy
+						.layer(layer)
+						.ok()
+						.and_then(|layer| layer.as_vector_data())
+						.map(|vector_data| vector_data.subpaths.clone())
+				};
+				let (Some(mut first_subpaths), Some(mut second_subpaths)) = (subpaths_of(&first_layer), subpaths_of(&second_layer)) else {
+					return;
+				};
+
+				self.backup(responses);
+				first_subpaths[first_index] = joined;
+				if first_layer == second_layer {
+					first_subpaths.remove(second_index);
+				} else {
+					second_subpaths.remove(second_index);
+
+					// The second shape is merged into the first one when it has no other paths left
+					if second_subpaths.is_empty() {
+						responses.add(DocumentOperation::DeleteLayer { path: second_layer.clone() });
+						responses.add(PropertiesPanelMessage::CheckSelectedWasDeleted { path: second_layer });
+					} else {
+						responses.add(GraphOperationMessage::Vector {
+							layer: second_layer,
+							modification: VectorDataModification::UpdateSubpaths { subpaths: second_subpaths },
+						});
+					}
+				}
+				responses.add(GraphOperationMessage::Vector {
+					layer: first_layer,
+					modification: VectorDataModification::UpdateSubpaths { subpaths: first_subpaths },
+				});
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
 			LayerChanged { affected_layer_path } => {
 				if let Ok(layer_entry) = self.layer_panel_entry(affected_layer_path.clone(), &render_data) {
 					responses.add(FrontendMessage::UpdateDocumentLayerDetails { data: layer_entry });
 				}
 				responses.add(PropertiesPanelMessage::CheckSelectedWasUpdated { path: affected_layer_path });
 				self.update_layer_tree_options_bar_widgets(responses, &render_data);
+				self.queue_document_thumbnail(responses);
+			}
+			LayerThumbnailRendered { job_id, layer_path, thumbnail } => {
+				// The layer has changed or been deleted since, so a fresher thumbnail is on its way or no longer needed
+				if !self.idle_work.finish_rendering(&layer_path, job_id) {
+					return;
+				}
+				let (Some(layer_metadata), Ok(layer)) = (self.layer_metadata.get(&layer_path), self.document_legacy.layer(&layer_path)) else {
+					return;
+				};
+				let data = LayerPanelEntry::with_thumbnail(layer_metadata, layer, layer_path.clone(), thumbnail.clone());
+				self.idle_work.store_thumbnail(layer_path, thumbnail);
+				responses.add(FrontendMessage::UpdateDocumentLayerDetails { data });
+			}
+			LinkSelectedLayerTransforms => {
+				let document = &self.document_legacy;
+				self.transform_links.retain(|layer| document.layer(layer).is_ok());
+				self.transform_links.link(self.selected_layers().map(|path| path.to_vec()).collect::<Vec<_>>());
 			}
 			MoveSelectedLayersTo {
 				folder_path,
 				insert_index,
 				reverse_index,
 			} => {
-				let selected_layers = self.selected_layers().collect::<Vec<_>>();
-
-				// Prevent trying to insert into self
-				if selected_layers.iter().any(|layer| folder_path.starts_with(layer)) {
+				if !self.check_selection_move(&folder_path, responses) {
 					return;
 				}
 
+				let selected_layers = self.selected_layers().collect::<Vec<_>>();
+
 				let insert_index = self.update_insert_index(&selected_layers, &folder_path, insert_index, reverse_index).unwrap();
 
 				responses.add(PortfolioMessage::Copy { clipboard: Clipboard::Internal });
@@ -530,6 +1704,7 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					insert_index,
 				});
 			}
+			MoveTransformLinks { from, to } => self.transform_links.move_layer(&from, &to),
 			NudgeSelectedLayers {
 				delta_x,
 				delta_y,
@@ -578,24 +1753,13 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				}
 				responses.add(BroadcastEvent::DocumentIsDirty);
 			}
+			PasteAtPointer => responses.add(FrontendMessage::TriggerPaste { mode: PasteMode::AtPointer }),
 			PasteImage { image, mouse } => {
 				let image_size = DVec2::new(image.width as f64, image.height as f64);
 
-				let Some(image_node_type) = crate::messages::portfolio::document::node_graph::resolve_document_node_type("Image") else {
-					warn!("Image node should be in registry");
-					return;
-				};
-				let Some(transform_node_type) = crate::messages::portfolio::document::node_graph::resolve_document_node_type("Transform") else {
-					warn!("Transform node should be in registry");
-					return;
-				};
-				let Some(downres_node_type) = crate::messages::portfolio::document::node_graph::resolve_document_node_type("Downres") else {
-					warn!("Downres node should be in registry");
-					return;
-				};
+				let Some(network) = image_layer_network(image) else { return };
 
 				let path = vec![generate_uuid()];
-				let mut network = NodeNetwork::default();
 
 				// Transform of parent folder
 				let to_parent_folder = self.document_legacy.generate_transform_across_scope(&path[..path.len() - 1], None).unwrap_or_default();
@@ -615,20 +1779,6 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 
 				responses.add(DocumentMessage::StartTransaction);
 
-				network.push_node(
-					image_node_type.to_document_node(
-						[graph_craft::document::NodeInput::value(
-							graph_craft::document::value::TaggedValue::ImageFrame(ImageFrame { image, transform: DAffine2::IDENTITY }),
-							false,
-						)],
-						graph_craft::document::DocumentNodeMetadata::position((8, 4)),
-					),
-					false,
-				);
-				network.push_node(transform_node_type.to_document_node_default_inputs([], Default::default()), true);
-				network.push_node(downres_node_type.to_document_node_default_inputs([], Default::default()), true);
-				network.push_output_node();
-
 				responses.add(DocumentOperation::AddFrame {
 					path: path.clone(),
 					insert_index: -1,
@@ -651,6 +1801,142 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				// Force chosen tool to be Select Tool after importing image.
 				responses.add(ToolMessage::ActivateTool { tool_type: ToolType::Select });
 			}
+			PasteInPlace => responses.add(FrontendMessage::TriggerPaste { mode: PasteMode::InPlace }),
+			PasteInto => responses.add(FrontendMessage::TriggerPaste { mode: PasteMode::IntoFolder }),
+			PerformIdleWork { deadline } => {
+				// The rest of a large document is attached before anything else, since its layers still need their thumbnails rendered afterwards
+				if self.progressive_loading.is_loading() {
+					while self.progressive_loading.is_loading() && now_milliseconds().map_or(false, |now| now < deadline) {
+						let layer_paths = self.progressive_loading.load_next(&mut self.document_legacy, &mut self.layer_metadata);
+						responses.add(DocumentMessage::QueueLayerThumbnails { layer_paths });
+					}
+					self.loading_progress_changed(responses);
+					responses.add(FrontendMessage::TriggerIdleWork);
+					return;
+				}
+
+				// While watching, the design tokens are exported again once the user pauses if the changes since the last export affected them
+				if let Some(format) = self.design_token_watch.filter(|_| self.idle_work.next_design_tokens()) {
+					let tokens = self.design_tokens();
+					if self.exported_design_tokens.as_ref() != Some(&tokens) {
+						self.download_design_tokens(tokens, format, responses);
+					}
+				}
+
+				// With threads to spare, everything waiting is handed to the render workers at once rather than spread over idle frames
+				if RenderWorkers::available() {
+					self.render_thumbnails_in_background(document_id, &persistent_data.render_workers);
+					return;
+				}
+
+				while now_milliseconds().map_or(false, |now| now < deadline) {
+					let Some(layer_path) = self.idle_work.next_thumbnail() else { break };
+					if let Ok(layer_entry) = self.layer_panel_entry(layer_path, &render_data) {
+						responses.add(FrontendMessage::UpdateDocumentLayerDetails { data: layer_entry });
+					}
+				}
+				if now_milliseconds().map_or(false, |now| now < deadline) && self.idle_work.next_document_thumbnail() {
+					let thumbnail = LayerPanelEntry::thumbnail(DAffine2::IDENTITY, &self.document_legacy.root, &render_data);
+					responses.add(FrontendMessage::UpdateDocumentThumbnail { document_id, thumbnail });
+				}
+
+				if self.idle_work.has_pending_work() {
+					responses.add(FrontendMessage::TriggerIdleWork);
+				}
+			}
+			PreviewBooleanOperation { operation } => {
+				self.clear_boolean_preview(responses);
+				let Some(operation) = operation else { return };
+
+				let (shapes, _) = self.boolean_operation_shapes();
+				if shapes.len() < 2 {
+					return;
+				}
+
+				// Large shapes are combined in the background so the pointer isn't held up, and leaving the button before they're done cancels the preview
+				if RenderWorkers::available() {
+					let job_id = persistent_data.render_workers.submit(Box::new(move |_| RenderOutput::BooleanPreview {
+						document_id,
+						result: combine_shapes(operation, shapes).ok(),
+					}));
+					self.boolean_preview.job = Some(job_id);
+				} else {
+					let points = shapes.iter().flat_map(|(subpaths, _)| subpaths).map(|subpath| subpath.len()).sum::<usize>();
+					if points <= BOOLEAN_PREVIEW_MAX_POINTS {
+						self.show_boolean_preview(combine_shapes(operation, shapes).ok(), responses);
+					}
+				}
+			}
+			ProceedWithCostlyOperation { operation } => {
+				self.costly_operation_confirmed = true;
+				responses.add_front(*operation);
+			}
+			ProjectSelectedLayersIsometric { plane } => {
+				self.backup(responses);
+				if let Some([min, max]) = self.document_legacy.combined_viewport_bounding_box(self.selected_layers(), &render_data) {
+					let center = (max + min) / 2.;
+					let bbox_trans = DAffine2::from_translation(-center);
+					// The projection is made in document space, so it still lines up with the isometric grid when the canvas is rotated or zoomed
+					let document_to_viewport = DAffine2::from_mat2(self.document_legacy.root.transform.matrix2);
+					let transform = document_to_viewport * plane.projection() * document_to_viewport.inverse();
+					for path in self.selected_layers() {
+						responses.add(GraphOperationMessage::TransformChange {
+							layer: path.to_vec(),
+							transform,
+							transform_in: TransformIn::Scope { scope: bbox_trans },
+							skip_rerender: false,
+						});
+					}
+					responses.add(BroadcastEvent::DocumentIsDirty);
+				}
+			}
+			QueueLayerThumbnails { layer_paths } => {
+				// Without a clock there's no telling when the user is idle, so render the thumbnails right away
+				if now_milliseconds().is_none() {
+					for affected_layer_path in layer_paths {
+						responses.add(LayerChanged { affected_layer_path });
+					}
+				} else {
+					// Show the layers immediately with whatever thumbnail they had before, and render fresh thumbnails once the user pauses
+					for layer_path in layer_paths {
+						let (Some(layer_metadata), Ok(layer)) = (self.layer_metadata.get(&layer_path), self.document_legacy.layer(&layer_path)) else {
+							continue;
+						};
+						let thumbnail = self.idle_work.thumbnail(&layer_path).unwrap_or_default().to_string();
+						let data = LayerPanelEntry::with_thumbnail(layer_metadata, layer, layer_path.clone(), thumbnail);
+						responses.add(FrontendMessage::UpdateDocumentLayerDetails { data });
+
+						self.idle_work.queue_thumbnail(layer_path);
+					}
+					self.idle_work.queue_document_thumbnail();
+					responses.add(FrontendMessage::TriggerIdleWork);
+				}
+			}
+			RadialArraySelectedLayers { count, angle } => {
+				let layers = self
+					.selected_layers()
+					.filter(|path| self.document_legacy.layer(path).ok().and_then(|layer| layer.as_vector_data()).is_some())
+					.map(|path| path.to_vec())
+					.collect::<Vec<_>>();
+				if layers.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for layer in layers {
+					responses.add(GraphOperationMessage::RadialArraySet { layer, count, angle });
+				}
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			RecordSelectionTransform { viewport_transform } => {
+				// Kept in document space so it repeats the same change after the canvas is zoomed or rotated
+				let document_to_viewport = DAffine2::from_mat2(self.document_legacy.root.transform.matrix2);
+				let transform = document_to_viewport.inverse() * viewport_transform * document_to_viewport;
+
+				if transform.is_finite() && transform != DAffine2::IDENTITY {
+					self.last_selection_transform = Some(transform);
+				}
+			}
 			Redo => {
 				responses.add(SelectToolMessage::Abort);
 				responses.add(DocumentHistoryForward);
@@ -665,12 +1951,45 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 			}
 			RenameLayer { layer_path, new_name } => responses.add(DocumentOperation::RenameLayer { layer_path, new_name }),
 			RenderDocument => {
-				responses.add(FrontendMessage::UpdateDocumentArtwork {
-					svg: self.document_legacy.render_root(&render_data),
-				});
+				// Move the layers of folders with an auto layout into place first, which renders the document again once they're moved
+				let auto_layout_changes = self.document_legacy.auto_layout_changes(&render_data);
+				if !auto_layout_changes.is_empty() {
+					for (path, translation) in auto_layout_changes {
+						let transform = DAffine2::from_translation(translation);
+						// Layers made by the node graph are moved by their transform node, or the graph would move them back when it's run again
+						if matches!(self.document_legacy.layer(&path).map(|layer| &layer.data), Ok(LayerDataType::Layer(_))) {
+							responses.add(GraphOperationMessage::TransformChange {
+								layer: path,
+								transform,
+								transform_in: TransformIn::Local,
+								skip_rerender: false,
+							});
+						} else {
+							let transform = transform.to_cols_array();
+							responses.add(DocumentOperation::TransformLayer { path, transform });
+						}
+					}
+					return;
+				}
+
+				let (svg_defs, changes) = self.document_legacy.render_root_changes(&render_data);
+
+				// Replace only the changed layers in the viewport, unless the whole artwork changed or is out of date
+				let whole_artwork = self.rendered_artwork_defs.as_ref() != Some(&svg_defs) || changes.len() > MAX_LAYER_RENDER_UPDATES || changes.iter().any(|(layer_path, _)| layer_path.is_empty());
+				if whole_artwork {
+					let svg = format!("{svg_defs}{}", self.document_legacy.canvas_root_svg());
+					let svg = if self.cmyk_preview { soft_proof_svg(&svg) } else { svg };
+					responses.add(FrontendMessage::UpdateDocumentArtwork { svg });
+					self.rendered_artwork_defs = Some(svg_defs);
+				} else {
+					for (layer_path, svg) in changes {
+						responses.add(FrontendMessage::UpdateDocumentLayerRender { layer_path, svg });
+					}
+				}
 				responses.add(ArtboardMessage::RenderArtboards);
 
-				let document_transform_scale = self.navigation_handler.snapped_scale();
+				// The scale from document units to CSS pixels in the viewport, which is less than the zoom on high-DPI displays
+				let document_transform_scale = self.navigation_handler.snapped_scale() / preferences.zoom_pixel_ratio(ipp);
 				let scale = 0.5 + ASYMPTOTIC_EFFECT + document_transform_scale * SCALE_EFFECT;
 				let viewport_size = ipp.viewport_bounds.size();
 				let viewport_mid = ipp.viewport_bounds.center();
@@ -698,6 +2017,170 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					origin: ruler_origin.into(),
 					spacing: ruler_spacing,
 					interval: ruler_interval,
+					flipped: self.navigation_handler.flipped,
+					decimal_separator: preferences.number_locale.decimal_separator(),
+				});
+
+				self.render_guide_grid(ipp, responses);
+			}
+			RenderFullDocument => {
+				self.rendered_artwork_defs = None;
+				responses.add(RenderDocument);
+			}
+			RepairDocument => {
+				// The layers still waiting to be attached are part of the document the log replays
+				self.finish_loading(responses);
+
+				let replayed_operations = self.operation_log.len();
+				let (diff, failed_operations) = match self.operation_log.replay() {
+					Some((mut repaired, failed_operations)) => {
+						// The view and the node inputs added when the document was opened aren't part of the log
+						repaired.root.transform = self.document_legacy.root.transform;
+						add_missing_node_inputs(&mut repaired.root.data);
+
+						let diff = self.document_legacy.diff(&repaired);
+						if !diff.is_empty() {
+							self.backup(responses);
+							self.document_legacy = repaired;
+							for layer in &diff.layers {
+								match layer.change {
+									LayerChange::Added => {
+										let mut added = vec![layer.path.clone()];
+										while let Some(path) = added.pop() {
+											added.extend(self.document_legacy.folder_children_paths(&path));
+											self.layer_metadata.entry(path).or_insert_with(|| LayerMetadata::new(false));
+										}
+									}
+									LayerChange::Removed => self.layer_metadata.retain(|path, _| !path.starts_with(&layer.path)),
+									LayerChange::Changed(_) => responses.add(LayerChanged {
+										affected_layer_path: layer.path.clone(),
+									}),
+								}
+							}
+							responses.add(BroadcastEvent::SelectionChanged);
+							responses.add(DocumentStructureChanged);
+							responses.add(NodeGraphMessage::SendGraph { should_rerender: false });
+							responses.add(DirtyRenderDocument);
+							responses.add(BroadcastEvent::DocumentIsDirty);
+						}
+						(Some(diff), failed_operations)
+					}
+					None => (None, 0),
+				};
+
+				let dialog = simple_dialogs::DocumentRepairDialog {
+					document_name: self.name.clone(),
+					diff,
+					replayed_operations,
+					failed_operations,
+				};
+				dialog.send_layout(responses, LayoutTarget::DialogDetails);
+				responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
+			}
+			RepeatLastExport => match self.export_history.latest() {
+				Some(record) => responses.add(record.export_message()),
+				None => responses.add(DialogMessage::RequestExportDialog),
+			},
+			ReplaceColor { from, to } => {
+				// Colors are compared as they're shown in the color inputs, so they match despite rounding
+				let matches = |color: &Color| color.to_rgba8_srgb() == from.to_rgba8_srgb();
+
+				let mut fills = Vec::new();
+				let mut strokes = Vec::new();
+				for path in self.all_layers() {
+					let Some(style) = self.document_legacy.layer(path).ok().and_then(|layer| layer.style().ok()) else {
+						continue;
+					};
+					if matches!(style.fill(), Fill::Solid(color) if matches(color)) {
+						fills.push(path.to_vec());
+					}
+					if style.stroke().and_then(|stroke| stroke.color).map_or(false, |color| matches(&color)) {
+						strokes.push(path.to_vec());
+					}
+				}
+				if fills.is_empty() && strokes.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for layer in fills {
+					responses.add(GraphOperationMessage::FillSet { layer, fill: Fill::Solid(to) });
+				}
+				for layer in strokes {
+					responses.add(GraphOperationMessage::StrokeColorSet { layer, color: Some(to) });
+				}
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			ReplaceFonts { replacements } => {
+				let replacements = replacements.into_iter().filter(|(old, new)| old != new).collect::<HashMap<_, _>>();
+
+				// Look up each layer's original font so that replacements never chain into each other
+				let layers = self
+					.all_layers()
+					.filter_map(|path| {
+						let font = self.document_legacy.layer(path).ok().and_then(layer_font)?;
+						replacements.get(font).map(|new_font| (path.to_vec(), new_font.clone()))
+					})
+					.collect::<Vec<_>>();
+				if layers.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for (layer, font) in layers {
+					responses.add(GraphOperationMessage::FontSet { layer, font });
+				}
+				for font in replacements.into_values().collect::<HashSet<_>>() {
+					responses.add(FrontendMessage::TriggerFontLoad { font, is_default: false });
+				}
+			}
+			ReplaceSelectedLayersGeometry => {
+				let Some(source) = &self.geometry_source else { return };
+				let source_bounds = nonzero_subpath_bounds(source);
+
+				let mut replacements = Vec::new();
+				for path in self.selected_layers() {
+					let Ok(layer) = self.document_legacy.layer(path) else { continue };
+					// Only shapes drawn from their own path can have their path replaced, rather than those drawn by generators like "Rectangle Generator"
+					let drawn_from_path = layer.as_layer_network().map_or(false, |network| network.nodes.values().any(|node| node.name == "Path Generator"));
+					let Some(vector_data) = layer.as_vector_data().filter(|_| drawn_from_path && layer_text(layer).is_none()) else {
+						continue;
+					};
+
+					let fit = fit_within_bounds(source_bounds, nonzero_subpath_bounds(&vector_data.subpaths));
+					let subpaths = source
+						.iter()
+						.map(|subpath| {
+							let mut subpath = subpath.clone();
+							subpath.apply_transform(fit);
+							subpath
+						})
+						.collect();
+					replacements.push((path.to_vec(), subpaths));
+				}
+				if replacements.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for (layer, subpaths) in replacements {
+					responses.add(GraphOperationMessage::Vector {
+						layer,
+						modification: VectorDataModification::UpdateSubpaths { subpaths },
+					});
+				}
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			RevealLayer { layer_path } => {
+				if let Ok(Some(bounds)) = self.document_legacy.viewport_bounding_box(&layer_path, &render_data) {
+					responses.add(NavigationMessage::FitViewportToBounds {
+						bounds,
+						padding_scale_factor: Some(VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR),
+						prevent_zoom_past_100: true,
+					});
+				}
+				responses.add(DocumentMessage::SetSelectedLayers {
+					replacement_selected_layers: vec![layer_path],
 				});
 			}
 			RollbackTransaction => {
@@ -705,21 +2188,97 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				responses.extend([RenderDocument.into(), DocumentStructureChanged.into()]);
 			}
 			SaveDocument => {
+				if self.read_only {
+					let dialog = simple_dialogs::DocumentLockedDialog {
+						document_name: self.name.clone(),
+						document_id,
+					};
+					dialog.send_layout(responses, LayoutTarget::DialogDetails);
+					responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
+					return;
+				}
+
 				self.set_save_state(true);
-				responses.add(PortfolioMessage::AutoSaveActiveDocument);
 				// Update the save status of the just saved document
 				responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 
-				let name = match self.name.ends_with(FILE_SAVE_SUFFIX) {
-					true => self.name.clone(),
-					false => self.name.clone() + FILE_SAVE_SUFFIX,
-				};
-				responses.add(FrontendMessage::TriggerDownloadTextFile {
-					document: self.serialize_document(),
-					name,
-				})
+				// The file is downloaded once JS has rasterized its thumbnail, or right away if there's no artwork to show in one
+				let old_transforms = self.remove_document_transform();
+				let bounds = self.all_layer_bounds(&render_data).filter(|[min, max]| (*max - *min).min_element() > 0.);
+				let rendered = bounds.map(|[min, max]| {
+					let size = max - min;
+					let transform = (DAffine2::from_translation(min) * DAffine2::from_scale(size)).inverse();
+					let svg = self.render_document(size, transform, true, false, false, persistent_data, DocumentRenderMode::Root);
+					(svg, size)
+				});
+				self.restore_document_transform(old_transforms);
+
+				match rendered {
+					Some((svg, size)) => {
+						let size = (size * (FILE_THUMBNAIL_SIZE / size.max_element())).round().max(DVec2::ONE);
+						responses.add(FrontendMessage::TriggerRasterizeFileThumbnail { document_id, svg, size });
+					}
+					None => responses.add(DocumentMessage::DownloadDocument { thumbnail: None }),
+				}
+			}
+			SaveSelectionAsShapePreset => {
+				let shape = self.selected_layers().find_map(|path| {
+					let layer = self.document_legacy.layer(path).ok()?;
+					let vector_data = layer.as_vector_data().filter(|_| layer_text(layer).is_none())?;
+					Some((path, layer, vector_data))
+				});
+				let Some((path, layer, vector_data)) = shape else { return };
+
+				// The size is measured in document space, so it doesn't depend on the zoom the preset was saved at
+				let transform = self.document_legacy.root.transform.inverse() * self.document_legacy.multiply_transforms(path).unwrap_or_default();
+				let size = layer.aabb_for_transform(transform, &render_data).map(|[min, max]| max - min);
+				let name = layer.name.clone().filter(|name| !name.is_empty()).unwrap_or_else(|| "Shape Preset".to_string());
+
+				responses.add(PreferencesMessage::SaveShapePreset {
+					preset: ShapePreset::new(name, &vector_data.style, size),
+				});
+			}
+			ScaleDocument { factor } => {
+				let Ok(root) = self.document_legacy.root.as_folder() else { return };
+				let layers = root.layer_ids.clone();
+				self.backup(responses);
+
+				for id in layers {
+					responses.add(GraphOperationMessage::TransformChange {
+						layer: vec![id],
+						transform: DAffine2::from_scale(DVec2::splat(factor)),
+						transform_in: TransformIn::Local,
+						skip_rerender: false,
+					});
+				}
+
+				// The artboards are scaled about the same origin, so they keep framing the same artwork
+				let artboards = &self.artboard_message_handler;
+				for &artboard in &artboards.artboard_ids {
+					let Ok(layer) = artboards.artboards_document.layer(&[artboard]) else { continue };
+					let Some([min, max]) = layer.aabb_for_transform(layer.transform, &render_data) else { continue };
+					let (position, size) = ((min * factor).round(), ((max - min) * factor).round());
+
+					responses.add(ArtboardMessage::ResizeArtboard {
+						artboard,
+						position: position.into(),
+						size: size.into(),
+					});
+					responses.add(GraphOperationMessage::ResizeArtboard {
+						id: artboard,
+						location: position.as_ivec2(),
+						dimensions: size.as_ivec2(),
+					});
+				}
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			SearchOutline { query } => {
+				self.finish_loading(responses);
+				let entries = document_outline(&self.document_legacy, &query, &render_data);
+				responses.add(FrontendMessage::UpdateDocumentOutline { query, entries });
 			}
 			SelectAllLayers => {
+				self.finish_loading(responses);
 				let all = self.all_layers().map(|path| path.to_vec()).collect();
 				responses.add_front(SetSelectedLayers { replacement_selected_layers: all });
 			}
@@ -784,6 +2343,48 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					responses.add(DocumentOperation::SetLayerBlendMode { path: path.to_vec(), blend_mode });
 				}
 			}
+			SetFolderDefaultStyle { path, default_style } => {
+				if self.document_legacy.folder(&path).map_or(true, |folder| folder.default_style == default_style) {
+					return;
+				}
+
+				self.backup(responses);
+				responses.add(DocumentOperation::SetFolderDefaultStyle { path: path.clone(), default_style });
+				// Restyled once the new default style is set
+				let layers = self.document_legacy.layers_inheriting_style(&path);
+				if !layers.is_empty() {
+					responses.add(ApplyInheritedStyle { layers });
+				}
+				responses.add(PropertiesPanelMessage::ResendActiveProperties);
+			}
+			SetFolderIsolationForSelectedLayers { isolated } => {
+				self.backup(responses);
+				for path in self.selected_layers_with_type(LayerDataTypeDiscriminant::Folder).map(|path| path.to_vec()) {
+					responses.add(DocumentOperation::SetFolderIsolation { path, isolated });
+				}
+			}
+			SetFolderKnockoutForSelectedLayers { knockout } => {
+				self.backup(responses);
+				for path in self.selected_layers_with_type(LayerDataTypeDiscriminant::Folder).map(|path| path.to_vec()) {
+					responses.add(DocumentOperation::SetFolderKnockout { path, knockout });
+				}
+			}
+			SetGeometrySource => {
+				let source = self
+					.selected_layers()
+					.filter_map(|path| self.document_legacy.layer(path).ok())
+					.find_map(|layer| layer.as_vector_data().filter(|vector_data| !vector_data.subpaths.is_empty()))
+					.map(|vector_data| vector_data.subpaths.clone());
+				if source.is_some() {
+					self.geometry_source = source;
+				}
+			}
+			SetGuideGrid { guide_grid } => {
+				self.guide_grid = guide_grid;
+				self.queue_design_tokens(responses);
+				self.render_guide_grid(ipp, responses);
+				self.update_document_widgets(responses);
+			}
 			SetImageBlobUrl {
 				layer_path,
 				blob_url,
@@ -813,6 +2414,29 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					message: DocumentOperation::SetLayerBlobUrl { layer_path, blob_url, resolution }.into(),
 				});
 			}
+			SetInheritStyleForSelectedLayers { inherit_style } => {
+				let layers = self
+					.selected_layers()
+					.filter(|path| {
+						let layer = self.document_legacy.layer(path).ok();
+						layer.map_or(false, |layer| layer.as_folder().is_err() && layer.inherit_style != inherit_style)
+					})
+					.map(|path| path.to_vec())
+					.collect::<Vec<_>>();
+				if layers.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for path in &layers {
+					responses.add(DocumentOperation::SetLayerInheritStyle { path: path.clone(), inherit_style });
+				}
+				// Detached layers keep the style they have until it's changed on them
+				if inherit_style {
+					responses.add(ApplyInheritedStyle { layers });
+				}
+				responses.add(PropertiesPanelMessage::ResendActiveProperties);
+			}
 			SetLayerExpansion { layer_path, set_expanded } => {
 				self.layer_metadata_mut(&layer_path).expanded = set_expanded;
 				responses.add(DocumentStructureChanged);
@@ -841,6 +2465,24 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				responses.add(OverlaysMessage::ClearAllOverlays);
 				responses.add(OverlaysMessage::Rerender);
 			}
+			SetPrototypeLink { layer, link } => {
+				let (document, artboards) = (&self.document_legacy, &self.artboard_message_handler.artboard_ids);
+				self.prototype_links.retain(|layer| document.layer(layer).is_ok(), |artboard| artboards.contains(&artboard));
+				self.prototype_links.set(layer, link);
+				responses.add(PropertiesPanelMessage::ResendActiveProperties);
+			}
+			SetRasterResolution { raster_resolution } => {
+				self.raster_resolution = raster_resolution.clamp(RASTER_RESOLUTION_MIN, RASTER_RESOLUTION_MAX);
+
+				// Rerun the graphs of the layers that follow the document's resolution so their effects are rasterized again
+				for layer_path in self.all_layers() {
+					let Ok(layer) = self.document_legacy.layer(layer_path) else { continue };
+					if matches!(layer.data, LayerDataType::Layer(_)) && layer.raster_resolution.is_none() {
+						responses.add(DocumentMessage::InputFrameRasterizeRegionBelowLayer { layer_path: layer_path.to_vec() });
+					}
+				}
+				responses.add(PropertiesPanelMessage::ResendActiveProperties);
+			}
 			SetSelectedLayers { replacement_selected_layers } => {
 				let selected = self.layer_metadata.iter_mut().filter(|(_, layer_metadata)| layer_metadata.selected);
 				selected.for_each(|(path, layer_metadata)| {
@@ -855,6 +2497,8 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				snapping_enabled,
 				bounding_box_snapping,
 				node_snapping,
+				grid_snapping,
+				grid_spacing,
 			} => {
 				if let Some(state) = snapping_enabled {
 					self.snapping_state.snapping_enabled = state
@@ -865,12 +2509,135 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				if let Some(state) = node_snapping {
 					self.snapping_state.node_snapping = state
 				};
+				if let Some(state) = grid_snapping {
+					self.snapping_state.grid_snapping = state
+				}
+				if let Some(spacing) = grid_spacing {
+					self.snapping_state.grid_spacing = spacing
+				}
+				self.update_document_widgets(responses);
 			}
 			SetViewMode { view_mode } => {
 				self.view_mode = view_mode;
 				responses.add_front(DocumentMessage::DirtyRenderDocument);
 			}
 			StartTransaction => self.backup(responses),
+			SwapSelectedFillAndStroke => {
+				let layers: Vec<_> = self.selected_layers().filter(|path| !self.is_raster_layer(path)).map(|path| path.to_vec()).collect();
+				if layers.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for layer in layers {
+					responses.add(GraphOperationMessage::SwapFillAndStroke { layer });
+				}
+			}
+			SymmetrizeSelectedLayers { flip_axis } => {
+				let Some([min, max]) = self.document_legacy.combined_viewport_bounding_box(self.selected_layers(), &render_data) else {
+					return;
+				};
+				let axis = match flip_axis {
+					FlipAxis::X => (min.x + max.x) / 2.,
+					FlipAxis::Y => (min.y + max.y) / 2.,
+				};
+				let tolerance = (max - min).max_element() * SYMMETRY_TOLERANCE;
+
+				// Anchors are only paired with anchors and handles with handles, and the anchors are moved first since moving a handle can depend on its anchor
+				let mut anchors = Vec::new();
+				let mut handles = Vec::new();
+				for path in self.selected_layers() {
+					let Ok(layer) = self.document_legacy.layer(path) else { continue };
+					let Some(vector_data) = layer.as_vector_data().filter(|_| layer_text(layer).is_none()) else {
+						continue;
+					};
+					let transform = self.document_legacy.multiply_transforms(path).unwrap_or_default();
+
+					for group in vector_data.manipulator_groups() {
+						for manipulator_type in [SelectedType::Anchor, SelectedType::InHandle, SelectedType::OutHandle] {
+							let Some(position) = manipulator_type.get_position(group) else { continue };
+							let point = (path.to_vec(), transform, ManipulatorPointId::new(group.id, manipulator_type), transform.transform_point2(position));
+							match manipulator_type {
+								SelectedType::Anchor => anchors.push(point),
+								_ => handles.push(point),
+							}
+						}
+					}
+				}
+
+				let moved = [anchors, handles]
+					.into_iter()
+					.flat_map(|points| {
+						let positions = points.iter().map(|&(_, _, _, position)| position).collect::<Vec<_>>();
+						symmetrize(&positions, &flip_axis, axis, tolerance).into_iter().zip(points)
+					})
+					.filter_map(|(position, (layer, transform, point, _))| Some((layer, point, transform.inverse().transform_point2(position?))))
+					.collect::<Vec<_>>();
+				if moved.is_empty() {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "No symmetry found".to_string(),
+						description: "None of the points in the selected shapes are close enough to a mirror image of another point to be made symmetric.".to_string(),
+					});
+					return;
+				}
+
+				self.backup(responses);
+				for (layer, point, position) in moved {
+					responses.add(GraphOperationMessage::Vector {
+						layer,
+						modification: VectorDataModification::SetManipulatorPosition { point, position },
+					});
+				}
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			TaperSelectedStrokes { profile } => {
+				let layers = self
+					.selected_layers()
+					.filter(|path| self.document_legacy.layer(path).ok().and_then(|layer| layer.as_vector_data()).is_some())
+					.map(|path| path.to_vec())
+					.collect::<Vec<_>>();
+				if layers.is_empty() {
+					return;
+				}
+
+				self.backup(responses);
+				for layer in layers {
+					responses.add(GraphOperationMessage::WidthProfileSet { layer, profile: profile.clone() });
+				}
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			TidySelectedLayers => {
+				let (paths, boxes): (Vec<_>, Vec<_>) = self
+					.selected_layers()
+					.filter_map(|path| self.document_legacy.viewport_bounding_box(path, &render_data).ok()?.map(|bounds| (path.to_vec(), bounds)))
+					.unzip();
+				if boxes.len() < 2 {
+					return;
+				}
+
+				let Some(translations) = tidy(&boxes) else {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Nothing to tidy".to_string(),
+						description: "The selected layers overlap each other instead of sitting in rows and columns.".to_string(),
+					});
+					return;
+				};
+
+				self.backup(responses);
+				for (layer, translation) in paths.into_iter().zip(translations) {
+					responses.add(GraphOperationMessage::TransformChange {
+						layer,
+						transform: DAffine2::from_translation(translation),
+						transform_in: TransformIn::Viewport,
+						skip_rerender: false,
+					});
+				}
+				responses.add(BroadcastEvent::DocumentIsDirty);
+			}
+			ToggleCmykPreview => {
+				self.cmyk_preview = !self.cmyk_preview;
+				responses.add(RenderFullDocument);
+			}
 			ToggleLayerExpansion { layer_path } => {
 				self.layer_metadata_mut(&layer_path).expanded ^= true;
 				responses.add(DocumentStructureChanged);
@@ -883,6 +2650,49 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 					responses.add(BroadcastEvent::DocumentIsDirty);
 				}
 			}
+			ToggleSelectedLayersAspectLock => {
+				// Lock every selected layer unless they are all locked already, in which case unlock them
+				let aspect_locked = self.layer_metadata.values().any(|data| data.selected && !data.aspect_locked);
+				for data in self.layer_metadata.values_mut().filter(|data| data.selected) {
+					data.aspect_locked = aspect_locked;
+				}
+				responses.add(DocumentStructureChanged);
+			}
+			TransformSelectedLayersAgain => {
+				let Some(transform) = self.last_selection_transform else { return };
+				let selected_layers = self.selected_layers().map(|path| path.to_vec()).collect::<Vec<_>>();
+				if selected_layers.is_empty() {
+					return;
+				}
+
+				let document_to_viewport = DAffine2::from_mat2(self.document_legacy.root.transform.matrix2);
+				let viewport_transform = document_to_viewport * transform * document_to_viewport.inverse();
+
+				responses.add(StartTransaction);
+				{
+					let selected = selected_layers.iter().collect::<Vec<_>>();
+					let mut original_transforms = OriginalTransforms::default();
+					let mut pivot = DVec2::ZERO;
+					let mut selected = Selected::new(&mut original_transforms, &mut pivot, &selected, responses, &self.document_legacy, None, &ToolType::Select);
+
+					*selected.pivot = selected.mean_average_of_pivots(&render_data);
+					selected.update_transforms(viewport_transform);
+				}
+				for layer_path in selected_layers {
+					responses.add(InputFrameRasterizeRegionBelowLayer { layer_path });
+				}
+				responses.add(CommitTransaction);
+			}
+			TranslateSelectedLayers { delta } => {
+				for path in self.selected_layers().map(|path| path.to_vec()) {
+					responses.add(GraphOperationMessage::TransformChange {
+						layer: path,
+						transform: DAffine2::from_translation(delta),
+						transform_in: TransformIn::Viewport,
+						skip_rerender: false,
+					});
+				}
+			}
 			Undo => {
 				self.undo_in_progress = true;
 				responses.add(BroadcastEvent::ToolAbort);
@@ -926,14 +2736,45 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				}
 				responses.add(DocumentMessage::CommitTransaction);
 			}
-			UpdateLayerMetadata { layer_path, layer_metadata } => {
-				self.layer_metadata.insert(layer_path, layer_metadata);
+			UnlinkSelectedLayerTransforms => {
+				let selected = self.selected_layers().map(|path| path.to_vec()).collect::<Vec<_>>();
+				self.transform_links.unlink(&selected);
+			}
+			UpdateLayerMetadata { layer_path, layer_metadata } => {
+				self.layer_metadata.insert(layer_path, layer_metadata);
+			}
+			UpdateTextStyle { text_style } => {
+				if self.text_style(text_style.id).is_none() {
+					return;
+				}
+
+				self.backup(responses);
+				for layer in self.layers_with_text_style(text_style.id) {
+					responses.add(GraphOperationMessage::TextStyleSet {
+						layer,
+						font: text_style.font.clone(),
+						font_size: text_style.font_size,
+					});
+				}
+				responses.add(FrontendMessage::TriggerFontLoad {
+					font: text_style.font.clone(),
+					is_default: false,
+				});
+				responses.add(DocumentOperation::SetTextStyle { text_style });
+				responses.add(PropertiesPanelMessage::ResendActiveProperties);
+			}
+			WatchDesignTokens { format } => {
+				self.design_token_watch = format;
+				self.exported_design_tokens = None;
+				if let Some(format) = format {
+					responses.add(ExportDesignTokens { format });
+				}
 			}
 			ZoomCanvasTo100Percent => {
-				responses.add_front(NavigationMessage::SetCanvasZoom { zoom_factor: 1. });
+				responses.add_front(NavigationMessage::ZoomCanvasTo { zoom_factor: 1. });
 			}
 			ZoomCanvasTo200Percent => {
-				responses.add_front(NavigationMessage::SetCanvasZoom { zoom_factor: 2. });
+				responses.add_front(NavigationMessage::ZoomCanvasTo { zoom_factor: 2. });
 			}
 			ZoomCanvasToFitAll => {
 				if let Some(bounds) = self.document_bounds(&render_data) {
@@ -948,26 +2789,43 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 	}
 
 	fn actions(&self) -> ActionList {
+		// While presenting, only the presentation can be stepped through or left, so the document can't be changed by accident
+		if self.presentation_handler.is_presenting() {
+			return self.presentation_handler.actions();
+		}
+
 		let mut common = actions!(DocumentMessageDiscriminant;
 			Undo,
 			Redo,
 			SelectAllLayers,
 			DeselectAllLayers,
 			RenderDocument,
+			RenderFullDocument,
 			ExportDocument,
+			RepeatLastExport,
 			SaveDocument,
 			SetSnapping,
+			ToggleCmykPreview,
 			DebugPrintDocument,
 			ZoomCanvasToFitAll,
 			ZoomCanvasTo100Percent,
 			ZoomCanvasTo200Percent,
 			CreateEmptyFolder,
+			PasteInPlace,
+			PasteInto,
+			PasteAtPointer,
 		);
 
 		if self.layer_metadata.values().any(|data| data.selected) {
 			let select = actions!(DocumentMessageDiscriminant;
+				BooleanOperation,
+				CopySelectedLayersAsPng,
+				FlattenSelectedLayerEffects,
+				InsertPlaceholderText,
 				DeleteSelectedLayers,
 				DuplicateSelectedLayers,
+				DuplicateSelectedLayersWithOffset,
+				TransformSelectedLayersAgain,
 				NudgeSelectedLayers,
 				SelectedLayersLower,
 				SelectedLayersLowerToBack,
@@ -975,16 +2833,86 @@ impl MessageHandler<DocumentMessage, (u64, &InputPreprocessorMessageHandler, &Pe
 				SelectedLayersRaiseToFront,
 				GroupSelectedLayers,
 				UngroupSelectedLayers,
+				ToggleSelectedLayersAspectLock,
+				SwapSelectedFillAndStroke,
+				ClearSelectedFill,
+				ClearSelectedStroke,
+				TidySelectedLayers,
+				LinkSelectedLayerTransforms,
+				UnlinkSelectedLayerTransforms,
+				CloseSelectedPaths,
+				JoinSelectedPaths,
+				SetGeometrySource,
+				ReplaceSelectedLayersGeometry,
 			);
 			common.extend(select);
 		}
 		common.extend(self.navigation_handler.actions());
 		common.extend(self.node_graph_handler.actions());
+		common.extend(self.presentation_handler.actions());
 		common
 	}
 }
 
 impl DocumentMessageHandler {
+	/// Checks that the selected layers can be moved into the folder, before any are removed from where they are, telling the user why not.
+	fn check_selection_move(&self, folder_path: &[LayerId], responses: &mut VecDeque<Message>) -> bool {
+		let Err(error) = self.selected_layers().try_for_each(|layer| self.document_legacy.check_move(layer, folder_path)) else {
+			return true;
+		};
+
+		let description = match error {
+			DocumentError::MoveIntoItself => "A folder can't be moved into itself or into a folder it contains.".to_string(),
+			DocumentError::NestingTooDeep(depth) => format!("Folders can't be nested more than {depth} deep. The limit can be changed in the preferences."),
+			error => format!("{error:?}"),
+		};
+		responses.add(DialogMessage::DisplayDialogError {
+			title: "Can't move layers there".to_string(),
+			description,
+		});
+		false
+	}
+
+	/// Warns that CSS or JS targeting layers by the IDs of the previous export tagging layers with their names needs updating, if layers were renamed since.
+	fn report_broken_element_ids(&mut self, responses: &mut VecDeque<Message>) {
+		let element_ids = self.document_legacy.exported_element_ids().into_iter().filter_map(|(path, id)| Some((*path.last()?, id))).collect();
+		let broken = self.export_history.record_element_ids(element_ids);
+		if broken.is_empty() {
+			return;
+		}
+
+		let mut description = "Layers were renamed, deleted, or hidden since the previous export, so anything selecting these IDs in CSS or JS needs updating:".to_string();
+		for broken in broken.iter().take(MAX_REPORTED_ELEMENT_IDS) {
+			match &broken.current {
+				Some(current) => description.push_str(&format!("\n#{} is now #{current}", broken.previous)),
+				None => description.push_str(&format!("\n#{} is no longer exported", broken.previous)),
+			}
+		}
+		if broken.len() > MAX_REPORTED_ELEMENT_IDS {
+			description.push_str(&format!("\n…and {} more", broken.len() - MAX_REPORTED_ELEMENT_IDS));
+		}
+		responses.add(DialogMessage::DisplayDialogError {
+			title: "Exported layer IDs changed".to_string(),
+			description,
+		});
+	}
+
+	/// Sets the fill or stroke of the selected shapes to none, leaving the rest of their stroke's settings for when it's given a color again.
+	fn clear_selected_style(&mut self, slot: ColorSlot, responses: &mut VecDeque<Message>) {
+		let layers: Vec<_> = self.selected_layers().filter(|path| !self.is_raster_layer(path)).map(|path| path.to_vec()).collect();
+		if layers.is_empty() {
+			return;
+		}
+
+		self.backup(responses);
+		for layer in layers {
+			match slot {
+				ColorSlot::Fill => responses.add(GraphOperationMessage::FillSet { layer, fill: Fill::None }),
+				ColorSlot::Stroke => responses.add(GraphOperationMessage::StrokeColorSet { layer, color: None }),
+			}
+		}
+	}
+
 	pub fn rasterize_region_below_layer(&mut self, document_id: u64, layer_path: Vec<LayerId>, _preferences: &PreferencesMessageHandler, persistent_data: &PersistentData) -> Option<Message> {
 		// Prepare the node graph input image
 
@@ -1003,9 +2931,10 @@ impl DocumentMessageHandler {
 
 			// Calculate the size of the region to be exported and generate an SVG of the artwork below this layer within that region
 			let transform = self.document_legacy.multiply_transforms(&layer_path).unwrap();
-			let size = DVec2::new(transform.transform_vector2(DVec2::new(1., 0.)).length(), transform.transform_vector2(DVec2::new(0., 1.)).length());
+			let resolution = self.document_legacy.layer(&layer_path).ok().and_then(|layer| layer.raster_resolution).unwrap_or(self.raster_resolution);
+			let size = DVec2::new(transform.transform_vector2(DVec2::new(1., 0.)).length(), transform.transform_vector2(DVec2::new(0., 1.)).length()) * resolution;
 			// TODO: Test if this would be better to have a transparent background
-			let svg = self.render_document(size, transform.inverse(), false, persistent_data, DocumentRenderMode::OnlyBelowLayerInFolder(&layer_path));
+			let svg = self.render_document(size, transform.inverse(), false, false, false, persistent_data, DocumentRenderMode::OnlyBelowLayerInFolder(&layer_path));
 
 			self.restore_document_transform(old_transforms);
 
@@ -1047,52 +2976,196 @@ impl DocumentMessageHandler {
 		DocumentLegacy::mark_children_as_dirty(&mut self.artboard_message_handler.artboards_document.root);
 	}
 
-	pub fn render_document(&mut self, size: DVec2, transform: DAffine2, transparent_background: bool, persistent_data: &PersistentData, render_mode: DocumentRenderMode) -> String {
+	#[allow(clippy::too_many_arguments)]
+	pub fn render_document(
+		&mut self,
+		size: DVec2,
+		transform: DAffine2,
+		transparent_background: bool,
+		layer_names: bool,
+		embed_fonts: bool,
+		persistent_data: &PersistentData,
+		render_mode: DocumentRenderMode,
+	) -> String {
 		// Render the document SVG code
 
-		let render_data = RenderData::new(&persistent_data.font_cache, ViewMode::Normal, None);
+		let element_ids = SvgElementIds::default();
+		let mut render_data = RenderData::new(&persistent_data.font_cache, ViewMode::Normal, None);
+		if layer_names {
+			render_data = render_data.with_element_ids(&element_ids);
+		}
+		if embed_fonts {
+			render_data = render_data.with_live_text(true);
+		}
+		// The artwork below a layer is rendered as it appears on the canvas, while the other modes produce exported files
+		if !matches!(render_mode, DocumentRenderMode::OnlyBelowLayerInFolder(_)) {
+			render_data = render_data.for_export();
+		}
 
 		let (artwork, outside) = match render_mode {
 			DocumentRenderMode::Root => (self.document_legacy.render_root(&render_data), None),
 			DocumentRenderMode::OnlyBelowLayerInFolder(below_layer_path) => (self.document_legacy.render_layers_below(below_layer_path, &render_data).unwrap(), None),
 			DocumentRenderMode::LayerCutout(layer_path, background) => (self.document_legacy.render_layer(layer_path, &render_data).unwrap(), Some(background)),
+			DocumentRenderMode::Layers(layer_paths) => {
+				let artwork = layer_paths
+					.iter()
+					.filter_map(|layer_path| {
+						let folder_transform = self.document_legacy.multiply_transforms(&layer_path[..layer_path.len() - 1]).ok()?;
+						let layer = self.document_legacy.render_layer(layer_path, &render_data)?;
+						let matrix = folder_transform.to_cols_array().map(|entry| entry.to_string()).join(",");
+						Some(format!(r#"<g transform="matrix({matrix})">{layer}</g>"#))
+					})
+					.collect();
+				(artwork, None)
+			}
 		};
 		let artboards = match transparent_background {
 			false => self.artboard_message_handler.artboards_document.render_root(&render_data),
 			true => "".into(),
 		};
-		let outside_artboards_color = outside.map_or_else(
-			|| if self.artboard_message_handler.artboard_ids.is_empty() { "ffffff" } else { "222222" }.to_string(),
-			|col| col.rgba_hex(),
-		);
-		let outside_artboards = match transparent_background {
-			false => format!(r##"<rect x="0" y="0" width="100%" height="100%" fill="#{}" />"##, outside_artboards_color),
-			true => "".into(),
+		let outside_artboards = self.outside_artboards(transparent_background, outside);
+		let fonts = match embed_fonts {
+			true => self.embedded_fonts(&persistent_data.font_cache),
+			false => String::new(),
 		};
-		let matrix = transform
-			.to_cols_array()
-			.iter()
-			.enumerate()
-			.fold(String::new(), |acc, (i, entry)| acc + &(entry.to_string() + if i == 5 { "" } else { "," }));
-		let svg = format!(
-			r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="none" viewBox="0 0 1 1" width="{}" height="{}">{}{outside_artboards}<g transform="matrix({matrix})">{artboards}{artwork}</g></svg>"#,
-			size.x, size.y, "\n",
+
+		document_svg(size, transform, &fonts, &outside_artboards, &artboards, &artwork)
+	}
+
+	/// Copies what [Self::render_document] draws of the whole document into a job which renders the same SVG on its own, so it can be done on a render worker.
+	fn render_document_job(
+		&self,
+		size: DVec2,
+		transform: DAffine2,
+		transparent_background: bool,
+		layer_names: bool,
+		embed_fonts: bool,
+		persistent_data: &PersistentData,
+	) -> Box<dyn FnOnce(&FontCache) -> String + Send> {
+		let mut document = self.document_legacy.clone();
+		let mut artboards = (!transparent_background).then(|| self.artboard_message_handler.artboards_document.clone());
+		let outside_artboards = self.outside_artboards(transparent_background, None);
+		let fonts = match embed_fonts {
+			true => self.embedded_fonts(&persistent_data.font_cache),
+			false => String::new(),
+		};
+
+		Box::new(move |font_cache| {
+			let element_ids = SvgElementIds::default();
+			let mut render_data = RenderData::new(font_cache, ViewMode::Normal, None).with_live_text(embed_fonts).for_export();
+			if layer_names {
+				render_data = render_data.with_element_ids(&element_ids);
+			}
+
+			let artwork = document.render_root(&render_data);
+			let artboards = artboards.as_mut().map(|artboards| artboards.render_root(&render_data)).unwrap_or_default();
+			document_svg(size, transform, &fonts, &outside_artboards, &artboards, &artwork)
+		})
+	}
+
+	/// The background filling the exported image around the artboards, or around the artwork if there are none.
+	fn outside_artboards(&self, transparent_background: bool, color: Option<Color>) -> String {
+		if transparent_background {
+			return String::new();
+		}
+		let color = color.map_or_else(
+			|| if self.artboard_message_handler.artboard_ids.is_empty() { "ffffff" } else { "222222" }.to_string(),
+			|color| color.rgba_hex(),
 		);
+		format!(r##"<rect x="0" y="0" width="100%" height="100%" fill="#{color}" />"##)
+	}
+
+	/// Hands the thumbnails waiting to be rendered to the render workers, each along with a copy of its layer.
+	fn render_thumbnails_in_background(&mut self, document_id: u64, render_workers: &RenderWorkers) {
+		let view_mode = self.view_mode;
+
+		while let Some(layer_path) = self.idle_work.next_thumbnail() {
+			let (Ok(layer), Ok(transform)) = (self.document_legacy.layer(&layer_path), self.document_legacy.multiply_transforms(&layer_path)) else {
+				continue;
+			};
+			let (layer, path) = (layer.clone(), layer_path.clone());
+			let job_id = render_workers.submit(Box::new(move |font_cache| RenderOutput::LayerThumbnail {
+				document_id,
+				thumbnail: LayerPanelEntry::thumbnail(transform, &layer, &RenderData::new(font_cache, view_mode, None)),
+				layer_path: path,
+			}));
+			self.idle_work.start_rendering(layer_path, job_id);
+		}
+
+		if self.idle_work.next_document_thumbnail() {
+			let root = self.document_legacy.root.clone();
+			let job_id = render_workers.submit(Box::new(move |font_cache| RenderOutput::DocumentThumbnail {
+				document_id,
+				thumbnail: LayerPanelEntry::thumbnail(DAffine2::IDENTITY, &root, &RenderData::new(font_cache, view_mode, None)),
+			}));
+			self.idle_work.start_document_thumbnail(job_id);
+		}
+	}
+
+	/// A style element embedding each font used by the document's text layers, subset to the glyphs of the text set in it.
+	fn embedded_fonts(&self, font_cache: &FontCache) -> String {
+		let mut fonts: Vec<(&Font, Vec<u16>)> = Vec::new();
+		for layer in self.all_layers().filter_map(|path| self.document_legacy.layer(path).ok()) {
+			let Some((text, font)) = layer_text(layer).zip(layer_font(layer)) else { continue };
+			let Some((font, data)) = font_cache.resolve_font(font).zip(font_cache.get(font)) else { continue };
+
+			let glyph_ids = glyph_ids(text, &load_face(data));
+			match fonts.iter_mut().find(|(used, _)| *used == font) {
+				Some((_, used_glyph_ids)) => used_glyph_ids.extend(glyph_ids),
+				None => fonts.push((font, glyph_ids)),
+			}
+		}
+		if fonts.is_empty() {
+			return String::new();
+		}
+
+		let rules = fonts
+			.into_iter()
+			.filter_map(|(font, glyph_ids)| Some(font_face_rule(&embedded_font_family(font), font_cache.get(font)?, &glyph_ids)))
+			.collect::<String>();
+		format!("<defs><style><![CDATA[{rules}]]></style></defs>")
+	}
 
-		svg
+	/// The name the document is saved under, which always ends with the ".graphite" extension.
+	pub fn file_name(&self) -> String {
+		match self.name.ends_with(FILE_SAVE_SUFFIX) {
+			true => self.name.clone(),
+			false => self.name.clone() + FILE_SAVE_SUFFIX,
+		}
 	}
 
 	pub fn serialize_document(&self) -> String {
+		// The folder contents still waiting to be attached are saved too, from a copy of the document so the loading carries on unaffected
+		if self.progressive_loading.is_loading() {
+			let mut document = self.clone();
+			document.progressive_loading.load_all(&mut document.document_legacy, &mut document.layer_metadata);
+			return document.serialize_document();
+		}
+
 		let val = serde_json::to_string(self);
 		// We fully expect the serialization to succeed
 		val.unwrap()
 	}
 
 	pub fn deserialize_document(serialized_content: &str) -> Result<Self, DocumentError> {
+		if serialized_content.len() > MAX_FILE_SIZE {
+			return Err(DocumentError::InvalidFile(format!("The file is larger than the limit of {} MB", MAX_FILE_SIZE / 1024 / 1024)));
+		}
+
 		let deserialized_result: Result<Self, DocumentError> = serde_json::from_str(serialized_content).map_err(|e| DocumentError::InvalidFile(e.to_string()));
 		match deserialized_result {
-			Ok(document) => {
+			Ok(mut document) => {
 				if document.version == GRAPHITE_DOCUMENT_VERSION {
+					// A file that was tampered with or damaged can still be well-formed, but describe a layer tree that can't be edited safely
+					document.document_legacy.validate()?;
+					document.artboard_message_handler.artboards_document.validate()?;
+					add_missing_node_inputs(&mut document.document_legacy.root.data);
+					// The same file read in another window must be given the same ID, so it's derived from what's in the file
+					if document.file_id == 0 {
+						let mut hasher = DefaultHasher::new();
+						serialized_content.hash(&mut hasher);
+						document.file_id = hasher.finish();
+					}
 					Ok(document)
 				} else {
 					Err(DocumentError::InvalidFile("Graphite document version mismatch".to_string()))
@@ -1102,26 +3175,213 @@ impl DocumentMessageHandler {
 		}
 	}
 
-	pub fn with_name(name: String, ipp: &InputPreprocessorMessageHandler) -> Self {
+	pub fn with_name(name: String, ipp: &InputPreprocessorMessageHandler, preferences: &PreferencesMessageHandler) -> Self {
 		let mut document = Self { name, ..Self::default() };
-		let starting_root_transform = document.navigation_handler.calculate_offset_transform(ipp.viewport_bounds.size() / 2.);
+		let pixel_ratio = preferences.zoom_pixel_ratio(ipp);
+		let starting_root_transform = document.navigation_handler.calculate_offset_transform(ipp.viewport_bounds.size() / 2. * pixel_ratio, pixel_ratio);
 		document.document_legacy.root.transform = starting_root_transform;
 		document.artboard_message_handler.artboards_document.root.transform = starting_root_transform;
+		document.operation_log = OperationLog::new();
 
 		document
 	}
 
-	pub fn with_name_and_content(name: String, serialized_content: String) -> Result<Self, EditorError> {
-		match Self::deserialize_document(&serialized_content) {
-			Ok(mut document) => {
+	/// Reads the document, and if some of its layers can't be read, leaves them out and opens the rest, returning the layers that were left out.
+	/// Other problems, like a document from a different version of the editor, still fail with the original error.
+	pub fn deserialize_document_recovering_layers(serialized_content: &str) -> Result<(Self, Vec<DroppedLayer>), DocumentError> {
+		let error = match Self::deserialize_document(serialized_content) {
+			Ok(document) => return Ok((document, Vec::new())),
+			Err(error) => error,
+		};
+		let Ok(mut serialized) = serde_json::from_str::<serde_json::Value>(serialized_content) else {
+			return Err(error);
+		};
+
+		let mut dropped = Vec::new();
+		if let Some(root) = serialized.pointer_mut("/document_legacy/root") {
+			dropped.extend(drop_unreadable_layers(root));
+		}
+		// Forget the selection and other details of the layers that were left out
+		if let Some(serde_json::Value::Array(layer_metadata)) = serialized.get_mut("layer_metadata") {
+			layer_metadata.retain(|entry| {
+				let path = entry.get(0).and_then(|path| serde_json::from_value::<Vec<LayerId>>(path.clone()).ok());
+				path.map_or(false, |path| !dropped.iter().any(|layer| path.starts_with(&layer.path)))
+			});
+		}
+
+		// The artboards are the top level layers of their own document, which are listed again by ID
+		if let Some(root) = serialized.pointer_mut("/artboard_message_handler/artboards_document/root") {
+			let dropped_artboards = drop_unreadable_layers(root);
+			if let Some(serde_json::Value::Array(artboard_ids)) = serialized.pointer_mut("/artboard_message_handler/artboard_ids") {
+				artboard_ids.retain(|id| !dropped_artboards.iter().any(|artboard| id.as_u64() == artboard.path.first().copied()));
+			}
+			dropped.extend(dropped_artboards);
+		}
+
+		if dropped.is_empty() {
+			return Err(error);
+		}
+		let document = Self::deserialize_document(&serialized.to_string()).map_err(|_| error)?;
+		Ok((document, dropped))
+	}
+
+	pub fn with_name_and_content(name: String, serialized_content: String) -> Result<(Self, Vec<DroppedLayer>), EditorError> {
+		match Self::deserialize_document_recovering_layers(&serialized_content) {
+			Ok((mut document, dropped)) => {
 				document.name = name;
-				Ok(document)
+				Ok((document, dropped))
 			}
 			Err(DocumentError::InvalidFile(msg)) => Err(EditorError::DocumentDeserialization(msg)),
 			_ => Err(EditorError::Document(String::from("Failed to open file"))),
 		}
 	}
 
+	/// The outlines, in document space, and styles of the selected shapes which a boolean operation combines, along with their layers.
+	/// They are listed from the top of the stacking order down, so the frontmost shape is the first one.
+	/// The same change, in viewport space, made to each unselected layer linked to a selected layer whose transform the message changes.
+	/// Only the links of selected layers are followed, since the selected layers are transformed together and the changes made to their links
+	/// must not be passed on again.
+	fn linked_transform_changes(&self, message: &GraphOperationMessage) -> Vec<GraphOperationMessage> {
+		let (layer, transform, transform_in, skip_rerender, set) = match message {
+			GraphOperationMessage::TransformChange {
+				layer,
+				transform,
+				transform_in,
+				skip_rerender,
+			} => (layer, *transform, *transform_in, *skip_rerender, false),
+			GraphOperationMessage::TransformSet {
+				layer,
+				transform,
+				transform_in,
+				skip_rerender,
+			} => (layer, *transform, *transform_in, *skip_rerender, true),
+			_ => return Vec::new(),
+		};
+		if !self.transform_links.is_linked(layer) || !self.layer_metadata.get(layer).map_or(false, |data| data.selected) {
+			return Vec::new();
+		}
+
+		let Some((_, parent_path)) = layer.split_last() else { return Vec::new() };
+		let (Ok(parent_to_viewport), Ok(current)) = (self.document_legacy.multiply_transforms(parent_path), self.document_legacy.multiply_transforms(layer)) else {
+			return Vec::new();
+		};
+		let change = viewport_change(transform, transform_in, set, parent_to_viewport, current);
+
+		self.transform_links
+			.linked_to(layer)
+			.filter(|linked| self.document_legacy.layer(linked).is_ok() && !self.layer_metadata.get(*linked).map_or(false, |data| data.selected))
+			.map(|linked| GraphOperationMessage::TransformChange {
+				layer: linked.to_vec(),
+				transform: change,
+				transform_in: TransformIn::Viewport,
+				skip_rerender,
+			})
+			.collect()
+	}
+
+	/// Asks the user to confirm an operation estimated to take a long time, unless they just did. Returns whether the operation should run now.
+	fn confirm_costly_operation(&mut self, cost: CostlyOperation, title: &str, operation: DocumentMessage, responses: &mut VecDeque<Message>) -> bool {
+		if std::mem::take(&mut self.costly_operation_confirmed) || !cost.needs_confirmation() {
+			return true;
+		}
+
+		let dialog = simple_dialogs::CostlyOperationDialog {
+			title: title.into(),
+			description: cost.description(),
+			operation,
+		};
+		dialog.send_layout(responses, LayoutTarget::DialogDetails);
+		responses.add(FrontendMessage::DisplayDialog { icon: "Warning".to_string() });
+		false
+	}
+
+	fn boolean_operation_shapes(&self) -> (Vec<StyledShape>, Vec<Vec<LayerId>>) {
+		let document_transform_inverse = self.document_legacy.root.transform.inverse();
+
+		let mut selected_layers = self.selected_layers_sorted().into_iter().map(|path| path.to_vec()).collect::<Vec<_>>();
+		selected_layers.reverse();
+		let mut shapes = Vec::new();
+		let mut source_layers = Vec::new();
+		for layer_path in selected_layers {
+			let Ok(layer) = self.document_legacy.layer(&layer_path) else { continue };
+			let Some(vector_data) = layer.as_vector_data() else { continue };
+			let Ok(to_document) = self.document_legacy.multiply_transforms(&layer_path).map(|transform| document_transform_inverse * transform) else {
+				continue;
+			};
+
+			let subpaths = vector_data
+				.subpaths
+				.iter()
+				.map(|subpath| {
+					let mut subpath = subpath.clone();
+					subpath.apply_transform(to_document);
+					subpath
+				})
+				.collect();
+			shapes.push((subpaths, vector_data.style.clone()));
+			source_layers.push(layer_path);
+		}
+
+		(shapes, source_layers)
+	}
+
+	/// Shows the result of a previewed boolean operation as an overlay, outlined like a selection and filled like the result will be.
+	fn show_boolean_preview(&mut self, result: Option<StyledShape>, responses: &mut VecDeque<Message>) {
+		let Some((subpaths, style)) = result else { return };
+
+		let path = vec![generate_uuid()];
+		let style = PathStyle::new(Some(Stroke::new(Some(overlay_colors().selection_outline), PATH_OUTLINE_WEIGHT)), style.fill().clone());
+		let operation = DocumentOperation::AddShape {
+			path: path.clone(),
+			insert_index: -1,
+			transform: self.document_legacy.root.transform.to_cols_array(),
+			style,
+			subpath: Subpath::from_bezier_rs(&subpaths),
+		};
+		responses.add(DocumentMessage::Overlays(operation.into()));
+		self.boolean_preview.overlay = Some(path);
+	}
+
+	/// Removes the preview of a boolean operation, or cancels computing it if it isn't shown yet.
+	fn clear_boolean_preview(&mut self, responses: &mut VecDeque<Message>) {
+		self.boolean_preview.job = None;
+		if let Some(path) = self.boolean_preview.overlay.take() {
+			responses.add(DocumentMessage::Overlays(DocumentOperation::DeleteLayer { path }.into()));
+		}
+	}
+
+	/// Opens the document progressively if it is large enough, so only its top level layers are shown at first. See [ProgressiveLoading].
+	pub fn load_progressively(&mut self, document_id: u64, responses: &mut VecDeque<Message>) {
+		// Without a clock there's no telling when the user is idle to load the rest, so it is all shown right away
+		if self.layer_metadata.len() <= PROGRESSIVE_LOADING_LAYER_COUNT || now_milliseconds().is_none() {
+			return;
+		}
+
+		self.progressive_loading = ProgressiveLoading::defer(document_id, &mut self.document_legacy, &mut self.layer_metadata);
+		let (loaded, total) = self.progressive_loading.progress();
+		responses.add(FrontendMessage::UpdateDocumentLoadingProgress { document_id, loaded, total });
+		responses.add(FrontendMessage::TriggerIdleWork);
+	}
+
+	/// Attaches the rest of a progressively loaded document right away, for anything which needs the whole document.
+	pub fn finish_loading(&mut self, responses: &mut VecDeque<Message>) {
+		if !self.progressive_loading.is_loading() {
+			return;
+		}
+
+		let layer_paths = self.progressive_loading.load_all(&mut self.document_legacy, &mut self.layer_metadata);
+		responses.add(DocumentMessage::QueueLayerThumbnails { layer_paths });
+		self.loading_progress_changed(responses);
+	}
+
+	fn loading_progress_changed(&self, responses: &mut VecDeque<Message>) {
+		let (loaded, total) = self.progressive_loading.progress();
+		let document_id = self.progressive_loading.document_id();
+		responses.add(FrontendMessage::UpdateDocumentLoadingProgress { document_id, loaded, total });
+		responses.add(DocumentMessage::DocumentStructureChanged);
+		responses.add(DocumentMessage::RenderDocument);
+	}
+
 	pub fn is_unmodified_default(&self) -> bool {
 		self.serialize_root().len() == Self::default().serialize_root().len()
 			&& self.document_undo_history.is_empty()
@@ -1149,6 +3409,12 @@ impl DocumentMessageHandler {
 		self.document_legacy.combined_viewport_bounding_box(paths, render_data)
 	}
 
+	/// The bounds of the selected layers that are included in exports, which may differ from those visible on the canvas.
+	pub fn selected_exported_layers_bounding_box(&self, render_data: &RenderData) -> Option<[DVec2; 2]> {
+		let paths = self.selected_layers().filter(|path| self.document_legacy.layer(path).map_or(false, |layer| layer.is_visible(true)));
+		self.document_legacy.combined_viewport_bounding_box(paths, render_data)
+	}
+
 	pub fn artboard_bounding_box_and_transform(&self, path: &[LayerId], render_data: &RenderData) -> Option<([DVec2; 2], DAffine2)> {
 		self.artboard_message_handler.artboards_document.bounding_box_and_transform(path, render_data).unwrap_or(None)
 	}
@@ -1166,6 +3432,15 @@ impl DocumentMessageHandler {
 		})
 	}
 
+	/// Whether the layer's graph renders a bitmap rather than vector shapes, which have no fill or stroke to recolor.
+	pub fn is_raster_layer(&self, path: &[LayerId]) -> bool {
+		self.document_legacy
+			.layer(path)
+			.ok()
+			.and_then(|layer| layer.as_layer().ok())
+			.map_or(false, |layer| matches!(layer.cached_output_data, CachedOutputData::BlobURL(_) | CachedOutputData::SurfaceId(_)))
+	}
+
 	pub fn non_selected_layers(&self) -> impl Iterator<Item = &[LayerId]> {
 		self.layer_metadata.iter().filter_map(|(path, data)| (!data.selected).then_some(path.as_slice()))
 	}
@@ -1273,6 +3548,53 @@ impl DocumentMessageHandler {
 		self.layer_metadata.keys().filter_map(|path| (!path.is_empty()).then_some(path.as_slice()))
 	}
 
+	/// The paths of the selected layers which are text layers
+	pub fn selected_text_layers(&self) -> Vec<Vec<LayerId>> {
+		self.selected_layers()
+			.filter(|path| self.document_legacy.layer(path).ok().and_then(layer_text).is_some())
+			.map(|path| path.to_vec())
+			.collect()
+	}
+
+	/// The document's text style with the given ID
+	pub fn text_style(&self, id: u64) -> Option<&TextStyle> {
+		self.document_legacy.text_styles.iter().find(|text_style| text_style.id == id)
+	}
+
+	/// The paths of all layers linked to the text style with the given ID
+	fn layers_with_text_style(&self, id: u64) -> Vec<Vec<LayerId>> {
+		self.all_layers()
+			.filter(|path| self.document_legacy.layer(path).map_or(false, |layer| layer.text_style == Some(id)))
+			.map(|path| path.to_vec())
+			.collect()
+	}
+
+	/// Every font used by the document's text layers along with how many layers use it
+	pub fn used_fonts(&self) -> Vec<(Font, usize)> {
+		used_fonts(self.all_layers().filter_map(|path| self.document_legacy.layer(path).ok()))
+	}
+
+	/// Estimates the memory held by the document's layer caches, thumbnails, undo history, and path data
+	pub fn memory_report(&self) -> MemoryReport {
+		let mut report = MemoryReport::default();
+		report.add_layers(&self.document_legacy.root);
+		report.add_layers(&self.artboard_message_handler.artboards_document.root);
+		for document_save in self.document_undo_history.iter().chain(&self.document_redo_history) {
+			report.add_history_entry(&document_save.document);
+			report.add_history_entry(&document_save.artboard.artboards_document);
+		}
+		report
+	}
+
+	/// Frees the undo and redo history and the rendered caches of the layers, which are rebuilt on the next render
+	pub fn purge_caches_and_history(&mut self) {
+		self.document_undo_history = VecDeque::new();
+		self.document_redo_history = VecDeque::new();
+		self.document_legacy.root.clear_caches();
+		self.artboard_message_handler.artboards_document.root.clear_caches();
+		self.rendered_artwork_defs = None;
+	}
+
 	/// Returns the paths to all layers in order
 	fn sort_layers<'a>(&self, paths: impl Iterator<Item = &'a [LayerId]>) -> Vec<&'a [LayerId]> {
 		// Compute the indices for each layer to be able to sort them
@@ -1328,10 +3650,17 @@ impl DocumentMessageHandler {
 	/// Places a document into the history system
 	fn backup_with_document(&mut self, document: DocumentLegacy, artboard: ArtboardMessageHandler, layer_metadata: HashMap<Vec<LayerId>, LayerMetadata>, responses: &mut VecDeque<Message>) {
 		self.document_redo_history.clear();
-		self.document_undo_history.push_back(DocumentSave { document, artboard, layer_metadata });
+		let edit_identifier = std::mem::replace(&mut self.edit_identifier, generate_uuid());
+		self.document_undo_history.push_back(DocumentSave {
+			document,
+			artboard,
+			layer_metadata,
+			edit_identifier,
+		});
 		if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
 			self.document_undo_history.pop_front();
 		}
+		self.operation_log.record(LoggedEdit::Checkpoint);
 
 		// Push the UpdateOpenDocumentsList message to the bus in order to update the save status of the open documents
 		responses.add(PortfolioMessage::UpdateOpenDocumentsList);
@@ -1339,11 +3668,19 @@ impl DocumentMessageHandler {
 
 	/// Copies the entire document into the history system
 	pub fn backup(&mut self, responses: &mut VecDeque<Message>) {
+		// Undoing has to bring back the whole document, so it all needs to be loaded before it is copied
+		self.finish_loading(responses);
 		self.backup_with_document(self.document_legacy.clone(), self.artboard_message_handler.clone(), self.layer_metadata.clone(), responses);
 	}
 
 	/// Push a message backing up the document in its current state
 	pub fn backup_nonmut(&self, responses: &mut VecDeque<Message>) {
+		// The rest of a document still being loaded can only be attached once it can be modified, which a transaction does before backing it up
+		if self.progressive_loading.is_loading() {
+			responses.add(DocumentMessage::StartTransaction);
+			return;
+		}
+
 		responses.add(DocumentMessage::BackupDocument {
 			document: self.document_legacy.clone(),
 			artboard: Box::new(self.artboard_message_handler.clone()),
@@ -1358,7 +3695,15 @@ impl DocumentMessageHandler {
 	}
 
 	/// Replace the document with a new document save, returning the document save.
-	pub fn replace_document(&mut self, DocumentSave { document, artboard, layer_metadata }: DocumentSave) -> DocumentSave {
+	pub fn replace_document(
+		&mut self,
+		DocumentSave {
+			document,
+			artboard,
+			layer_metadata,
+			edit_identifier,
+		}: DocumentSave,
+	) -> DocumentSave {
 		// Keeping the root is required if the bounds of the viewport have changed during the operation
 		let old_root = self.document_legacy.root.transform;
 		let old_artboard_root = self.artboard_message_handler.artboards_document.root.transform;
@@ -1370,8 +3715,17 @@ impl DocumentMessageHandler {
 		self.artboard_message_handler.artboards_document.root.cache_dirty = true;
 
 		let layer_metadata = std::mem::replace(&mut self.layer_metadata, layer_metadata);
-
-		DocumentSave { document, artboard, layer_metadata }
+		let edit_identifier = std::mem::replace(&mut self.edit_identifier, edit_identifier);
+		// Undoing or redoing can remove layers without deleting them one by one
+		let current_layers = &self.layer_metadata;
+		self.idle_work.retain(|path| current_layers.contains_key(path));
+
+		DocumentSave {
+			document,
+			artboard,
+			layer_metadata,
+			edit_identifier,
+		}
 	}
 
 	pub fn undo(&mut self, responses: &mut VecDeque<Message>) -> Result<(), EditorError> {
@@ -1381,16 +3735,21 @@ impl DocumentMessageHandler {
 		let selected_paths: Vec<Vec<LayerId>> = self.selected_layers().map(|path| path.to_vec()).collect();
 
 		match self.document_undo_history.pop_back() {
-			Some(DocumentSave { document, artboard, layer_metadata }) => {
+			Some(document_save) => {
 				// Update the currently displayed layer on the Properties panel if the selection changes after an undo action
 				// Also appropriately update the Properties panel if an undo action results in a layer being deleted
-				let prev_selected_paths: Vec<Vec<LayerId>> = layer_metadata.iter().filter_map(|(layer_id, metadata)| metadata.selected.then_some(layer_id.clone())).collect();
+				let prev_selected_paths: Vec<Vec<LayerId>> = document_save
+					.layer_metadata
+					.iter()
+					.filter_map(|(layer_id, metadata)| metadata.selected.then_some(layer_id.clone()))
+					.collect();
 
 				if prev_selected_paths != selected_paths {
 					responses.add(BroadcastEvent::SelectionChanged);
 				}
 
-				let document_save = self.replace_document(DocumentSave { document, artboard, layer_metadata });
+				let document_save = self.replace_document(document_save);
+				self.operation_log.record(LoggedEdit::Undo);
 
 				self.document_redo_history.push_back(document_save);
 				if self.document_redo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
@@ -1416,16 +3775,21 @@ impl DocumentMessageHandler {
 		let selected_paths: Vec<Vec<LayerId>> = self.selected_layers().map(|path| path.to_vec()).collect();
 
 		match self.document_redo_history.pop_back() {
-			Some(DocumentSave { document, artboard, layer_metadata }) => {
+			Some(document_save) => {
 				// Update currently displayed layer on property panel if selection changes after redo action
 				// Also appropriately update property panel if redo action results in a layer being added
-				let next_selected_paths: Vec<Vec<LayerId>> = layer_metadata.iter().filter_map(|(layer_id, metadata)| metadata.selected.then_some(layer_id.clone())).collect();
+				let next_selected_paths: Vec<Vec<LayerId>> = document_save
+					.layer_metadata
+					.iter()
+					.filter_map(|(layer_id, metadata)| metadata.selected.then_some(layer_id.clone()))
+					.collect();
 
 				if next_selected_paths != selected_paths {
 					responses.add(BroadcastEvent::SelectionChanged);
 				}
 
-				let document_save = self.replace_document(DocumentSave { document, artboard, layer_metadata });
+				let document_save = self.replace_document(document_save);
+				self.operation_log.record(LoggedEdit::Redo);
 				self.document_undo_history.push_back(document_save);
 				if self.document_undo_history.len() > crate::consts::MAX_UNDO_HISTORY_LEN {
 					self.document_undo_history.pop_front();
@@ -1443,14 +3807,11 @@ impl DocumentMessageHandler {
 		}
 	}
 
+	/// Identifies the current contents of the document, for telling whether they have been modified since the document was last saved.
+	/// A new identifier is assigned by each undoable edit and undo and redo restore the identifier of the state they return to, while
+	/// changes to the view (like panning, zooming, and selecting a view mode) leave it untouched. A new document starts out at 0.
 	pub fn current_identifier(&self) -> u64 {
-		// We can use the last state of the document to serve as the identifier to compare against
-		// This is useful since when the document is empty the identifier will be 0
-		self.document_undo_history
-			.iter()
-			.last()
-			.map(|DocumentSave { document, .. }| document.current_state_identifier())
-			.unwrap_or(0)
+		self.edit_identifier
 	}
 
 	pub fn is_auto_saved(&self) -> bool {
@@ -1477,6 +3838,58 @@ impl DocumentMessageHandler {
 		}
 	}
 
+	/// The named solid colors of layers, the text styles, and the spacing of the guide grid, which make up the design tokens of the document.
+	fn design_tokens(&self) -> DesignTokens {
+		let mut tokens = DesignTokens::default();
+
+		for layer in self.all_layers_sorted().into_iter().filter_map(|path| self.document_legacy.layer(path).ok()) {
+			let Some(name) = layer.name.as_deref().filter(|name| !name.is_empty()) else { continue };
+			if let Some(Fill::Solid(color)) = layer.as_vector_data().map(|vector_data| vector_data.style.fill().clone()) {
+				tokens.add_color(name, color);
+			}
+		}
+		for text_style in &self.document_legacy.text_styles {
+			tokens.add_text_style(&text_style.name, text_style.font.clone(), text_style.font_size);
+		}
+		if self.guide_grid.grid_type == GuideGridType::Isometric {
+			tokens.add_spacing("Guide Grid", self.guide_grid.isometric_spacing);
+		}
+
+		tokens
+	}
+
+	/// Downloads the design tokens as a file named after the document, and remembers them to compare against while watching for changes.
+	fn download_design_tokens(&mut self, tokens: DesignTokens, format: DesignTokenFormat, responses: &mut VecDeque<Message>) {
+		let base_name = self.name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(&self.name);
+		let name = format!("{base_name}.{}", format.file_suffix());
+		responses.add(FrontendMessage::TriggerDownloadTextFile { document: tokens.write(format), name });
+		self.exported_design_tokens = Some(tokens);
+	}
+
+	/// Notes that the design tokens may have changed, so while watching for changes they're compared with the last export once the user pauses.
+	fn queue_design_tokens(&mut self, responses: &mut VecDeque<Message>) {
+		if self.design_token_watch.is_none() {
+			return;
+		}
+
+		let idle_work_requested = self.idle_work.has_pending_work();
+		self.idle_work.queue_design_tokens();
+		if now_milliseconds().is_some() && !idle_work_requested {
+			responses.add(FrontendMessage::TriggerIdleWork);
+		}
+	}
+
+	/// Marks the composite thumbnail shown in the document's tab as outdated, so it is rendered again once the user pauses.
+	fn queue_document_thumbnail(&mut self, responses: &mut VecDeque<Message>) {
+		let idle_work_requested = self.idle_work.has_pending_work();
+		self.idle_work.queue_document_thumbnail();
+
+		// Without a clock there's no idle time to render it in, and if other work was already pending the idle time has been requested before
+		if now_milliseconds().is_some() && !idle_work_requested {
+			responses.add(FrontendMessage::TriggerIdleWork);
+		}
+	}
+
 	// TODO: This should probably take a slice not a vec, also why does this even exist when `layer_panel_entry_from_path` also exists?
 	pub fn layer_panel_entry(&mut self, path: Vec<LayerId>, render_data: &RenderData) -> Result<LayerPanelEntry, EditorError> {
 		let data: LayerMetadata = *self
@@ -1484,7 +3897,8 @@ impl DocumentMessageHandler {
 			.get_mut(&path)
 			.ok_or_else(|| EditorError::Document(format!("Could not get layer metadata for {:?}", path)))?;
 		let layer = self.document_legacy.layer(&path)?;
-		let entry = LayerPanelEntry::new(&data, self.document_legacy.multiply_transforms(&path)?, layer, path, render_data);
+		let entry = LayerPanelEntry::new(&data, self.document_legacy.multiply_transforms(&path)?, layer, path.clone(), render_data);
+		self.idle_work.store_thumbnail(path, entry.thumbnail.clone());
 		Ok(entry)
 	}
 
@@ -1530,16 +3944,61 @@ impl DocumentMessageHandler {
 		}
 	}
 
+	/// Calculates the folder that pasted layers are inserted into and their insert index within it.
+	/// Layers are pasted directly above the topmost selected layer in its folder, or at the top of the selected folder when pasting into it.
+	/// If the selected layers are in different folders, they are pasted at the top of the deepest folder containing all of them.
+	pub fn paste_destination(&self, into_folder: bool) -> (Vec<LayerId>, isize) {
+		let selected_layers = self.selected_layers_without_children();
+
+		if let [folder_path] = selected_layers[..] {
+			if into_folder && self.document_legacy.folder(folder_path).is_ok() {
+				return (folder_path.to_vec(), -1);
+			}
+		}
+
+		let Some(&topmost_layer) = selected_layers.last() else { return (Vec::new(), -1) };
+		let folder_path = self.document_legacy.common_layer_path_prefix(selected_layers.iter().map(|path| &path[..path.len() - 1]));
+		if topmost_layer.len() != folder_path.len() + 1 {
+			return (folder_path.to_vec(), -1);
+		}
+
+		let insert_index = self
+			.document_legacy
+			.folder(folder_path)
+			.and_then(|folder| folder.position_of_layer(topmost_layer[folder_path.len()]))
+			.map_or(-1, |position| position as isize + 1);
+		(folder_path.to_vec(), insert_index)
+	}
+
 	/// Calculate the path that new layers should be inserted to.
 	/// Depends on the selected layers as well as their types (Folder/Non-Folder)
+	/// The path for a layer drawn by a tool, in the folder chosen by the new layer placement preference. It is inserted at [Self::get_insert_index_for_new_layer].
 	pub fn get_path_for_new_layer(&self) -> Vec<u64> {
 		// If the selected layers don't actually exist, a new uuid for the
 		// root folder will be returned
-		let mut path = self.document_legacy.shallowest_common_folder(self.selected_layers()).map_or(vec![], |v| v.to_vec());
+		let mut path = match new_layer_placement() {
+			NewLayerPlacement::TopOfDocument => vec![],
+			NewLayerPlacement::AboveSelection => self.selected_layers_sorted().last().map_or(vec![], |path| path[..path.len() - 1].to_vec()),
+			NewLayerPlacement::IntoSelectedFolder => self.document_legacy.shallowest_common_folder(self.selected_layers()).map_or(vec![], |v| v.to_vec()),
+		};
 		path.push(generate_uuid());
 		path
 	}
 
+	/// The index in its folder at which a layer drawn by a tool is inserted, which is the top of the folder unless it goes above the selection.
+	pub fn get_insert_index_for_new_layer(&self) -> isize {
+		if new_layer_placement() != NewLayerPlacement::AboveSelection {
+			return -1;
+		}
+
+		// Layers later in the folder are drawn above the earlier ones
+		let Some((layer_id, folder_path)) = self.selected_layers_sorted().last().and_then(|path| path.split_last()) else {
+			return -1;
+		};
+		let position = self.document_legacy.folder(folder_path).and_then(|folder| folder.position_of_layer(*layer_id));
+		position.map_or(-1, |position| position as isize + 1)
+	}
+
 	/// Loads layer resources such as creating the blob URLs for the images and loading all of the fonts in the document
 	pub fn load_layer_resources(&self, responses: &mut VecDeque<Message>, root: &LayerDataType, mut path: Vec<LayerId>, _document_id: u64) {
 		fn walk_layers(data: &LayerDataType, path: &mut Vec<LayerId>, responses: &mut VecDeque<Message>, fonts: &mut HashSet<Font>) {
@@ -1578,8 +4037,73 @@ impl DocumentMessageHandler {
 		}
 	}
 
+	/// Draws the guide grid over the part of the canvas shown in the viewport, or removes it while it's hidden.
+	fn render_guide_grid(&mut self, ipp: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
+		// The overlay is gone if all of the overlays were cleared since it was drawn
+		let overlays_document = &self.overlays_message_handler.overlays_document;
+		let existing_overlay = self.guide_grid_overlay.take().filter(|path| overlays_document.layer(path).is_ok());
+
+		if !self.guide_grid.visible {
+			if let Some(path) = existing_overlay {
+				responses.add(DocumentMessage::Overlays(DocumentOperation::DeleteLayer { path }.into()));
+			}
+			return;
+		}
+
+		let lines = self.guide_grid.viewport_lines(self.document_legacy.root.transform, ipp.viewport_bounds.size());
+		let subpaths = lines.into_iter().map(|[start, end]| bezier_rs::Subpath::new_line(start, end)).collect::<Vec<_>>();
+
+		let path = existing_overlay.unwrap_or_else(|| {
+			let path = vec![generate_uuid()];
+			responses.add(DocumentMessage::Overlays(
+				DocumentOperation::AddShape {
+					path: path.clone(),
+					subpath: Default::default(),
+					style: PathStyle::new(Some(Stroke::new(Some(overlay_colors().snap_guides), 1.)), Fill::None),
+					insert_index: 0,
+					transform: DAffine2::IDENTITY.to_cols_array(),
+				}
+				.into(),
+			));
+			responses.add(DocumentMessage::Overlays(
+				DocumentOperation::SetLayerOpacity {
+					path: path.clone(),
+					opacity: GUIDE_GRID_OVERLAY_OPACITY,
+				}
+				.into(),
+			));
+			path
+		});
+		responses.add(DocumentMessage::Overlays(
+			DocumentOperation::SetShapePath {
+				path: path.clone(),
+				subpath: Subpath::from_bezier_rs(&subpaths),
+			}
+			.into(),
+		));
+		self.guide_grid_overlay = Some(path);
+	}
+
+	/// Moves the end of a segment drawn from the start, both in viewport space, onto the closest guide of the guide grid if it's close enough to snap to.
+	pub fn snap_to_guide_grid(&self, start: DVec2, end: DVec2) -> DVec2 {
+		if !(self.snapping_state.snapping_enabled && self.guide_grid.visible && self.guide_grid.snapping) {
+			return end;
+		}
+
+		let document_to_viewport = self.document_legacy.root.transform;
+		let viewport_to_document = document_to_viewport.inverse();
+		let snapped = self.guide_grid.snap_segment(viewport_to_document.transform_point2(start), viewport_to_document.transform_point2(end));
+		snapped.map_or(end, |snapped| document_to_viewport.transform_point2(snapped))
+	}
+
+	/// The zoom of the canvas as a percentage, as shown in the document bar.
+	pub fn zoom_percentage(&self) -> f64 {
+		self.navigation_handler.snapped_scale() * 100.
+	}
+
 	pub fn update_document_widgets(&self, responses: &mut VecDeque<Message>) {
 		let snapping_state = self.snapping_state.clone();
+		let guide_grid = self.guide_grid.clone();
 		let mut widgets = vec![
 			OptionalInput::new(snapping_state.snapping_enabled, "Snapping")
 				.tooltip("Snapping")
@@ -1589,6 +4113,8 @@ impl DocumentMessageHandler {
 						snapping_enabled: Some(snapping_enabled),
 						bounding_box_snapping: Some(snapping_state.bounding_box_snapping),
 						node_snapping: Some(snapping_state.node_snapping),
+						grid_snapping: Some(snapping_state.grid_snapping),
+						grid_spacing: Some(snapping_state.grid_spacing),
 					}
 					.into()
 				})
@@ -1604,6 +4130,8 @@ impl DocumentMessageHandler {
 										snapping_enabled: None,
 										bounding_box_snapping: Some(input.checked),
 										node_snapping: None,
+										grid_snapping: None,
+										grid_spacing: None,
 									}
 									.into()
 								})
@@ -1622,6 +4150,8 @@ impl DocumentMessageHandler {
 										snapping_enabled: None,
 										bounding_box_snapping: None,
 										node_snapping: Some(input.checked),
+										grid_snapping: None,
+										grid_spacing: None,
 									}
 									.into()
 								})
@@ -1630,14 +4160,59 @@ impl DocumentMessageHandler {
 							TextLabel::new(SnappingOptions::Points.to_string()).table_align(false).min_width(60).widget_holder(),
 						],
 					},
+					LayoutGroup::Row {
+						widgets: vec![
+							CheckboxInput::new(self.snapping_state.grid_snapping)
+								.tooltip(SnappingOptions::Grid.to_string())
+								.on_update(|input: &CheckboxInput| {
+									DocumentMessage::SetSnapping {
+										snapping_enabled: None,
+										bounding_box_snapping: None,
+										node_snapping: None,
+										grid_snapping: Some(input.checked),
+										grid_spacing: None,
+									}
+									.into()
+								})
+								.widget_holder(),
+							Separator::new(SeparatorType::Unrelated).widget_holder(),
+							TextLabel::new(SnappingOptions::Grid.to_string()).table_align(false).min_width(60).widget_holder(),
+							Separator::new(SeparatorType::Related).widget_holder(),
+							NumberInput::new(Some(self.snapping_state.grid_spacing))
+								.unit(" px")
+								.min(0.01)
+								.tooltip("Grid spacing")
+								.on_update(|number_input: &NumberInput| {
+									DocumentMessage::SetSnapping {
+										snapping_enabled: None,
+										bounding_box_snapping: None,
+										node_snapping: None,
+										grid_snapping: None,
+										grid_spacing: number_input.value,
+									}
+									.into()
+								})
+								.widget_holder(),
+						],
+					},
 				])
 				.widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
-			OptionalInput::new(true, "Grid")
-				.tooltip("Grid")
-				.on_update(|_| DialogMessage::RequestComingSoonDialog { issue: Some(318) }.into())
+			OptionalInput::new(self.guide_grid.visible, "Guide Grid")
+				.tooltip("Guide Grid")
+				.on_update(move |optional_input: &OptionalInput| {
+					DocumentMessage::SetGuideGrid {
+						guide_grid: GuideGrid {
+							visible: optional_input.checked,
+							..guide_grid.clone()
+						},
+					}
+					.into()
+				})
+				.widget_holder(),
+			PopoverButton::new("Guide Grid", "Isometric and perspective guide settings")
+				.options_widget(self.guide_grid.options_widget())
 				.widget_holder(),
-			PopoverButton::new("Grid", "Coming soon").widget_holder(),
 			Separator::new(SeparatorType::Unrelated).widget_holder(),
 			OptionalInput::new(self.overlays_visible, "Overlays")
 				.tooltip("Overlays")
@@ -1682,10 +4257,10 @@ impl DocumentMessageHandler {
 			IconButton::new("ZoomReset", 24)
 				.tooltip("Zoom to 100%")
 				.tooltip_shortcut(action_keys!(DocumentMessageDiscriminant::ZoomCanvasTo100Percent))
-				.on_update(|_| NavigationMessage::SetCanvasZoom { zoom_factor: 1. }.into())
+				.on_update(|_| NavigationMessage::ZoomCanvasTo { zoom_factor: 1. }.into())
 				.widget_holder(),
 			Separator::new(SeparatorType::Related).widget_holder(),
-			NumberInput::new(Some(self.navigation_handler.snapped_scale() * 100.))
+			NumberInput::new(Some(self.zoom_percentage()))
 				.unit("%")
 				.min(0.000001)
 				.max(1000000.)
@@ -1715,6 +4290,20 @@ impl DocumentMessageHandler {
 						.into()
 					})
 					.widget_holder(),
+				IconButton::new("Reset", 16)
+					.tooltip("Reset Rotation")
+					.on_update(|_| NavigationMessage::ResetCanvasRotation.into())
+					.widget_holder(),
+			]);
+		}
+		if self.navigation_handler.flipped {
+			widgets.extend([
+				Separator::new(SeparatorType::Related).widget_holder(),
+				IconButton::new("FlipHorizontal", 16)
+					.tooltip("The view of the canvas is flipped horizontally (click to unflip it)")
+					.tooltip_shortcut(action_keys!(NavigationMessageDiscriminant::ToggleCanvasFlip))
+					.on_update(|_| NavigationMessage::ToggleCanvasFlip.into())
+					.widget_holder(),
 			]);
 		}
 		widgets.extend([
@@ -1765,6 +4354,11 @@ impl DocumentMessageHandler {
 		let mut blend_mode = None;
 		let mut blend_mode_is_mixed = false;
 
+		// Whether any selected folder exists, and whether every one of them is isolated or a knockout group
+		let mut any_folder = false;
+		let mut all_isolated = true;
+		let mut all_knockout = true;
+
 		self.layer_metadata
 			.keys()
 			.filter_map(|path| self.layer_panel_entry_from_path(path, render_data))
@@ -1788,6 +4382,12 @@ impl DocumentMessageHandler {
 						}
 					}
 				}
+
+				if let Ok(folder) = layer.as_folder() {
+					any_folder = true;
+					all_isolated &= folder.isolated;
+					all_knockout &= folder.knockout;
+				}
 			});
 
 		if opacity_is_mixed {
@@ -1837,6 +4437,20 @@ impl DocumentMessageHandler {
 						}
 					})
 					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				CheckboxInput::new(any_folder && all_isolated)
+					.disabled(!any_folder)
+					.tooltip("Isolate Blending: the blend modes of the layers in the selected folders only mix with each other, not with the artwork below")
+					.on_update(|value: &CheckboxInput| DocumentMessage::SetFolderIsolationForSelectedLayers { isolated: value.checked }.into())
+					.widget_holder(),
+				TextLabel::new("Isolate").disabled(!any_folder).widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				CheckboxInput::new(any_folder && all_knockout)
+					.disabled(!any_folder)
+					.tooltip("Knockout Group: the layers in the selected folders hide the layers below them in the folder instead of blending with them")
+					.on_update(|value: &CheckboxInput| DocumentMessage::SetFolderKnockoutForSelectedLayers { knockout: value.checked }.into())
+					.widget_holder(),
+				TextLabel::new("Knockout").disabled(!any_folder).widget_holder(),
 				Separator::new(SeparatorType::Section).widget_holder(),
 				IconButton::new("Folder", 24)
 					.tooltip("New Folder")
@@ -1910,3 +4524,101 @@ impl DocumentMessageHandler {
 		}
 	}
 }
+
+/// The node graph of a layer showing the image, which can be moved and resized with the layer's transform.
+fn image_layer_network(image: Image<Color>) -> Option<NodeNetwork> {
+	let Some(image_node_type) = crate::messages::portfolio::document::node_graph::resolve_document_node_type("Image") else {
+		warn!("Image node should be in registry");
+		return None;
+	};
+	let Some(transform_node_type) = crate::messages::portfolio::document::node_graph::resolve_document_node_type("Transform") else {
+		warn!("Transform node should be in registry");
+		return None;
+	};
+	let Some(downres_node_type) = crate::messages::portfolio::document::node_graph::resolve_document_node_type("Downres") else {
+		warn!("Downres node should be in registry");
+		return None;
+	};
+
+	let mut network = NodeNetwork::default();
+	network.push_node(
+		image_node_type.to_document_node(
+			[graph_craft::document::NodeInput::value(
+				graph_craft::document::value::TaggedValue::ImageFrame(ImageFrame { image, transform: DAffine2::IDENTITY }),
+				false,
+			)],
+			graph_craft::document::DocumentNodeMetadata::position((8, 4)),
+		),
+		false,
+	);
+	network.push_node(transform_node_type.to_document_node_default_inputs([], Default::default()), true);
+	network.push_node(downres_node_type.to_document_node_default_inputs([], Default::default()), true);
+	network.push_output_node();
+
+	Some(network)
+}
+
+/// Scales and moves geometry with the source bounds so it fits centered within the target bounds, keeping its aspect ratio.
+/// A source with no width or height is fitted by its other side, and one that's a single point is only moved.
+fn fit_within_bounds([source_min, source_max]: [DVec2; 2], [target_min, target_max]: [DVec2; 2]) -> DAffine2 {
+	// Dividing by a side of zero length gives an infinite or undefined ratio, which the smaller ratio of the other side takes precedence over
+	let scale = ((target_max - target_min) / (source_max - source_min)).min_element();
+	let scale = if scale.is_finite() { scale } else { 1. };
+	DAffine2::from_translation((target_min + target_max) / 2.) * DAffine2::from_scale(DVec2::splat(scale)) * DAffine2::from_translation(-(source_min + source_max) / 2.)
+}
+
+/// Fills in the inputs added to node types since the document was saved, throughout the layers of the folder.
+fn add_missing_node_inputs(data: &mut LayerDataType) {
+	match data {
+		LayerDataType::Folder(folder) => folder.layers_mut().iter_mut().for_each(|layer| add_missing_node_inputs(&mut layer.data)),
+		LayerDataType::Layer(layer) => add_missing_inputs(&mut layer.network),
+		_ => {}
+	}
+}
+
+/// Wraps the rendered artwork into a standalone SVG of the given size, with the transform mapping the exported region onto it.
+fn document_svg(size: DVec2, transform: DAffine2, fonts: &str, outside_artboards: &str, artboards: &str, artwork: &str) -> String {
+	let matrix = transform
+		.to_cols_array()
+		.iter()
+		.enumerate()
+		.fold(String::new(), |acc, (i, entry)| acc + &(entry.to_string() + if i == 5 { "" } else { "," }));
+	format!(
+		r#"<svg xmlns="http://www.w3.org/2000/svg" preserveAspectRatio="none" viewBox="0 0 1 1" width="{}" height="{}">{}{fonts}{outside_artboards}<g transform="matrix({matrix})">{artboards}{artwork}</g></svg>"#,
+		size.x, size.y, "\n",
+	)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn fitting_keeps_the_aspect_ratio_and_centers() {
+		// A 20 by 10 source fits a 100 by 100 target by its width, leaving space above and below
+		let fit = fit_within_bounds([DVec2::new(10., 10.), DVec2::new(30., 20.)], [DVec2::ZERO, DVec2::splat(100.)]);
+		assert!(fit.transform_point2(DVec2::new(10., 10.)).abs_diff_eq(DVec2::new(0., 25.), 1e-9));
+		assert!(fit.transform_point2(DVec2::new(30., 20.)).abs_diff_eq(DVec2::new(100., 75.), 1e-9));
+
+		// A taller target than source fits it by its height instead
+		let fit = fit_within_bounds([DVec2::ZERO, DVec2::new(10., 20.)], [DVec2::ZERO, DVec2::new(100., 10.)]);
+		assert!(fit.transform_point2(DVec2::ZERO).abs_diff_eq(DVec2::new(47.5, 0.), 1e-9));
+		assert!(fit.transform_point2(DVec2::new(10., 20.)).abs_diff_eq(DVec2::new(52.5, 10.), 1e-9));
+	}
+
+	#[test]
+	fn fitting_zero_size_bounds_stays_finite() {
+		// A horizontal line is fitted by its width alone
+		let fit = fit_within_bounds([DVec2::ZERO, DVec2::new(10., 0.)], [DVec2::ZERO, DVec2::splat(50.)]);
+		assert!(fit.transform_point2(DVec2::new(10., 0.)).abs_diff_eq(DVec2::new(50., 25.), 1e-9));
+
+		// A single point is moved to the center of the target without being scaled
+		let fit = fit_within_bounds([DVec2::splat(5.); 2], [DVec2::ZERO, DVec2::splat(50.)]);
+		assert_eq!(fit, DAffine2::from_translation(DVec2::splat(20.)));
+
+		// Fitting into a target with no size collapses the geometry onto its center
+		let fit = fit_within_bounds([DVec2::ZERO, DVec2::splat(10.)], [DVec2::splat(8.); 2]);
+		assert!(fit.transform_point2(DVec2::splat(10.)).abs_diff_eq(DVec2::splat(8.), 1e-9));
+		assert!(fit.is_finite());
+	}
+}
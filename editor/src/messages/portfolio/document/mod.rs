@@ -5,6 +5,7 @@ pub mod artboard;
 pub mod navigation;
 pub mod node_graph;
 pub mod overlays;
+pub mod presentation;
 pub mod properties_panel;
 pub mod utility_types;
 
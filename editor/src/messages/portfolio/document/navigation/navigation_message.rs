@@ -9,6 +9,8 @@ use serde::{Deserialize, Serialize};
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum NavigationMessage {
 	// Messages
+	AnimationFrame,
+	AutoScroll,
 	DecreaseCanvasZoom {
 		center_on_mouse: bool,
 	},
@@ -27,6 +29,7 @@ pub enum NavigationMessage {
 		snap_zoom: Key,
 		zoom_from_viewport: Option<DVec2>,
 	},
+	ResetCanvasRotation,
 	RotateCanvasBegin,
 	SetCanvasRotation {
 		angle_radians: f64,
@@ -34,6 +37,7 @@ pub enum NavigationMessage {
 	SetCanvasZoom {
 		zoom_factor: f64,
 	},
+	ToggleCanvasFlip,
 	TransformCanvasEnd,
 	TranslateCanvas {
 		delta: DVec2,
@@ -47,4 +51,9 @@ pub enum NavigationMessage {
 	},
 	WheelCanvasZoom,
 	ZoomCanvasBegin,
+	ZoomCanvasTo {
+		zoom_factor: f64,
+	},
+	/// Flips between the current view and the one before the last change to the zoom, pan, or rotation.
+	ZoomToPreviousView,
 }
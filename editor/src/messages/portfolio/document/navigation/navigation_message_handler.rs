@@ -1,10 +1,11 @@
 use crate::consts::{
-	VIEWPORT_ROTATE_SNAP_INTERVAL, VIEWPORT_SCROLL_RATE, VIEWPORT_ZOOM_LEVELS, VIEWPORT_ZOOM_MIN_FRACTION_COVER, VIEWPORT_ZOOM_MOUSE_RATE, VIEWPORT_ZOOM_SCALE_MAX, VIEWPORT_ZOOM_SCALE_MIN,
-	VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR, VIEWPORT_ZOOM_WHEEL_RATE,
+	VIEWPORT_ANIMATION_DURATION, VIEWPORT_AUTO_SCROLL_MARGIN, VIEWPORT_AUTO_SCROLL_SPEED, VIEWPORT_PAN_INERTIA_DECAY, VIEWPORT_PAN_INERTIA_MAX_REST, VIEWPORT_PAN_INERTIA_MIN_SPEED,
+	VIEWPORT_PAN_INERTIA_STOP_SPEED, VIEWPORT_PREVIOUS_VIEW_SETTLE_TIME, VIEWPORT_ROTATE_SNAP_INTERVAL, VIEWPORT_SCROLL_RATE, VIEWPORT_ZOOM_LEVELS, VIEWPORT_ZOOM_MIN_FRACTION_COVER,
+	VIEWPORT_ZOOM_MOUSE_RATE, VIEWPORT_ZOOM_SCALE_MAX, VIEWPORT_ZOOM_SCALE_MIN, VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR, VIEWPORT_ZOOM_WHEEL_RATE,
 };
 use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeysGroup};
-use crate::messages::input_mapper::utility_types::input_mouse::{ViewportBounds, ViewportPosition};
+use crate::messages::input_mapper::utility_types::input_mouse::{MouseKeys, ViewportBounds, ViewportPosition};
 use crate::messages::prelude::*;
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
@@ -15,21 +16,82 @@ use graphene_core::renderer::format_transform_matrix;
 use glam::{DAffine2, DVec2};
 use serde::{Deserialize, Serialize};
 
+/// The view of the canvas is saved with the document, so it reopens where it was left, while the state of any navigation in progress is not.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NavigationMessageHandler {
 	pub pan: DVec2,
+	#[serde(skip)]
 	panning: bool,
+	#[serde(skip)]
 	snap_tilt: bool,
+	#[serde(skip)]
 	snap_tilt_released: bool,
 
 	pub tilt: f64,
+	#[serde(skip)]
 	tilting: bool,
 
 	pub zoom: f64,
+	#[serde(skip)]
 	zooming: bool,
+	#[serde(skip)]
 	snap_zoom: bool,
 
+	/// Mirrors the view of the canvas horizontally, without changing the artwork, to help spot errors in its proportions.
+	#[serde(default)]
+	pub flipped: bool,
+
+	#[serde(skip)]
 	mouse_position: ViewportPosition,
+	/// When the canvas was last panned by dragging near the edge of the viewport, in milliseconds, or `None` if it isn't being auto-scrolled.
+	#[serde(skip)]
+	auto_scroll_time: Option<f64>,
+	/// The eased change of the view, or the drift of the canvas after a quick pan, which is advanced on each animation frame.
+	#[serde(skip)]
+	animation: Option<CanvasAnimation>,
+	/// When the last animation frame was drawn, in milliseconds, or `None` before the first frame of an animation.
+	#[serde(skip)]
+	animation_time: Option<f64>,
+	/// The recent speed of the pointer while panning, in viewport pixels per second, and when the pointer last moved, in milliseconds.
+	#[serde(skip)]
+	pan_velocity: (DVec2, Option<f64>),
+	/// The settled view before the latest change to it, which "Previous Zoom" returns to.
+	#[serde(skip)]
+	previous_view: Option<CanvasView>,
+	/// When the view last began changing, in milliseconds, to tell a new change from the continuation of a scroll or a run of key presses.
+	#[serde(skip)]
+	last_view_change: Option<f64>,
+}
+
+/// The part of the view of the canvas which is eased between its old and new states.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CanvasView {
+	pan: DVec2,
+	zoom: f64,
+	tilt: f64,
+}
+
+impl CanvasView {
+	/// The view at `progress` between this view and the other, from 0 to 1.
+	/// The zoom changes by the same factor in equal steps, so zooming in and out both appear to move at a steady rate.
+	fn interpolate(self, other: Self, progress: f64) -> Self {
+		if progress >= 1. {
+			return other;
+		}
+		Self {
+			pan: self.pan.lerp(other.pan, progress),
+			zoom: self.zoom * (other.zoom / self.zoom).powf(progress),
+			tilt: self.tilt + (other.tilt - self.tilt) * progress,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CanvasAnimation {
+	/// Easing the view from one state to another, with the milliseconds elapsed so far.
+	Transition { from: CanvasView, to: CanvasView, elapsed: f64 },
+	/// Drifting after a pan was released, at a speed in viewport pixels per second which decays over time.
+	Inertia { velocity: DVec2 },
 }
 
 impl Default for NavigationMessageHandler {
@@ -47,27 +109,105 @@ impl Default for NavigationMessageHandler {
 			zooming: false,
 			snap_zoom: false,
 
+			flipped: false,
+
 			mouse_position: ViewportPosition::default(),
+			auto_scroll_time: None,
+			animation: None,
+			animation_time: None,
+			pan_velocity: (DVec2::ZERO, None),
+			previous_view: None,
+			last_view_change: None,
 		}
 	}
 }
 
-impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPreprocessorMessageHandler, Option<[DVec2; 2]>)> for NavigationMessageHandler {
+impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPreprocessorMessageHandler, Option<[DVec2; 2]>, f64, bool, &[f64])> for NavigationMessageHandler {
 	#[remain::check]
 	fn process_message(
 		&mut self,
 		message: NavigationMessage,
 		responses: &mut VecDeque<Message>,
-		(document, document_bounds, ipp, selection_bounds): (&Document, Option<[DVec2; 2]>, &InputPreprocessorMessageHandler, Option<[DVec2; 2]>),
+		(document, document_bounds, ipp, selection_bounds, pixel_ratio, animate, zoom_levels): (&Document, Option<[DVec2; 2]>, &InputPreprocessorMessageHandler, Option<[DVec2; 2]>, f64, bool, &[f64]),
 	) {
 		use NavigationMessage::*;
 
 		let old_zoom = self.zoom;
 
+		// Any other change to the view takes over from an animation in progress
+		if !matches!(message, AnimationFrame | AutoScroll | PointerMove { .. } | ToggleCanvasFlip | TransformCanvasEnd) {
+			self.animation = None;
+		}
+
+		// Panning by dragging and the panning that keeps the pointer in place while zooming carry on a change which has already begun
+		if !matches!(
+			message,
+			AnimationFrame | AutoScroll | PointerMove { .. } | ToggleCanvasFlip | TransformCanvasEnd | TranslateCanvas { .. } | ZoomToPreviousView
+		) {
+			self.remember_view();
+		}
+
 		#[remain::sorted]
 		match message {
+			AnimationFrame => {
+				let Some(animation) = self.animation.take() else {
+					self.animation_time = None;
+					return;
+				};
+
+				// Start with the length of one frame, and limit long gaps between frames so the view doesn't jump, or finish right away without a clock
+				let now = now_milliseconds();
+				let frame_time = match (now, self.animation_time) {
+					(Some(now), Some(time)) => (now - time).clamp(0., 100.),
+					(Some(_), None) => 1000. / 60.,
+					(None, _) => f64::INFINITY,
+				};
+				self.animation_time = now;
+
+				match animation {
+					CanvasAnimation::Transition { from, to, elapsed } => {
+						let elapsed = elapsed + frame_time;
+						let progress = (elapsed / VIEWPORT_ANIMATION_DURATION).min(1.);
+						self.set_view(from.interpolate(to, ease_out(progress)));
+						if progress < 1. {
+							self.animation = Some(CanvasAnimation::Transition { from, to, elapsed });
+						}
+						self.view_changed(ipp, pixel_ratio, responses);
+					}
+					CanvasAnimation::Inertia { velocity } => {
+						let (delta, velocity) = inertia_step(velocity, frame_time);
+						self.pan += document.root.transform.inverse().transform_vector2(delta);
+						if velocity.length() > VIEWPORT_PAN_INERTIA_STOP_SPEED {
+							self.animation = Some(CanvasAnimation::Inertia { velocity });
+						}
+						responses.add(BroadcastEvent::DocumentIsDirty);
+						self.create_document_transform(&ipp.viewport_bounds, pixel_ratio, responses);
+					}
+				}
+
+				match self.animation {
+					Some(_) => responses.add(FrontendMessage::TriggerAnimationFrame),
+					None => self.animation_time = None,
+				}
+			}
+			AutoScroll => {
+				let dragging = ipp.mouse.mouse_keys.contains(MouseKeys::LEFT) && !(self.panning || self.tilting || self.zooming);
+				let velocity = auto_scroll_velocity(ipp.mouse.position, ipp.viewport_bounds.size());
+				let (true, Some(now)) = (dragging && velocity != DVec2::ZERO, now_milliseconds()) else {
+					self.auto_scroll_time = None;
+					return;
+				};
+
+				// Start with the length of one frame, and limit long gaps between frames so the canvas doesn't jump
+				let elapsed = self.auto_scroll_time.map_or(1000. / 60., |time| (now - time).clamp(0., 100.));
+				self.auto_scroll_time = Some(now);
+
+				responses.add(TranslateCanvas { delta: velocity * elapsed / 1000. });
+				// The pointer hasn't moved, but it's now over a different part of the document, so the tool updates its drag
+				responses.add(InputMapperMessage::PointerMove);
+			}
 			DecreaseCanvasZoom { center_on_mouse } => {
-				let new_scale = *VIEWPORT_ZOOM_LEVELS.iter().rev().find(|scale| **scale < self.zoom).unwrap_or(&self.zoom);
+				let new_scale = *zoom_levels.iter().rev().find(|scale| **scale < self.zoom).unwrap_or(&self.zoom);
 				if center_on_mouse {
 					responses.add(self.center_zoom(ipp.viewport_bounds.size(), new_scale / self.zoom, ipp.mouse.position));
 				}
@@ -88,19 +228,17 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 				let size = 1. / size;
 				let new_scale = size.min_element();
 
-				self.pan += center;
-				self.zoom *= new_scale;
+				let mut view = self.view();
+				view.pan += center;
+				view.zoom *= new_scale;
 
-				self.zoom /= padding_scale_factor.unwrap_or(1.) as f64;
+				view.zoom /= padding_scale_factor.unwrap_or(1.) as f64;
 
-				if self.zoom > 1. && prevent_zoom_past_100 {
-					self.zoom = 1.
+				if view.zoom > 1. && prevent_zoom_past_100 {
+					view.zoom = 1.
 				}
 
-				responses.add(BroadcastEvent::DocumentIsDirty);
-				responses.add(DocumentMessage::DirtyRenderDocumentInOutlineView);
-				responses.add(PortfolioMessage::UpdateDocumentWidgets);
-				self.create_document_transform(&ipp.viewport_bounds, responses);
+				self.transition_to(view, animate, ipp, pixel_ratio, responses);
 			}
 			FitViewportToSelection => {
 				if let Some(bounds) = selection_bounds {
@@ -112,7 +250,7 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 				}
 			}
 			IncreaseCanvasZoom { center_on_mouse } => {
-				let new_scale = *VIEWPORT_ZOOM_LEVELS.iter().find(|scale| **scale > self.zoom).unwrap_or(&self.zoom);
+				let new_scale = *zoom_levels.iter().find(|scale| **scale > self.zoom).unwrap_or(&self.zoom);
 				if center_on_mouse {
 					responses.add(self.center_zoom(ipp.viewport_bounds.size(), new_scale / self.zoom, ipp.mouse.position));
 				}
@@ -126,6 +264,7 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 			} => {
 				if self.panning {
 					let delta = ipp.mouse.position - self.mouse_position;
+					self.track_pan_velocity(delta);
 
 					responses.add(TranslateCanvas { delta });
 				}
@@ -147,6 +286,8 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 						let end_offset = ipp.mouse.position - half_viewport;
 						start_offset.angle_between(end_offset)
 					};
+					// The mirrored view turns the opposite way, so the canvas follows the pointer
+					let rotation = if self.flipped { -rotation } else { rotation };
 
 					responses.add(SetCanvasRotation { angle_radians: self.tilt + rotation });
 				}
@@ -179,6 +320,13 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 
 				self.mouse_position = ipp.mouse.position;
 			}
+			ResetCanvasRotation => {
+				// Turn back the shorter way around
+				self.tilt = (self.tilt + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU) - std::f64::consts::PI;
+
+				let view = CanvasView { tilt: 0., ..self.view() };
+				self.transition_to(view, animate, ipp, pixel_ratio, responses);
+			}
 			RotateCanvasBegin => {
 				responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Default });
 				responses.add(FrontendMessage::UpdateInputHints {
@@ -196,7 +344,7 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 			}
 			SetCanvasRotation { angle_radians } => {
 				self.tilt = angle_radians;
-				self.create_document_transform(&ipp.viewport_bounds, responses);
+				self.create_document_transform(&ipp.viewport_bounds, pixel_ratio, responses);
 				responses.add(BroadcastEvent::DocumentIsDirty);
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
 			}
@@ -206,9 +354,23 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 				responses.add(BroadcastEvent::DocumentIsDirty);
 				responses.add(DocumentMessage::DirtyRenderDocumentInOutlineView);
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
-				self.create_document_transform(&ipp.viewport_bounds, responses);
+				self.create_document_transform(&ipp.viewport_bounds, pixel_ratio, responses);
+			}
+			ToggleCanvasFlip => {
+				self.flipped = !self.flipped;
+				responses.add(BroadcastEvent::DocumentIsDirty);
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+				self.create_document_transform(&ipp.viewport_bounds, pixel_ratio, responses);
 			}
 			TransformCanvasEnd => {
+				if self.panning && animate {
+					if let Some(velocity) = release_velocity(self.pan_velocity, now_milliseconds()) {
+						self.animation = Some(CanvasAnimation::Inertia { velocity });
+						self.animation_time = None;
+						responses.add(FrontendMessage::TriggerAnimationFrame);
+					}
+				}
+
 				self.tilt = self.snapped_angle();
 				self.zoom = self.snapped_scale();
 				responses.add(BroadcastEvent::DocumentIsDirty);
@@ -226,7 +388,7 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 
 				self.pan += transformed_delta;
 				responses.add(BroadcastEvent::DocumentIsDirty);
-				self.create_document_transform(&ipp.viewport_bounds, responses);
+				self.create_document_transform(&ipp.viewport_bounds, pixel_ratio, responses);
 			}
 			TranslateCanvasBegin => {
 				responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Grabbing });
@@ -234,13 +396,14 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 
 				self.panning = true;
 				self.mouse_position = ipp.mouse.position;
+				self.pan_velocity = (DVec2::ZERO, now_milliseconds());
 			}
 			TranslateCanvasByViewportFraction { delta } => {
 				let transformed_delta = document.root.transform.inverse().transform_vector2(delta * ipp.viewport_bounds.size());
 
 				self.pan += transformed_delta;
 				responses.add(BroadcastEvent::DocumentIsDirty);
-				self.create_document_transform(&ipp.viewport_bounds, responses);
+				self.create_document_transform(&ipp.viewport_bounds, pixel_ratio, responses);
 			}
 			WheelCanvasTranslate { use_y_as_x } => {
 				let delta = match use_y_as_x {
@@ -275,6 +438,21 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 				self.zooming = true;
 				self.mouse_position = ipp.mouse.position;
 			}
+			ZoomCanvasTo { zoom_factor } => {
+				let mut zoom = zoom_factor.clamp(VIEWPORT_ZOOM_SCALE_MIN, VIEWPORT_ZOOM_SCALE_MAX);
+				zoom *= Self::clamp_zoom(zoom, document_bounds, old_zoom, ipp);
+
+				let view = CanvasView { zoom, ..self.view() };
+				self.transition_to(view, animate, ipp, pixel_ratio, responses);
+			}
+			ZoomToPreviousView => {
+				let Some(previous_view) = self.previous_view else { return };
+				self.previous_view = Some(self.view());
+				// Whatever changes the view next starts a new change, so toggling again still returns here
+				self.last_view_change = None;
+
+				self.transition_to(previous_view, animate, ipp, pixel_ratio, responses);
+			}
 		}
 	}
 
@@ -291,6 +469,10 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 			TranslateCanvas,
 			TranslateCanvasByViewportFraction,
 			FitViewportToSelection,
+			ToggleCanvasFlip,
+			ZoomCanvasTo,
+			ZoomToPreviousView,
+			ResetCanvasRotation,
 		);
 
 		if self.panning || self.tilting || self.zooming {
@@ -305,6 +487,66 @@ impl MessageHandler<NavigationMessage, (&Document, Option<[DVec2; 2]>, &InputPre
 }
 
 impl NavigationMessageHandler {
+	fn view(&self) -> CanvasView {
+		CanvasView {
+			pan: self.pan,
+			zoom: self.zoom,
+			tilt: self.tilt,
+		}
+	}
+
+	fn set_view(&mut self, CanvasView { pan, zoom, tilt }: CanvasView) {
+		self.pan = pan;
+		self.zoom = zoom;
+		self.tilt = tilt;
+	}
+
+	/// Keeps the current view as the previous one if it has settled, rather than being partway through a change like a scroll or a run of key presses.
+	fn remember_view(&mut self) {
+		let now = now_milliseconds();
+		let settled = match (now, self.last_view_change) {
+			(Some(now), Some(last_change)) => now - last_change > VIEWPORT_PREVIOUS_VIEW_SETTLE_TIME,
+			_ => true,
+		};
+		self.last_view_change = now;
+
+		if settled {
+			self.previous_view = Some(self.view());
+		}
+	}
+
+	/// Moves the view to the given one, easing into it over the next few animation frames if `animate` is set.
+	fn transition_to(&mut self, to: CanvasView, animate: bool, ipp: &InputPreprocessorMessageHandler, pixel_ratio: f64, responses: &mut VecDeque<Message>) {
+		if animate && to != self.view() {
+			self.animation = Some(CanvasAnimation::Transition { from: self.view(), to, elapsed: 0. });
+			self.animation_time = None;
+			responses.add(FrontendMessage::TriggerAnimationFrame);
+		} else {
+			self.set_view(to);
+			self.view_changed(ipp, pixel_ratio, responses);
+		}
+	}
+
+	fn view_changed(&self, ipp: &InputPreprocessorMessageHandler, pixel_ratio: f64, responses: &mut VecDeque<Message>) {
+		responses.add(BroadcastEvent::DocumentIsDirty);
+		responses.add(DocumentMessage::DirtyRenderDocumentInOutlineView);
+		responses.add(PortfolioMessage::UpdateDocumentWidgets);
+		self.create_document_transform(&ipp.viewport_bounds, pixel_ratio, responses);
+	}
+
+	/// Keeps a smoothed measure of how fast the pointer is panning the canvas, so a quick release can set it drifting.
+	fn track_pan_velocity(&mut self, delta: DVec2) {
+		let Some(now) = now_milliseconds() else { return };
+		let (velocity, time) = self.pan_velocity;
+
+		self.pan_velocity = match time {
+			Some(time) if now > time => (velocity.lerp(delta / (now - time) * 1000., 0.5), Some(now)),
+			// Several moves within the same millisecond can't be measured on their own
+			Some(time) => (velocity, Some(time)),
+			None => (DVec2::ZERO, Some(now)),
+		};
+	}
+
 	pub fn snapped_angle(&self) -> f64 {
 		let increment_radians: f64 = VIEWPORT_ROTATE_SNAP_INTERVAL.to_radians();
 		if self.snap_tilt {
@@ -325,35 +567,45 @@ impl NavigationMessageHandler {
 		}
 	}
 
-	pub fn calculate_offset_transform(&self, offset: DVec2) -> DAffine2 {
-		// Try to avoid fractional coordinates to reduce anti aliasing.
-		let scale = self.snapped_scale();
-		let rounded_pan = ((self.pan + offset) * scale).round() / scale - offset;
+	/// The transform from document space to viewport space, where `offset` is half the viewport in document units.
+	/// The zoom is divided by the `pixel_ratio` so that a zoom of 1 shows one document unit per physical screen pixel.
+	pub fn calculate_offset_transform(&self, offset: DVec2, pixel_ratio: f64) -> DAffine2 {
+		// Try to avoid fractional coordinates to reduce anti aliasing, rounding the pan to whole physical pixels.
+		let scale = self.snapped_scale() / pixel_ratio;
+		let physical_scale = self.snapped_scale();
+		let rounded_pan = ((self.pan + offset) * physical_scale).round() / physical_scale - offset;
 
 		// TODO: replace with DAffine2::from_scale_angle_translation and fix the errors
 		let offset_transform = DAffine2::from_translation(offset);
 		let scale_transform = DAffine2::from_scale(DVec2::splat(scale));
 		let angle_transform = DAffine2::from_angle(self.snapped_angle());
 		let translation_transform = DAffine2::from_translation(rounded_pan);
-		scale_transform * offset_transform * angle_transform * translation_transform
+		let transform = scale_transform * offset_transform * angle_transform * translation_transform;
+
+		// Mirror the view across the vertical line through the center of the viewport
+		if self.flipped {
+			let center = offset * scale;
+			return DAffine2::from_translation(center) * DAffine2::from_scale(DVec2::new(-1., 1.)) * DAffine2::from_translation(-center) * transform;
+		}
+		transform
 	}
 
-	fn create_document_transform(&self, viewport_bounds: &ViewportBounds, responses: &mut VecDeque<Message>) {
+	fn create_document_transform(&self, viewport_bounds: &ViewportBounds, pixel_ratio: f64, responses: &mut VecDeque<Message>) {
 		let half_viewport = viewport_bounds.size() / 2.;
-		let scaled_half_viewport = half_viewport / self.snapped_scale();
+		let scaled_half_viewport = half_viewport / (self.snapped_scale() / pixel_ratio);
 		responses.add(DocumentOperation::SetLayerTransform {
 			path: vec![],
-			transform: self.calculate_offset_transform(scaled_half_viewport).to_cols_array(),
+			transform: self.calculate_offset_transform(scaled_half_viewport, pixel_ratio).to_cols_array(),
 		});
 
 		responses.add(ArtboardMessage::DispatchOperation(
 			DocumentOperation::SetLayerTransform {
 				path: vec![],
-				transform: self.calculate_offset_transform(scaled_half_viewport).to_cols_array(),
+				transform: self.calculate_offset_transform(scaled_half_viewport, pixel_ratio).to_cols_array(),
 			}
 			.into(),
 		));
-		let transform = format_transform_matrix(self.calculate_offset_transform(scaled_half_viewport));
+		let transform = format_transform_matrix(self.calculate_offset_transform(scaled_half_viewport, pixel_ratio));
 		responses.add(FrontendMessage::UpdateDocumentTransform { transform });
 		// TODO: Artboard pos
 	}
@@ -378,3 +630,117 @@ impl NavigationMessageHandler {
 		}
 	}
 }
+
+/// Eases out of the animation so the view slows down as it settles into place, for `progress` from 0 to 1.
+fn ease_out(progress: f64) -> f64 {
+	1. - (1. - progress).powi(3)
+}
+
+/// The distance, in viewport pixels, that the drifting canvas moves over the given milliseconds, and its slower velocity afterwards.
+/// The velocity decays exponentially, so a frame of any length moves the canvas as far as the same time split over many frames.
+fn inertia_step(velocity: DVec2, milliseconds: f64) -> (DVec2, DVec2) {
+	let decay = (-milliseconds / VIEWPORT_PAN_INERTIA_DECAY).exp();
+	let distance = velocity * (VIEWPORT_PAN_INERTIA_DECAY / 1000.) * (1. - decay);
+	(distance, velocity * decay)
+}
+
+/// The velocity that the canvas keeps drifting with when a pan is released, if the pointer was still moving quickly when it was let go.
+fn release_velocity((velocity, last_moved): (DVec2, Option<f64>), now: Option<f64>) -> Option<DVec2> {
+	let rested = now? - last_moved?;
+	(rested <= VIEWPORT_PAN_INERTIA_MAX_REST && velocity.length() >= VIEWPORT_PAN_INERTIA_MIN_SPEED).then_some(velocity)
+}
+
+/// The velocity, in viewport pixels per second, to pan the canvas while dragging near the edges of the viewport.
+/// It moves the canvas to reveal more of the side the pointer is approaching, faster the closer the pointer is to the edge.
+fn auto_scroll_velocity(position: ViewportPosition, viewport_size: DVec2) -> DVec2 {
+	let proximity = |distance_to_edge: f64| ((VIEWPORT_AUTO_SCROLL_MARGIN - distance_to_edge) / VIEWPORT_AUTO_SCROLL_MARGIN).clamp(0., 2.);
+	let axis = |position: f64, size: f64| (proximity(position) - proximity(size - position)) * VIEWPORT_AUTO_SCROLL_SPEED;
+
+	if viewport_size.min_element() <= 2. * VIEWPORT_AUTO_SCROLL_MARGIN {
+		return DVec2::ZERO;
+	}
+	DVec2::new(axis(position.x, viewport_size.x), axis(position.y, viewport_size.y))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn auto_scroll_speeds_up_toward_the_edges() {
+		let size = DVec2::new(800., 600.);
+		let speed = VIEWPORT_AUTO_SCROLL_SPEED;
+
+		assert_eq!(auto_scroll_velocity(DVec2::new(400., 300.), size), DVec2::ZERO);
+		assert_eq!(auto_scroll_velocity(DVec2::new(VIEWPORT_AUTO_SCROLL_MARGIN / 2., 300.), size), DVec2::new(speed / 2., 0.));
+		assert_eq!(auto_scroll_velocity(DVec2::new(800., 600.), size), DVec2::new(-speed, -speed));
+		assert_eq!(auto_scroll_velocity(DVec2::new(-1000., 300.), size), DVec2::new(2. * speed, 0.));
+		assert_eq!(auto_scroll_velocity(DVec2::new(0., 0.), DVec2::new(50., 50.)), DVec2::ZERO);
+	}
+
+	#[test]
+	fn zoom_is_interpolated_by_equal_factors() {
+		let from = CanvasView { pan: DVec2::ZERO, zoom: 1., tilt: 1. };
+		let to = CanvasView {
+			pan: DVec2::new(100., -50.),
+			zoom: 4.,
+			tilt: 0.,
+		};
+
+		let halfway = from.interpolate(to, 0.5);
+		assert_eq!(halfway.pan, DVec2::new(50., -25.));
+		assert!((halfway.zoom - 2.).abs() < 1e-9);
+		assert_eq!(halfway.tilt, 0.5);
+		assert_eq!(from.interpolate(to, 1.), to);
+		assert_eq!(ease_out(0.), 0.);
+		assert_eq!(ease_out(1.), 1.);
+		assert!(ease_out(0.5) > 0.5, "the animation starts quickly and slows down");
+	}
+
+	#[test]
+	fn inertia_decays_the_same_regardless_of_frame_rate() {
+		let velocity = DVec2::new(1000., 0.);
+
+		let (whole, whole_velocity) = inertia_step(velocity, 100.);
+		let (first, half_velocity) = inertia_step(velocity, 50.);
+		let (second, split_velocity) = inertia_step(half_velocity, 50.);
+		assert!((whole - (first + second)).length() < 1e-9);
+		assert!((whole_velocity - split_velocity).length() < 1e-9);
+		assert!(whole_velocity.x < velocity.x);
+
+		// Without a clock, the canvas drifts the whole distance at once
+		let (total, stopped) = inertia_step(velocity, f64::INFINITY);
+		assert!((total - velocity * VIEWPORT_PAN_INERTIA_DECAY / 1000.).length() < 1e-9);
+		assert_eq!(stopped, DVec2::ZERO);
+	}
+
+	#[test]
+	fn only_quick_releases_keep_drifting() {
+		let fast = DVec2::new(VIEWPORT_PAN_INERTIA_MIN_SPEED * 2., 0.);
+		let slow = DVec2::new(VIEWPORT_PAN_INERTIA_MIN_SPEED / 2., 0.);
+
+		assert_eq!(release_velocity((fast, Some(1000.)), Some(1010.)), Some(fast));
+		assert_eq!(release_velocity((slow, Some(1000.)), Some(1010.)), None);
+		assert_eq!(release_velocity((fast, Some(1000.)), Some(1000. + VIEWPORT_PAN_INERTIA_MAX_REST * 2.)), None);
+		assert_eq!(release_velocity((fast, None), Some(1000.)), None);
+	}
+
+	#[test]
+	fn flipping_mirrors_the_view_around_the_viewport_center() {
+		let mut navigation = NavigationMessageHandler {
+			pan: DVec2::new(30., -20.),
+			tilt: 0.5,
+			zoom: 2.,
+			..Default::default()
+		};
+		let viewport_size = DVec2::new(400., 300.);
+		let offset = viewport_size / 2. / navigation.zoom;
+		let document_point = DVec2::new(12., 34.);
+
+		let unflipped = navigation.calculate_offset_transform(offset, 1.).transform_point2(document_point);
+		navigation.flipped = true;
+		let flipped = navigation.calculate_offset_transform(offset, 1.).transform_point2(document_point);
+
+		assert!((flipped - DVec2::new(viewport_size.x - unflipped.x, unflipped.y)).length() < 1e-9, "{flipped} mirrors {unflipped}");
+	}
+}
@@ -1,11 +1,14 @@
 use crate::messages::prelude::*;
 
 use graph_craft::document::NodeId;
+use graphene_core::raster::Image;
+use graphene_core::text::Font;
 use graphene_core::uuid::ManipulatorGroupId;
 use graphene_core::vector::brush_stroke::BrushStroke;
 use graphene_core::vector::style::{Fill, Stroke};
+use graphene_core::vector::width_profile::WidthPoint;
 use graphene_core::vector::ManipulatorPointId;
-use graphene_core::Artboard;
+use graphene_core::{Artboard, Color};
 
 use glam::{DAffine2, DVec2, IVec2};
 
@@ -27,6 +30,14 @@ pub enum GraphOperationMessage {
 		layer: LayerIdentifier,
 		stroke: Stroke,
 	},
+	/// Changes only the stroke color, giving the stroke a visible weight if it had none.
+	StrokeColorSet {
+		layer: LayerIdentifier,
+		color: Option<Color>,
+	},
+	SwapFillAndStroke {
+		layer: LayerIdentifier,
+	},
 
 	TransformChange {
 		layer: LayerIdentifier,
@@ -44,6 +55,24 @@ pub enum GraphOperationMessage {
 		layer: LayerIdentifier,
 		pivot: DVec2,
 	},
+	/// Repeats the layer's shape in `count` copies rotated over `angle` degrees about the layer's pivot, or updates its existing radial array.
+	RadialArraySet {
+		layer: LayerIdentifier,
+		count: u32,
+		angle: f64,
+	},
+	/// Varies the layer's stroke width along its path with a "Width Profile" node after its stroke, or updates its existing one.
+	WidthProfileSet {
+		layer: LayerIdentifier,
+		profile: Vec<WidthPoint>,
+	},
+	/// Sets the corner radii of a layer drawn by a "Rectangle Generator", clockwise from the top left, along with the size they are relative to.
+	/// Other layers are left unchanged.
+	RectangleCornersSet {
+		layer: LayerIdentifier,
+		radii: [f64; 4],
+		size: DVec2,
+	},
 
 	Vector {
 		layer: LayerIdentifier,
@@ -53,6 +82,27 @@ pub enum GraphOperationMessage {
 		layer: LayerIdentifier,
 		strokes: Vec<BrushStroke>,
 	},
+	/// Replaces the image shown by an image layer, which is stretched to fill the layer's existing frame.
+	ImageSet {
+		layer: LayerIdentifier,
+		image: Image<Color>,
+	},
+	/// Replaces the font of a text layer. Layers without text are left unchanged.
+	FontSet {
+		layer: LayerIdentifier,
+		font: Font,
+	},
+	/// Replaces the text of a text layer. Layers without text are left unchanged.
+	TextSet {
+		layer: LayerIdentifier,
+		text: String,
+	},
+	/// Replaces the font and size of a text layer. Layers without text are left unchanged.
+	TextStyleSet {
+		layer: LayerIdentifier,
+		font: Font,
+		font_size: f64,
+	},
 
 	NewArtboard {
 		id: NodeId,
@@ -89,4 +139,5 @@ pub enum VectorDataModification {
 	SetManipulatorHandleMirroring { id: ManipulatorGroupId, mirror_angle: bool },
 	SetManipulatorPosition { point: ManipulatorPointId, position: DVec2 },
 	ToggleManipulatorHandleMirroring { id: ManipulatorGroupId },
+	UpdateSubpaths { subpaths: Vec<bezier_rs::Subpath<ManipulatorGroupId>> },
 }
@@ -5,12 +5,15 @@ use document_legacy::document::Document;
 use document_legacy::{LayerId, Operation};
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{generate_uuid, DocumentNode, DocumentNodeMetadata, NodeId, NodeInput, NodeNetwork, NodeOutput};
+use graphene_core::raster::{Image, ImageFrame};
+use graphene_core::text::Font;
 use graphene_core::vector::brush_stroke::BrushStroke;
 use graphene_core::vector::style::{Fill, FillType, Stroke};
-use graphene_core::Artboard;
+use graphene_core::vector::width_profile::WidthPoint;
+use graphene_core::{Artboard, Color};
 use transform_utils::LayerBounds;
 
-use glam::{DAffine2, DVec2, IVec2};
+use glam::{DAffine2, DMat2, DVec2, IVec2};
 
 pub mod transform_utils;
 
@@ -184,6 +187,11 @@ impl<'a> ModifyInputsContext<'a> {
 		} else {
 			self.modify_new_node(name, update_input);
 		}
+		self.finish_modification(skip_rerender, existing_node_id.is_none());
+	}
+
+	/// Refreshes the properties panel and graph after the layer's nodes were changed, and rerenders the layer.
+	fn finish_modification(&mut self, skip_rerender: bool, node_added: bool) {
 		self.node_graph.update_layer_path(Some(self.layer.to_vec()), self.responses);
 		self.node_graph.nested_path.clear();
 		self.responses.add(PropertiesPanelMessage::ResendActiveProperties);
@@ -194,11 +202,51 @@ impl<'a> ModifyInputsContext<'a> {
 		} else {
 			self.responses.add(DocumentMessage::FrameClear);
 		}
-		if existing_node_id.is_none() {
+		if node_added {
 			self.responses.add(NodeGraphMessage::SendGraph { should_rerender: false });
 		}
 	}
 
+	/// Repeats the layer's shape around a point with a "Radial Array" node placed before its transform, so the copies move along with the layer.
+	/// A new array is centered on the layer's pivot, while an existing one keeps its center and only has its count and angle changed.
+	fn radial_array_set(&mut self, count: u32, angle: f64, bounds: LayerBounds) {
+		let update_input = |inputs: &mut Vec<NodeInput>| {
+			inputs[1] = NodeInput::value(TaggedValue::U32(count), false);
+			inputs[2] = NodeInput::value(TaggedValue::F64(angle), false);
+		};
+
+		let existing_node_id = self.network.primary_flow_from_opt(self.layer_node).find(|(node, _)| node.name == "Radial Array").map(|(_, id)| id);
+		if let Some(node_id) = existing_node_id {
+			self.modify_existing_node_inputs(node_id, update_input);
+		} else {
+			let Some((transform_node, transform_id)) = self.network.primary_flow_from_opt(self.layer_node).find(|(node, _)| node.name == "Transform") else {
+				warn!("A radial array needs the layer to have a transform node");
+				return;
+			};
+			let NodeInput::Node { node_id, output_index, .. } = transform_node.inputs[0] else { return };
+			let center = bounds.layerspace_pivot(transform_utils::get_current_normalized_pivot(&transform_node.inputs));
+
+			let Some(node_type) = resolve_document_node_type("Radial Array") else {
+				warn!("Node type \"Radial Array\" doesn't exist");
+				return;
+			};
+			let mut document_node = node_type.to_document_node_default_inputs([], Default::default());
+			update_input(&mut document_node.inputs);
+			document_node.inputs[3] = NodeInput::value(TaggedValue::DVec2(center), false);
+			self.insert_between(NodeOutput::new(node_id, output_index), NodeOutput::new(transform_id, 0), document_node, 0, 0);
+		}
+		self.finish_modification(false, existing_node_id.is_none());
+	}
+
+	fn width_profile_set(&mut self, profile: &[WidthPoint]) {
+		let positions = profile.iter().map(|point| point.position as f32).collect();
+		let widths = profile.iter().map(|point| point.width as f32).collect();
+		self.modify_inputs("Width Profile", false, |inputs| {
+			inputs[1] = NodeInput::value(TaggedValue::VecF32(positions), false);
+			inputs[2] = NodeInput::value(TaggedValue::VecF32(widths), false);
+		});
+	}
+
 	fn fill_set(&mut self, fill: Fill) {
 		self.modify_inputs("Fill", false, |inputs| {
 			let fill_type = match fill {
@@ -233,6 +281,15 @@ impl<'a> ModifyInputsContext<'a> {
 		});
 	}
 
+	fn stroke_color_set(&mut self, color: Option<Color>) {
+		self.modify_inputs("Stroke", false, |inputs| {
+			if color.is_some() && matches!(inputs[2], NodeInput::Value { tagged_value: TaggedValue::F32(weight), .. } if weight == 0.) {
+				inputs[2] = NodeInput::value(TaggedValue::F32(1.), false);
+			}
+			inputs[1] = NodeInput::value(TaggedValue::OptionalColor(color), false);
+		});
+	}
+
 	fn transform_change(&mut self, transform: DAffine2, transform_in: TransformIn, parent_transform: DAffine2, bounds: LayerBounds, skip_rerender: bool) {
 		self.modify_inputs("Transform", skip_rerender, |inputs| {
 			let layer_transform = transform_utils::get_current_transform(inputs);
@@ -241,10 +298,21 @@ impl<'a> ModifyInputsContext<'a> {
 				TransformIn::Scope { scope } => scope * parent_transform,
 				TransformIn::Viewport => parent_transform,
 			};
+
+			// Moves, like nudges, only offset the translation so the rest of the stored transform doesn't pick up rounding error
+			if transform.matrix2 == DMat2::IDENTITY {
+				let offset = to.matrix2.inverse() * transform.translation;
+				if offset.is_finite() {
+					transform_utils::translate_transform(inputs, offset);
+					return;
+				}
+			}
+
 			let pivot = DAffine2::from_translation(bounds.layerspace_pivot(transform_utils::get_current_normalized_pivot(inputs)));
 			let transform = pivot.inverse() * to.inverse() * transform * to * pivot * layer_transform;
 			transform_utils::update_transform(inputs, transform);
 		});
+		self.update_rectangle_size();
 	}
 
 	fn transform_set(&mut self, mut transform: DAffine2, transform_in: TransformIn, parent_transform: DAffine2, current_transform: Option<DAffine2>, bounds: LayerBounds, skip_rerender: bool) {
@@ -270,6 +338,7 @@ impl<'a> ModifyInputsContext<'a> {
 			let transform = pivot.inverse() * to.inverse() * transform * pivot;
 			transform_utils::update_transform(inputs, transform);
 		});
+		self.update_rectangle_size();
 	}
 
 	fn pivot_set(&mut self, new_pivot: DVec2, bounds: LayerBounds) {
@@ -337,12 +406,78 @@ impl<'a> ModifyInputsContext<'a> {
 		self.update_bounds([old_bounds_min, old_bounds_max], [new_bounds_min, new_bounds_max]);
 	}
 
+	fn rectangle_corners_set(&mut self, radii: [f64; 4], size: DVec2) {
+		if !self.network.nodes.values().any(|node| node.name == "Rectangle Generator") {
+			return;
+		}
+
+		self.modify_inputs("Rectangle Generator", false, |inputs| {
+			inputs[1] = NodeInput::value(TaggedValue::DVec2(size), false);
+			for (input, radius) in inputs[2..6].iter_mut().zip(radii) {
+				*input = NodeInput::value(TaggedValue::F64(radius), false);
+			}
+		});
+	}
+
+	/// Keeps the size of a "Rectangle Generator" at how far the layer's transform stretches its unit square, since its corner radii are
+	/// measured in those units and would otherwise be stretched along with the rectangle.
+	fn update_rectangle_size(&mut self) {
+		let find_node = |name: &str| self.network.primary_flow_from_opt(self.layer_node).find(|(node, _)| node.name == name);
+		let (Some((transform_node, _)), Some((generator, generator_id))) = (find_node("Transform"), find_node("Rectangle Generator")) else {
+			return;
+		};
+
+		let transform = transform_utils::get_current_transform(&transform_node.inputs);
+		let size = DVec2::new(transform.matrix2.x_axis.length(), transform.matrix2.y_axis.length());
+		let unchanged = matches!(generator.inputs.get(1), Some(NodeInput::Value { tagged_value: TaggedValue::DVec2(current), .. }) if *current == size);
+		if !unchanged {
+			self.modify_existing_node_inputs(generator_id, |inputs| inputs[1] = NodeInput::value(TaggedValue::DVec2(size), false));
+		}
+	}
+
 	fn brush_modify(&mut self, strokes: Vec<BrushStroke>) {
 		self.modify_inputs("Brush", false, |inputs| {
 			inputs[2] = NodeInput::value(TaggedValue::BrushStrokes(strokes), false);
 		});
 	}
 
+	fn image_set(&mut self, image: Image<Color>) {
+		self.modify_inputs("Image", false, |inputs| {
+			inputs[0] = NodeInput::value(TaggedValue::ImageFrame(ImageFrame { image, transform: DAffine2::IDENTITY }), false);
+		});
+	}
+
+	fn text_set(&mut self, text: String) {
+		if !self.network.nodes.values().any(|node| node.name == "Text") {
+			return;
+		}
+
+		self.modify_inputs("Text", false, |inputs| {
+			inputs[1] = NodeInput::value(TaggedValue::String(text), false);
+		});
+	}
+
+	fn font_set(&mut self, font: Font) {
+		if !self.network.nodes.values().any(|node| node.name == "Text") {
+			return;
+		}
+
+		self.modify_inputs("Text", false, |inputs| {
+			inputs[2] = NodeInput::value(TaggedValue::Font(font), false);
+		});
+	}
+
+	fn text_style_set(&mut self, font: Font, font_size: f64) {
+		if !self.network.nodes.values().any(|node| node.name == "Text") {
+			return;
+		}
+
+		self.modify_inputs("Text", false, |inputs| {
+			inputs[2] = NodeInput::value(TaggedValue::Font(font), false);
+			inputs[3] = NodeInput::value(TaggedValue::F32(font_size as f32), false);
+		});
+	}
+
 	fn resize_artboard(&mut self, location: IVec2, dimensions: IVec2) {
 		self.modify_inputs("Artboard", false, |inputs| {
 			inputs[1] = NodeInput::value(TaggedValue::IVec2(location), false);
@@ -374,6 +509,16 @@ impl<'a> ModifyInputsContext<'a> {
 	}
 }
 
+/// A copy of the stroke with a new color, given a visible weight if it had none.
+fn recolored_stroke(stroke: Option<Stroke>, color: Option<Color>) -> Stroke {
+	let mut stroke = stroke.unwrap_or_default();
+	if color.is_some() && stroke.weight == 0. {
+		stroke.weight = 1.;
+	}
+	stroke.color = color;
+	stroke
+}
+
 impl MessageHandler<GraphOperationMessage, (&mut Document, &mut NodeGraphMessageHandler)> for GraphOperationMessageHandler {
 	fn process_message(&mut self, message: GraphOperationMessage, responses: &mut VecDeque<Message>, (document, node_graph): (&mut Document, &mut NodeGraphMessageHandler)) {
 		match message {
@@ -396,6 +541,39 @@ impl MessageHandler<GraphOperationMessage, (&mut Document, &mut NodeGraphMessage
 					responses.add(Operation::SetLayerStroke { path: layer, stroke });
 				}
 			}
+			GraphOperationMessage::StrokeColorSet { layer, color } => {
+				let stroke = document.layer(&layer).ok().and_then(|layer| layer.style().ok()).and_then(|style| style.stroke());
+				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
+					modify_inputs.stroke_color_set(color);
+				} else {
+					responses.add(Operation::SetLayerStroke {
+						path: layer,
+						stroke: recolored_stroke(stroke, color),
+					});
+				}
+			}
+			GraphOperationMessage::SwapFillAndStroke { layer } => {
+				let Some(style) = document.layer(&layer).ok().and_then(|layer| layer.style().ok()) else { return };
+				let fill_color = match style.fill() {
+					Fill::None => None,
+					Fill::Solid(color) => Some(*color),
+					// A gradient has no single color to give the stroke
+					Fill::Gradient(_) => return,
+				};
+				let stroke = style.stroke();
+				let fill = stroke.as_ref().and_then(|stroke| stroke.color).map_or(Fill::None, Fill::Solid);
+
+				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
+					modify_inputs.fill_set(fill);
+					modify_inputs.stroke_color_set(fill_color);
+				} else {
+					responses.add(Operation::SetLayerFill { path: layer.clone(), fill });
+					responses.add(Operation::SetLayerStroke {
+						path: layer,
+						stroke: recolored_stroke(stroke, fill_color),
+					});
+				}
+			}
 			GraphOperationMessage::TransformChange {
 				layer,
 				transform,
@@ -440,6 +618,17 @@ impl MessageHandler<GraphOperationMessage, (&mut Document, &mut NodeGraphMessage
 					TransformIn::Viewport => Operation::SetLayerTransformInViewport { path: layer, transform },
 				});
 			}
+			GraphOperationMessage::RadialArraySet { layer, count, angle } => {
+				let bounds = LayerBounds::new(document, &layer);
+				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
+					modify_inputs.radial_array_set(count, angle, bounds);
+				}
+			}
+			GraphOperationMessage::WidthProfileSet { layer, profile } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
+					modify_inputs.width_profile_set(&profile);
+				}
+			}
 			GraphOperationMessage::TransformSetPivot { layer, pivot } => {
 				let bounds = LayerBounds::new(document, &layer);
 				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
@@ -449,6 +638,11 @@ impl MessageHandler<GraphOperationMessage, (&mut Document, &mut NodeGraphMessage
 				let pivot = pivot.into();
 				responses.add(Operation::SetPivot { layer_path: layer, pivot });
 			}
+			GraphOperationMessage::RectangleCornersSet { layer, radii, size } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
+					modify_inputs.rectangle_corners_set(radii, size);
+				}
+			}
 			GraphOperationMessage::Vector { layer, modification } => {
 				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
 					modify_inputs.vector_modify(modification);
@@ -459,6 +653,26 @@ impl MessageHandler<GraphOperationMessage, (&mut Document, &mut NodeGraphMessage
 					modify_inputs.brush_modify(strokes);
 				}
 			}
+			GraphOperationMessage::ImageSet { layer, image } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
+					modify_inputs.image_set(image);
+				}
+			}
+			GraphOperationMessage::FontSet { layer, font } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
+					modify_inputs.font_set(font);
+				}
+			}
+			GraphOperationMessage::TextSet { layer, text } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
+					modify_inputs.text_set(text);
+				}
+			}
+			GraphOperationMessage::TextStyleSet { layer, font, font_size } => {
+				if let Some(mut modify_inputs) = ModifyInputsContext::new(&layer, document, node_graph, responses) {
+					modify_inputs.text_style_set(font, font_size);
+				}
+			}
 			GraphOperationMessage::NewArtboard { id, artboard } => {
 				let mut modify_inputs = ModifyInputsContext::new_doc(document, node_graph, responses);
 				if let Some(layer) = modify_inputs.create_layer(id, modify_inputs.network.outputs[0].node_id) {
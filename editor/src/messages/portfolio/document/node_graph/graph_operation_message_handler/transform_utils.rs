@@ -6,6 +6,25 @@ use graph_craft::document::{value::TaggedValue, NodeInput};
 use graphene_core::uuid::ManipulatorGroupId;
 use graphene_core::vector::{ManipulatorPointId, SelectedType};
 
+/// The grid, in steps per unit, that the decomposed parts of a transform are rounded to when stored. It is a power of two so the steps are exact
+/// in floating point, and fine enough (about a billionth) to never be noticed, but coarse enough to swallow the rounding error of recomposing the
+/// transform. Otherwise that error piles up over many small transforms, slowly shifting layers that are nudged or rotated over and over.
+const TRANSFORM_PRECISION: f64 = (1u64 << 30) as f64;
+
+/// Round a decomposed part of a transform to its canonical value, so a transform that is recomposed and decomposed again is stored unchanged.
+fn canonical(value: f64) -> f64 {
+	let rounded = (value * TRANSFORM_PRECISION).round() / TRANSFORM_PRECISION;
+	if rounded.is_finite() {
+		rounded
+	} else {
+		value
+	}
+}
+
+fn canonical_vec(value: DVec2) -> DVec2 {
+	DVec2::new(canonical(value.x), canonical(value.y))
+}
+
 /// Convert an affine transform into the tuple `(scale, angle, translation, shear)` assuming `shear.y = 0`.
 pub fn compute_scale_angle_translation_shear(transform: DAffine2) -> (DVec2, f64, DVec2, DVec2) {
 	let x_axis = transform.matrix2.x_axis;
@@ -35,10 +54,16 @@ pub fn compute_scale_angle_translation_shear(transform: DAffine2) -> (DVec2, f64
 pub fn update_transform(inputs: &mut [NodeInput], transform: DAffine2) {
 	let (scale, angle, translation, shear) = compute_scale_angle_translation_shear(transform);
 
-	inputs[1] = NodeInput::value(TaggedValue::DVec2(translation), false);
+	inputs[1] = NodeInput::value(TaggedValue::DVec2(canonical_vec(translation)), false);
 	inputs[2] = NodeInput::value(TaggedValue::F32(angle as f32), false);
-	inputs[3] = NodeInput::value(TaggedValue::DVec2(scale), false);
-	inputs[4] = NodeInput::value(TaggedValue::DVec2(shear), false);
+	inputs[3] = NodeInput::value(TaggedValue::DVec2(canonical_vec(scale)), false);
+	inputs[4] = NodeInput::value(TaggedValue::DVec2(canonical_vec(shear)), false);
+}
+
+/// Move the transform node by an offset in its parent's space, leaving its rotation, scale, and shear as they are stored rather than recomposing them.
+pub fn translate_transform(inputs: &mut [NodeInput], offset: DVec2) {
+	let translation = get_current_transform(inputs).translation;
+	inputs[1] = NodeInput::value(TaggedValue::DVec2(canonical_vec(translation + offset)), false);
 }
 
 // TODO: This should be extracted from the graph at the location of the transform node.
@@ -184,6 +209,29 @@ fn derive_transform() {
 	}
 }
 
+#[test]
+fn repeated_transforms_do_not_drift() {
+	let mut inputs = vec![NodeInput::value(TaggedValue::None, false); 6];
+	let original = DAffine2::from_scale_angle_translation(DVec2::new(1.5, 0.75), 0.3, DVec2::new(12.25, -40.5));
+	update_transform(&mut inputs, original);
+	let original = get_current_transform(&inputs);
+
+	for _ in 0..1000 {
+		translate_transform(&mut inputs, DVec2::new(0.1, -0.1));
+	}
+	for _ in 0..1000 {
+		translate_transform(&mut inputs, DVec2::new(-0.1, 0.1));
+	}
+	assert!(get_current_transform(&inputs).abs_diff_eq(original, 1e-8));
+
+	let stored = inputs.clone();
+	for _ in 0..1000 {
+		let current = get_current_transform(&inputs);
+		update_transform(&mut inputs, current);
+	}
+	assert_eq!(inputs, stored, "Recomposing a stored transform should leave it unchanged");
+}
+
 /// Expand a bounds to avoid div zero errors
 fn clamp_bounds(bounds_min: DVec2, mut bounds_max: DVec2) -> [DVec2; 2] {
 	let bounds_size = bounds_max - bounds_min;
@@ -314,6 +362,7 @@ impl<'a> VectorModificationState<'a> {
 			VectorDataModification::SetManipulatorHandleMirroring { id, mirror_angle } => self.set_mirror(id, mirror_angle),
 			VectorDataModification::SetManipulatorPosition { point, position } => self.set_position(point, position),
 			VectorDataModification::ToggleManipulatorHandleMirroring { id } => self.toggle_mirror(id),
+			VectorDataModification::UpdateSubpaths { subpaths } => *self.subpaths = subpaths,
 		}
 	}
 }
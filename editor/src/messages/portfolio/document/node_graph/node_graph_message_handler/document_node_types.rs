@@ -1,5 +1,5 @@
 use super::{node_properties, FrontendGraphDataType, FrontendNodeType};
-use crate::consts::{DEFAULT_FONT_FAMILY, DEFAULT_FONT_STYLE};
+use crate::consts::{DEFAULT_CHART_DATA, DEFAULT_FONT_FAMILY, DEFAULT_FONT_STYLE};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::node_graph_executor::NodeGraphExecutor;
 
@@ -1672,15 +1672,145 @@ fn static_nodes() -> Vec<DocumentNodeType> {
 			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
 			..Default::default()
 		},
+		DocumentNodeType {
+			name: "Arrow Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::ArrowGenerator<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Head Length", TaggedValue::F64(0.4), false),
+				DocumentInputType::value("Head Width", TaggedValue::F64(1.), false),
+				DocumentInputType::value("Shaft Width", TaggedValue::F64(0.4), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::arrow_generator_properties,
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Speech Bubble Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::SpeechBubbleGenerator<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Corner Radius", TaggedValue::F64(0.2), false),
+				DocumentInputType::value("Tail Position", TaggedValue::F64(0.3), false),
+				DocumentInputType::value("Tail Length", TaggedValue::F64(0.25), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::speech_bubble_generator_properties,
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Rectangle Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::RectangleGenerator<_, _, _, _, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Size", TaggedValue::DVec2(DVec2::ONE), false),
+				DocumentInputType::value("Top Left Radius", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Top Right Radius", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Bottom Right Radius", TaggedValue::F64(0.), false),
+				DocumentInputType::value("Bottom Left Radius", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::rectangle_generator_properties,
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Gear Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::GearGenerator<_, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Teeth", TaggedValue::U32(12), false),
+				DocumentInputType::value("Tooth Depth", TaggedValue::F64(0.2), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::gear_generator_properties,
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Heart Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::HeartGenerator"),
+			inputs: vec![DocumentInputType::none()],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Placeholder Image Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::PlaceholderImageGenerator"),
+			inputs: vec![DocumentInputType::none()],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "QR Code Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::QrCodeGenerator<_>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Text", TaggedValue::String("https://graphite.rs".to_string()), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::code_generator_properties,
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Barcode Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::BarcodeGenerator<_>"),
+			inputs: vec![DocumentInputType::none(), DocumentInputType::value("Text", TaggedValue::String("Graphite".to_string()), false)],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::code_generator_properties,
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Bar Chart Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::BarChartGenerator<_, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Data", TaggedValue::String(DEFAULT_CHART_DATA.to_string()), false),
+				DocumentInputType::value("Gap", TaggedValue::F64(0.2), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::bar_chart_generator_properties,
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Line Chart Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::LineChartGenerator<_>"),
+			inputs: vec![DocumentInputType::none(), DocumentInputType::value("Data", TaggedValue::String(DEFAULT_CHART_DATA.to_string()), false)],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::line_chart_generator_properties,
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Pie Chart Generator",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::generator_nodes::PieChartGenerator<_, _>"),
+			inputs: vec![
+				DocumentInputType::none(),
+				DocumentInputType::value("Data", TaggedValue::String(DEFAULT_CHART_DATA.to_string()), false),
+				DocumentInputType::value("Inner Radius", TaggedValue::F64(0.), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::pie_chart_generator_properties,
+			..Default::default()
+		},
 		DocumentNodeType {
 			name: "Text",
 			category: "Vector",
-			identifier: NodeImplementation::proto("graphene_core::text::TextGenerator<_, _, _>"),
+			identifier: NodeImplementation::proto("graphene_core::text::TextGenerator<_, _, _, _>"),
 			inputs: vec![
 				DocumentInputType::none(),
 				DocumentInputType::value("Text", TaggedValue::String("hello world".to_string()), false),
 				DocumentInputType::value("Font", TaggedValue::Font(Font::new(DEFAULT_FONT_FAMILY.into(), DEFAULT_FONT_STYLE.into())), false),
 				DocumentInputType::value("Size", TaggedValue::F32(24.), false),
+				DocumentInputType::value("Kerning", TaggedValue::VecF32(Vec::new()), false),
 			],
 			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
 			properties: node_properties::node_section_font,
@@ -1749,6 +1879,33 @@ fn static_nodes() -> Vec<DocumentNodeType> {
 			properties: node_properties::stroke_properties,
 			..Default::default()
 		},
+		DocumentNodeType {
+			name: "Width Profile",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::SetWidthProfileNode<_, _>"),
+			inputs: vec![
+				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
+				DocumentInputType::value("Positions", TaggedValue::VecF32(vec![0., 0.5, 1.]), false),
+				DocumentInputType::value("Widths", TaggedValue::VecF32(vec![0., 1., 0.]), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::width_profile_properties,
+			..Default::default()
+		},
+		DocumentNodeType {
+			name: "Radial Array",
+			category: "Vector",
+			identifier: NodeImplementation::proto("graphene_core::vector::RadialArrayNode<_, _, _>"),
+			inputs: vec![
+				DocumentInputType::value("Vector Data", TaggedValue::VectorData(graphene_core::vector::VectorData::empty()), true),
+				DocumentInputType::value("Count", TaggedValue::U32(6), false),
+				DocumentInputType::value("Angle", TaggedValue::F64(360.), false),
+				DocumentInputType::value("Center", TaggedValue::DVec2(DVec2::ZERO), false),
+			],
+			outputs: vec![DocumentOutputType::new("Vector", FrontendGraphDataType::Subpath)],
+			properties: node_properties::radial_array_properties,
+			..Default::default()
+		},
 		DocumentNodeType {
 			name: "Image Segmentation",
 			category: "Image Adjustments",
@@ -1856,6 +2013,19 @@ pub fn resolve_document_node_type(name: &str) -> Option<&DocumentNodeType> {
 	DOCUMENT_NODE_TYPES.iter().find(|node| node.name == name)
 }
 
+/// Gives the nodes saved before an input was added to their type the default value of every input they're missing, including the nodes
+/// of nested networks, so documents made with earlier versions of a node keep working.
+pub fn add_missing_inputs(network: &mut NodeNetwork) {
+	for node in network.nodes.values_mut() {
+		if let DocumentNodeImplementation::Network(inner_network) = &mut node.implementation {
+			add_missing_inputs(inner_network);
+		}
+		let Some(node_type) = resolve_document_node_type(&node.name) else { continue };
+		let missing = node_type.inputs.iter().skip(node.inputs.len()).map(|input| input.default.clone());
+		node.inputs.extend(missing);
+	}
+}
+
 pub fn collect_node_types() -> Vec<FrontendNodeType> {
 	DOCUMENT_NODE_TYPES
 		.iter()
@@ -2020,6 +2190,27 @@ pub fn new_vector_network(subpaths: Vec<bezier_rs::Subpath<uuid::ManipulatorGrou
 	network
 }
 
+/// Creates a vector layer network whose shape comes from one of the parametric generator nodes, so the shape stays editable after it's placed.
+pub fn new_generated_vector_network(generator: &str, input_override: impl IntoIterator<Item = Option<NodeInput>>) -> NodeNetwork {
+	let generator = resolve_document_node_type(generator).expect("Generator node does not exist");
+	let transform = resolve_document_node_type("Transform").expect("Transform node does not exist");
+	let fill = resolve_document_node_type("Fill").expect("Fill node does not exist");
+	let stroke = resolve_document_node_type("Stroke").expect("Stroke node does not exist");
+	let output = resolve_document_node_type("Output").expect("Output node does not exist");
+
+	let mut network = NodeNetwork {
+		inputs: vec![0],
+		..Default::default()
+	};
+
+	network.push_node(generator.to_document_node_default_inputs(input_override, DocumentNodeMetadata::position((0, 4))), false);
+	network.push_node(transform.to_document_node_default_inputs([None], Default::default()), true);
+	network.push_node(fill.to_document_node_default_inputs([None], Default::default()), true);
+	network.push_node(stroke.to_document_node_default_inputs([None], Default::default()), true);
+	network.push_node(output.to_document_node_default_inputs([None], Default::default()), true);
+	network
+}
+
 pub fn new_text_network(text: String, font: Font, size: f32) -> NodeNetwork {
 	let text_generator = resolve_document_node_type("Text").expect("Text node does not exist");
 	let transform = resolve_document_node_type("Transform").expect("Transform node does not exist");
@@ -2038,6 +2229,7 @@ pub fn new_text_network(text: String, font: Font, size: f32) -> NodeNetwork {
 				NodeInput::value(TaggedValue::String(text), false),
 				NodeInput::value(TaggedValue::Font(font), false),
 				NodeInput::value(TaggedValue::F32(size), false),
+				NodeInput::value(TaggedValue::VecF32(Vec::new()), false),
 			],
 			DocumentNodeMetadata::position((0, 4)),
 		),
@@ -443,7 +443,8 @@ fn gradient_type_widget(document_node: &DocumentNode, node_id: u64, index: usize
 }
 
 fn gradient_row(row: &mut Vec<WidgetHolder>, positions: &Vec<(f64, Option<Color>)>, index: usize, node_id: NodeId, input_index: usize) {
-	let label = TextLabel::new(format!("Gradient: {:.0}%", positions[index].0 * 100.)).tooltip("Adjustable by dragging the gradient stops in the viewport with the Gradient tool active");
+	let label = TextLabel::new(format!("Gradient: {}", number_locale().format_with_unit(positions[index].0 * 100., 0, "%")))
+		.tooltip("Adjustable by dragging the gradient stops in the viewport with the Gradient tool active");
 	row.push(label.widget_holder());
 	let on_update = {
 		let positions = positions.clone();
@@ -941,6 +942,94 @@ pub fn add_properties(document_node: &DocumentNode, node_id: NodeId, _context: &
 	vec![operand("Input", 0), operand("Addend", 1)]
 }
 
+pub fn arrow_generator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let fraction = || NumberInput::default().min(0.).max(1.);
+	let head_length = number_widget(document_node, node_id, 1, "Head Length", fraction(), true);
+	let head_width = number_widget(document_node, node_id, 2, "Head Width", fraction(), true);
+	let shaft_width = number_widget(document_node, node_id, 3, "Shaft Width", fraction(), true);
+
+	vec![
+		LayoutGroup::Row { widgets: head_length },
+		LayoutGroup::Row { widgets: head_width },
+		LayoutGroup::Row { widgets: shaft_width },
+	]
+}
+
+pub fn speech_bubble_generator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let fraction = |max: f64| NumberInput::default().min(0.).max(max);
+	let corner_radius = number_widget(document_node, node_id, 1, "Corner Radius", fraction(0.4), true);
+	let tail_position = number_widget(document_node, node_id, 2, "Tail Position", fraction(1.), true);
+	let tail_length = number_widget(document_node, node_id, 3, "Tail Length", fraction(0.9), true);
+
+	vec![
+		LayoutGroup::Row { widgets: corner_radius },
+		LayoutGroup::Row { widgets: tail_position },
+		LayoutGroup::Row { widgets: tail_length },
+	]
+}
+
+pub fn rectangle_generator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let radius = |index: usize, name: &str| {
+		let widgets = number_widget(document_node, node_id, index, name, NumberInput::default().min(0.).unit(" px"), true);
+		LayoutGroup::Row { widgets }
+	};
+
+	vec![
+		radius(2, "Top Left Radius"),
+		radius(3, "Top Right Radius"),
+		radius(4, "Bottom Right Radius"),
+		radius(5, "Bottom Left Radius"),
+	]
+}
+
+pub fn gear_generator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let teeth = number_widget(document_node, node_id, 1, "Teeth", NumberInput::default().min(3.).max(1000.).int().mode_increment(), true);
+	let tooth_depth = number_widget(document_node, node_id, 2, "Tooth Depth", NumberInput::default().min(0.).max(0.9), true);
+
+	vec![LayoutGroup::Row { widgets: teeth }, LayoutGroup::Row { widgets: tooth_depth }]
+}
+
+pub fn code_generator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let text = text_widget(document_node, node_id, 1, "Text", true);
+
+	vec![LayoutGroup::Row { widgets: text }]
+}
+
+pub fn bar_chart_generator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let data = text_area_widget(document_node, node_id, 1, "Data", true);
+	let gap = number_widget(document_node, node_id, 2, "Gap", NumberInput::default().min(0.).max(0.95), true);
+
+	vec![LayoutGroup::Row { widgets: data }, LayoutGroup::Row { widgets: gap }]
+}
+
+pub fn line_chart_generator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let data = text_area_widget(document_node, node_id, 1, "Data", true);
+
+	vec![LayoutGroup::Row { widgets: data }]
+}
+
+pub fn pie_chart_generator_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let data = text_area_widget(document_node, node_id, 1, "Data", true);
+	let inner_radius = number_widget(document_node, node_id, 2, "Inner Radius", NumberInput::default().min(0.).max(0.95), true);
+
+	vec![LayoutGroup::Row { widgets: data }, LayoutGroup::Row { widgets: inner_radius }]
+}
+
+pub fn radial_array_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let count = number_widget(document_node, node_id, 1, "Count", NumberInput::default().min(1.).max(1000.).int().mode_increment(), true);
+	let angle = number_widget(document_node, node_id, 2, "Angle", NumberInput::default().min(-360.).max(360.).unit("°"), true);
+	let center = vec2_widget(document_node, node_id, 3, "Center", "X", "Y", " px", add_blank_assist);
+
+	vec![LayoutGroup::Row { widgets: count }, LayoutGroup::Row { widgets: angle }, center]
+}
+
+pub fn width_profile_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
+	let positions = vec_f32_input(document_node, node_id, 1, "Positions", TextInput::default().centered(true), true);
+	let widths = vec_f32_input(document_node, node_id, 2, "Widths", TextInput::default().centered(true), true);
+
+	vec![LayoutGroup::Row { widgets: positions }, LayoutGroup::Row { widgets: widths }]
+}
+
 pub fn transform_properties(document_node: &DocumentNode, node_id: NodeId, _context: &mut NodePropertiesContext) -> Vec<LayoutGroup> {
 	let translation_assist = |widgets: &mut Vec<WidgetHolder>| {
 		let pivot_index = 5;
@@ -998,12 +1087,14 @@ pub fn node_section_font(document_node: &DocumentNode, node_id: NodeId, _context
 	let text = text_area_widget(document_node, node_id, 1, "Text", true);
 	let (font, style) = font_inputs(document_node, node_id, 2, "Font", true);
 	let size = number_widget(document_node, node_id, 3, "Size", NumberInput::default().unit(" px").min(1.), true);
+	let kerning = vec_f32_input(document_node, node_id, 4, "Kerning", TextInput::default().centered(true), true);
 
 	let mut result = vec![LayoutGroup::Row { widgets: text }, LayoutGroup::Row { widgets: font }];
 	if let Some(style) = style {
 		result.push(LayoutGroup::Row { widgets: style });
 	}
 	result.push(LayoutGroup::Row { widgets: size });
+	result.push(LayoutGroup::Row { widgets: kerning });
 	result
 }
 
@@ -0,0 +1,40 @@
+use crate::consts::{COLOR_ACCENT, COLOR_HIGH_CONTRAST_HANDLES, COLOR_HIGH_CONTRAST_SELECTION, COLOR_HIGH_CONTRAST_SNAP};
+
+use graphene_core::raster::color::Color;
+
+use serde::{Deserialize, Serialize};
+
+/// The colors used to draw the editor overlays on top of the artwork, configurable in the preferences since the default accent color can be hard to see on some artwork.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct OverlayColors {
+	/// Used for the outlines of selected and hovered layers, bounding boxes, and text frames
+	pub selection_outline: Color,
+	/// Used for anchor points, handles, transform cage grips, the pivot, and gradient controls
+	pub handles: Color,
+	/// Used for the alignment lines and points drawn while snapping
+	pub snap_guides: Color,
+}
+
+impl OverlayColors {
+	pub const DEFAULT: Self = Self {
+		selection_outline: COLOR_ACCENT,
+		handles: COLOR_ACCENT,
+		snap_guides: COLOR_ACCENT,
+	};
+
+	pub const HIGH_CONTRAST: Self = Self {
+		selection_outline: COLOR_HIGH_CONTRAST_SELECTION,
+		handles: COLOR_HIGH_CONTRAST_HANDLES,
+		snap_guides: COLOR_HIGH_CONTRAST_SNAP,
+	};
+
+	pub fn is_high_contrast(&self) -> bool {
+		*self == Self::HIGH_CONTRAST
+	}
+}
+
+impl Default for OverlayColors {
+	fn default() -> Self {
+		Self::DEFAULT
+	}
+}
@@ -0,0 +1,7 @@
+mod presentation_message;
+mod presentation_message_handler;
+
+#[doc(inline)]
+pub use presentation_message::{PresentationMessage, PresentationMessageDiscriminant};
+#[doc(inline)]
+pub use presentation_message_handler::{PresentationMessageHandler, PresentationMessageHandlerData};
@@ -0,0 +1,19 @@
+use crate::messages::prelude::*;
+
+use document_legacy::LayerId;
+
+use serde::{Deserialize, Serialize};
+
+#[remain::sorted]
+#[impl_message(Message, DocumentMessage, Presentation)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub enum PresentationMessage {
+	// Messages
+	Click,
+	Enter,
+	Exit,
+	FitArtboard,
+	NextArtboard,
+	PreviousArtboard,
+	ShowArtboard { artboard: LayerId },
+}
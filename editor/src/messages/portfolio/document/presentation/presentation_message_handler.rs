@@ -0,0 +1,152 @@
+use crate::consts::VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR;
+use crate::messages::portfolio::document::utility_types::prototype_links::PrototypeLinks;
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::hit_targets::pointer_quad;
+
+use document_legacy::document::Document as DocumentLegacy;
+use document_legacy::layers::style::RenderData;
+use document_legacy::LayerId;
+
+use glam::DVec2;
+
+pub struct PresentationMessageHandlerData<'a> {
+	pub document: &'a DocumentLegacy,
+	pub artboards_document: &'a DocumentLegacy,
+	/// The artboards in the order they are presented in
+	pub artboard_ids: &'a [LayerId],
+	pub prototype_links: &'a PrototypeLinks,
+	/// The bounds of the whole document in viewport space, which is presented when it has no artboards
+	pub document_bounds: Option<[DVec2; 2]>,
+	pub overlays_visible: bool,
+	pub ipp: &'a InputPreprocessorMessageHandler,
+	pub render_data: &'a RenderData<'a>,
+}
+
+/// Presents the artboards of the document one at a time, fitted to the viewport without the overlays, tools, and panels around the canvas.
+/// The arrow keys step through the artboards in order and clicking a layer linked to an artboard shows that artboard, so prototypes can be
+/// clicked through. Presenting is driven entirely by its messages, so it can also be scripted.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PresentationMessageHandler {
+	presenting: bool,
+	/// The artboard being presented, which is `None` for a document without artboards
+	artboard: Option<LayerId>,
+	/// Whether the overlays were visible before presenting, so they can be shown again afterwards
+	overlays_were_visible: bool,
+}
+
+impl<'a> MessageHandler<PresentationMessage, PresentationMessageHandlerData<'a>> for PresentationMessageHandler {
+	#[remain::check]
+	fn process_message(&mut self, message: PresentationMessage, responses: &mut VecDeque<Message>, data: PresentationMessageHandlerData<'a>) {
+		use PresentationMessage::*;
+
+		let PresentationMessageHandlerData {
+			document,
+			artboards_document,
+			artboard_ids,
+			prototype_links,
+			document_bounds,
+			overlays_visible,
+			ipp,
+			render_data,
+		} = data;
+
+		#[remain::sorted]
+		match message {
+			Click => {
+				if !self.presenting {
+					return;
+				}
+
+				let hotspot = document.intersects_quad_root(pointer_quad(ipp.mouse.position), render_data).pop();
+				let Some((layer, link)) = hotspot.and_then(|layer| prototype_links.hotspot_link(&layer).map(|link| (layer, link))) else {
+					return;
+				};
+				if artboard_ids.contains(&link.target) {
+					self.artboard = Some(link.target);
+					responses.add(DocumentMessage::FollowPrototypeLink { layer });
+				}
+			}
+			Enter => {
+				if self.presenting {
+					return;
+				}
+
+				self.presenting = true;
+				self.artboard = artboard_ids.first().copied();
+				self.overlays_were_visible = overlays_visible;
+
+				responses.add(ToolMessage::AbortCurrent);
+				responses.add(DocumentMessage::SetOverlaysVisibility { visible: false });
+				responses.add(FrontendMessage::UpdatePresentationMode { presenting: true });
+				responses.add(FitArtboard);
+			}
+			Exit => {
+				if !self.presenting {
+					return;
+				}
+
+				self.presenting = false;
+				responses.add(DocumentMessage::SetOverlaysVisibility { visible: self.overlays_were_visible });
+				responses.add(FrontendMessage::UpdatePresentationMode { presenting: false });
+			}
+			FitArtboard => {
+				if !self.presenting {
+					return;
+				}
+
+				let bounds = match self.artboard {
+					Some(artboard) => artboards_document.viewport_bounding_box(&[artboard], render_data).ok().flatten(),
+					None => document_bounds,
+				};
+				if let Some(bounds) = bounds {
+					responses.add(NavigationMessage::FitViewportToBounds {
+						bounds,
+						padding_scale_factor: Some(VIEWPORT_ZOOM_TO_FIT_PADDING_SCALE_FACTOR),
+						prevent_zoom_past_100: false,
+					});
+				}
+			}
+			NextArtboard => self.step(1, artboard_ids, responses),
+			PreviousArtboard => self.step(-1, artboard_ids, responses),
+			ShowArtboard { artboard } => {
+				if artboard_ids.contains(&artboard) {
+					self.artboard = Some(artboard);
+					responses.add(FitArtboard);
+				}
+			}
+		}
+	}
+
+	fn actions(&self) -> ActionList {
+		if self.presenting {
+			actions!(PresentationMessageDiscriminant;
+				Click,
+				Exit,
+				NextArtboard,
+				PreviousArtboard,
+			)
+		} else {
+			actions!(PresentationMessageDiscriminant;
+				Enter,
+			)
+		}
+	}
+}
+
+impl PresentationMessageHandler {
+	pub fn is_presenting(&self) -> bool {
+		self.presenting
+	}
+
+	/// Moves forward or back through the artboards by the given number of artboards, stopping at the first and last.
+	fn step(&mut self, offset: isize, artboard_ids: &[LayerId], responses: &mut VecDeque<Message>) {
+		if !self.presenting || artboard_ids.is_empty() {
+			return;
+		}
+
+		let index = self.artboard.and_then(|artboard| artboard_ids.iter().position(|&id| id == artboard)).unwrap_or_default();
+		let index = index.saturating_add_signed(offset).min(artboard_ids.len() - 1);
+		self.artboard = Some(artboard_ids[index]);
+		responses.add(PresentationMessage::FitArtboard);
+	}
+}
@@ -1,8 +1,11 @@
 use super::utility_types::TransformOp;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::utility_types::misc::TargetDocument;
+use crate::messages::portfolio::document::utility_types::prototype_links::PrototypeLink;
 use crate::messages::prelude::*;
 
+use document_legacy::layers::auto_layout::AutoLayout;
+use document_legacy::layers::constraints::LayerConstraints;
 use document_legacy::layers::style::{Fill, Stroke};
 use document_legacy::LayerId;
 
@@ -18,9 +21,15 @@ pub enum PropertiesPanelMessage {
 	ClearSelection,
 	Deactivate,
 	Init,
+	ModifyAutoLayout { auto_layout: Option<AutoLayout> },
+	ModifyClickThrough { click_through: bool },
+	ModifyConstraints { constraints: LayerConstraints },
+	ModifyExportVisibility { export_visible: Option<bool> },
 	ModifyFill { fill: Fill },
 	ModifyName { name: String },
 	ModifyPreserveAspect { preserve_aspect: bool },
+	ModifyPrototypeLink { link: Option<PrototypeLink> },
+	ModifyRasterResolution { raster_resolution: Option<f64> },
 	ModifyStroke { stroke: Stroke },
 	ModifyTransform { value: f64, transform_op: TransformOp },
 	ResendActiveProperties,
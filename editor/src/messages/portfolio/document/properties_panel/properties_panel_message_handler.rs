@@ -1,5 +1,6 @@
 use super::utility_functions::{register_artboard_layer_properties, register_artwork_layer_properties, register_document_graph_properties};
 use super::utility_types::PropertiesPanelMessageHandlerData;
+use crate::consts::{RASTER_RESOLUTION_MAX, RASTER_RESOLUTION_MIN};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::properties_panel::utility_functions::apply_transform_operation;
 use crate::messages::portfolio::document::utility_types::misc::TargetDocument;
@@ -26,7 +27,9 @@ impl<'a> MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPane
 			document_name,
 			artwork_document,
 			artboard_document,
+			raster_resolution,
 			selected_layers,
+			prototype_links,
 			node_graph_message_handler,
 			executor,
 		} = data;
@@ -96,10 +99,43 @@ impl<'a> MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPane
 				let (path, _) = self.active_selection.clone().expect("Received update for properties panel with no active layer");
 				self.create_document_operation(Operation::SetLayerName { path, name }, true, responses);
 			}
+			ModifyAutoLayout { auto_layout } => {
+				let (path, _) = self.active_selection.clone().expect("Received update for properties panel with no active layer");
+				self.create_document_operation(Operation::SetFolderAutoLayout { path, auto_layout }, true, responses);
+			}
+			ModifyClickThrough { click_through } => {
+				let (path, _) = self.active_selection.clone().expect("Received update for properties panel with no active layer");
+				self.create_document_operation(Operation::SetLayerClickThrough { path, click_through }, true, responses);
+			}
+			ModifyConstraints { constraints } => {
+				let (path, _) = self.active_selection.clone().expect("Received update for properties panel with no active layer");
+				self.create_document_operation(Operation::SetLayerConstraints { path, constraints }, true, responses);
+			}
+			ModifyExportVisibility { export_visible } => {
+				let (path, _) = self.active_selection.clone().expect("Received update for properties panel with no active layer");
+				self.create_document_operation(Operation::SetLayerExportVisibility { path, export_visible }, true, responses);
+			}
 			ModifyPreserveAspect { preserve_aspect } => {
 				let (layer_path, _) = self.active_selection.clone().expect("Received update for properties panel with no active layer");
 				self.create_document_operation(Operation::SetLayerPreserveAspect { layer_path, preserve_aspect }, true, responses);
 			}
+			ModifyPrototypeLink { link } => {
+				let (layer, _) = self.active_selection.clone().expect("Received update for properties panel with no active layer");
+				responses.add(DocumentMessage::SetPrototypeLink { layer, link });
+			}
+			ModifyRasterResolution { raster_resolution } => {
+				let (path, _) = self.active_selection.clone().expect("Received update for properties panel with no active layer");
+				let raster_resolution = raster_resolution.map(|resolution| resolution.clamp(RASTER_RESOLUTION_MIN, RASTER_RESOLUTION_MAX));
+				self.create_document_operation(
+					Operation::SetLayerRasterResolution {
+						path: path.clone(),
+						raster_resolution,
+					},
+					true,
+					responses,
+				);
+				responses.add(DocumentMessage::InputFrameRasterizeRegionBelowLayer { layer_path: path });
+			}
 			ModifyFill { fill } => {
 				let (path, _) = self.active_selection.clone().expect("Received update for properties panel with no active layer");
 				self.create_document_operation(Operation::SetLayerFill { path, fill }, true, responses);
@@ -141,7 +177,21 @@ impl<'a> MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPane
 					let layer = document.layer(&path).unwrap();
 					match target_document {
 						TargetDocument::Artboard => register_artboard_layer_properties(layer, responses, persistent_data),
-						TargetDocument::Artwork => register_artwork_layer_properties(document, path, layer, responses, persistent_data, node_graph_message_handler, executor),
+						TargetDocument::Artwork => {
+							let prototype_link = prototype_links.get(&path);
+							register_artwork_layer_properties(
+								document,
+								path,
+								layer,
+								responses,
+								persistent_data,
+								node_graph_message_handler,
+								executor,
+								raster_resolution,
+								artboard_document,
+								prototype_link,
+							)
+						}
 					}
 				} else {
 					let context = crate::messages::portfolio::document::node_graph::NodePropertiesContext {
@@ -153,7 +203,7 @@ impl<'a> MessageHandler<PropertiesPanelMessage, (&PersistentData, PropertiesPane
 						executor,
 						network: &artwork_document.document_network,
 					};
-					register_document_graph_properties(context, node_graph_message_handler, document_name);
+					register_document_graph_properties(context, node_graph_message_handler, document_name, raster_resolution);
 				}
 			}
 			UpdateSelectedDocumentProperties => responses.add(PropertiesPanelMessage::SetActiveLayers {
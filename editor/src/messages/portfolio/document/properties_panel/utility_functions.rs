@@ -1,15 +1,23 @@
 use super::utility_types::TransformOp;
 use crate::application::generate_uuid;
+use crate::consts::{RASTER_RESOLUTION_MAX, RASTER_RESOLUTION_MIN};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::node_graph::NodePropertiesContext;
+use crate::messages::portfolio::document::utility_types::outline::layer_font;
+use crate::messages::portfolio::document::utility_types::prototype_links::{PrototypeLink, PrototypeTransition};
 use crate::messages::portfolio::utility_types::PersistentData;
 use crate::messages::prelude::*;
 use crate::node_graph_executor::NodeGraphExecutor;
 
 use document_legacy::document::Document;
+use document_legacy::layers::auto_layout::{AutoLayout, AutoLayoutAlignment, AutoLayoutDirection};
+use document_legacy::layers::constraints::{Constraint, LayerConstraints};
+use document_legacy::layers::folder_style::FolderStyle;
 use document_legacy::layers::layer_info::{Layer, LayerDataType};
 use document_legacy::layers::style::{Fill, Gradient, GradientType, LineCap, LineJoin, RenderData, Stroke, ViewMode};
+use document_legacy::layers::text_style::TextStyle;
 use graphene_core::raster::color::Color;
+use graphene_core::text::Font;
 
 use glam::{DAffine2, DVec2};
 use std::f64::consts::PI;
@@ -191,6 +199,7 @@ pub fn register_artboard_layer_properties(layer: &Layer, responses: &mut VecDequ
 	});
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn register_artwork_layer_properties(
 	document: &Document,
 	layer_path: Vec<document_legacy::LayerId>,
@@ -199,6 +208,9 @@ pub fn register_artwork_layer_properties(
 	persistent_data: &PersistentData,
 	node_graph_message_handler: &NodeGraphMessageHandler,
 	executor: &mut NodeGraphExecutor,
+	document_raster_resolution: f64,
+	artboard_document: &Document,
+	prototype_link: Option<PrototypeLink>,
 ) {
 	let options_bar = vec![LayoutGroup::Row {
 		widgets: vec![
@@ -212,11 +224,29 @@ pub fn register_artwork_layer_properties(
 				.on_update(|text_input: &TextInput| PropertiesPanelMessage::ModifyName { name: text_input.value.clone() }.into())
 				.widget_holder(),
 			Separator::new(SeparatorType::Related).widget_holder(),
-			PopoverButton::new("Additional Options", "Coming soon").widget_holder(),
+			export_visibility_widget(layer.export_visible),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			CheckboxInput::new(layer.click_through)
+				.tooltip("Let clicks and box selections pass through this layer to the layers below, while it is still shown and exported")
+				.on_update(|input: &CheckboxInput| PropertiesPanelMessage::ModifyClickThrough { click_through: input.checked }.into())
+				.widget_holder(),
+			TextLabel::new("Click-Through").widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			CheckboxInput::new(layer.inherit_style)
+				.disabled(matches!(layer.data, LayerDataType::Folder(_)))
+				.tooltip("Take the fill and stroke from the default style of the folders this layer is in, or keep its own style when unchecked")
+				.on_update(|input: &CheckboxInput| DocumentMessage::SetInheritStyleForSelectedLayers { inherit_style: input.checked }.into())
+				.widget_holder(),
+			TextLabel::new("Inherit Style").widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			match &layer.data {
+				LayerDataType::Layer(_) => layer_options_widget(layer.raster_resolution, document_raster_resolution),
+				_ => PopoverButton::new("Additional Options", "Coming soon").widget_holder(),
+			},
 		],
 	}];
 
-	let properties_body = match &layer.data {
+	let mut properties_body = match &layer.data {
 		LayerDataType::Shape(shape) => {
 			if let Some(fill_layout) = node_section_fill(shape.style.fill()) {
 				vec![
@@ -228,8 +258,11 @@ pub fn register_artwork_layer_properties(
 				vec![node_section_transform(layer, persistent_data), node_section_stroke(&shape.style.stroke().unwrap_or_default())]
 			}
 		}
-		LayerDataType::Layer(layer) => {
+		LayerDataType::Layer(layer_layer) => {
 			let mut properties_sections = Vec::new();
+			if layer_font(layer).is_some() {
+				properties_sections.push(node_section_text_style(document, layer.text_style));
+			}
 
 			let mut context = NodePropertiesContext {
 				persistent_data,
@@ -238,16 +271,27 @@ pub fn register_artwork_layer_properties(
 				nested_path: &node_graph_message_handler.nested_path,
 				layer_path: &layer_path,
 				executor,
-				network: &layer.network,
+				network: &layer_layer.network,
 			};
 			node_graph_message_handler.collate_properties(&mut context, &mut properties_sections);
 
 			properties_sections
 		}
-		LayerDataType::Folder(_) => {
-			vec![node_section_transform(layer, persistent_data)]
+		LayerDataType::Folder(folder) => {
+			vec![
+				node_section_transform(layer, persistent_data),
+				node_section_auto_layout(folder.auto_layout),
+				node_section_default_style(&layer_path, &folder.default_style),
+			]
 		}
 	};
+	// Only the layers at the root of the document are on an artboard rather than in a folder
+	if layer_path.len() == 1 {
+		properties_body.push(node_section_constraints(layer.constraints));
+	}
+	if let Some(prototype_section) = node_section_prototype(artboard_document, prototype_link) {
+		properties_body.push(prototype_section);
+	}
 
 	responses.add(LayoutMessage::SendLayout {
 		layout: Layout::WidgetLayout(WidgetLayout::new(options_bar)),
@@ -259,7 +303,56 @@ pub fn register_artwork_layer_properties(
 	});
 }
 
-pub fn register_document_graph_properties(mut context: NodePropertiesContext, node_graph_message_handler: &NodeGraphMessageHandler, document_name: &str) {
+fn export_visibility_widget(export_visible: Option<bool>) -> WidgetHolder {
+	let options = [(None, "Export if Visible"), (Some(true), "Always Export"), (Some(false), "Never Export")];
+	let entries = options
+		.into_iter()
+		.map(|(export_visible, label)| DropdownEntryData::new(label).on_update(move |_| PropertiesPanelMessage::ModifyExportVisibility { export_visible }.into()))
+		.collect();
+	let selected_index = options.iter().position(|&(option, _)| option == export_visible).map(|index| index as u32);
+
+	DropdownInput::new(vec![entries])
+		.selected_index(selected_index)
+		.tooltip("Whether the layer is included in exports, regardless of its visibility on the canvas")
+		.widget_holder()
+}
+
+/// The additional options of a node graph layer, where the resolution its raster effects are rasterized at can override the document's.
+fn layer_options_widget(raster_resolution: Option<f64>, document_raster_resolution: f64) -> WidgetHolder {
+	let options = vec![LayoutGroup::Row {
+		widgets: vec![
+			CheckboxInput::new(raster_resolution.is_some())
+				.tooltip("Rasterize the effects of this layer at a different resolution than the document's")
+				.on_update(move |input: &CheckboxInput| {
+					PropertiesPanelMessage::ModifyRasterResolution {
+						raster_resolution: input.checked.then_some(document_raster_resolution),
+					}
+					.into()
+				})
+				.widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			TextLabel::new("Effects Resolution").table_align(true).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			NumberInput::new(Some(raster_resolution.unwrap_or(document_raster_resolution)))
+				.unit("x")
+				.min(RASTER_RESOLUTION_MIN)
+				.max(RASTER_RESOLUTION_MAX)
+				.disabled(raster_resolution.is_none())
+				.tooltip("Pixels per document pixel at which the artwork below this layer is rasterized before its raster effects are applied")
+				.on_update(|number_input: &NumberInput| {
+					PropertiesPanelMessage::ModifyRasterResolution {
+						raster_resolution: number_input.value,
+					}
+					.into()
+				})
+				.widget_holder(),
+		],
+	}];
+
+	PopoverButton::new("Additional Options", "Layer options").options_widget(options).widget_holder()
+}
+
+pub fn register_document_graph_properties(mut context: NodePropertiesContext, node_graph_message_handler: &NodeGraphMessageHandler, document_name: &str, raster_resolution: f64) {
 	let mut properties_sections = Vec::new();
 	node_graph_message_handler.collate_properties(&mut context, &mut properties_sections);
 	let options_bar = vec![LayoutGroup::Row {
@@ -270,7 +363,26 @@ pub fn register_document_graph_properties(mut context: NodePropertiesContext, no
 				.on_update(|text_input| DocumentMessage::RenameDocument { new_name: text_input.value.clone() }.into())
 				.widget_holder(),
 			Separator::new(SeparatorType::Related).widget_holder(),
-			PopoverButton::new("Additional Options", "Coming soon").widget_holder(),
+			PopoverButton::new("Additional Options", "Document options")
+				.options_widget(vec![LayoutGroup::Row {
+					widgets: vec![
+						TextLabel::new("Effects Resolution").table_align(true).widget_holder(),
+						Separator::new(SeparatorType::Unrelated).widget_holder(),
+						NumberInput::new(Some(raster_resolution))
+							.unit("x")
+							.min(RASTER_RESOLUTION_MIN)
+							.max(RASTER_RESOLUTION_MAX)
+							.tooltip("Pixels per document pixel at which artwork is rasterized before the raster effects of layers are applied, affecting their sharpness and size in exports")
+							.on_update(|number_input: &NumberInput| {
+								DocumentMessage::SetRasterResolution {
+									raster_resolution: number_input.value.unwrap(),
+								}
+								.into()
+							})
+							.widget_holder(),
+					],
+				}])
+				.widget_holder(),
 		],
 	}];
 
@@ -284,6 +396,112 @@ pub fn register_document_graph_properties(mut context: NodePropertiesContext, no
 	});
 }
 
+/// The text style that a text layer is linked to, where editing its font and size restyles every layer linked to it.
+fn node_section_text_style(document: &Document, text_style: Option<u64>) -> LayoutGroup {
+	let linked = text_style.and_then(|id| document.text_styles.iter().position(|text_style| text_style.id == id));
+
+	let entries = std::iter::once(DropdownEntryData::new("None").on_update(|_| DocumentMessage::DetachTextStyle.into()))
+		.chain(document.text_styles.iter().map(|text_style| {
+			let id = text_style.id;
+			DropdownEntryData::new(text_style.name.clone()).on_update(move |_| DocumentMessage::ApplyTextStyle { id }.into())
+		}))
+		.collect();
+
+	let mut layout = vec![LayoutGroup::Row {
+		widgets: vec![
+			TextLabel::new("Style").widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries])
+				.selected_index(Some(linked.map_or(0, |index| index + 1) as u32))
+				.tooltip("Link the selected text layers to a text style, so editing the style restyles all of them")
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			TextButton::new("New")
+				.tooltip("Create a text style from this layer's font and size, linked to the selected text layers")
+				.on_update(|_| DocumentMessage::CreateTextStyle.into())
+				.widget_holder(),
+		],
+	}];
+
+	if let Some(text_style) = linked.map(|index| &document.text_styles[index]) {
+		let update = move |text_style: TextStyle| -> Message { DocumentMessage::UpdateTextStyle { text_style }.into() };
+		let font_update = {
+			let text_style = text_style.clone();
+			move |font_input: &FontInput| {
+				update(TextStyle {
+					font: Font::new(font_input.font_family.clone(), font_input.font_style.clone()),
+					..text_style.clone()
+				})
+			}
+		};
+		let name_update = {
+			let text_style = text_style.clone();
+			move |text_input: &TextInput| {
+				update(TextStyle {
+					name: text_input.value.clone(),
+					..text_style.clone()
+				})
+			}
+		};
+		let size_update = {
+			let text_style = text_style.clone();
+			move |number_input: &NumberInput| {
+				update(TextStyle {
+					font_size: number_input.value.unwrap(),
+					..text_style.clone()
+				})
+			}
+		};
+		let id = text_style.id;
+
+		layout.extend([
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Name").widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextInput::new(text_style.name.clone()).on_update(name_update).widget_holder(),
+				],
+			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Font").widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					FontInput::new(text_style.font.font_family.clone(), text_style.font.font_style.clone())
+						.on_update(font_update.clone())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					FontInput::new(text_style.font.font_family.clone(), text_style.font.font_style.clone())
+						.is_style_picker(true)
+						.on_update(font_update)
+						.widget_holder(),
+				],
+			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Size").widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					NumberInput::new(Some(text_style.font_size)).unit(" px").min(1.).on_update(size_update).widget_holder(),
+				],
+			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextButton::new("Detach")
+						.tooltip("Unlink the selected text layers from this style, keeping their current font and size")
+						.on_update(|_| DocumentMessage::DetachTextStyle.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextButton::new("Delete Style")
+						.tooltip("Remove this style from the document, keeping the font and size of the layers linked to it")
+						.on_update(move |_| DocumentMessage::DeleteTextStyle { id }.into())
+						.widget_holder(),
+				],
+			},
+		]);
+	}
+
+	LayoutGroup::Section { name: "Text Style".into(), layout }
+}
+
 fn node_section_transform(layer: &Layer, persistent_data: &PersistentData) -> LayoutGroup {
 	let render_data = RenderData::new(&persistent_data.font_cache, ViewMode::default(), None);
 	let pivot = layer.transform.transform_vector2(layer.layerspace_pivot(&render_data));
@@ -469,7 +687,7 @@ fn node_gradient_color(gradient: &Gradient, position: usize) -> LayoutGroup {
 	let gradient_3 = gradient_clone.clone();
 	let send_fill_message = move |new_gradient: Gradient| PropertiesPanelMessage::ModifyFill { fill: Fill::Gradient(new_gradient) }.into();
 
-	let value = format!("Gradient: {:.0}%", gradient_clone.positions[position].0 * 100.);
+	let value = format!("Gradient: {}", number_locale().format_with_unit(gradient_clone.positions[position].0 * 100., 0, "%"));
 	let mut widgets = vec![
 		TextLabel::new(value)
 			.tooltip("Adjustable by dragging the gradient stops in the viewport with the Gradient tool active")
@@ -528,6 +746,314 @@ fn node_gradient_color(gradient: &Gradient, position: usize) -> LayoutGroup {
 	LayoutGroup::Row { widgets }
 }
 
+/// A row choosing the constraint along one axis, where `modify` gives the layer's constraints with the chosen one along that axis.
+fn constraint_row(label: &str, tooltip: &str, options: [(Constraint, &str); 5], selected: Constraint, modify: impl Fn(Constraint) -> LayerConstraints + Copy + Send + Sync + 'static) -> LayoutGroup {
+	let entries = options
+		.into_iter()
+		.map(|(constraint, label)| DropdownEntryData::new(label).on_update(move |_| PropertiesPanelMessage::ModifyConstraints { constraints: modify(constraint) }.into()))
+		.collect();
+	let selected_index = options.iter().position(|&(option, _)| option == selected).map(|index| index as u32);
+
+	LayoutGroup::Row {
+		widgets: vec![
+			TextLabel::new(label).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: These three separators add up to 24px,
+			Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: which is the width of the Assist area.
+			Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: Remove these when we have proper entry row formatting that includes room for Assists.
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			DropdownInput::new(vec![entries]).selected_index(selected_index).tooltip(tooltip).widget_holder(),
+		],
+	}
+}
+
+fn node_section_constraints(constraints: LayerConstraints) -> LayoutGroup {
+	LayoutGroup::Section {
+		name: "Constraints".into(),
+		layout: vec![
+			constraint_row(
+				"Horizontal",
+				"How the layer follows the artboard it's on when the artboard is resized horizontally",
+				[
+					(Constraint::Start, "Left"),
+					(Constraint::End, "Right"),
+					(Constraint::StartAndEnd, "Left & Right"),
+					(Constraint::Center, "Center"),
+					(Constraint::Scale, "Scale"),
+				],
+				constraints.horizontal,
+				move |horizontal| LayerConstraints { horizontal, ..constraints },
+			),
+			constraint_row(
+				"Vertical",
+				"How the layer follows the artboard it's on when the artboard is resized vertically",
+				[
+					(Constraint::Start, "Top"),
+					(Constraint::End, "Bottom"),
+					(Constraint::StartAndEnd, "Top & Bottom"),
+					(Constraint::Center, "Center"),
+					(Constraint::Scale, "Scale"),
+				],
+				constraints.vertical,
+				move |vertical| LayerConstraints { vertical, ..constraints },
+			),
+		],
+	}
+}
+
+/// The artboard the layer links to when it's clicked as a hotspot of a prototype, shown once the document has artboards to link to.
+fn node_section_prototype(artboard_document: &Document, link: Option<PrototypeLink>) -> Option<LayoutGroup> {
+	let artboard_ids = &artboard_document.root.as_folder().ok()?.layer_ids;
+	if artboard_ids.is_empty() {
+		return None;
+	}
+	let transition = link.map(|link| link.transition).unwrap_or_default();
+
+	let no_link = DropdownEntryData::new("None").on_update(|_| PropertiesPanelMessage::ModifyPrototypeLink { link: None }.into());
+	let artboard_entries = artboard_ids.iter().enumerate().map(|(index, &target)| {
+		let name = artboard_document.layer(&[target]).ok().and_then(|artboard| artboard.name.clone());
+		DropdownEntryData::new(name.unwrap_or_else(|| format!("Artboard {}", index + 1))).on_update(move |_| {
+			PropertiesPanelMessage::ModifyPrototypeLink {
+				link: Some(PrototypeLink { target, transition }),
+			}
+			.into()
+		})
+	});
+	let selected_index = match link {
+		Some(link) => artboard_ids.iter().position(|&id| id == link.target).map(|index| index as u32 + 1),
+		None => Some(0),
+	};
+
+	let transition_entries = [(PrototypeTransition::Instant, "Instant"), (PrototypeTransition::Smooth, "Smooth")]
+		.into_iter()
+		.map(|(transition, label)| {
+			RadioEntryData::new(label).on_update(move |_| {
+				let link = link.map(|link| PrototypeLink { transition, ..link });
+				PropertiesPanelMessage::ModifyPrototypeLink { link }.into()
+			})
+		})
+		.collect();
+
+	Some(LayoutGroup::Section {
+		name: "Prototype".into(),
+		layout: vec![
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Link To").widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: These three separators add up to 24px,
+					Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: which is the width of the Assist area.
+					Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: Remove these when we have proper entry row formatting that includes room for Assists.
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					DropdownInput::new(vec![vec![no_link], artboard_entries.collect()])
+						.selected_index(selected_index)
+						.tooltip("The artboard shown when this layer is clicked while presenting the artboards as a prototype")
+						.widget_holder(),
+				],
+			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Transition").widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: These three separators add up to 24px,
+					Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: which is the width of the Assist area.
+					Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: Remove these when we have proper entry row formatting that includes room for Assists.
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					RadioInput::new(transition_entries).selected_index(transition as u32).disabled(link.is_none()).widget_holder(),
+				],
+			},
+		],
+	})
+}
+
+fn node_section_auto_layout(auto_layout: Option<AutoLayout>) -> LayoutGroup {
+	let enabled = auto_layout.is_some();
+	let layout = auto_layout.unwrap_or_default();
+	let modify = move |auto_layout: AutoLayout| -> Message { PropertiesPanelMessage::ModifyAutoLayout { auto_layout: Some(auto_layout) }.into() };
+
+	let row = |label: &str, widget: WidgetHolder| LayoutGroup::Row {
+		widgets: vec![
+			TextLabel::new(label).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: These three separators add up to 24px,
+			Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: which is the width of the Assist area.
+			Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: Remove these when we have proper entry row formatting that includes room for Assists.
+			Separator::new(SeparatorType::Unrelated).widget_holder(),
+			widget,
+		],
+	};
+
+	let direction_entries = [(AutoLayoutDirection::Row, "Row"), (AutoLayoutDirection::Column, "Column")]
+		.into_iter()
+		.map(|(direction, label)| RadioEntryData::new(label).on_update(move |_| modify(AutoLayout { direction, ..layout })))
+		.collect();
+	let alignment_entries = [(AutoLayoutAlignment::Start, "Start"), (AutoLayoutAlignment::Center, "Center"), (AutoLayoutAlignment::End, "End")]
+		.into_iter()
+		.map(|(alignment, label)| RadioEntryData::new(label).on_update(move |_| modify(AutoLayout { alignment, ..layout })))
+		.collect();
+
+	let mut rows = vec![row(
+		"Arrange Layers",
+		CheckboxInput::new(enabled)
+			.tooltip("Place the layers of this folder one after another in a row or column, moving them whenever they change size or order")
+			.on_update(move |input: &CheckboxInput| {
+				PropertiesPanelMessage::ModifyAutoLayout {
+					auto_layout: input.checked.then_some(layout),
+				}
+				.into()
+			})
+			.widget_holder(),
+	)];
+	if enabled {
+		rows.extend([
+			row("Direction", RadioInput::new(direction_entries).selected_index(layout.direction as u32).widget_holder()),
+			row(
+				"Gap",
+				NumberInput::new(Some(layout.gap))
+					.unit(" px")
+					.on_update(move |number_input: &NumberInput| {
+						modify(AutoLayout {
+							gap: number_input.value.unwrap(),
+							..layout
+						})
+					})
+					.widget_holder(),
+			),
+			row(
+				"Padding",
+				NumberInput::new(Some(layout.padding))
+					.unit(" px")
+					.on_update(move |number_input: &NumberInput| {
+						modify(AutoLayout {
+							padding: number_input.value.unwrap(),
+							..layout
+						})
+					})
+					.widget_holder(),
+			),
+			row("Alignment", RadioInput::new(alignment_entries).selected_index(layout.alignment as u32).widget_holder()),
+		]);
+	}
+
+	LayoutGroup::Section {
+		name: "Auto Layout".into(),
+		layout: rows,
+	}
+}
+
+/// The fill and stroke a folder gives to the layers within it which inherit their style, each of which can be left to the folders above.
+fn node_section_default_style(path: &[document_legacy::LayerId], default_style: &FolderStyle) -> LayoutGroup {
+	let modify = {
+		let path = path.to_vec();
+		move |default_style: FolderStyle| -> Message { DocumentMessage::SetFolderDefaultStyle { path: path.clone(), default_style }.into() }
+	};
+
+	let row = |label: &str, widgets: Vec<WidgetHolder>| LayoutGroup::Row {
+		widgets: [
+			vec![
+				TextLabel::new(label).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: These three separators add up to 24px,
+				Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: which is the width of the Assist area.
+				Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: Remove these when we have proper entry row formatting that includes room for Assists.
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+			],
+			widgets,
+		]
+		.concat(),
+	};
+
+	let fill = default_style.fill.clone();
+	let stroke = default_style.stroke.clone();
+	let fill_color = if let Some(Fill::Solid(color)) = &fill { Some(*color) } else { None };
+	let stroke_color = stroke.as_ref().and_then(Stroke::color);
+	let stroke_weight = stroke.as_ref().map_or(1., Stroke::weight);
+
+	let fill_row = row(
+		"Fill",
+		vec![
+			CheckboxInput::new(fill.is_some())
+				.tooltip("Give a fill to the layers which inherit their style, rather than leaving it to the folders above")
+				.on_update({
+					let (default_style, modify) = (default_style.clone(), modify.clone());
+					move |input: &CheckboxInput| {
+						let fill = input.checked.then(|| Fill::Solid(Color::BLACK));
+						modify(FolderStyle { fill, ..default_style.clone() })
+					}
+				})
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			ColorInput::new(fill_color)
+				.on_update({
+					let (default_style, modify) = (default_style.clone(), modify.clone());
+					move |color_input: &ColorInput| {
+						let fill = color_input.value.map_or(Fill::None, Fill::Solid);
+						modify(FolderStyle {
+							fill: Some(fill),
+							..default_style.clone()
+						})
+					}
+				})
+				.widget_holder(),
+		],
+	);
+
+	let stroke_row = row(
+		"Stroke",
+		vec![
+			CheckboxInput::new(stroke.is_some())
+				.tooltip("Give a stroke to the layers which inherit their style, rather than leaving it to the folders above")
+				.on_update({
+					let (default_style, modify) = (default_style.clone(), modify.clone());
+					move |input: &CheckboxInput| {
+						let stroke = input.checked.then(|| Stroke::new(Some(Color::BLACK), stroke_weight));
+						modify(FolderStyle { stroke, ..default_style.clone() })
+					}
+				})
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			ColorInput::new(stroke_color)
+				.on_update({
+					let (default_style, modify) = (default_style.clone(), modify.clone());
+					move |color_input: &ColorInput| {
+						let stroke = default_style.stroke.clone().unwrap_or_else(|| Stroke::new(None, stroke_weight));
+						modify(FolderStyle {
+							stroke: stroke.with_color(&color_input.value),
+							..default_style.clone()
+						})
+					}
+				})
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(stroke_weight))
+				.is_integer(false)
+				.min(0.)
+				.unit(" px")
+				.disabled(stroke.is_none())
+				.on_update({
+					let default_style = default_style.clone();
+					move |number_input: &NumberInput| {
+						let stroke = default_style
+							.stroke
+							.clone()
+							.unwrap_or_else(|| Stroke::new(Some(Color::BLACK), 1.))
+							.with_weight(number_input.value.unwrap());
+						modify(FolderStyle {
+							stroke: Some(stroke),
+							..default_style.clone()
+						})
+					}
+				})
+				.widget_holder(),
+		],
+	);
+
+	LayoutGroup::Section {
+		name: "Default Style".into(),
+		layout: vec![fill_row, stroke_row],
+	}
+}
+
 fn node_section_fill(fill: &Fill) -> Option<LayoutGroup> {
 	let initial_color = if let Fill::Solid(color) = fill { *color } else { Color::BLACK };
 
@@ -649,6 +1175,7 @@ fn node_section_stroke(stroke: &Stroke) -> LayoutGroup {
 	let internal_stroke9 = stroke.clone();
 	let internal_stroke10 = stroke.clone();
 	let internal_stroke11 = stroke.clone();
+	let internal_stroke12 = stroke.clone();
 
 	LayoutGroup::Section {
 		name: "Stroke".into(),
@@ -732,6 +1259,27 @@ fn node_section_stroke(stroke: &Stroke) -> LayoutGroup {
 						.widget_holder(),
 				],
 			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Dash Speed").widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: These three separators add up to 24px,
+					Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: which is the width of the Assist area.
+					Separator::new(SeparatorType::Unrelated).widget_holder(), // TODO: Remove these when we have proper entry row formatting that includes room for Assists.
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					NumberInput::new(Some(stroke.dash_speed()))
+						.is_integer(false)
+						.unit(" px/s")
+						.tooltip("Marches the dashes along the path, backwards if negative, as previewed in the viewport and kept in SVG exports")
+						.on_update(move |number_input: &NumberInput| {
+							PropertiesPanelMessage::ModifyStroke {
+								stroke: internal_stroke12.clone().with_dash_speed(number_input.value.unwrap()),
+							}
+							.into()
+						})
+						.widget_holder(),
+				],
+			},
 			LayoutGroup::Row {
 				widgets: vec![
 					TextLabel::new("Line Cap").widget_holder(),
@@ -1,3 +1,5 @@
+use crate::messages::portfolio::document::utility_types::prototype_links::PrototypeLinks;
+
 use document_legacy::document::Document as DocumentLegacy;
 use document_legacy::LayerId;
 
@@ -9,7 +11,11 @@ pub struct PropertiesPanelMessageHandlerData<'a> {
 	pub document_name: &'a str,
 	pub artwork_document: &'a DocumentLegacy,
 	pub artboard_document: &'a DocumentLegacy,
+	/// The resolution, in pixels per document pixel, that raster effects are rasterized at unless a layer overrides it
+	pub raster_resolution: f64,
 	pub selected_layers: &'a mut dyn Iterator<Item = &'a [LayerId]>,
+	/// The links from layers to artboards, shown in the properties of the linked layers
+	pub prototype_links: &'a PrototypeLinks,
 	pub node_graph_message_handler: &'a NodeGraphMessageHandler,
 	pub executor: &'a mut NodeGraphExecutor,
 }
@@ -0,0 +1,70 @@
+use document_legacy::boolean_ops::{composite_boolean_operation, BooleanOperation, BooleanOperationError};
+use document_legacy::layers::shape_layer::ShapeLayer;
+use document_legacy::layers::style::PathStyle;
+use document_legacy::LayerId;
+use graphene_core::uuid::ManipulatorGroupId;
+use graphene_core::vector::subpath::Subpath as LegacySubpath;
+
+use bezier_rs::{ManipulatorGroup, Subpath};
+use std::cell::RefCell;
+
+/// The outline of a shape, as subpaths, along with its style.
+pub type StyledShape = (Vec<Subpath<ManipulatorGroupId>>, PathStyle);
+
+/// Boolean operations on shapes with more points than this in total are only previewed where they can be computed in the background,
+/// since computing them on the spot would hold up the pointer.
+pub const BOOLEAN_PREVIEW_MAX_POINTS: usize = 2000;
+
+/// The live preview of a boolean operation's result, shown while the button of the operation is hovered.
+#[derive(Debug, Clone, Default)]
+pub struct BooleanPreview {
+	/// The render worker job computing the previewed result. The results of older jobs, whose previews were since cancelled or replaced, are ignored.
+	pub job: Option<u64>,
+	/// The overlay showing the result.
+	pub overlay: Option<Vec<LayerId>>,
+}
+
+/// Combines shapes, each made of the subpaths and style of one layer and listed from the top of the stacking order down, into the
+/// outline resulting from the boolean operation. Returns its subpaths, in the same space as the given ones, along with its style.
+pub fn combine_shapes(operation: BooleanOperation, shapes: Vec<StyledShape>) -> Result<StyledShape, BooleanOperationError> {
+	if shapes.len() < 2 {
+		return Err(BooleanOperationError::InvalidSelection);
+	}
+
+	let mut shapes = shapes
+		.into_iter()
+		.map(|(subpaths, style)| RefCell::new(ShapeLayer::new(to_legacy_subpath(&subpaths), style)))
+		.collect::<Vec<_>>();
+	let results = composite_boolean_operation(operation, &mut shapes)?;
+
+	let style = results.first().ok_or(BooleanOperationError::NoResult)?.style.clone();
+	let subpaths = results.iter().flat_map(|shape| shape.shape.to_bezier_rs()).collect::<Vec<_>>();
+	if subpaths.is_empty() {
+		return Err(BooleanOperationError::NoResult);
+	}
+
+	Ok((subpaths, style))
+}
+
+/// Closes every subpath, since boolean operations act on the filled areas, and drops handles that sit on their anchors so straight
+/// segments are intersected as lines.
+fn to_legacy_subpath(subpaths: &[Subpath<ManipulatorGroupId>]) -> LegacySubpath {
+	let subpaths = subpaths
+		.iter()
+		.filter(|subpath| subpath.len() > 1)
+		.map(|subpath| {
+			let manipulator_groups = subpath
+				.manipulator_groups()
+				.iter()
+				.map(|group| ManipulatorGroup {
+					in_handle: group.in_handle.filter(|&handle| handle != group.anchor),
+					out_handle: group.out_handle.filter(|&handle| handle != group.anchor),
+					..group.clone()
+				})
+				.collect();
+			Subpath::new(manipulator_groups, true)
+		})
+		.collect::<Vec<_>>();
+
+	LegacySubpath::from_bezier_rs(&subpaths)
+}
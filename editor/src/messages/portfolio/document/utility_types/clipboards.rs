@@ -1,6 +1,7 @@
 use super::layer_panel::LayerMetadata;
 
 use document_legacy::layers::layer_info::Layer;
+use document_legacy::LayerId;
 
 use serde::{Deserialize, Serialize};
 
@@ -16,8 +17,24 @@ pub enum Clipboard {
 
 pub const INTERNAL_CLIPBOARD_COUNT: u8 = Clipboard::_InternalClipboardCount as u8;
 
+/// Where pasted layers are placed in the document.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default, specta::Type)]
+pub enum PasteMode {
+	/// Next to the selected layers, at the same coordinates they were copied from.
+	#[default]
+	InPlace,
+	/// At the top of the selected folder, at the same coordinates they were copied from.
+	IntoFolder,
+	/// Next to the selected layers, centered on the pointer.
+	AtPointer,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CopyBufferEntry {
 	pub layer: Layer,
 	pub layer_metadata: LayerMetadata,
+	/// The path the layer was copied from. Layers are moved by copying them and pasting them where they go, so this lets what's kept
+	/// about the layer by its path follow it there.
+	#[serde(skip)]
+	pub source: Option<Vec<LayerId>>,
 }
@@ -0,0 +1,209 @@
+//! Filling in a template's placeholders with each row of a table, to make batches of badges, certificates, tickets, and the like.
+//!
+//! Text placeholders are written as `{{Column}}` anywhere in a text layer. An image placeholder is an image layer named `{{Column}}`,
+//! whose image is replaced by the one at the URL given in that column.
+
+use document_legacy::layers::layer_info::{Layer, LayerDataType};
+use document_legacy::layers::layer_layer::CachedOutputData;
+use document_legacy::LayerId;
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::NodeInput;
+
+use glam::DVec2;
+
+/// The rows of data to merge, read from CSV whose first row names the columns.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MergeTable {
+	pub columns: Vec<String>,
+	pub rows: Vec<Vec<String>>,
+}
+
+impl MergeTable {
+	/// Reads CSV with fields separated by commas, where fields containing commas, quotes, or line breaks are wrapped in double quotes
+	/// and quotes inside them are doubled. Blank lines are skipped. Returns `None` if there is no header row naming the columns.
+	pub fn from_csv(csv: &str) -> Option<Self> {
+		let mut records = Vec::new();
+		let mut record = Vec::new();
+		let mut field = String::new();
+		let mut quoted = false;
+
+		let mut chars = csv.chars().peekable();
+		while let Some(character) = chars.next() {
+			match character {
+				'"' if quoted && chars.peek() == Some(&'"') => {
+					field.push('"');
+					chars.next();
+				}
+				'"' if quoted => quoted = false,
+				'"' if field.trim().is_empty() => {
+					field.clear();
+					quoted = true;
+				}
+				',' if !quoted => record.push(std::mem::take(&mut field)),
+				'\r' if !quoted && chars.peek() == Some(&'\n') => {}
+				'\n' if !quoted => {
+					record.push(std::mem::take(&mut field));
+					records.push(std::mem::take(&mut record));
+				}
+				_ => field.push(character),
+			}
+		}
+		record.push(field);
+		records.push(record);
+
+		let mut records = records
+			.into_iter()
+			.map(|record| record.into_iter().map(|field| field.trim().to_string()).collect::<Vec<_>>())
+			.filter(|record| record.iter().any(|field| !field.is_empty()));
+
+		let columns = records.next()?;
+		let rows = records.collect();
+		Some(Self { columns, rows })
+	}
+
+	/// The value in a row for the named column, which is empty if the row is missing that field.
+	pub fn value<'a>(&'a self, row: &'a [String], column: &str) -> Option<&'a str> {
+		let index = self.columns.iter().position(|name| name == column)?;
+		Some(row.get(index).map_or("", String::as_str))
+	}
+
+	/// Replaces each `{{Column}}` placeholder in the text with the row's value for that column. Placeholders naming a column that
+	/// isn't in the table are left as they are. Returns `None` if the text has no placeholders to fill in.
+	pub fn fill_placeholders(&self, text: &str, row: &[String]) -> Option<String> {
+		let mut filled = String::with_capacity(text.len());
+		let mut replaced = false;
+
+		let mut rest = text;
+		while let Some(start) = rest.find("{{") {
+			let Some(length) = rest[start + 2..].find("}}") else { break };
+
+			let column = rest[start + 2..start + 2 + length].trim();
+			let end = start + 2 + length + 2;
+			filled.push_str(&rest[..start]);
+			match self.value(row, column) {
+				Some(value) => {
+					filled.push_str(value);
+					replaced = true;
+				}
+				None => filled.push_str(&rest[start..end]),
+			}
+			rest = &rest[end..];
+		}
+		filled.push_str(rest);
+
+		replaced.then_some(filled)
+	}
+}
+
+/// The column named by an image placeholder's layer name, like `Photo` for a layer named `{{Photo}}`.
+pub fn placeholder_column(layer_name: &str) -> Option<&str> {
+	let column = layer_name.trim().strip_prefix("{{")?.strip_suffix("}}")?.trim();
+	(!column.is_empty()).then_some(column)
+}
+
+/// Fills in the text placeholders of a copy of a template layer and its descendants with the row's values, and collects the paths
+/// (continuing from `path`) and URLs of its image placeholders, whose images must be fetched before they can be filled in.
+pub fn fill_layer_placeholders(layer: &mut Layer, table: &MergeTable, row: &[String], path: &mut Vec<LayerId>, images: &mut Vec<(Vec<LayerId>, String)>) {
+	let image_url = layer.name.as_deref().and_then(placeholder_column).and_then(|column| table.value(row, column));
+
+	match &mut layer.data {
+		LayerDataType::Folder(folder) => {
+			let ids = folder.layer_ids.clone();
+			for (id, child) in ids.into_iter().zip(folder.layers_mut()) {
+				path.push(id);
+				fill_layer_placeholders(child, table, row, path, images);
+				path.pop();
+			}
+		}
+		LayerDataType::Layer(layer_layer) => {
+			let mut changed = false;
+			for node in layer_layer.network.nodes.values_mut() {
+				match (node.name.as_str(), node.inputs.get_mut(1)) {
+					(
+						"Text",
+						Some(NodeInput::Value {
+							tagged_value: TaggedValue::String(text),
+							..
+						}),
+					) => {
+						if let Some(filled) = table.fill_placeholders(text, row) {
+							*text = filled;
+							changed = true;
+						}
+					}
+					("Image", _) => {
+						if let Some(url) = image_url.filter(|url| !url.is_empty()) {
+							images.push((path.clone(), url.to_string()));
+						}
+					}
+					_ => {}
+				}
+			}
+			if changed {
+				layer_layer.cached_output_data = CachedOutputData::None;
+			}
+		}
+		_ => {}
+	}
+}
+
+/// How far the copy of the template made for the row at `index` is moved from the template. The copies continue the template's row
+/// to the right, `per_row` artboards across including the template, and then wrap onto new rows below it.
+pub fn merge_offset(index: usize, per_row: usize, artboard_size: DVec2, spacing: f64) -> DVec2 {
+	let per_row = per_row.max(1);
+	let position = index + 1;
+	let cell = DVec2::new((position % per_row) as f64, (position / per_row) as f64);
+	cell * (artboard_size + spacing)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn csv_with_quoted_fields() {
+		let table = MergeTable::from_csv("Name, Title\n\"Doe, Jane\",\"The \"\"Boss\"\"\"\r\n\nJohn Smith,\"Line one\nLine two\"\n").unwrap();
+
+		assert_eq!(table.columns, ["Name", "Title"]);
+		assert_eq!(
+			table.rows,
+			[
+				vec!["Doe, Jane".to_string(), "The \"Boss\"".to_string()],
+				vec!["John Smith".to_string(), "Line one\nLine two".to_string()]
+			]
+		);
+		assert_eq!(MergeTable::from_csv(" \n\n"), None);
+	}
+
+	#[test]
+	fn filling_placeholders() {
+		let table = MergeTable::from_csv("Name,Event\nAda,Conference").unwrap();
+		let row = &table.rows[0];
+
+		assert_eq!(
+			table.fill_placeholders("Hello {{Name}}, welcome to the {{ Event }}!", row).as_deref(),
+			Some("Hello Ada, welcome to the Conference!")
+		);
+		assert_eq!(table.fill_placeholders("{{Name}} {{Unknown}} {{", row).as_deref(), Some("Ada {{Unknown}} {{"));
+		assert_eq!(table.fill_placeholders("No placeholders {{Unknown}}", row), None);
+		assert_eq!(table.fill_placeholders("{{Event}}", &[]).as_deref(), Some(""));
+	}
+
+	#[test]
+	fn image_placeholder_names() {
+		assert_eq!(placeholder_column("{{Photo}}"), Some("Photo"));
+		assert_eq!(placeholder_column(" {{ Logo }} "), Some("Logo"));
+		assert_eq!(placeholder_column("{{}}"), None);
+		assert_eq!(placeholder_column("Photo of {{Name}}"), None);
+	}
+
+	#[test]
+	fn copies_are_laid_out_in_a_grid() {
+		let size = DVec2::new(100., 50.);
+
+		assert_eq!(merge_offset(0, 3, size, 10.), DVec2::new(110., 0.));
+		assert_eq!(merge_offset(1, 3, size, 10.), DVec2::new(220., 0.));
+		assert_eq!(merge_offset(2, 3, size, 10.), DVec2::new(0., 60.));
+		assert_eq!(merge_offset(0, 1, size, 10.), DVec2::new(0., 60.));
+	}
+}
@@ -0,0 +1,630 @@
+//! Reading design files from other editors, so existing work can be brought across into a new document.
+//!
+//! Two formats are read, each only in the subset they have in common:
+//! - Figma files as returned by its REST API (`GET /v1/files/:key` or `GET /v1/files/:key/nodes`). Vector shapes other than rectangles,
+//!   ellipses, and lines only have geometry when the file is requested with `geometry=paths`.
+//! - Sketch pages, which are the JSON files in the `pages` folder of a `.sketch` file (itself a ZIP archive).
+//!
+//! Top-level frames and artboards become artboards, groups and frames become folders, vectors become shapes, and text becomes text layers.
+//! Only solid fills and strokes are read. Only the first page is imported, since each page has its own canvas and they would overlap.
+//! Content that can't be represented is skipped with a warning rather than failing the whole import.
+
+use crate::consts::DEFAULT_FONT_FAMILY;
+
+use document_legacy::svg_import::parse_path_data;
+use graphene_core::text::Font;
+use graphene_core::uuid::ManipulatorGroupId;
+use graphene_core::vector::style::{Fill, Stroke};
+use graphene_core::Color;
+
+use bezier_rs::{ManipulatorGroup, Subpath};
+use glam::{DAffine2, DVec2};
+use serde_json::Value;
+
+/// The artboards and layers read from a design file, with the layers listed from the bottom of the stacking order to the top.
+#[derive(Debug, Default)]
+pub struct DesignImport {
+	pub artboards: Vec<ImportedArtboard>,
+	pub layers: Vec<ImportedLayer>,
+	/// Descriptions of the content that couldn't be imported, each listed once.
+	pub warnings: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedArtboard {
+	pub name: String,
+	pub position: DVec2,
+	pub size: DVec2,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedLayer {
+	pub name: Option<String>,
+	pub visible: bool,
+	pub opacity: f64,
+	pub content: ImportedContent,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportedContent {
+	Group(Vec<ImportedLayer>),
+	/// A shape whose geometry is already in document coordinates.
+	Shape {
+		subpaths: Vec<Subpath<ManipulatorGroupId>>,
+		fill: Fill,
+		stroke: Option<Stroke>,
+	},
+	/// Text whose top left corner is placed by the transform, in document coordinates.
+	Text {
+		text: String,
+		font: Font,
+		font_size: f64,
+		color: Option<Color>,
+		transform: DAffine2,
+	},
+}
+
+/// Parses a Figma or Sketch JSON file into artboards and layers. Fails only if the file isn't JSON in either format.
+pub fn import_design_file(json: &str) -> Result<DesignImport, String> {
+	let root: Value = serde_json::from_str(json).map_err(|error| format!("The file isn't valid JSON: {error}"))?;
+
+	let mut import = DesignImport::default();
+	if root.get("_class").is_some() || root.get("pages").is_some() {
+		import_sketch(&root, &mut import);
+	} else if root.get("document").is_some() || root.get("nodes").is_some() || root.get("type").is_some() {
+		import_figma(&root, &mut import);
+	} else {
+		return Err("Only Figma files from its REST API and Sketch pages can be imported".to_string());
+	}
+
+	Ok(import)
+}
+
+fn warn_once(warnings: &mut Vec<String>, warning: &str) {
+	if !warnings.iter().any(|existing| existing == warning) {
+		warnings.push(warning.to_string());
+	}
+}
+
+fn children<'a>(value: &'a Value, key: &str) -> impl DoubleEndedIterator<Item = &'a Value> {
+	value.get(key).and_then(Value::as_array).into_iter().flatten()
+}
+
+fn number(value: &Value, key: &str) -> Option<f64> {
+	value.get(key)?.as_f64()
+}
+
+fn flag(value: &Value, key: &str) -> Option<bool> {
+	value.get(key)?.as_bool()
+}
+
+fn string(value: &Value, key: &str) -> Option<String> {
+	value.get(key)?.as_str().map(str::to_string)
+}
+
+/// Converts a color from sRGB channels between 0 and 1, as both formats store them.
+fn srgb_color(red: f64, green: f64, blue: f64, alpha: f64) -> Color {
+	let byte = |channel: f64| (channel.clamp(0., 1.) * 255.).round() as u8;
+	Color::from_rgba8_srgb(byte(red), byte(green), byte(blue), byte(alpha))
+}
+
+/// The style name used by the font catalog for a weight, such as "Semi Bold Italic (600)".
+fn font_style(weight: u32, italic: bool) -> String {
+	let name = match weight {
+		0..=149 => "Thin",
+		150..=249 => "Extra Light",
+		250..=349 => "Light",
+		350..=449 => "Normal",
+		450..=549 => "Medium",
+		550..=649 => "Semi Bold",
+		650..=749 => "Bold",
+		750..=849 => "Extra Bold",
+		850..=924 => "Black",
+		_ => "Extra Black",
+	};
+	let weight = (weight.clamp(100, 950) + 50) / 100 * 100;
+	let italic = if italic { " Italic" } else { "" };
+
+	format!("{name}{italic} ({weight})")
+}
+
+fn background_layer(size: DVec2, transform: DAffine2, color: Color) -> ImportedLayer {
+	let mut background = Subpath::new_rect(DVec2::ZERO, size);
+	background.apply_transform(transform);
+
+	ImportedLayer {
+		name: Some("Background".to_string()),
+		visible: true,
+		opacity: 1.,
+		content: ImportedContent::Shape {
+			subpaths: vec![background],
+			fill: Fill::Solid(color),
+			stroke: None,
+		},
+	}
+}
+
+// =======
+// Figma
+// =======
+
+fn import_figma(root: &Value, import: &mut DesignImport) {
+	match root.get("nodes").and_then(Value::as_object) {
+		Some(nodes) => nodes.values().filter_map(|node| node.get("document")).for_each(|node| figma_top_level(node, import)),
+		None => figma_top_level(root.get("document").unwrap_or(root), import),
+	}
+}
+
+fn figma_top_level(node: &Value, import: &mut DesignImport) {
+	match node.get("type").and_then(Value::as_str) {
+		Some("DOCUMENT") => {
+			let mut pages = children(node, "children");
+			if let Some(page) = pages.next() {
+				figma_top_level(page, import);
+			}
+			if pages.next().is_some() {
+				warn_once(&mut import.warnings, "Only the first page was imported");
+			}
+		}
+		Some("CANVAS") => {
+			for child in children(node, "children") {
+				if let Some(layer) = figma_node(child, DAffine2::IDENTITY, true, import) {
+					import.layers.push(layer);
+				}
+			}
+		}
+		_ => {
+			if let Some(layer) = figma_node(node, DAffine2::IDENTITY, true, import) {
+				import.layers.push(layer);
+			}
+		}
+	}
+}
+
+/// The node's transform in document coordinates. Without the `relativeTransform` given by `geometry=paths`, nodes are placed unrotated at their bounding box.
+fn figma_transform(node: &Value, parent_transform: DAffine2) -> DAffine2 {
+	let relative = node.get("relativeTransform").and_then(Value::as_array).and_then(|rows| {
+		let row = |index: usize| -> Option<Vec<f64>> { rows.get(index)?.as_array()?.iter().map(Value::as_f64).collect() };
+		let (first, second) = (row(0)?, row(1)?);
+		(first.len() == 3 && second.len() == 3).then(|| DAffine2::from_cols_array(&[first[0], second[0], first[1], second[1], first[2], second[2]]))
+	});
+
+	match relative {
+		Some(relative) => parent_transform * relative,
+		None => {
+			let bounds = node.get("absoluteBoundingBox");
+			let position = bounds.map_or(DVec2::ZERO, |bounds| DVec2::new(number(bounds, "x").unwrap_or_default(), number(bounds, "y").unwrap_or_default()));
+			DAffine2::from_translation(position)
+		}
+	}
+}
+
+fn figma_size(node: &Value) -> DVec2 {
+	let size = node.get("size").and_then(|size| Some(DVec2::new(number(size, "x")?, number(size, "y")?)));
+	let bounds = || node.get("absoluteBoundingBox").and_then(|bounds| Some(DVec2::new(number(bounds, "width")?, number(bounds, "height")?)));
+	size.or_else(bounds).unwrap_or_default()
+}
+
+/// The topmost visible paint of a list of fills or strokes as a solid color.
+fn figma_paint(node: &Value, key: &str, warnings: &mut Vec<String>) -> Option<Color> {
+	let paint = children(node, key).rev().find(|paint| flag(paint, "visible") != Some(false))?;
+	let opacity = number(paint, "opacity").unwrap_or(1.);
+	let color = |color: &Value| Some(srgb_color(number(color, "r")?, number(color, "g")?, number(color, "b")?, number(color, "a").unwrap_or(1.) * opacity));
+
+	match paint.get("type").and_then(Value::as_str)? {
+		"SOLID" => color(paint.get("color")?),
+		kind if kind.starts_with("GRADIENT_") => {
+			warn_once(warnings, "Gradients aren't imported, so gradient fills and strokes were replaced by their first color");
+			color(children(paint, "gradientStops").next()?.get("color")?)
+		}
+		_ => {
+			warn_once(warnings, "Image fills aren't imported, so they were left empty");
+			None
+		}
+	}
+}
+
+fn figma_subpaths(node: &Value, kind: &str, size: DVec2, warnings: &mut Vec<String>) -> Vec<Subpath<ManipulatorGroupId>> {
+	let mut subpaths = children(node, "fillGeometry")
+		.filter_map(|geometry| geometry.get("path")?.as_str())
+		.flat_map(|path| parse_path_data(path).0)
+		.collect::<Vec<_>>();
+
+	if subpaths.is_empty() {
+		match kind {
+			"RECTANGLE" => subpaths.push(Subpath::new_rect(DVec2::ZERO, size)),
+			"ELLIPSE" => subpaths.push(Subpath::new_ellipse(DVec2::ZERO, size)),
+			"LINE" => subpaths.push(Subpath::new_line(DVec2::ZERO, DVec2::new(size.x, 0.))),
+			_ => warn_once(
+				warnings,
+				"Figma only includes the geometry of vector shapes when the file is requested with `geometry=paths`, so shapes without it were skipped",
+			),
+		}
+	}
+
+	subpaths
+}
+
+fn figma_node(node: &Value, parent_transform: DAffine2, top_level: bool, import: &mut DesignImport) -> Option<ImportedLayer> {
+	let kind = node.get("type").and_then(Value::as_str).unwrap_or_default();
+	let transform = figma_transform(node, parent_transform);
+	let size = figma_size(node);
+
+	let content = match kind {
+		"FRAME" | "GROUP" | "COMPONENT" | "COMPONENT_SET" | "INSTANCE" | "SECTION" => {
+			let mut layers = Vec::new();
+
+			// Groups have no fills of their own, but frames are filled behind their content
+			let background = if kind == "GROUP" { None } else { figma_paint(node, "fills", &mut import.warnings) };
+			let is_artboard = top_level && kind != "GROUP";
+			if is_artboard {
+				import.artboards.push(ImportedArtboard {
+					name: string(node, "name").unwrap_or_default(),
+					position: transform.translation,
+					size,
+				});
+			}
+			if let Some(color) = background.filter(|&color| !is_artboard || color != Color::WHITE) {
+				layers.push(background_layer(size, transform, color));
+			}
+
+			layers.extend(children(node, "children").filter_map(|child| figma_node(child, transform, false, import)));
+			if layers.is_empty() {
+				return None;
+			}
+			ImportedContent::Group(layers)
+		}
+		"VECTOR" | "RECTANGLE" | "ELLIPSE" | "LINE" | "STAR" | "REGULAR_POLYGON" | "BOOLEAN_OPERATION" => {
+			let mut subpaths = figma_subpaths(node, kind, size, &mut import.warnings);
+			if subpaths.is_empty() {
+				return None;
+			}
+			subpaths.iter_mut().for_each(|subpath| subpath.apply_transform(transform));
+
+			let fill = figma_paint(node, "fills", &mut import.warnings).map_or(Fill::None, Fill::Solid);
+			let stroke_color = figma_paint(node, "strokes", &mut import.warnings);
+			let stroke = stroke_color.map(|color| Stroke::new(Some(color), number(node, "strokeWeight").unwrap_or(1.)));
+			ImportedContent::Shape { subpaths, fill, stroke }
+		}
+		"TEXT" => {
+			let style = node.get("style").cloned().unwrap_or_default();
+			let weight = number(&style, "fontWeight").unwrap_or(400.) as u32;
+			let family = string(&style, "fontFamily").unwrap_or_else(|| DEFAULT_FONT_FAMILY.to_string());
+
+			ImportedContent::Text {
+				text: string(node, "characters").unwrap_or_default(),
+				font: Font::new(family, font_style(weight, flag(&style, "italic").unwrap_or_default())),
+				font_size: number(&style, "fontSize").unwrap_or(24.),
+				color: figma_paint(node, "fills", &mut import.warnings),
+				transform,
+			}
+		}
+		// Slices only mark areas for exporting and aren't drawn
+		"SLICE" => return None,
+		kind => {
+			warn_once(&mut import.warnings, &format!("Figma {kind} nodes aren't supported and were skipped"));
+			return None;
+		}
+	};
+
+	Some(ImportedLayer {
+		name: string(node, "name"),
+		visible: flag(node, "visible").unwrap_or(true),
+		opacity: number(node, "opacity").unwrap_or(1.),
+		content,
+	})
+}
+
+// ========
+// Sketch
+// ========
+
+fn import_sketch(root: &Value, import: &mut DesignImport) {
+	let mut pages = children(root, "pages");
+	let page = pages.next().unwrap_or(root);
+	if pages.next().is_some() {
+		warn_once(&mut import.warnings, "Only the first page was imported");
+	}
+
+	match page.get("_class").and_then(Value::as_str) {
+		Some("page") => {
+			for child in children(page, "layers") {
+				if let Some(layer) = sketch_layer(child, DAffine2::IDENTITY, true, import) {
+					import.layers.push(layer);
+				}
+			}
+		}
+		_ => {
+			if let Some(layer) = sketch_layer(page, DAffine2::IDENTITY, true, import) {
+				import.layers.push(layer);
+			}
+		}
+	}
+}
+
+/// The layer's transform in document coordinates and the size of its frame. Sketch rotates layers counterclockwise about the center of their frame.
+fn sketch_transform(layer: &Value, parent_transform: DAffine2) -> (DAffine2, DVec2) {
+	let frame = layer.get("frame").cloned().unwrap_or_default();
+	let position = DVec2::new(number(&frame, "x").unwrap_or_default(), number(&frame, "y").unwrap_or_default());
+	let size = DVec2::new(number(&frame, "width").unwrap_or_default(), number(&frame, "height").unwrap_or_default());
+
+	let center = size / 2.;
+	let flip = DVec2::new(
+		if flag(layer, "isFlippedHorizontal").unwrap_or_default() { -1. } else { 1. },
+		if flag(layer, "isFlippedVertical").unwrap_or_default() { -1. } else { 1. },
+	);
+	let rotation = -number(layer, "rotation").unwrap_or_default().to_radians();
+	let local = DAffine2::from_translation(position + center) * DAffine2::from_angle(rotation) * DAffine2::from_scale(flip) * DAffine2::from_translation(-center);
+
+	(parent_transform * local, size)
+}
+
+fn sketch_color(color: &Value) -> Option<Color> {
+	Some(srgb_color(number(color, "red")?, number(color, "green")?, number(color, "blue")?, number(color, "alpha").unwrap_or(1.)))
+}
+
+/// The topmost enabled fill or border of the layer's style as a solid color, along with the border thickness.
+fn sketch_paint(layer: &Value, key: &str, warnings: &mut Vec<String>) -> Option<(Color, f64)> {
+	let style = layer.get("style")?;
+	let paint = children(style, key).rev().find(|paint| flag(paint, "isEnabled") != Some(false))?;
+	let thickness = number(paint, "thickness").unwrap_or(1.);
+
+	match paint.get("fillType").and_then(Value::as_u64).unwrap_or_default() {
+		0 => Some((sketch_color(paint.get("color")?)?, thickness)),
+		1 => {
+			warn_once(warnings, "Gradients aren't imported, so gradient fills and strokes were replaced by their first color");
+			Some((sketch_color(children(paint.get("gradient")?, "stops").next()?.get("color")?)?, thickness))
+		}
+		_ => {
+			warn_once(warnings, "Image fills aren't imported, so they were left empty");
+			None
+		}
+	}
+}
+
+/// Reads a point written like `{0.5, 1}`.
+fn sketch_point(value: Option<&Value>) -> Option<DVec2> {
+	let text = value?.as_str()?.trim().strip_prefix('{')?.strip_suffix('}')?;
+	let (x, y) = text.split_once(',')?;
+	Some(DVec2::new(x.trim().parse().ok()?, y.trim().parse().ok()?))
+}
+
+/// The path of a shape layer in its own frame, whose points are stored as fractions of the frame's size.
+fn sketch_subpath(layer: &Value, size: DVec2) -> Option<Subpath<ManipulatorGroupId>> {
+	let groups = children(layer, "points")
+		.filter_map(|point| {
+			let anchor = sketch_point(point.get("point"))? * size;
+			let handle = |key: &str, has_key: &str| {
+				let handle = sketch_point(point.get(key))? * size;
+				flag(point, has_key).unwrap_or(handle != anchor).then_some(handle)
+			};
+			Some(ManipulatorGroup::new(anchor, handle("curveTo", "hasCurveTo"), handle("curveFrom", "hasCurveFrom")))
+		})
+		.collect::<Vec<_>>();
+
+	(!groups.is_empty()).then(|| Subpath::new(groups, flag(layer, "isClosed").unwrap_or(true)))
+}
+
+/// Splits a PostScript font name like `OpenSans-SemiBoldItalic` into a family and style.
+fn sketch_font(postscript_name: &str) -> Font {
+	let (family, style) = postscript_name.split_once('-').unwrap_or((postscript_name, ""));
+
+	let mut family_name = String::new();
+	let mut previous = None;
+	for character in family.chars() {
+		if character.is_uppercase() && previous.map_or(false, char::is_lowercase) {
+			family_name.push(' ');
+		}
+		family_name.push(character);
+		previous = Some(character);
+	}
+
+	let italic = style.contains("Italic") || style.contains("Oblique");
+	let weight = match style.replace("Italic", "").replace("Oblique", "").to_lowercase().as_str() {
+		"thin" | "hairline" => 100,
+		"extralight" | "ultralight" => 200,
+		"light" => 300,
+		"medium" => 500,
+		"semibold" | "demibold" => 600,
+		"bold" => 700,
+		"extrabold" | "ultrabold" | "heavy" => 800,
+		"black" => 900,
+		_ => 400,
+	};
+
+	Font::new(family_name, font_style(weight, italic))
+}
+
+fn sketch_layer(layer: &Value, parent_transform: DAffine2, top_level: bool, import: &mut DesignImport) -> Option<ImportedLayer> {
+	let class = layer.get("_class").and_then(Value::as_str).unwrap_or_default();
+	let (transform, size) = sketch_transform(layer, parent_transform);
+
+	let content = match class {
+		"artboard" | "symbolMaster" | "group" => {
+			let mut layers = Vec::new();
+
+			let is_artboard = top_level && class != "group";
+			if is_artboard {
+				import.artboards.push(ImportedArtboard {
+					name: string(layer, "name").unwrap_or_default(),
+					position: transform.translation,
+					size,
+				});
+			}
+			let background = layer.get("backgroundColor").and_then(sketch_color).filter(|_| flag(layer, "hasBackgroundColor").unwrap_or_default());
+			if let Some(color) = background.filter(|&color| !is_artboard || color != Color::WHITE) {
+				layers.push(background_layer(size, transform, color));
+			}
+
+			layers.extend(children(layer, "layers").filter_map(|child| sketch_layer(child, transform, false, import)));
+			if layers.is_empty() {
+				return None;
+			}
+			ImportedContent::Group(layers)
+		}
+		"rectangle" | "oval" | "shapePath" | "triangle" | "star" | "polygon" | "shapeGroup" => {
+			// A shape group draws the paths of all its shapes combined with its own style
+			let subpaths = match class {
+				"shapeGroup" => children(layer, "layers")
+					.filter_map(|child| {
+						let (child_transform, child_size) = sketch_transform(child, transform);
+						let mut subpath = sketch_subpath(child, child_size)?;
+						subpath.apply_transform(child_transform);
+						Some(subpath)
+					})
+					.collect::<Vec<_>>(),
+				_ => sketch_subpath(layer, size)
+					.map(|mut subpath| {
+						subpath.apply_transform(transform);
+						subpath
+					})
+					.into_iter()
+					.collect(),
+			};
+			if subpaths.is_empty() {
+				return None;
+			}
+
+			let fill = sketch_paint(layer, "fills", &mut import.warnings).map_or(Fill::None, |(color, _)| Fill::Solid(color));
+			let stroke = sketch_paint(layer, "borders", &mut import.warnings).map(|(color, thickness)| Stroke::new(Some(color), thickness));
+			ImportedContent::Shape { subpaths, fill, stroke }
+		}
+		"text" => {
+			let attributes = layer.pointer("/style/textStyle/encodedAttributes");
+			let font = attributes.and_then(|attributes| attributes.pointer("/MSAttributedStringFontAttribute/attributes"));
+			let color = attributes.and_then(|attributes| attributes.get("MSAttributedStringColorAttribute")).and_then(sketch_color);
+
+			ImportedContent::Text {
+				text: layer.pointer("/attributedString/string").and_then(Value::as_str).unwrap_or_default().to_string(),
+				font: sketch_font(font.and_then(|font| font.get("name")?.as_str()).unwrap_or(DEFAULT_FONT_FAMILY)),
+				font_size: font.and_then(|font| number(font, "size")).unwrap_or(24.),
+				color,
+				transform,
+			}
+		}
+		// Slices and hotspots only mark areas for exporting and prototyping and aren't drawn
+		"slice" | "MSImmutableHotspotLayer" => return None,
+		"bitmap" => {
+			warn_once(&mut import.warnings, "Images aren't imported and were skipped");
+			return None;
+		}
+		"symbolInstance" => {
+			warn_once(&mut import.warnings, "Symbol instances aren't imported, since their symbols are stored outside the page");
+			return None;
+		}
+		class => {
+			warn_once(&mut import.warnings, &format!("Sketch {class} layers aren't supported and were skipped"));
+			return None;
+		}
+	};
+
+	Some(ImportedLayer {
+		name: string(layer, "name"),
+		visible: flag(layer, "isVisible").unwrap_or(true),
+		opacity: layer.pointer("/style/contextSettings/opacity").and_then(Value::as_f64).unwrap_or(1.),
+		content,
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn anchors(content: &ImportedContent) -> Vec<DVec2> {
+		match content {
+			ImportedContent::Shape { subpaths, .. } => subpaths.iter().flat_map(|subpath| subpath.manipulator_groups().iter().map(|group| group.anchor)).collect(),
+			_ => panic!("Expected a shape"),
+		}
+	}
+
+	#[test]
+	fn figma_frames_become_artboards() {
+		let json = r#"{
+			"document": { "type": "DOCUMENT", "children": [{ "type": "CANVAS", "children": [{
+				"type": "FRAME", "name": "Card",
+				"absoluteBoundingBox": { "x": 100, "y": 50, "width": 200, "height": 100 },
+				"fills": [{ "type": "SOLID", "color": { "r": 1, "g": 1, "b": 1, "a": 1 } }],
+				"children": [
+					{
+						"type": "RECTANGLE", "name": "Box",
+						"absoluteBoundingBox": { "x": 110, "y": 60, "width": 20, "height": 10 },
+						"fills": [{ "type": "SOLID", "color": { "r": 1, "g": 0, "b": 0, "a": 1 } }]
+					},
+					{
+						"type": "TEXT", "characters": "Hello",
+						"absoluteBoundingBox": { "x": 110, "y": 80, "width": 50, "height": 20 },
+						"style": { "fontFamily": "Inter", "fontWeight": 700, "fontSize": 16 }
+					}
+				]
+			}] }] }
+		}"#;
+		let import = import_design_file(json).unwrap();
+
+		assert_eq!(
+			import.artboards,
+			vec![ImportedArtboard {
+				name: "Card".to_string(),
+				position: DVec2::new(100., 50.),
+				size: DVec2::new(200., 100.)
+			}]
+		);
+		let ImportedContent::Group(layers) = &import.layers[0].content else { panic!("Expected a group") };
+		assert_eq!(layers.len(), 2, "a white artboard doesn't need a background layer");
+		assert_eq!(
+			anchors(&layers[0].content),
+			vec![DVec2::new(110., 60.), DVec2::new(130., 60.), DVec2::new(130., 70.), DVec2::new(110., 70.)]
+		);
+		match &layers[1].content {
+			ImportedContent::Text { text, font, font_size, .. } => {
+				assert_eq!(text, "Hello");
+				assert_eq!(font, &Font::new("Inter".to_string(), "Bold (700)".to_string()));
+				assert_eq!(*font_size, 16.);
+			}
+			_ => panic!("Expected text"),
+		}
+	}
+
+	#[test]
+	fn sketch_points_are_scaled_to_their_frame() {
+		let json = r#"{
+			"_class": "page", "layers": [{
+				"_class": "artboard", "name": "Icon", "frame": { "x": 0, "y": 0, "width": 64, "height": 64 },
+				"hasBackgroundColor": true, "backgroundColor": { "red": 0, "green": 0, "blue": 0, "alpha": 1 },
+				"layers": [{
+					"_class": "shapePath", "isClosed": false, "frame": { "x": 10, "y": 20, "width": 40, "height": 20 },
+					"points": [{ "point": "{0, 0}", "curveFrom": "{0, 0}", "curveTo": "{0, 0}" }, { "point": "{1, 1}", "curveFrom": "{1, 1}", "curveTo": "{1, 1}" }],
+					"style": { "borders": [{ "isEnabled": true, "fillType": 0, "thickness": 2, "color": { "red": 0, "green": 0, "blue": 1, "alpha": 1 } }] }
+				}]
+			}]
+		}"#;
+		let import = import_design_file(json).unwrap();
+
+		assert_eq!(import.artboards.len(), 1);
+		let ImportedContent::Group(layers) = &import.layers[0].content else { panic!("Expected a group") };
+		assert_eq!(layers[0].name.as_deref(), Some("Background"));
+		assert_eq!(anchors(&layers[1].content), vec![DVec2::new(10., 20.), DVec2::new(50., 40.)]);
+		match &layers[1].content {
+			ImportedContent::Shape { fill, stroke, subpaths } => {
+				assert_eq!(fill, &Fill::None);
+				assert_eq!(stroke.as_ref().map(|stroke| stroke.weight), Some(2.));
+				assert!(!subpaths[0].closed());
+				assert!(subpaths[0].manipulator_groups().iter().all(|group| group.in_handle.is_none() && group.out_handle.is_none()));
+			}
+			_ => panic!("Expected a shape"),
+		}
+	}
+
+	#[test]
+	fn font_names_are_matched_to_the_catalog() {
+		assert_eq!(font_style(400, false), "Normal (400)");
+		assert_eq!(font_style(600, true), "Semi Bold Italic (600)");
+		assert_eq!(sketch_font("OpenSans-BoldItalic"), Font::new("Open Sans".to_string(), "Bold Italic (700)".to_string()));
+		assert_eq!(sketch_font("Inter"), Font::new("Inter".to_string(), "Normal (400)".to_string()));
+	}
+
+	#[test]
+	fn unknown_json_is_rejected() {
+		assert!(import_design_file("{}").is_err());
+		assert!(import_design_file("not json").is_err());
+	}
+}
@@ -0,0 +1,174 @@
+//! Design tokens are the named colors, text styles, and spacing of a design, written to a file in the format of the Design Tokens Community Group
+//! so engineering teams can pick up style changes from it rather than having them specified again by hand.
+
+use graphene_core::text::Font;
+use graphene_core::Color;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum DesignTokenFormat {
+	#[default]
+	Json,
+	Yaml,
+}
+
+impl DesignTokenFormat {
+	pub fn file_suffix(self) -> &'static str {
+		match self {
+			DesignTokenFormat::Json => "tokens.json",
+			DesignTokenFormat::Yaml => "tokens.yaml",
+		}
+	}
+}
+
+/// The tokens of a document by name. When several share a name, the first one added is kept.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DesignTokens {
+	colors: BTreeMap<String, Color>,
+	text_styles: BTreeMap<String, (Font, f64)>,
+	spacing: BTreeMap<String, f64>,
+}
+
+impl DesignTokens {
+	pub fn add_color(&mut self, name: &str, color: Color) {
+		self.colors.entry(token_name(name)).or_insert(color);
+	}
+
+	pub fn add_text_style(&mut self, name: &str, font: Font, font_size: f64) {
+		self.text_styles.entry(token_name(name)).or_insert((font, font_size));
+	}
+
+	/// Adds a spacing in document pixels.
+	pub fn add_spacing(&mut self, name: &str, spacing: f64) {
+		self.spacing.entry(token_name(name)).or_insert(spacing);
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.colors.is_empty() && self.text_styles.is_empty() && self.spacing.is_empty()
+	}
+
+	/// The tokens grouped by type, leaving out the groups without any tokens.
+	fn to_value(&self) -> Value {
+		let token = |token_type: &str, value: Value| json!({ "$type": token_type, "$value": value });
+
+		let mut groups = Map::new();
+		let mut add_group = |group: &str, tokens: Map<String, Value>| {
+			if !tokens.is_empty() {
+				groups.insert(group.to_string(), Value::Object(tokens));
+			}
+		};
+		add_group(
+			"color",
+			self.colors
+				.iter()
+				.map(|(name, color)| (name.clone(), token("color", json!(format!("#{}", color.rgba_hex())))))
+				.collect(),
+		);
+		add_group(
+			"typography",
+			self.text_styles
+				.iter()
+				.map(|(name, (font, font_size))| {
+					let value = json!({ "fontFamily": font.font_family, "fontStyle": font.font_style, "fontSize": format!("{font_size}px") });
+					(name.clone(), token("typography", value))
+				})
+				.collect(),
+		);
+		add_group(
+			"spacing",
+			self.spacing.iter().map(|(name, spacing)| (name.clone(), token("dimension", json!(format!("{spacing}px"))))).collect(),
+		);
+
+		Value::Object(groups)
+	}
+
+	/// Writes the contents of a tokens file in the given format.
+	pub fn write(&self, format: DesignTokenFormat) -> String {
+		let value = self.to_value();
+		match format {
+			DesignTokenFormat::Json => serde_json::to_string_pretty(&value).unwrap_or_default(),
+			DesignTokenFormat::Yaml => {
+				let mut yaml = String::new();
+				write_yaml(&value, 0, &mut yaml);
+				yaml
+			}
+		}
+	}
+}
+
+/// Turns a layer or style name into a token name, in lowercase with the words joined by dashes, like "brand-primary" for "Brand Primary".
+pub fn token_name(name: &str) -> String {
+	let words = name.split(|character: char| !character.is_alphanumeric()).filter(|word| !word.is_empty());
+	let name = words.map(str::to_lowercase).collect::<Vec<_>>().join("-");
+	if name.is_empty() {
+		"unnamed".to_string()
+	} else {
+		name
+	}
+}
+
+/// Writes the tokens as YAML, where the nesting of objects is shown by indentation. Keys and strings are quoted like in JSON, which YAML accepts too.
+fn write_yaml(value: &Value, indent: usize, yaml: &mut String) {
+	let Value::Object(map) = value else { return };
+
+	for (key, value) in map {
+		yaml.push_str(&"  ".repeat(indent));
+		yaml.push_str(&Value::String(key.clone()).to_string());
+		yaml.push(':');
+		match value {
+			Value::Object(inner) if !inner.is_empty() => {
+				yaml.push('\n');
+				write_yaml(value, indent + 1, yaml);
+			}
+			Value::Object(_) => yaml.push_str(" {}\n"),
+			scalar => {
+				yaml.push(' ');
+				yaml.push_str(&scalar.to_string());
+				yaml.push('\n');
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn example_tokens() -> DesignTokens {
+		let mut tokens = DesignTokens::default();
+		tokens.add_color("Brand Primary", Color::from_rgbaf32_unchecked(1., 0., 0., 1.));
+		tokens.add_color("brand primary", Color::BLACK);
+		tokens.add_text_style("Heading 1", Font::new("Inter".to_string(), "Bold (700)".to_string()), 32.);
+		tokens.add_spacing("Guide Grid", 50.);
+		tokens
+	}
+
+	#[test]
+	fn tokens_are_named_after_their_source() {
+		assert_eq!(token_name("Brand Primary"), "brand-primary");
+		assert_eq!(token_name("  Heading / Level 2 "), "heading-level-2");
+		assert_eq!(token_name("—"), "unnamed");
+	}
+
+	#[test]
+	fn first_token_with_a_name_is_kept() {
+		let json: Value = serde_json::from_str(&example_tokens().write(DesignTokenFormat::Json)).unwrap();
+		assert_eq!(json["color"]["brand-primary"]["$value"], "#FF0000FF");
+		assert_eq!(json["color"]["brand-primary"]["$type"], "color");
+		assert_eq!(json["typography"]["heading-1"]["$value"]["fontSize"], "32px");
+		assert_eq!(json["spacing"]["guide-grid"]["$value"], "50px");
+	}
+
+	#[test]
+	fn yaml_nests_by_indentation() {
+		let mut tokens = DesignTokens::default();
+		tokens.add_spacing("Gutter", 8.);
+		assert_eq!(
+			tokens.write(DesignTokenFormat::Yaml),
+			"\"spacing\":\n  \"gutter\":\n    \"$type\": \"dimension\"\n    \"$value\": \"8px\"\n"
+		);
+	}
+}
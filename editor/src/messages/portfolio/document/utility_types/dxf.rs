@@ -0,0 +1,322 @@
+use super::vector_export::{flatten_bezier, ExportedShape};
+
+use bezier_rs::{ArcStrategy, ArcsOptions, Bezier};
+use glam::DVec2;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// The physical unit that document pixels are converted into, assuming the CSS resolution of 96 pixels per inch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DxfUnits {
+	#[default]
+	Millimeters,
+	Inches,
+	Pixels,
+}
+
+impl DxfUnits {
+	/// How many of this unit make up one document pixel.
+	pub fn per_pixel(self) -> f64 {
+		match self {
+			DxfUnits::Millimeters => 25.4 / 96.,
+			DxfUnits::Inches => 1. / 96.,
+			DxfUnits::Pixels => 1.,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DxfOptions {
+	/// The furthest, in document pixels, that the exported geometry may stray from the original curves.
+	pub tolerance: f64,
+	pub units: DxfUnits,
+	/// Approximate curves with circular arcs (stored as polyline bulges) where possible, instead of only straight segments.
+	pub arcs: bool,
+}
+
+impl Default for DxfOptions {
+	fn default() -> Self {
+		Self {
+			tolerance: 0.1,
+			units: DxfUnits::default(),
+			arcs: true,
+		}
+	}
+}
+
+/// A polyline vertex and the bulge of the segment leaving it (0 for a straight segment, otherwise the tangent of a quarter of the arc's sweep angle).
+type Vertex = (DVec2, f64);
+
+/// Writes the shapes as an AutoCAD R12 DXF file, with each Graphite layer placed on a DXF layer of the same name.
+/// The bottom left corner of `bounds` becomes the origin, and the Y axis is flipped to point up as DXF expects.
+pub fn shapes_to_dxf(shapes: &[ExportedShape], bounds: [DVec2; 2], options: DxfOptions) -> String {
+	let scale = options.units.per_pixel();
+	let to_dxf = |point: DVec2| DVec2::new(point.x - bounds[0].x, bounds[1].y - point.y) * scale;
+
+	let mut layer_names: Vec<String> = Vec::new();
+	for shape in shapes {
+		let name = dxf_layer_name(&shape.layer_name);
+		if !layer_names.contains(&name) {
+			layer_names.push(name);
+		}
+	}
+
+	let mut dxf = String::new();
+
+	group(&mut dxf, 0, "SECTION");
+	group(&mut dxf, 2, "HEADER");
+	group(&mut dxf, 9, "$ACADVER");
+	group(&mut dxf, 1, "AC1009");
+	group(&mut dxf, 0, "ENDSEC");
+
+	group(&mut dxf, 0, "SECTION");
+	group(&mut dxf, 2, "TABLES");
+	group(&mut dxf, 0, "TABLE");
+	group(&mut dxf, 2, "LAYER");
+	group(&mut dxf, 70, layer_names.len());
+	for name in &layer_names {
+		group(&mut dxf, 0, "LAYER");
+		group(&mut dxf, 2, name);
+		group(&mut dxf, 70, 0);
+		group(&mut dxf, 62, 7);
+		group(&mut dxf, 6, "CONTINUOUS");
+	}
+	group(&mut dxf, 0, "ENDTAB");
+	group(&mut dxf, 0, "ENDSEC");
+
+	group(&mut dxf, 0, "SECTION");
+	group(&mut dxf, 2, "ENTITIES");
+	for shape in shapes {
+		let layer = dxf_layer_name(&shape.layer_name);
+
+		for subpath in &shape.subpaths {
+			let Some(first) = subpath.manipulator_groups().first() else { continue };
+
+			let mut vertices: Vec<Vertex> = vec![(first.anchor, 0.)];
+			for bezier in subpath.iter() {
+				let segments = if options.arcs { bezier_to_arcs(&bezier, options.tolerance) } else { None };
+				let segments = segments.unwrap_or_else(|| flatten_bezier(&bezier, options.tolerance).into_iter().map(|point| (point, 0.)).collect());
+
+				for (end, bulge) in segments {
+					if let Some(last) = vertices.last_mut() {
+						last.1 = bulge;
+					}
+					vertices.push((end, 0.));
+				}
+			}
+
+			// The closing segment is implied by the closed flag, so the repeated start point is dropped (keeping the bulge of the segment leading into it)
+			let closed = subpath.closed();
+			if closed && vertices.len() > 1 && vertices.first().map(|vertex| vertex.0) == vertices.last().map(|vertex| vertex.0) {
+				vertices.pop();
+			}
+			if vertices.len() < 2 {
+				continue;
+			}
+
+			group(&mut dxf, 0, "POLYLINE");
+			group(&mut dxf, 8, &layer);
+			group(&mut dxf, 66, 1);
+			group(&mut dxf, 10, 0.);
+			group(&mut dxf, 20, 0.);
+			group(&mut dxf, 30, 0.);
+			group(&mut dxf, 70, if closed { 1 } else { 0 });
+			for (point, bulge) in vertices {
+				let point = to_dxf(point);
+				group(&mut dxf, 0, "VERTEX");
+				group(&mut dxf, 8, &layer);
+				group(&mut dxf, 10, format_number(point.x));
+				group(&mut dxf, 20, format_number(point.y));
+				group(&mut dxf, 30, 0.);
+				// Flipping the Y axis reverses the winding direction of every arc
+				if bulge != 0. {
+					group(&mut dxf, 42, format_number(-bulge));
+				}
+			}
+			group(&mut dxf, 0, "SEQEND");
+			group(&mut dxf, 8, &layer);
+		}
+	}
+	group(&mut dxf, 0, "ENDSEC");
+	group(&mut dxf, 0, "EOF");
+
+	dxf
+}
+
+/// Approximates a curved segment with a chain of circular arcs, returning each arc's end point and bulge.
+/// Returns `None` if the segment is straight or the arcs don't form a continuous chain from its start to its end.
+fn bezier_to_arcs(bezier: &Bezier, tolerance: f64) -> Option<Vec<Vertex>> {
+	if bezier.get_points().count() <= 2 {
+		return None;
+	}
+
+	let arcs = bezier.arcs(ArcsOptions {
+		strategy: ArcStrategy::FavorCorrectness,
+		error: tolerance,
+		..Default::default()
+	});
+
+	let joint_tolerance = tolerance * 2.;
+	let mut current = bezier.start();
+	let mut segments = Vec::with_capacity(arcs.len());
+	for arc in arcs {
+		let point_at = |angle: f64| arc.center + arc.radius * DVec2::from_angle(angle);
+		let (arc_start, arc_end) = (point_at(arc.start_angle), point_at(arc.end_angle));
+
+		let mut sweep = arc.end_angle - arc.start_angle;
+		if sweep < 0. {
+			sweep += std::f64::consts::TAU;
+		}
+
+		// The arcs always run in the direction of increasing angle, so an arc traversed from its end is swept backwards
+		let (end, sweep) = if current.distance(arc_start) <= joint_tolerance {
+			(arc_end, sweep)
+		} else if current.distance(arc_end) <= joint_tolerance {
+			(arc_start, -sweep)
+		} else {
+			return None;
+		};
+
+		segments.push((end, (sweep / 4.).tan()));
+		current = end;
+	}
+
+	if segments.is_empty() || current.distance(bezier.end()) > joint_tolerance {
+		return None;
+	}
+	// Snap the chain's end to the segment's end so neighboring segments stay connected
+	if let Some(last) = segments.last_mut() {
+		last.0 = bezier.end();
+	}
+
+	Some(segments)
+}
+
+/// Replaces the characters that aren't allowed in DXF layer names.
+fn dxf_layer_name(name: &str) -> String {
+	name.chars().map(|c| if "<>/\\\":;?*|=`".contains(c) || c.is_control() { '_' } else { c }).collect()
+}
+
+fn format_number(value: f64) -> String {
+	let formatted = format!("{value:.6}");
+	formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn group(dxf: &mut String, code: u16, value: impl std::fmt::Display) {
+	let _ = write!(dxf, "{code:>3}\n{value}\n");
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use bezier_rs::Subpath;
+
+	/// A polyline read back from the DXF, with whether it's closed and its vertices along with their bulges.
+	type Polyline = (bool, Vec<Vertex>);
+
+	fn shape(subpaths: Vec<Subpath<graphene_core::uuid::ManipulatorGroupId>>) -> ExportedShape {
+		ExportedShape {
+			layer_name: "Shape".to_string(),
+			subpaths,
+			filled: false,
+			opaque: false,
+			stroked: true,
+		}
+	}
+
+	fn export(subpaths: Vec<Subpath<graphene_core::uuid::ManipulatorGroupId>>, units: DxfUnits, arcs: bool) -> Vec<Polyline> {
+		let options = DxfOptions { tolerance: 0.01, units, arcs };
+		polylines(&shapes_to_dxf(&[shape(subpaths)], [DVec2::ZERO, DVec2::splat(96.)], options))
+	}
+
+	fn polylines(dxf: &str) -> Vec<Polyline> {
+		let lines = dxf.lines().collect::<Vec<_>>();
+		let mut polylines: Vec<Polyline> = Vec::new();
+		let mut in_vertex = false;
+		for pair in lines.chunks(2) {
+			let (code, value) = (pair[0].trim().parse::<u16>().unwrap(), pair[1].trim());
+			let number = || value.parse::<f64>().unwrap();
+			match (code, value) {
+				(0, "POLYLINE") => {
+					polylines.push((false, Vec::new()));
+					in_vertex = false;
+				}
+				(0, "VERTEX") => {
+					polylines.last_mut().unwrap().1.push((DVec2::ZERO, 0.));
+					in_vertex = true;
+				}
+				(0, _) => in_vertex = false,
+				(70, _) if !in_vertex => {
+					if let Some(polyline) = polylines.last_mut() {
+						polyline.0 = value == "1";
+					}
+				}
+				(10, _) if in_vertex => polylines.last_mut().unwrap().1.last_mut().unwrap().0.x = number(),
+				(20, _) if in_vertex => polylines.last_mut().unwrap().1.last_mut().unwrap().0.y = number(),
+				(42, _) if in_vertex => polylines.last_mut().unwrap().1.last_mut().unwrap().1 = number(),
+				_ => {}
+			}
+		}
+		polylines
+	}
+
+	#[test]
+	fn unit_rectangle_is_a_closed_polyline_with_flipped_y() {
+		let polylines = export(vec![Subpath::new_rect(DVec2::ZERO, DVec2::splat(96.))], DxfUnits::Inches, true);
+		assert_eq!(polylines.len(), 1);
+
+		let (closed, vertices) = &polylines[0];
+		assert!(closed);
+		// The start point isn't repeated at the end, and the top left corner of the document is at the top of the DXF
+		let points = vertices.iter().map(|vertex| vertex.0).collect::<Vec<_>>();
+		assert_eq!(points, [DVec2::new(0., 1.), DVec2::new(1., 1.), DVec2::new(1., 0.), DVec2::new(0., 0.)]);
+		assert!(vertices.iter().all(|vertex| vertex.1 == 0.), "Straight segments have no bulge");
+	}
+
+	#[test]
+	fn circle_is_made_of_arcs_sweeping_a_full_turn() {
+		let polylines = export(vec![Subpath::new_ellipse(DVec2::ZERO, DVec2::splat(96.))], DxfUnits::Inches, true);
+		let (closed, vertices) = &polylines[0];
+		assert!(closed);
+
+		let center = DVec2::splat(0.5);
+		for (point, bulge) in vertices {
+			assert!((point.distance(center) - 0.5).abs() < 1e-3, "{point} should be on the circle");
+			assert!(*bulge < 0., "The circle runs clockwise once the Y axis points up");
+		}
+		// The bulge is the tangent of a quarter of the sweep angle
+		let sweep = vertices.iter().map(|vertex| 4. * vertex.1.atan()).sum::<f64>();
+		assert!((sweep + std::f64::consts::TAU).abs() < 1e-3, "The arcs should sweep a full turn, not {sweep}");
+
+		// Without arcs, the circle is flattened into straight segments that stay close to it
+		let (_, vertices) = &export(vec![Subpath::new_ellipse(DVec2::ZERO, DVec2::splat(96.))], DxfUnits::Inches, false)[0];
+		assert!(vertices.len() > 8);
+		assert!(vertices.iter().all(|(point, bulge)| *bulge == 0. && (point.distance(center) - 0.5).abs() < 0.01 / 96. + 1e-3));
+	}
+
+	#[test]
+	fn units_scale_document_pixels() {
+		assert_eq!(DxfUnits::Inches.per_pixel() * 96., 1.);
+		assert!((DxfUnits::Millimeters.per_pixel() * 96. - 25.4).abs() < 1e-12);
+		assert_eq!(DxfUnits::Pixels.per_pixel(), 1.);
+
+		let line = || vec![Subpath::new_line(DVec2::new(0., 96.), DVec2::new(96., 96.))];
+		let end = |units| export(line(), units, true)[0].1[1].0;
+		assert!(end(DxfUnits::Millimeters).abs_diff_eq(DVec2::new(25.4, 0.), 1e-6));
+		assert!(end(DxfUnits::Inches).abs_diff_eq(DVec2::new(1., 0.), 1e-6));
+		assert!(end(DxfUnits::Pixels).abs_diff_eq(DVec2::new(96., 0.), 1e-6));
+	}
+
+	#[test]
+	fn open_subpaths_keep_both_ends_and_closed_ones_imply_the_closing_segment() {
+		let open = Subpath::from_anchors([DVec2::ZERO, DVec2::new(96., 0.), DVec2::new(96., 96.)], false);
+		let closed = Subpath::from_anchors([DVec2::ZERO, DVec2::new(96., 0.), DVec2::new(96., 96.)], true);
+		let polylines = export(vec![open, closed], DxfUnits::Pixels, true);
+
+		assert_eq!(polylines.len(), 2);
+		assert!(!polylines[0].0);
+		assert_eq!(polylines[0].1.len(), 3);
+		assert!(polylines[1].0);
+		assert_eq!(polylines[1].1.len(), 3, "The closing segment back to the start isn't written as a vertex");
+	}
+}
@@ -0,0 +1,158 @@
+use crate::consts::MAX_EXPORT_HISTORY_LEN;
+use crate::messages::frontend::utility_types::{ExportBounds, FileType};
+use crate::messages::portfolio::document::utility_types::dxf::DxfOptions;
+use crate::messages::portfolio::document::utility_types::plotter::PlotterOptions;
+use crate::messages::portfolio::document::utility_types::print_marks::PrintOptions;
+use crate::messages::prelude::*;
+
+use document_legacy::LayerId;
+
+use glam::DVec2;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The settings of a finished export, kept with the document so the same export can be repeated without filling in the export dialog again.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExportRecord {
+	pub file_name: String,
+	pub file_type: FileType,
+	pub scale_factor: f64,
+	pub bounds: ExportBounds,
+	pub custom_bounds: [DVec2; 2],
+	pub padding: f64,
+	pub transparent_background: bool,
+	pub layer_names: bool,
+	pub embed_fonts: bool,
+	pub dxf_options: DxfOptions,
+	pub plotter_options: PlotterOptions,
+	pub print_options: PrintOptions,
+	/// Milliseconds since the Unix epoch when the export was made, if the platform provides the date.
+	pub exported_at: Option<f64>,
+}
+
+impl ExportRecord {
+	/// The message which exports the document again with these settings.
+	pub fn export_message(&self) -> DocumentMessage {
+		DocumentMessage::ExportDocument {
+			file_name: self.file_name.clone(),
+			file_type: self.file_type,
+			scale_factor: self.scale_factor,
+			bounds: self.bounds,
+			custom_bounds: self.custom_bounds,
+			padding: self.padding,
+			transparent_background: self.transparent_background,
+			layer_names: self.layer_names,
+			embed_fonts: self.embed_fonts,
+			dxf_options: self.dxf_options,
+			plotter_options: self.plotter_options,
+			print_options: self.print_options,
+			preview: false,
+		}
+	}
+}
+
+/// An element ID given to a layer by an earlier export tagging layers with their names, which CSS or JS targeting the layer may still refer to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BrokenElementId {
+	pub previous: String,
+	/// The ID the layer is exported with now, or `None` if it's no longer tagged because it was deleted, hidden from exports, or left unnamed.
+	pub current: Option<String>,
+}
+
+/// The most recent exports of a document, newest first.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportHistory {
+	records: VecDeque<ExportRecord>,
+	/// The element ID of each layer, by its layer ID, in the latest export tagging layers with their names.
+	#[serde(default)]
+	element_ids: BTreeMap<LayerId, String>,
+}
+
+impl ExportHistory {
+	pub fn record(&mut self, record: ExportRecord) {
+		self.records.push_front(record);
+		self.records.truncate(MAX_EXPORT_HISTORY_LEN);
+	}
+
+	pub fn latest(&self) -> Option<&ExportRecord> {
+		self.records.front()
+	}
+
+	pub fn records(&self) -> impl Iterator<Item = &ExportRecord> {
+		self.records.iter()
+	}
+
+	/// Remembers the element IDs of an export tagging layers with their names, returning the IDs of the previous such export which no longer
+	/// point at the same layer, since renaming the layer (or another one with the same name) changed them.
+	pub fn record_element_ids(&mut self, element_ids: BTreeMap<LayerId, String>) -> Vec<BrokenElementId> {
+		let previous = std::mem::replace(&mut self.element_ids, element_ids);
+		previous
+			.into_iter()
+			.filter_map(|(layer_id, previous)| {
+				let current = self.element_ids.get(&layer_id).cloned();
+				(current.as_ref() != Some(&previous)).then_some(BrokenElementId { previous, current })
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn record(file_name: &str) -> ExportRecord {
+		ExportRecord {
+			file_name: file_name.to_string(),
+			file_type: FileType::Png,
+			scale_factor: 2.,
+			bounds: ExportBounds::AllArtwork,
+			custom_bounds: [DVec2::ZERO, DVec2::splat(100.)],
+			padding: 0.,
+			transparent_background: true,
+			layer_names: false,
+			embed_fonts: false,
+			dxf_options: Default::default(),
+			plotter_options: Default::default(),
+			print_options: Default::default(),
+			exported_at: None,
+		}
+	}
+
+	#[test]
+	fn keeps_only_the_most_recent_exports() {
+		let mut history = ExportHistory::default();
+		for index in 0..MAX_EXPORT_HISTORY_LEN + 3 {
+			history.record(record(&format!("export-{index}")));
+		}
+
+		assert_eq!(history.records().count(), MAX_EXPORT_HISTORY_LEN);
+		assert_eq!(history.latest().unwrap().file_name, format!("export-{}", MAX_EXPORT_HISTORY_LEN + 2));
+		assert_eq!(history.records().last().unwrap().file_name, "export-3");
+	}
+
+	#[test]
+	fn repeating_an_export_downloads_the_file() {
+		let message = record("artwork").export_message();
+		assert!(matches!(message, DocumentMessage::ExportDocument { preview: false, scale_factor, .. } if scale_factor == 2.));
+	}
+	#[test]
+	fn element_ids_changed_since_the_previous_export_are_reported() {
+		let element_ids = |ids: &[(LayerId, &str)]| ids.iter().map(|&(layer_id, id)| (layer_id, id.to_string())).collect();
+		let broken = |previous: &str, current: Option<&str>| BrokenElementId {
+			previous: previous.to_string(),
+			current: current.map(str::to_string),
+		};
+
+		// Nothing was exported with IDs before, so nothing refers to them yet
+		let mut history = ExportHistory::default();
+		assert!(history.record_element_ids(element_ids(&[(1, "logo"), (2, "menu"), (3, "button")])).is_empty());
+		assert!(history.record_element_ids(element_ids(&[(1, "logo"), (2, "menu"), (3, "button")])).is_empty());
+
+		// The menu was renamed, the button was deleted, and a new layer took the button's name
+		let reported = history.record_element_ids(element_ids(&[(1, "logo"), (2, "navigation"), (4, "button")]));
+		assert_eq!(reported, vec![broken("menu", Some("navigation")), broken("button", None)]);
+
+		// Only the export right before is compared with
+		assert!(history.record_element_ids(element_ids(&[(1, "logo"), (2, "navigation"), (4, "button")])).is_empty());
+	}
+}
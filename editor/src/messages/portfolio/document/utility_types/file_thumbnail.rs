@@ -0,0 +1,32 @@
+//! A small PNG of the artwork embedded in saved documents, so file browsers and the like can preview a document without opening it.
+
+use serde::Deserialize;
+
+/// Only the thumbnail of a saved document, with everything else in the file skipped over rather than read into a document.
+#[derive(Deserialize)]
+struct ThumbnailOnly {
+	#[serde(default)]
+	thumbnail: Option<String>,
+}
+
+/// Reads the thumbnail, a PNG data URL, from the contents of a saved document without reading the document itself,
+/// which is much quicker for large documents. Documents saved before thumbnails were embedded have none.
+pub fn read_document_thumbnail(serialized_content: &str) -> Option<String> {
+	let thumbnail = serde_json::from_str::<ThumbnailOnly>(serialized_content).ok()?.thumbnail?;
+	thumbnail.starts_with("data:image/png;base64,").then_some(thumbnail)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn thumbnail_is_read_among_other_fields() {
+		let document = r#"{"thumbnail":"data:image/png;base64,iVBORw0KGgo=","document_legacy":{"root":{"visible":true,"layers":[1,2,3]}},"name":"Test"}"#;
+		assert_eq!(read_document_thumbnail(document).as_deref(), Some("data:image/png;base64,iVBORw0KGgo="));
+
+		assert_eq!(read_document_thumbnail(r#"{"name":"Without a thumbnail"}"#), None);
+		assert_eq!(read_document_thumbnail(r#"{"thumbnail":"javascript:alert(1)"}"#), None);
+		assert_eq!(read_document_thumbnail("not a document"), None);
+	}
+}
@@ -0,0 +1,156 @@
+//! Finding and replacing text in the text layers of every open document.
+
+use super::outline::layer_text;
+
+use document_legacy::document::Document as DocumentLegacy;
+use document_legacy::layers::layer_info::{Layer, LayerDataType};
+use document_legacy::LayerId;
+
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// What to look for in the text layers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct TextQuery {
+	pub pattern: String,
+	#[serde(rename = "caseSensitive")]
+	pub case_sensitive: bool,
+	/// Whether the pattern is a regular expression rather than text to match literally.
+	pub regex: bool,
+}
+
+impl TextQuery {
+	/// Builds the matcher for the pattern, failing with a description of the problem if it isn't a valid regular expression.
+	pub fn matcher(&self) -> Result<Regex, String> {
+		let pattern = match self.regex {
+			true => self.pattern.clone(),
+			false => regex::escape(&self.pattern),
+		};
+		RegexBuilder::new(&pattern).case_insensitive(!self.case_sensitive).build().map_err(|error| error.to_string())
+	}
+}
+
+/// A text layer containing at least one match of the query.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct TextMatch {
+	#[serde(rename = "documentId")]
+	pub document_id: u64,
+	#[serde(rename = "documentName")]
+	pub document_name: String,
+	#[serde(rename = "layerPath")]
+	pub layer_path: Vec<LayerId>,
+	#[serde(rename = "layerName")]
+	pub layer_name: String,
+	pub text: String,
+	/// The start and end character indices of each match in the text.
+	#[serde(rename = "matchRanges")]
+	pub match_ranges: Vec<(usize, usize)>,
+}
+
+/// The text layers of the document along with their text, from the top of the layer stack to the bottom.
+pub fn text_layers(document: &DocumentLegacy) -> Vec<(Vec<LayerId>, &Layer, &str)> {
+	fn collect<'a>(layer: &'a Layer, path: &mut Vec<LayerId>, layers: &mut Vec<(Vec<LayerId>, &'a Layer, &'a str)>) {
+		match &layer.data {
+			LayerDataType::Folder(folder) => {
+				for (&id, child) in folder.layer_ids.iter().zip(folder.layers()).rev() {
+					path.push(id);
+					collect(child, path, layers);
+					path.pop();
+				}
+			}
+			_ => {
+				if let Some(text) = layer_text(layer) {
+					layers.push((path.clone(), layer, text));
+				}
+			}
+		}
+	}
+
+	let mut layers = Vec::new();
+	collect(&document.root, &mut Vec::new(), &mut layers);
+	layers
+}
+
+/// The start and end character indices of each match in the text. Matches of nothing, which a regular expression like `a*` can find, are skipped.
+pub fn match_ranges(text: &str, matcher: &Regex) -> Vec<(usize, usize)> {
+	let char_index = |byte_index: usize| text[..byte_index].chars().count();
+	matcher
+		.find_iter(text)
+		.filter(|found| !found.is_empty())
+		.map(|found| (char_index(found.start()), char_index(found.end())))
+		.collect()
+}
+
+/// The text layers of the document with at least one match, from the top of the layer stack to the bottom.
+pub fn document_text_matches(document_id: u64, document_name: &str, document: &DocumentLegacy, matcher: &Regex) -> Vec<TextMatch> {
+	text_layers(document)
+		.into_iter()
+		.filter_map(|(layer_path, layer, text)| {
+			let match_ranges = match_ranges(text, matcher);
+			(!match_ranges.is_empty()).then(|| TextMatch {
+				document_id,
+				document_name: document_name.to_string(),
+				layer_path,
+				layer_name: layer.name.clone().unwrap_or_else(|| text.lines().next().unwrap_or_default().to_string()),
+				text: text.to_string(),
+				match_ranges,
+			})
+		})
+		.collect()
+}
+
+/// Replaces every match in the text, or returns `None` if there is nothing to replace. For regular expressions, the replacement can refer to
+/// capture groups like `$1`, while other queries use the replacement literally.
+pub fn replace_matches(text: &str, matcher: &Regex, replacement: &str, expand_captures: bool) -> Option<String> {
+	if match_ranges(text, matcher).is_empty() {
+		return None;
+	}
+
+	let replaced = match expand_captures {
+		true => matcher.replace_all(text, replacement),
+		false => matcher.replace_all(text, NoExpand(replacement)),
+	};
+	Some(replaced.into_owned())
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn query(pattern: &str, case_sensitive: bool, regex: bool) -> TextQuery {
+		TextQuery {
+			pattern: pattern.to_string(),
+			case_sensitive,
+			regex,
+		}
+	}
+
+	#[test]
+	fn literal_queries_escape_regex_syntax() {
+		let matcher = query("1.5 (x)", true, false).matcher().unwrap();
+		assert_eq!(match_ranges("Scale 1.5 (x) not 125 x", &matcher), vec![(6, 13)]);
+	}
+
+	#[test]
+	fn ranges_count_characters_and_respect_case() {
+		let text = "Über über ÜBER";
+		assert_eq!(match_ranges(text, &query("über", false, false).matcher().unwrap()), vec![(0, 4), (5, 9), (10, 14)]);
+		assert_eq!(match_ranges(text, &query("über", true, false).matcher().unwrap()), vec![(5, 9)]);
+	}
+
+	#[test]
+	fn replacements_expand_captures_only_for_regex_queries() {
+		let matcher = query(r"(\w+)@(\w+)", true, true).matcher().unwrap();
+		assert_eq!(replace_matches("me@home", &matcher, "$2 at $1", true).as_deref(), Some("home at me"));
+
+		let matcher = query("cost", false, false).matcher().unwrap();
+		assert_eq!(replace_matches("Cost: 5", &matcher, "$price", false).as_deref(), Some("$price: 5"));
+		assert_eq!(replace_matches("Free", &matcher, "$price", false), None);
+	}
+
+	#[test]
+	fn invalid_regex_is_reported() {
+		assert!(query("(unclosed", false, true).matcher().is_err());
+		assert!(query("(unclosed", false, false).matcher().is_ok());
+	}
+}
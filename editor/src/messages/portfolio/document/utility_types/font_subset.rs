@@ -0,0 +1,270 @@
+//! Font embedding for SVG exports, which lets text stay editable and selectable in the exported file while still showing in the right typeface.
+//!
+//! Fonts with TrueType outlines are subset to the glyphs the text uses. Unused glyphs are emptied out of the `glyf` table rather than
+//! removed, so the glyph IDs stay the same and the character map and every other table referring to glyphs by ID remain valid without
+//! being rewritten. Fonts with CFF outlines are embedded whole.
+
+use base64::Engine;
+use std::collections::BTreeSet;
+
+/// The value which the checksum of a whole font file must add up to, as set by the `checkSumAdjustment` field of the `head` table.
+const FONT_CHECKSUM: u32 = 0xB1B0AFBA;
+
+/// Flags of a component in a composite glyph which decide the size of its arguments and transform.
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+	Some(u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+	Some(u32::from_be_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+/// The sum of the data as big endian 32-bit words, with the last word padded with zeros.
+fn checksum(data: &[u8]) -> u32 {
+	data.chunks(4).fold(0, |sum: u32, chunk| {
+		let mut word = [0; 4];
+		word[..chunk.len()].copy_from_slice(chunk);
+		sum.wrapping_add(u32::from_be_bytes(word))
+	})
+}
+
+/// Each table of the font by its tag, in the order of the table directory. Returns `None` unless this is a TrueType font.
+fn read_tables(font: &[u8]) -> Option<Vec<([u8; 4], &[u8])>> {
+	if !matches!(read_u32(font, 0)?, 0x00010000 | 0x74727565) {
+		return None;
+	}
+
+	let count = read_u16(font, 4)? as usize;
+	(0..count)
+		.map(|index| {
+			let record = 12 + index * 16;
+			let tag = font.get(record..record + 4)?.try_into().ok()?;
+			let offset = read_u32(font, record + 8)? as usize;
+			let length = read_u32(font, record + 12)? as usize;
+			Some((tag, font.get(offset..offset.checked_add(length)?)?))
+		})
+		.collect()
+}
+
+/// Assembles a font file from its tables, filling in the table directory and the checksums.
+fn write_font(mut tables: Vec<([u8; 4], &[u8])>) -> Vec<u8> {
+	tables.sort_by_key(|(tag, _)| *tag);
+
+	let count = tables.len() as u16;
+	let entry_selector = count.max(1).ilog2() as u16;
+	let search_range = 16 << entry_selector;
+
+	let mut font = Vec::new();
+	font.extend(0x00010000_u32.to_be_bytes());
+	for value in [count, search_range, entry_selector, (count * 16).saturating_sub(search_range)] {
+		font.extend(value.to_be_bytes());
+	}
+
+	let mut offset = 12 + tables.len() * 16;
+	for (tag, data) in &tables {
+		font.extend(tag);
+		font.extend(checksum(data).to_be_bytes());
+		font.extend((offset as u32).to_be_bytes());
+		font.extend((data.len() as u32).to_be_bytes());
+		offset += (data.len() + 3) & !3;
+	}
+
+	let mut head_offset = None;
+	for (tag, data) in &tables {
+		if tag == b"head" {
+			head_offset = Some(font.len());
+		}
+		font.extend(*data);
+		font.resize((font.len() + 3) & !3, 0);
+	}
+
+	if let Some(head_offset) = head_offset.filter(|&head_offset| font.len() >= head_offset + 12) {
+		let adjustment = FONT_CHECKSUM.wrapping_sub(checksum(&font));
+		font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+	}
+
+	font
+}
+
+/// The glyphs which a composite glyph is built from, or none if it is a simple glyph.
+fn component_glyphs(glyph: &[u8]) -> Vec<u16> {
+	let mut components = Vec::new();
+	if read_u16(glyph, 0).map_or(true, |contours| (contours as i16) >= 0) {
+		return components;
+	}
+
+	let mut offset = 10;
+	while let (Some(flags), Some(glyph_id)) = (read_u16(glyph, offset), read_u16(glyph, offset + 2)) {
+		components.push(glyph_id);
+
+		offset += if flags & ARG_1_AND_2_ARE_WORDS != 0 { 8 } else { 6 };
+		offset += match flags {
+			flags if flags & WE_HAVE_A_SCALE != 0 => 2,
+			flags if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 => 4,
+			flags if flags & WE_HAVE_A_TWO_BY_TWO != 0 => 8,
+			_ => 0,
+		};
+
+		if flags & MORE_COMPONENTS == 0 {
+			break;
+		}
+	}
+
+	components
+}
+
+/// Reduces a TrueType font to the given glyphs, along with the `.notdef` glyph and any glyphs which they are composed of.
+/// Returns `None` if the font doesn't have TrueType outlines or can't be read.
+pub fn subset_font(font: &[u8], glyph_ids: impl IntoIterator<Item = u16>) -> Option<Vec<u8>> {
+	let tables = read_tables(font)?;
+	let table = |tag: &[u8; 4]| tables.iter().find(|(table_tag, _)| table_tag == tag).map(|(_, data)| *data);
+	let (glyf, loca, head, maxp) = (table(b"glyf")?, table(b"loca")?, table(b"head")?, table(b"maxp")?);
+
+	let glyph_count = read_u16(maxp, 4)? as usize;
+	let long_offsets = read_u16(head, 50)? != 0;
+	let offsets = (0..=glyph_count)
+		.map(|index| match long_offsets {
+			true => read_u32(loca, index * 4).map(|offset| offset as usize),
+			false => read_u16(loca, index * 2).map(|offset| offset as usize * 2),
+		})
+		.collect::<Option<Vec<_>>>()?;
+	let glyph = |id: u16| glyf.get(offsets[id as usize]..offsets[id as usize + 1]);
+
+	// Glyph 0 is the `.notdef` glyph shown in place of missing characters, which every font must keep
+	let mut kept = BTreeSet::new();
+	let mut pending = glyph_ids.into_iter().chain([0]).collect::<Vec<_>>();
+	while let Some(id) = pending.pop() {
+		if (id as usize) < glyph_count && kept.insert(id) {
+			pending.extend(component_glyphs(glyph(id)?));
+		}
+	}
+
+	let mut subset_glyf = Vec::new();
+	let mut subset_loca = Vec::with_capacity((glyph_count + 1) * 4);
+	for id in 0..glyph_count as u16 {
+		subset_loca.extend((subset_glyf.len() as u32).to_be_bytes());
+		if kept.contains(&id) {
+			subset_glyf.extend(glyph(id)?);
+			subset_glyf.resize((subset_glyf.len() + 3) & !3, 0);
+		}
+	}
+	subset_loca.extend((subset_glyf.len() as u32).to_be_bytes());
+
+	// The new `loca` table always uses 32-bit offsets, and the checksum adjustment is cleared to be recalculated for the new file
+	let mut subset_head = head.to_vec();
+	subset_head[8..12].fill(0);
+	subset_head[50..52].copy_from_slice(&1_u16.to_be_bytes());
+
+	let subset_tables = tables
+		.iter()
+		// A digital signature no longer matches once the font is changed
+		.filter(|(tag, _)| tag != b"DSIG")
+		.map(|&(tag, data)| match &tag {
+			b"glyf" => (tag, subset_glyf.as_slice()),
+			b"loca" => (tag, subset_loca.as_slice()),
+			b"head" => (tag, subset_head.as_slice()),
+			_ => (tag, data),
+		})
+		.collect();
+
+	Some(write_font(subset_tables))
+}
+
+/// A CSS `@font-face` rule which embeds the font under the given family name as a data URL, subset to the given glyphs if possible.
+pub fn font_face_rule(family: &str, font: &[u8], glyph_ids: &[u16]) -> String {
+	let (data, mime, format) = match subset_font(font, glyph_ids.iter().copied()) {
+		Some(subset) => (subset, "font/ttf", "truetype"),
+		None if font.starts_with(b"OTTO") => (font.to_vec(), "font/otf", "opentype"),
+		None => (font.to_vec(), "font/ttf", "truetype"),
+	};
+	let data = base64::engine::general_purpose::STANDARD.encode(data);
+
+	format!(r#"@font-face{{font-family:'{family}';src:url(data:{mime};base64,{data}) format("{format}");}}"#)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// A simple glyph with a single contour and some arbitrary outline data.
+	fn simple_glyph(marker: u8) -> Vec<u8> {
+		let mut glyph = vec![0, 1, 0, 0, 0, 0, 0, 10, 0, 10];
+		glyph.extend([marker; 6]);
+		glyph
+	}
+
+	/// A font with four glyphs, where glyph 2 is a composite of glyph 3, using 16-bit `loca` offsets.
+	fn test_font() -> Vec<u8> {
+		let composite = vec![0xFF, 0xFF, 0, 0, 0, 0, 0, 10, 0, 10, 0, 0, 0, 3, 0, 0];
+		let glyphs = [simple_glyph(0xA0), simple_glyph(0xA1), composite, simple_glyph(0xA3)];
+
+		let mut glyf = Vec::new();
+		let mut loca = Vec::new();
+		for glyph in &glyphs {
+			loca.extend(((glyf.len() / 2) as u16).to_be_bytes());
+			glyf.extend(glyph);
+		}
+		loca.extend(((glyf.len() / 2) as u16).to_be_bytes());
+
+		let head = vec![0; 54];
+		let maxp = vec![0, 0, 0x50, 0, 0, 4];
+
+		write_font(vec![
+			(*b"glyf", &glyf[..]),
+			(*b"loca", &loca[..]),
+			(*b"head", &head[..]),
+			(*b"maxp", &maxp[..]),
+			(*b"DSIG", &[0; 8][..]),
+		])
+	}
+
+	fn glyphs_of(font: &[u8]) -> Vec<Vec<u8>> {
+		let tables = read_tables(font).unwrap();
+		let table = |tag: &[u8; 4]| tables.iter().find(|(table_tag, _)| table_tag == tag).unwrap().1;
+		let (glyf, loca) = (table(b"glyf"), table(b"loca"));
+		assert_eq!(read_u16(table(b"head"), 50), Some(1));
+
+		(0..4)
+			.map(|id| glyf[read_u32(loca, id * 4).unwrap() as usize..read_u32(loca, id * 4 + 4).unwrap() as usize].to_vec())
+			.collect()
+	}
+
+	#[test]
+	fn subset_keeps_used_and_component_glyphs() {
+		let font = test_font();
+		let subset = subset_font(&font, [2]).unwrap();
+
+		let glyphs = glyphs_of(&subset);
+		assert_eq!(glyphs[0], simple_glyph(0xA0));
+		assert!(glyphs[1].is_empty());
+		assert_eq!(glyphs[2].len(), 16);
+		assert_eq!(glyphs[3], simple_glyph(0xA3));
+
+		let tables = read_tables(&subset).unwrap();
+		assert!(tables.iter().all(|(tag, _)| tag != b"DSIG"));
+		assert_eq!(checksum(&subset), FONT_CHECKSUM);
+	}
+
+	#[test]
+	fn composite_glyph_components() {
+		assert_eq!(component_glyphs(&simple_glyph(0)), Vec::<u16>::new());
+
+		// Two components, the first with 16-bit arguments and a scale, the second with 8-bit arguments
+		let mut composite = vec![0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0];
+		composite.extend([0, 0x29, 0, 7, 0, 0, 0, 0, 0x40, 0]);
+		composite.extend([0, 0, 0, 9, 0, 0]);
+		assert_eq!(component_glyphs(&composite), vec![7, 9]);
+	}
+
+	#[test]
+	fn fonts_without_truetype_outlines_are_not_subset() {
+		assert!(subset_font(b"OTTO\0\0\0\0\0\0\0\0", [1]).is_none());
+		assert!(subset_font(&[], [1]).is_none());
+	}
+}
@@ -0,0 +1,343 @@
+//! Isometric and perspective guide grids, drawn over the canvas as an aid for technical and environment illustration.
+//!
+//! The guides only exist in the viewport and are never part of the artwork. While drawing with the Line and Pen tools, segments drawn
+//! close to the direction of one of the guides through their start are snapped onto it.
+
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+use glam::{DAffine2, DVec2};
+use serde::{Deserialize, Serialize};
+
+/// How far, in degrees, a segment can be from the direction of a guide before it stops snapping to it.
+const SNAP_ANGLE_TOLERANCE: f64 = 4.;
+/// How many guides fan out from each vanishing point of a perspective grid, evenly spaced around it.
+const PERSPECTIVE_RAYS: usize = 36;
+/// The most lines drawn for each direction of an isometric grid, beyond which the grid is too dense to be useful and that direction is left out.
+const MAX_ISOMETRIC_LINES: f64 = 400.;
+/// Opacity of the guide grid overlay, kept faint so it doesn't compete with the artwork.
+pub const GUIDE_GRID_OVERLAY_OPACITY: f64 = 0.35;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuideGridType {
+	#[default]
+	Isometric,
+	Perspective,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GuideGrid {
+	pub visible: bool,
+	/// Snaps line and pen segments to the guide directions when they are drawn close to one
+	pub snapping: bool,
+	pub grid_type: GuideGridType,
+	/// Angle, in degrees above the horizontal, of the two slanted axes of the isometric grid
+	pub isometric_angle: f64,
+	/// Length, in document pixels, of the edges of the isometric grid along its slanted axes
+	pub isometric_spacing: f64,
+	/// How many of the vanishing points the perspective grid uses, from one to three
+	pub vanishing_point_count: usize,
+	/// Positions of the vanishing points in document space, with the first two on the horizon of a two-point perspective
+	pub vanishing_points: [DVec2; 3],
+}
+
+impl Default for GuideGrid {
+	fn default() -> Self {
+		Self {
+			visible: false,
+			snapping: true,
+			grid_type: GuideGridType::Isometric,
+			isometric_angle: 30.,
+			isometric_spacing: 50.,
+			vanishing_point_count: 2,
+			vanishing_points: [DVec2::new(-500., 400.), DVec2::new(1500., 400.), DVec2::new(500., 3000.)],
+		}
+	}
+}
+
+impl GuideGrid {
+	fn active_vanishing_points(&self) -> &[DVec2] {
+		&self.vanishing_points[..self.vanishing_point_count.clamp(1, 3)]
+	}
+
+	/// The unit directions of the guides passing through a point in document space.
+	pub fn directions(&self, point: DVec2) -> Vec<DVec2> {
+		match self.grid_type {
+			GuideGridType::Isometric => {
+				let (sin, cos) = self.isometric_angle.to_radians().sin_cos();
+				vec![DVec2::new(cos, -sin), DVec2::new(cos, sin), DVec2::Y]
+			}
+			GuideGridType::Perspective => {
+				let vanishing_points = self.active_vanishing_points();
+				// Edges which don't recede toward any of the vanishing points stay horizontal or vertical
+				let parallel: &[DVec2] = match vanishing_points.len() {
+					1 => &[DVec2::X, DVec2::Y],
+					2 => &[DVec2::Y],
+					_ => &[],
+				};
+				let receding = vanishing_points.iter().filter_map(|&vanishing_point| (vanishing_point - point).try_normalize());
+				parallel.iter().copied().chain(receding).collect()
+			}
+		}
+	}
+
+	/// Moves the end of a segment in document space onto the guide through its start that is closest in direction, if it is close enough to snap to.
+	pub fn snap_segment(&self, start: DVec2, end: DVec2) -> Option<DVec2> {
+		let segment = end - start;
+		let length = segment.length();
+		if length == 0. {
+			return None;
+		}
+
+		// The sine of the angle between the segment and each guide, which is small when they are parallel in either direction
+		self.directions(start)
+			.into_iter()
+			.map(|direction| (direction, segment.perp_dot(direction).abs() / length))
+			.filter(|&(_, sine)| sine <= SNAP_ANGLE_TOLERANCE.to_radians().sin())
+			.min_by(|(_, a), (_, b)| a.total_cmp(b))
+			.map(|(direction, _)| start + direction * segment.dot(direction))
+	}
+
+	/// The guide lines that cross the viewport, clipped to it, in viewport space.
+	pub fn viewport_lines(&self, document_to_viewport: DAffine2, viewport_size: DVec2) -> Vec<[DVec2; 2]> {
+		let viewport_line =
+			|point: DVec2, direction: DVec2, range: [f64; 2]| clip_line(document_to_viewport.transform_point2(point), document_to_viewport.transform_vector2(direction), range, viewport_size);
+
+		match self.grid_type {
+			GuideGridType::Isometric => {
+				let spacing = self.isometric_spacing;
+				if spacing <= 0. {
+					return Vec::new();
+				}
+
+				// Every guide passes through the points of the lattice with these edges, so each direction's guides are spaced by the farther edge from the guide through the origin
+				let (sin, cos) = self.isometric_angle.to_radians().sin_cos();
+				let [right_up, right_down] = [DVec2::new(cos, -sin) * spacing, DVec2::new(cos, sin) * spacing];
+				let viewport_to_document = document_to_viewport.inverse();
+				let corners = [DVec2::ZERO, DVec2::new(viewport_size.x, 0.), DVec2::new(0., viewport_size.y), viewport_size].map(|corner| viewport_to_document.transform_point2(corner));
+
+				let mut lines = Vec::new();
+				for direction in self.directions(DVec2::ZERO) {
+					let normal = direction.perp();
+					let line_spacing = right_up.dot(normal).abs().max(right_down.dot(normal).abs());
+					let offsets = corners.map(|corner| corner.dot(normal) / line_spacing);
+					let first = offsets.iter().copied().fold(f64::INFINITY, f64::min).ceil();
+					let last = offsets.iter().copied().fold(f64::NEG_INFINITY, f64::max).floor();
+					if !(line_spacing > f64::EPSILON && last - first < MAX_ISOMETRIC_LINES) {
+						continue;
+					}
+
+					let mut index = first;
+					while index <= last {
+						lines.extend(viewport_line(normal * index * line_spacing, direction, [f64::NEG_INFINITY, f64::INFINITY]));
+						index += 1.;
+					}
+				}
+				lines
+			}
+			GuideGridType::Perspective => {
+				let vanishing_points = self.active_vanishing_points();
+				let rays = vanishing_points.iter().flat_map(|&vanishing_point| {
+					(0..PERSPECTIVE_RAYS).filter_map(move |index| {
+						let angle = index as f64 * std::f64::consts::TAU / PERSPECTIVE_RAYS as f64;
+						viewport_line(vanishing_point, DVec2::from_angle(angle), [0., f64::INFINITY])
+					})
+				});
+				// The horizon is level with the first vanishing point, which it shares with the second in a two-point perspective
+				let horizon = (vanishing_points.len() < 3)
+					.then(|| viewport_line(vanishing_points[0], DVec2::X, [f64::NEG_INFINITY, f64::INFINITY]))
+					.flatten();
+				rays.chain(horizon).collect()
+			}
+		}
+	}
+
+	/// The settings in the popover of the guide grid, each of which sends an updated copy of the guide grid.
+	pub fn options_widget(&self) -> Vec<LayoutGroup> {
+		let update = |guide_grid: &Self, modify: fn(&mut Self, f64)| {
+			let guide_grid = guide_grid.clone();
+			move |number_input: &NumberInput| -> Message {
+				let mut guide_grid = guide_grid.clone();
+				modify(&mut guide_grid, number_input.value.unwrap_or_default());
+				DocumentMessage::SetGuideGrid { guide_grid }.into()
+			}
+		};
+		let row = |label: &str, widgets: Vec<WidgetHolder>| LayoutGroup::Row {
+			widgets: [
+				TextLabel::new(label).table_align(true).min_width(80).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+			]
+			.into_iter()
+			.chain(widgets)
+			.collect(),
+		};
+
+		let grid_types = [("Isometric", GuideGridType::Isometric), ("Perspective", GuideGridType::Perspective)]
+			.into_iter()
+			.map(|(name, grid_type)| {
+				let guide_grid = self.clone();
+				RadioEntryData::new(name).on_update(move |_| {
+					DocumentMessage::SetGuideGrid {
+						guide_grid: GuideGrid { grid_type, ..guide_grid.clone() },
+					}
+					.into()
+				})
+			})
+			.collect();
+		let grid_type_index = match self.grid_type {
+			GuideGridType::Isometric => 0,
+			GuideGridType::Perspective => 1,
+		};
+
+		let guide_grid = self.clone();
+		let mut options = vec![
+			row("Type", vec![RadioInput::new(grid_types).selected_index(grid_type_index).widget_holder()]),
+			row(
+				"Snap Segments",
+				vec![CheckboxInput::new(self.snapping)
+					.tooltip("Snap line and pen segments drawn close to the direction of a guide onto it")
+					.on_update(move |input: &CheckboxInput| {
+						DocumentMessage::SetGuideGrid {
+							guide_grid: GuideGrid {
+								snapping: input.checked,
+								..guide_grid.clone()
+							},
+						}
+						.into()
+					})
+					.widget_holder()],
+			),
+		];
+
+		match self.grid_type {
+			GuideGridType::Isometric => {
+				options.push(row(
+					"Angle",
+					vec![NumberInput::new(Some(self.isometric_angle))
+						.unit("°")
+						.min(1.)
+						.max(89.)
+						.on_update(update(self, |guide_grid, angle| guide_grid.isometric_angle = angle))
+						.widget_holder()],
+				));
+				options.push(row(
+					"Spacing",
+					vec![NumberInput::new(Some(self.isometric_spacing))
+						.unit(" px")
+						.min(1.)
+						.on_update(update(self, |guide_grid, spacing| guide_grid.isometric_spacing = spacing))
+						.widget_holder()],
+				));
+			}
+			GuideGridType::Perspective => {
+				let counts = (1..=3)
+					.map(|count| {
+						let guide_grid = self.clone();
+						RadioEntryData::new(count.to_string()).on_update(move |_| {
+							DocumentMessage::SetGuideGrid {
+								guide_grid: GuideGrid {
+									vanishing_point_count: count,
+									..guide_grid.clone()
+								},
+							}
+							.into()
+						})
+					})
+					.collect();
+				options.push(row(
+					"Vanishing Points",
+					vec![RadioInput::new(counts).selected_index(self.active_vanishing_points().len() as u32 - 1).widget_holder()],
+				));
+
+				let modifiers: [[fn(&mut Self, f64); 2]; 3] = [
+					[|guide_grid, x| guide_grid.vanishing_points[0].x = x, |guide_grid, y| guide_grid.vanishing_points[0].y = y],
+					[|guide_grid, x| guide_grid.vanishing_points[1].x = x, |guide_grid, y| guide_grid.vanishing_points[1].y = y],
+					[|guide_grid, x| guide_grid.vanishing_points[2].x = x, |guide_grid, y| guide_grid.vanishing_points[2].y = y],
+				];
+				for (index, (vanishing_point, [modify_x, modify_y])) in self.active_vanishing_points().iter().zip(modifiers).enumerate() {
+					options.push(row(
+						&format!("Point {}", index + 1),
+						vec![
+							NumberInput::new(Some(vanishing_point.x)).label("X").unit(" px").on_update(update(self, modify_x)).widget_holder(),
+							Separator::new(SeparatorType::Related).widget_holder(),
+							NumberInput::new(Some(vanishing_point.y)).label("Y").unit(" px").on_update(update(self, modify_y)).widget_holder(),
+						],
+					));
+				}
+			}
+		}
+
+		options
+	}
+}
+
+/// The part of the line `origin + direction * t`, with `t` within the range, that crosses the rectangle from the origin to `size`.
+fn clip_line(origin: DVec2, direction: DVec2, mut range: [f64; 2], size: DVec2) -> Option<[DVec2; 2]> {
+	if direction == DVec2::ZERO {
+		return None;
+	}
+
+	for axis in 0..2 {
+		if direction[axis] == 0. {
+			if origin[axis] < 0. || origin[axis] > size[axis] {
+				return None;
+			}
+			continue;
+		}
+
+		let entry = -origin[axis] / direction[axis];
+		let exit = (size[axis] - origin[axis]) / direction[axis];
+		range[0] = range[0].max(entry.min(exit));
+		range[1] = range[1].min(entry.max(exit));
+	}
+
+	(range[0] < range[1]).then(|| range.map(|t| origin + direction * t))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn segments_snap_to_the_closest_guide() {
+		let isometric = GuideGrid::default();
+		let start = DVec2::new(10., 10.);
+		let end = start + DVec2::from_angle(-31_f64.to_radians()) * 100.;
+
+		let snapped = isometric.snap_segment(start, end).unwrap();
+		assert!(((snapped - start).angle_between(DVec2::X) - 30_f64.to_radians()).abs() < 1e-9);
+		assert!(isometric.snap_segment(start, start + DVec2::new(100., 0.)).is_none());
+
+		let perspective = GuideGrid {
+			grid_type: GuideGridType::Perspective,
+			vanishing_point_count: 1,
+			vanishing_points: [DVec2::new(100., 0.), DVec2::ZERO, DVec2::ZERO],
+			..Default::default()
+		};
+		let snapped = perspective.snap_segment(DVec2::ZERO, DVec2::new(50., 2.)).unwrap();
+		assert!((snapped - DVec2::new(50., 0.)).length() < 1e-9);
+		let snapped = perspective.snap_segment(DVec2::new(0., 100.), DVec2::new(52., 50.)).unwrap();
+		assert!((snapped - DVec2::new(51., 49.)).length() < 1e-9);
+	}
+
+	#[test]
+	fn lines_are_clipped_to_the_viewport() {
+		let size = DVec2::new(100., 50.);
+		let infinite = [f64::NEG_INFINITY, f64::INFINITY];
+
+		assert_eq!(clip_line(DVec2::new(20., 20.), DVec2::X, infinite, size), Some([DVec2::new(0., 20.), DVec2::new(100., 20.)]));
+		assert_eq!(
+			clip_line(DVec2::new(20., 20.), DVec2::X, [0., f64::INFINITY], size),
+			Some([DVec2::new(20., 20.), DVec2::new(100., 20.)])
+		);
+		assert_eq!(clip_line(DVec2::new(20., 60.), DVec2::X, infinite, size), None);
+		assert_eq!(clip_line(DVec2::new(-10., 0.), DVec2::new(-1., 1.), [0., f64::INFINITY], size), None);
+
+		let lines = GuideGrid::default().viewport_lines(DAffine2::IDENTITY, size);
+		assert!(!lines.is_empty());
+		assert!(lines
+			.iter()
+			.flatten()
+			.all(|point| point.x >= -1e-9 && point.y >= -1e-9 && point.x <= size.x + 1e-9 && point.y <= size.y + 1e-9));
+	}
+}
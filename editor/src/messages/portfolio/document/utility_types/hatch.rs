@@ -0,0 +1,294 @@
+use super::vector_export::flatten_subpath;
+
+use graphene_core::uuid::ManipulatorGroupId;
+
+use bezier_rs::Subpath;
+use glam::{DAffine2, DVec2};
+use serde::{Deserialize, Serialize};
+
+/// How far, in document pixels, the flattened outline may stray from the curves of the shape being hatched.
+const HATCH_FLATTENING_TOLERANCE: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HatchOptions {
+	/// Direction of the hatch lines in degrees, measured clockwise from the horizontal.
+	pub angle: f64,
+	/// Distance between neighboring hatch lines in document pixels.
+	pub spacing: f64,
+	/// Distance kept clear between the hatch lines and the outline of the shape.
+	pub inset: f64,
+	/// Add a second set of hatch lines perpendicular to the first.
+	pub cross_hatch: bool,
+}
+
+impl Default for HatchOptions {
+	fn default() -> Self {
+		Self {
+			angle: 45.,
+			spacing: 5.,
+			inset: 0.,
+			cross_hatch: false,
+		}
+	}
+}
+
+/// Generates the hatch lines that fill the interior of the subpaths (using the nonzero fill rule), in the same space as the subpaths.
+/// Consecutive lines alternate direction so a plotter can draw them with little travel.
+pub fn hatch_subpaths(subpaths: &[Subpath<ManipulatorGroupId>], options: HatchOptions) -> Vec<Subpath<ManipulatorGroupId>> {
+	if options.spacing <= 0. {
+		return Vec::new();
+	}
+
+	let polygons: Vec<_> = subpaths
+		.iter()
+		.map(|subpath| flatten_subpath(subpath, HATCH_FLATTENING_TOLERANCE).0)
+		.filter(|points| points.len() >= 3)
+		.collect();
+
+	let mut angles = vec![options.angle];
+	if options.cross_hatch {
+		angles.push(options.angle + 90.);
+	}
+
+	angles
+		.into_iter()
+		.flat_map(|angle| hatch_polygons(&polygons, angle.to_radians(), options.spacing, options.inset))
+		.map(|[start, end]| Subpath::from_anchors([start, end], false))
+		.collect()
+}
+
+fn hatch_polygons(polygons: &[Vec<DVec2>], angle: f64, spacing: f64, inset: f64) -> Vec<[DVec2; 2]> {
+	// Rotate the outline so the hatch lines become horizontal scanlines, then rotate the results back
+	let to_hatch_space = DAffine2::from_angle(-angle);
+	let from_hatch_space = DAffine2::from_angle(angle);
+	let polygons: Vec<Vec<DVec2>> = polygons.iter().map(|polygon| polygon.iter().map(|&point| to_hatch_space.transform_point2(point)).collect()).collect();
+	let edges: Vec<(DVec2, DVec2)> = polygons.iter().flat_map(|polygon| polygon.iter().copied().zip(polygon.iter().copied().cycle().skip(1))).collect();
+
+	let Some((min_y, max_y)) = edges
+		.iter()
+		.map(|(a, _)| a.y)
+		.fold(None, |range: Option<(f64, f64)>, y| Some(range.map_or((y, y), |(min, max)| (min.min(y), max.max(y)))))
+	else {
+		return Vec::new();
+	};
+
+	let mut lines = Vec::new();
+	let mut y = min_y + spacing / 2.;
+	let mut reverse = false;
+	while y < max_y {
+		let mut spans = scanline_spans(&edges, y);
+		if inset > 0. {
+			spans = spans.into_iter().flat_map(|span| subtract_inset(span, y, &edges, inset)).collect();
+		}
+
+		if reverse {
+			spans.reverse();
+		}
+		for (start, end) in spans {
+			let (start, end) = if reverse { (end, start) } else { (start, end) };
+			lines.push([from_hatch_space.transform_point2(DVec2::new(start, y)), from_hatch_space.transform_point2(DVec2::new(end, y))]);
+		}
+
+		reverse = !reverse;
+		y += spacing;
+	}
+
+	lines
+}
+
+/// Finds the horizontal spans at height `y` that are inside the outline according to the nonzero fill rule.
+fn scanline_spans(edges: &[(DVec2, DVec2)], y: f64) -> Vec<(f64, f64)> {
+	let mut crossings: Vec<(f64, i32)> = edges
+		.iter()
+		.filter_map(|&(a, b)| {
+			let direction = match (a.y <= y, b.y <= y) {
+				(true, false) => 1,
+				(false, true) => -1,
+				_ => return None,
+			};
+			let x = a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x);
+			Some((x, direction))
+		})
+		.collect();
+	crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+	let mut spans = Vec::new();
+	let mut winding = 0;
+	let mut span_start = 0.;
+	for (x, direction) in crossings {
+		let was_inside = winding != 0;
+		winding += direction;
+		match (was_inside, winding != 0) {
+			(false, true) => span_start = x,
+			(true, false) if x > span_start => spans.push((span_start, x)),
+			_ => {}
+		}
+	}
+	spans
+}
+
+/// Removes the parts of a span that come closer than `inset` to any edge of the outline.
+fn subtract_inset(span: (f64, f64), y: f64, edges: &[(DVec2, DVec2)], inset: f64) -> Vec<(f64, f64)> {
+	let mut spans = vec![span];
+	for &(a, b) in edges {
+		let Some((cut_start, cut_end)) = capsule_interval(a, b, y, inset) else { continue };
+		spans = spans
+			.into_iter()
+			.flat_map(|(start, end)| {
+				let mut remaining = Vec::with_capacity(2);
+				if cut_start > start {
+					remaining.push((start, cut_start.min(end)));
+				}
+				if cut_end < end {
+					remaining.push((cut_end.max(start), end));
+				}
+				remaining.into_iter().filter(|(start, end)| end > start)
+			})
+			.collect();
+	}
+	spans
+}
+
+/// Finds the interval of x values on the horizontal line at height `y` that lie within `radius` of the segment from `a` to `b`.
+fn capsule_interval(a: DVec2, b: DVec2, y: f64, radius: f64) -> Option<(f64, f64)> {
+	// The capsule is the union of a circle around each end and the rectangle swept between them, and since it's convex, so is its intersection with the line
+	let circle = |center: DVec2| {
+		let dy = y - center.y;
+		let half_width = (radius * radius - dy * dy).sqrt();
+		half_width.is_finite().then_some((center.x - half_width, center.x + half_width))
+	};
+
+	let mut interval = [circle(a), circle(b)].into_iter().flatten().reduce(|a, b| (a.0.min(b.0), a.1.max(b.1)));
+
+	let direction = b - a;
+	let length = direction.length();
+	if length > f64::EPSILON {
+		let direction = direction / length;
+		// Points on the line are inside the rectangle when their projection onto the segment lands within it and they are near enough to the segment's line
+		let (mut low, mut high) = (f64::NEG_INFINITY, f64::INFINITY);
+		for (axis, min, max) in [(direction, 0., length), (direction.perp(), -radius, radius)] {
+			// Solve min <= axis · ((x, y) - a) <= max for x
+			let constant = axis.y * (y - a.y) - axis.x * a.x;
+			if axis.x.abs() < f64::EPSILON {
+				if constant < min || constant > max {
+					return interval;
+				}
+				continue;
+			}
+			let (first, second) = ((min - constant) / axis.x, (max - constant) / axis.x);
+			low = low.max(first.min(second));
+			high = high.min(first.max(second));
+		}
+		if low <= high {
+			interval = Some(interval.map_or((low, high), |(start, end)| (start.min(low), end.max(high))));
+		}
+	}
+
+	interval
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn polygon(points: &[(f64, f64)]) -> Subpath<ManipulatorGroupId> {
+		Subpath::from_anchors(points.iter().map(|&(x, y)| DVec2::new(x, y)), true)
+	}
+
+	fn square() -> Subpath<ManipulatorGroupId> {
+		Subpath::new_rect(DVec2::ZERO, DVec2::splat(100.))
+	}
+
+	fn ends(lines: &[Subpath<ManipulatorGroupId>]) -> Vec<[DVec2; 2]> {
+		lines.iter().map(|line| [line.manipulator_groups()[0].anchor, line.manipulator_groups()[1].anchor]).collect()
+	}
+
+	fn hatch(subpaths: &[Subpath<ManipulatorGroupId>], angle: f64, inset: f64, cross_hatch: bool) -> Vec<[DVec2; 2]> {
+		let options = HatchOptions {
+			angle,
+			spacing: 10.,
+			inset,
+			cross_hatch,
+		};
+		ends(&hatch_subpaths(subpaths, options))
+	}
+
+	#[test]
+	fn scanlines_are_spaced_evenly_and_alternate_direction() {
+		let lines = hatch(&[square()], 0., 0., false);
+
+		// The first line is half the spacing in from the edge, so the lines are centered in the shape
+		assert_eq!(lines.len(), 10);
+		for (index, [start, end]) in lines.iter().enumerate() {
+			let y = 5. + 10. * index as f64;
+			let (left, right) = (DVec2::new(0., y), DVec2::new(100., y));
+			assert_eq!([*start, *end], if index % 2 == 0 { [left, right] } else { [right, left] });
+		}
+	}
+
+	#[test]
+	fn angle_turns_the_lines_clockwise() {
+		for [start, end] in hatch(&[square()], 90., 0., false) {
+			assert!((start.x - end.x).abs() < 1e-9, "Lines at 90° are vertical");
+			assert!((start.y - end.y).abs() > 99.);
+		}
+
+		let lines = hatch(&[square()], 45., 0., false);
+		for [start, end] in &lines {
+			assert!((end - start).perp_dot(DVec2::ONE).abs() < 1e-6, "Lines at 45° run from the top left to the bottom right");
+		}
+		// Neighboring lines are the spacing apart, measured across them
+		let across = DVec2::new(1., -1.).normalize();
+		for pair in lines.windows(2) {
+			assert!(((pair[1][0] - pair[0][0]).dot(across).abs() - 10.).abs() < 1e-6);
+		}
+	}
+
+	#[test]
+	fn inset_keeps_lines_clear_of_the_outline() {
+		let lines = hatch(&[square()], 0., 10., false);
+
+		// The lines within the inset of the top and bottom edges are left out entirely, and the rest are shortened at both ends
+		assert_eq!(lines.len(), 8);
+		for [start, end] in lines {
+			assert!(start.y >= 10. && start.y <= 90.);
+			assert!((start.x.min(end.x) - 10.).abs() < 1e-9 && (start.x.max(end.x) - 90.).abs() < 1e-9);
+		}
+	}
+
+	#[test]
+	fn capsule_covers_the_segment_and_its_rounded_ends() {
+		let horizontal = (DVec2::ZERO, DVec2::new(10., 0.));
+		let (start, end) = capsule_interval(horizontal.0, horizontal.1, 1., 2.).unwrap();
+		assert!((start + 3_f64.sqrt()).abs() < 1e-9 && (end - 10. - 3_f64.sqrt()).abs() < 1e-9);
+		assert_eq!(capsule_interval(horizontal.0, horizontal.1, 3., 2.), None);
+
+		let (start, end) = capsule_interval(DVec2::ZERO, DVec2::new(0., 10.), 5., 2.).unwrap();
+		assert!((start + 2.).abs() < 1e-9 && (end - 2.).abs() < 1e-9);
+	}
+
+	#[test]
+	fn cross_hatch_adds_perpendicular_lines() {
+		let lines = hatch(&[square()], 0., 0., true);
+		assert_eq!(lines.len(), 20);
+		assert!(lines[..10].iter().all(|[start, end]| start.y == end.y));
+		assert!(lines[10..].iter().all(|[start, end]| (start.x - end.x).abs() < 1e-9));
+	}
+
+	#[test]
+	fn concave_shapes_are_hatched_in_separate_spans() {
+		// A U shape, whose arms are split apart by the gap between them
+		let u_shape = polygon(&[(0., 0.), (30., 0.), (30., 30.), (20., 30.), (20., 10.), (10., 10.), (10., 30.), (0., 30.)]);
+		let lines = hatch(&[u_shape], 0., 0., false);
+
+		let expected = [
+			[DVec2::new(0., 5.), DVec2::new(30., 5.)],
+			// The spans of a reversed scanline are drawn from right to left too
+			[DVec2::new(30., 15.), DVec2::new(20., 15.)],
+			[DVec2::new(10., 15.), DVec2::new(0., 15.)],
+			[DVec2::new(0., 25.), DVec2::new(10., 25.)],
+			[DVec2::new(20., 25.), DVec2::new(30., 25.)],
+		];
+		assert_eq!(lines, expected);
+	}
+}
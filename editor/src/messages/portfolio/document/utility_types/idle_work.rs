@@ -0,0 +1,106 @@
+use document_legacy::LayerId;
+
+use std::collections::{HashMap, VecDeque};
+
+/// Work that is put off until the user pauses, so opening a large document doesn't stall while every layer's thumbnail is rendered up front.
+#[derive(Debug, Clone, Default)]
+pub struct IdleWork {
+	pending_thumbnails: VecDeque<Vec<LayerId>>,
+	/// The most recently rendered thumbnail of each layer, shown in the meantime while a fresh one waits to be rendered.
+	thumbnails: HashMap<Vec<LayerId>, String>,
+	/// Whether the artwork changed since the composite thumbnail of the whole document, shown in its tab, was last rendered.
+	document_thumbnail_pending: bool,
+	/// The latest render worker job of each layer whose thumbnail is being rendered in the background. Older jobs finishing later are ignored.
+	rendering: HashMap<Vec<LayerId>, u64>,
+	document_thumbnail_job: Option<u64>,
+	/// Whether the document changed since its design tokens were last compared with those exported while watching for changes.
+	design_tokens_pending: bool,
+}
+
+impl IdleWork {
+	pub fn has_pending_work(&self) -> bool {
+		!self.pending_thumbnails.is_empty() || self.document_thumbnail_pending || self.design_tokens_pending
+	}
+
+	pub fn queue_thumbnail(&mut self, layer_path: Vec<LayerId>) {
+		if !self.pending_thumbnails.contains(&layer_path) {
+			self.pending_thumbnails.push_back(layer_path);
+		}
+	}
+
+	pub fn next_thumbnail(&mut self) -> Option<Vec<LayerId>> {
+		self.pending_thumbnails.pop_front()
+	}
+
+	pub fn queue_document_thumbnail(&mut self) {
+		self.document_thumbnail_pending = true;
+	}
+
+	/// Whether the document thumbnail needs rendering, which is then considered done. Layer thumbnails are rendered first since they are smaller.
+	pub fn next_document_thumbnail(&mut self) -> bool {
+		let pending = self.document_thumbnail_pending && self.pending_thumbnails.is_empty();
+		if pending {
+			self.document_thumbnail_pending = false;
+		}
+		pending
+	}
+
+	pub fn queue_design_tokens(&mut self) {
+		self.design_tokens_pending = true;
+	}
+
+	/// Whether the design tokens need comparing with those last exported, which is then considered done.
+	pub fn next_design_tokens(&mut self) -> bool {
+		std::mem::take(&mut self.design_tokens_pending)
+	}
+
+	pub fn thumbnail(&self, layer_path: &[LayerId]) -> Option<&str> {
+		self.thumbnails.get(layer_path).map(String::as_str)
+	}
+
+	/// Keeps a freshly rendered thumbnail, which also makes any queued or background rendering of it unnecessary.
+	pub fn store_thumbnail(&mut self, layer_path: Vec<LayerId>, thumbnail: String) {
+		self.pending_thumbnails.retain(|pending| *pending != layer_path);
+		self.rendering.remove(&layer_path);
+		self.thumbnails.insert(layer_path, thumbnail);
+	}
+
+	pub fn start_rendering(&mut self, layer_path: Vec<LayerId>, job_id: u64) {
+		self.rendering.insert(layer_path, job_id);
+	}
+
+	/// Whether a finished render worker job is still the latest rendering of the layer's thumbnail, which is then considered done.
+	pub fn finish_rendering(&mut self, layer_path: &[LayerId], job_id: u64) -> bool {
+		let latest = self.rendering.get(layer_path) == Some(&job_id);
+		if latest {
+			self.rendering.remove(layer_path);
+		}
+		latest
+	}
+
+	pub fn start_document_thumbnail(&mut self, job_id: u64) {
+		self.document_thumbnail_job = Some(job_id);
+	}
+
+	pub fn finish_document_thumbnail(&mut self, job_id: u64) -> bool {
+		let latest = self.document_thumbnail_job == Some(job_id);
+		if latest {
+			self.document_thumbnail_job = None;
+		}
+		latest
+	}
+
+	/// Drops everything kept for a deleted layer and the layers inside it.
+	pub fn forget(&mut self, layer_path: &[LayerId]) {
+		self.pending_thumbnails.retain(|pending| !pending.starts_with(layer_path));
+		self.rendering.retain(|path, _| !path.starts_with(layer_path));
+		self.thumbnails.retain(|path, _| !path.starts_with(layer_path));
+	}
+
+	/// Drops everything kept for the layers which are no longer in the document.
+	pub fn retain(&mut self, exists: impl Fn(&[LayerId]) -> bool) {
+		self.pending_thumbnails.retain(|pending| exists(pending));
+		self.rendering.retain(|path, _| exists(path));
+		self.thumbnails.retain(|path, _| exists(path));
+	}
+}
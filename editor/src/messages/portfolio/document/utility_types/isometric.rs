@@ -0,0 +1,116 @@
+//! Helpers for isometric illustration, where the three visible faces of a box are drawn with their edges along lines 30° from the horizontal
+//! and vertical lines, keeping the lengths along each of those axes.
+
+use graphene_core::Color;
+
+use glam::{DAffine2, DVec2};
+use serde::{Deserialize, Serialize};
+
+/// The angle from the horizontal of the receding edges of an isometric drawing.
+const ISOMETRIC_ANGLE: f64 = std::f64::consts::PI / 6.;
+
+/// How much darker each side face of an extruded box is drawn than its top face, as if lit from above.
+const LEFT_FACE_SHADE: f32 = 0.8;
+const RIGHT_FACE_SHADE: f32 = 0.6;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum IsometricPlane {
+	/// The horizontal plane, seen from above as a diamond.
+	Top,
+	/// The vertical plane facing front-left.
+	Left,
+	/// The vertical plane facing front-right.
+	Right,
+}
+
+impl IsometricPlane {
+	/// The isometric axes the flat x and y axes are mapped onto (in document space, where y points down).
+	fn axes(self) -> (DVec2, DVec2) {
+		let (sin, cos) = ISOMETRIC_ANGLE.sin_cos();
+		match self {
+			IsometricPlane::Top => (DVec2::new(cos, sin), DVec2::new(-cos, sin)),
+			IsometricPlane::Left => (DVec2::new(cos, sin), DVec2::Y),
+			IsometricPlane::Right => (DVec2::new(cos, -sin), DVec2::Y),
+		}
+	}
+
+	/// The shear and scale drawing a flat shape as if it lay on the plane, keeping the lengths along its horizontal and vertical edges.
+	pub fn projection(self) -> DAffine2 {
+		let (x_axis, y_axis) = self.axes();
+		DAffine2::from_cols(x_axis, y_axis, DVec2::ZERO)
+	}
+
+	/// The face color of an extruded box on this plane, shaded from the color of its top face.
+	pub fn shade(self, color: Color) -> Color {
+		let factor = match self {
+			IsometricPlane::Top => return color,
+			IsometricPlane::Left => LEFT_FACE_SHADE,
+			IsometricPlane::Right => RIGHT_FACE_SHADE,
+		};
+		Color::from_rgbaf32_unchecked(color.r() * factor, color.g() * factor, color.b() * factor, color.a())
+	}
+}
+
+/// The corners of the three visible faces of an isometric box, with a footprint of `width` by `depth` and a height of `height`.
+/// The box sits below `origin`, which is the back corner of its top face.
+pub fn isometric_box(origin: DVec2, width: f64, depth: f64, height: f64) -> [(IsometricPlane, [DVec2; 4]); 3] {
+	let (x_axis, y_axis) = IsometricPlane::Top.axes();
+	let down = DVec2::Y * height;
+
+	let back = origin;
+	let right = back + x_axis * width;
+	let front = right + y_axis * depth;
+	let left = back + y_axis * depth;
+
+	[
+		(IsometricPlane::Top, [back, right, front, left]),
+		(IsometricPlane::Left, [left, front, front + down, left + down]),
+		(IsometricPlane::Right, [front, right, right + down, front + down]),
+	]
+}
+
+/// An isometric box with the width and depth of a flat shape's bounds, as tall as the shorter of the two, centered horizontally on the shape
+/// with its top level with the top of the shape. There is no box for bounds without an area.
+pub fn isometric_box_over([min, max]: [DVec2; 2]) -> Option<[(IsometricPlane, [DVec2; 4]); 3]> {
+	let (width, depth) = (max.x - min.x, max.y - min.y);
+	if width <= f64::EPSILON || depth <= f64::EPSILON {
+		return None;
+	}
+
+	// The top face spans from `origin.x - depth * cos` to `origin.x + width * cos`, so this centers it on the shape
+	let origin = DVec2::new((min.x + max.x) / 2. - (width - depth) * ISOMETRIC_ANGLE.cos() / 2., min.y);
+	Some(isometric_box(origin, width, depth, width.min(depth)))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn projection_keeps_lengths_along_the_axes() {
+		for plane in [IsometricPlane::Top, IsometricPlane::Left, IsometricPlane::Right] {
+			let projection = plane.projection();
+			assert!((projection.transform_vector2(DVec2::X * 10.).length() - 10.).abs() < 1e-9);
+			assert!((projection.transform_vector2(DVec2::Y * 10.).length() - 10.).abs() < 1e-9);
+		}
+	}
+
+	#[test]
+	fn box_faces_meet_at_the_front_corner() {
+		let [(_, top), (_, left), (_, right)] = isometric_box(DVec2::ZERO, 40., 20., 30.);
+		let front = top[2];
+		assert!(left.contains(&front) && right.contains(&front));
+		// The side faces share the vertical front edge
+		assert_eq!(left[2], right[3]);
+		assert!((left[2] - front - DVec2::new(0., 30.)).length() < 1e-9);
+	}
+
+	#[test]
+	fn box_over_bounds_is_centered() {
+		let [(_, top), ..] = isometric_box_over([DVec2::new(10., 0.), DVec2::new(70., 20.)]).unwrap();
+		let (left_x, right_x) = (top[3].x, top[1].x);
+		assert!(((left_x + right_x) / 2. - 40.).abs() < 1e-9);
+		assert_eq!(top[0].y, 0.);
+		assert!(isometric_box_over([DVec2::ZERO, DVec2::new(10., 0.)]).is_none());
+	}
+}
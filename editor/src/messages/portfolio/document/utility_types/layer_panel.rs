@@ -1,5 +1,6 @@
 use document_legacy::layers::layer_info::{Layer, LayerData, LayerDataTypeDiscriminant};
 use document_legacy::layers::style::RenderData;
+use document_legacy::layers::svg_defs::{content_addressed_ids, deduplicate_defs};
 use document_legacy::LayerId;
 
 use glam::{DAffine2, DVec2};
@@ -36,11 +37,18 @@ impl Serialize for JsRawBuffer {
 pub struct LayerMetadata {
 	pub selected: bool,
 	pub expanded: bool,
+	/// Whether the Select tool's scale handles should always preserve this layer's aspect ratio, as if Shift were held.
+	#[serde(default, rename = "aspectLocked")]
+	pub aspect_locked: bool,
 }
 
 impl LayerMetadata {
 	pub fn new(expanded: bool) -> Self {
-		Self { selected: false, expanded }
+		Self {
+			selected: false,
+			expanded,
+			aspect_locked: false,
+		}
 	}
 }
 
@@ -59,6 +67,11 @@ pub struct LayerPanelEntry {
 
 impl LayerPanelEntry {
 	pub fn new(layer_metadata: &LayerMetadata, transform: DAffine2, layer: &Layer, path: Vec<LayerId>, render_data: &RenderData) -> Self {
+		Self::with_thumbnail(layer_metadata, layer, path, Self::thumbnail(transform, layer, render_data))
+	}
+
+	/// Builds the entry around an already rendered thumbnail, which is the expensive part.
+	pub fn with_thumbnail(layer_metadata: &LayerMetadata, layer: &Layer, path: Vec<LayerId>, thumbnail: String) -> Self {
 		let name = layer.name.clone().unwrap_or_else(|| String::from(""));
 
 		let mut tooltip = name.clone();
@@ -68,13 +81,29 @@ impl LayerPanelEntry {
 			tooltip = tooltip.trim().to_string();
 		}
 
+		LayerPanelEntry {
+			name,
+			tooltip,
+			visible: layer.visible,
+			layer_type: (&layer.data).into(),
+			layer_metadata: *layer_metadata,
+			path,
+			thumbnail,
+		}
+	}
+
+	/// Renders the layer as a standalone SVG, framed by its bounding box.
+	pub fn thumbnail(transform: DAffine2, layer: &Layer, render_data: &RenderData) -> String {
 		let arr = layer.data.bounding_box(transform, render_data).unwrap_or([DVec2::ZERO, DVec2::ZERO]);
 		let arr = arr.iter().map(|x| (*x).into()).collect::<Vec<(f64, f64)>>();
 		let mut thumbnail = String::new();
 		let mut svg_defs = String::new();
 		layer.data.clone().render(&mut thumbnail, &mut svg_defs, &mut vec![transform], render_data);
+		// Thumbnails are shown on the same page as the canvas, so their definitions are given the same collision-free IDs
+		content_addressed_ids(&mut svg_defs, &mut thumbnail);
+		let svg_defs = deduplicate_defs(&svg_defs);
 		let transform = transform.to_cols_array().iter().map(ToString::to_string).collect::<Vec<_>>().join(",");
-		let thumbnail = if let [(x_min, y_min), (x_max, y_max)] = arr.as_slice() {
+		if let [(x_min, y_min), (x_max, y_max)] = arr.as_slice() {
 			format!(
 				r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}"><defs>{}</defs><g transform="matrix({})">{}</g></svg>"#,
 				x_min,
@@ -87,16 +116,6 @@ impl LayerPanelEntry {
 			)
 		} else {
 			String::new()
-		};
-
-		LayerPanelEntry {
-			name,
-			tooltip,
-			visible: layer.visible,
-			layer_type: (&layer.data).into(),
-			layer_metadata: *layer_metadata,
-			path,
-			thumbnail,
 		}
 	}
 }
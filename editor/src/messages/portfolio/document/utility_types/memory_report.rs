@@ -0,0 +1,109 @@
+//! Rough estimates of the memory held by an open document, so machines that are low on memory can reclaim it without closing the document.
+
+use crate::messages::globals::global_variables::number_locale;
+
+use document_legacy::document::Document as DocumentLegacy;
+use document_legacy::layers::layer_info::{Layer, LayerDataType};
+use document_legacy::layers::layer_layer::CachedOutputData;
+use graphene_core::uuid::ManipulatorGroupId;
+
+use bezier_rs::ManipulatorGroup;
+
+/// Estimated bytes of memory held by the parts of a document which can grow large.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryReport {
+	/// The rendered SVG of each layer and the definitions it uses, kept so unchanged layers aren't rendered again.
+	pub layer_caches: usize,
+	/// The rendered content of each layer, which its thumbnail in the Layers panel shows.
+	pub thumbnails: usize,
+	/// The copies of the document kept to undo and redo edits, along with their caches.
+	pub undo_history: usize,
+	/// The anchors and handles of the vector paths in the artwork.
+	pub path_data: usize,
+}
+
+impl MemoryReport {
+	pub fn total(&self) -> usize {
+		self.layer_caches + self.thumbnails + self.undo_history + self.path_data
+	}
+
+	/// Adds the caches and paths of the layer and all of its children.
+	pub fn add_layers(&mut self, root: &Layer) {
+		for layer in root.iter() {
+			self.layer_caches += layer.cache.capacity() + layer.svg_defs_cache.capacity();
+			self.thumbnails += layer.thumbnail_cache.capacity();
+			self.path_data += path_data_size(layer);
+		}
+	}
+
+	/// Adds a copy of the document kept in the undo or redo history, estimated from the size of its saved form and the caches it carries.
+	pub fn add_history_entry(&mut self, document: &DocumentLegacy) {
+		let mut entry = Self::default();
+		entry.add_layers(&document.root);
+		let saved = serde_json::to_string(document).map_or(0, |serialized| serialized.len());
+
+		self.undo_history += saved + entry.total();
+	}
+}
+
+fn path_data_size(layer: &Layer) -> usize {
+	let manipulator_groups = match &layer.data {
+		LayerDataType::Shape(shape) => shape.shape.len(),
+		LayerDataType::Layer(layer) => match &layer.cached_output_data {
+			CachedOutputData::VectorPath(vector_data) => vector_data.subpaths.iter().map(|subpath| subpath.len()).sum(),
+			_ => 0,
+		},
+		LayerDataType::Folder(_) => 0,
+	};
+	manipulator_groups * std::mem::size_of::<ManipulatorGroup<ManipulatorGroupId>>()
+}
+
+/// Describes a number of bytes in the largest unit that keeps the number at least 1, like "1.5 MB".
+pub fn format_bytes(bytes: usize) -> String {
+	const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+	if bytes < 1024 {
+		return format!("{bytes} B");
+	}
+	let mut size = bytes as f64 / 1024.;
+	let mut unit = 0;
+	while size >= 1024. && unit + 1 < UNITS.len() {
+		size /= 1024.;
+		unit += 1;
+	}
+	number_locale().format_with_unit(size, 1, UNITS[unit])
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use document_legacy::layers::folder_layer::FolderLayer;
+
+	#[test]
+	fn bytes_are_described_in_the_largest_unit() {
+		assert_eq!(format_bytes(0), "0 B");
+		assert_eq!(format_bytes(1023), "1023 B");
+		assert_eq!(format_bytes(1536), "1.5 KB");
+		assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MB");
+		assert_eq!(format_bytes(2048 * 1024 * 1024 * 1024 * 1024), "2048.0 TB");
+	}
+
+	#[test]
+	fn caches_of_each_layer_are_counted() {
+		let mut layer = Layer::new(LayerDataType::Folder(FolderLayer::default()), glam::DAffine2::IDENTITY.to_cols_array());
+		layer.cache = "a".repeat(100);
+		layer.svg_defs_cache = "b".repeat(20);
+		layer.thumbnail_cache = "c".repeat(50);
+
+		let mut report = MemoryReport::default();
+		report.add_layers(&layer);
+		assert_eq!(report.layer_caches, 120);
+		assert_eq!(report.thumbnails, 50);
+		assert_eq!(report.total(), 170);
+
+		layer.clear_caches();
+		let mut report = MemoryReport::default();
+		report.add_layers(&layer);
+		assert_eq!(report, MemoryReport::default());
+	}
+}
@@ -1,4 +1,5 @@
 pub use super::layer_panel::{LayerMetadata, LayerPanelEntry};
+use crate::consts::SNAP_GRID_DEFAULT_SPACING;
 use crate::messages::prelude::ArtboardMessageHandler;
 
 use document_legacy::document::Document as DocumentLegacy;
@@ -14,6 +15,8 @@ pub struct DocumentSave {
 	pub document: DocumentLegacy,
 	pub artboard: ArtboardMessageHandler,
 	pub layer_metadata: HashMap<Vec<LayerId>, LayerMetadata>,
+	/// Identifies the edit that brought the document to this state, see [`DocumentMessageHandler::current_identifier`](crate::messages::portfolio::document::DocumentMessageHandler::current_identifier)
+	pub edit_identifier: u64,
 }
 
 #[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize, Hash)]
@@ -69,10 +72,24 @@ impl DocumentMode {
 	}
 }
 
+/// Where the tools insert the layers they draw.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize, specta::Type)]
+pub enum NewLayerPlacement {
+	/// At the top of the document, outside of any folder.
+	TopOfDocument,
+	/// Directly above the topmost selected layer, in the folder containing it.
+	AboveSelection,
+	/// At the top of the selected folder, or of the folder containing the selected layers.
+	#[default]
+	IntoSelectedFolder,
+}
+
 pub enum DocumentRenderMode<'a> {
 	Root,
 	OnlyBelowLayerInFolder(&'a [LayerId]),
 	LayerCutout(&'a [LayerId], Color),
+	/// Only the given layers, in the order listed, each placed by the transforms of the folders containing it.
+	Layers(&'a [Vec<LayerId>]),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -81,6 +98,9 @@ pub struct SnappingState {
 	pub snapping_enabled: bool,
 	pub bounding_box_snapping: bool,
 	pub node_snapping: bool,
+	pub grid_snapping: bool,
+	/// Distance in document space between the lines of the snapping grid, which starts at the document origin
+	pub grid_spacing: f64,
 }
 
 impl Default for SnappingState {
@@ -89,6 +109,8 @@ impl Default for SnappingState {
 			snapping_enabled: true,
 			bounding_box_snapping: true,
 			node_snapping: true,
+			grid_snapping: false,
+			grid_spacing: SNAP_GRID_DEFAULT_SPACING,
 		}
 	}
 }
@@ -97,6 +119,7 @@ impl Default for SnappingState {
 pub enum SnappingOptions {
 	BoundingBoxes,
 	Points,
+	Grid,
 }
 
 impl fmt::Display for SnappingOptions {
@@ -104,6 +127,7 @@ impl fmt::Display for SnappingOptions {
 		match self {
 			SnappingOptions::BoundingBoxes => write!(f, "Bounding Boxes"),
 			SnappingOptions::Points => write!(f, "Points"),
+			SnappingOptions::Grid => write!(f, "Grid"),
 		}
 	}
 }
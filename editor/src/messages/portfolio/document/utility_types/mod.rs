@@ -1,6 +1,39 @@
+pub mod boolean_operations;
 pub mod clipboards;
+pub mod data_merge;
+pub mod design_import;
+pub mod design_tokens;
+pub mod dxf;
 pub mod error;
+pub mod export_history;
+pub mod file_thumbnail;
+pub mod find_replace;
+pub mod font_subset;
+pub mod guide_grid;
+pub mod hatch;
+pub mod idle_work;
+pub mod isometric;
 pub mod layer_panel;
+pub mod memory_report;
 pub mod misc;
+pub mod occlusion;
+pub mod operation_cost;
+pub mod operation_log;
+pub mod outline;
+pub mod path_joining;
+pub mod placeholder;
+pub mod plotter;
+pub mod print_marks;
+pub mod progressive_loading;
+pub mod prototype_links;
+pub mod recovery;
+pub mod scene_graph;
+pub mod soft_proof;
+pub mod sprite_sheet;
+pub mod svg_profile;
+pub mod symmetry;
+pub mod tidy;
+pub mod transform_links;
 pub mod transformation;
+pub mod vector_export;
 pub mod vectorize_layer_metadata;
@@ -0,0 +1,213 @@
+use super::vector_export::{flatten_subpath, ExportedShape};
+
+use glam::DVec2;
+
+/// The flattened fill region of an opaque shape, which hides any lines of the shapes stacked beneath it.
+pub struct Occluder {
+	polygons: Vec<Vec<DVec2>>,
+	bounds: [DVec2; 2],
+}
+
+impl Occluder {
+	/// Builds the occluder for a shape, or `None` if its fill doesn't completely hide what's beneath it.
+	pub fn new(shape: &ExportedShape, tolerance: f64) -> Option<Self> {
+		if !shape.opaque {
+			return None;
+		}
+
+		// Fills are always closed, even when the subpath itself is open
+		let polygons: Vec<_> = shape.subpaths.iter().map(|subpath| flatten_subpath(subpath, tolerance).0).filter(|points| points.len() >= 3).collect();
+		let bounds = polygons
+			.iter()
+			.flatten()
+			.fold([DVec2::splat(f64::INFINITY), DVec2::splat(f64::NEG_INFINITY)], |[min, max], &point| [min.min(point), max.max(point)]);
+
+		(!polygons.is_empty()).then_some(Self { polygons, bounds })
+	}
+
+	fn overlaps(&self, a: DVec2, b: DVec2) -> bool {
+		a.min(b).cmple(self.bounds[1]).all() && a.max(b).cmpge(self.bounds[0]).all()
+	}
+
+	fn edges(&self) -> impl Iterator<Item = (DVec2, DVec2)> + '_ {
+		self.polygons.iter().flat_map(|polygon| polygon.iter().copied().zip(polygon.iter().copied().cycle().skip(1)))
+	}
+
+	/// Tests whether the point is inside the fill, using the nonzero fill rule.
+	fn contains(&self, point: DVec2) -> bool {
+		if point.cmplt(self.bounds[0]).any() || point.cmpgt(self.bounds[1]).any() {
+			return false;
+		}
+
+		let mut winding = 0;
+		for (a, b) in self.edges() {
+			let side = (b - a).perp_dot(point - a);
+			if a.y <= point.y && b.y > point.y && side > 0. {
+				winding += 1;
+			} else if a.y > point.y && b.y <= point.y && side < 0. {
+				winding -= 1;
+			}
+		}
+		winding != 0
+	}
+}
+
+/// Splits a polyline into the runs that aren't covered by any of the occluders.
+/// A closed polyline that is entirely visible is returned unchanged; otherwise every run is open.
+pub fn visible_runs(points: &[DVec2], closed: bool, occluders: &[&Occluder]) -> Vec<(Vec<DVec2>, bool)> {
+	if points.len() < 2 {
+		return Vec::new();
+	}
+	if occluders.is_empty() {
+		return vec![(points.to_vec(), closed)];
+	}
+
+	let segment_count = if closed { points.len() } else { points.len() - 1 };
+	let mut runs: Vec<Vec<DVec2>> = Vec::new();
+	let mut current: Vec<DVec2> = Vec::new();
+	let mut starts_visible = false;
+	let mut fully_visible = true;
+
+	for index in 0..segment_count {
+		let (start, end) = (points[index], points[(index + 1) % points.len()]);
+		let nearby: Vec<_> = occluders.iter().filter(|occluder| occluder.overlaps(start, end)).collect();
+
+		// Split the segment wherever it crosses an occluder's outline, so each piece is either entirely hidden or entirely visible
+		let mut splits = vec![0., 1.];
+		for occluder in &nearby {
+			splits.extend(occluder.edges().filter_map(|(a, b)| segment_intersection(start, end, a, b)));
+		}
+		splits.sort_by(|a, b| a.total_cmp(b));
+		splits.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+
+		for piece in splits.windows(2) {
+			let middle = start.lerp(end, (piece[0] + piece[1]) / 2.);
+			let visible = !nearby.iter().any(|occluder| occluder.contains(middle));
+
+			if index == 0 && piece[0] == 0. {
+				starts_visible = visible;
+			}
+
+			if visible {
+				if current.is_empty() {
+					current.push(start.lerp(end, piece[0]));
+				}
+				current.push(start.lerp(end, piece[1]));
+			} else {
+				fully_visible = false;
+				if current.len() >= 2 {
+					runs.push(std::mem::take(&mut current));
+				}
+				current.clear();
+			}
+		}
+	}
+
+	if fully_visible {
+		return vec![(points.to_vec(), closed)];
+	}
+
+	// A closed polyline that is visible where it starts continues the run that reaches its end
+	if closed && starts_visible && !current.is_empty() && !runs.is_empty() {
+		let first = runs.remove(0);
+		current.extend(first.into_iter().skip(1));
+	}
+	if current.len() >= 2 {
+		runs.push(current);
+	}
+
+	runs.into_iter().map(|run| (run, false)).collect()
+}
+
+/// Finds where the segment from `start` to `end` crosses the segment from `a` to `b`, as a parametric position along the former.
+fn segment_intersection(start: DVec2, end: DVec2, a: DVec2, b: DVec2) -> Option<f64> {
+	let direction = end - start;
+	let edge = b - a;
+	let denominator = direction.perp_dot(edge);
+	if denominator.abs() < f64::EPSILON {
+		return None;
+	}
+
+	let t = (a - start).perp_dot(edge) / denominator;
+	let u = (a - start).perp_dot(direction) / denominator;
+	((0.0..=1.).contains(&t) && (0.0..=1.).contains(&u)).then_some(t)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use bezier_rs::Subpath;
+
+	fn occluder(subpaths: Vec<Subpath<graphene_core::uuid::ManipulatorGroupId>>) -> Occluder {
+		let shape = ExportedShape {
+			layer_name: "Occluder".to_string(),
+			subpaths,
+			filled: true,
+			opaque: true,
+			stroked: false,
+		};
+		Occluder::new(&shape, 0.1).unwrap()
+	}
+
+	fn square(min: f64, max: f64) -> Subpath<graphene_core::uuid::ManipulatorGroupId> {
+		Subpath::new_rect(DVec2::splat(min), DVec2::splat(max))
+	}
+
+	fn points(points: &[(f64, f64)]) -> Vec<DVec2> {
+		points.iter().map(|&(x, y)| DVec2::new(x, y)).collect()
+	}
+
+	fn assert_open_runs(runs: &[(Vec<DVec2>, bool)], expected: &[&[(f64, f64)]]) {
+		assert_eq!(runs.len(), expected.len(), "{runs:?}");
+		for ((run, closed), expected) in runs.iter().zip(expected) {
+			assert!(!closed);
+			assert_eq!(run.len(), expected.len(), "{run:?}");
+			assert!(run.iter().zip(points(expected)).all(|(point, expected)| point.abs_diff_eq(expected, 1e-9)), "{run:?}");
+		}
+	}
+
+	#[test]
+	fn line_behind_a_square_is_hidden() {
+		let square = occluder(vec![square(0., 10.)]);
+		assert!(visible_runs(&points(&[(2., 5.), (8., 5.)]), false, &[&square]).is_empty());
+	}
+
+	#[test]
+	fn line_across_a_square_is_cut_where_it_is_hidden() {
+		let square = occluder(vec![square(0., 10.)]);
+		let runs = visible_runs(&points(&[(-5., 5.), (15., 5.)]), false, &[&square]);
+		assert_open_runs(&runs, &[&[(-5., 5.), (0., 5.)], &[(10., 5.), (15., 5.)]]);
+
+		// Lines clear of the square are left whole, including closed ones
+		let outline = points(&[(20., 0.), (30., 0.), (30., 10.)]);
+		assert_eq!(visible_runs(&outline, true, &[&square]), [(outline, true)]);
+	}
+
+	#[test]
+	fn line_across_a_hole_is_visible_within_it() {
+		let line = points(&[(1., 5.), (9., 5.)]);
+
+		// Under the nonzero fill rule, an inner square running the other way around cuts a hole
+		let reversed_inner = Subpath::from_anchors(points(&[(3., 3.), (3., 7.), (7., 7.), (7., 3.)]), true);
+		let with_hole = occluder(vec![square(0., 10.), reversed_inner]);
+		assert_open_runs(&visible_runs(&line, false, &[&with_hole]), &[&[(3., 5.), (7., 5.)]]);
+
+		// While one running the same way around is filled too
+		let without_hole = occluder(vec![square(0., 10.), square(3., 7.)]);
+		assert!(visible_runs(&line, false, &[&without_hole]).is_empty());
+	}
+
+	#[test]
+	fn closed_path_starting_out_of_sight_becomes_one_open_run() {
+		let square = occluder(vec![square(0., 10.)]);
+		let expected: &[&[(f64, f64)]] = &[&[(10., 5.), (20., 5.), (20., 15.), (5., 15.), (5., 10.)]];
+
+		// Starting where it's hidden, the run begins where the path comes out from behind the square
+		let hidden_start = points(&[(5., 5.), (20., 5.), (20., 15.), (5., 15.)]);
+		assert_open_runs(&visible_runs(&hidden_start, true, &[&square]), expected);
+
+		// Starting where it's visible, the run reaching the end of the path carries on into the one leaving its start
+		let visible_start = points(&[(20., 15.), (5., 15.), (5., 5.), (20., 5.)]);
+		assert_open_runs(&visible_runs(&visible_start, true, &[&square]), expected);
+	}
+}
@@ -0,0 +1,83 @@
+//! Rough estimates of how long the operations which can keep the editor busy for a long time will take, made from the size of their
+//! inputs before they run, so the user can be warned and given the chance to cancel rather than finding the editor frozen for minutes.
+
+use glam::DVec2;
+
+/// Operations estimated to take longer than this ask the user to confirm them first.
+pub const COSTLY_OPERATION_WARNING_SECONDS: f64 = 5.;
+
+/// Every segment of the shapes in a boolean operation is intersected with every other, so the time grows with the square of their points.
+const BOOLEAN_OPERATION_SECONDS_PER_POINT_PAIR: f64 = 2e-7;
+
+/// Rendering and encoding an exported image takes time in proportion to its pixels.
+const RASTER_EXPORT_SECONDS_PER_MEGAPIXEL: f64 = 0.4;
+
+/// An operation whose cost depends on the size of its input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CostlyOperation {
+	/// A boolean operation on shapes with this many points in total.
+	BooleanOperation { points: usize },
+	/// An export to an image of this size in pixels.
+	RasterExport { size: DVec2 },
+}
+
+impl CostlyOperation {
+	pub fn estimated_seconds(&self) -> f64 {
+		match *self {
+			CostlyOperation::BooleanOperation { points } => (points as f64).powi(2) * BOOLEAN_OPERATION_SECONDS_PER_POINT_PAIR,
+			CostlyOperation::RasterExport { size } => megapixels(size) * RASTER_EXPORT_SECONDS_PER_MEGAPIXEL,
+		}
+	}
+
+	/// Whether the user should confirm the operation before it runs.
+	pub fn needs_confirmation(&self) -> bool {
+		self.estimated_seconds() > COSTLY_OPERATION_WARNING_SECONDS
+	}
+
+	/// Explains what makes the operation slow and roughly how long it may take.
+	pub fn description(&self) -> String {
+		let reason = match *self {
+			CostlyOperation::BooleanOperation { points } => format!("The selected shapes have {points} points in total, so combining them"),
+			CostlyOperation::RasterExport { size } => format!(
+				"The exported image is {} × {} pixels ({:.0} megapixels), so rendering it",
+				size.x.round(),
+				size.y.round(),
+				megapixels(size)
+			),
+		};
+		format!("{reason} may take {}, during which Graphite won't respond.", describe_seconds(self.estimated_seconds()))
+	}
+}
+
+fn megapixels(size: DVec2) -> f64 {
+	size.x.max(0.) * size.y.max(0.) / 1e6
+}
+
+/// Describes an estimated duration in round terms, since the estimate is only rough.
+fn describe_seconds(seconds: f64) -> String {
+	match seconds {
+		seconds if seconds < 60. => format!("about {} seconds", (seconds / 5.).ceil() * 5.),
+		seconds if seconds < 3600. => format!("about {} minutes", (seconds / 60.).ceil()),
+		_ => "over an hour".to_string(),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn only_large_inputs_need_confirmation() {
+		assert!(!CostlyOperation::BooleanOperation { points: 1000 }.needs_confirmation());
+		assert!(CostlyOperation::BooleanOperation { points: 20_000 }.needs_confirmation());
+		assert!(!CostlyOperation::RasterExport { size: DVec2::new(3840., 2160.) }.needs_confirmation());
+		assert!(CostlyOperation::RasterExport { size: DVec2::new(20_000., 20_000.) }.needs_confirmation());
+	}
+
+	#[test]
+	fn durations_are_rounded() {
+		assert_eq!(describe_seconds(12.), "about 15 seconds");
+		assert_eq!(describe_seconds(150.), "about 3 minutes");
+		assert_eq!(describe_seconds(4000.), "over an hour");
+	}
+}
@@ -0,0 +1,342 @@
+//! The edits made to a document since it was created, saved along with the document so they can be replayed later. Replaying them onto an
+//! empty document with a newer version of the editor redoes them without the bugs that may have been in the version they were first applied
+//! with, so the result can be compared with the document as it was saved to find and repair the damage those bugs left behind.
+//!
+//! [Operation]s and [GraphOperationMessage]s are logged, along with where undo and redo return to. Edits made in the node graph are logged
+//! as the network they leave behind, since the messages making them depend on what's shown in the node graph. The output the node graph
+//! caches in its layers isn't logged, since it's worked out again once the replayed document is rendered.
+//!
+//! The log can't repair every document. Files saved before the log was added, and documents whose log grew too long, aren't covered by it,
+//! and a document is only repaired as far as the edits logged for it still apply.
+
+use crate::consts::MAX_UNDO_HISTORY_LEN;
+use crate::messages::prelude::*;
+
+use document_legacy::document::Document as DocumentLegacy;
+use document_legacy::{LayerId, Operation as DocumentOperation};
+use graph_craft::document::NodeNetwork;
+
+use serde::{Deserialize, Serialize};
+
+/// The most edits logged, after which the log stops rather than growing without bound, since dropping its oldest edits would leave it unable to replay the rest.
+const OPERATION_LOG_MAX_LENGTH: usize = 10_000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum LoggedEdit {
+	Operation(DocumentOperation),
+	GraphOperation(GraphOperationMessage),
+	/// The network of a layer, or of the document if there's no layer, after it was edited in the node graph.
+	SetNetwork {
+		layer: Option<Vec<LayerId>>,
+		network: NodeNetwork,
+	},
+	/// The document was backed up to the undo history, so it can be returned to by a later [LoggedEdit::Undo].
+	Checkpoint,
+	Undo,
+	Redo,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OperationLog {
+	/// Whether the log holds every edit made to the document since it was created, which isn't the case for files saved before the log
+	/// was added, or once the log grew too long
+	#[serde(default)]
+	since_creation: bool,
+	edits: Vec<LoggedEdit>,
+}
+
+impl OperationLog {
+	/// A log of a document which is being created.
+	pub fn new() -> Self {
+		Self {
+			since_creation: true,
+			..Default::default()
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.edits.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.edits.is_empty()
+	}
+
+	/// Whether edits are logged, which is checked before applying one so it's only copied for the log if needed.
+	pub fn is_logging(&self) -> bool {
+		self.since_creation
+	}
+
+	/// Whether the operation is logged, unlike those storing the output of the node graph, which is worked out again after replaying.
+	pub fn logs(operation: &DocumentOperation) -> bool {
+		!matches!(
+			operation,
+			DocumentOperation::SetVectorData { .. } | DocumentOperation::SetSurface { .. } | DocumentOperation::SetLayerBlobUrl { .. }
+		)
+	}
+
+	/// Logs an edit which was applied to the document. The log stops, and forgets its edits, once it has grown too long.
+	pub fn record(&mut self, edit: LoggedEdit) {
+		if !self.since_creation {
+			return;
+		}
+
+		// Panning and zooming the canvas transforms the root, and only the latest transform matters to the edits after it.
+		// Likewise, only the latest network matters when a network is edited in the node graph several times in a row.
+		let replaces_last = match (self.edits.last(), &edit) {
+			(Some(LoggedEdit::SetNetwork { layer: last, .. }), LoggedEdit::SetNetwork { layer, .. }) => last == layer,
+			(Some(last), edit) => changes_view(last) && changes_view(edit),
+			(None, _) => false,
+		};
+		if replaces_last {
+			self.edits.pop();
+		}
+
+		if self.edits.len() >= OPERATION_LOG_MAX_LENGTH {
+			warn!("The operation log of the document stopped since it grew longer than {OPERATION_LOG_MAX_LENGTH} edits");
+			*self = Self::default();
+			return;
+		}
+		self.edits.push(edit);
+	}
+
+	/// Applies the logged edits to an empty document, giving the document as the current version of the editor makes it, along with how many
+	/// of the edits failed. Returns `None` if the log doesn't cover the document.
+	pub fn replay(&self) -> Option<(DocumentLegacy, usize)> {
+		if !self.since_creation {
+			return None;
+		}
+
+		let mut replayed = DocumentLegacy::default();
+		let mut node_graph = NodeGraphMessageHandler::default();
+		let mut undo_history = VecDeque::new();
+		let mut redo_history = VecDeque::new();
+		let mut failed = 0;
+		for edit in &self.edits {
+			match edit {
+				LoggedEdit::Operation(operation) => failed += replayed.handle_operation(operation.clone()).is_err() as usize,
+				// The operations a graph operation responds with are logged themselves when they're applied, so its responses are dropped
+				LoggedEdit::GraphOperation(message) => GraphOperationMessageHandler.process_message(message.clone(), &mut VecDeque::new(), (&mut replayed, &mut node_graph)),
+				LoggedEdit::SetNetwork { layer, network } => match layer {
+					Some(path) => match replayed.layer_mut(path).and_then(|layer| layer.as_layer_network_mut()) {
+						Ok(layer_network) => *layer_network = network.clone(),
+						Err(_) => failed += 1,
+					},
+					None => replayed.document_network = network.clone(),
+				},
+				// The history is kept as long as the editor keeps it, so undo and redo return to the same documents
+				LoggedEdit::Checkpoint => {
+					redo_history.clear();
+					undo_history.push_back(replayed.clone());
+					if undo_history.len() > MAX_UNDO_HISTORY_LEN {
+						undo_history.pop_front();
+					}
+				}
+				LoggedEdit::Undo => match undo_history.pop_back() {
+					Some(previous) => redo_history.push_back(restore(&mut replayed, previous)),
+					None => failed += 1,
+				},
+				LoggedEdit::Redo => match redo_history.pop_back() {
+					Some(next) => undo_history.push_back(restore(&mut replayed, next)),
+					None => failed += 1,
+				},
+			}
+		}
+		Some((replayed, failed))
+	}
+}
+
+fn changes_view(edit: &LoggedEdit) -> bool {
+	matches!(edit, LoggedEdit::Operation(DocumentOperation::SetLayerTransform { path, .. } | DocumentOperation::TransformLayer { path, .. }) if path.is_empty())
+}
+
+/// Replaces the document with one from the history, keeping the view like undo and redo do, and returns the document it replaced.
+fn restore(document: &mut DocumentLegacy, from_history: DocumentLegacy) -> DocumentLegacy {
+	let view = document.root.transform;
+	let replaced = std::mem::replace(document, from_history);
+	document.root.transform = view;
+	replaced
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::messages::portfolio::document::node_graph::new_vector_network;
+	use document_legacy::layers::style::{Fill, PathStyle};
+
+	use bezier_rs::Subpath;
+	use glam::{DAffine2, DVec2};
+	use graphene_core::vector::VectorData;
+	use graphene_core::Color;
+
+	fn add_rect(path: Vec<u64>) -> DocumentOperation {
+		DocumentOperation::AddRect {
+			path,
+			transform: DAffine2::IDENTITY.to_cols_array(),
+			style: PathStyle::default(),
+			insert_index: -1,
+		}
+	}
+
+	fn apply(log: &mut OperationLog, document: &mut DocumentLegacy, operation: DocumentOperation) {
+		let logged = OperationLog::logs(&operation) && log.is_logging();
+		if document.handle_operation(operation.clone()).is_ok() && logged {
+			log.record(LoggedEdit::Operation(operation));
+		}
+	}
+
+	#[test]
+	fn replaying_gives_the_document_back() {
+		let mut document = DocumentLegacy::default();
+		let mut log = OperationLog::new();
+
+		let operations = [
+			add_rect(vec![1]),
+			DocumentOperation::SetLayerTransform {
+				path: vec![],
+				transform: DAffine2::from_scale(DVec2::splat(2.)).to_cols_array(),
+			},
+			DocumentOperation::SetLayerTransform {
+				path: vec![],
+				transform: DAffine2::from_scale(DVec2::splat(3.)).to_cols_array(),
+			},
+			DocumentOperation::RenameLayer {
+				layer_path: vec![1],
+				new_name: "Rectangle".to_string(),
+			},
+			DocumentOperation::DeleteLayer { path: vec![2] },
+		];
+		for operation in operations {
+			apply(&mut log, &mut document, operation);
+		}
+		// The transforms of the view are merged, and the operation that failed isn't logged
+		assert_eq!(log.len(), 3);
+
+		let (replayed, failed) = log.replay().unwrap();
+		assert_eq!(failed, 0);
+		assert!(replayed.diff(&document).is_empty());
+		assert_eq!(replayed.root.transform, document.root.transform);
+	}
+
+	#[test]
+	fn replaying_returns_to_the_documents_undo_and_redo_returned_to() {
+		let mut document = DocumentLegacy::default();
+		let mut log = OperationLog::new();
+		let mut undo_history = Vec::new();
+		let mut redo_history = Vec::new();
+		let checkpoint = |log: &mut OperationLog, document: &DocumentLegacy, undo_history: &mut Vec<DocumentLegacy>| {
+			undo_history.push(document.clone());
+			log.record(LoggedEdit::Checkpoint);
+		};
+
+		checkpoint(&mut log, &document, &mut undo_history);
+		apply(&mut log, &mut document, add_rect(vec![1]));
+		checkpoint(&mut log, &document, &mut undo_history);
+		apply(&mut log, &mut document, add_rect(vec![2]));
+
+		// Undo the second rectangle, then redo it, then undo it again and draw a third one in its place
+		redo_history.push(std::mem::replace(&mut document, undo_history.pop().unwrap()));
+		log.record(LoggedEdit::Undo);
+		assert!(log.replay().unwrap().0.diff(&document).is_empty());
+
+		undo_history.push(std::mem::replace(&mut document, redo_history.pop().unwrap()));
+		log.record(LoggedEdit::Redo);
+		assert!(log.replay().unwrap().0.layer(&[2]).is_ok());
+
+		document = undo_history.pop().unwrap();
+		log.record(LoggedEdit::Undo);
+		checkpoint(&mut log, &document, &mut undo_history);
+		apply(&mut log, &mut document, add_rect(vec![3]));
+
+		let (replayed, failed) = log.replay().unwrap();
+		assert_eq!(failed, 0);
+		assert!(replayed.diff(&document).is_empty());
+		assert!(replayed.layer(&[2]).is_err());
+	}
+
+	#[test]
+	fn replaying_redoes_graph_operations_on_node_graph_layers() {
+		let mut document = DocumentLegacy::default();
+		let mut log = OperationLog::new();
+
+		let original_network = new_vector_network(vec![Subpath::new_rect(DVec2::ZERO, DVec2::ONE)]);
+		let add_frame = DocumentOperation::AddFrame {
+			path: vec![1],
+			insert_index: -1,
+			transform: DAffine2::IDENTITY.to_cols_array(),
+			network: original_network.clone(),
+		};
+		apply(&mut log, &mut document, add_frame);
+
+		let fill_set = GraphOperationMessage::FillSet {
+			layer: vec![1],
+			fill: Fill::Solid(Color::RED),
+		};
+		assert!(log.is_logging());
+		GraphOperationMessageHandler.process_message(fill_set.clone(), &mut VecDeque::new(), (&mut document, &mut NodeGraphMessageHandler::default()));
+		log.record(LoggedEdit::GraphOperation(fill_set));
+
+		// The output of the node graph isn't logged
+		apply(
+			&mut log,
+			&mut document,
+			DocumentOperation::SetVectorData {
+				path: vec![1],
+				vector_data: VectorData::empty(),
+			},
+		);
+		assert_eq!(log.len(), 2);
+
+		let (replayed, failed) = log.replay().unwrap();
+		assert_eq!(failed, 0);
+		let network = |document: &DocumentLegacy| document.layer(&[1]).unwrap().as_layer_network().unwrap().clone();
+		assert_eq!(network(&replayed), network(&document));
+		assert_ne!(network(&replayed), original_network);
+	}
+
+	#[test]
+	fn replaying_sets_the_networks_left_by_the_node_graph() {
+		let mut document = DocumentLegacy::default();
+		let mut log = OperationLog::new();
+
+		let add_frame = DocumentOperation::AddFrame {
+			path: vec![1],
+			insert_index: -1,
+			transform: DAffine2::IDENTITY.to_cols_array(),
+			network: new_vector_network(vec![Subpath::new_rect(DVec2::ZERO, DVec2::ONE)]),
+		};
+		apply(&mut log, &mut document, add_frame);
+
+		// Only the latest of the networks set in a row is kept
+		for size in [2., 3.] {
+			let network = new_vector_network(vec![Subpath::new_rect(DVec2::ZERO, DVec2::splat(size))]);
+			*document.layer_mut(&[1]).unwrap().as_layer_network_mut().unwrap() = network.clone();
+			log.record(LoggedEdit::SetNetwork { layer: Some(vec![1]), network });
+		}
+		assert_eq!(log.len(), 2);
+
+		let (replayed, failed) = log.replay().unwrap();
+		assert_eq!(failed, 0);
+		assert_eq!(replayed.layer(&[1]).unwrap().as_layer_network().unwrap(), document.layer(&[1]).unwrap().as_layer_network().unwrap());
+	}
+
+	#[test]
+	fn logs_not_kept_since_creation_are_not_replayed() {
+		let mut document = DocumentLegacy::default();
+
+		// Such as the log of a file saved before the log was added
+		let mut log = OperationLog::default();
+		apply(&mut log, &mut document, add_rect(vec![1]));
+		assert!(log.is_empty());
+		assert!(log.replay().is_none());
+
+		// The log stops once it grows too long, since the edits it would have to drop are needed to replay the rest
+		let mut log = OperationLog::new();
+		for id in 0..OPERATION_LOG_MAX_LENGTH as u64 + 1 {
+			apply(&mut log, &mut document, add_rect(vec![id + 2]));
+		}
+		assert!(!log.is_logging());
+		assert!(log.is_empty());
+		assert!(log.replay().is_none());
+	}
+}
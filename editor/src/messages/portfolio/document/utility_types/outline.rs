@@ -0,0 +1,155 @@
+use document_legacy::document::Document as DocumentLegacy;
+use document_legacy::layers::layer_info::{Layer, LayerDataType};
+use document_legacy::layers::style::RenderData;
+use document_legacy::LayerId;
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::NodeInput;
+use graphene_core::text::Font;
+
+use serde::{Deserialize, Serialize};
+
+/// One line of the document outline: either a folder, which acts as a heading for the layers inside it, or a text layer and its content.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, specta::Type)]
+pub struct OutlineEntry {
+	pub name: String,
+	#[serde(rename = "layerPath")]
+	pub layer_path: Vec<LayerId>,
+	/// How many folders contain this entry.
+	pub depth: usize,
+	#[serde(rename = "isFolder")]
+	pub is_folder: bool,
+	/// The content of a text layer.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub text: Option<String>,
+	/// The top left corner of the layer's bounding box in document space.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub position: Option<(f64, f64)>,
+	/// The start and end character indices of each occurrence of the search query in the text.
+	#[serde(rename = "matchRanges")]
+	pub match_ranges: Vec<(usize, usize)>,
+}
+
+/// The text typed into a text layer, if the layer is generated by a "Text" node.
+pub fn layer_text(layer: &Layer) -> Option<&str> {
+	let network = layer.as_layer_network().ok()?;
+	let node = network.nodes.values().find(|node| node.name == "Text")?;
+	match node.inputs.get(1)? {
+		NodeInput::Value {
+			tagged_value: TaggedValue::String(text),
+			..
+		} => Some(text),
+		_ => None,
+	}
+}
+
+/// The font of a text layer, if the layer is generated by a "Text" node.
+pub fn layer_font(layer: &Layer) -> Option<&Font> {
+	let network = layer.as_layer_network().ok()?;
+	let node = network.nodes.values().find(|node| node.name == "Text")?;
+	match node.inputs.get(2)? {
+		NodeInput::Value {
+			tagged_value: TaggedValue::Font(font),
+			..
+		} => Some(font),
+		_ => None,
+	}
+}
+
+/// The font size of a text layer, if the layer is generated by a "Text" node.
+pub fn layer_font_size(layer: &Layer) -> Option<f64> {
+	let network = layer.as_layer_network().ok()?;
+	let node = network.nodes.values().find(|node| node.name == "Text")?;
+	match node.inputs.get(3)? {
+		NodeInput::Value {
+			tagged_value: TaggedValue::F32(font_size),
+			..
+		} => Some(*font_size as f64),
+		_ => None,
+	}
+}
+
+/// Every font used by the given text layers along with how many of the layers use it, sorted by family and then style.
+pub fn used_fonts<'a>(layers: impl Iterator<Item = &'a Layer>) -> Vec<(Font, usize)> {
+	let mut fonts: Vec<(Font, usize)> = Vec::new();
+	for font in layers.filter_map(layer_font) {
+		match fonts.iter_mut().find(|(used, _)| used == font) {
+			Some((_, count)) => *count += 1,
+			None => fonts.push((font.clone(), 1)),
+		}
+	}
+	fonts.sort_by(|(a, _), (b, _)| (&a.font_family, &a.font_style).cmp(&(&b.font_family, &b.font_style)));
+	fonts
+}
+
+/// Lists the folders and text layers from the top of the layer stack to the bottom, in the order they appear in the Layers panel.
+/// With a non-empty `query`, only the text layers containing it (ignoring case) are listed, along with the folders leading to them.
+pub fn document_outline(document: &DocumentLegacy, query: &str, render_data: &RenderData) -> Vec<OutlineEntry> {
+	let mut entries = Vec::new();
+	let query = query.to_lowercase();
+	if let LayerDataType::Folder(folder) = &document.root.data {
+		for (&id, layer) in folder.layer_ids.iter().zip(folder.layers()).rev() {
+			outline_layer(document, layer, &mut vec![id], &query, render_data, &mut entries);
+		}
+	}
+	entries
+}
+
+fn outline_layer(document: &DocumentLegacy, layer: &Layer, path: &mut Vec<LayerId>, query: &str, render_data: &RenderData, entries: &mut Vec<OutlineEntry>) {
+	let position = document
+		.viewport_bounding_box(path, render_data)
+		.ok()
+		.flatten()
+		.map(|[min, _]| document.root.transform.inverse().transform_point2(min).into());
+	let depth = path.len() - 1;
+
+	match &layer.data {
+		LayerDataType::Folder(folder) => {
+			let heading_index = entries.len();
+			entries.push(OutlineEntry {
+				name: layer.name.clone().unwrap_or_else(|| "Folder".to_string()),
+				layer_path: path.clone(),
+				depth,
+				is_folder: true,
+				text: None,
+				position,
+				match_ranges: Vec::new(),
+			});
+
+			for (&id, child) in folder.layer_ids.iter().zip(folder.layers()).rev() {
+				path.push(id);
+				outline_layer(document, child, path, query, render_data, entries);
+				path.pop();
+			}
+
+			// Folders without any matching text are left out of search results
+			if !query.is_empty() && entries.len() == heading_index + 1 {
+				entries.pop();
+			}
+		}
+		_ => {
+			let Some(text) = layer_text(layer) else { return };
+
+			let match_ranges = match query.is_empty() {
+				true => Vec::new(),
+				false => {
+					let lowercase = text.to_lowercase();
+					let char_index = |byte_index: usize| lowercase[..byte_index].chars().count();
+					lowercase.match_indices(query).map(|(start, found)| (char_index(start), char_index(start + found.len()))).collect()
+				}
+			};
+			if !query.is_empty() && match_ranges.is_empty() {
+				return;
+			}
+
+			entries.push(OutlineEntry {
+				name: layer.name.clone().unwrap_or_else(|| text.lines().next().unwrap_or_default().to_string()),
+				layer_path: path.clone(),
+				depth,
+				is_folder: false,
+				text: Some(text.to_string()),
+				position,
+				match_ranges,
+			});
+		}
+	}
+}
@@ -0,0 +1,186 @@
+//! Connecting open paths, either by closing a path back onto its own start or by joining the ends of two paths into a single path,
+//! and the reverse of both, breaking paths apart at some of their points.
+
+use graphene_core::uuid::ManipulatorGroupId;
+
+use bezier_rs::{ManipulatorGroup, Subpath};
+use glam::DVec2;
+
+/// How close two endpoints must be, in viewport pixels, to be merged into a single point instead of being connected by a new straight segment.
+pub const PATH_JOIN_TOLERANCE: f64 = 10.;
+
+/// Merges the end point into the start point, keeping the handle of the end point on its side of the merged point.
+fn merge_ends(start: &mut ManipulatorGroup<ManipulatorGroupId>, end: &ManipulatorGroup<ManipulatorGroupId>, keep_in_handle: bool) {
+	let offset = start.anchor - end.anchor;
+	match keep_in_handle {
+		true => start.in_handle = end.in_handle.map(|handle| handle + offset),
+		false => start.out_handle = end.out_handle.map(|handle| handle + offset),
+	}
+}
+
+/// Closes an open path. If its two ends are within the tolerance of each other, they are merged into a single point at the start of the path.
+pub fn close_subpath(subpath: &Subpath<ManipulatorGroupId>, tolerance: f64) -> Subpath<ManipulatorGroupId> {
+	let mut groups = subpath.manipulator_groups().to_vec();
+	if groups.len() > 2 && groups[0].anchor.distance(groups[groups.len() - 1].anchor) <= tolerance {
+		if let Some(last) = groups.pop() {
+			merge_ends(&mut groups[0], &last, true);
+		}
+	}
+
+	Subpath::new(groups, true)
+}
+
+/// Whether each path must be reversed so that the first path ends at the end which is nearest to the start of the second path, and the distance between those ends.
+pub fn nearest_ends(first: &Subpath<ManipulatorGroupId>, second: &Subpath<ManipulatorGroupId>) -> Option<(bool, bool, f64)> {
+	let ends = |subpath: &Subpath<ManipulatorGroupId>| -> Option<[DVec2; 2]> { Some([subpath.manipulator_groups().first()?.anchor, subpath.manipulator_groups().last()?.anchor]) };
+	let ([first_start, first_end], [second_start, second_end]) = (ends(first)?, ends(second)?);
+
+	[
+		(false, false, first_end.distance(second_start)),
+		(false, true, first_end.distance(second_end)),
+		(true, false, first_start.distance(second_start)),
+		(true, true, first_start.distance(second_end)),
+	]
+	.into_iter()
+	.min_by(|(_, _, a), (_, _, b)| a.total_cmp(b))
+}
+
+/// Joins the nearest ends of two open paths into one open path. If those ends are within the tolerance of each other, they are merged into
+/// a single point where the first path ends. Otherwise they are connected by a new straight segment.
+pub fn join_subpaths(first: &Subpath<ManipulatorGroupId>, second: &Subpath<ManipulatorGroupId>, tolerance: f64) -> Option<Subpath<ManipulatorGroupId>> {
+	let (reverse_first, reverse_second, distance) = nearest_ends(first, second)?;
+	let oriented = |subpath: &Subpath<ManipulatorGroupId>, reverse: bool| match reverse {
+		true => subpath.reverse().manipulator_groups().to_vec(),
+		false => subpath.manipulator_groups().to_vec(),
+	};
+
+	let mut groups = oriented(first, reverse_first);
+	let mut appended = oriented(second, reverse_second);
+	if distance <= tolerance {
+		let joined = appended.remove(0);
+		if let Some(last) = groups.last_mut() {
+			merge_ends(last, &joined, false);
+		}
+	}
+	groups.extend(appended);
+
+	Some(Subpath::new(groups, false))
+}
+
+/// Breaks the path at each of the given points. A closed path is opened at the first of them and every other one splits off a new path,
+/// with the point where the path is broken appearing at the end of one piece and the start of the next. The ends of an open path can't break it.
+pub fn break_subpath(subpath: &Subpath<ManipulatorGroupId>, breaks: &[ManipulatorGroupId]) -> Vec<Subpath<ManipulatorGroupId>> {
+	let mut groups = subpath.manipulator_groups().to_vec();
+	if subpath.closed() {
+		let Some(first_break) = groups.iter().position(|group| breaks.contains(&group.id)) else {
+			return vec![subpath.clone()];
+		};
+		groups.rotate_left(first_break);
+
+		let start = groups[0];
+		groups[0].in_handle = None;
+		groups.push(ManipulatorGroup::new(start.anchor, start.in_handle, None));
+	}
+
+	let mut pieces = Vec::new();
+	let mut piece = Vec::new();
+	for (index, &group) in groups.iter().enumerate() {
+		piece.push(group);
+		if index > 0 && index + 1 < groups.len() && breaks.contains(&group.id) {
+			if let Some(end) = piece.last_mut() {
+				end.out_handle = None;
+			}
+			pieces.push(Subpath::new(std::mem::take(&mut piece), false));
+			piece.push(ManipulatorGroup::new(group.anchor, None, group.out_handle));
+		}
+	}
+	pieces.push(Subpath::new(piece, false));
+
+	pieces
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn anchors(subpath: &Subpath<ManipulatorGroupId>) -> Vec<DVec2> {
+		subpath.manipulator_groups().iter().map(|group| group.anchor).collect()
+	}
+
+	#[test]
+	fn close_merges_nearby_ends() {
+		let open = Subpath::new(
+			vec![
+				ManipulatorGroup::new(DVec2::new(0., 0.), None, Some(DVec2::new(1., 1.))),
+				ManipulatorGroup::new(DVec2::new(10., 0.), None, None),
+				ManipulatorGroup::new(DVec2::new(10., 10.), None, None),
+				ManipulatorGroup::new(DVec2::new(0.5, 0.), Some(DVec2::new(0.5, 2.)), None),
+			],
+			false,
+		);
+
+		let closed = close_subpath(&open, 1.);
+		assert!(closed.closed());
+		assert_eq!(anchors(&closed), vec![DVec2::new(0., 0.), DVec2::new(10., 0.), DVec2::new(10., 10.)]);
+		assert_eq!(closed.manipulator_groups()[0].in_handle, Some(DVec2::new(0., 2.)));
+		assert_eq!(closed.manipulator_groups()[0].out_handle, Some(DVec2::new(1., 1.)));
+
+		let connected = close_subpath(&open, 0.1);
+		assert!(connected.closed());
+		assert_eq!(connected.len(), 4, "ends farther apart than the tolerance are connected by a new segment");
+	}
+
+	#[test]
+	fn join_connects_the_nearest_ends() {
+		let first = Subpath::from_anchors([DVec2::new(0., 0.), DVec2::new(10., 0.)], false);
+		let second = Subpath::from_anchors([DVec2::new(30., 0.), DVec2::new(20., 0.)], false);
+
+		let joined = join_subpaths(&first, &second, 1.).unwrap();
+		assert!(!joined.closed());
+		assert_eq!(anchors(&joined), vec![DVec2::new(0., 0.), DVec2::new(10., 0.), DVec2::new(20., 0.), DVec2::new(30., 0.)]);
+
+		let reversed = join_subpaths(&second, &first, 1.).unwrap();
+		assert_eq!(anchors(&reversed), vec![DVec2::new(30., 0.), DVec2::new(20., 0.), DVec2::new(10., 0.), DVec2::new(0., 0.)]);
+	}
+
+	#[test]
+	fn join_merges_nearby_ends() {
+		let first = Subpath::from_anchors([DVec2::new(0., 0.), DVec2::new(10., 0.)], false);
+		let second = Subpath::new(
+			vec![
+				ManipulatorGroup::new(DVec2::new(10.5, 0.), None, Some(DVec2::new(12.5, 5.))),
+				ManipulatorGroup::new(DVec2::new(20., 10.), None, None),
+			],
+			false,
+		);
+
+		let joined = join_subpaths(&first, &second, 1.).unwrap();
+		assert_eq!(anchors(&joined), vec![DVec2::new(0., 0.), DVec2::new(10., 0.), DVec2::new(20., 10.)]);
+		assert_eq!(joined.manipulator_groups()[1].out_handle, Some(DVec2::new(12., 5.)));
+		assert_eq!(join_subpaths(&first, &Subpath::new(Vec::new(), false), 1.), None);
+	}
+
+	#[test]
+	fn break_opens_closed_paths_and_splits_open_ones() {
+		let square = Subpath::from_anchors([DVec2::new(0., 0.), DVec2::new(10., 0.), DVec2::new(10., 10.), DVec2::new(0., 10.)], true);
+		let ids = square.manipulator_groups().iter().map(|group| group.id).collect::<Vec<_>>();
+
+		let opened = break_subpath(&square, &ids[1..2]);
+		assert_eq!(opened.len(), 1);
+		assert!(!opened[0].closed());
+		assert_eq!(
+			anchors(&opened[0]),
+			vec![DVec2::new(10., 0.), DVec2::new(10., 10.), DVec2::new(0., 10.), DVec2::new(0., 0.), DVec2::new(10., 0.)]
+		);
+
+		let split = break_subpath(&square, &[ids[1], ids[3]]);
+		assert_eq!(split.len(), 2);
+		assert_eq!(anchors(&split[0]), vec![DVec2::new(10., 0.), DVec2::new(10., 10.), DVec2::new(0., 10.)]);
+		assert_eq!(anchors(&split[1]), vec![DVec2::new(0., 10.), DVec2::new(0., 0.), DVec2::new(10., 0.)]);
+
+		let line = Subpath::from_anchors([DVec2::new(0., 0.), DVec2::new(10., 0.), DVec2::new(20., 0.)], false);
+		let line_ids = line.manipulator_groups().iter().map(|group| group.id).collect::<Vec<_>>();
+		assert_eq!(break_subpath(&line, &line_ids[1..]).len(), 2);
+		assert_eq!(break_subpath(&line, &[line_ids[0], line_ids[2]]), vec![line.clone()], "the ends of an open path don't break it");
+	}
+}
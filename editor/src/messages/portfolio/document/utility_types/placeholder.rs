@@ -0,0 +1,71 @@
+//! Placeholder content for quickly laying out wireframes before the real copy and images are ready.
+
+const LOREM_IPSUM: &str = "lorem ipsum dolor sit amet consectetur adipiscing elit sed do eiusmod tempor incididunt ut labore et dolore magna aliqua \
+	ut enim ad minim veniam quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat duis aute irure dolor in \
+	reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur excepteur sint occaecat cupidatat non proident sunt in \
+	culpa qui officia deserunt mollit anim id est laborum";
+
+/// The number of words in each sentence, repeated in turn so the sentences don't all have the same rhythm.
+const SENTENCE_LENGTHS: [usize; 5] = [8, 12, 6, 10, 14];
+
+/// The lengths of placeholder text offered by the commands that insert it, as a label and a number of words.
+pub const PLACEHOLDER_TEXT_LENGTHS: [(&str, usize); 4] = [("Heading", 4), ("Sentence", 12), ("Paragraph", 50), ("Long Text", 150)];
+
+/// Generates "lorem ipsum" text with the given number of words, split into capitalized sentences ending in periods.
+/// Lines are broken between words before they grow longer than `line_length` characters, since text layers don't wrap on their own.
+pub fn placeholder_text(words: usize, line_length: usize) -> String {
+	let mut text = String::new();
+	let mut line_start = 0;
+	let mut sentence_lengths = SENTENCE_LENGTHS.iter().cycle();
+	let mut sentence_remaining = 0;
+
+	for (index, word) in LOREM_IPSUM.split_whitespace().cycle().take(words).enumerate() {
+		let sentence_start = sentence_remaining == 0;
+		if sentence_start {
+			sentence_remaining = *sentence_lengths.next().unwrap();
+		}
+		sentence_remaining -= 1;
+		let sentence_end = sentence_remaining == 0 || index + 1 == words;
+
+		let mut word = match sentence_start {
+			true => word[..1].to_uppercase() + &word[1..],
+			false => word.to_string(),
+		};
+		if sentence_end {
+			word.push('.');
+		}
+
+		if index > 0 {
+			if text.len() - line_start + 1 + word.len() > line_length {
+				text.push('\n');
+				line_start = text.len();
+			} else {
+				text.push(' ');
+			}
+		}
+		text.push_str(&word);
+	}
+
+	text
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn sentences_are_capitalized_and_end_in_periods() {
+		assert_eq!(placeholder_text(4, 100), "Lorem ipsum dolor sit.");
+		assert_eq!(placeholder_text(10, 100), "Lorem ipsum dolor sit amet consectetur adipiscing elit. Sed do.");
+		assert_eq!(placeholder_text(0, 100), "");
+	}
+
+	#[test]
+	fn long_text_is_broken_into_lines() {
+		let text = placeholder_text(150, 40);
+
+		assert_eq!(text.split_whitespace().count(), 150);
+		assert!(text.lines().count() > 1);
+		assert!(text.lines().all(|line| line.len() <= 40), "{text}");
+	}
+}
@@ -0,0 +1,264 @@
+use super::occlusion::{visible_runs, Occluder};
+use super::vector_export::{flatten_subpath, ExportedShape};
+
+use glam::DVec2;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Millimeters per document pixel, assuming the CSS resolution of 96 pixels per inch.
+const MILLIMETERS_PER_PIXEL: f64 = 25.4 / 96.;
+/// HPGL plotter units per millimeter.
+const HPGL_UNITS_PER_MILLIMETER: f64 = 40.;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlotterOptions {
+	/// The furthest, in document pixels, that the plotted lines may stray from the original curves.
+	pub tolerance: f64,
+	/// Drawing speed in millimeters per minute (G-code only).
+	pub feed_rate: f64,
+	/// Height of the Z axis in millimeters while the pen is lifted (G-code only).
+	pub pen_up_height: f64,
+	/// Height of the Z axis in millimeters while the pen is drawing (G-code only).
+	pub pen_down_height: f64,
+	/// Reorder and reverse the paths to shorten the distance traveled with the pen lifted, instead of plotting them in stacking order.
+	pub optimize_travel: bool,
+	/// Cut away the parts of lines that are hidden behind opaque fills stacked above them, so only the visible line art is plotted.
+	pub remove_hidden_lines: bool,
+}
+
+impl Default for PlotterOptions {
+	fn default() -> Self {
+		Self {
+			tolerance: 0.1,
+			feed_rate: 1500.,
+			pen_up_height: 5.,
+			pen_down_height: 0.,
+			optimize_travel: true,
+			remove_hidden_lines: false,
+		}
+	}
+}
+
+/// A continuous stroke drawn without lifting the pen, in millimeters with the origin at the bottom left of the plot.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Toolpath {
+	pub points: Vec<DVec2>,
+	pub closed: bool,
+}
+
+/// Converts the outlines of the stroked shapes into toolpaths, relative to the bottom left corner of `bounds`.
+pub fn shapes_to_toolpaths(shapes: &[ExportedShape], bounds: [DVec2; 2], options: PlotterOptions) -> Vec<Toolpath> {
+	let to_plot = |point: DVec2| DVec2::new(point.x - bounds[0].x, bounds[1].y - point.y) * MILLIMETERS_PER_PIXEL;
+
+	let occluders: Vec<_> = match options.remove_hidden_lines {
+		true => shapes.iter().map(|shape| Occluder::new(shape, options.tolerance)).collect(),
+		false => Vec::new(),
+	};
+
+	let mut toolpaths = Vec::new();
+	for (index, shape) in shapes.iter().enumerate().filter(|(_, shape)| shape.stroked) {
+		// The shapes are in stacking order, so only the fills of the later shapes can cover this one's lines
+		let above: Vec<_> = occluders.iter().skip(index + 1).flatten().collect();
+
+		for subpath in &shape.subpaths {
+			let (points, closed) = flatten_subpath(subpath, options.tolerance);
+			for (points, closed) in visible_runs(&points, closed, &above) {
+				toolpaths.push(Toolpath {
+					points: points.into_iter().map(to_plot).collect(),
+					closed,
+				});
+			}
+		}
+	}
+
+	if options.optimize_travel {
+		order_toolpaths(toolpaths)
+	} else {
+		toolpaths
+	}
+}
+
+/// Greedily plots the nearest remaining toolpath next, reversing open paths and rotating the start of closed paths to whichever end is closest to the pen.
+fn order_toolpaths(mut remaining: Vec<Toolpath>) -> Vec<Toolpath> {
+	let mut ordered = Vec::with_capacity(remaining.len());
+	let mut pen = DVec2::ZERO;
+
+	while !remaining.is_empty() {
+		let mut best = (f64::INFINITY, 0, 0);
+		for (index, toolpath) in remaining.iter().enumerate() {
+			let candidates: Box<dyn Iterator<Item = (usize, &DVec2)>> = if toolpath.closed {
+				Box::new(toolpath.points.iter().enumerate())
+			} else {
+				Box::new([(0, &toolpath.points[0]), (toolpath.points.len() - 1, &toolpath.points[toolpath.points.len() - 1])].into_iter())
+			};
+			for (start, point) in candidates {
+				let distance = pen.distance_squared(*point);
+				if distance < best.0 {
+					best = (distance, index, start);
+				}
+			}
+		}
+
+		let (_, index, start) = best;
+		let mut toolpath = remaining.swap_remove(index);
+		if toolpath.closed {
+			toolpath.points.rotate_left(start);
+		} else if start != 0 {
+			toolpath.points.reverse();
+		}
+
+		pen = if toolpath.closed { toolpath.points[0] } else { toolpath.points[toolpath.points.len() - 1] };
+		ordered.push(toolpath);
+	}
+
+	ordered
+}
+
+/// The points visited while drawing a toolpath, returning to the start for closed paths.
+fn toolpath_points(toolpath: &Toolpath) -> impl Iterator<Item = DVec2> + '_ {
+	toolpath.points.iter().copied().chain(toolpath.closed.then(|| toolpath.points[0]))
+}
+
+/// Writes the toolpaths as G-code in millimeters, raising and lowering the pen along the Z axis.
+pub fn toolpaths_to_gcode(toolpaths: &[Toolpath], options: PlotterOptions) -> String {
+	let mut gcode = String::new();
+	let _ = writeln!(gcode, "G21 ; Millimeters");
+	let _ = writeln!(gcode, "G90 ; Absolute positioning");
+	let _ = writeln!(gcode, "G0 Z{}", format_number(options.pen_up_height));
+
+	for toolpath in toolpaths {
+		let mut points = toolpath_points(toolpath);
+		let Some(start) = points.next() else { continue };
+
+		let _ = writeln!(gcode, "G0 X{} Y{}", format_number(start.x), format_number(start.y));
+		let _ = writeln!(gcode, "G1 Z{} F{}", format_number(options.pen_down_height), format_number(options.feed_rate));
+		for point in points {
+			let _ = writeln!(gcode, "G1 X{} Y{}", format_number(point.x), format_number(point.y));
+		}
+		let _ = writeln!(gcode, "G0 Z{}", format_number(options.pen_up_height));
+	}
+
+	let _ = writeln!(gcode, "G0 X0 Y0");
+	let _ = writeln!(gcode, "M2 ; End of program");
+	gcode
+}
+
+/// Writes the toolpaths as HPGL using the first pen.
+pub fn toolpaths_to_hpgl(toolpaths: &[Toolpath]) -> String {
+	let to_units = |point: DVec2| (point * HPGL_UNITS_PER_MILLIMETER).round().as_ivec2();
+
+	let mut hpgl = String::from("IN;SP1;");
+	for toolpath in toolpaths {
+		let mut points = toolpath_points(toolpath).map(to_units);
+		let Some(start) = points.next() else { continue };
+
+		let _ = write!(hpgl, "PU{},{};PD", start.x, start.y);
+		let coordinates = points.map(|point| format!("{},{}", point.x, point.y)).collect::<Vec<_>>();
+		let _ = write!(hpgl, "{};", coordinates.join(","));
+	}
+	hpgl.push_str("PU0,0;SP0;");
+	hpgl
+}
+
+fn format_number(value: f64) -> String {
+	let formatted = format!("{value:.3}");
+	formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use bezier_rs::Subpath;
+
+	fn shape(subpath: Subpath<graphene_core::uuid::ManipulatorGroupId>, stroked: bool) -> ExportedShape {
+		ExportedShape {
+			layer_name: "Shape".to_string(),
+			subpaths: vec![subpath],
+			filled: false,
+			opaque: false,
+			stroked,
+		}
+	}
+
+	/// A line along the bottom of a 96 pixel (one inch) square and a triangle along its top, along with an unstroked shape which isn't plotted.
+	fn two_paths(options: PlotterOptions) -> Vec<Toolpath> {
+		let shapes = [
+			shape(Subpath::new_line(DVec2::new(0., 96.), DVec2::new(96., 96.)), true),
+			shape(Subpath::from_anchors([DVec2::ZERO, DVec2::new(96., 0.), DVec2::new(96., 48.)], true), true),
+			shape(Subpath::new_rect(DVec2::ZERO, DVec2::splat(96.)), false),
+		];
+		shapes_to_toolpaths(&shapes, [DVec2::ZERO, DVec2::splat(96.)], options)
+	}
+
+	/// The distance traveled with the pen lifted, starting from the origin.
+	fn travel(toolpaths: &[Toolpath]) -> f64 {
+		let mut pen = DVec2::ZERO;
+		let mut distance = 0.;
+		for toolpath in toolpaths {
+			let mut points = toolpath_points(toolpath);
+			distance += pen.distance(points.next().unwrap());
+			pen = points.last().unwrap();
+		}
+		distance
+	}
+
+	#[test]
+	fn gcode_raises_the_pen_between_paths_and_flips_y() {
+		let options = PlotterOptions {
+			feed_rate: 900.,
+			pen_up_height: 3.,
+			pen_down_height: -1.,
+			optimize_travel: false,
+			..Default::default()
+		};
+		let gcode = toolpaths_to_gcode(&two_paths(options), options);
+
+		let expected = [
+			"G21 ; Millimeters",
+			"G90 ; Absolute positioning",
+			"G0 Z3",
+			"G0 X0 Y0",
+			"G1 Z-1 F900",
+			"G1 X25.4 Y0",
+			"G0 Z3",
+			"G0 X0 Y25.4",
+			"G1 Z-1 F900",
+			"G1 X25.4 Y25.4",
+			"G1 X25.4 Y12.7",
+			"G1 X0 Y25.4",
+			"G0 Z3",
+			"G0 X0 Y0",
+			"M2 ; End of program",
+		];
+		assert_eq!(gcode.lines().collect::<Vec<_>>(), expected);
+	}
+
+	#[test]
+	fn hpgl_lifts_the_pen_between_paths_and_flips_y() {
+		let options = PlotterOptions {
+			optimize_travel: false,
+			..Default::default()
+		};
+		let hpgl = toolpaths_to_hpgl(&two_paths(options));
+
+		// 40 plotter units make up a millimeter, so an inch is 1016 of them
+		assert_eq!(hpgl, "IN;SP1;PU0,0;PD1016,0;PU0,1016;PD1016,1016,1016,508,0,1016;PU0,0;SP0;");
+	}
+
+	#[test]
+	fn ordering_shortens_the_travel() {
+		let open = |points: &[(f64, f64)]| Toolpath {
+			points: points.iter().map(|&(x, y)| DVec2::new(x, y)).collect(),
+			closed: false,
+		};
+		let closed = |points: &[(f64, f64)]| Toolpath { closed: true, ..open(points) };
+		let toolpaths = vec![open(&[(50., 0.), (60., 0.)]), open(&[(12., 0.), (2., 0.)]), closed(&[(40., 10.), (30., 10.), (30., 0.), (40., 0.)])];
+
+		let ordered = order_toolpaths(toolpaths.clone());
+		// The nearest line is drawn first from its nearer end, and the square is started from its corner closest to where that line ends
+		let expected = vec![open(&[(2., 0.), (12., 0.)]), closed(&[(30., 0.), (40., 0.), (40., 10.), (30., 10.)]), open(&[(50., 0.), (60., 0.)])];
+		assert_eq!(ordered, expected);
+		assert_eq!(travel(&ordered), 40.);
+		assert!(travel(&toolpaths) > 130.);
+	}
+}
@@ -0,0 +1,188 @@
+//! Bleed and printer's marks around exported artwork, for sending designs to a commercial printer.
+//!
+//! The exported area is the trim box, where the printed sheet is cut. The artwork is extended past it by the bleed, so the cut can
+//! be slightly off without leaving a sliver of blank paper, and the marks are drawn in a margin outside the bleed.
+
+use graphene_core::Color;
+
+use glam::DVec2;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Gap, in document pixels, between the bleed edge and the start of the marks, so the marks aren't printed on the trimmed piece.
+const MARK_OFFSET: f64 = 6.;
+/// Length, in document pixels, of each crop mark line and the space in the margin taken up by the marks.
+const MARK_LENGTH: f64 = 18.;
+/// Radius, in document pixels, of the circle of each registration mark.
+const REGISTRATION_MARK_RADIUS: f64 = 5.;
+/// Thickness, in document pixels, of the lines of the marks.
+const MARK_STROKE_WIDTH: f64 = 0.5;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrintOptions {
+	/// How far, in document pixels, the artwork extends past the trim box on every side.
+	pub bleed: f64,
+	/// Lines outside each corner marking where the sheet is cut.
+	pub crop_marks: bool,
+	/// Targets centered outside each edge, used by the printer to line up the plates of each ink.
+	pub registration_marks: bool,
+}
+
+impl PrintOptions {
+	pub fn is_enabled(&self) -> bool {
+		self.bleed > 0. || self.crop_marks || self.registration_marks
+	}
+
+	/// The space outside the bleed that is reserved for the marks.
+	pub fn mark_margin(&self) -> f64 {
+		match self.crop_marks || self.registration_marks {
+			true => MARK_OFFSET + MARK_LENGTH,
+			false => 0.,
+		}
+	}
+
+	/// The trim box extended by the bleed, which is the area of the artwork that is printed.
+	pub fn bleed_bounds(&self, [min, max]: [DVec2; 2]) -> [DVec2; 2] {
+		let bleed = DVec2::splat(self.bleed.max(0.));
+		[min - bleed, max + bleed]
+	}
+
+	/// The size of the sheet holding the artwork with its bleed and the margin for the marks.
+	pub fn sheet_size(&self, [min, max]: [DVec2; 2]) -> DVec2 {
+		max - min + 2. * (self.bleed.max(0.) + self.mark_margin())
+	}
+}
+
+/// The lines of the crop marks in sheet space, which line up with the edges of the trim box just outside each corner.
+fn crop_mark_lines(trim_size: DVec2, options: &PrintOptions) -> Vec<[DVec2; 2]> {
+	let bleed = options.bleed.max(0.);
+	let inset = bleed + options.mark_margin();
+	let [left, top] = [inset, inset];
+	let [right, bottom] = (DVec2::splat(inset) + trim_size).to_array();
+	let start = bleed + MARK_OFFSET;
+
+	let mut lines = Vec::new();
+	for (x, horizontal_direction) in [(left, -1.), (right, 1.)] {
+		for (y, vertical_direction) in [(top, -1.), (bottom, 1.)] {
+			let horizontal = DVec2::new(horizontal_direction, 0.);
+			let vertical = DVec2::new(0., vertical_direction);
+			let corner = DVec2::new(x, y);
+			lines.push([corner + horizontal * start, corner + horizontal * (start + MARK_LENGTH)]);
+			lines.push([corner + vertical * start, corner + vertical * (start + MARK_LENGTH)]);
+		}
+	}
+	lines
+}
+
+/// The centers of the registration marks in sheet space, in the middle of the margin outside each edge.
+fn registration_mark_centers(trim_size: DVec2, options: &PrintOptions) -> [DVec2; 4] {
+	let margin = options.mark_margin();
+	let sheet_size = trim_size + 2. * (options.bleed.max(0.) + margin);
+	let middle = sheet_size / 2.;
+	let edge = margin / 2.;
+
+	[
+		DVec2::new(middle.x, edge),
+		DVec2::new(sheet_size.x - edge, middle.y),
+		DVec2::new(middle.x, sheet_size.y - edge),
+		DVec2::new(edge, middle.y),
+	]
+}
+
+/// Places an export covering the trim box extended by the bleed, rendered without a background, onto a sheet with the marks around it.
+/// Unless the background is transparent, the sheet is white paper and the given backgrounds, like the artboards, are extended by the bleed.
+pub fn compose_print_sheet(artwork_svg: &str, trim: [DVec2; 2], backgrounds: &[([DVec2; 2], Color)], options: &PrintOptions, transparent_background: bool) -> String {
+	let bleed = options.bleed.max(0.);
+	let margin = options.mark_margin();
+	let trim_size = trim[1] - trim[0];
+	let sheet_size = options.sheet_size(trim);
+	// Moves document space into sheet space, where the top left corner of the trim box sits inside the margin and bleed
+	let offset = DVec2::splat(bleed + margin) - trim[0];
+	let [bleed_min, bleed_max] = options.bleed_bounds(trim).map(|point| point + offset);
+
+	let mut svg = String::new();
+	let _ = write!(
+		svg,
+		r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+		sheet_size.x, sheet_size.y
+	);
+
+	if !transparent_background {
+		svg.push_str(r##"<rect x="0" y="0" width="100%" height="100%" fill="#ffffff" />"##);
+		for &([min, max], color) in backgrounds {
+			let min = (min + offset - bleed).max(bleed_min);
+			let max = (max + offset + bleed).min(bleed_max);
+			if min.x < max.x && min.y < max.y {
+				let size = max - min;
+				let _ = write!(svg, r##"<rect x="{}" y="{}" width="{}" height="{}" fill="#{}" />"##, min.x, min.y, size.x, size.y, color.rgba_hex());
+			}
+		}
+	}
+
+	// The nested SVG of the artwork clips it to the bleed box
+	let _ = write!(svg, r#"<g transform="translate({},{})">{artwork_svg}</g>"#, bleed_min.x, bleed_min.y);
+
+	let _ = write!(svg, r##"<g fill="none" stroke="#000000" stroke-width="{MARK_STROKE_WIDTH}">"##);
+	if options.crop_marks {
+		for [start, end] in crop_mark_lines(trim_size, options) {
+			let _ = write!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" />"#, start.x, start.y, end.x, end.y);
+		}
+	}
+	if options.registration_marks {
+		let radius = REGISTRATION_MARK_RADIUS;
+		let reach = radius * 1.5;
+		for center in registration_mark_centers(trim_size, options) {
+			let _ = write!(svg, r#"<circle cx="{}" cy="{}" r="{radius}" />"#, center.x, center.y);
+			let _ = write!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" />"#, center.x - reach, center.y, center.x + reach, center.y);
+			let _ = write!(svg, r#"<line x1="{}" y1="{}" x2="{}" y2="{}" />"#, center.x, center.y - reach, center.x, center.y + reach);
+		}
+	}
+	svg.push_str("</g></svg>");
+
+	svg
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn sheet_fits_the_bleed_and_marks() {
+		let trim = [DVec2::new(100., 100.), DVec2::new(300., 200.)];
+		let options = PrintOptions {
+			bleed: 9.,
+			crop_marks: true,
+			registration_marks: false,
+		};
+
+		assert_eq!(options.bleed_bounds(trim), [DVec2::new(91., 91.), DVec2::new(309., 209.)]);
+		assert_eq!(options.sheet_size(trim), DVec2::new(200. + 2. * (9. + 24.), 100. + 2. * (9. + 24.)));
+
+		let bleed_only = PrintOptions { bleed: 9., ..Default::default() };
+		assert_eq!(bleed_only.sheet_size(trim), DVec2::new(218., 118.));
+		assert!(!PrintOptions::default().is_enabled());
+	}
+
+	#[test]
+	fn crop_marks_stay_outside_the_bleed() {
+		let trim_size = DVec2::new(200., 100.);
+		let options = PrintOptions {
+			bleed: 9.,
+			crop_marks: true,
+			registration_marks: false,
+		};
+		let lines = crop_mark_lines(trim_size, &options);
+		let trim_min = DVec2::splat(33.);
+
+		// The marks at the top left corner line up with the trim edges and start just past the bleed
+		assert_eq!(lines[0], [DVec2::new(33. - 15., 33.), DVec2::new(33. - 33., 33.)]);
+		assert_eq!(lines[1], [DVec2::new(33., 33. - 15.), DVec2::new(33., 33. - 33.)]);
+
+		let bleed_min = trim_min - 9.;
+		let bleed_max = trim_min + trim_size + 9.;
+		for point in lines.iter().flatten() {
+			let inside = point.x > bleed_min.x && point.x < bleed_max.x && point.y > bleed_min.y && point.y < bleed_max.y;
+			assert!(!inside, "{point} is inside the bleed");
+		}
+	}
+}
@@ -0,0 +1,183 @@
+//! Opens very large documents a piece at a time. Only the top level of the layer tree is attached when the document opens, leaving its
+//! folders as empty placeholders, so the layer panel and canvas navigation are usable right away. The contents of the folders are then
+//! attached in chunks while the user is idle, outer folders before the folders nested inside them.
+//!
+//! Anything that relies on the whole document, like an undoable edit, saving, or exporting, first attaches whatever is still left.
+
+use super::layer_panel::LayerMetadata;
+
+use document_legacy::document::Document as DocumentLegacy;
+use document_legacy::layers::layer_info::Layer;
+use document_legacy::LayerId;
+
+use std::collections::{HashMap, VecDeque};
+
+/// Documents with more layers than this are opened progressively.
+pub const PROGRESSIVE_LOADING_LAYER_COUNT: usize = 2000;
+
+/// The most layers attached to the document in one step of loading.
+const LOADING_CHUNK_SIZE: usize = 200;
+
+/// The contents of a placeholder folder which are yet to be attached.
+#[derive(Debug, Clone)]
+struct DeferredFolder {
+	path: Vec<LayerId>,
+	layer_ids: VecDeque<LayerId>,
+	layers: VecDeque<Layer>,
+	layer_metadata: HashMap<Vec<LayerId>, LayerMetadata>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProgressiveLoading {
+	document_id: u64,
+	folders: VecDeque<DeferredFolder>,
+	loaded: usize,
+	total: usize,
+}
+
+impl ProgressiveLoading {
+	/// Detaches the contents of every folder in the document, leaving the folders in place as empty placeholders until they are loaded.
+	pub fn defer(document_id: u64, document: &mut DocumentLegacy, layer_metadata: &mut HashMap<Vec<LayerId>, LayerMetadata>) -> Self {
+		let mut folders = VecDeque::new();
+		if let Ok(root) = document.root.as_folder_mut() {
+			folders.extend(root.layer_ids.iter().zip(root.layers.iter_mut()).filter_map(|(id, layer)| take_contents(vec![*id], layer)));
+		}
+
+		// Folders are queued outermost first, so every placeholder is attached before the placeholders inside of it are filled in
+		let mut index = 0;
+		while index < folders.len() {
+			let parent = &mut folders[index];
+			let parent_path = parent.path.clone();
+			let nested = parent
+				.layer_ids
+				.iter()
+				.zip(parent.layers.iter_mut())
+				.filter_map(|(id, layer)| take_contents([parent_path.as_slice(), &[*id]].concat(), layer))
+				.collect::<Vec<_>>();
+			folders.extend(nested);
+			index += 1;
+		}
+
+		for folder in folders.iter_mut() {
+			for id in &folder.layer_ids {
+				let path = [folder.path.as_slice(), &[*id]].concat();
+				if let Some(metadata) = layer_metadata.remove(&path) {
+					folder.layer_metadata.insert(path, metadata);
+				}
+			}
+		}
+
+		let total = folders.iter().map(|folder| folder.layer_ids.len()).sum();
+		Self {
+			document_id,
+			folders,
+			loaded: 0,
+			total,
+		}
+	}
+
+	/// The document being loaded, which its progress is reported for.
+	pub fn document_id(&self) -> u64 {
+		self.document_id
+	}
+
+	pub fn is_loading(&self) -> bool {
+		!self.folders.is_empty()
+	}
+
+	/// How many of the deferred layers have been attached so far, out of how many in total.
+	pub fn progress(&self) -> (usize, usize) {
+		(self.loaded, self.total)
+	}
+
+	/// Attaches the next chunk of deferred layers to their placeholder folder, returning the paths of the attached layers.
+	pub fn load_next(&mut self, document: &mut DocumentLegacy, layer_metadata: &mut HashMap<Vec<LayerId>, LayerMetadata>) -> Vec<Vec<LayerId>> {
+		let Some(folder) = self.folders.front_mut() else { return Vec::new() };
+
+		let count = folder.layer_ids.len().min(LOADING_CHUNK_SIZE);
+		let layer_ids = folder.layer_ids.drain(..count).collect::<Vec<_>>();
+		let layers = folder.layers.drain(..count).collect::<Vec<_>>();
+		let paths = layer_ids.iter().map(|id| [folder.path.as_slice(), &[*id]].concat()).collect::<Vec<_>>();
+
+		let placeholder = document.layer_mut(&folder.path).ok().and_then(|layer| layer.as_folder_mut().ok());
+		if let Some(placeholder) = placeholder {
+			placeholder.layer_ids.extend(layer_ids);
+			placeholder.layers.extend(layers);
+			for path in &paths {
+				if let Some(metadata) = folder.layer_metadata.remove(path) {
+					layer_metadata.insert(path.clone(), metadata);
+				}
+			}
+			document
+				.mark_as_dirty(&folder.path)
+				.unwrap_or_else(|error| warn!("Could not mark the loaded folder as dirty: {error:?}"));
+		} else {
+			warn!("The placeholder folder {:?} was removed before its contents were loaded", folder.path);
+		}
+
+		self.loaded += count;
+		if folder.layer_ids.is_empty() {
+			self.folders.pop_front();
+		}
+
+		paths
+	}
+
+	/// Attaches all the layers still deferred, returning their paths.
+	pub fn load_all(&mut self, document: &mut DocumentLegacy, layer_metadata: &mut HashMap<Vec<LayerId>, LayerMetadata>) -> Vec<Vec<LayerId>> {
+		let mut paths = Vec::new();
+		while self.is_loading() {
+			paths.extend(self.load_next(document, layer_metadata));
+		}
+		paths
+	}
+}
+
+/// Empties a folder layer into a deferred folder, if it isn't empty already.
+fn take_contents(path: Vec<LayerId>, layer: &mut Layer) -> Option<DeferredFolder> {
+	let folder = layer.as_folder_mut().ok().filter(|folder| !folder.layer_ids.is_empty())?;
+	Some(DeferredFolder {
+		path,
+		layer_ids: std::mem::take(&mut folder.layer_ids).into(),
+		layers: std::mem::take(&mut folder.layers).into(),
+		layer_metadata: HashMap::new(),
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use document_legacy::layers::folder_layer::FolderLayer;
+
+	fn folder_with(layers: Vec<(LayerId, Layer)>) -> Layer {
+		let mut folder = FolderLayer::default();
+		for (id, layer) in layers {
+			folder.add_layer(layer, Some(id), -1);
+		}
+		folder.into()
+	}
+
+	#[test]
+	fn deferred_folders_are_restored_in_order() {
+		let mut document = DocumentLegacy::default();
+		let nested = folder_with((0..3).map(|id| (id, folder_with(Vec::new()))).collect());
+		let outer = folder_with(vec![(10, nested), (11, folder_with(Vec::new()))]);
+		document.root.as_folder_mut().unwrap().add_layer(outer, Some(1), -1);
+		let original = document.root.clone();
+
+		let mut layer_metadata = HashMap::new();
+		let mut loading = ProgressiveLoading::defer(0, &mut document, &mut layer_metadata);
+		assert!(loading.is_loading());
+		assert_eq!(loading.progress(), (0, 5));
+		assert!(document.layer(&[1]).unwrap().as_folder().unwrap().layer_ids.is_empty());
+
+		let first = loading.load_next(&mut document, &mut layer_metadata);
+		assert_eq!(first, vec![vec![1, 10], vec![1, 11]]);
+
+		loading.load_all(&mut document, &mut layer_metadata);
+		assert!(!loading.is_loading());
+		assert_eq!(loading.progress(), (5, 5));
+		assert_eq!(document.layer(&[1, 10]).unwrap().as_folder().unwrap().layer_ids, vec![0, 1, 2]);
+		assert_eq!(document.root.as_folder().unwrap().layer_ids, original.as_folder().unwrap().layer_ids);
+	}
+}
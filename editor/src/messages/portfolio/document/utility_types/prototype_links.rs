@@ -0,0 +1,79 @@
+//! Links from layers to artboards, turning the layers into hotspots which show the linked artboard when clicked, so a series of artboards
+//! can be clicked through as a prototype of an app or website.
+
+use document_legacy::LayerId;
+
+use serde::{Deserialize, Serialize};
+
+/// How the view moves to the linked artboard when a link is followed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrototypeTransition {
+	/// Jumps straight to the artboard.
+	#[default]
+	Instant,
+	/// Pans and zooms over to the artboard.
+	Smooth,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrototypeLink {
+	/// The ID of the artboard the link leads to.
+	pub target: LayerId,
+	pub transition: PrototypeTransition,
+}
+
+/// The links of the document, at most one for each layer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrototypeLinks {
+	links: Vec<(Vec<LayerId>, PrototypeLink)>,
+}
+
+impl PrototypeLinks {
+	/// Links the layer to an artboard, replacing its previous link, or removes its link if `link` is `None`.
+	pub fn set(&mut self, layer: Vec<LayerId>, link: Option<PrototypeLink>) {
+		self.links.retain(|(linked, _)| *linked != layer);
+		if let Some(link) = link {
+			self.links.push((layer, link));
+		}
+	}
+
+	pub fn get(&self, layer: &[LayerId]) -> Option<PrototypeLink> {
+		self.links.iter().find(|(linked, _)| linked == layer).map(|&(_, link)| link)
+	}
+
+	/// The link followed when the layer is clicked, which is the layer's own or else that of the innermost folder containing it with one.
+	pub fn hotspot_link(&self, layer: &[LayerId]) -> Option<PrototypeLink> {
+		(1..=layer.len()).rev().find_map(|length| self.get(&layer[..length]))
+	}
+
+	/// Forgets the links of the layers which no longer exist and the links to artboards which no longer exist.
+	pub fn retain(&mut self, layer_exists: impl Fn(&[LayerId]) -> bool, artboard_exists: impl Fn(LayerId) -> bool) {
+		self.links.retain(|(layer, link)| layer_exists(layer) && artboard_exists(link.target));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn clicks_follow_the_innermost_link() {
+		let mut links = PrototypeLinks::default();
+		let to = |target| {
+			Some(PrototypeLink {
+				target,
+				transition: PrototypeTransition::Instant,
+			})
+		};
+		links.set(vec![1], to(10));
+		links.set(vec![1, 2], to(20));
+		links.set(vec![1, 2], to(30));
+
+		assert_eq!(links.hotspot_link(&[1, 2, 3]).map(|link| link.target), Some(30));
+		assert_eq!(links.hotspot_link(&[1, 4]).map(|link| link.target), Some(10));
+		assert_eq!(links.hotspot_link(&[5]), None);
+
+		links.set(vec![1, 2], None);
+		assert_eq!(links.hotspot_link(&[1, 2, 3]).map(|link| link.target), Some(10));
+	}
+}
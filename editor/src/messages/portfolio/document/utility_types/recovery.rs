@@ -0,0 +1,110 @@
+//! Recovers what it can of a saved document in which some layers can't be read, such as after the file was corrupted or edited by hand,
+//! so the rest of the artwork can still be opened instead of the whole document failing to open.
+
+use document_legacy::layers::layer_info::Layer;
+use document_legacy::LayerId;
+
+use serde_json::Value;
+
+/// A layer left out of a recovered document, with the reason it couldn't be read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DroppedLayer {
+	pub path: Vec<LayerId>,
+	pub name: Option<String>,
+	pub error: String,
+}
+
+impl std::fmt::Display for DroppedLayer {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let path = self.path.iter().map(|id| id.to_string()).collect::<Vec<_>>().join("/");
+		match &self.name {
+			Some(name) => write!(f, "\"{name}\" ({path}): {}", self.error),
+			None => write!(f, "Layer {path}: {}", self.error),
+		}
+	}
+}
+
+/// Removes every layer that can't be read from the folders within the serialized root layer, returning the layers that were removed.
+/// The children of each folder are checked first, so a folder is kept with its readable children when only some of them are broken.
+pub fn drop_unreadable_layers(root: &mut Value) -> Vec<DroppedLayer> {
+	let mut dropped = Vec::new();
+	drop_unreadable_children(root, &mut Vec::new(), &mut dropped);
+	dropped
+}
+
+fn drop_unreadable_children(layer: &mut Value, path: &mut Vec<LayerId>, dropped: &mut Vec<DroppedLayer>) {
+	let Some(folder) = layer.pointer_mut("/data/Folder").and_then(Value::as_object_mut) else { return };
+	let ids = folder.get("layer_ids").and_then(Value::as_array).cloned().unwrap_or_default();
+	let Some(Value::Array(children)) = folder.get_mut("layers") else { return };
+
+	// A layer without an ID (or an ID without a layer) can't be addressed, so the longer list is cut to match the shorter one
+	let mut kept_ids = Vec::new();
+	let mut kept_children = Vec::new();
+	for (id, mut child) in ids.into_iter().zip(std::mem::take(children)) {
+		let Some(layer_id) = id.as_u64() else { continue };
+		path.push(layer_id);
+
+		drop_unreadable_children(&mut child, path, dropped);
+		match serde_json::from_value::<Layer>(child.clone()) {
+			Ok(_) => {
+				kept_ids.push(id);
+				kept_children.push(child);
+			}
+			Err(error) => dropped.push(DroppedLayer {
+				path: path.clone(),
+				name: child.get("name").and_then(Value::as_str).map(String::from),
+				error: error.to_string(),
+			}),
+		}
+
+		path.pop();
+	}
+
+	*children = kept_children;
+	folder.insert("layer_ids".to_string(), Value::Array(kept_ids));
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use document_legacy::layers::folder_layer::FolderLayer;
+	use document_legacy::layers::layer_info::LayerDataType;
+
+	fn folder(children: Vec<(LayerId, Value)>) -> Value {
+		let mut folder = serde_json::to_value(Layer::new(LayerDataType::Folder(FolderLayer::default()), glam::DAffine2::IDENTITY.to_cols_array())).unwrap();
+		let (ids, layers): (Vec<_>, Vec<_>) = children.into_iter().unzip();
+		folder["data"]["Folder"]["layer_ids"] = serde_json::to_value(ids).unwrap();
+		folder["data"]["Folder"]["layers"] = Value::Array(layers);
+		folder
+	}
+
+	fn broken(name: &str) -> Value {
+		let mut layer = folder(Vec::new());
+		layer["name"] = Value::String(name.to_string());
+		layer["opacity"] = Value::String("not a number".to_string());
+		layer
+	}
+
+	#[test]
+	fn only_unreadable_layers_are_dropped() {
+		let mut root = folder(vec![(1, folder(Vec::new())), (2, broken("Broken")), (3, folder(vec![(4, broken("Nested")), (5, folder(Vec::new()))]))]);
+
+		let dropped = drop_unreadable_layers(&mut root);
+		assert_eq!(dropped.iter().map(|layer| layer.path.clone()).collect::<Vec<_>>(), vec![vec![2], vec![3, 4]]);
+		assert_eq!(dropped[0].name.as_deref(), Some("Broken"));
+		assert_eq!(root["data"]["Folder"]["layer_ids"], serde_json::json!([1, 3]));
+		assert_eq!(root["data"]["Folder"]["layers"][1]["data"]["Folder"]["layer_ids"], serde_json::json!([5]));
+
+		let recovered: Layer = serde_json::from_value(root).unwrap();
+		assert_eq!(recovered.as_folder().unwrap().layers().len(), 2);
+	}
+
+	#[test]
+	fn layers_without_ids_are_left_out() {
+		let mut root = folder(vec![(1, folder(Vec::new()))]);
+		root["data"]["Folder"]["layers"].as_array_mut().unwrap().push(folder(Vec::new()));
+
+		assert!(drop_unreadable_layers(&mut root).is_empty());
+		assert_eq!(root["data"]["Folder"]["layers"].as_array().unwrap().len(), 1);
+	}
+}
@@ -0,0 +1,291 @@
+//! A read-only JSON description of the document's layers for programs which draw or process the artwork themselves, such as game engines
+//! and web renderers. Unlike the native document format, which stores the node graphs needed to keep editing, the scene graph only holds
+//! what is needed to draw each layer, and its structure is kept stable between versions of the editor.
+//!
+//! The file is an object with these fields:
+//! - `version`: the [SCENE_GRAPH_VERSION] of the format, which is increased whenever a change could break existing readers.
+//! - `bounds`: the exported area as `x`, `y`, `width`, and `height` in document coordinates.
+//! - `layers`: the layers at the root of the document, from the bottom of the stacking order to the top.
+//!
+//! Each layer has an `id` (a string, since the IDs can be too large for the numbers of JavaScript), an optional `name`, `opacity` from 0 to 1,
+//! a CSS `blendMode`, and a `transform` given as the SVG matrix `[a, b, c, d, e, f]` relative to the layer's parent. Its `type` is one of:
+//! - `"group"`, with its `children` from bottom to top.
+//! - `"shape"`, with its geometry as SVG path data in `path`, and an optional `fill` and `stroke`.
+//! - `"text"`, with the same fields as a shape along with its `text`, `fontFamily`, `fontStyle`, and `fontSize`.
+//! - `"raster"`, for layers whose content is an image, which isn't included.
+//!
+//! A fill is either `{ "type": "solid", "color" }` or `{ "type": "linear" | "radial", "start", "end", "transform", "stops" }` where each
+//! stop has an `offset` from 0 to 1 and a `color`. A stroke has a `color`, `weight`, `dashLengths`, `dashOffset`, `dashSpeed`, `lineCap`,
+//! `lineJoin`, and `miterLimit`. Colors are `#RRGGBBAA` hex strings.
+
+use super::outline::{layer_font, layer_font_size, layer_text};
+
+use document_legacy::document::Document as DocumentLegacy;
+use document_legacy::layers::layer_info::{Layer, LayerDataType};
+use document_legacy::LayerId;
+use graphene_core::vector::style::{Fill, GradientType, PathStyle};
+use graphene_core::Color;
+
+use glam::DVec2;
+use serde::Serialize;
+
+/// The version of the scene graph format, which is increased whenever a change could break existing readers.
+pub const SCENE_GRAPH_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SceneGraph {
+	pub version: u32,
+	pub bounds: SceneBounds,
+	pub layers: Vec<SceneLayer>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SceneBounds {
+	pub x: f64,
+	pub y: f64,
+	pub width: f64,
+	pub height: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneLayer {
+	pub id: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub name: Option<String>,
+	pub opacity: f64,
+	pub blend_mode: String,
+	pub transform: [f64; 6],
+	#[serde(flatten)]
+	pub content: SceneContent,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SceneContent {
+	Group {
+		children: Vec<SceneLayer>,
+	},
+	Shape {
+		path: String,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		fill: Option<SceneFill>,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		stroke: Option<SceneStroke>,
+	},
+	#[serde(rename_all = "camelCase")]
+	Text {
+		text: String,
+		font_family: String,
+		font_style: String,
+		font_size: f64,
+		path: String,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		fill: Option<SceneFill>,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		stroke: Option<SceneStroke>,
+	},
+	Raster,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SceneFill {
+	Solid {
+		color: String,
+	},
+	Linear {
+		start: [f64; 2],
+		end: [f64; 2],
+		transform: [f64; 6],
+		stops: Vec<SceneGradientStop>,
+	},
+	Radial {
+		start: [f64; 2],
+		end: [f64; 2],
+		transform: [f64; 6],
+		stops: Vec<SceneGradientStop>,
+	},
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SceneGradientStop {
+	pub offset: f64,
+	pub color: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneStroke {
+	pub color: String,
+	pub weight: f64,
+	pub dash_lengths: Vec<f32>,
+	pub dash_offset: f64,
+	pub dash_speed: f64,
+	pub line_cap: String,
+	pub line_join: String,
+	pub miter_limit: f64,
+}
+
+fn hex_color(color: Option<Color>) -> String {
+	format!("#{}", color.unwrap_or(Color::TRANSPARENT).rgba_hex())
+}
+
+fn scene_fill(style: &PathStyle) -> Option<SceneFill> {
+	match style.fill() {
+		Fill::None => None,
+		Fill::Solid(color) => Some(SceneFill::Solid { color: hex_color(Some(*color)) }),
+		Fill::Gradient(gradient) => {
+			let (start, end, transform) = (gradient.start.to_array(), gradient.end.to_array(), gradient.transform.to_cols_array());
+			let stops = gradient.positions.iter().map(|&(offset, color)| SceneGradientStop { offset, color: hex_color(color) }).collect();
+			Some(match gradient.gradient_type {
+				GradientType::Linear => SceneFill::Linear { start, end, transform, stops },
+				GradientType::Radial => SceneFill::Radial { start, end, transform, stops },
+			})
+		}
+	}
+}
+
+fn scene_stroke(style: &PathStyle) -> Option<SceneStroke> {
+	let stroke = style.stroke().filter(|stroke| stroke.color.is_some() && stroke.weight > 0.)?;
+	Some(SceneStroke {
+		color: hex_color(stroke.color),
+		weight: stroke.weight,
+		dash_lengths: stroke.dash_lengths.clone(),
+		dash_offset: stroke.dash_offset,
+		dash_speed: stroke.dash_speed,
+		line_cap: stroke.line_cap.to_string(),
+		line_join: stroke.line_join.to_string(),
+		miter_limit: stroke.line_join_miter_limit,
+	})
+}
+
+/// Describes the layers which are included in exports, or only the selected ones (along with the folders containing them) when `selected` is given.
+pub fn scene_graph(document: &DocumentLegacy, bounds: [DVec2; 2], selected: Option<&[Vec<LayerId>]>) -> SceneGraph {
+	let layers = match &document.root.data {
+		LayerDataType::Folder(folder) => folder
+			.layer_ids
+			.iter()
+			.zip(folder.layers())
+			.filter_map(|(&id, layer)| scene_layer(layer, &mut vec![id], selected))
+			.collect(),
+		_ => Vec::new(),
+	};
+
+	let size = bounds[1] - bounds[0];
+	SceneGraph {
+		version: SCENE_GRAPH_VERSION,
+		bounds: SceneBounds {
+			x: bounds[0].x,
+			y: bounds[0].y,
+			width: size.x,
+			height: size.y,
+		},
+		layers,
+	}
+}
+
+fn scene_layer(layer: &Layer, path: &mut Vec<LayerId>, selected: Option<&[Vec<LayerId>]>) -> Option<SceneLayer> {
+	if !layer.is_visible(true) {
+		return None;
+	}
+	let is_selected = selected.map_or(true, |selected| selected.iter().any(|selected_path| path.starts_with(selected_path)));
+
+	let content = match &layer.data {
+		LayerDataType::Folder(folder) => {
+			let children = folder
+				.layer_ids
+				.iter()
+				.zip(folder.layers())
+				.filter_map(|(&id, child)| {
+					path.push(id);
+					let child = scene_layer(child, path, selected);
+					path.pop();
+					child
+				})
+				.collect::<Vec<_>>();
+
+			// Folders outside the selection are only kept to hold the selected layers inside them
+			if children.is_empty() && !is_selected {
+				return None;
+			}
+			SceneContent::Group { children }
+		}
+		LayerDataType::Layer(_) if !is_selected => return None,
+		LayerDataType::Layer(_) => match layer.as_vector_data() {
+			Some(vector_data) => {
+				let mut path_data = String::new();
+				for subpath in vector_data.subpaths.iter().filter(|subpath| !subpath.is_empty()) {
+					let _ = subpath.subpath_to_svg(&mut path_data, vector_data.transform);
+				}
+				let (fill, stroke) = (scene_fill(&vector_data.style), scene_stroke(&vector_data.style));
+
+				match (layer_text(layer), layer_font(layer)) {
+					(Some(text), Some(font)) => SceneContent::Text {
+						text: text.to_string(),
+						font_family: font.font_family.clone(),
+						font_style: font.font_style.clone(),
+						font_size: layer_font_size(layer).unwrap_or_default(),
+						path: path_data,
+						fill,
+						stroke,
+					},
+					_ => SceneContent::Shape { path: path_data, fill, stroke },
+				}
+			}
+			None => SceneContent::Raster,
+		},
+	};
+
+	Some(SceneLayer {
+		id: path.last().map(ToString::to_string).unwrap_or_default(),
+		name: layer.name.clone().filter(|name| !name.is_empty()),
+		opacity: layer.opacity,
+		blend_mode: layer.blend_mode.to_svg_style_name().to_string(),
+		transform: layer.transform.to_cols_array(),
+		content,
+	})
+}
+
+/// The scene graph as pretty-printed JSON.
+pub fn scene_graph_json(scene_graph: &SceneGraph) -> String {
+	serde_json::to_string_pretty(scene_graph).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use graphene_core::vector::style::Stroke;
+
+	#[test]
+	fn styles_are_described_with_hex_colors() {
+		let mut style = PathStyle::new(Some(Stroke::new(Some(Color::BLACK), 2.)), Fill::Solid(Color::from_rgbaf32_unchecked(1., 0., 0., 1.)));
+		assert_eq!(scene_fill(&style), Some(SceneFill::Solid { color: "#ff0000ff".to_string() }));
+		assert_eq!(scene_stroke(&style).map(|stroke| (stroke.color, stroke.weight)), Some(("#000000ff".to_string(), 2.)));
+
+		style.set_stroke(Stroke::new(None, 2.));
+		assert_eq!(scene_stroke(&style), None, "a stroke without a color isn't drawn");
+	}
+
+	#[test]
+	fn layers_are_tagged_with_their_type() {
+		let layer = SceneLayer {
+			id: "18446744073709551615".to_string(),
+			name: None,
+			opacity: 1.,
+			blend_mode: "normal".to_string(),
+			transform: [1., 0., 0., 1., 0., 0.],
+			content: SceneContent::Shape {
+				path: "M0,0 L10,0".to_string(),
+				fill: None,
+				stroke: None,
+			},
+		};
+
+		let json = serde_json::to_value(&layer).unwrap();
+		assert_eq!(json["type"], "shape");
+		assert_eq!(json["id"], "18446744073709551615");
+		assert_eq!(json["blendMode"], "normal");
+		assert!(json.get("name").is_none() && json.get("fill").is_none());
+	}
+}
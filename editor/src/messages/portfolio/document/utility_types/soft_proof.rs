@@ -0,0 +1,75 @@
+//! Soft proofing, which previews in the viewport roughly how the artwork's colors survive being printed with CMYK process inks.
+//!
+//! Each color is separated into cyan, magenta, and yellow ink coverage, and the light absorbed by each ink is summed to find the
+//! printed color. Because real inks aren't pure, saturated colors outside the gamut they can reproduce (like bright blues and greens)
+//! come out duller and darker, much as they would from a print shop. This is a basic approximation rather than a color-managed profile.
+
+use std::fmt::Write;
+
+/// The colors of the cyan, magenta, and yellow process inks printed on white paper, as sRGB channel values from 0 to 1.
+const INK_COLORS: [[f64; 3]; 3] = [[0., 0.68, 0.94], [0.93, 0., 0.55], [1., 0.95, 0.]];
+
+/// The ID of the SVG filter applied to the artwork while soft proofing.
+pub const SOFT_PROOF_FILTER_ID: &str = "cmyk-soft-proof";
+
+/// The affine map from an RGB color to its proofed RGB color, as one row per output channel of three weights and an offset.
+///
+/// Separating a color into inks gives a coverage of `1 - channel` for each of cyan, magenta, and yellow, and each ink removes
+/// `1 - ink channel` of the light in each output channel per unit of coverage, so the result is linear in the input color.
+fn proof_matrix() -> [[f64; 4]; 3] {
+	let mut matrix = [[0.; 4]; 3];
+	for (channel, row) in matrix.iter_mut().enumerate() {
+		let absorptions = INK_COLORS.map(|ink| 1. - ink[channel]);
+		row[..3].copy_from_slice(&absorptions);
+		row[3] = 1. - absorptions.iter().sum::<f64>();
+	}
+	matrix
+}
+
+/// Approximates the color, with channels from 0 to 1, that the given color comes out as when printed with CMYK inks.
+pub fn proof_color(color: [f64; 3]) -> [f64; 3] {
+	proof_matrix().map(|[red, green, blue, offset]| (red * color[0] + green * color[1] + blue * color[2] + offset).clamp(0., 1.))
+}
+
+/// Wraps the SVG of the artwork in a filter which shows how its colors look when printed with CMYK inks.
+pub fn soft_proof_svg(artwork_svg: &str) -> String {
+	let mut values = String::new();
+	for [red, green, blue, offset] in proof_matrix() {
+		let _ = write!(values, "{red} {green} {blue} 0 {offset} ");
+	}
+	values.push_str("0 0 0 1 0");
+
+	format!(
+		r#"<defs><filter id="{SOFT_PROOF_FILTER_ID}" color-interpolation-filters="sRGB"><feColorMatrix type="matrix" values="{values}" /></filter></defs><g filter="url(#{SOFT_PROOF_FILTER_ID})">{artwork_svg}</g>"#
+	)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn assert_close(actual: [f64; 3], expected: [f64; 3]) {
+		assert!(actual.iter().zip(expected).all(|(actual, expected)| (actual - expected).abs() < 1e-9), "{actual:?} is not {expected:?}");
+	}
+
+	#[test]
+	fn paper_and_inks_are_reproduced() {
+		assert_close(proof_color([1., 1., 1.]), [1., 1., 1.]);
+		assert_close(proof_color([0., 0., 0.]), [0., 0., 0.]);
+		for (index, ink) in INK_COLORS.iter().enumerate() {
+			let mut color = [1.; 3];
+			color[index] = 0.;
+			assert_close(proof_color(color), *ink);
+		}
+	}
+
+	#[test]
+	fn out_of_gamut_colors_are_dulled() {
+		let [red, green, blue] = proof_color([0., 0., 1.]);
+		assert!(blue < 0.6, "pure blue should print noticeably darker");
+		assert!(red < 0.01 && green < 0.01);
+
+		let [red, green, blue] = proof_color([1., 0., 0.]);
+		assert!(red > 0.9 && green < 0.01 && blue < 0.01, "pure red should stay close to itself");
+	}
+}
@@ -0,0 +1,146 @@
+use glam::UVec2;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Which parts of the document become the sprites of a sprite sheet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpriteSource {
+	#[default]
+	Artboards,
+	SelectedLayers,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasRect {
+	pub x: u32,
+	pub y: u32,
+	pub w: u32,
+	pub h: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasSize {
+	pub w: u32,
+	pub h: u32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AtlasFrame {
+	pub frame: AtlasRect,
+	#[serde(rename = "sourceSize")]
+	pub source_size: AtlasSize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AtlasMeta {
+	pub image: String,
+	pub size: AtlasSize,
+	pub scale: f64,
+}
+
+/// The JSON description of a sprite sheet, following the widely supported "hash" layout of frames keyed by name.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpriteAtlas {
+	pub frames: BTreeMap<String, AtlasFrame>,
+	pub meta: AtlasMeta,
+}
+
+/// Arranges rectangles of the given sizes into rows (tallest first) that fill a roughly square sheet, leaving `spacing` pixels between neighbors.
+/// Returns the top left corner of each rectangle, in the order given, and the size of the whole sheet.
+pub fn pack_sprites(sizes: &[UVec2], spacing: u32) -> (Vec<UVec2>, UVec2) {
+	let total_area: u64 = sizes.iter().map(|size| (size.x + spacing) as u64 * (size.y + spacing) as u64).sum();
+	let widest = sizes.iter().map(|size| size.x).max().unwrap_or_default();
+	let row_width = widest.max((total_area as f64).sqrt().ceil() as u32);
+
+	let mut order: Vec<usize> = (0..sizes.len()).collect();
+	order.sort_by_key(|&index| std::cmp::Reverse(sizes[index].y));
+
+	let mut positions = vec![UVec2::ZERO; sizes.len()];
+	let mut cursor = UVec2::ZERO;
+	let mut row_height = 0;
+	let mut sheet_size = UVec2::ZERO;
+	for index in order {
+		let size = sizes[index];
+		if cursor.x > 0 && cursor.x + size.x > row_width {
+			cursor = UVec2::new(0, cursor.y + row_height + spacing);
+			row_height = 0;
+		}
+
+		positions[index] = cursor;
+		sheet_size = sheet_size.max(cursor + size);
+		row_height = row_height.max(size.y);
+		cursor.x += size.x + spacing;
+	}
+
+	(positions, sheet_size)
+}
+
+/// Gives each sprite a unique name by numbering repeats, since the atlas looks up frames by name.
+pub fn unique_sprite_names(names: impl IntoIterator<Item = String>) -> Vec<String> {
+	let mut used = std::collections::HashSet::new();
+	names
+		.into_iter()
+		.map(|name| {
+			let mut unique = name.clone();
+			let mut suffix = 2;
+			while !used.insert(unique.clone()) {
+				unique = format!("{name} {suffix}");
+				suffix += 1;
+			}
+			unique
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	/// Checks that every two sprites are at least `spacing` pixels apart along one axis, which also keeps them from overlapping.
+	fn assert_spaced(positions: &[UVec2], sizes: &[UVec2], spacing: u32) {
+		for a in 0..sizes.len() {
+			for b in a + 1..sizes.len() {
+				let apart = |first: usize, second: usize| (positions[first] + sizes[first] + spacing).cmple(positions[second]);
+				let (a_before_b, b_before_a) = (apart(a, b), apart(b, a));
+				assert!(a_before_b.x || a_before_b.y || b_before_a.x || b_before_a.y, "Sprites {a} and {b} are closer than {spacing} pixels");
+			}
+		}
+	}
+
+	#[test]
+	fn sprites_are_spaced_apart_on_a_tight_sheet() {
+		let sizes = [UVec2::new(30, 10), UVec2::new(12, 40), UVec2::new(25, 25), UVec2::new(8, 8), UVec2::new(50, 5), UVec2::new(16, 30)];
+		for spacing in [0, 1, 4] {
+			let (positions, sheet_size) = pack_sprites(&sizes, spacing);
+			assert_spaced(&positions, &sizes, spacing);
+
+			// The sheet ends right at the edges of the furthest sprites, without spacing after them
+			let furthest = positions.iter().zip(sizes).fold(UVec2::ZERO, |furthest, (&position, size)| furthest.max(position + size));
+			assert_eq!(sheet_size, furthest);
+		}
+	}
+
+	#[test]
+	fn equal_sprites_are_packed_into_a_square() {
+		let sizes = [UVec2::splat(10); 4];
+		let (positions, sheet_size) = pack_sprites(&sizes, 2);
+		assert_eq!(positions, [UVec2::new(0, 0), UVec2::new(12, 0), UVec2::new(0, 12), UVec2::new(12, 12)]);
+		assert_eq!(sheet_size, UVec2::splat(22));
+	}
+
+	#[test]
+	fn positions_are_in_the_order_given() {
+		// The tallest sprite is placed first, although it's listed last, and the shorter one is put on the next row
+		let (positions, sheet_size) = pack_sprites(&[UVec2::new(10, 10), UVec2::new(10, 20)], 0);
+		assert_eq!(positions, [UVec2::new(0, 20), UVec2::ZERO]);
+		assert_eq!(sheet_size, UVec2::new(10, 30));
+
+		assert_eq!(pack_sprites(&[], 4), (Vec::new(), UVec2::ZERO));
+	}
+
+	#[test]
+	fn repeated_names_are_numbered() {
+		let names = ["Idle", "Walk", "Idle", "Idle 2", "Idle"].map(String::from);
+		assert_eq!(unique_sprite_names(names), ["Idle", "Walk", "Idle 2", "Idle 2 2", "Idle 3"]);
+	}
+}
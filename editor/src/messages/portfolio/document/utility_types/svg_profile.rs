@@ -0,0 +1,188 @@
+//! Checking which features of SVG the document uses against the profiles of SVG that some viewers are limited to, so the layers which won't
+//! look right in the target viewer can be found before exporting.
+
+use document_legacy::document::Document;
+use document_legacy::layers::blend_mode::BlendMode;
+use document_legacy::layers::layer_info::{Layer, LayerDataType};
+use document_legacy::LayerId;
+
+use serde::{Deserialize, Serialize};
+
+/// The SVG features that a viewer may be limited to, which an exported SVG can be checked against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SvgProfile {
+	/// Everything supported by current web browsers, which is all the renderer produces.
+	#[default]
+	Browsers,
+	/// SVG 1.1, without the CSS compositing that browsers have added on top of it.
+	Svg11,
+	/// SVG Tiny 1.2, the subset of SVG for mobile devices and embedded viewers, which also lacks filters, masks, and clipping.
+	SvgTiny12,
+	/// Browsers and email clients which don't render SVG filters.
+	NoFilters,
+}
+
+impl SvgProfile {
+	pub fn label(self) -> &'static str {
+		match self {
+			Self::Browsers => "Web Browsers",
+			Self::Svg11 => "SVG 1.1",
+			Self::SvgTiny12 => "SVG Tiny 1.2",
+			Self::NoFilters => "No Filters",
+		}
+	}
+
+	pub fn supports(self, feature: SvgFeature) -> bool {
+		use SvgFeature::*;
+		match self {
+			Self::Browsers => feature != MeshGradient,
+			Self::Svg11 => matches!(feature, GroupOpacity | Filter | Mask | ClipPath),
+			Self::SvgTiny12 => false,
+			Self::NoFilters => matches!(feature, BlendMode | GroupOpacity | Isolation | Mask | ClipPath),
+		}
+	}
+}
+
+/// The features of SVG used by the document which not every profile supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum SvgFeature {
+	/// A blend mode other than normal, written as the CSS `mix-blend-mode` property.
+	BlendMode,
+	/// Opacity applied to a whole layer or folder rather than its fill or stroke.
+	GroupOpacity,
+	/// A folder isolating its blending from the layers below it, written as the CSS `isolation` property.
+	Isolation,
+	Filter,
+	Mask,
+	ClipPath,
+	/// Mesh gradients, which were dropped from SVG 2 and are only supported by Inkscape.
+	MeshGradient,
+}
+
+impl SvgFeature {
+	pub fn label(self) -> &'static str {
+		match self {
+			Self::BlendMode => "Blend mode",
+			Self::GroupOpacity => "Layer opacity",
+			Self::Isolation => "Isolated blending",
+			Self::Filter => "Filter",
+			Self::Mask => "Mask",
+			Self::ClipPath => "Clipping path",
+			Self::MeshGradient => "Mesh gradient",
+		}
+	}
+}
+
+/// A feature used by a layer, where the layer is named for showing to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureUse {
+	pub layer: Vec<LayerId>,
+	pub layer_name: String,
+	pub feature: SvgFeature,
+}
+
+/// Every use of a feature which not every profile supports, in layer order, from the layers as they were last rendered.
+pub fn svg_feature_uses(document: &Document) -> Vec<FeatureUse> {
+	let mut uses = Vec::new();
+	if let LayerDataType::Folder(root) = &document.root.data {
+		for (&id, layer) in root.layer_ids.iter().zip(root.layers()) {
+			collect_feature_uses(layer, &mut vec![id], &mut uses);
+		}
+	}
+	uses
+}
+
+/// The uses of features which the profile doesn't support.
+pub fn unsupported_feature_uses(uses: &[FeatureUse], profile: SvgProfile) -> impl Iterator<Item = &FeatureUse> {
+	uses.iter().filter(move |feature_use| !profile.supports(feature_use.feature))
+}
+
+fn collect_feature_uses(layer: &Layer, path: &mut Vec<LayerId>, uses: &mut Vec<FeatureUse>) {
+	let mut features = Vec::new();
+	if layer.blend_mode != BlendMode::Normal {
+		features.push(SvgFeature::BlendMode);
+	}
+	if layer.opacity < 1. {
+		features.push(SvgFeature::GroupOpacity);
+	}
+
+	match &layer.data {
+		LayerDataType::Folder(folder) => {
+			if folder.isolated {
+				features.push(SvgFeature::Isolation);
+			}
+			// Knockout is emulated by masking each layer with the silhouettes of those above, made by a filter
+			if folder.knockout {
+				features.extend([SvgFeature::Mask, SvgFeature::Filter]);
+			}
+		}
+		// The artwork of other layers is only known from its rendered SVG
+		_ => features.extend(svg_features(&layer.cache).into_iter().chain(svg_features(&layer.svg_defs_cache))),
+	}
+
+	features.sort();
+	features.dedup();
+	let layer_name = layer.name.clone().unwrap_or_else(|| "Untitled Layer".to_string());
+	uses.extend(features.into_iter().map(|feature| FeatureUse {
+		layer: path.clone(),
+		layer_name: layer_name.clone(),
+		feature,
+	}));
+
+	if let LayerDataType::Folder(folder) = &layer.data {
+		for (&id, child) in folder.layer_ids.iter().zip(folder.layers()) {
+			path.push(id);
+			collect_feature_uses(child, path, uses);
+			path.pop();
+		}
+	}
+}
+
+/// The features found in an SVG fragment by the elements and attributes that write them. The blend mode and opacity of each layer are left
+/// out since every layer is written with them, even when they have no effect.
+fn svg_features(svg: &str) -> Vec<SvgFeature> {
+	let patterns = [
+		(SvgFeature::Filter, "<filter"),
+		(SvgFeature::Filter, "filter=\""),
+		(SvgFeature::Mask, "<mask"),
+		(SvgFeature::ClipPath, "<clipPath"),
+		(SvgFeature::ClipPath, "clip-path=\""),
+		(SvgFeature::MeshGradient, "<meshgradient"),
+		(SvgFeature::MeshGradient, "<meshGradient"),
+	];
+	let mut features = patterns.into_iter().filter(|(_, pattern)| svg.contains(pattern)).map(|(feature, _)| feature).collect::<Vec<_>>();
+	features.dedup();
+	features
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn features_are_found_in_svg() {
+		let svg = r##"<g><clipPath id="a"><rect /></clipPath><path clip-path="url(#a)" filter="url(#b)" /></g>"##;
+		assert_eq!(svg_features(svg), vec![SvgFeature::Filter, SvgFeature::ClipPath]);
+		assert!(svg_features(r#"<path d="M0 0" fill="url(#gradient)" />"#).is_empty());
+	}
+
+	#[test]
+	fn profiles_are_nested() {
+		let features = [
+			SvgFeature::BlendMode,
+			SvgFeature::GroupOpacity,
+			SvgFeature::Isolation,
+			SvgFeature::Filter,
+			SvgFeature::Mask,
+			SvgFeature::ClipPath,
+			SvgFeature::MeshGradient,
+		];
+		for feature in features {
+			if SvgProfile::Svg11.supports(feature) || SvgProfile::NoFilters.supports(feature) {
+				assert!(SvgProfile::Browsers.supports(feature), "{feature:?}");
+			}
+			assert!(!SvgProfile::SvgTiny12.supports(feature), "{feature:?}");
+		}
+		assert!(!SvgProfile::NoFilters.supports(SvgFeature::Filter));
+	}
+}
@@ -0,0 +1,81 @@
+//! Cleaning up geometry which should be mirror-symmetric, such as a hand-drawn icon, by finding the points which mirror each other
+//! across an axis and moving them so they mirror each other exactly.
+
+use super::misc::FlipAxis;
+
+use glam::DVec2;
+
+/// How far a point may be from the mirror image of its counterpart to be considered symmetric with it, as a fraction of the larger side of the geometry's bounds.
+pub const SYMMETRY_TOLERANCE: f64 = 0.05;
+
+/// The reflection of the point across the vertical line at `axis` (when flipping along X) or the horizontal line at `axis` (when flipping along Y).
+pub fn mirror(point: DVec2, flip_axis: &FlipAxis, axis: f64) -> DVec2 {
+	match flip_axis {
+		FlipAxis::X => DVec2::new(2. * axis - point.x, point.y),
+		FlipAxis::Y => DVec2::new(point.x, 2. * axis - point.y),
+	}
+}
+
+/// Pairs up the points which approximately mirror each other across the axis, with the closest matches paired first, and moves each pair to be
+/// exactly symmetric by splitting the difference between them. A point closer than the tolerance to the axis may be its own counterpart and is
+/// moved onto the axis. Returns the new position of each point, or `None` for points left without a counterpart.
+pub fn symmetrize(points: &[DVec2], flip_axis: &FlipAxis, axis: f64, tolerance: f64) -> Vec<Option<DVec2>> {
+	let mut candidates = Vec::new();
+	for (index, &point) in points.iter().enumerate() {
+		for (other_index, &other) in points.iter().enumerate().skip(index) {
+			let distance = point.distance(mirror(other, flip_axis, axis));
+			if distance <= tolerance {
+				candidates.push((distance, index, other_index));
+			}
+		}
+	}
+	candidates.sort_by(|(a, _, _), (b, _, _)| a.total_cmp(b));
+
+	let mut symmetrized = vec![None; points.len()];
+	for (_, index, other_index) in candidates {
+		if symmetrized[index].is_some() || symmetrized[other_index].is_some() {
+			continue;
+		}
+
+		let position = (points[index] + mirror(points[other_index], flip_axis, axis)) / 2.;
+		symmetrized[index] = Some(position);
+		symmetrized[other_index] = Some(mirror(position, flip_axis, axis));
+	}
+
+	symmetrized
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn pairs_are_made_exactly_symmetric() {
+		let points = [DVec2::new(-10., 0.), DVec2::new(9., 1.), DVec2::new(-5., 20.), DVec2::new(5.5, 19.)];
+		let symmetrized = symmetrize(&points, &FlipAxis::X, 0., 2.);
+
+		assert_eq!(symmetrized[0], Some(DVec2::new(-9.5, 0.5)));
+		assert_eq!(symmetrized[1], Some(DVec2::new(9.5, 0.5)));
+		assert_eq!(symmetrized[2], Some(DVec2::new(-5.25, 19.5)));
+		assert_eq!(symmetrized[3], Some(DVec2::new(5.25, 19.5)));
+	}
+
+	#[test]
+	fn points_near_the_axis_are_moved_onto_it() {
+		let points = [DVec2::new(3., 10.5), DVec2::new(-40., 50.)];
+		let symmetrized = symmetrize(&points, &FlipAxis::Y, 10., 2.);
+
+		assert_eq!(symmetrized[0], Some(DVec2::new(3., 10.)));
+		assert_eq!(symmetrized[1], None, "a point with no counterpart is left alone");
+	}
+
+	#[test]
+	fn closest_matches_are_paired_first() {
+		let points = [DVec2::new(-10., 0.), DVec2::new(10., 0.), DVec2::new(11., 0.)];
+		let symmetrized = symmetrize(&points, &FlipAxis::X, 0., 2.);
+
+		assert_eq!(symmetrized[0], Some(DVec2::new(-10., 0.)));
+		assert_eq!(symmetrized[1], Some(DVec2::new(10., 0.)));
+		assert_eq!(symmetrized[2], None);
+	}
+}
@@ -0,0 +1,157 @@
+//! Tidying up roughly placed layers, such as a grid of cards or a row of icons, by detecting the rows and columns they were meant to be in
+//! and evening out the gaps between them.
+
+use glam::DVec2;
+
+/// Sorts the boxes into rows and columns and lays them out again with the median gap between neighboring rows and columns, keeping the top left
+/// corner of the whole arrangement in place. Each box is centered in its cell, which is as wide as the widest box in its column and as tall as the
+/// tallest box in its row. Returns the translation moving each box into place, or `None` if two boxes are meant to share a cell, since the boxes
+/// then aren't arranged in rows and columns.
+pub fn tidy(boxes: &[[DVec2; 2]]) -> Option<Vec<DVec2>> {
+	if boxes.len() < 2 {
+		return None;
+	}
+
+	let centers = boxes.iter().map(|[min, max]| (*min + *max) / 2.).collect::<Vec<_>>();
+	let sizes = boxes.iter().map(|[min, max]| *max - *min).collect::<Vec<_>>();
+	let columns = cluster(&centers.iter().map(|center| center.x).collect::<Vec<_>>(), &sizes.iter().map(|size| size.x).collect::<Vec<_>>());
+	let rows = cluster(&centers.iter().map(|center| center.y).collect::<Vec<_>>(), &sizes.iter().map(|size| size.y).collect::<Vec<_>>());
+	let column_count = columns.iter().max()? + 1;
+	let row_count = rows.iter().max()? + 1;
+
+	let mut occupied = vec![false; column_count * row_count];
+	for (&column, &row) in columns.iter().zip(&rows) {
+		let cell = &mut occupied[row * column_count + column];
+		if *cell {
+			return None;
+		}
+		*cell = true;
+	}
+
+	let column_widths = track_sizes(&columns, column_count, sizes.iter().map(|size| size.x));
+	let row_heights = track_sizes(&rows, row_count, sizes.iter().map(|size| size.y));
+	let column_gap = median_gap(boxes, &columns, column_count, |point| point.x);
+	let row_gap = median_gap(boxes, &rows, row_count, |point| point.y);
+
+	let origin = boxes.iter().map(|[min, _]| *min).reduce(DVec2::min)?;
+	let column_starts = track_starts(&column_widths, column_gap, origin.x);
+	let row_starts = track_starts(&row_heights, row_gap, origin.y);
+
+	let translations = boxes
+		.iter()
+		.enumerate()
+		.map(|(index, [min, _])| {
+			let (column, row) = (columns[index], rows[index]);
+			let cell_start = DVec2::new(column_starts[column], row_starts[row]);
+			let cell_size = DVec2::new(column_widths[column], row_heights[row]);
+			cell_start + (cell_size - sizes[index]) / 2. - *min
+		})
+		.collect();
+	Some(translations)
+}
+
+/// Groups the positions along one axis into rows or columns, numbered in order along the axis. A position joins the previous group if it is
+/// within half the median extent of the boxes from the last position in that group.
+fn cluster(positions: &[f64], extents: &[f64]) -> Vec<usize> {
+	let threshold = median(extents.to_vec()).unwrap_or_default() / 2.;
+
+	let mut order = (0..positions.len()).collect::<Vec<_>>();
+	order.sort_by(|&a, &b| positions[a].total_cmp(&positions[b]));
+
+	let mut groups = vec![0; positions.len()];
+	let mut group = 0;
+	for pair in order.windows(2) {
+		if positions[pair[1]] - positions[pair[0]] > threshold {
+			group += 1;
+		}
+		groups[pair[1]] = group;
+	}
+	groups
+}
+
+/// The largest extent of the boxes in each row or column.
+fn track_sizes(tracks: &[usize], count: usize, extents: impl Iterator<Item = f64>) -> Vec<f64> {
+	let mut sizes = vec![0_f64; count];
+	for (&track, extent) in tracks.iter().zip(extents) {
+		sizes[track] = sizes[track].max(extent);
+	}
+	sizes
+}
+
+/// Where each row or column starts, with the gap between each one and the next.
+fn track_starts(sizes: &[f64], gap: f64, origin: f64) -> Vec<f64> {
+	sizes
+		.iter()
+		.scan(origin, |start, size| {
+			let track_start = *start;
+			*start += size + gap;
+			Some(track_start)
+		})
+		.collect()
+}
+
+/// The median of the gaps between the far edge of each row or column and the near edge of the next, which is zero where they overlap.
+fn median_gap(boxes: &[[DVec2; 2]], tracks: &[usize], count: usize, axis: impl Fn(DVec2) -> f64) -> f64 {
+	let mut near_edges = vec![f64::INFINITY; count];
+	let mut far_edges = vec![f64::NEG_INFINITY; count];
+	for (&track, [min, max]) in tracks.iter().zip(boxes) {
+		near_edges[track] = near_edges[track].min(axis(*min));
+		far_edges[track] = far_edges[track].max(axis(*max));
+	}
+
+	let gaps = far_edges.iter().zip(near_edges.iter().skip(1)).map(|(far, next_near)| (next_near - far).max(0.)).collect();
+	median(gaps).unwrap_or_default()
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+	values.sort_by(f64::total_cmp);
+	let middle = values.len() / 2;
+	match values.len() {
+		0 => None,
+		length if length % 2 == 0 => Some((values[middle - 1] + values[middle]) / 2.),
+		_ => Some(values[middle]),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn square(x: f64, y: f64, size: f64) -> [DVec2; 2] {
+		[DVec2::new(x, y), DVec2::new(x + size, y + size)]
+	}
+
+	fn tidied(boxes: &[[DVec2; 2]]) -> Vec<DVec2> {
+		let translations = tidy(boxes).unwrap();
+		boxes.iter().zip(translations).map(|([min, _], translation)| *min + translation).collect()
+	}
+
+	#[test]
+	fn row_takes_the_median_gap() {
+		let boxes = [square(0., 0., 10.), square(22., 3., 10.), square(40., -2., 10.), square(65., 1., 10.)];
+		let positions = tidied(&boxes);
+		// The gaps were 12, 8, and 15, so the median is 12
+		assert_eq!(positions, vec![DVec2::new(0., -2.), DVec2::new(22., -2.), DVec2::new(44., -2.), DVec2::new(66., -2.)]);
+	}
+
+	#[test]
+	fn grid_lines_up_rows_and_columns() {
+		let boxes = [square(0., 0., 20.), square(31., 2., 20.), square(-3., 29., 20.), square(30., 32., 20.)];
+		let positions = tidied(&boxes);
+		// The columns were 10 apart and the rows 7 apart, measured between their outermost edges
+		assert_eq!(positions, vec![DVec2::new(-3., 0.), DVec2::new(27., 0.), DVec2::new(-3., 27.), DVec2::new(27., 27.)]);
+	}
+
+	#[test]
+	fn smaller_boxes_are_centered_in_their_cells() {
+		let boxes = [square(0., 0., 20.), square(30., 4., 10.)];
+		let positions = tidied(&boxes);
+		assert_eq!(positions, vec![DVec2::new(0., 0.), DVec2::new(30., 5.)]);
+	}
+
+	#[test]
+	fn overlapping_boxes_are_left_alone() {
+		assert!(tidy(&[square(0., 0., 20.), square(2., 1., 20.)]).is_none());
+		assert!(tidy(&[square(0., 0., 20.)]).is_none());
+	}
+}
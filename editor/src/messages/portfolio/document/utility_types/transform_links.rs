@@ -0,0 +1,136 @@
+//! Layers whose transforms are linked, so moving, rotating, or scaling one of them does the same to the others, without having to group
+//! them in a folder. The links are applied as the transforms of the layers are changed.
+
+use crate::messages::portfolio::document::node_graph::TransformIn;
+
+use document_legacy::LayerId;
+
+use glam::DAffine2;
+use serde::{Deserialize, Serialize};
+
+/// Groups of layers, each group's transforms being linked together. No layer is in more than one group, and each group has at least two layers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TransformLinks {
+	groups: Vec<Vec<Vec<LayerId>>>,
+}
+
+impl TransformLinks {
+	/// Links the layers together, along with the layers already linked to any of them.
+	pub fn link(&mut self, layers: impl IntoIterator<Item = Vec<LayerId>>) {
+		let mut group = Vec::new();
+		for layer in layers {
+			match self.groups.iter().position(|group| group.contains(&layer)) {
+				Some(index) => group.extend(self.groups.swap_remove(index)),
+				None if !group.contains(&layer) => group.push(layer),
+				None => {}
+			}
+		}
+
+		if group.len() > 1 {
+			self.groups.push(group);
+		}
+	}
+
+	/// Unlinks the layers from the layers they are linked to.
+	pub fn unlink(&mut self, layers: &[Vec<LayerId>]) {
+		for group in &mut self.groups {
+			group.retain(|layer| !layers.contains(layer));
+		}
+		self.groups.retain(|group| group.len() > 1);
+	}
+
+	/// Keeps the links of a layer after it's moved to another path, along with those of the layers inside it if it's a folder.
+	pub fn move_layer(&mut self, from: &[LayerId], to: &[LayerId]) {
+		for layer in self.groups.iter_mut().flatten() {
+			if layer.starts_with(from) {
+				*layer = [to, &layer[from.len()..]].concat();
+			}
+		}
+	}
+
+	/// Forgets the links of the layers which no longer exist.
+	pub fn retain(&mut self, exists: impl Fn(&[LayerId]) -> bool) {
+		for group in &mut self.groups {
+			group.retain(|layer| exists(layer));
+		}
+		self.groups.retain(|group| group.len() > 1);
+	}
+
+	/// The other layers linked to this one.
+	pub fn linked_to<'a>(&'a self, layer: &'a [LayerId]) -> impl Iterator<Item = &'a [LayerId]> {
+		let group = self.groups.iter().find(|group| group.iter().any(|linked| linked == layer));
+		group.into_iter().flatten().map(|linked| linked.as_slice()).filter(move |&linked| linked != layer)
+	}
+
+	pub fn is_linked(&self, layer: &[LayerId]) -> bool {
+		self.linked_to(layer).next().is_some()
+	}
+}
+
+/// The change in viewport space made to a layer by changing or setting its transform, given the transform from its parent folder to the
+/// viewport and its current transform in viewport space. Linked layers are given this same change, so they move as one.
+pub fn viewport_change(transform: DAffine2, transform_in: TransformIn, set: bool, parent_to_viewport: DAffine2, current: DAffine2) -> DAffine2 {
+	// The transform in viewport space which the change is made in
+	let space = match transform_in {
+		TransformIn::Local => parent_to_viewport,
+		TransformIn::Scope { scope } => scope.inverse(),
+		TransformIn::Viewport => DAffine2::IDENTITY,
+	};
+
+	if set {
+		space * transform * current.inverse()
+	} else {
+		space * transform * space.inverse()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	use glam::DVec2;
+
+	#[test]
+	fn linking_merges_groups() {
+		let mut links = TransformLinks::default();
+		links.link([vec![1], vec![2]]);
+		links.link([vec![3], vec![4]]);
+		links.link([vec![2], vec![3]]);
+		assert_eq!(links.linked_to(&[1]).count(), 3);
+
+		links.unlink(&[vec![1], vec![2], vec![3]]);
+		assert!(!links.is_linked(&[4]));
+		assert_eq!(links, TransformLinks::default());
+	}
+
+	#[test]
+	fn links_follow_moved_layers() {
+		let mut links = TransformLinks::default();
+		links.link([vec![1, 10], vec![2], vec![3]]);
+
+		// Moving a folder moves the layers inside it too
+		links.move_layer(&[1], &[4, 5]);
+		links.move_layer(&[2], &[6]);
+		assert!(!links.is_linked(&[1, 10]));
+		assert!(!links.is_linked(&[2]));
+		assert_eq!(links.linked_to(&[4, 5, 10]).collect::<Vec<_>>(), vec![&[6][..], &[3][..]]);
+	}
+
+	#[test]
+	fn changes_are_the_same_in_viewport_space() {
+		let parent_to_viewport = DAffine2::from_scale(DVec2::splat(2.));
+		let local = DAffine2::from_translation(DVec2::new(5., 0.));
+		let current = parent_to_viewport * local;
+		let translation = DAffine2::from_translation(DVec2::new(1., 0.));
+
+		// Moving one unit in the layer's parent space moves it two units in the viewport
+		let change = viewport_change(translation, TransformIn::Local, false, parent_to_viewport, current);
+		assert_eq!(change.translation, DVec2::new(2., 0.));
+
+		let change = viewport_change(translation * local, TransformIn::Local, true, parent_to_viewport, current);
+		assert_eq!(change.translation, DVec2::new(2., 0.));
+
+		let change = viewport_change(translation, TransformIn::Viewport, false, parent_to_viewport, current);
+		assert_eq!(change, translation);
+	}
+}
@@ -182,15 +182,18 @@ pub enum TransformOperation {
 }
 
 impl TransformOperation {
-	pub fn apply_transform_operation(&self, selected: &mut Selected, snapping: bool, axis_constraint: Axis) {
-		if self != &TransformOperation::None {
-			let transformation = match self {
-				TransformOperation::Grabbing(translation) => DAffine2::from_translation(translation.to_dvec()),
-				TransformOperation::Rotating(rotation) => DAffine2::from_angle(rotation.to_f64(snapping)),
-				TransformOperation::Scaling(scale) => DAffine2::from_scale(scale.to_dvec(snapping)),
-				TransformOperation::None => unreachable!(),
-			};
+	/// The change this operation makes in viewport space about the pivot, or `None` if there is no operation.
+	pub fn transformation(&self, snapping: bool) -> Option<DAffine2> {
+		match self {
+			TransformOperation::None => None,
+			TransformOperation::Grabbing(translation) => Some(DAffine2::from_translation(translation.to_dvec())),
+			TransformOperation::Rotating(rotation) => Some(DAffine2::from_angle(rotation.to_f64(snapping))),
+			TransformOperation::Scaling(scale) => Some(DAffine2::from_scale(scale.to_dvec(snapping))),
+		}
+	}
 
+	pub fn apply_transform_operation(&self, selected: &mut Selected, snapping: bool, axis_constraint: Axis) {
+		if let Some(transformation) = self.transformation(snapping) {
 			selected.update_transforms(transformation);
 			self.hints(snapping, axis_constraint, selected.responses);
 		}
@@ -530,6 +533,18 @@ impl Typing {
 		Some(result)
 	}
 
+	/// The number as it has been typed, including a trailing decimal point, or `None` if nothing has been typed.
+	pub fn text(&self) -> Option<String> {
+		if self.digits.is_empty() && !self.negative {
+			return None;
+		}
+
+		let sign = if self.negative { "-" } else { "" };
+		let decimal_separator = number_locale().decimal_separator();
+		let digits = self.digits.iter().map(|&digit| if digit == DECIMAL_POINT { decimal_separator } else { char::from(b'0' + digit) });
+		Some(sign.chars().chain(digits).collect())
+	}
+
 	pub fn clear(&mut self) {
 		self.digits.clear();
 		self.contains_decimal = false;
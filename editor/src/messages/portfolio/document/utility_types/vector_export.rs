@@ -0,0 +1,179 @@
+use document_legacy::document::Document as DocumentLegacy;
+use document_legacy::layers::layer_info::{Layer, LayerDataType};
+use document_legacy::LayerId;
+use graphene_core::uuid::ManipulatorGroupId;
+use graphene_core::vector::style::Fill;
+
+use bezier_rs::{Bezier, Subpath, TValue};
+use glam::{DAffine2, DVec2};
+
+/// The DXF layer name used for geometry whose Graphite layer (and every folder above it) is unnamed.
+pub const DEFAULT_LAYER_NAME: &str = "0";
+
+/// The geometry of one visible vector layer in document space, as needed by the fabrication exporters (DXF, plotter toolpaths).
+#[derive(Clone, Debug)]
+pub struct ExportedShape {
+	/// The name of the layer, or of the closest named folder containing it.
+	pub layer_name: String,
+	pub subpaths: Vec<Subpath<ManipulatorGroupId>>,
+	pub filled: bool,
+	/// Whether the fill completely hides whatever is beneath it, which requires full opacity of the fill and of the layer and its folders.
+	pub opaque: bool,
+	pub stroked: bool,
+}
+
+/// Collects the vector geometry of every layer included in exports from bottom to top in the stacking order.
+/// When `selected` is given, only those layers (and the contents of selected folders) are included.
+pub fn collect_shapes(document: &DocumentLegacy, selected: Option<&[Vec<LayerId>]>) -> Vec<ExportedShape> {
+	let mut shapes = Vec::new();
+	if let LayerDataType::Folder(folder) = &document.root.data {
+		for (&id, layer) in folder.layer_ids.iter().zip(folder.layers()) {
+			collect_layer_shapes(layer, &mut vec![id], DAffine2::IDENTITY, 1., None, selected, &mut shapes);
+		}
+	}
+	shapes
+}
+
+fn collect_layer_shapes(
+	layer: &Layer,
+	path: &mut Vec<LayerId>,
+	parent_transform: DAffine2,
+	parent_opacity: f64,
+	parent_name: Option<&str>,
+	selected: Option<&[Vec<LayerId>]>,
+	shapes: &mut Vec<ExportedShape>,
+) {
+	if !layer.is_visible(true) {
+		return;
+	}
+
+	let transform = parent_transform * layer.transform;
+	let opacity = parent_opacity * layer.opacity;
+	let name = layer.name.as_deref().filter(|name| !name.is_empty()).or(parent_name);
+
+	match &layer.data {
+		LayerDataType::Folder(folder) => {
+			for (&id, child) in folder.layer_ids.iter().zip(folder.layers()) {
+				path.push(id);
+				collect_layer_shapes(child, path, transform, opacity, name, selected, shapes);
+				path.pop();
+			}
+		}
+		LayerDataType::Layer(_) => {
+			let is_selected = selected.map_or(true, |selected| selected.iter().any(|selected_path| path.starts_with(selected_path)));
+			let Some(vector_data) = layer.as_vector_data().filter(|_| is_selected) else { return };
+
+			let subpaths = vector_data
+				.subpaths
+				.iter()
+				.map(|subpath| {
+					let mut subpath = subpath.clone();
+					subpath.apply_transform(transform);
+					subpath
+				})
+				.collect();
+
+			let opaque_fill = match vector_data.style.fill() {
+				Fill::None => false,
+				Fill::Solid(color) => color.a() >= 1.,
+				Fill::Gradient(gradient) => gradient.positions.iter().all(|(_, color)| color.map_or(false, |color| color.a() >= 1.)),
+			};
+
+			shapes.push(ExportedShape {
+				layer_name: name.unwrap_or(DEFAULT_LAYER_NAME).to_string(),
+				subpaths,
+				filled: !matches!(vector_data.style.fill(), Fill::None),
+				opaque: opaque_fill && opacity >= 1.,
+				stroked: vector_data.style.stroke().map_or(false, |stroke| stroke.color().is_some() && stroke.weight() > 0.),
+			});
+		}
+		// Shape layers are only used by the overlays, which aren't part of the artwork
+		LayerDataType::Shape(_) => {}
+	}
+}
+
+/// Approximates a bezier segment with line segments that stray no further than `tolerance` from the curve.
+/// Returns the points after the start of the segment, ending with its end point.
+pub fn flatten_bezier(bezier: &Bezier, tolerance: f64) -> Vec<DVec2> {
+	let points = bezier.get_points().collect::<Vec<_>>();
+	if points.len() <= 2 {
+		return vec![bezier.end()];
+	}
+
+	// The second differences of the control points bound how far the curve can deviate from a chord
+	let deviation = points.windows(3).map(|window| (window[0] - 2. * window[1] + window[2]).length()).fold(0., f64::max);
+	let steps = ((deviation * 0.75 / tolerance.max(f64::EPSILON)).sqrt().ceil() as usize).clamp(1, 1000);
+
+	(1..=steps).map(|step| bezier.evaluate(TValue::Parametric(step as f64 / steps as f64))).collect()
+}
+
+/// Approximates a subpath with a polyline, returning its points and whether it is closed.
+/// Closed subpaths don't repeat their first point at the end.
+pub fn flatten_subpath(subpath: &Subpath<ManipulatorGroupId>, tolerance: f64) -> (Vec<DVec2>, bool) {
+	let Some(first) = subpath.manipulator_groups().first() else { return (Vec::new(), false) };
+
+	let mut points = vec![first.anchor];
+	for bezier in subpath.iter() {
+		points.extend(flatten_bezier(&bezier, tolerance));
+	}
+
+	let closed = subpath.closed();
+	if closed && points.len() > 1 && points.first() == points.last() {
+		points.pop();
+	}
+
+	(points, closed)
+}
+
+/// The bounding box containing all the given shapes.
+pub fn shapes_bounding_box(shapes: &[ExportedShape]) -> Option<[DVec2; 2]> {
+	shapes
+		.iter()
+		.flat_map(|shape| shape.subpaths.iter())
+		.filter_map(|subpath| subpath.bounding_box())
+		.reduce(|a, b| [a[0].min(b[0]), a[1].max(b[1])])
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn flattening_stays_within_the_tolerance() {
+		let line = Bezier::from_linear_dvec2(DVec2::ZERO, DVec2::new(10., 0.));
+		assert_eq!(flatten_bezier(&line, 0.1), [DVec2::new(10., 0.)], "Straight segments aren't split");
+
+		let curve = Bezier::from_cubic_dvec2(DVec2::ZERO, DVec2::new(0., 100.), DVec2::new(100., 100.), DVec2::new(100., 0.));
+		for tolerance in [1., 0.1, 0.01] {
+			let points = flatten_bezier(&curve, tolerance);
+			assert_eq!(points.last(), Some(&curve.end()));
+
+			// The curve is checked halfway between each pair of points, where it is away from both of them
+			let steps = points.len();
+			let starts = std::iter::once(curve.start()).chain(points.iter().copied());
+			for (step, (start, end)) in starts.zip(points.iter().copied()).enumerate() {
+				let middle = curve.evaluate(TValue::Parametric((step as f64 + 0.5) / steps as f64));
+				let along = (middle - start).dot(end - start) / (end - start).length_squared();
+				let distance = middle.distance(start.lerp(end, along.clamp(0., 1.)));
+				assert!(distance <= tolerance, "The curve strays {distance} from its flattening at a tolerance of {tolerance}");
+			}
+		}
+		assert!(flatten_bezier(&curve, 0.01).len() > flatten_bezier(&curve, 1.).len());
+	}
+
+	#[test]
+	fn flattened_subpaths_only_repeat_their_start_if_open() {
+		let corners = [DVec2::ZERO, DVec2::new(10., 0.), DVec2::new(10., 10.)];
+
+		let (points, closed) = flatten_subpath(&Subpath::from_anchors(corners, false), 0.1);
+		assert!(!closed);
+		assert_eq!(points, corners);
+
+		let (points, closed) = flatten_subpath(&Subpath::from_anchors(corners, true), 0.1);
+		assert!(closed);
+		assert_eq!(points, corners, "The closing segment back to the start is implied");
+
+		let (points, closed) = flatten_subpath(&Subpath::new(Vec::new(), false), 0.1);
+		assert!(points.is_empty() && !closed);
+	}
+}
@@ -1,7 +1,16 @@
+use crate::consts::DEFAULT_RADIAL_ARRAY_COUNT;
 use crate::messages::input_mapper::utility_types::macros::action_keys;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
+use crate::messages::portfolio::document::utility_types::design_tokens::DesignTokenFormat;
+use crate::messages::portfolio::document::utility_types::isometric::IsometricPlane;
+use crate::messages::portfolio::document::utility_types::misc::FlipAxis;
+use crate::messages::portfolio::document::utility_types::placeholder::PLACEHOLDER_TEXT_LENGTHS;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::color_selector::ColorSlot;
+use crate::messages::tutorial::utility_types::Tutorial;
+
+use graphene_core::vector::width_profile::WidthPoint;
 
 #[derive(Debug, Clone, Default)]
 pub struct MenuBarMessageHandler {
@@ -70,13 +79,21 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
-					vec![MenuBarEntry {
-						label: "Save".into(),
-						shortcut: action_keys!(DocumentMessageDiscriminant::SaveDocument),
-						action: MenuBarEntry::create_action(|_| DocumentMessage::SaveDocument.into()),
-						disabled: no_active_document,
-						..MenuBarEntry::default()
-					}],
+					vec![
+						MenuBarEntry {
+							label: "Save".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::SaveDocument),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::SaveDocument.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Restore Backup…".into(),
+							action: MenuBarEntry::create_action(|_| DialogMessage::RequestBackupsDialog.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+					],
 					vec![
 						MenuBarEntry {
 							label: "Import…".into(),
@@ -91,6 +108,76 @@ impl LayoutHolder for MenuBarMessageHandler {
 							disabled: no_active_document,
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Repeat Last Export".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::RepeatLastExport),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::RepeatLastExport.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Export Sprite Sheet…".into(),
+							action: MenuBarEntry::create_action(|_| DialogMessage::RequestSpriteSheetDialog.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Export Design Tokens".into(),
+							action: MenuBarEntry::no_action(),
+							disabled: no_active_document,
+							children: MenuBarEntryChildren(vec![
+								vec![
+									MenuBarEntry {
+										label: "As JSON".into(),
+										action: MenuBarEntry::create_action(|_| DocumentMessage::ExportDesignTokens { format: DesignTokenFormat::Json }.into()),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "As YAML".into(),
+										action: MenuBarEntry::create_action(|_| DocumentMessage::ExportDesignTokens { format: DesignTokenFormat::Yaml }.into()),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+								],
+								vec![
+									MenuBarEntry {
+										label: "Watch for Changes as JSON".into(),
+										action: MenuBarEntry::create_action(|_| {
+											DocumentMessage::WatchDesignTokens {
+												format: Some(DesignTokenFormat::Json),
+											}
+											.into()
+										}),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "Watch for Changes as YAML".into(),
+										action: MenuBarEntry::create_action(|_| {
+											DocumentMessage::WatchDesignTokens {
+												format: Some(DesignTokenFormat::Yaml),
+											}
+											.into()
+										}),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "Stop Watching".into(),
+										action: MenuBarEntry::create_action(|_| DocumentMessage::WatchDesignTokens { format: None }.into()),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+								],
+							]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Batch Process…".into(),
+							action: MenuBarEntry::create_action(|_| DialogMessage::RequestBatchDialog.into()),
+							..MenuBarEntry::default()
+						},
 					],
 					vec![MenuBarEntry {
 						label: "Preferences…".into(),
@@ -137,11 +224,37 @@ impl LayoutHolder for MenuBarMessageHandler {
 							disabled: no_active_document,
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Copy as PNG".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::CopySelectedLayersAsPng { scale_factor: 1. }.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Copy as PNG at 2x".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::CopySelectedLayersAsPng { scale_factor: 2. }.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
 						MenuBarEntry {
 							label: "Paste".into(),
 							icon: Some("Paste".into()),
-							shortcut: action_keys!(FrontendMessageDiscriminant::TriggerPaste),
-							action: MenuBarEntry::create_action(|_| FrontendMessage::TriggerPaste.into()),
+							shortcut: action_keys!(DocumentMessageDiscriminant::PasteInPlace),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::PasteInPlace.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Paste Into".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::PasteInto),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::PasteInto.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Paste at Pointer".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::PasteAtPointer),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::PasteAtPointer.into()),
 							disabled: no_active_document,
 							..MenuBarEntry::default()
 						},
@@ -168,14 +281,37 @@ impl LayoutHolder for MenuBarMessageHandler {
 							..MenuBarEntry::default()
 						},
 					],
-					vec![MenuBarEntry {
-						label: "Delete Selected".into(),
-						icon: Some("Trash".into()),
-						shortcut: action_keys!(DocumentMessageDiscriminant::DeleteSelectedLayers),
-						action: MenuBarEntry::create_action(|_| DocumentMessage::DeleteSelectedLayers.into()),
-						disabled: no_active_document,
-						..MenuBarEntry::default()
-					}],
+					vec![
+						MenuBarEntry {
+							label: "Duplicate Selected".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::DuplicateSelectedLayers),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::DuplicateSelectedLayers.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Duplicate Selected with Offset".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::DuplicateSelectedLayersWithOffset),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::DuplicateSelectedLayersWithOffset.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Transform Again".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::TransformSelectedLayersAgain),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::TransformSelectedLayersAgain.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Delete Selected".into(),
+							icon: Some("Trash".into()),
+							shortcut: action_keys!(DocumentMessageDiscriminant::DeleteSelectedLayers),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::DeleteSelectedLayers.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+					],
 					vec![
 						MenuBarEntry {
 							label: "Grab Selected".into(),
@@ -198,6 +334,261 @@ impl LayoutHolder for MenuBarMessageHandler {
 							disabled: no_active_document,
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Toggle Aspect Ratio Lock".into(),
+							icon: Some("Link".into()),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::ToggleSelectedLayersAspectLock.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Link Transforms".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::LinkSelectedLayerTransforms.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Unlink Transforms".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::UnlinkSelectedLayerTransforms.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Join Paths".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::JoinSelectedPaths.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Close Paths".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::CloseSelectedPaths.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Use as Geometry Source".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::SetGeometrySource.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Replace Geometry".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::ReplaceSelectedLayersGeometry.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Radial Array".into(),
+							action: MenuBarEntry::create_action(|_| {
+								DocumentMessage::RadialArraySelectedLayers {
+									count: DEFAULT_RADIAL_ARRAY_COUNT,
+									angle: 360.,
+								}
+								.into()
+							}),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Taper Stroke".into(),
+							action: MenuBarEntry::no_action(),
+							disabled: no_active_document,
+							children: MenuBarEntryChildren(vec![
+								vec![
+									MenuBarEntry {
+										label: "Both Ends".into(),
+										action: MenuBarEntry::create_action(|_| {
+											let profile = vec![WidthPoint::new(0., 0.), WidthPoint::new(0.5, 1.), WidthPoint::new(1., 0.)];
+											DocumentMessage::TaperSelectedStrokes { profile }.into()
+										}),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "Start".into(),
+										action: MenuBarEntry::create_action(|_| {
+											let profile = vec![WidthPoint::new(0., 0.), WidthPoint::new(1., 1.)];
+											DocumentMessage::TaperSelectedStrokes { profile }.into()
+										}),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "End".into(),
+										action: MenuBarEntry::create_action(|_| {
+											let profile = vec![WidthPoint::new(0., 1.), WidthPoint::new(1., 0.)];
+											DocumentMessage::TaperSelectedStrokes { profile }.into()
+										}),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+								],
+								vec![MenuBarEntry {
+									label: "Constant Width".into(),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::TaperSelectedStrokes { profile: Vec::new() }.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								}],
+							]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Symmetrize".into(),
+							action: MenuBarEntry::no_action(),
+							disabled: no_active_document,
+							children: MenuBarEntryChildren(vec![vec![
+								MenuBarEntry {
+									label: "Across Vertical Axis".into(),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::SymmetrizeSelectedLayers { flip_axis: FlipAxis::X }.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								},
+								MenuBarEntry {
+									label: "Across Horizontal Axis".into(),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::SymmetrizeSelectedLayers { flip_axis: FlipAxis::Y }.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								},
+							]]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Tidy Up".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::TidySelectedLayers),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::TidySelectedLayers.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Isometric".into(),
+							action: MenuBarEntry::no_action(),
+							disabled: no_active_document,
+							children: MenuBarEntryChildren(vec![
+								vec![
+									MenuBarEntry {
+										label: "Project onto Top Plane".into(),
+										action: MenuBarEntry::create_action(|_| DocumentMessage::ProjectSelectedLayersIsometric { plane: IsometricPlane::Top }.into()),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "Project onto Left Plane".into(),
+										action: MenuBarEntry::create_action(|_| DocumentMessage::ProjectSelectedLayersIsometric { plane: IsometricPlane::Left }.into()),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+									MenuBarEntry {
+										label: "Project onto Right Plane".into(),
+										action: MenuBarEntry::create_action(|_| DocumentMessage::ProjectSelectedLayersIsometric { plane: IsometricPlane::Right }.into()),
+										disabled: no_active_document,
+										..MenuBarEntry::default()
+									},
+								],
+								vec![MenuBarEntry {
+									label: "Extrude to Isometric Box".into(),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::ExtrudeSelectedLayersToIsometricBox.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								}],
+							]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Flatten Effects".into(),
+							action: MenuBarEntry::no_action(),
+							disabled: no_active_document,
+							children: MenuBarEntryChildren(vec![vec![
+								MenuBarEntry {
+									label: "Flatten to Image at 1x".into(),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::FlattenSelectedLayerEffects { scale_factor: 1. }.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								},
+								MenuBarEntry {
+									label: "Flatten to Image at 2x".into(),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::FlattenSelectedLayerEffects { scale_factor: 2. }.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								},
+								MenuBarEntry {
+									label: "Flatten to Image at 4x".into(),
+									action: MenuBarEntry::create_action(|_| DocumentMessage::FlattenSelectedLayerEffects { scale_factor: 4. }.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								},
+							]]),
+							..MenuBarEntry::default()
+						},
+					],
+					vec![
+						MenuBarEntry {
+							label: "Apply Primary Color to Fill".into(),
+							action: MenuBarEntry::create_action(|_| ToolMessage::ApplyPrimaryColorToSelection { slot: ColorSlot::Fill }.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Apply Primary Color to Stroke".into(),
+							action: MenuBarEntry::create_action(|_| ToolMessage::ApplyPrimaryColorToSelection { slot: ColorSlot::Stroke }.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Swap Fill and Stroke".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::SwapSelectedFillAndStroke),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::SwapSelectedFillAndStroke.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Remove Fill".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::ClearSelectedFill),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::ClearSelectedFill.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Remove Stroke".into(),
+							shortcut: action_keys!(DocumentMessageDiscriminant::ClearSelectedStroke),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::ClearSelectedStroke.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Hatch Fill…".into(),
+							action: MenuBarEntry::create_action(|_| DialogMessage::RequestHatchFillDialog.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Save Style as Shape Preset".into(),
+							action: MenuBarEntry::create_action(|_| DocumentMessage::SaveSelectionAsShapePreset.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
+					],
+					vec![
+						MenuBarEntry {
+							label: "Placeholder Text".into(),
+							action: MenuBarEntry::no_action(),
+							disabled: no_active_document,
+							children: MenuBarEntryChildren(vec![PLACEHOLDER_TEXT_LENGTHS
+								.into_iter()
+								.map(|(label, words)| MenuBarEntry {
+									label: label.into(),
+									action: MenuBarEntry::create_action(move |_| DocumentMessage::InsertPlaceholderText { words }.into()),
+									disabled: no_active_document,
+									..MenuBarEntry::default()
+								})
+								.collect()]),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Replace Fonts…".into(),
+							action: MenuBarEntry::create_action(|_| DialogMessage::RequestReplaceFontsDialog.into()),
+							disabled: no_active_document,
+							..MenuBarEntry::default()
+						},
 					],
 					vec![MenuBarEntry {
 						label: "Order".into(),
@@ -240,12 +631,26 @@ impl LayoutHolder for MenuBarMessageHandler {
 			MenuBarEntry::new_root(
 				"Document".into(),
 				no_active_document,
-				MenuBarEntryChildren(vec![vec![MenuBarEntry {
-					label: "Clear Artboards".into(),
-					action: MenuBarEntry::create_action(|_| ArtboardMessage::ClearArtboards.into()),
-					disabled: no_active_document,
-					..MenuBarEntry::default()
-				}]]),
+				MenuBarEntryChildren(vec![
+					vec![MenuBarEntry {
+						label: "Clear Artboards".into(),
+						action: MenuBarEntry::create_action(|_| ArtboardMessage::ClearArtboards.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
+					vec![MenuBarEntry {
+						label: "Data Merge…".into(),
+						action: MenuBarEntry::create_action(|_| DialogMessage::RequestDataMergeDialog.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
+					vec![MenuBarEntry {
+						label: "Repair Document".into(),
+						action: MenuBarEntry::create_action(|_| DocumentMessage::RepairDocument.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					}],
+				]),
 			),
 			MenuBarEntry::new_root(
 				"View".into(),
@@ -279,6 +684,40 @@ impl LayoutHolder for MenuBarMessageHandler {
 						disabled: no_active_document,
 						..MenuBarEntry::default()
 					},
+					MenuBarEntry {
+						label: "Previous Zoom".into(),
+						shortcut: action_keys!(NavigationMessageDiscriminant::ZoomToPreviousView),
+						action: MenuBarEntry::create_action(|_| NavigationMessage::ZoomToPreviousView.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					},
+					MenuBarEntry {
+						label: "Reset Rotation".into(),
+						action: MenuBarEntry::create_action(|_| NavigationMessage::ResetCanvasRotation.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					},
+					MenuBarEntry {
+						label: "Flip Canvas Horizontally".into(),
+						shortcut: action_keys!(NavigationMessageDiscriminant::ToggleCanvasFlip),
+						action: MenuBarEntry::create_action(|_| NavigationMessage::ToggleCanvasFlip.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					},
+					MenuBarEntry {
+						label: "Preview CMYK Colors".into(),
+						shortcut: action_keys!(DocumentMessageDiscriminant::ToggleCmykPreview),
+						action: MenuBarEntry::create_action(|_| DocumentMessage::ToggleCmykPreview.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					},
+					MenuBarEntry {
+						label: "Present".into(),
+						shortcut: action_keys!(PresentationMessageDiscriminant::Enter),
+						action: MenuBarEntry::create_action(|_| PresentationMessage::Enter.into()),
+						disabled: no_active_document,
+						..MenuBarEntry::default()
+					},
 				]]),
 			),
 			MenuBarEntry::new_root(
@@ -291,6 +730,23 @@ impl LayoutHolder for MenuBarMessageHandler {
 						action: MenuBarEntry::create_action(|_| DialogMessage::RequestAboutGraphiteDialog.into()),
 						..MenuBarEntry::default()
 					}],
+					vec![MenuBarEntry {
+						label: "Interactive Tutorial".into(),
+						action: MenuBarEntry::create_action(|_| TutorialMessage::Start { tutorial: Tutorial::Basics }.into()),
+						..MenuBarEntry::default()
+					}],
+					vec![
+						MenuBarEntry {
+							label: "Memory Usage…".into(),
+							action: MenuBarEntry::create_action(|_| DialogMessage::RequestMemoryReportDialog.into()),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Usage Statistics…".into(),
+							action: MenuBarEntry::create_action(|_| DialogMessage::RequestUsageStatisticsDialog.into()),
+							..MenuBarEntry::default()
+						},
+					],
 					vec![
 						MenuBarEntry {
 							label: "Report a Bug".into(),
@@ -347,6 +803,18 @@ impl LayoutHolder for MenuBarMessageHandler {
 							action: MenuBarEntry::create_action(|_| DebugMessage::ToggleTraceLogs.into()),
 							..MenuBarEntry::default()
 						},
+						MenuBarEntry {
+							label: "Debug: Start/Stop Recording Performance".into(),
+							shortcut: action_keys!(DebugMessageDiscriminant::ToggleDiagnostics),
+							action: MenuBarEntry::create_action(|_| DebugMessage::ToggleDiagnostics.into()),
+							..MenuBarEntry::default()
+						},
+						MenuBarEntry {
+							label: "Debug: Copy Performance Report".into(),
+							shortcut: action_keys!(DebugMessageDiscriminant::ReportDiagnostics),
+							action: MenuBarEntry::create_action(|_| DebugMessage::ReportDiagnostics.into()),
+							..MenuBarEntry::default()
+						},
 						MenuBarEntry {
 							label: "Debug: Print Document".into(),
 							shortcut: action_keys!(DocumentMessageDiscriminant::DebugPrintDocument),
@@ -1,8 +1,10 @@
 mod portfolio_message;
 mod portfolio_message_handler;
 
+pub mod batch;
 pub mod document;
 pub mod menu_bar;
+pub mod render_workers;
 pub mod utility_types;
 
 #[doc(inline)]
@@ -1,4 +1,6 @@
-use crate::messages::portfolio::document::utility_types::clipboards::Clipboard;
+use crate::messages::portfolio::batch::{BatchSource, BatchStep};
+use crate::messages::portfolio::document::utility_types::clipboards::{Clipboard, PasteMode};
+use crate::messages::portfolio::document::utility_types::find_replace::TextQuery;
 use crate::messages::prelude::*;
 
 use document_legacy::LayerId;
@@ -37,6 +39,12 @@ pub enum PortfolioMessage {
 	CloseDocumentWithConfirmation {
 		document_id: u64,
 	},
+	/// Compares a document recovered from its auto-save with the version it was last saved as, letting the user choose which to keep if they differ.
+	CompareWithSavedVersion {
+		document_id: u64,
+		saved_at: String,
+		saved_serialized_content: String,
+	},
 	Copy {
 		clipboard: Clipboard,
 	},
@@ -47,6 +55,15 @@ pub enum PortfolioMessage {
 		document_id: u64,
 	},
 	DestroyAllDocuments,
+	/// A document source given at launch couldn't be read, for the reason given by the platform.
+	FetchDocumentFailed {
+		source: String,
+		error: String,
+	},
+	/// Searches the text layers of every open document, sending the results to the frontend.
+	FindText {
+		query: TextQuery,
+	},
 	FontLoaded {
 		font_family: String,
 		font_style: String,
@@ -66,10 +83,21 @@ pub enum PortfolioMessage {
 		font: Font,
 		is_default: bool,
 	},
+	/// Applies the preference for how deep folders may be nested to every open document.
+	MaxFolderDepthPreference,
 	NewDocumentWithName {
 		name: String,
 	},
 	NextDocument,
+	/// Sent once the output of evaluating the node graph of a layer has been applied to its document, or the evaluation failed.
+	NodeGraphEvaluated {
+		document_id: u64,
+		layer_path: Vec<LayerId>,
+	},
+	OpenDesignFile {
+		name: String,
+		content: String,
+	},
 	OpenDocument,
 	OpenDocumentFile {
 		document_name: String,
@@ -82,6 +110,22 @@ pub enum PortfolioMessage {
 		document_is_saved: bool,
 		document_serialized_content: String,
 	},
+	/// Opens the content read from a file path or URL, as a Graphite document, an SVG, or a design file depending on its extension.
+	OpenDocumentSource {
+		source: String,
+		content: String,
+	},
+	/// Opens the file paths (on native) or URLs (on the web) that the editor was launched with, such as by "Open with Graphite" or a shared link.
+	OpenLaunchDocuments {
+		sources: Vec<String>,
+	},
+	OpenSvgDocument {
+		name: String,
+		content: String,
+	},
+	OverrideDocumentLock {
+		document_id: u64,
+	},
 	// TODO: Paste message is unused, delete it?
 	Paste {
 		clipboard: Clipboard,
@@ -93,20 +137,46 @@ pub enum PortfolioMessage {
 	},
 	PasteSerializedData {
 		data: String,
+		mode: PasteMode,
+	},
+	PerformIdleWork {
+		time_remaining: f64,
 	},
 	PrevDocument,
+	/// Applies the steps to each of the documents in turn, reporting the progress and the documents which couldn't be processed.
+	ProcessBatch {
+		sources: Vec<BatchSource>,
+		steps: Vec<BatchStep>,
+	},
+	/// Closes the document of the batch which has had its steps applied, and opens the next one.
+	ProcessNextBatchDocument,
+	PurgeCachesAndHistory,
 	RenderGraphUsingRasterizedRegionBelowLayer {
 		document_id: u64,
 		layer_path: Vec<LayerId>,
 		input_image_data: Vec<u8>,
 		size: (u32, u32),
 	},
+	/// Replaces every match of the query in the text layers of every open document, as one undo step in each document.
+	ReplaceAllText {
+		query: TextQuery,
+		replacement: String,
+	},
 	SelectDocument {
 		document_id: u64,
 	},
+	/// Switches to the document containing a text match, selecting its layer and moving the viewport to show it.
+	SelectTextMatch {
+		document_id: u64,
+		layer_path: Vec<LayerId>,
+	},
 	SetActiveDocument {
 		document_id: u64,
 	},
+	SetDocumentLockStatus {
+		document_id: u64,
+		locked_elsewhere: bool,
+	},
 	SetImageBlobUrl {
 		document_id: u64,
 		layer_path: Vec<LayerId>,
@@ -1,22 +1,31 @@
+use super::batch::{Batch, BatchResult, BatchSource};
+use super::render_workers::RenderOutput;
 use super::utility_types::PersistentData;
 use crate::application::generate_uuid;
-use crate::consts::{DEFAULT_DOCUMENT_NAME, GRAPHITE_DOCUMENT_VERSION};
+use crate::consts::{DEFAULT_DOCUMENT_NAME, GRAPHITE_DOCUMENT_VERSION, IDLE_INPUT_QUIET_PERIOD};
 use crate::messages::dialog::simple_dialogs;
 use crate::messages::frontend::utility_types::FrontendDocumentDetails;
 use crate::messages::layout::utility_types::widget_prelude::*;
-use crate::messages::portfolio::document::utility_types::clipboards::{Clipboard, CopyBufferEntry, INTERNAL_CLIPBOARD_COUNT};
+use crate::messages::portfolio::document::node_graph::{new_text_network, TransformIn};
+use crate::messages::portfolio::document::utility_types::clipboards::{Clipboard, CopyBufferEntry, PasteMode, INTERNAL_CLIPBOARD_COUNT};
+use crate::messages::portfolio::document::utility_types::design_import::{import_design_file, ImportedContent, ImportedLayer};
+use crate::messages::portfolio::document::utility_types::find_replace::{document_text_matches, replace_matches, text_layers};
+use crate::messages::portfolio::document::utility_types::memory_report::MemoryReport;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::graph_modification_utils;
 use crate::messages::tool::utility_types::{HintData, HintGroup};
 use crate::node_graph_executor::NodeGraphExecutor;
 
 use document_legacy::layers::layer_info::LayerDataType;
-use document_legacy::layers::style::RenderData;
-use document_legacy::Operation as DocumentOperation;
+use document_legacy::layers::style::{RenderData, ViewMode};
+use document_legacy::svg_import::import_svg;
+use document_legacy::{DocumentError, LayerId, Operation as DocumentOperation};
 use graph_craft::document::value::TaggedValue;
 use graph_craft::document::{NodeId, NodeInput};
 use graphene_core::text::Font;
+use graphene_core::vector::style::{Fill, Stroke};
 
-use glam::DAffine2;
+use glam::{DAffine2, DVec2, IVec2};
 use std::sync::Arc;
 
 #[derive(Debug, Default)]
@@ -28,6 +37,7 @@ pub struct PortfolioMessageHandler {
 	active_document_id: Option<u64>,
 	copy_buffer: [Vec<CopyBufferEntry>; INTERNAL_CLIPBOARD_COUNT as usize],
 	pub persistent_data: PersistentData,
+	batch: Option<Batch>,
 }
 
 impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &PreferencesMessageHandler)> for PortfolioMessageHandler {
@@ -71,6 +81,7 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 					details: FrontendDocumentDetails {
 						is_auto_saved: document.is_auto_saved(),
 						is_saved: document.is_saved(),
+						is_read_only: document.read_only,
 						id: document_id,
 						name: document.name.clone(),
 					},
@@ -97,12 +108,15 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 
 				for document_id in &self.document_ids {
 					responses.add(FrontendMessage::TriggerIndexedDbRemoveDocument { document_id: *document_id });
+					responses.add(FrontendMessage::TriggerReleaseDocumentLock { document_id: *document_id });
 				}
 
 				responses.add(PortfolioMessage::DestroyAllDocuments);
 				responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 			}
 			PortfolioMessage::CloseDocument { document_id } => {
+				self.exit_presentation(document_id, responses);
+
 				// Is this the last document?
 				if self.documents.len() == 1 && self.document_ids[0] == document_id {
 					// Clear UI layouts that assume the existence of a document
@@ -112,6 +126,9 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 					responses.add(FrontendMessage::UpdateInputHints { hint_data });
 				}
 
+				// Let other windows save this document now that it's no longer open here
+				responses.add(FrontendMessage::TriggerReleaseDocumentLock { document_id });
+
 				// Actually delete the document (delay to delete document is required to let the document and properties panel messages above get processed)
 				responses.add(PortfolioMessage::DeleteDocument { document_id });
 				responses.add(FrontendMessage::TriggerIndexedDbRemoveDocument { document_id });
@@ -144,14 +161,58 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 					responses.add(PortfolioMessage::SelectDocument { document_id });
 				}
 			}
+			PortfolioMessage::CompareWithSavedVersion {
+				document_id,
+				saved_at,
+				saved_serialized_content,
+			} => {
+				let Some(document) = self.documents.get_mut(&document_id) else { return };
+				// The layers still waiting to be attached would otherwise show up as removed since the save
+				document.finish_loading(responses);
+				let saved = match DocumentMessageHandler::deserialize_document(&saved_serialized_content) {
+					Ok(saved) => saved,
+					Err(error) => {
+						warn!("The saved version of \"{}\" couldn't be read to compare it with the recovered one: {error:?}", document.name);
+						return;
+					}
+				};
+
+				let diff = saved.document_legacy.diff(&document.document_legacy);
+				if diff.is_empty() {
+					// Nothing changed since the save after all, such as when only the view was moved
+					document.set_save_state(true);
+					responses.add(PortfolioMessage::UpdateOpenDocumentsList);
+					return;
+				}
+
+				let dialog = simple_dialogs::AutoSaveRecoveryDialog {
+					document_id,
+					document_name: document.name.clone(),
+					file_name: document.file_name(),
+					saved_at,
+					diff,
+					saved_document: saved_serialized_content,
+				};
+				dialog.send_layout(responses, LayoutTarget::DialogDetails);
+				responses.add(FrontendMessage::DisplayDialog { icon: "File".to_string() });
+			}
 			PortfolioMessage::Copy { clipboard } => {
+				// Copying a folder copies everything inside it, so the whole document needs to be loaded first
+				if let Some(active_document) = self.active_document_id.and_then(|id| self.documents.get_mut(&id)) {
+					active_document.finish_loading(responses);
+				}
+
 				// We can't use `self.active_document()` because it counts as an immutable borrow of the entirety of `self`
 				if let Some(active_document) = self.active_document_id.and_then(|id| self.documents.get(&id)) {
 					let copy_val = |buffer: &mut Vec<CopyBufferEntry>| {
 						for layer_path in active_document.selected_layers_without_children() {
 							match (active_document.document_legacy.layer(layer_path).map(|t| t.clone()), *active_document.layer_metadata(layer_path)) {
 								(Ok(layer), layer_metadata) => {
-									buffer.push(CopyBufferEntry { layer, layer_metadata });
+									buffer.push(CopyBufferEntry {
+										layer,
+										layer_metadata,
+										source: Some(layer_path.to_vec()),
+									});
 								}
 								(Err(e), _) => warn!("Could not access selected layer {:?}: {:?}", layer_path, e),
 							}
@@ -202,6 +263,43 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 				self.active_document_id = None;
 				responses.add(MenuBarMessage::SendLayout);
 			}
+			PortfolioMessage::FetchDocumentFailed { source, error } => {
+				if let Some(batch) = self.batch.as_mut() {
+					if batch.finish_fetching(&source) {
+						batch.fail(source, error);
+						responses.add(PortfolioMessage::ProcessNextBatchDocument);
+						return;
+					}
+				}
+
+				warn!("Failed to read the document \"{source}\": {error}");
+				responses.add(DialogMessage::DisplayDialogError {
+					title: "Failed to open document".to_string(),
+					description: format!("\"{source}\" couldn't be read:\n\n{error}"),
+				});
+			}
+			PortfolioMessage::FindText { query } => {
+				// Text inside the folders of documents still being loaded is searched too
+				if !query.pattern.is_empty() {
+					self.documents.values_mut().for_each(|document| document.finish_loading(responses));
+				}
+
+				let result = match query.pattern.is_empty() {
+					true => Ok(Vec::new()),
+					false => query.matcher().map(|matcher| {
+						self.document_ids
+							.iter()
+							.filter_map(|document_id| self.documents.get(document_id).map(|document| (*document_id, document)))
+							.flat_map(|(document_id, document)| document_text_matches(document_id, &document.name, &document.document_legacy, &matcher))
+							.collect()
+					}),
+				};
+				let (matches, error) = match result {
+					Ok(matches) => (matches, None),
+					Err(error) => (Vec::new(), Some(error)),
+				};
+				responses.add(FrontendMessage::UpdateTextSearchResults { query, matches, error });
+			}
 			PortfolioMessage::FontLoaded {
 				font_family,
 				font_style,
@@ -219,6 +317,7 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 
 				self.persistent_data.font_cache.insert(font, preview_url, data, is_default);
 				self.executor.update_font_cache(self.persistent_data.font_cache.clone());
+				self.persistent_data.render_workers.update_font_cache(self.persistent_data.font_cache.clone());
 			}
 			PortfolioMessage::ImaginateCheckServerStatus => {
 				let server_status = self.persistent_data.imaginate.server_status().clone();
@@ -266,8 +365,13 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 					responses.add_front(FrontendMessage::TriggerFontLoad { font, is_default });
 				}
 			}
+			PortfolioMessage::MaxFolderDepthPreference => {
+				for document in self.documents.values_mut() {
+					document.document_legacy.max_folder_depth = preferences.max_folder_depth as usize;
+				}
+			}
 			PortfolioMessage::NewDocumentWithName { name } => {
-				let new_document = DocumentMessageHandler::with_name(name, ipp);
+				let new_document = DocumentMessageHandler::with_name(name, ipp, preferences);
 				let document_id = generate_uuid();
 				if self.active_document().is_some() {
 					responses.add(BroadcastEvent::ToolAbort);
@@ -285,6 +389,54 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 					responses.add(PortfolioMessage::SelectDocument { document_id: next_id });
 				}
 			}
+			PortfolioMessage::NodeGraphEvaluated { document_id, layer_path } => {
+				if self.batch.as_mut().map_or(false, |batch| batch.finish_evaluation(document_id, &layer_path)) {
+					self.apply_batch_steps(document_id, responses);
+				}
+			}
+			PortfolioMessage::OpenDesignFile { name, content } => {
+				let import = match import_design_file(&content) {
+					Ok(import) => import,
+					Err(description) => {
+						responses.add(DialogMessage::DisplayDialogError {
+							title: "Failed to open design file".to_string(),
+							description,
+						});
+						return;
+					}
+				};
+
+				let name = name.strip_suffix(".json").unwrap_or(&name).to_string();
+				responses.add(PortfolioMessage::NewDocumentWithName { name });
+
+				for artboard in import.artboards {
+					let id = generate_uuid();
+					responses.add(ArtboardMessage::AddArtboard {
+						id: Some(id),
+						position: artboard.position.into(),
+						size: artboard.size.into(),
+					});
+					responses.add(GraphOperationMessage::NewArtboard {
+						id,
+						artboard: graphene_core::Artboard::new(artboard.position.round().as_ivec2(), artboard.size.round().as_ivec2()),
+					});
+				}
+				insert_imported_layers(import.layers, &[], responses);
+				responses.add(DocumentMessage::DeselectAllLayers);
+				responses.add(DocumentMessage::ZoomCanvasToFitAll);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(NodeGraphMessage::UpdateNewNodeGraph);
+
+				if !import.warnings.is_empty() {
+					for warning in &import.warnings {
+						warn!("Design file import: {warning}");
+					}
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Some content wasn't imported".to_string(),
+						description: import.warnings.join("\n"),
+					});
+				}
+			}
 			PortfolioMessage::OpenDocument => {
 				// This portfolio message wraps the frontend message so it can be listed as an action, which isn't possible for frontend messages
 				responses.add(FrontendMessage::TriggerOpenDocument);
@@ -310,9 +462,34 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 			} => {
 				let document = DocumentMessageHandler::with_name_and_content(document_name, document_serialized_content);
 				match document {
-					Ok(mut document) => {
+					Ok((mut document, dropped_layers)) => {
 						document.set_auto_save_state(document_is_auto_saved);
 						document.set_save_state(document_is_saved);
+
+						// The recovered document no longer matches the file it was read from, so it starts out unsaved
+						if !dropped_layers.is_empty() {
+							document.set_save_state(false);
+
+							let layers = dropped_layers.iter().map(|layer| layer.to_string()).collect::<Vec<_>>().join("\n");
+							responses.add(DialogMessage::DisplayDialogError {
+								title: "Some layers couldn't be opened".to_string(),
+								description: format!(
+									"\"{}\" was opened without {} layer(s) that couldn't be read, which may be damaged:\n\n{layers}",
+									document.name,
+									dropped_layers.len()
+								),
+							});
+						}
+
+						// Changes recovered from an auto-save are compared with the last save, so the user can choose which to keep
+						if document_is_auto_saved && !document_is_saved {
+							responses.add(FrontendMessage::TriggerLoadSavedVersion {
+								document_id,
+								file_name: document.file_name(),
+							});
+						}
+
+						document.load_progressively(document_id, responses);
 						self.load_document(document, document_id, responses);
 					}
 					Err(e) => {
@@ -326,6 +503,97 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 					}
 				}
 			}
+			PortfolioMessage::OpenDocumentSource { source, content } => {
+				let name = document_name_from_source(&source);
+				if self.batch.as_mut().map_or(false, |batch| batch.finish_fetching(&source)) {
+					self.open_batch_document(name, content, responses);
+					return;
+				}
+
+				let extension = name.rsplit_once('.').map(|(_, extension)| extension.to_lowercase()).unwrap_or_default();
+
+				match extension.as_str() {
+					"svg" => responses.add(PortfolioMessage::OpenSvgDocument { name, content }),
+					"json" => responses.add(PortfolioMessage::OpenDesignFile { name, content }),
+					_ => responses.add(PortfolioMessage::OpenDocumentFile {
+						document_name: name,
+						document_serialized_content: content,
+					}),
+				}
+			}
+			PortfolioMessage::OpenLaunchDocuments { sources } => {
+				for source in sources {
+					let source = source.trim();
+					if !source.is_empty() {
+						responses.add(FrontendMessage::TriggerFetchDocument { source: source.to_string() });
+					}
+				}
+			}
+			PortfolioMessage::OpenSvgDocument { name, content } => {
+				let import = match import_svg(&content) {
+					Ok(import) => import,
+					Err(error) => {
+						let description = match error {
+							DocumentError::InvalidFile(message) => message,
+							error => format!("{error:?}"),
+						};
+						responses.add(DialogMessage::DisplayDialogError {
+							title: "Failed to open SVG".to_string(),
+							description,
+						});
+						return;
+					}
+				};
+
+				let name = name.strip_suffix(".svg").unwrap_or(&name).to_string();
+				responses.add(PortfolioMessage::NewDocumentWithName { name });
+
+				if let Some(size) = import.size {
+					let id = generate_uuid();
+					responses.add(ArtboardMessage::AddArtboard {
+						id: Some(id),
+						position: (0., 0.),
+						size: size.into(),
+					});
+					responses.add(GraphOperationMessage::NewArtboard {
+						id,
+						artboard: graphene_core::Artboard::new(IVec2::ZERO, size.round().as_ivec2()),
+					});
+				}
+				for layer in import.layers {
+					responses.add(DocumentOperation::InsertLayer {
+						layer: Box::new(layer),
+						destination_path: vec![generate_uuid()],
+						insert_index: -1,
+						duplicating: false,
+					});
+				}
+				responses.add(DocumentMessage::DeselectAllLayers);
+				responses.add(DocumentMessage::ZoomCanvasToFitAll);
+				responses.add(NodeGraphMessage::RunDocumentGraph);
+				responses.add(NodeGraphMessage::UpdateNewNodeGraph);
+
+				if !import.warnings.is_empty() {
+					for warning in &import.warnings {
+						warn!("SVG import: {warning}");
+					}
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "Some SVG content wasn't imported".to_string(),
+						description: import.warnings.join("\n"),
+					});
+				}
+			}
+			PortfolioMessage::OverrideDocumentLock { document_id } => {
+				if let Some(document) = self.documents.get_mut(&document_id) {
+					document.read_only = false;
+					responses.add(FrontendMessage::TriggerAcquireDocumentLock {
+						document_id,
+						file_id: document.file_id,
+						force: true,
+					});
+					responses.add(PortfolioMessage::UpdateOpenDocumentsList);
+				}
+			}
 			// TODO: Paste message is unused, delete it?
 			PortfolioMessage::Paste { clipboard } => {
 				let shallowest_common_folder = self.active_document().map(|document| {
@@ -356,6 +624,13 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 						trace!("Pasting into folder {:?} as index: {}", &path, insert_index);
 						let destination_path = [path.to_vec(), vec![generate_uuid()]].concat();
 
+						// The layer it was copied from is already deleted when it's moved, or cut and pasted, so its transform links go with it
+						if let Some(source) = entry.source.as_ref().filter(|source| document.document_legacy.layer(source).is_err()) {
+							responses.add_front(DocumentMessage::MoveTransformLinks {
+								from: source.clone(),
+								to: destination_path.clone(),
+							});
+						}
 						responses.add_front(DocumentMessage::UpdateLayerMetadata {
 							layer_path: destination_path.clone(),
 							layer_metadata: entry.layer_metadata,
@@ -380,18 +655,39 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 					}
 				}
 			}
-			PortfolioMessage::PasteSerializedData { data } => {
+			PortfolioMessage::PasteSerializedData { data, mode } => {
 				if let Some(document) = self.active_document() {
 					if let Ok(data) = serde_json::from_str::<Vec<CopyBufferEntry>>(&data) {
-						let shallowest_common_folder = document
-							.document_legacy
-							.shallowest_common_folder(document.selected_layers())
-							.expect("While pasting from serialized, the selected layers did not exist while attempting to find the appropriate folder path for insertion");
+						let (folder_path, insert_index) = document.paste_destination(mode == PasteMode::IntoFolder);
+
+						// When pasting at the pointer, the layers are moved together so the center of their bounds lands under the pointer, or the viewport center if it's elsewhere
+						let offset = (mode == PasteMode::AtPointer)
+							.then(|| {
+								let to_folder = document.document_legacy.generate_transform_across_scope(&folder_path, None).unwrap_or_default();
+								let render_data = RenderData::new(&self.persistent_data.font_cache, ViewMode::Normal, None);
+								data.iter()
+									.filter_map(|entry| entry.layer.aabb_for_transform(to_folder * entry.layer.transform, &render_data))
+									.reduce(|[a_min, a_max], [b_min, b_max]| [a_min.min(b_min), a_max.max(b_max)])
+							})
+							.flatten()
+							.map(|[min, max]| {
+								let viewport_size = ipp.viewport_bounds.size();
+								let pointer = ipp.mouse.position;
+								let inside_viewport = pointer.cmpge(DVec2::ZERO).all() && pointer.cmple(viewport_size).all();
+								let target = if inside_viewport { pointer } else { viewport_size / 2. };
+								target - (min + max) / 2.
+							});
+
 						responses.add(DocumentMessage::DeselectAllLayers);
 						responses.add(DocumentMessage::StartTransaction);
 
-						for entry in data.iter().rev() {
-							let destination_path = [shallowest_common_folder.to_vec(), vec![generate_uuid()]].concat();
+						// The insertions are added to the front of the queue so they run in reverse order, which keeps the stacking order when each one is placed at the same index
+						let mut entries = data.iter().collect::<Vec<_>>();
+						if insert_index == -1 {
+							entries.reverse();
+						}
+						for entry in entries {
+							let destination_path = [folder_path.clone(), vec![generate_uuid()]].concat();
 
 							responses.add_front(DocumentMessage::UpdateLayerMetadata {
 								layer_path: destination_path.clone(),
@@ -400,16 +696,34 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 							document.load_layer_resources(responses, &entry.layer.data, destination_path.clone(), self.active_document_id.unwrap());
 							responses.add_front(DocumentOperation::InsertLayer {
 								layer: Box::new(entry.layer.clone()),
-								destination_path,
-								insert_index: -1,
+								destination_path: destination_path.clone(),
+								insert_index,
 								duplicating: false,
 							});
+							if let Some(offset) = offset {
+								responses.add(GraphOperationMessage::TransformChange {
+									layer: destination_path,
+									transform: DAffine2::from_translation(offset),
+									transform_in: TransformIn::Viewport,
+									skip_rerender: false,
+								});
+							}
 						}
 
 						responses.add(DocumentMessage::CommitTransaction);
 					}
 				}
 			}
+			PortfolioMessage::PerformIdleWork { time_remaining } => {
+				let Some(now) = now_milliseconds() else { return };
+
+				// Wait for a pause in the user's input so the background work doesn't compete with what they're doing
+				if now - ipp.last_input_time < IDLE_INPUT_QUIET_PERIOD {
+					responses.add(FrontendMessage::TriggerIdleWork);
+				} else if has_active_document {
+					responses.add(DocumentMessage::PerformIdleWork { deadline: now + time_remaining });
+				}
+			}
 			PortfolioMessage::PrevDocument => {
 				if let Some(active_document_id) = self.active_document_id {
 					let len = self.document_ids.len();
@@ -419,6 +733,58 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 					responses.add(PortfolioMessage::SelectDocument { document_id: prev_id });
 				}
 			}
+			PortfolioMessage::ProcessBatch { sources, steps } => {
+				if self.batch.is_some() {
+					responses.add(DialogMessage::DisplayDialogError {
+						title: "A batch is already being processed".to_string(),
+						description: "Wait for it to finish before starting another.".to_string(),
+					});
+					return;
+				}
+
+				self.batch = Some(Batch::new(sources, steps));
+				responses.add(PortfolioMessage::ProcessNextBatchDocument);
+			}
+			PortfolioMessage::ProcessNextBatchDocument => {
+				let Some(batch) = &mut self.batch else { return };
+
+				// All the steps of the previous document have been applied by now, so it's closed to make way for the next one
+				if let Some(document_id) = batch.close() {
+					responses.add(PortfolioMessage::CloseDocument { document_id });
+				}
+
+				let (completed, total) = batch.progress();
+				let results = batch.results().to_vec();
+				responses.add(FrontendMessage::UpdateBatchProgress {
+					completed,
+					total,
+					results: results.clone(),
+				});
+				responses.add(BatchDialogMessage::Progress { completed, total, results });
+
+				match batch.next_source() {
+					Some(BatchSource::Path(source)) => {
+						batch.start_fetching(source.clone());
+						responses.add(FrontendMessage::TriggerFetchDocument { source });
+					}
+					Some(BatchSource::Content { name, content }) => self.open_batch_document(name, content, responses),
+					None => {
+						let failures = batch.results().iter().filter(|result| result.error.is_some()).count();
+						info!("Finished processing a batch of {total} documents, {failures} of which failed");
+						self.batch = None;
+					}
+				}
+			}
+			PortfolioMessage::PurgeCachesAndHistory => {
+				for document in self.documents.values_mut() {
+					document.purge_caches_and_history();
+				}
+
+				// The rest are rendered again when they are next shown
+				if has_active_document {
+					responses.add(DocumentMessage::RenderDocument);
+				}
+			}
 			PortfolioMessage::RenderGraphUsingRasterizedRegionBelowLayer {
 				document_id,
 				layer_path,
@@ -440,7 +806,41 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 					});
 				}
 			}
+			PortfolioMessage::ReplaceAllText { query, replacement } => {
+				if query.pattern.is_empty() {
+					return;
+				}
+				// An invalid query has nothing to replace, and its error is already shown with the search results
+				let Ok(matcher) = query.matcher() else { return };
+
+				self.documents.values_mut().for_each(|document| document.finish_loading(responses));
+				for &document_id in &self.document_ids {
+					let Some(document) = self.documents.get(&document_id) else { continue };
+					let replacements: Vec<_> = text_layers(&document.document_legacy)
+						.into_iter()
+						.filter_map(|(layer, _, text)| replace_matches(text, &matcher, &replacement, query.regex).map(|text| (layer, text)))
+						.collect();
+					if replacements.is_empty() {
+						continue;
+					}
+
+					// Each document gets its own transaction, so undoing in one document leaves the replacements in the others
+					let pass_message = |message: DocumentMessage| PortfolioMessage::DocumentPassMessage { document_id, message };
+					responses.add(pass_message(DocumentMessage::StartTransaction));
+					for (layer, text) in replacements {
+						responses.add(pass_message(DocumentMessage::GraphOperation(GraphOperationMessage::TextSet { layer, text })));
+					}
+					responses.add(pass_message(DocumentMessage::CommitTransaction));
+				}
+
+				// Refresh the results, which now only list what the replacement itself matches
+				responses.add(PortfolioMessage::FindText { query });
+			}
 			PortfolioMessage::SelectDocument { document_id } => {
+				if let Some(active_document_id) = self.active_document_id.filter(|&id| id != document_id) {
+					self.exit_presentation(active_document_id, responses);
+				}
+
 				if let Some(document) = self.active_document() {
 					if !document.is_auto_saved() {
 						responses.add(PortfolioMessage::AutoSaveDocument {
@@ -459,21 +859,39 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 				responses.add(PortfolioMessage::SetActiveDocument { document_id });
 				responses.add(PortfolioMessage::UpdateOpenDocumentsList);
 				responses.add(FrontendMessage::UpdateActiveDocument { document_id });
-				responses.add(DocumentMessage::RenderDocument);
+				responses.add(DocumentMessage::RenderFullDocument);
 				responses.add(DocumentMessage::DocumentStructureChanged);
-				for layer in self.documents.get(&document_id).unwrap().layer_metadata.keys() {
-					responses.add(DocumentMessage::LayerChanged { affected_layer_path: layer.clone() });
-				}
+				let layer_paths = self.documents.get(&document_id).unwrap().layer_metadata.keys().cloned().collect();
+				responses.add(DocumentMessage::QueueLayerThumbnails { layer_paths });
 				responses.add(BroadcastEvent::SelectionChanged);
 				responses.add(BroadcastEvent::DocumentIsDirty);
 				responses.add(PortfolioMessage::UpdateDocumentWidgets);
 				responses.add(NavigationMessage::TranslateCanvas { delta: (0., 0.).into() });
 				responses.add(NodeGraphMessage::RunDocumentGraph);
 			}
+			PortfolioMessage::SelectTextMatch { document_id, layer_path } => {
+				if !self.documents.contains_key(&document_id) {
+					return;
+				}
+
+				if self.active_document_id != Some(document_id) {
+					responses.add(PortfolioMessage::SelectDocument { document_id });
+				}
+				responses.add(PortfolioMessage::DocumentPassMessage {
+					document_id,
+					message: DocumentMessage::RevealLayer { layer_path },
+				});
+			}
 			PortfolioMessage::SetActiveDocument { document_id } => {
 				self.active_document_id = Some(document_id);
 				responses.add(MenuBarMessage::SendLayout);
 			}
+			PortfolioMessage::SetDocumentLockStatus { document_id, locked_elsewhere } => {
+				if let Some(document) = self.documents.get_mut(&document_id) {
+					document.read_only = locked_elsewhere;
+					responses.add(PortfolioMessage::UpdateOpenDocumentsList);
+				}
+			}
 			PortfolioMessage::SetImageBlobUrl {
 				document_id,
 				layer_path,
@@ -496,6 +914,10 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 			PortfolioMessage::UpdateDocumentWidgets => {
 				if let Some(document) = self.active_document() {
 					document.update_document_widgets(responses);
+					responses.add(FrontendMessage::UpdateCanvasZoom {
+						zoom_percentage: document.zoom_percentage(),
+						zoom_levels: preferences.zoom_levels.iter().map(|zoom| zoom * 100.).collect(),
+					});
 				}
 			}
 			PortfolioMessage::UpdateOpenDocumentsList => {
@@ -507,6 +929,7 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 						self.documents.get(id).map(|document| FrontendDocumentDetails {
 							is_auto_saved: document.is_auto_saved(),
 							is_saved: document.is_saved(),
+							is_read_only: document.read_only,
 							id: *id,
 							name: document.name.clone(),
 						})
@@ -519,18 +942,26 @@ impl MessageHandler<PortfolioMessage, (&InputPreprocessorMessageHandler, &Prefer
 
 	fn actions(&self) -> ActionList {
 		let mut common = actions!(PortfolioMessageDiscriminant;
-			CloseActiveDocumentWithConfirmation,
 			CloseAllDocuments,
-			Import,
-			NextDocument,
 			OpenDocument,
-			Paste,
-			PasteIntoFolder,
-			PrevDocument,
 		);
 
+		// While presenting, the document being presented can't be changed, closed, or switched away from by accident
+		let presenting = self.active_document().map_or(false, |document| document.presentation_handler.is_presenting());
+		if !presenting {
+			let editing = actions!(PortfolioMessageDiscriminant;
+				CloseActiveDocumentWithConfirmation,
+				Import,
+				NextDocument,
+				Paste,
+				PasteIntoFolder,
+				PrevDocument,
+			);
+			common.extend(editing);
+		}
+
 		if let Some(document) = self.active_document() {
-			if document.layer_metadata.values().any(|data| data.selected) {
+			if !presenting && document.layer_metadata.values().any(|data| data.selected) {
 				let select = actions!(PortfolioMessageDiscriminant;
 					Copy,
 					Cut,
@@ -561,6 +992,11 @@ impl PortfolioMessageHandler {
 		self.active_document_id.and_then(|id| self.documents.get(&id))
 	}
 
+	/// The name of each open document, in the order of their tabs, with an estimate of the memory it holds
+	pub fn memory_reports(&self) -> Vec<(String, MemoryReport)> {
+		self.ordered_document_iterator().map(|document| (document.name.clone(), document.memory_report())).collect()
+	}
+
 	pub fn active_document_mut(&mut self) -> Option<&mut DocumentMessageHandler> {
 		self.active_document_id.and_then(|id| self.documents.get_mut(&id))
 	}
@@ -606,7 +1042,15 @@ impl PortfolioMessageHandler {
 		);
 		new_document.update_layer_tree_options_bar_widgets(responses, &render_data);
 
+		// Ask the storage layer whether another window already has this file open, in which case it becomes read-only here
+		responses.add(FrontendMessage::TriggerAcquireDocumentLock {
+			document_id,
+			file_id: new_document.file_id,
+			force: false,
+		});
 		self.documents.insert(document_id, new_document);
+		// The limit on nesting folders isn't saved with the document, since it's a preference
+		responses.add(PortfolioMessage::MaxFolderDepthPreference);
 
 		if self.active_document().is_some() {
 			responses.add(PropertiesPanelMessage::Deactivate);
@@ -626,6 +1070,53 @@ impl PortfolioMessageHandler {
 		responses.add(PropertiesPanelMessage::UpdateSelectedDocumentProperties);
 	}
 
+	/// Opens a document of the batch, and applies its steps once the node graph layers it loads with have been evaluated.
+	fn open_batch_document(&mut self, name: String, content: String, responses: &mut VecDeque<Message>) {
+		let Some(batch) = &mut self.batch else { return };
+
+		match DocumentMessageHandler::with_name_and_content(name.clone(), content) {
+			Ok((document, dropped_layers)) => {
+				let document_id = generate_uuid();
+				let error = (!dropped_layers.is_empty()).then(|| format!("Processed without {} layer(s) that couldn't be read", dropped_layers.len()));
+				// The output of node graph layers isn't saved, so each is evaluated once the document is loaded, and the steps wait on that
+				let awaiting_evaluation = document
+					.all_layers()
+					.filter(|path| document.document_legacy.layer(path).and_then(|layer| layer.as_layer()).is_ok())
+					.map(|path| path.to_vec())
+					.collect();
+				let ready = batch.open(document_id, BatchResult { name, error }, awaiting_evaluation);
+
+				self.load_document(document, document_id, responses);
+				if ready {
+					self.apply_batch_steps(document_id, responses);
+				}
+			}
+			Err(error) => {
+				batch.fail(name, error.to_string());
+				responses.add(PortfolioMessage::ProcessNextBatchDocument);
+			}
+		}
+	}
+
+	/// Applies each step of the batch to its open document, then moves on to the next document.
+	fn apply_batch_steps(&self, document_id: u64, responses: &mut VecDeque<Message>) {
+		let (Some(batch), Some(document)) = (&self.batch, self.documents.get(&document_id)) else { return };
+
+		for step in batch.steps() {
+			let message = step.message(&document.name);
+			responses.add(PortfolioMessage::DocumentPassMessage { document_id, message });
+		}
+		responses.add(PortfolioMessage::ProcessNextBatchDocument);
+	}
+
+	/// Leaves presentation mode in the document if it's being presented, which is done before it's closed or another document is shown.
+	fn exit_presentation(&self, document_id: u64, responses: &mut VecDeque<Message>) {
+		if self.documents.get(&document_id).map_or(false, |document| document.presentation_handler.is_presenting()) {
+			let message = PresentationMessage::Exit.into();
+			responses.add(PortfolioMessage::DocumentPassMessage { document_id, message });
+		}
+	}
+
 	/// Returns an iterator over the open documents in order.
 	pub fn ordered_document_iterator(&self) -> impl Iterator<Item = &DocumentMessageHandler> {
 		self.document_ids.iter().map(|id| self.documents.get(id).expect("document id was not found in the document hashmap"))
@@ -671,5 +1162,96 @@ impl PortfolioMessageHandler {
 		self.executor.poll_node_graph_evaluation(transform, responses).unwrap_or_else(|e| {
 			log::error!("Error while evaluating node graph: {}", e);
 		});
+
+		for (job_id, output) in self.persistent_data.render_workers.poll() {
+			match output {
+				RenderOutput::LayerThumbnail { document_id, layer_path, thumbnail } => responses.add(PortfolioMessage::DocumentPassMessage {
+					document_id,
+					message: DocumentMessage::LayerThumbnailRendered { job_id, layer_path, thumbnail },
+				}),
+				RenderOutput::DocumentThumbnail { document_id, thumbnail } => responses.add(PortfolioMessage::DocumentPassMessage {
+					document_id,
+					message: DocumentMessage::DocumentThumbnailRendered { job_id, thumbnail },
+				}),
+				RenderOutput::BooleanPreview { document_id, result } => responses.add(PortfolioMessage::DocumentPassMessage {
+					document_id,
+					message: DocumentMessage::BooleanPreviewComputed { job_id, result },
+				}),
+				RenderOutput::Export { svg, name, file_type, size } => responses.add(file_type.download(svg, name, size)),
+			}
+		}
+	}
+}
+
+/// The file name at the end of a path or URL, without the query or fragment of a URL, used to name the document opened from it.
+fn document_name_from_source(source: &str) -> String {
+	let path = match source.contains("://") {
+		true => source.split(['?', '#']).next().unwrap_or(source),
+		false => source,
+	};
+	let name = path.rsplit(['/', '\\']).find(|segment| !segment.is_empty()).unwrap_or(path);
+
+	if name.is_empty() {
+		DEFAULT_DOCUMENT_NAME.to_string()
+	} else {
+		name.to_string()
+	}
+}
+
+/// Adds layers read from a design file to the folder at the given path, creating a folder for each group.
+fn insert_imported_layers(layers: Vec<ImportedLayer>, folder_path: &[LayerId], responses: &mut VecDeque<Message>) {
+	for layer in layers {
+		let path = [folder_path, &[generate_uuid()]].concat();
+
+		match layer.content {
+			ImportedContent::Group(children) => {
+				responses.add(DocumentOperation::CreateFolder { path: path.clone(), insert_index: -1 });
+				insert_imported_layers(children, &path, responses);
+			}
+			ImportedContent::Shape { subpaths, fill, stroke } => {
+				graph_modification_utils::new_vector_layer(subpaths, path.clone(), responses);
+				responses.add(GraphOperationMessage::FillSet { layer: path.clone(), fill });
+				responses.add(GraphOperationMessage::StrokeSet {
+					layer: path.clone(),
+					stroke: stroke.unwrap_or_else(|| Stroke::new(None, 0.)),
+				});
+			}
+			ImportedContent::Text {
+				text,
+				font,
+				font_size,
+				color,
+				transform,
+			} => {
+				responses.add(PortfolioMessage::LoadFont {
+					font: font.clone(),
+					is_default: false,
+				});
+				graph_modification_utils::new_custom_layer(new_text_network(text, font, font_size as f32), path.clone(), responses);
+				responses.add(GraphOperationMessage::FillSet {
+					layer: path.clone(),
+					fill: color.map_or(Fill::None, Fill::Solid),
+				});
+				responses.add(GraphOperationMessage::TransformSet {
+					layer: path.clone(),
+					transform,
+					transform_in: TransformIn::Local,
+					skip_rerender: true,
+				});
+			}
+		}
+
+		if let Some(name) = layer.name {
+			responses.add(DocumentOperation::SetLayerName { path: path.clone(), name });
+		}
+		if layer.opacity != 1. {
+			responses.add(DocumentOperation::SetLayerOpacity {
+				path: path.clone(),
+				opacity: layer.opacity,
+			});
+		}
+		if !layer.visible {
+			responses.add(DocumentOperation::SetLayerVisibility { path, visible: false });
+		}
 	}
 }
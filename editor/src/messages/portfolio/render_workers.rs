@@ -0,0 +1,127 @@
+//! Renders layer thumbnails, exported documents, and boolean operation previews on a pool of background threads in native builds, keeping
+//! the UI thread responsive while large documents are worked through. Each job is handed a copy of what it renders, and its output comes
+//! back over a completion channel which the editor drains whenever it polls the node graph evaluation.
+//!
+//! Web builds have no threads to spare, so there the thumbnails are instead rendered a few at a time across frames while the user is idle
+//! (see `IdleWork`), and exports are rendered on the spot.
+
+use crate::messages::frontend::utility_types::FileType;
+use crate::messages::portfolio::document::utility_types::boolean_operations::StyledShape;
+
+use document_legacy::LayerId;
+use graphene_core::text::FontCache;
+
+use glam::DVec2;
+use std::cell::Cell;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+
+/// The most threads rendered on at once, leaving the remaining cores to the node graph and the rest of the system.
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_RENDER_WORKERS: usize = 4;
+
+/// What a job rendered, along with where it goes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderOutput {
+	LayerThumbnail { document_id: u64, layer_path: Vec<LayerId>, thumbnail: String },
+	DocumentThumbnail { document_id: u64, thumbnail: String },
+	BooleanPreview { document_id: u64, result: Option<StyledShape> },
+	Export { svg: String, name: String, file_type: FileType, size: DVec2 },
+}
+
+pub type RenderJob = Box<dyn FnOnce(&FontCache) -> RenderOutput + Send>;
+
+pub struct RenderWorkers {
+	/// Started along with the threads on the first submitted job, so editors which never render in the background don't spawn any.
+	#[cfg(not(target_arch = "wasm32"))]
+	jobs: once_cell::unsync::OnceCell<Sender<(u64, Arc<FontCache>, RenderJob)>>,
+	completed_sender: Sender<(u64, RenderOutput)>,
+	completed: Receiver<(u64, RenderOutput)>,
+	/// Shared with the jobs, and replaced whenever a font is loaded so jobs already running keep the fonts they started with.
+	font_cache: Arc<FontCache>,
+	next_job_id: Cell<u64>,
+}
+
+impl Default for RenderWorkers {
+	fn default() -> Self {
+		let (completed_sender, completed) = channel();
+		Self {
+			#[cfg(not(target_arch = "wasm32"))]
+			jobs: Default::default(),
+			completed_sender,
+			completed,
+			font_cache: Default::default(),
+			next_job_id: Cell::new(0),
+		}
+	}
+}
+
+impl std::fmt::Debug for RenderWorkers {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("RenderWorkers").field("available", &Self::available()).finish_non_exhaustive()
+	}
+}
+
+impl RenderWorkers {
+	/// Whether jobs are run on background threads. Otherwise they shouldn't be submitted, since they would block the UI thread just the same.
+	pub const fn available() -> bool {
+		cfg!(not(target_arch = "wasm32"))
+	}
+
+	pub fn update_font_cache(&mut self, font_cache: FontCache) {
+		self.font_cache = Arc::new(font_cache);
+	}
+
+	/// Queues a job for the next free thread, returning the ID its output is later received with.
+	pub fn submit(&self, job: RenderJob) -> u64 {
+		let job_id = self.next_job_id.get();
+		self.next_job_id.set(job_id + 1);
+
+		#[cfg(not(target_arch = "wasm32"))]
+		{
+			let jobs = self.jobs.get_or_init(|| spawn_workers(self.completed_sender.clone()));
+			if let Err(error) = jobs.send((job_id, self.font_cache.clone(), job)) {
+				// The threads are gone, which only happens if they all panicked, so the job is run here rather than lost
+				let (job_id, font_cache, job) = error.0;
+				self.completed_sender.send((job_id, job(&font_cache))).expect("The completion channel is owned by the render workers");
+			}
+		}
+		#[cfg(target_arch = "wasm32")]
+		self.completed_sender
+			.send((job_id, job(&self.font_cache)))
+			.expect("The completion channel is owned by the render workers");
+
+		job_id
+	}
+
+	/// The outputs of the jobs finished since the last call, along with their IDs.
+	pub fn poll(&self) -> impl Iterator<Item = (u64, RenderOutput)> + '_ {
+		self.completed.try_iter()
+	}
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn spawn_workers(completed: Sender<(u64, RenderOutput)>) -> Sender<(u64, Arc<FontCache>, RenderJob)> {
+	let (sender, receiver) = channel::<(u64, Arc<FontCache>, RenderJob)>();
+	let receiver = Arc::new(std::sync::Mutex::new(receiver));
+
+	let count = std::thread::available_parallelism().map_or(1, |count| count.get() / 2).clamp(1, MAX_RENDER_WORKERS);
+	for index in 0..count {
+		let (receiver, completed) = (receiver.clone(), completed.clone());
+		let spawned = std::thread::Builder::new().name(format!("render-worker-{index}")).spawn(move || loop {
+			// The lock is only held while waiting for a job, so the other threads can pick up the next one while this one renders
+			let job = receiver.lock().ok().and_then(|receiver| receiver.recv().ok());
+			let Some((job_id, font_cache, job)) = job else { break };
+
+			// The editor has been dropped, so there's nobody left to render for
+			if completed.send((job_id, job(&font_cache))).is_err() {
+				break;
+			}
+		});
+		if let Err(error) = spawned {
+			warn!("A render worker thread couldn't be started: {error}");
+		}
+	}
+
+	sender
+}
@@ -1,3 +1,6 @@
+use super::render_workers::RenderWorkers;
+use crate::consts::{FILE_BACKUP_SUFFIX, FILE_SAVE_SUFFIX};
+
 use graphene_std::{imaginate::ImaginatePersistentData, text::FontCache};
 
 use serde::{Deserialize, Serialize};
@@ -6,6 +9,7 @@ use serde::{Deserialize, Serialize};
 pub struct PersistentData {
 	pub font_cache: FontCache,
 	pub imaginate: ImaginatePersistentData,
+	pub render_workers: RenderWorkers,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Default, Debug, Serialize, Deserialize)]
@@ -38,3 +42,14 @@ pub enum KeyboardPlatformLayout {
 	/// Keyboard mapping used by Macs where Command is sometimes used in favor of Control
 	Mac,
 }
+
+/// The name a backup of a saved file is listed under, counting back from the latest save, like "Drawing.graphite.bak1".
+pub fn backup_file_name(file_name: &str, generation: usize) -> String {
+	format!("{file_name}{FILE_BACKUP_SUFFIX}{generation}")
+}
+
+/// The name of a document opened from a backup, like "Drawing.bak1.graphite", which is kept apart from the original so saving it doesn't overwrite that file.
+pub fn restored_backup_name(file_name: &str, generation: usize) -> String {
+	let base_name = file_name.strip_suffix(FILE_SAVE_SUFFIX).unwrap_or(file_name);
+	format!("{base_name}{FILE_BACKUP_SUFFIX}{generation}{FILE_SAVE_SUFFIX}")
+}
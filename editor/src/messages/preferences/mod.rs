@@ -1,6 +1,9 @@
 mod preferences_message;
 mod preferences_message_handler;
 
+pub mod usage_statistics;
+pub mod utility_types;
+
 #[doc(inline)]
 pub use preferences_message::{PreferencesMessage, PreferencesMessageDiscriminant};
 #[doc(inline)]
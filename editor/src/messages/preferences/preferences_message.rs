@@ -1,4 +1,10 @@
+use crate::messages::input_mapper::utility_types::misc::{MiddleDragBehavior, PointerRole, RoleTrigger};
+use crate::messages::portfolio::document::overlays::utility_types::OverlayColors;
+use crate::messages::portfolio::document::utility_types::misc::NewLayerPlacement;
+use crate::messages::preferences::utility_types::NumberLocale;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::shape_presets::ShapePreset;
+use crate::messages::workspace::utility_types::WorkspaceLayout;
 
 use serde::{Deserialize, Serialize};
 
@@ -7,8 +13,28 @@ use serde::{Deserialize, Serialize};
 pub enum PreferencesMessage {
 	Load { preferences: String },
 	ResetToDefaults,
+	ResetUsageStatistics,
 
+	BackupCount { count: u32 },
+	CssPixelZoom { css_pixel_zoom: bool },
+	DeleteShapePreset { name: String },
+	DuplicateOffset { offset: f64 },
 	ImaginateRefreshFrequency { seconds: f64 },
 	ImaginateServerHostname { hostname: String },
+	KeyRepeatDelay { delay: f64 },
+	KeyRepeatInterval { interval: f64 },
+	LargerHandles { larger_handles: bool },
+	MaxFolderDepth { depth: u32 },
+	MiddleDrag { middle_drag: MiddleDragBehavior },
 	ModifyLayout { zoom_with_scroll: bool },
+	NewLayerPlacement { placement: NewLayerPlacement },
+	NumberLocale { locale: NumberLocale },
+	OverlayColors { overlay_colors: OverlayColors },
+	PointerRole { role: PointerRole, trigger: RoleTrigger },
+	RepeatUndoRedo { repeat_undo_redo: bool },
+	SaveShapePreset { preset: ShapePreset },
+	SmoothNavigation { smooth_navigation: bool },
+	UsageStatistics { enabled: bool },
+	WorkspaceLayout { layout: WorkspaceLayout },
+	ZoomLevels { zoom_levels: Vec<f64> },
 }
@@ -1,7 +1,18 @@
+use crate::consts::{DEFAULT_DUPLICATE_OFFSET, KEY_REPEAT_DEFAULT_DELAY, KEY_REPEAT_DEFAULT_INTERVAL, KEY_REPEAT_MIN_TIME, MAX_DOCUMENT_BACKUPS, VIEWPORT_ZOOM_LEVELS};
 use crate::messages::input_mapper::key_mapping::MappingVariant;
+use crate::messages::input_mapper::utility_types::mapping_definition::MappingDefinition;
+use crate::messages::input_mapper::utility_types::misc::{MiddleDragBehavior, PointerRoles};
+use crate::messages::portfolio::document::overlays::utility_types::OverlayColors;
+use crate::messages::portfolio::document::utility_types::misc::NewLayerPlacement;
+use crate::messages::preferences::usage_statistics::UsageStatistics;
+use crate::messages::preferences::utility_types::NumberLocale;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::shape_presets::ShapePreset;
+use crate::messages::workspace::utility_types::WorkspaceLayout;
+use document_legacy::consts::MAX_FOLDER_DEPTH;
 use graph_craft::imaginate_input::ImaginatePreferences;
 
+use glam::DVec2;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, specta::Type)]
@@ -9,6 +20,83 @@ pub struct PreferencesMessageHandler {
 	pub imaginate_server_hostname: String,
 	pub imaginate_refresh_frequency: f64,
 	pub zoom_with_scroll: bool,
+	#[serde(default)]
+	pub middle_drag: MiddleDragBehavior,
+	/// The inputs which, besides the middle and right mouse buttons, pan, zoom, and right click, for trackpads and one-button mice
+	#[serde(default)]
+	pub pointer_roles: PointerRoles,
+	/// Time, in milliseconds, that a key is held before its action repeats, for the actions which repeat
+	#[serde(default = "default_key_repeat_delay")]
+	pub key_repeat_delay: f64,
+	/// Time, in milliseconds, between repeats of the action of a held key
+	#[serde(default = "default_key_repeat_interval")]
+	pub key_repeat_interval: f64,
+	/// Keeps undoing or redoing while the shortcut is held, which is easy to overshoot with so it can be turned off
+	#[serde(default = "default_repeat_undo_redo")]
+	pub repeat_undo_redo: bool,
+	/// Eases the view into place when zooming to fit or to a set level and when resetting the rotation, and lets a quickly released pan keep drifting
+	#[serde(default = "default_smooth_navigation")]
+	pub smooth_navigation: bool,
+	/// The zoom factors that zooming in and out steps between, in increasing order
+	#[serde(default = "default_zoom_levels")]
+	pub zoom_levels: Vec<f64>,
+	#[serde(default)]
+	pub larger_handles: bool,
+	#[serde(default)]
+	pub overlay_colors: OverlayColors,
+	#[serde(default)]
+	pub workspace_layout: WorkspaceLayout,
+	#[serde(default = "default_duplicate_offset")]
+	pub duplicate_offset: f64,
+	/// Makes 100% zoom show one document pixel per CSS pixel rather than per physical screen pixel, so the canvas looks larger on high-DPI displays
+	#[serde(default)]
+	pub css_pixel_zoom: bool,
+	/// How many of the previous saves of each document are kept as backups in the browser's storage, or none when 0
+	#[serde(default)]
+	pub backup_count: u32,
+	/// How deeply folders may be nested within each other by grouping, moving, and pasting layers
+	#[serde(default = "default_max_folder_depth")]
+	pub max_folder_depth: u32,
+	/// Where the tools insert the layers they draw
+	#[serde(default)]
+	pub new_layer_placement: NewLayerPlacement,
+	/// Styles saved from shapes to draw new shapes with, chosen in the options of the shape tools
+	#[serde(default)]
+	pub shape_presets: Vec<ShapePreset>,
+	/// How numbers are written in the readouts, with a decimal point or a decimal comma
+	#[serde(default)]
+	pub number_locale: NumberLocale,
+	/// Statistics about how the editor is used, recorded only once the user turns them on and never transmitted anywhere
+	#[serde(default)]
+	pub usage_statistics: Option<UsageStatistics>,
+}
+
+fn default_duplicate_offset() -> f64 {
+	DEFAULT_DUPLICATE_OFFSET
+}
+
+fn default_max_folder_depth() -> u32 {
+	MAX_FOLDER_DEPTH as u32
+}
+
+fn default_key_repeat_delay() -> f64 {
+	KEY_REPEAT_DEFAULT_DELAY
+}
+
+fn default_key_repeat_interval() -> f64 {
+	KEY_REPEAT_DEFAULT_INTERVAL
+}
+
+fn default_repeat_undo_redo() -> bool {
+	true
+}
+
+fn default_smooth_navigation() -> bool {
+	true
+}
+
+fn default_zoom_levels() -> Vec<f64> {
+	VIEWPORT_ZOOM_LEVELS.to_vec()
 }
 
 impl PreferencesMessageHandler {
@@ -17,6 +105,29 @@ impl PreferencesMessageHandler {
 			host_name: self.imaginate_server_hostname.clone(),
 		}
 	}
+
+	/// The built-in mapping for the scroll wheel preference, where scrolling without modifiers either zooms or pans the canvas.
+	fn mapping_variant(&self) -> MappingVariant {
+		match self.zoom_with_scroll {
+			false => MappingVariant::Default,
+			true => MappingVariant::ZoomWithScroll,
+		}
+	}
+
+	fn key_repeat_message(&self) -> InputPreprocessorMessage {
+		InputPreprocessorMessage::SetKeyRepeat {
+			delay: self.key_repeat_delay,
+			interval: self.key_repeat_interval,
+		}
+	}
+
+	/// The device pixel ratio that the canvas zoom is matched to, so 100% zoom shows one document pixel per physical screen pixel, or 1 when zooming in CSS pixels.
+	pub fn zoom_pixel_ratio(&self, ipp: &InputPreprocessorMessageHandler) -> f64 {
+		match self.css_pixel_zoom {
+			true => 1.,
+			false => ipp.device_pixel_ratio,
+		}
+	}
 }
 
 impl Default for PreferencesMessageHandler {
@@ -26,6 +137,24 @@ impl Default for PreferencesMessageHandler {
 			imaginate_server_hostname: host_name,
 			imaginate_refresh_frequency: 1.,
 			zoom_with_scroll: matches!(MappingVariant::default(), MappingVariant::ZoomWithScroll),
+			middle_drag: MiddleDragBehavior::default(),
+			pointer_roles: PointerRoles::default(),
+			key_repeat_delay: default_key_repeat_delay(),
+			key_repeat_interval: default_key_repeat_interval(),
+			repeat_undo_redo: default_repeat_undo_redo(),
+			smooth_navigation: default_smooth_navigation(),
+			zoom_levels: default_zoom_levels(),
+			larger_handles: false,
+			overlay_colors: OverlayColors::default(),
+			workspace_layout: WorkspaceLayout::default(),
+			duplicate_offset: DEFAULT_DUPLICATE_OFFSET,
+			css_pixel_zoom: false,
+			backup_count: 0,
+			max_folder_depth: default_max_folder_depth(),
+			new_layer_placement: NewLayerPlacement::default(),
+			shape_presets: Vec::new(),
+			number_locale: NumberLocale::default(),
+			usage_statistics: None,
 		}
 	}
 }
@@ -37,19 +166,90 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 			PreferencesMessage::Load { preferences } => {
 				if let Ok(deserialized_preferences) = serde_json::from_str::<PreferencesMessageHandler>(&preferences) {
 					*self = deserialized_preferences;
+					// Preferences saved when deeper nesting was allowed are brought within the current limit
+					self.max_folder_depth = self.max_folder_depth.clamp(1, MAX_FOLDER_DEPTH as u32);
 
 					responses.add(PortfolioMessage::ImaginateServerHostname);
 					responses.add(PortfolioMessage::ImaginateCheckServerStatus);
 					responses.add(PortfolioMessage::ImaginatePreferences);
+					responses.add(PortfolioMessage::MaxFolderDepthPreference);
+					responses.add(GlobalsMessage::SetLargerHandles { larger_handles: self.larger_handles });
+					responses.add(GlobalsMessage::SetOverlayColors { overlay_colors: self.overlay_colors });
+					responses.add(GlobalsMessage::SetNewLayerPlacement { placement: self.new_layer_placement });
+					responses.add(GlobalsMessage::SetShapePresets { presets: self.shape_presets.clone() });
+					responses.add(GlobalsMessage::SetNumberLocale { locale: self.number_locale });
+					responses.add(WorkspaceMessage::RestoreLayout { layout: self.workspace_layout });
+					responses.add(KeyMappingMessage::SetMiddleDragBehavior { middle_drag: self.middle_drag });
+					responses.add(KeyMappingMessage::SetPointerRoles { pointer_roles: self.pointer_roles });
+					responses.add(KeyMappingMessage::SetRepeatUndoRedo {
+						repeat_undo_redo: self.repeat_undo_redo,
+					});
+					responses.add(self.key_repeat_message());
+					responses.add(KeyMappingMessage::ModifyMapping(self.mapping_variant()));
+					responses.add(FrontendMessage::UpdateZoomWithScroll {
+						zoom_with_scroll: self.zoom_with_scroll,
+					});
 				}
 			}
 			PreferencesMessage::ResetToDefaults => {
 				refresh_dialog(responses);
 				responses.add(KeyMappingMessage::ModifyMapping(MappingVariant::Default));
+				responses.add(FrontendMessage::UpdateZoomWithScroll { zoom_with_scroll: false });
+				responses.add(KeyMappingMessage::SetMiddleDragBehavior {
+					middle_drag: MiddleDragBehavior::default(),
+				});
+				responses.add(KeyMappingMessage::SetPointerRoles {
+					pointer_roles: PointerRoles::default(),
+				});
+				responses.add(KeyMappingMessage::SetRepeatUndoRedo {
+					repeat_undo_redo: default_repeat_undo_redo(),
+				});
+				responses.add(Self::default().key_repeat_message());
+				responses.add(InputMapperMessage::SetMapping(MappingDefinition::default()));
+				responses.add(GlobalsMessage::SetLargerHandles { larger_handles: false });
+				responses.add(GlobalsMessage::SetOverlayColors {
+					overlay_colors: OverlayColors::default(),
+				});
+				responses.add(GlobalsMessage::SetNewLayerPlacement {
+					placement: NewLayerPlacement::default(),
+				});
+				responses.add(WorkspaceMessage::RestoreLayout { layout: WorkspaceLayout::default() });
+				responses.add(GlobalsMessage::SetShapePresets { presets: Vec::new() });
+				responses.add(GlobalsMessage::SetNumberLocale { locale: NumberLocale::default() });
+				responses.add(ToolMessage::RefreshToolOptions);
+				responses.add(PortfolioMessage::MaxFolderDepthPreference);
 
 				*self = Self::default()
 			}
 
+			PreferencesMessage::ResetUsageStatistics => {
+				if let Some(statistics) = &mut self.usage_statistics {
+					*statistics = UsageStatistics::new(unix_time_milliseconds().unwrap_or_default());
+				}
+
+				responses.add(DialogMessage::CloseDialogAndThen {
+					followups: vec![DialogMessage::RequestUsageStatisticsDialog.into()],
+				});
+			}
+
+			PreferencesMessage::BackupCount { count } => {
+				self.backup_count = count.min(MAX_DOCUMENT_BACKUPS);
+			}
+			PreferencesMessage::CssPixelZoom { css_pixel_zoom } => {
+				self.css_pixel_zoom = css_pixel_zoom;
+
+				// Rescale the canvas to the new meaning of the zoom level
+				responses.add(NavigationMessage::TranslateCanvas { delta: DVec2::ZERO });
+			}
+			PreferencesMessage::DeleteShapePreset { name } => {
+				self.shape_presets.retain(|preset| preset.name != name);
+
+				responses.add(GlobalsMessage::SetShapePresets { presets: self.shape_presets.clone() });
+				responses.add(ToolMessage::RefreshToolOptions);
+			}
+			PreferencesMessage::DuplicateOffset { offset } => {
+				self.duplicate_offset = offset;
+			}
 			PreferencesMessage::ImaginateRefreshFrequency { seconds } => {
 				self.imaginate_refresh_frequency = seconds;
 				responses.add(PortfolioMessage::ImaginateCheckServerStatus);
@@ -70,16 +270,97 @@ impl MessageHandler<PreferencesMessage, ()> for PreferencesMessageHandler {
 				responses.add(PortfolioMessage::ImaginateCheckServerStatus);
 				responses.add(PortfolioMessage::ImaginatePreferences);
 			}
+			PreferencesMessage::KeyRepeatDelay { delay } => {
+				self.key_repeat_delay = delay.max(KEY_REPEAT_MIN_TIME);
+
+				responses.add(self.key_repeat_message());
+			}
+			PreferencesMessage::KeyRepeatInterval { interval } => {
+				self.key_repeat_interval = interval.max(KEY_REPEAT_MIN_TIME);
+
+				responses.add(self.key_repeat_message());
+			}
+			PreferencesMessage::LargerHandles { larger_handles } => {
+				self.larger_handles = larger_handles;
+
+				responses.add(GlobalsMessage::SetLargerHandles { larger_handles });
+				// Redraw the overlays of the active tool at the new size
+				responses.add(BroadcastEvent::SelectionChanged);
+			}
+			PreferencesMessage::MaxFolderDepth { depth } => {
+				self.max_folder_depth = depth.clamp(1, MAX_FOLDER_DEPTH as u32);
+
+				responses.add(PortfolioMessage::MaxFolderDepthPreference);
+			}
+			PreferencesMessage::MiddleDrag { middle_drag } => {
+				self.middle_drag = middle_drag;
+
+				responses.add(KeyMappingMessage::SetMiddleDragBehavior { middle_drag });
+			}
 			PreferencesMessage::ModifyLayout { zoom_with_scroll } => {
 				self.zoom_with_scroll = zoom_with_scroll;
 
-				let variant = match zoom_with_scroll {
-					false => MappingVariant::Default,
-					true => MappingVariant::ZoomWithScroll,
-				};
-				responses.add(KeyMappingMessage::ModifyMapping(variant));
+				responses.add(KeyMappingMessage::ModifyMapping(self.mapping_variant()));
 				responses.add(FrontendMessage::UpdateZoomWithScroll { zoom_with_scroll });
 			}
+			PreferencesMessage::NewLayerPlacement { placement } => {
+				self.new_layer_placement = placement;
+
+				responses.add(GlobalsMessage::SetNewLayerPlacement { placement });
+			}
+			PreferencesMessage::NumberLocale { locale } => {
+				self.number_locale = locale;
+
+				responses.add(GlobalsMessage::SetNumberLocale { locale });
+				// Rewrite the numbers already shown with the new separator
+				responses.add(ToolMessage::RefreshToolOptions);
+				responses.add(PropertiesPanelMessage::ResendActiveProperties);
+				responses.add(DocumentMessage::RenderDocument);
+				refresh_dialog(responses);
+			}
+			PreferencesMessage::SaveShapePreset { preset } => {
+				// Saving under an existing name updates that preset
+				match self.shape_presets.iter_mut().find(|existing| existing.name == preset.name) {
+					Some(existing) => *existing = preset,
+					None => self.shape_presets.push(preset),
+				}
+
+				responses.add(GlobalsMessage::SetShapePresets { presets: self.shape_presets.clone() });
+				responses.add(ToolMessage::RefreshToolOptions);
+			}
+			PreferencesMessage::SmoothNavigation { smooth_navigation } => {
+				self.smooth_navigation = smooth_navigation;
+			}
+			PreferencesMessage::ZoomLevels { zoom_levels } => {
+				// Clearing the levels restores the built-in ones, since zooming in and out needs at least some to step between
+				self.zoom_levels = if zoom_levels.is_empty() { default_zoom_levels() } else { zoom_levels };
+
+				responses.add(PortfolioMessage::UpdateDocumentWidgets);
+				refresh_dialog(responses);
+			}
+			PreferencesMessage::UsageStatistics { enabled } => {
+				// Turning the statistics off deletes what was recorded, rather than keeping it around unused
+				self.usage_statistics = enabled.then(|| self.usage_statistics.take().unwrap_or_else(|| UsageStatistics::new(unix_time_milliseconds().unwrap_or_default())));
+			}
+			PreferencesMessage::OverlayColors { overlay_colors } => {
+				self.overlay_colors = overlay_colors;
+
+				responses.add(GlobalsMessage::SetOverlayColors { overlay_colors });
+				refresh_dialog(responses);
+			}
+			PreferencesMessage::PointerRole { role, trigger } => {
+				self.pointer_roles.set(role, trigger);
+
+				responses.add(KeyMappingMessage::SetPointerRoles { pointer_roles: self.pointer_roles });
+			}
+			PreferencesMessage::RepeatUndoRedo { repeat_undo_redo } => {
+				self.repeat_undo_redo = repeat_undo_redo;
+
+				responses.add(KeyMappingMessage::SetRepeatUndoRedo { repeat_undo_redo });
+			}
+			PreferencesMessage::WorkspaceLayout { layout } => {
+				self.workspace_layout = layout;
+			}
 		}
 
 		responses.add(FrontendMessage::TriggerSavePreferences { preferences: self.clone() });
@@ -0,0 +1,185 @@
+//! Personal usage statistics, which are only recorded once turned on in the preferences. They are kept along with the preferences in local
+//! storage purely for the user's own insight into how they use the editor, and are never transmitted anywhere.
+
+use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ToolType;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Pauses between inputs longer than this are taken as time away from the editor, which isn't counted.
+const USAGE_IDLE_THRESHOLD_MILLISECONDS: f64 = 60_000.;
+
+/// How many of the most frequent operations are listed in the dashboard.
+pub const USAGE_REPORTED_OPERATIONS: usize = 15;
+
+/// Counts of what has been done with documents.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct DocumentActivity {
+	pub created: u64,
+	pub opened: u64,
+	pub saved: u64,
+	pub exported: u64,
+	pub closed: u64,
+}
+
+/// What a message means for the statistics, noted before it is handed to its handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageEvent {
+	Input,
+	DocumentCreated,
+	DocumentOpened,
+	DocumentSaved,
+	DocumentExported,
+	DocumentClosed,
+	/// Any other message, which counts as an operation if it adds a step to the undo history.
+	Other(MessageDiscriminant),
+}
+
+impl UsageEvent {
+	pub fn of(message: &Message) -> Self {
+		match message {
+			Message::InputPreprocessor(_) => Self::Input,
+			Message::Portfolio(PortfolioMessage::NewDocumentWithName { .. }) => Self::DocumentCreated,
+			Message::Portfolio(PortfolioMessage::OpenDocumentFileWithId { document_is_auto_saved: false, .. }) => Self::DocumentOpened,
+			Message::Portfolio(PortfolioMessage::CloseDocument { .. }) => Self::DocumentClosed,
+			Message::Portfolio(PortfolioMessage::Document(DocumentMessage::SaveDocument)) => Self::DocumentSaved,
+			Message::Portfolio(PortfolioMessage::Document(DocumentMessage::ExportDocument { preview: false, .. })) => Self::DocumentExported,
+			_ => Self::Other(message.to_discriminant()),
+		}
+	}
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct UsageStatistics {
+	/// When recording began, in milliseconds since the Unix epoch.
+	pub since: f64,
+	/// Time spent using the editor, not counting pauses in the input.
+	#[serde(rename = "activeMilliseconds")]
+	pub active_milliseconds: f64,
+	/// The active time spent with each tool, by the tool's name.
+	#[serde(rename = "toolMilliseconds")]
+	pub tool_milliseconds: BTreeMap<String, f64>,
+	/// How many times each operation that can be undone was done, by the operation's name.
+	pub operations: BTreeMap<String, u64>,
+	pub documents: DocumentActivity,
+	#[serde(skip)]
+	last_input: Option<f64>,
+}
+
+impl UsageStatistics {
+	pub fn new(since: f64) -> Self {
+		Self { since, ..Default::default() }
+	}
+
+	/// Notes what a processed message means for the statistics, given the messages it queued up and the tool active while it was processed.
+	/// Returns whether the statistics should be saved now, which happens along with the less frequent document activity.
+	pub fn record(&mut self, event: UsageEvent, queued: &VecDeque<Message>, active_tool: Option<ToolType>) -> bool {
+		match event {
+			UsageEvent::Input => {
+				if let Some(now) = now_milliseconds() {
+					self.record_input(now, active_tool);
+				}
+				return false;
+			}
+			UsageEvent::DocumentCreated => self.documents.created += 1,
+			UsageEvent::DocumentOpened => self.documents.opened += 1,
+			UsageEvent::DocumentSaved => self.documents.saved += 1,
+			UsageEvent::DocumentExported => self.documents.exported += 1,
+			UsageEvent::DocumentClosed => self.documents.closed += 1,
+			UsageEvent::Other(discriminant) => {
+				// A transaction is started by the operation that queued it, which has already been counted
+				if discriminant == MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::StartTransaction)) {
+					return false;
+				}
+
+				let adds_undo_step = queued.iter().any(|message| {
+					matches!(
+						message,
+						Message::Portfolio(PortfolioMessage::Document(DocumentMessage::BackupDocument { .. } | DocumentMessage::StartTransaction))
+					)
+				});
+				if adds_undo_step {
+					*self.operations.entry(operation_name(discriminant, active_tool)).or_default() += 1;
+				}
+				return false;
+			}
+		}
+
+		true
+	}
+
+	/// Adds the time since the previous input to the active time, and to the time of the active tool, unless it was a pause.
+	fn record_input(&mut self, now: f64, active_tool: Option<ToolType>) {
+		let elapsed = self.last_input.replace(now).map_or(0., |last| now - last);
+		if elapsed <= 0. || elapsed > USAGE_IDLE_THRESHOLD_MILLISECONDS {
+			return;
+		}
+
+		self.active_milliseconds += elapsed;
+		if let Some(tool) = active_tool {
+			*self.tool_milliseconds.entry(format!("{tool:?}")).or_default() += elapsed;
+		}
+	}
+
+	/// The most frequent operations, most frequent first.
+	pub fn top_operations(&self) -> Vec<(&str, u64)> {
+		let mut operations = self.operations.iter().map(|(name, &count)| (name.as_str(), count)).collect::<Vec<_>>();
+		operations.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+		operations.truncate(USAGE_REPORTED_OPERATIONS);
+		operations
+	}
+
+	/// The tools by active time spent with them, longest first.
+	pub fn tools_by_time(&self) -> Vec<(&str, f64)> {
+		let mut tools = self.tool_milliseconds.iter().map(|(name, &milliseconds)| (name.as_str(), milliseconds)).collect::<Vec<_>>();
+		tools.sort_by(|a, b| b.1.total_cmp(&a.1));
+		tools
+	}
+}
+
+/// Names an operation after the innermost message that did it, or after the tool it was done with.
+fn operation_name(discriminant: MessageDiscriminant, active_tool: Option<ToolType>) -> String {
+	if let (MessageDiscriminant::Tool(_), Some(tool)) = (discriminant, active_tool) {
+		return format!("{tool:?} Tool");
+	}
+
+	// The debug output of a nested discriminant reads like "Portfolio(Document(FlipSelectedLayers))"
+	let nested = format!("{discriminant:?}");
+	nested.trim_end_matches(')').rsplit('(').next().unwrap_or_default().to_string()
+}
+
+/// Describes a duration in the largest units that fit, like "2 h 15 min".
+pub fn format_duration(milliseconds: f64) -> String {
+	let minutes = (milliseconds / 60_000.).floor() as u64;
+	match (minutes / 60, minutes % 60) {
+		(0, 0) => "Less than 1 min".to_string(),
+		(0, minutes) => format!("{minutes} min"),
+		(hours, minutes) => format!("{hours} h {minutes} min"),
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn pauses_are_not_counted() {
+		let mut statistics = UsageStatistics::new(0.);
+		statistics.record_input(1000., Some(ToolType::Select));
+		statistics.record_input(3000., Some(ToolType::Select));
+		statistics.record_input(4000., Some(ToolType::Pen));
+		statistics.record_input(4000. + USAGE_IDLE_THRESHOLD_MILLISECONDS + 1., Some(ToolType::Pen));
+
+		assert_eq!(statistics.active_milliseconds, 3000.);
+		assert_eq!(statistics.tool_milliseconds.get("Select"), Some(&2000.));
+		assert_eq!(statistics.tool_milliseconds.get("Pen"), Some(&1000.));
+	}
+
+	#[test]
+	fn operations_are_named_after_their_message() {
+		let discriminant = MessageDiscriminant::Portfolio(PortfolioMessageDiscriminant::Document(DocumentMessageDiscriminant::FlipSelectedLayers));
+		assert_eq!(operation_name(discriminant, None), "FlipSelectedLayers");
+		assert_eq!(format_duration(2. * 3_600_000. + 15. * 60_000.), "2 h 15 min");
+	}
+}
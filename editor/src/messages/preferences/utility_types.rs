@@ -0,0 +1,126 @@
+use crate::consts::{VIEWPORT_ZOOM_SCALE_MAX, VIEWPORT_ZOOM_SCALE_MIN};
+
+use serde::{Deserialize, Serialize};
+
+/// How numbers are written in the readouts shown to the user, like the measurements of the tools, the ruler numbers, and the number inputs.
+/// Whatever the locale, typed numbers are accepted with either a decimal point or a decimal comma.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize, specta::Type)]
+pub enum NumberLocale {
+	/// A decimal point, as in "1.5 px" and "50%".
+	#[default]
+	Point,
+	/// A decimal comma with percentages set apart by a space, as in "1,5 px" and "50 %", like most of continental Europe.
+	Comma,
+}
+
+impl NumberLocale {
+	pub fn decimal_separator(self) -> char {
+		match self {
+			NumberLocale::Point => '.',
+			NumberLocale::Comma => ',',
+		}
+	}
+
+	/// Writes the number rounded to the given number of decimal places.
+	pub fn format(self, value: f64, decimal_places: usize) -> String {
+		let formatted = format!("{value:.decimal_places$}");
+		// Rounding a tiny negative number to zero decimal places shouldn't leave a lone minus sign
+		let formatted = if formatted.trim_start_matches('-').chars().all(|character| matches!(character, '0' | '.')) {
+			formatted.trim_start_matches('-').to_string()
+		} else {
+			formatted
+		};
+
+		match self {
+			NumberLocale::Point => formatted,
+			NumberLocale::Comma => formatted.replace('.', ","),
+		}
+	}
+
+	/// Writes the number rounded to the given number of decimal places, followed by its unit, like "px", "%", or "°".
+	pub fn format_with_unit(self, value: f64, decimal_places: usize, unit: &str) -> String {
+		let number = self.format(value, decimal_places);
+		let spaced = match unit {
+			"" | "°" => false,
+			"%" => self == NumberLocale::Comma,
+			_ => true,
+		};
+
+		if spaced {
+			format!("{number} {unit}")
+		} else {
+			format!("{number}{unit}")
+		}
+	}
+}
+
+/// Reads a typed number, accepting either a decimal point or a decimal comma regardless of the locale.
+/// Whichever of the two comes last separates the decimals, and any others before it are taken to group the digits, as are spaces.
+pub fn parse_number(text: &str) -> Option<f64> {
+	let decimal_separator = text.rfind(['.', ',']);
+	let normalized = text
+		.char_indices()
+		.filter(|(_, character)| !character.is_whitespace())
+		.filter_map(|(index, character)| match character {
+			'.' | ',' => (Some(index) == decimal_separator).then_some('.'),
+			_ => Some(character),
+		})
+		.collect::<String>();
+
+	normalized.parse().ok().filter(|value: &f64| value.is_finite())
+}
+
+/// Reads the zoom levels typed as percentages separated by semicolons or spaces, like "25; 50; 100", into sorted zoom factors. Commas aren't
+/// separators since they may be typed as the decimal separator, like "12,5". Anything that isn't a percentage within the range the canvas
+/// can be zoomed to is left out, as are repeated levels.
+pub fn parse_zoom_levels(text: &str) -> Vec<f64> {
+	let mut zoom_levels = text
+		.split(|character: char| character == ';' || character.is_whitespace())
+		.filter_map(|percentage| percentage.trim_end_matches('%').replace(',', ".").parse::<f64>().ok())
+		.map(|percentage| percentage / 100.)
+		.filter(|zoom| (VIEWPORT_ZOOM_SCALE_MIN..=VIEWPORT_ZOOM_SCALE_MAX).contains(zoom))
+		.collect::<Vec<_>>();
+	zoom_levels.sort_by(f64::total_cmp);
+	zoom_levels.dedup_by(|a, b| (*a - *b).abs() < f64::EPSILON * a.max(*b));
+	zoom_levels
+}
+
+/// Writes the zoom levels as the percentages they are typed as, separated by semicolons.
+pub fn format_zoom_levels(zoom_levels: &[f64]) -> String {
+	zoom_levels.iter().map(|zoom| format!("{}", (zoom * 100. * 1e4).round() / 1e4)).collect::<Vec<_>>().join("; ")
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn format_in_each_locale() {
+		assert_eq!(NumberLocale::Point.format_with_unit(12.345, 2, "px"), "12.35 px");
+		assert_eq!(NumberLocale::Comma.format_with_unit(12.345, 2, "px"), "12,35 px");
+		assert_eq!(NumberLocale::Point.format_with_unit(50., 0, "%"), "50%");
+		assert_eq!(NumberLocale::Comma.format_with_unit(50., 0, "%"), "50 %");
+		assert_eq!(NumberLocale::Comma.format_with_unit(-0.001, 1, "°"), "0,0°");
+	}
+
+	#[test]
+	fn parse_either_separator() {
+		assert_eq!(parse_number("1.5"), Some(1.5));
+		assert_eq!(parse_number("1,5"), Some(1.5));
+		assert_eq!(parse_number(" -2,25 "), Some(-2.25));
+		assert_eq!(parse_number("1.234,5"), Some(1234.5));
+		assert_eq!(parse_number("1,234.5"), Some(1234.5));
+		assert_eq!(parse_number("1 234,5"), Some(1234.5));
+		assert_eq!(parse_number("abc"), None);
+	}
+
+	#[test]
+	fn zoom_levels_round_trip() {
+		let zoom_levels = parse_zoom_levels("400%; 100 50;25; 12.5; 100; nonsense; 0");
+		assert_eq!(zoom_levels, vec![0.125, 0.25, 0.5, 1., 4.]);
+		assert_eq!(format_zoom_levels(&zoom_levels), "12.5; 25; 50; 100; 400");
+
+		// A decimal comma is read as part of the percentage rather than separating two of them
+		assert_eq!(parse_zoom_levels("12,5% 37,5;50"), vec![0.125, 0.375, 0.5]);
+	}
+}
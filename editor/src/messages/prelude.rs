@@ -3,10 +3,16 @@ pub use crate::utility_traits::{ActionList, AsMessage, MessageHandler, ToDiscrim
 
 // Message, MessageDiscriminant, MessageHandler
 pub use crate::messages::broadcast::{BroadcastMessage, BroadcastMessageDiscriminant, BroadcastMessageHandler};
+pub use crate::messages::color_picker::{ColorPickerMessage, ColorPickerMessageDiscriminant, ColorPickerMessageHandler};
 pub use crate::messages::debug::{DebugMessage, DebugMessageDiscriminant, DebugMessageHandler};
+pub use crate::messages::dialog::batch_dialog::{BatchDialogMessage, BatchDialogMessageDiscriminant, BatchDialogMessageHandler};
+pub use crate::messages::dialog::data_merge_dialog::{DataMergeDialogMessage, DataMergeDialogMessageDiscriminant, DataMergeDialogMessageHandler};
 pub use crate::messages::dialog::export_dialog::{ExportDialogMessage, ExportDialogMessageDiscriminant, ExportDialogMessageHandler};
+pub use crate::messages::dialog::hatch_fill_dialog::{HatchFillDialogMessage, HatchFillDialogMessageDiscriminant, HatchFillDialogMessageHandler};
 pub use crate::messages::dialog::new_document_dialog::{NewDocumentDialogMessage, NewDocumentDialogMessageDiscriminant, NewDocumentDialogMessageHandler};
 pub use crate::messages::dialog::preferences_dialog::{PreferencesDialogMessage, PreferencesDialogMessageDiscriminant, PreferencesDialogMessageHandler};
+pub use crate::messages::dialog::replace_fonts_dialog::{ReplaceFontsDialogMessage, ReplaceFontsDialogMessageDiscriminant, ReplaceFontsDialogMessageHandler};
+pub use crate::messages::dialog::sprite_sheet_dialog::{SpriteSheetDialogMessage, SpriteSheetDialogMessageDiscriminant, SpriteSheetDialogMessageHandler};
 pub use crate::messages::dialog::{DialogMessage, DialogMessageDiscriminant, DialogMessageHandler};
 pub use crate::messages::frontend::{FrontendMessage, FrontendMessageDiscriminant};
 pub use crate::messages::globals::{GlobalsMessage, GlobalsMessageDiscriminant, GlobalsMessageHandler};
@@ -19,6 +25,7 @@ pub use crate::messages::portfolio::document::navigation::{NavigationMessage, Na
 pub use crate::messages::portfolio::document::node_graph::{GraphOperationMessage, GraphOperationMessageDiscriminant, GraphOperationMessageHandler};
 pub use crate::messages::portfolio::document::node_graph::{NodeGraphMessage, NodeGraphMessageDiscriminant, NodeGraphMessageHandler};
 pub use crate::messages::portfolio::document::overlays::{OverlaysMessage, OverlaysMessageDiscriminant, OverlaysMessageHandler};
+pub use crate::messages::portfolio::document::presentation::{PresentationMessage, PresentationMessageDiscriminant, PresentationMessageHandler};
 pub use crate::messages::portfolio::document::properties_panel::{PropertiesPanelMessage, PropertiesPanelMessageDiscriminant, PropertiesPanelMessageHandler};
 pub use crate::messages::portfolio::document::{DocumentMessage, DocumentMessageDiscriminant, DocumentMessageHandler};
 pub use crate::messages::portfolio::menu_bar::{MenuBarMessage, MenuBarMessageDiscriminant, MenuBarMessageHandler};
@@ -26,6 +33,7 @@ pub use crate::messages::portfolio::{PortfolioMessage, PortfolioMessageDiscrimin
 pub use crate::messages::preferences::{PreferencesMessage, PreferencesMessageDiscriminant, PreferencesMessageHandler};
 pub use crate::messages::tool::transform_layer::{TransformLayerMessage, TransformLayerMessageDiscriminant, TransformLayerMessageHandler};
 pub use crate::messages::tool::{ToolMessage, ToolMessageDiscriminant, ToolMessageHandler};
+pub use crate::messages::tutorial::{TutorialMessage, TutorialMessageDiscriminant, TutorialMessageHandler};
 pub use crate::messages::workspace::{WorkspaceMessage, WorkspaceMessageDiscriminant, WorkspaceMessageHandler};
 
 // Message, MessageDiscriminant
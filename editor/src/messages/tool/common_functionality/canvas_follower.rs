@@ -0,0 +1,24 @@
+use crate::messages::prelude::*;
+
+use glam::DAffine2;
+
+/// Tracks the canvas being panned or zoomed during a drag, such as when it auto-scrolls near the edge of the viewport, so viewport
+/// positions recorded earlier in the drag can be moved to stay over the same part of the document.
+#[derive(Clone, Debug, Default)]
+pub struct CanvasFollower {
+	root_transform: DAffine2,
+}
+
+impl CanvasFollower {
+	pub fn start(&mut self, document: &DocumentMessageHandler) {
+		self.root_transform = document.document_legacy.root.transform;
+	}
+
+	/// The viewport space transform that moves positions from where the canvas was at the previous call to where it is now.
+	pub fn follow(&mut self, document: &DocumentMessageHandler) -> DAffine2 {
+		let root_transform = document.document_legacy.root.transform;
+		let change = root_transform * self.root_transform.inverse();
+		self.root_transform = root_transform;
+		change
+	}
+}
@@ -12,6 +12,15 @@ pub enum ToolColorType {
 	Custom,
 }
 
+/// The part of a shape that a color is applied to.
+/// By convention, tools that draw shapes stroke them with the primary working color and fill them with the secondary one.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default, Serialize, Deserialize, specta::Type)]
+pub enum ColorSlot {
+	#[default]
+	Fill,
+	Stroke,
+}
+
 /// Color selector widgets seen in [`LayoutTarget::ToolOptions`] bar.
 pub struct ToolColorOptions {
 	pub custom_color: Option<Color>,
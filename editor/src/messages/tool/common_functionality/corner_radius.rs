@@ -0,0 +1,183 @@
+//! Handler for the corner radius handles visible inside the corners of a selected rectangle drawn by a "Rectangle Generator" whilst using the Select tool.
+//! Dragging a handle towards the middle of the rectangle rounds that corner, updating the generator's parameters rather than the shape's geometry.
+
+use crate::application::generate_uuid;
+use crate::consts::PIVOT_OUTER_OUTLINE_THICKNESS;
+use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::hit_targets::corner_radius_handle_sizes;
+
+use document_legacy::layers::style;
+use document_legacy::{LayerId, Operation};
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::NodeInput;
+use graphene_core::vector::generator_nodes::fit_corner_radii;
+
+use glam::{DAffine2, DVec2};
+
+/// The corners of the unit square drawn by the generator, clockwise from the top left, with the direction pointing into the rectangle from each.
+const CORNERS: [(DVec2, DVec2); 4] = [
+	(DVec2::new(0., 0.), DVec2::new(1., 1.)),
+	(DVec2::new(1., 0.), DVec2::new(-1., 1.)),
+	(DVec2::new(1., 1.), DVec2::new(-1., -1.)),
+	(DVec2::new(0., 1.), DVec2::new(1., -1.)),
+];
+
+#[derive(Clone, Debug, Default)]
+pub struct CornerRadiusHandles {
+	/// The selected rectangle, if exactly one layer is selected and it is drawn by a "Rectangle Generator"
+	layer_path: Option<Vec<LayerId>>,
+	/// Transform from the generator's unit square to the viewport
+	transform: DAffine2,
+	/// The size of the rectangle in the units of its layer's parent, which its corner radii are relative to
+	size: DVec2,
+	radii: [f64; 4],
+	/// The corner whose handle is being dragged
+	dragging: Option<usize>,
+	/// A reference to the previous overlays so we can destroy them
+	overlays: Vec<Vec<LayerId>>,
+}
+
+impl CornerRadiusHandles {
+	/// Reads the corner radii of the selected rectangle and redraws its handles, or removes them if no single rectangle is selected.
+	pub fn update(&mut self, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
+		self.clear_overlays(responses);
+
+		let mut selected = document.selected_visible_layers();
+		let (Some(layer_path), None) = (selected.next(), selected.next()) else {
+			self.layer_path = None;
+			return;
+		};
+		let Some(radii) = rectangle_corner_radii(document, layer_path) else {
+			self.layer_path = None;
+			return;
+		};
+
+		self.layer_path = Some(layer_path.to_vec());
+		self.transform = document.document_legacy.multiply_transforms(layer_path).unwrap_or_default();
+		// Like the generator's size, which is kept up to date as the layer is transformed, this is how far the layer's own transform stretches the unit square
+		let layer_transform = document.document_legacy.layer(layer_path).map(|layer| layer.transform).unwrap_or_default();
+		self.size = DVec2::new(layer_transform.matrix2.x_axis.length(), layer_transform.matrix2.y_axis.length());
+		self.radii = radii;
+
+		self.redraw(responses);
+	}
+
+	/// The viewport position of a corner's handle, which sits at the center of the corner's arc unless that would be too close to the corner to grab.
+	fn handle_position(&self, corner: usize) -> DVec2 {
+		let (position, inwards) = CORNERS[corner];
+		let corner_position = self.transform.transform_point2(position);
+		let radii = fit_corner_radii(self.size, self.radii);
+		let direction = self.transform.transform_vector2(inwards / self.size);
+
+		let (_, min_inset) = corner_radius_handle_sizes();
+		let offset = direction * radii[corner];
+		if offset.length() >= min_inset {
+			corner_position + offset
+		} else {
+			corner_position + direction.normalize_or_zero() * min_inset
+		}
+	}
+
+	fn redraw(&mut self, responses: &mut VecDeque<Message>) {
+		// Handles on a rectangle too small to show them all apart from each other would only get in the way of moving it
+		let (handle_size, min_inset) = corner_radius_handle_sizes();
+		let viewport_size = self.transform.transform_vector2(DVec2::X).length().min(self.transform.transform_vector2(DVec2::Y).length());
+		if viewport_size < min_inset * 4. {
+			return;
+		}
+
+		for corner in 0..CORNERS.len() {
+			let path = vec![generate_uuid()];
+			responses.add(DocumentMessage::Overlays(
+				Operation::AddEllipse {
+					path: path.clone(),
+					transform: DAffine2::IDENTITY.to_cols_array(),
+					style: style::PathStyle::new(
+						Some(style::Stroke::new(Some(overlay_colors().handles), PIVOT_OUTER_OUTLINE_THICKNESS)),
+						style::Fill::Solid(graphene_core::raster::color::Color::WHITE),
+					),
+					insert_index: -1,
+				}
+				.into(),
+			));
+
+			let diameter = handle_size - PIVOT_OUTER_OUTLINE_THICKNESS;
+			let translation = self.handle_position(corner) - DVec2::splat(diameter / 2.);
+			let transform = DAffine2::from_scale_angle_translation(DVec2::splat(diameter), 0., translation).to_cols_array();
+			responses.add(DocumentMessage::Overlays(Operation::TransformLayerInViewport { path: path.clone(), transform }.into()));
+
+			self.overlays.push(path);
+		}
+	}
+
+	pub fn clear_overlays(&mut self, responses: &mut VecDeque<Message>) {
+		for path in self.overlays.drain(..) {
+			responses.add(DocumentMessage::Overlays(Operation::DeleteLayer { path }.into()));
+		}
+	}
+
+	/// The corner whose handle is under the pointer, if any.
+	pub fn corner_under(&self, mouse: DVec2) -> Option<usize> {
+		if self.overlays.is_empty() {
+			return None;
+		}
+
+		let (handle_size, _) = corner_radius_handle_sizes();
+		(0..CORNERS.len()).find(|&corner| self.handle_position(corner).distance_squared(mouse) < handle_size.powi(2))
+	}
+
+	pub fn start_drag(&mut self, corner: usize) {
+		self.dragging = Some(corner);
+	}
+
+	/// Rounds the dragged corner, or all the corners at once, by as much as the pointer has been dragged into the rectangle along the corner's diagonal.
+	pub fn drag(&mut self, mouse: DVec2, all_corners: bool, responses: &mut VecDeque<Message>) {
+		let (Some(corner), Some(layer)) = (self.dragging, self.layer_path.clone()) else { return };
+
+		// The handle of a corner with a radius of one document unit would be this far into the rectangle from the corner
+		let (position, inwards) = CORNERS[corner];
+		let direction = self.transform.transform_vector2(inwards / self.size);
+		if direction.length_squared() <= f64::EPSILON {
+			return;
+		}
+		let radius = ((mouse - self.transform.transform_point2(position)).dot(direction) / direction.length_squared()).max(0.);
+		let radius = radius.min(self.size.x.min(self.size.y) / 2.);
+
+		if all_corners {
+			self.radii = [radius; 4];
+		} else {
+			self.radii[corner] = radius;
+		}
+
+		responses.add(GraphOperationMessage::RectangleCornersSet {
+			layer,
+			radii: self.radii,
+			size: self.size,
+		});
+		self.clear_overlays(responses);
+		self.redraw(responses);
+	}
+
+	pub fn stop_drag(&mut self) {
+		self.dragging = None;
+	}
+}
+
+/// The corner radii of a layer drawn by a "Rectangle Generator", clockwise from the top left.
+fn rectangle_corner_radii(document: &DocumentMessageHandler, layer_path: &[LayerId]) -> Option<[f64; 4]> {
+	let network = document.document_legacy.layer(layer_path).ok()?.as_layer_network().ok()?;
+	let (node, _) = network.primary_flow().find(|(node, _)| node.name == "Rectangle Generator")?;
+
+	let mut radii = [0.; 4];
+	for (radius, input) in radii.iter_mut().zip(node.inputs.get(2..6)?) {
+		let NodeInput::Value {
+			tagged_value: TaggedValue::F64(value),
+			..
+		} = input
+		else {
+			return None;
+		};
+		*radius = *value;
+	}
+	Some(radii)
+}
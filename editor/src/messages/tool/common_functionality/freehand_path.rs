@@ -0,0 +1,166 @@
+//! Turns the pointer samples of a freehand drag into a smooth path, optionally with a width that follows the stylus pressure or, for those
+//! drawing with a mouse, the speed of the pointer.
+
+use crate::consts::FREEHAND_SPEED_DYNAMIC_HALF_SPEED;
+
+use graphene_core::uuid::ManipulatorGroupId;
+
+use bezier_rs::{ManipulatorGroup, Subpath};
+use glam::DVec2;
+
+/// The indices of the points kept when the polyline is simplified so that no removed point is farther than `tolerance` from it,
+/// using the Ramer-Douglas-Peucker algorithm. The first and last points are always kept.
+pub fn simplify(points: &[DVec2], tolerance: f64) -> Vec<usize> {
+	if points.len() < 3 {
+		return (0..points.len()).collect();
+	}
+
+	let mut kept = vec![false; points.len()];
+	kept[0] = true;
+	kept[points.len() - 1] = true;
+
+	let mut ranges = vec![(0, points.len() - 1)];
+	while let Some((start, end)) = ranges.pop() {
+		let farthest = (start + 1..end)
+			.map(|index| (index, distance_to_segment(points[index], points[start], points[end])))
+			.max_by(|(_, a), (_, b)| a.total_cmp(b));
+
+		if let Some((index, distance)) = farthest {
+			if distance > tolerance {
+				kept[index] = true;
+				ranges.push((start, index));
+				ranges.push((index, end));
+			}
+		}
+	}
+
+	kept.iter().enumerate().filter_map(|(index, &kept)| kept.then_some(index)).collect()
+}
+
+fn distance_to_segment(point: DVec2, start: DVec2, end: DVec2) -> f64 {
+	let segment = end - start;
+	let length_squared = segment.length_squared();
+	if length_squared == 0. {
+		return point.distance(start);
+	}
+	let t = ((point - start).dot(segment) / length_squared).clamp(0., 1.);
+	point.distance(start + segment * t)
+}
+
+/// The in and out handles of each point which make a Catmull-Rom spline passing smoothly through all of the points.
+/// The ends of an open path have no handles on their outer sides.
+pub fn spline_handles(points: &[DVec2], closed: bool) -> Vec<(Option<DVec2>, Option<DVec2>)> {
+	let count = points.len();
+	(0..count)
+		.map(|index| {
+			let previous = match (index, closed) {
+				(0, true) => points[count - 1],
+				(0, false) => points[0],
+				_ => points[index - 1],
+			};
+			let next = match (index + 1 == count, closed) {
+				(true, true) => points[0],
+				(true, false) => points[index],
+				_ => points[index + 1],
+			};
+			let tangent = (next - previous) / 6.;
+
+			let in_handle = (closed || index > 0).then_some(points[index] - tangent);
+			let out_handle = (closed || index + 1 < count).then_some(points[index] + tangent);
+			(in_handle, out_handle)
+		})
+		.collect()
+}
+
+/// A smooth curve passing through each of the points.
+pub fn smooth_subpath(points: &[DVec2], closed: bool) -> Subpath<ManipulatorGroupId> {
+	let closed = closed && points.len() > 1;
+	let manipulator_groups = points
+		.iter()
+		.zip(spline_handles(points, closed))
+		.map(|(&anchor, (in_handle, out_handle))| ManipulatorGroup::new(anchor, in_handle, out_handle))
+		.collect();
+
+	Subpath::new(manipulator_groups, closed)
+}
+
+/// The fraction of the full width that the speed dynamic leaves the line drawn at the given pointer speed, in viewport pixels per millisecond,
+/// which thins faster strokes like a pen pressed more lightly. A `strength` of 1 thins the line away entirely as the speed approaches infinity.
+pub fn speed_width_factor(speed: f64, strength: f64) -> f64 {
+	let thinning = speed.max(0.) / (speed.max(0.) + FREEHAND_SPEED_DYNAMIC_HALF_SPEED);
+	1. - strength.clamp(0., 1.) * thinning
+}
+
+/// The outline of a stroke along the polyline with the given width at each point, running forward along one side and back along the other.
+/// Returns no points unless there are at least two points with a width for each.
+pub fn variable_width_outline(points: &[DVec2], widths: &[f64]) -> Vec<DVec2> {
+	if points.len() < 2 || points.len() != widths.len() {
+		return Vec::new();
+	}
+
+	let offsets = (0..points.len())
+		.map(|index| {
+			let previous = points[index.saturating_sub(1)];
+			let next = points[(index + 1).min(points.len() - 1)];
+			(next - previous).normalize_or_zero().perp() * widths[index].max(0.) / 2.
+		})
+		.collect::<Vec<_>>();
+
+	let left = points.iter().zip(&offsets).map(|(&point, &offset)| point + offset);
+	let right = points.iter().zip(&offsets).rev().map(|(&point, &offset)| point - offset);
+	left.chain(right).collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn simplify_removes_points_close_to_the_line() {
+		let points = [
+			DVec2::new(0., 0.),
+			DVec2::new(5., 0.1),
+			DVec2::new(10., 0.),
+			DVec2::new(10., 10.),
+			DVec2::new(10.2, 15.),
+			DVec2::new(10., 20.),
+		];
+
+		assert_eq!(simplify(&points, 0.5), vec![0, 2, 5]);
+		assert_eq!(simplify(&points, 0.01), vec![0, 1, 2, 3, 4, 5]);
+		assert_eq!(simplify(&points[..2], 100.), vec![0, 1]);
+	}
+
+	#[test]
+	fn spline_passes_through_the_points() {
+		let points = [DVec2::new(0., 0.), DVec2::new(10., 0.), DVec2::new(20., 10.)];
+		let handles = spline_handles(&points, false);
+
+		assert_eq!(handles[0], (None, Some(DVec2::new(10. / 6., 0.))));
+		assert_eq!(handles[1], (Some(DVec2::new(10. - 20. / 6., -10. / 6.)), Some(DVec2::new(10. + 20. / 6., 10. / 6.))));
+		assert_eq!(handles[2].1, None);
+
+		let closed = spline_handles(&points, true);
+		assert!(closed.iter().all(|(in_handle, out_handle)| in_handle.is_some() && out_handle.is_some()));
+	}
+
+	#[test]
+	fn outline_width_follows_the_pressure() {
+		let points = [DVec2::new(0., 0.), DVec2::new(10., 0.), DVec2::new(20., 0.)];
+		let outline = variable_width_outline(&points, &[2., 4., 0.]);
+
+		assert_eq!(outline.len(), 6);
+		assert_eq!(outline[..3], [DVec2::new(0., 1.), DVec2::new(10., 2.), DVec2::new(20., 0.)]);
+		assert_eq!(outline[3..], [DVec2::new(20., 0.), DVec2::new(10., -2.), DVec2::new(0., -1.)]);
+		assert!(variable_width_outline(&points[..1], &[1.]).is_empty());
+	}
+
+	#[test]
+	fn faster_strokes_are_thinner() {
+		assert_eq!(speed_width_factor(0., 1.), 1.);
+		assert_eq!(speed_width_factor(FREEHAND_SPEED_DYNAMIC_HALF_SPEED, 1.), 0.5);
+		assert_eq!(speed_width_factor(FREEHAND_SPEED_DYNAMIC_HALF_SPEED, 0.5), 0.75);
+		assert_eq!(speed_width_factor(100., 0.), 1.);
+		assert!(speed_width_factor(10., 1.) < speed_width_factor(5., 1.));
+	}
+}
@@ -4,7 +4,7 @@ use crate::messages::prelude::*;
 
 use bezier_rs::{ManipulatorGroup, Subpath};
 use document_legacy::{LayerId, Operation};
-use graph_craft::document::NodeNetwork;
+use graph_craft::document::{NodeInput, NodeNetwork};
 use graphene_core::uuid::ManipulatorGroupId;
 
 use glam::DAffine2;
@@ -16,11 +16,32 @@ pub fn new_vector_layer(subpaths: Vec<Subpath<ManipulatorGroupId>>, layer_path:
 	new_custom_layer(network, layer_path, responses);
 }
 
+/// Create a new vector layer at the given position in its folder, where negative indices count from the top.
+pub fn new_vector_layer_at(subpaths: Vec<Subpath<ManipulatorGroupId>>, layer_path: Vec<LayerId>, insert_index: isize, responses: &mut VecDeque<Message>) {
+	let network = node_graph::new_vector_network(subpaths);
+	new_custom_layer_at(network, layer_path, insert_index, responses);
+}
+
+/// Create a new vector layer drawn by the named generator node, overriding its default parameters with any given inputs.
+pub fn new_generated_vector_layer(generator: &str, input_override: impl IntoIterator<Item = Option<NodeInput>>, layer_path: Vec<LayerId>, responses: &mut VecDeque<Message>) {
+	new_generated_vector_layer_at(generator, input_override, layer_path, -1, responses);
+}
+
+/// Create a new vector layer drawn by the named generator node at the given position in its folder, where negative indices count from the top.
+pub fn new_generated_vector_layer_at(generator: &str, input_override: impl IntoIterator<Item = Option<NodeInput>>, layer_path: Vec<LayerId>, insert_index: isize, responses: &mut VecDeque<Message>) {
+	let network = node_graph::new_generated_vector_network(generator, input_override);
+	new_custom_layer_at(network, layer_path, insert_index, responses);
+}
+
 pub fn new_custom_layer(network: NodeNetwork, layer_path: Vec<LayerId>, responses: &mut VecDeque<Message>) {
+	new_custom_layer_at(network, layer_path, -1, responses);
+}
+
+pub fn new_custom_layer_at(network: NodeNetwork, layer_path: Vec<LayerId>, insert_index: isize, responses: &mut VecDeque<Message>) {
 	responses.add(DocumentMessage::DeselectAllLayers);
 	responses.add(Operation::AddFrame {
 		path: layer_path.clone(),
-		insert_index: -1,
+		insert_index,
 		transform: DAffine2::ZERO.to_cols_array(),
 		network,
 	});
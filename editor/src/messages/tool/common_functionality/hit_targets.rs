@@ -0,0 +1,88 @@
+//! Sizes of the handles drawn over the canvas and of the areas around them that respond to the pointer.
+//!
+//! These are all measured in viewport (screen) pixels so they look and feel the same at every zoom level.
+//! Code working in document or layer space should convert them with [viewport_distance_in_local_space] rather than using them directly.
+//! They are also multiplied by the scale factor of the viewport, so they stay the same physical size on high-DPI monitors of native builds.
+
+use crate::consts::{
+	BOUNDS_ROTATE_THRESHOLD, BOUNDS_SELECT_THRESHOLD, CORNER_RADIUS_HANDLE_MIN_INSET, CORNER_RADIUS_HANDLE_SIZE, DRAG_THRESHOLD, HIDE_HANDLE_DISTANCE, LARGER_HANDLES_SCALE,
+	MANIPULATOR_GROUP_MARKER_SIZE, PIVOT_INNER, PIVOT_OUTER, SELECTION_THRESHOLD, SELECTION_TOLERANCE, SNAP_AXIS_TOLERANCE, SNAP_POINT_TOLERANCE,
+};
+use crate::messages::prelude::*;
+
+use document_legacy::intersection::Quad;
+
+use glam::{DAffine2, DVec2};
+
+/// How much the handles and hit targets are enlarged by the "Larger Handles" preference and the scale factor of the viewport.
+pub fn handle_scale() -> f64 {
+	let preference_scale = if larger_handles() { LARGER_HANDLES_SCALE } else { 1. };
+	preference_scale * viewport_scale_factor()
+}
+
+/// Width and height of the square and circular markers for anchors, handles, gradient stops, and the transform cage corners.
+pub fn marker_size() -> f64 {
+	MANIPULATOR_GROUP_MARKER_SIZE * handle_scale()
+}
+
+/// Diameters of the outer ring and the inner dot of the pivot marker.
+pub fn pivot_sizes() -> (f64, f64) {
+	(PIVOT_OUTER * handle_scale(), PIVOT_INNER * handle_scale())
+}
+
+/// Diameter of the corner radius handles, and how far inside its corner each is kept while the corner isn't rounded enough to move it further.
+pub fn corner_radius_handle_sizes() -> (f64, f64) {
+	(CORNER_RADIUS_HANDLE_SIZE * handle_scale(), CORNER_RADIUS_HANDLE_MIN_INSET * handle_scale())
+}
+
+/// Distance from the pointer within which a click hits a layer or a path segment.
+pub fn selection_tolerance() -> f64 {
+	SELECTION_TOLERANCE * handle_scale()
+}
+
+/// Distance from the pointer within which a click grabs an anchor, a handle, or a gradient line.
+pub fn selection_threshold() -> f64 {
+	SELECTION_THRESHOLD * handle_scale()
+}
+
+/// How far the pointer may be from an edge of the transform cage to resize it.
+pub fn bounds_select_threshold() -> f64 {
+	BOUNDS_SELECT_THRESHOLD * handle_scale()
+}
+
+/// How far outside the transform cage the pointer may be to rotate it.
+pub fn bounds_rotate_threshold() -> f64 {
+	BOUNDS_ROTATE_THRESHOLD * handle_scale()
+}
+
+/// Handles closer than this to their anchor are hidden, since they would be drawn on top of it.
+pub fn hide_handle_distance() -> f64 {
+	HIDE_HANDLE_DISTANCE * handle_scale()
+}
+
+/// How far the pointer must move while a button is held before it counts as a drag rather than a click.
+pub fn drag_threshold() -> f64 {
+	DRAG_THRESHOLD * viewport_scale_factor()
+}
+
+/// Distance from the pointer within which it snaps to a point.
+pub fn snap_point_tolerance() -> f64 {
+	SNAP_POINT_TOLERANCE * viewport_scale_factor()
+}
+
+/// Distance from the pointer along each axis within which it snaps to the alignment of another layer.
+pub fn snap_axis_tolerance() -> f64 {
+	SNAP_AXIS_TOLERANCE * viewport_scale_factor()
+}
+
+/// The square around a viewport position used to test which layers the pointer is over.
+pub fn pointer_quad(viewport_position: DVec2) -> Quad {
+	let tolerance = DVec2::splat(selection_tolerance());
+	Quad::from_box([viewport_position - tolerance, viewport_position + tolerance])
+}
+
+/// Converts a distance in viewport pixels into the local units along each axis of the space that `to_viewport` maps onto the viewport.
+pub fn viewport_distance_in_local_space(to_viewport: DAffine2, pixels: f64) -> DVec2 {
+	let axis_scale = DVec2::new(to_viewport.matrix2.x_axis.length(), to_viewport.matrix2.y_axis.length());
+	DVec2::splat(pixels) / axis_scale.max(DVec2::splat(f64::EPSILON))
+}
@@ -1,9 +1,18 @@
+pub mod canvas_follower;
 pub mod color_selector;
+pub mod corner_radius;
+pub mod freehand_path;
 pub mod graph_modification_utils;
+pub mod hit_targets;
 pub mod overlay_renderer;
 pub mod path_outline;
 pub mod pivot;
+pub mod quick_mask;
 pub mod resize;
+pub mod sampled_color;
 pub mod shape_editor;
+pub mod shape_presets;
 pub mod snapping;
+pub mod stabilizer;
+pub mod stroke_options;
 pub mod transformation_cage;
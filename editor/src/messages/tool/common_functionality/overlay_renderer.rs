@@ -1,8 +1,9 @@
 use super::shape_editor::SelectedShapeState;
 use crate::application::generate_uuid;
+use crate::consts::PATH_OUTLINE_WEIGHT;
 use crate::consts::VIEWPORT_GRID_ROUNDING_BIAS;
-use crate::consts::{COLOR_ACCENT, HIDE_HANDLE_DISTANCE, MANIPULATOR_GROUP_MARKER_SIZE, PATH_OUTLINE_WEIGHT};
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::hit_targets::{hide_handle_distance, marker_size};
 
 use bezier_rs::ManipulatorGroup;
 use document_legacy::document::Document;
@@ -75,7 +76,7 @@ impl OverlayRenderer {
 						let anchor = manipulator_group.anchor;
 
 						let anchor_position = transform.transform_point2(anchor);
-						let not_under_anchor = |&position: &DVec2| transform.transform_point2(position).distance_squared(anchor_position) >= HIDE_HANDLE_DISTANCE * HIDE_HANDLE_DISTANCE;
+						let not_under_anchor = |&position: &DVec2| transform.transform_point2(position).distance_squared(anchor_position) >= hide_handle_distance().powi(2);
 						let filter_handle = |manipulator: Option<DVec2>| manipulator.filter(not_under_anchor);
 						[filter_handle(manipulator_group.in_handle), filter_handle(manipulator_group.out_handle)]
 					};
@@ -172,7 +173,7 @@ impl OverlayRenderer {
 		let operation = Operation::AddShape {
 			path: layer_path.clone(),
 			subpath,
-			style: style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), PATH_OUTLINE_WEIGHT)), Fill::None),
+			style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().selection_outline), PATH_OUTLINE_WEIGHT)), Fill::None),
 			insert_index: -1,
 			transform: DAffine2::IDENTITY.to_cols_array(),
 		};
@@ -187,7 +188,7 @@ impl OverlayRenderer {
 		let operation = Operation::AddRect {
 			path: layer_path.clone(),
 			transform: DAffine2::IDENTITY.to_cols_array(),
-			style: style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), 2.0)), Fill::solid(Color::WHITE)),
+			style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().handles), 2.0)), Fill::solid(Color::WHITE)),
 			insert_index: -1,
 		};
 		responses.add(DocumentMessage::Overlays(operation.into()));
@@ -200,7 +201,7 @@ impl OverlayRenderer {
 		let operation = Operation::AddEllipse {
 			path: layer_path.clone(),
 			transform: DAffine2::IDENTITY.to_cols_array(),
-			style: style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), 2.0)), Fill::solid(Color::WHITE)),
+			style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().handles), 2.0)), Fill::solid(Color::WHITE)),
 			insert_index: -1,
 		};
 		responses.add(DocumentMessage::Overlays(operation.into()));
@@ -225,7 +226,7 @@ impl OverlayRenderer {
 		let operation = Operation::AddLine {
 			path: layer_path.clone(),
 			transform: DAffine2::IDENTITY.to_cols_array(),
-			style: style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), 1.0)), Fill::None),
+			style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().handles), 1.0)), Fill::None),
 			insert_index: -1,
 		};
 		responses.add_front(DocumentMessage::Overlays(operation.into()));
@@ -262,7 +263,7 @@ impl OverlayRenderer {
 
 			let marker_overlay = marker_source.take().unwrap_or_else(|| Self::create_handle_overlay(responses));
 
-			let scale = DVec2::splat(MANIPULATOR_GROUP_MARKER_SIZE);
+			let scale = DVec2::splat(marker_size());
 			let angle = 0.;
 			let translation = (parent_transform.transform_point2(handle_position) - (scale / 2.) + VIEWPORT_GRID_ROUNDING_BIAS).round();
 			let transform = DAffine2::from_scale_angle_translation(scale, angle, translation).to_cols_array();
@@ -282,7 +283,7 @@ impl OverlayRenderer {
 
 		// Place the anchor point overlay
 		if let Some(anchor_overlay) = &overlays.anchor {
-			let scale = DVec2::splat(MANIPULATOR_GROUP_MARKER_SIZE);
+			let scale = DVec2::splat(marker_size());
 			let angle = 0.;
 			let translation = (parent_transform.transform_point2(anchor) - (scale / 2.) + VIEWPORT_GRID_ROUNDING_BIAS).round();
 			let transform = DAffine2::from_scale_angle_translation(scale, angle, translation).to_cols_array();
@@ -340,8 +341,9 @@ impl OverlayRenderer {
 	/// Sets the overlay style for this point.
 	fn style_overlays(state: &SelectedShapeState, layer_path: &[LayerId], manipulator_group: &GraphiteManipulatorGroup, overlays: &ManipulatorGroupOverlays, responses: &mut VecDeque<Message>) {
 		// TODO Move the style definitions out of the Subpath, should be looked up from a stylesheet or similar
-		let selected_style = style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), POINT_STROKE_WEIGHT + 1.0)), Fill::solid(COLOR_ACCENT));
-		let deselected_style = style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), POINT_STROKE_WEIGHT)), Fill::solid(Color::WHITE));
+		let handles = overlay_colors().handles;
+		let selected_style = style::PathStyle::new(Some(Stroke::new(Some(handles), POINT_STROKE_WEIGHT + 1.0)), Fill::solid(handles));
+		let deselected_style = style::PathStyle::new(Some(Stroke::new(Some(handles), POINT_STROKE_WEIGHT)), Fill::solid(Color::WHITE));
 		let selected_shape_state = state.get(layer_path);
 		// Update if the manipulator points are shown as selected
 		// Here the index is important, even though overlays[..] has five elements we only care about the first three
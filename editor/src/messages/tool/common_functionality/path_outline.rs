@@ -1,14 +1,18 @@
 use crate::application::generate_uuid;
-use crate::consts::{COLOR_ACCENT, PATH_OUTLINE_WEIGHT, SELECTION_TOLERANCE};
+use crate::consts::{PATH_OUTLINE_SIMPLIFICATION_TOLERANCE, PATH_OUTLINE_WEIGHT};
+use crate::messages::portfolio::document::utility_types::vector_export::flatten_subpath;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::freehand_path::simplify;
+use crate::messages::tool::common_functionality::hit_targets::pointer_quad;
 
-use document_legacy::intersection::Quad;
-use document_legacy::layers::layer_info::LayerDataType;
+use document_legacy::layers::layer_info::{Layer, LayerDataType};
 use document_legacy::layers::style::{self, Fill, RenderData, Stroke};
 use document_legacy::{LayerId, Operation};
+use graphene_core::uuid::ManipulatorGroupId;
 use graphene_std::vector::subpath::Subpath;
 
 use glam::{DAffine2, DVec2};
+use std::collections::HashMap;
 
 /// Manages the overlay used by the select tool for outlining selected shapes and when hovering over a non selected shape.
 #[derive(Clone, Debug, Default)]
@@ -16,34 +20,102 @@ pub struct PathOutline {
 	hovered_layer_path: Option<Vec<LayerId>>,
 	hovered_overlay_path: Option<Vec<LayerId>>,
 	selected_overlay_paths: Vec<Vec<LayerId>>,
+	/// The outlines last drawn for the selected and hovered layers, so they aren't simplified again each time the document changes
+	outline_cache: HashMap<Vec<LayerId>, CachedOutline>,
+}
+
+#[derive(Clone, Debug)]
+struct CachedOutline {
+	/// The paths the outline was traced from, in the space of the layer
+	source: Vec<bezier_rs::Subpath<ManipulatorGroupId>>,
+	simplification: Option<i32>,
+	outline: Subpath,
+}
+
+/// The paths of the layer in its own space, or those of the visible layers within it for a folder, so the outline shows the artwork itself rather than its bounds.
+fn layer_paths(layer: &Layer) -> Vec<bezier_rs::Subpath<ManipulatorGroupId>> {
+	match &layer.data {
+		LayerDataType::Shape(shape) => shape.shape.to_bezier_rs(),
+		LayerDataType::Layer(layer) => match layer.as_vector_data() {
+			// Vector graph output
+			Some(vector_data) => vector_data.subpaths.clone(),
+			// Frame graph output
+			None => vec![bezier_rs::Subpath::new_rect(DVec2::ZERO, DVec2::ONE)],
+		},
+		LayerDataType::Folder(folder) => folder
+			.layers()
+			.iter()
+			.filter(|child| child.visible)
+			.flat_map(|child| {
+				let mut subpaths = layer_paths(child);
+				subpaths.iter_mut().for_each(|subpath| subpath.apply_transform(child.transform));
+				subpaths
+			})
+			.collect(),
+	}
+}
+
+/// How coarsely the outline of a layer shown at `scale` viewport pixels per unit of its own space is simplified, counted in halvings of the scale below 1.
+/// Outlines shown at 100% or larger are drawn exactly, since their curves would be visibly faceted.
+fn simplification_level(scale: f64) -> Option<i32> {
+	(scale > 0. && scale < 1.).then(|| (-scale.log2()).ceil() as i32)
+}
+
+/// Replaces the curves of the paths with polylines having as few points as possible while staying within `tolerance` of them.
+fn simplify_paths(subpaths: &[bezier_rs::Subpath<ManipulatorGroupId>], tolerance: f64) -> Vec<bezier_rs::Subpath<ManipulatorGroupId>> {
+	subpaths
+		.iter()
+		.map(|subpath| {
+			let (points, closed) = flatten_subpath(subpath, tolerance);
+			let kept = simplify(&points, tolerance);
+			bezier_rs::Subpath::from_anchors(kept.into_iter().map(|index| points[index]), closed)
+		})
+		.collect()
 }
 
 impl PathOutline {
+	/// The outline of the layer in its own space, simplified when the layer is shown small, which is reused from the last time if its paths haven't changed.
+	fn outline(&mut self, document_layer: &Layer, document_layer_path: &[LayerId], transform: DAffine2) -> Option<Subpath> {
+		let source = layer_paths(document_layer);
+		if source.is_empty() {
+			return None;
+		}
+
+		let simplification = simplification_level(transform.matrix2.determinant().abs().sqrt());
+		if let Some(cached) = self.outline_cache.get(document_layer_path) {
+			if cached.simplification == simplification && cached.source == source {
+				return Some(cached.outline.clone());
+			}
+		}
+
+		let outline = match simplification {
+			Some(level) => Subpath::from_bezier_rs(&simplify_paths(&source, PATH_OUTLINE_SIMPLIFICATION_TOLERANCE * 2_f64.powi(level))),
+			None => Subpath::from_bezier_rs(&source),
+		};
+		let cached = CachedOutline {
+			source,
+			simplification,
+			outline: outline.clone(),
+		};
+		self.outline_cache.insert(document_layer_path.to_vec(), cached);
+
+		Some(outline)
+	}
+
 	/// Creates an outline of a layer either with a pre-existing overlay or by generating a new one
 	fn try_create_outline(
+		&mut self,
 		document_layer_path: Vec<LayerId>,
 		overlay_path: Option<Vec<LayerId>>,
 		document: &DocumentMessageHandler,
 		responses: &mut VecDeque<Message>,
-		render_data: &RenderData,
 	) -> Option<Vec<LayerId>> {
 		// Get layer data
 		let document_layer = document.document_legacy.layer(&document_layer_path).ok()?;
+		let transform = document.document_legacy.multiply_transforms(&document_layer_path).ok()?;
 
-		// Get the subpath from the shape
-		let subpath = match &document_layer.data {
-			LayerDataType::Shape(shape) => Some(shape.shape.clone()),
-			LayerDataType::Layer(layer) => {
-				if let Some(vector_data) = layer.as_vector_data() {
-					// Vector graph output
-					Some(Subpath::from_bezier_rs(&vector_data.subpaths))
-				} else {
-					// Frame graph output
-					Some(Subpath::new_rect(DVec2::new(0., 0.), DVec2::new(1., 1.)))
-				}
-			}
-			_ => document_layer.aabb_for_transform(DAffine2::IDENTITY, render_data).map(|[p1, p2]| Subpath::new_rect(p1, p2)),
-		}?;
+		// Trace the outline from the paths of the layer
+		let subpath = self.outline(document_layer, &document_layer_path, transform)?;
 
 		// Generate a new overlay layer if necessary
 		let overlay = overlay_path.unwrap_or_else(|| {
@@ -53,7 +125,7 @@ impl PathOutline {
 				(Operation::AddShape {
 					path: overlay_path.clone(),
 					subpath: Default::default(),
-					style: style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), PATH_OUTLINE_WEIGHT)), Fill::None),
+					style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().selection_outline), PATH_OUTLINE_WEIGHT)), Fill::None),
 					insert_index: -1,
 					transform: DAffine2::IDENTITY.to_cols_array(),
 				})
@@ -70,7 +142,7 @@ impl PathOutline {
 		responses.add(DocumentMessage::Overlays(
 			(Operation::SetLayerTransform {
 				path: overlay.clone(),
-				transform: document.document_legacy.multiply_transforms(&document_layer_path).unwrap().to_cols_array(),
+				transform: transform.to_cols_array(),
 			})
 			.into(),
 		));
@@ -81,15 +153,9 @@ impl PathOutline {
 	/// Creates an outline of a layer either with a pre-existing overlay or by generating a new one.
 	///
 	/// Creates an outline, discarding the overlay on failure.
-	fn create_outline(
-		document_layer_path: Vec<LayerId>,
-		overlay_path: Option<Vec<LayerId>>,
-		document: &DocumentMessageHandler,
-		responses: &mut VecDeque<Message>,
-		render_data: &RenderData,
-	) -> Option<Vec<LayerId>> {
+	fn create_outline(&mut self, document_layer_path: Vec<LayerId>, overlay_path: Option<Vec<LayerId>>, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) -> Option<Vec<LayerId>> {
 		let copied_overlay_path = overlay_path.clone();
-		let result = Self::try_create_outline(document_layer_path, overlay_path, document, responses, render_data);
+		let result = self.try_create_outline(document_layer_path, overlay_path, document, responses);
 		if result.is_none() {
 			// Discard the overlay layer if it exists
 			if let Some(overlay_path) = copied_overlay_path {
@@ -106,21 +172,26 @@ impl PathOutline {
 			let operation = Operation::DeleteLayer { path };
 			responses.add(DocumentMessage::Overlays(operation.into()));
 		}
-		self.hovered_layer_path = None;
+		if let Some(path) = self.hovered_layer_path.take() {
+			self.outline_cache.remove(&path);
+		}
 	}
 
 	/// Performs an intersect test and generates a hovered overlay if necessary
 	pub fn intersect_test_hovered(&mut self, input: &InputPreprocessorMessageHandler, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>, render_data: &RenderData) {
 		// Get the layer the user is hovering over
-		let tolerance = DVec2::splat(SELECTION_TOLERANCE);
-		let quad = Quad::from_box([input.mouse.position - tolerance, input.mouse.position + tolerance]);
+		let quad = pointer_quad(input.mouse.position);
 		let mut intersection = document.document_legacy.intersects_quad_root(quad, render_data);
 
 		// If the user is hovering over a layer they have not already selected, then update outline
 		if let Some(path) = intersection.pop() {
 			if !document.selected_visible_layers().any(|visible| visible == path.as_slice()) {
 				// Updates the overlay, generating a new one if necessary
-				self.hovered_overlay_path = Self::create_outline(path.clone(), self.hovered_overlay_path.take(), document, responses, render_data);
+				if let Some(previous) = self.hovered_layer_path.take().filter(|previous| *previous != path) {
+					self.outline_cache.remove(&previous);
+				}
+				let overlay_path = self.hovered_overlay_path.take();
+				self.hovered_overlay_path = self.create_outline(path.clone(), overlay_path, document, responses);
 				if self.hovered_overlay_path.is_none() {
 					self.clear_hovered(responses);
 				}
@@ -143,11 +214,12 @@ impl PathOutline {
 	}
 
 	/// Updates the selected overlays, generating or removing overlays if necessary
-	pub fn update_selected<'a>(&mut self, selected: impl Iterator<Item = &'a [LayerId]>, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>, render_data: &RenderData) {
+	pub fn update_selected<'a>(&mut self, selected: impl Iterator<Item = &'a [LayerId]>, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) {
 		let mut old_overlay_paths = std::mem::take(&mut self.selected_overlay_paths);
 
-		for document_layer_path in selected {
-			if let Some(overlay_path) = Self::create_outline(document_layer_path.to_vec(), old_overlay_paths.pop(), document, responses, render_data) {
+		let selected = selected.collect::<Vec<_>>();
+		for document_layer_path in &selected {
+			if let Some(overlay_path) = self.create_outline(document_layer_path.to_vec(), old_overlay_paths.pop(), document, responses) {
 				self.selected_overlay_paths.push(overlay_path);
 			}
 		}
@@ -155,5 +227,50 @@ impl PathOutline {
 			let operation = Operation::DeleteLayer { path };
 			responses.add(DocumentMessage::Overlays(operation.into()));
 		}
+
+		// Forget the outlines of layers which are no longer selected or hovered
+		let hovered = self.hovered_layer_path.as_deref();
+		self.outline_cache.retain(|path, _| selected.contains(&path.as_slice()) || hovered == Some(path.as_slice()));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use document_legacy::layers::folder_layer::FolderLayer;
+	use document_legacy::layers::shape_layer::ShapeLayer;
+
+	#[test]
+	fn folder_outline_traces_its_visible_layers() {
+		let square = |translation: DVec2, visible: bool| {
+			let shape = ShapeLayer::new(Subpath::new_rect(DVec2::ZERO, DVec2::ONE), Default::default());
+			let mut layer = Layer::new(LayerDataType::Shape(shape), DAffine2::from_translation(translation).to_cols_array());
+			layer.visible = visible;
+			layer
+		};
+		let mut folder = FolderLayer::default();
+		folder.add_layer(square(DVec2::new(10., 0.), true), None, -1);
+		folder.add_layer(square(DVec2::new(0., 10.), true), None, -1);
+		folder.add_layer(square(DVec2::new(20., 20.), false), None, -1);
+		let folder = Layer::new(LayerDataType::Folder(folder), DAffine2::IDENTITY.to_cols_array());
+
+		let bounds = layer_paths(&folder).iter().filter_map(|subpath| subpath.bounding_box()).collect::<Vec<_>>();
+		assert_eq!(bounds, [[DVec2::new(10., 0.), DVec2::new(11., 1.)], [DVec2::new(0., 10.), DVec2::new(1., 11.)]]);
+	}
+
+	#[test]
+	fn outlines_are_simplified_only_when_zoomed_out() {
+		assert_eq!(simplification_level(1.), None);
+		assert_eq!(simplification_level(4.), None);
+		assert_eq!(simplification_level(0.5), Some(1));
+		assert_eq!(simplification_level(0.3), Some(2));
+
+		// A finely sampled circle keeps only the points needed to stay within the tolerance
+		let points = (0..1000).map(|index| DVec2::from_angle(index as f64 / 1000. * std::f64::consts::TAU) * 100.);
+		let circle = bezier_rs::Subpath::from_anchors(points, true);
+		let simplified = simplify_paths(&[circle], 1.);
+		assert!(simplified[0].closed());
+		assert!((10..100).contains(&simplified[0].len()), "{}", simplified[0].len());
+		assert!(simplified[0].manipulator_groups().iter().all(|group| (group.anchor.length() - 100.).abs() < 1e-6));
 	}
 }
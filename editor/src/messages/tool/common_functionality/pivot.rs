@@ -1,9 +1,10 @@
 //! Handler for the pivot overlay visible on the selected layer(s) whilst using the Select tool which controls the center of rotation/scale and origin of the layer.
 
 use crate::application::generate_uuid;
-use crate::consts::{COLOR_ACCENT, PIVOT_INNER, PIVOT_OUTER, PIVOT_OUTER_OUTLINE_THICKNESS};
+use crate::consts::PIVOT_OUTER_OUTLINE_THICKNESS;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::hit_targets::pivot_sizes;
 
 use document_legacy::layers::style::{self, RenderData};
 use document_legacy::{LayerId, Operation};
@@ -112,7 +113,7 @@ impl Pivot {
 				path: layer_paths[0].clone(),
 				transform: DAffine2::IDENTITY.to_cols_array(),
 				style: style::PathStyle::new(
-					Some(style::Stroke::new(Some(COLOR_ACCENT), PIVOT_OUTER_OUTLINE_THICKNESS)),
+					Some(style::Stroke::new(Some(overlay_colors().handles), PIVOT_OUTER_OUTLINE_THICKNESS)),
 					style::Fill::Solid(graphene_core::raster::color::Color::WHITE),
 				),
 				insert_index: -1,
@@ -123,7 +124,7 @@ impl Pivot {
 			Operation::AddEllipse {
 				path: layer_paths[1].clone(),
 				transform: DAffine2::IDENTITY.to_cols_array(),
-				style: style::PathStyle::new(None, style::Fill::Solid(COLOR_ACCENT)),
+				style: style::PathStyle::new(None, style::Fill::Solid(overlay_colors().handles)),
 				insert_index: -1,
 			}
 			.into(),
@@ -132,11 +133,12 @@ impl Pivot {
 		self.pivot_overlay_circles = Some(layer_paths.clone());
 		let [outer, inner] = layer_paths;
 
-		let pivot_diameter_without_outline = PIVOT_OUTER - PIVOT_OUTER_OUTLINE_THICKNESS;
+		let (pivot_outer, pivot_inner) = pivot_sizes();
+		let pivot_diameter_without_outline = pivot_outer - PIVOT_OUTER_OUTLINE_THICKNESS;
 		let transform = DAffine2::from_scale_angle_translation(DVec2::splat(pivot_diameter_without_outline), 0., pivot - DVec2::splat(pivot_diameter_without_outline / 2.)).to_cols_array();
 		responses.add(DocumentMessage::Overlays(Operation::TransformLayerInViewport { path: outer, transform }.into()));
 
-		let transform = DAffine2::from_scale_angle_translation(DVec2::splat(PIVOT_INNER), 0., pivot - DVec2::splat(PIVOT_INNER / 2.)).to_cols_array();
+		let transform = DAffine2::from_scale_angle_translation(DVec2::splat(pivot_inner), 0., pivot - DVec2::splat(pivot_inner / 2.)).to_cols_array();
 		responses.add(DocumentMessage::Overlays(Operation::TransformLayerInViewport { path: inner, transform }.into()));
 	}
 
@@ -179,6 +181,6 @@ impl Pivot {
 
 	/// Answers if the pointer is currently positioned over the pivot.
 	pub fn is_over(&self, mouse: DVec2) -> bool {
-		self.pivot.filter(|&pivot| mouse.distance_squared(pivot) < (PIVOT_OUTER / 2.).powi(2)).is_some()
+		self.pivot.filter(|&pivot| mouse.distance_squared(pivot) < (pivot_sizes().0 / 2.).powi(2)).is_some()
 	}
 }
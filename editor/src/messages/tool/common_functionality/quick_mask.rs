@@ -0,0 +1,161 @@
+//! The quick mask is a temporary region painted over the canvas with a round brush, which is then turned into a selection of the layers it
+//! touches. It picks out layers that are awkward to reach with a click or a box, like those scattered among others or hidden beneath them.
+
+use crate::application::generate_uuid;
+use crate::consts::{BRUSH_SIZE_CHANGE_KEYBOARD, QUICK_MASK_DEFAULT_DIAMETER, QUICK_MASK_MIN_DIAMETER, QUICK_MASK_OPACITY, QUICK_MASK_SPACING};
+use crate::messages::prelude::*;
+
+use document_legacy::document::Document;
+use document_legacy::intersection::Quad;
+use document_legacy::layers::style::{Fill, PathStyle, RenderData};
+use document_legacy::{LayerId, Operation};
+use graphene_core::vector::brush_stroke::{BrushInputSample, BrushStroke, BrushStyle};
+use graphene_std::vector::subpath::Subpath;
+
+use glam::{DAffine2, DVec2};
+
+#[derive(Clone, Debug)]
+pub struct QuickMask {
+	/// Diameter of the brush in viewport pixels, so it looks the same size at any zoom
+	diameter: f64,
+	/// The painted strokes, in document space so they stay over the same artwork as the canvas is panned and zoomed
+	strokes: Vec<BrushStroke>,
+	overlay: Option<Vec<LayerId>>,
+}
+
+impl Default for QuickMask {
+	fn default() -> Self {
+		Self {
+			diameter: QUICK_MASK_DEFAULT_DIAMETER,
+			strokes: Vec::new(),
+			overlay: None,
+		}
+	}
+}
+
+impl QuickMask {
+	pub fn is_shown(&self) -> bool {
+		self.overlay.is_some()
+	}
+
+	/// Shows an empty mask over the canvas, ready to be painted on.
+	pub fn show(&mut self, responses: &mut VecDeque<Message>) {
+		self.clear(responses);
+
+		let path = vec![generate_uuid()];
+		let color = overlay_colors().selection_outline.with_alpha(QUICK_MASK_OPACITY);
+		let operation = Operation::AddShape {
+			path: path.clone(),
+			subpath: Default::default(),
+			style: PathStyle::new(None, Fill::solid(color)),
+			insert_index: -1,
+			transform: DAffine2::IDENTITY.to_cols_array(),
+		};
+		responses.add(DocumentMessage::Overlays(operation.into()));
+		self.overlay = Some(path);
+	}
+
+	/// Removes the mask along with everything painted on it.
+	pub fn clear(&mut self, responses: &mut VecDeque<Message>) {
+		self.strokes.clear();
+		if let Some(path) = self.overlay.take() {
+			responses.add(DocumentMessage::Overlays(Operation::DeleteLayer { path }.into()));
+		}
+	}
+
+	/// Makes the brush larger or smaller by the same step as the Brush tool.
+	pub fn resize_brush(&mut self, grow: bool) {
+		let change = if grow { BRUSH_SIZE_CHANGE_KEYBOARD } else { -BRUSH_SIZE_CHANGE_KEYBOARD };
+		self.diameter = (self.diameter + change).max(QUICK_MASK_MIN_DIAMETER);
+	}
+
+	/// Starts painting a new stroke at the pointer, in addition to those already painted.
+	pub fn start_stroke(&mut self, viewport_position: DVec2, document: &Document, responses: &mut VecDeque<Message>) {
+		let to_document = document.root.transform.inverse();
+		let style = BrushStyle {
+			diameter: self.diameter * to_document.matrix2.x_axis.length(),
+			spacing: QUICK_MASK_SPACING,
+			..Default::default()
+		};
+		self.strokes.push(BrushStroke { style, trace: Vec::new() });
+		self.extend_stroke(viewport_position, document, responses);
+	}
+
+	/// Continues the latest stroke to the pointer.
+	pub fn extend_stroke(&mut self, viewport_position: DVec2, document: &Document, responses: &mut VecDeque<Message>) {
+		let Some(stroke) = self.strokes.last_mut() else { return };
+
+		let position = document.root.transform.inverse().transform_point2(viewport_position);
+		if stroke.trace.last().map(|sample| sample.position) == Some(position) {
+			return;
+		}
+		stroke.trace.push(BrushInputSample { position });
+
+		self.update_overlay(document, responses);
+	}
+
+	/// Draws the painted region over the canvas where it is now, which is done again whenever the canvas moves.
+	pub fn update_overlay(&self, document: &Document, responses: &mut VecDeque<Message>) {
+		let Some(path) = &self.overlay else { return };
+
+		let circles = self
+			.dabs()
+			.map(|(center, diameter)| bezier_rs::Subpath::new_ellipse(center - diameter / 2., center + diameter / 2.))
+			.collect::<Vec<_>>();
+		let subpath = Subpath::from_bezier_rs(&circles);
+		responses.add(DocumentMessage::Overlays(Operation::SetShapePath { path: path.clone(), subpath }.into()));
+
+		let transform = document.root.transform.to_cols_array();
+		responses.add(DocumentMessage::Overlays(Operation::SetLayerTransform { path: path.clone(), transform }.into()));
+	}
+
+	/// The layers touched by the painted region, found by testing the square around each dab of the brush against the artwork.
+	pub fn touched_layers(&self, document: &Document, render_data: &RenderData) -> Vec<Vec<LayerId>> {
+		let mut layers = Vec::new();
+		for (center, diameter) in self.dabs() {
+			let quad = document.root.transform * Quad::from_box([center - diameter / 2., center + diameter / 2.]);
+			for layer in document.intersects_quad_root(quad, render_data) {
+				if !layers.contains(&layer) {
+					layers.push(layer);
+				}
+			}
+		}
+		layers
+	}
+
+	/// The centers of the round dabs which make up the painted region, in document space, along with their diameters.
+	fn dabs(&self) -> impl Iterator<Item = (DVec2, f64)> + '_ {
+		self.strokes
+			.iter()
+			.flat_map(|stroke| stroke.compute_blit_points().into_iter().map(move |center| (center, stroke.style.diameter)))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn strokes_are_painted_in_document_space() {
+		let mut document = Document::default();
+		document.root.transform = DAffine2::from_scale_angle_translation(DVec2::splat(2.), 0., DVec2::new(100., 0.));
+		let responses = &mut VecDeque::new();
+
+		let mut quick_mask = QuickMask::default();
+		quick_mask.show(responses);
+		quick_mask.start_stroke(DVec2::new(100., 0.), &document, responses);
+		quick_mask.extend_stroke(DVec2::new(300., 0.), &document, responses);
+
+		let dabs = quick_mask.dabs().collect::<Vec<_>>();
+		let diameter = QUICK_MASK_DEFAULT_DIAMETER / 2.;
+		assert_eq!(dabs.first(), Some(&(DVec2::ZERO, diameter)));
+		assert!(dabs
+			.iter()
+			.all(|&(center, dab_diameter)| center.y == 0. && (0. ..=100.).contains(&center.x) && dab_diameter == diameter));
+		assert!(dabs.windows(2).all(|pair| pair[1].0.x - pair[0].0.x <= diameter));
+
+		quick_mask.clear(responses);
+		assert!(!quick_mask.is_shown());
+		assert_eq!(quick_mask.dabs().count(), 0);
+	}
+}
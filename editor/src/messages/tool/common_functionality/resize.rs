@@ -37,6 +37,7 @@ impl Resize {
 		ipp: &InputPreprocessorMessageHandler,
 		center: Key,
 		lock_ratio: Key,
+		aspect_locked: bool,
 		skip_rerender: bool,
 	) -> Option<Message> {
 		let Some(path) = &self.path else {
@@ -47,7 +48,7 @@ impl Resize {
 		let stop = self.snap_manager.snap_position(responses, document, ipp.mouse.position);
 
 		let mut size = stop - start;
-		if ipp.keyboard.get(lock_ratio as usize) {
+		if aspect_locked || ipp.keyboard.get(lock_ratio as usize) {
 			size = size.abs().max(size.abs().yx()) * size.signum();
 		}
 		if ipp.keyboard.get(center as usize) {
@@ -66,6 +67,22 @@ impl Resize {
 		)
 	}
 
+	/// Gives the shape a fixed size in document space with its top left corner at the drag start, for shapes placed with a click rather than a drag.
+	pub fn place_with_size(&self, document: &DocumentMessageHandler, size: DVec2) -> Option<Message> {
+		let path = self.path.as_ref()?;
+		let root_transform = document.document_legacy.root.transform;
+
+		Some(
+			GraphOperationMessage::TransformSet {
+				layer: path.to_vec(),
+				transform: root_transform * DAffine2::from_scale_angle_translation(size, 0., self.drag_start),
+				transform_in: TransformIn::Viewport,
+				skip_rerender: false,
+			}
+			.into(),
+		)
+	}
+
 	pub fn cleanup(&mut self, responses: &mut VecDeque<Message>) {
 		self.snap_manager.cleanup(responses);
 		self.path = None;
@@ -0,0 +1,66 @@
+//! Colors sampled by the frontend from its captured pixels, which are converted from the color space of the capture to the sRGB that the
+//! colors of the document are in.
+
+use graphene_core::Color;
+
+use serde::{Deserialize, Serialize};
+
+/// The color spaces the frontend can read captured pixels in, depending on the gamut of the display.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default, Serialize, Deserialize, specta::Type)]
+pub enum SampledColorSpace {
+	#[default]
+	Srgb,
+	/// The wider gamut of most recent phones and laptops, with the same transfer function as sRGB.
+	DisplayP3,
+}
+
+impl SampledColorSpace {
+	/// The color space named as it is by the canvas API, if it's one the frontend may capture in.
+	pub fn from_canvas_name(name: &str) -> Option<Self> {
+		match name {
+			"srgb" => Some(Self::Srgb),
+			"display-p3" => Some(Self::DisplayP3),
+			_ => None,
+		}
+	}
+
+	/// Converts an 8-bit RGBA pixel in this color space to a color of the document, clipping colors which are outside the sRGB gamut.
+	pub fn to_document_color(self, [red, green, blue, alpha]: [u8; 4]) -> Color {
+		let [red, green, blue] = match self {
+			Self::Srgb => [red, green, blue].map(|channel| channel as f32 / 255.),
+			Self::DisplayP3 => {
+				let [red, green, blue] = [red, green, blue].map(|channel| Color::srgb_to_linear(channel as f32 / 255.));
+				[
+					1.224_940_2 * red - 0.224_940_4 * green,
+					-0.042_056_9 * red + 1.042_057_1 * green,
+					-0.019_637_6 * red - 0.078_636_1 * green + 1.098_273_5 * blue,
+				]
+				.map(|channel| Color::linear_to_srgb(channel.clamp(0., 1.)))
+			}
+		};
+		Color::from_rgbaf32_unchecked(red, green, blue, alpha as f32 / 255.)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn display_p3_is_converted_to_srgb() {
+		// Within a few levels of 8-bit precision, which the pixels are rounded to
+		let close = |a: Color, b: Color| [a.r() - b.r(), a.g() - b.g(), a.b() - b.b()].iter().all(|difference| difference.abs() < 0.02);
+
+		// Grays are the same in both
+		let gray = [128, 128, 128, 255];
+		assert!(close(SampledColorSpace::DisplayP3.to_document_color(gray), SampledColorSpace::Srgb.to_document_color(gray)));
+
+		// The most saturated red of Display P3 is outside of sRGB, so it's clipped to the most saturated red of sRGB
+		let red = SampledColorSpace::DisplayP3.to_document_color([255, 0, 0, 255]);
+		assert!(close(red, Color::from_rgbaf32_unchecked(1., 0., 0., 1.)));
+
+		// The most saturated green of sRGB is within Display P3, where it's less saturated
+		let srgb_green_in_p3 = [117, 251, 76, 255];
+		assert!(close(SampledColorSpace::DisplayP3.to_document_color(srgb_green_in_p3), Color::from_rgbaf32_unchecked(0., 1., 0., 1.)));
+	}
+}
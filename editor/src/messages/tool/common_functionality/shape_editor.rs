@@ -1,10 +1,16 @@
-use crate::consts::DRAG_THRESHOLD;
+use crate::application::generate_uuid;
 use crate::messages::portfolio::document::node_graph::VectorDataModification;
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis};
+use crate::messages::portfolio::document::utility_types::path_joining::break_subpath;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::freehand_path::spline_handles;
+use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::common_functionality::hit_targets::{drag_threshold, hide_handle_distance};
 
 use bezier_rs::{Bezier, TValue};
 use document_legacy::LayerId;
 use graphene_core::uuid::ManipulatorGroupId;
+use graphene_core::vector::style::Stroke;
 use graphene_core::vector::{ManipulatorPointId, SelectedType, VectorData};
 
 use document_legacy::document::Document;
@@ -143,6 +149,72 @@ impl ShapeState {
 		}
 	}
 
+	/// Selects every anchor of each shape with a point already selected, or of every shape being edited if no point is selected yet.
+	pub fn select_all_anchors_in_shapes(&mut self, document: &Document) {
+		let any_selected = self.selected_points().next().is_some();
+		let layer_paths = self
+			.selected_shape_state
+			.iter()
+			.filter(|(_, state)| !any_selected || !state.selected_points.is_empty())
+			.map(|(layer_path, _)| layer_path.clone())
+			.collect::<Vec<_>>();
+
+		for layer_path in layer_paths {
+			self.select_all_anchors(document, &layer_path);
+		}
+	}
+
+	/// Selects the anchors that aren't selected in the shapes being edited and deselects the rest, along with any selected handles.
+	pub fn invert_anchor_selection(&mut self, document: &Document) {
+		for (layer_path, state) in &mut self.selected_shape_state {
+			let Ok(layer) = document.layer(layer_path) else { continue };
+			let Some(vector_data) = layer.as_vector_data() else { continue };
+
+			let previously_selected = std::mem::take(&mut state.selected_points);
+			for manipulator in vector_data.manipulator_groups() {
+				let anchor = ManipulatorPointId::new(manipulator.id, SelectedType::Anchor);
+				if !previously_selected.contains(&anchor) {
+					state.select_point(anchor);
+				}
+			}
+		}
+	}
+
+	/// Extends the selected anchors to their neighbors along the paths, so repeating it selects outwards from the selection one anchor at a time.
+	pub fn grow_anchor_selection(&mut self, document: &Document) {
+		self.map_anchor_selection(document, grown_selection);
+	}
+
+	/// Deselects the anchors at the edges of each run of selected anchors along the paths, undoing [grow_anchor_selection](Self::grow_anchor_selection).
+	pub fn shrink_anchor_selection(&mut self, document: &Document) {
+		self.map_anchor_selection(document, shrunk_selection);
+	}
+
+	/// Replaces which anchors of each subpath are selected, given which are selected now and whether the subpath is closed.
+	fn map_anchor_selection(&mut self, document: &Document, map: fn(&[bool], bool) -> Vec<bool>) {
+		for (layer_path, state) in &mut self.selected_shape_state {
+			let Ok(layer) = document.layer(layer_path) else { continue };
+			let Some(vector_data) = layer.as_vector_data() else { continue };
+
+			for subpath in &vector_data.subpaths {
+				let anchors = subpath
+					.manipulator_groups()
+					.iter()
+					.map(|group| ManipulatorPointId::new(group.id, SelectedType::Anchor))
+					.collect::<Vec<_>>();
+				let selected = anchors.iter().map(|&anchor| state.is_selected(anchor)).collect::<Vec<_>>();
+
+				for (anchor, selected) in anchors.into_iter().zip(map(&selected, subpath.closed())) {
+					if selected {
+						state.select_point(anchor);
+					} else {
+						state.deselect_point(anchor);
+					}
+				}
+			}
+		}
+	}
+
 	/// Provide the currently selected points by reference.
 	pub fn selected_points(&self) -> impl Iterator<Item = &'_ ManipulatorPointId> {
 		self.selected_shape_state.values().flat_map(|state| &state.selected_points)
@@ -214,6 +286,256 @@ impl ShapeState {
 		}
 	}
 
+	/// The selected anchors of every layer, with their positions in viewport space.
+	fn selected_anchors(&self, document: &Document) -> Vec<(Vec<LayerId>, ManipulatorGroupId, DVec2)> {
+		let mut anchors = Vec::new();
+		for (layer_path, state) in &self.selected_shape_state {
+			let Ok(layer) = document.layer(layer_path) else { continue };
+			let Some(vector_data) = layer.as_vector_data() else { continue };
+			let transform = document.multiply_transforms(layer_path).unwrap_or_default();
+
+			for group in vector_data.manipulator_groups() {
+				if state.is_selected(ManipulatorPointId::new(group.id, SelectedType::Anchor)) {
+					anchors.push((layer_path.clone(), group.id, transform.transform_point2(group.anchor)));
+				}
+			}
+		}
+		anchors
+	}
+
+	/// Moves each anchor to a new position in viewport space, taking its handles along with it.
+	fn move_anchors(&self, document: &Document, anchors: impl IntoIterator<Item = (Vec<LayerId>, ManipulatorGroupId, DVec2)>, responses: &mut VecDeque<Message>) {
+		for (layer_path, id, position) in anchors {
+			let Ok(layer) = document.layer(&layer_path) else { continue };
+			let Some(group) = layer.as_vector_data().and_then(|vector_data| vector_data.manipulator_from_id(id)) else {
+				continue;
+			};
+			let transform = document.multiply_transforms(&layer_path).unwrap_or_default();
+			let delta = transform.inverse().transform_point2(position) - group.anchor;
+
+			for manipulator_type in [SelectedType::Anchor, SelectedType::InHandle, SelectedType::OutHandle] {
+				let Some(previous_position) = manipulator_type.get_position(group) else { continue };
+				responses.add(GraphOperationMessage::Vector {
+					layer: layer_path.clone(),
+					modification: VectorDataModification::SetManipulatorPosition {
+						point: ManipulatorPointId::new(id, manipulator_type),
+						position: previous_position + delta,
+					},
+				});
+			}
+		}
+	}
+
+	/// Lines up the selected anchors along the axis at their minimum, maximum, center, or average position.
+	pub fn align_selected_anchors(&self, document: &Document, axis: &AlignAxis, aggregate: &AlignAggregate, responses: &mut VecDeque<Message>) {
+		let anchors = self.selected_anchors(document);
+		if anchors.len() < 2 {
+			return;
+		}
+
+		let coordinates = anchors.iter().map(|(_, _, position)| axis_coordinate(*position, axis)).collect::<Vec<_>>();
+		let target = align_target(&coordinates, aggregate);
+		let anchors = anchors.into_iter().map(|(layer_path, id, position)| (layer_path, id, with_axis_coordinate(position, axis, target)));
+
+		responses.add(DocumentMessage::StartTransaction);
+		self.move_anchors(document, anchors, responses);
+	}
+
+	/// Spaces the selected anchors evenly along the axis between the two outermost ones, keeping their order.
+	pub fn distribute_selected_anchors(&self, document: &Document, axis: &AlignAxis, responses: &mut VecDeque<Message>) {
+		let mut anchors = self.selected_anchors(document);
+		if anchors.len() < 3 {
+			return;
+		}
+
+		anchors.sort_by(|(_, _, a), (_, _, b)| axis_coordinate(*a, axis).total_cmp(&axis_coordinate(*b, axis)));
+		let coordinates = distributed_coordinates(axis_coordinate(anchors[0].2, axis), axis_coordinate(anchors[anchors.len() - 1].2, axis), anchors.len());
+		let anchors = anchors
+			.into_iter()
+			.zip(coordinates)
+			.map(|((layer_path, id, position), target)| (layer_path, id, with_axis_coordinate(position, axis, target)));
+
+		responses.add(DocumentMessage::StartTransaction);
+		self.move_anchors(document, anchors, responses);
+	}
+
+	/// Merges the selected anchors together by moving them all to their centroid.
+	pub fn average_selected_anchors(&self, document: &Document, responses: &mut VecDeque<Message>) {
+		let anchors = self.selected_anchors(document);
+		if anchors.len() < 2 {
+			return;
+		}
+
+		let centroid = anchors.iter().map(|(_, _, position)| *position).sum::<DVec2>() / anchors.len() as f64;
+		let anchors = anchors.into_iter().map(|(layer_path, id, _)| (layer_path, id, centroid));
+
+		responses.add(DocumentMessage::StartTransaction);
+		self.move_anchors(document, anchors, responses);
+	}
+
+	/// Recomputes the handles of the selected anchors so the path flows smoothly through them and their neighbors, like a Catmull-Rom spline.
+	/// The anchors stay in place and their handles are set to mirror each other, so a jittery run of points becomes one even curve.
+	pub fn smooth_selected_anchors(&self, document: &Document, responses: &mut VecDeque<Message>) {
+		let mut modifications = Vec::new();
+		for (layer_path, state) in &self.selected_shape_state {
+			let Ok(layer) = document.layer(layer_path) else { continue };
+			let Some(vector_data) = layer.as_vector_data() else { continue };
+
+			for subpath in &vector_data.subpaths {
+				let groups = subpath.manipulator_groups();
+				let anchors = groups.iter().map(|group| group.anchor).collect::<Vec<_>>();
+				let handles = spline_handles(&anchors, subpath.closed());
+
+				for (group, (in_handle, out_handle)) in groups.iter().zip(handles) {
+					if !state.is_selected(ManipulatorPointId::new(group.id, SelectedType::Anchor)) {
+						continue;
+					}
+
+					let handles = [(SelectedType::InHandle, in_handle), (SelectedType::OutHandle, out_handle)];
+					for (manipulator_type, position) in handles.into_iter().filter_map(|(manipulator_type, position)| Some((manipulator_type, position?))) {
+						modifications.push(GraphOperationMessage::Vector {
+							layer: layer_path.clone(),
+							modification: VectorDataModification::SetManipulatorPosition {
+								point: ManipulatorPointId::new(group.id, manipulator_type),
+								position,
+							},
+						});
+					}
+					modifications.push(GraphOperationMessage::Vector {
+						layer: layer_path.clone(),
+						modification: VectorDataModification::SetManipulatorHandleMirroring { id: group.id, mirror_angle: true },
+					});
+				}
+			}
+		}
+
+		if !modifications.is_empty() {
+			responses.add(DocumentMessage::StartTransaction);
+			responses.extend(modifications.into_iter().map(Message::from));
+		}
+	}
+
+	/// Makes each run of segments between selected anchors a straight line running along the axis, so `AlignAxis::X` makes them horizontal.
+	/// Each run is moved to the average position of its anchors, and the handles of its segments are laid onto the line.
+	pub fn straighten_selected_segments(&self, document: &Document, direction: &AlignAxis, responses: &mut VecDeque<Message>) {
+		let perpendicular = match direction {
+			AlignAxis::X => AlignAxis::Y,
+			AlignAxis::Y => AlignAxis::X,
+		};
+
+		let mut modifications = Vec::new();
+		for (layer_path, state) in &self.selected_shape_state {
+			let Ok(layer) = document.layer(layer_path) else { continue };
+			let Some(vector_data) = layer.as_vector_data() else { continue };
+			let transform = document.multiply_transforms(layer_path).unwrap_or_default();
+
+			for subpath in &vector_data.subpaths {
+				let groups = subpath.manipulator_groups();
+				let selected = groups
+					.iter()
+					.map(|group| state.is_selected(ManipulatorPointId::new(group.id, SelectedType::Anchor)))
+					.collect::<Vec<_>>();
+
+				for run in selected_segment_runs(&selected, subpath.closed()) {
+					let anchors = run.iter().flat_map(|&segment| [segment, (segment + 1) % groups.len()]).collect::<HashSet<_>>();
+					let target = anchors
+						.iter()
+						.map(|&index| axis_coordinate(transform.transform_point2(groups[index].anchor), &perpendicular))
+						.sum::<f64>() / anchors.len() as f64;
+
+					for &index in &anchors {
+						let group = &groups[index];
+						let anchor = transform.transform_point2(group.anchor);
+						let delta = with_axis_coordinate(anchor, &perpendicular, target) - anchor;
+
+						// Handles of segments in the run are laid onto the line, and the others keep their place relative to the anchor
+						let in_segment = run.contains(&((index + groups.len() - 1) % groups.len()));
+						let out_segment = run.contains(&index);
+						let points = [(SelectedType::Anchor, false), (SelectedType::InHandle, in_segment), (SelectedType::OutHandle, out_segment)];
+						for (manipulator_type, on_line) in points {
+							let Some(position) = manipulator_type.get_position(group) else { continue };
+							let position = transform.transform_point2(position);
+							let position = if on_line { with_axis_coordinate(position, &perpendicular, target) } else { position + delta };
+
+							modifications.push(GraphOperationMessage::Vector {
+								layer: layer_path.clone(),
+								modification: VectorDataModification::SetManipulatorPosition {
+									point: ManipulatorPointId::new(group.id, manipulator_type),
+									position: transform.inverse().transform_point2(position),
+								},
+							});
+						}
+					}
+				}
+			}
+		}
+
+		if !modifications.is_empty() {
+			responses.add(DocumentMessage::StartTransaction);
+			responses.extend(modifications.into_iter().map(Message::from));
+		}
+	}
+
+	/// Break the paths at the selected anchors. The first piece of each path stays in its layer, and any further pieces are split off into new layers above it.
+	pub fn break_at_selected_anchors(&self, document: &Document, responses: &mut VecDeque<Message>) {
+		let mut broken_layers = Vec::new();
+		for (layer_path, state) in &self.selected_shape_state {
+			let Ok(layer) = document.layer(layer_path) else { continue };
+			let Some(vector_data) = layer.as_vector_data() else { continue };
+			let breaks = vector_data
+				.manipulator_groups()
+				.map(|group| group.id)
+				.filter(|&id| state.is_selected(ManipulatorPointId::new(id, SelectedType::Anchor)))
+				.collect::<Vec<_>>();
+
+			let mut kept = Vec::new();
+			let mut split_off = Vec::new();
+			for subpath in &vector_data.subpaths {
+				let mut pieces = break_subpath(subpath, &breaks).into_iter();
+				kept.extend(pieces.next());
+				split_off.extend(pieces);
+			}
+			if kept != vector_data.subpaths {
+				broken_layers.push((layer_path.clone(), kept, split_off, vector_data.style.clone()));
+			}
+		}
+		if broken_layers.is_empty() {
+			return;
+		}
+
+		responses.add(DocumentMessage::StartTransaction);
+		for (layer_path, kept, split_off, style) in broken_layers {
+			responses.add(GraphOperationMessage::Vector {
+				layer: layer_path.clone(),
+				modification: VectorDataModification::UpdateSubpaths { subpaths: kept },
+			});
+
+			// The new layers are placed directly above the original one, in its parent folder
+			let Some((&layer_id, parent_path)) = layer_path.split_last() else { continue };
+			let layer_to_parent = document.multiply_transforms(parent_path).unwrap_or_default().inverse() * document.multiply_transforms(&layer_path).unwrap_or_default();
+			let insert_index = document
+				.folder(parent_path)
+				.and_then(|folder| folder.position_of_layer(layer_id))
+				.map_or(-1, |index| index as isize + 1);
+			for mut piece in split_off {
+				piece.apply_transform(layer_to_parent);
+				let mut new_path = parent_path.to_vec();
+				new_path.push(generate_uuid());
+
+				graph_modification_utils::new_vector_layer_at(vec![piece], new_path.clone(), insert_index, responses);
+				responses.add(GraphOperationMessage::FillSet {
+					layer: new_path.clone(),
+					fill: style.fill().clone(),
+				});
+				responses.add(GraphOperationMessage::StrokeSet {
+					layer: new_path,
+					stroke: style.stroke().unwrap_or_else(|| Stroke::new(None, 0.)),
+				});
+			}
+		}
+		responses.add(BroadcastEvent::DocumentIsDirty);
+	}
+
 	/// Delete selected and mirrored handles with zero length when the drag stops.
 	pub fn delete_selected_handles_with_zero_length(&self, document: &Document, opposing_handle_lengths: &Option<OpposingHandleLengths>, responses: &mut VecDeque<Message>) {
 		for (layer_path, state) in &self.selected_shape_state {
@@ -240,7 +562,7 @@ impl ShapeState {
 					continue;
 				};
 
-				if (anchor_position - point_position).length() < DRAG_THRESHOLD {
+				if (anchor_position - point_position).length() < drag_threshold() {
 					responses.add(GraphOperationMessage::Vector {
 						layer: layer_path.clone(),
 						modification: VectorDataModification::RemoveManipulatorPoint { point },
@@ -432,7 +754,7 @@ impl ShapeState {
 		let viewspace = document.generate_transform_relative_to_viewport(layer_path).ok()?;
 		for subpath in &vector_data.subpaths {
 			for manipulator in subpath.manipulator_groups() {
-				let (selected, distance_squared) = SelectedType::closest_widget(manipulator, viewspace, pos, crate::consts::HIDE_HANDLE_DISTANCE);
+				let (selected, distance_squared) = SelectedType::closest_widget(manipulator, viewspace, pos, hide_handle_distance());
 
 				if distance_squared < closest_distance_squared {
 					closest_distance_squared = distance_squared;
@@ -651,3 +973,132 @@ impl ShapeState {
 		}
 	}
 }
+
+fn axis_coordinate(position: DVec2, axis: &AlignAxis) -> f64 {
+	match axis {
+		AlignAxis::X => position.x,
+		AlignAxis::Y => position.y,
+	}
+}
+
+fn with_axis_coordinate(position: DVec2, axis: &AlignAxis, coordinate: f64) -> DVec2 {
+	match axis {
+		AlignAxis::X => DVec2::new(coordinate, position.y),
+		AlignAxis::Y => DVec2::new(position.x, coordinate),
+	}
+}
+
+/// The position along an axis which points at the given coordinates are aligned to.
+fn align_target(coordinates: &[f64], aggregate: &AlignAggregate) -> f64 {
+	let min = coordinates.iter().copied().fold(f64::INFINITY, f64::min);
+	let max = coordinates.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+	match aggregate {
+		AlignAggregate::Min => min,
+		AlignAggregate::Max => max,
+		AlignAggregate::Center => (min + max) / 2.,
+		AlignAggregate::Average => coordinates.iter().sum::<f64>() / coordinates.len() as f64,
+	}
+}
+
+/// The given number of evenly spaced coordinates from the start to the end.
+fn distributed_coordinates(start: f64, end: f64, count: usize) -> impl Iterator<Item = f64> {
+	let spacing = (end - start) / (count.max(2) - 1) as f64;
+	(0..count).map(move |index| start + spacing * index as f64)
+}
+
+/// The anchors next to the anchor along a subpath, wrapping around from the last anchor to the first if the subpath is closed.
+fn neighboring_anchors(index: usize, count: usize, closed: bool) -> impl Iterator<Item = usize> {
+	let previous = match index {
+		0 if closed => Some(count - 1),
+		0 => None,
+		_ => Some(index - 1),
+	};
+	let next = match index + 1 {
+		next if next < count => Some(next),
+		_ if closed => Some(0),
+		_ => None,
+	};
+	previous.into_iter().chain(next).filter(move |&neighbor| neighbor != index)
+}
+
+/// Which anchors of a subpath are selected once the selection grows to include the neighbors of the selected anchors.
+fn grown_selection(selected: &[bool], closed: bool) -> Vec<bool> {
+	(0..selected.len())
+		.map(|index| selected[index] || neighboring_anchors(index, selected.len(), closed).any(|neighbor| selected[neighbor]))
+		.collect()
+}
+
+/// Which anchors of a subpath are still selected once the selection shrinks to exclude the selected anchors next to unselected ones.
+/// The ends of an open subpath only have one neighbor, so a selection reaching the end of the path doesn't shrink from there.
+fn shrunk_selection(selected: &[bool], closed: bool) -> Vec<bool> {
+	(0..selected.len())
+		.map(|index| selected[index] && neighboring_anchors(index, selected.len(), closed).all(|neighbor| selected[neighbor]))
+		.collect()
+}
+
+/// Groups the segments of a subpath whose anchors at both ends are selected into runs of consecutive segments, given which anchors are selected.
+/// A segment is identified by the index of its starting anchor, and in a closed subpath a run may wrap around from the last segment to the first.
+fn selected_segment_runs(selected: &[bool], closed: bool) -> Vec<Vec<usize>> {
+	let count = selected.len();
+	let segment_count = if closed { count } else { count.saturating_sub(1) };
+
+	let mut runs: Vec<Vec<usize>> = Vec::new();
+	for segment in (0..segment_count).filter(|&segment| selected[segment] && selected[(segment + 1) % count]) {
+		match runs.last_mut() {
+			Some(run) if run.last().copied() == segment.checked_sub(1) => run.push(segment),
+			_ => runs.push(vec![segment]),
+		}
+	}
+
+	let wraps_around = closed && runs.len() > 1 && runs[0][0] == 0 && runs[runs.len() - 1].last() == Some(&(segment_count - 1));
+	if wraps_around {
+		let first = runs.remove(0);
+		runs.last_mut().unwrap().extend(first);
+	}
+
+	runs
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn align_targets() {
+		let coordinates = [4., -2., 10., 0.];
+		assert_eq!(align_target(&coordinates, &AlignAggregate::Min), -2.);
+		assert_eq!(align_target(&coordinates, &AlignAggregate::Max), 10.);
+		assert_eq!(align_target(&coordinates, &AlignAggregate::Center), 4.);
+		assert_eq!(align_target(&coordinates, &AlignAggregate::Average), 3.);
+	}
+
+	#[test]
+	fn distribution_is_even() {
+		assert_eq!(distributed_coordinates(0., 30., 4).collect::<Vec<_>>(), vec![0., 10., 20., 30.]);
+	}
+
+	#[test]
+	fn segment_runs() {
+		let selected = [true, true, true, false, true, true];
+		assert_eq!(selected_segment_runs(&selected, false), vec![vec![0, 1], vec![4]]);
+		// The run ending at the last anchor joins the one starting at the first anchor when the path is closed
+		assert_eq!(selected_segment_runs(&selected, true), vec![vec![4, 5, 0, 1]]);
+		assert!(selected_segment_runs(&[true, false, true], false).is_empty());
+	}
+
+	#[test]
+	fn grow_and_shrink_along_the_path() {
+		let selected = [false, false, true, false, false, false];
+		let grown = grown_selection(&selected, false);
+		assert_eq!(grown, vec![false, true, true, true, false, false]);
+		assert_eq!(shrunk_selection(&grown, false), selected);
+
+		// Growing from the first anchor reaches the last one only when the path is closed
+		let first = [true, false, false, false];
+		assert_eq!(grown_selection(&first, false), vec![true, true, false, false]);
+		assert_eq!(grown_selection(&first, true), vec![true, true, false, true]);
+
+		// A selection running to the end of an open path only shrinks from its other end
+		assert_eq!(shrunk_selection(&[false, true, true, true], false), vec![false, false, true, true]);
+	}
+}
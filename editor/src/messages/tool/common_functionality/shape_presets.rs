@@ -0,0 +1,107 @@
+use super::color_selector::{ToolColorOptions, ToolColorType};
+use super::stroke_options::ToolStrokeOptions;
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::*;
+
+use graphene_core::vector::style::{Fill, PathStyle, Stroke};
+use graphene_core::Color;
+
+use glam::DVec2;
+use serde::{Deserialize, Serialize};
+
+/// A named style and size saved from a shape, which the Rectangle and Ellipse tools can draw new shapes with so repeated elements come out the same.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct ShapePreset {
+	pub name: String,
+	/// The solid fill color, or `None` for no fill. Gradient fills are saved as no fill, since the tools only draw solid fills.
+	pub fill: Option<Color>,
+	pub stroke: Option<Stroke>,
+	/// The width and height in document space that shapes are placed at when clicking rather than dragging.
+	pub size: Option<(f64, f64)>,
+}
+
+impl ShapePreset {
+	pub fn new(name: String, style: &PathStyle, size: Option<DVec2>) -> Self {
+		let fill = match style.fill() {
+			Fill::Solid(color) => Some(*color),
+			_ => None,
+		};
+		Self {
+			name,
+			fill,
+			stroke: style.stroke().filter(|stroke| stroke.weight() > 0. && stroke.color().is_some()),
+			size: size.filter(|size| size.x > 0. && size.y > 0.).map(Into::into),
+		}
+	}
+
+	/// Sets a shape tool's options to draw with this preset's style.
+	pub fn apply(&self, fill: &mut ToolColorOptions, stroke: &mut ToolColorOptions, line_weight: &mut f64, stroke_style: &mut ToolStrokeOptions) {
+		fill.custom_color = self.fill;
+		fill.color_type = ToolColorType::Custom;
+
+		stroke.custom_color = self.stroke.as_ref().and_then(|stroke| stroke.color());
+		stroke.color_type = ToolColorType::Custom;
+
+		if let Some(preset_stroke) = &self.stroke {
+			*line_weight = preset_stroke.weight();
+			*stroke_style = ToolStrokeOptions {
+				dash_lengths: preset_stroke.dash_lengths.clone(),
+				dash_offset: preset_stroke.dash_offset,
+				line_cap: preset_stroke.line_cap,
+				line_join: preset_stroke.line_join,
+				miter_limit: preset_stroke.line_join_miter_limit,
+			};
+		}
+	}
+}
+
+/// The preset chosen in a shape tool's options. Changing the style options afterwards deselects it, since they no longer match it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ToolShapePreset {
+	pub name: Option<String>,
+}
+
+impl ToolShapePreset {
+	/// Chooses the saved preset with the given name, returning it so its style can be applied to the tool options.
+	pub fn select(&mut self, name: Option<String>) -> Option<ShapePreset> {
+		let preset = name.and_then(|name| shape_presets().into_iter().find(|preset| preset.name == name));
+		self.name = preset.as_ref().map(|preset| preset.name.clone());
+		preset
+	}
+
+	/// The size shapes are placed at when clicking rather than dragging. It's looked up from the saved preset each time, so once the preset
+	/// is deleted, clicking goes back to what it does without a preset.
+	pub fn size(&self) -> Option<DVec2> {
+		let name = self.name.as_ref()?;
+		shape_presets().into_iter().find(|preset| &preset.name == name)?.size.map(DVec2::from)
+	}
+
+	pub fn create_widgets(&self, select_callback: fn(Option<String>) -> Message) -> Vec<WidgetHolder> {
+		let presets = shape_presets();
+		let selected_index = self.name.as_ref().and_then(|name| presets.iter().position(|preset| &preset.name == name)).map_or(0, |index| index + 1);
+
+		let mut entries = vec![DropdownEntryData::new("No Preset").on_update(move |_| select_callback(None))];
+		entries.extend(presets.iter().map(|preset| {
+			let name = preset.name.clone();
+			DropdownEntryData::new(preset.name.clone()).on_update(move |_| select_callback(Some(name.clone())))
+		}));
+
+		let delete_name = self.name.clone();
+		vec![
+			DropdownInput::new(vec![entries])
+				.selected_index(Some(selected_index as u32))
+				.tooltip("Draw with the style and click size of a shape saved with \"Layer > Save Style as Shape Preset\"")
+				.disabled(presets.is_empty())
+				.widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			IconButton::new("Trash", 24)
+				.tooltip("Delete Preset")
+				.disabled(self.name.is_none())
+				.on_update(move |_| match &delete_name {
+					Some(name) => PreferencesMessage::DeleteShapePreset { name: name.clone() }.into(),
+					None => Message::NoOp,
+				})
+				.widget_holder(),
+		]
+	}
+}
@@ -1,10 +1,8 @@
 use super::shape_editor::ManipulatorPointInfo;
 use crate::application::generate_uuid;
-use crate::consts::{
-	COLOR_ACCENT, SNAP_AXIS_OVERLAY_FADE_DISTANCE, SNAP_AXIS_TOLERANCE, SNAP_AXIS_UNSNAPPED_OPACITY, SNAP_POINT_OVERLAY_FADE_FAR, SNAP_POINT_OVERLAY_FADE_NEAR, SNAP_POINT_SIZE, SNAP_POINT_TOLERANCE,
-	SNAP_POINT_UNSNAPPED_OPACITY,
-};
+use crate::consts::{SNAP_AXIS_OVERLAY_FADE_DISTANCE, SNAP_AXIS_UNSNAPPED_OPACITY, SNAP_POINT_OVERLAY_FADE_FAR, SNAP_POINT_OVERLAY_FADE_NEAR, SNAP_POINT_SIZE, SNAP_POINT_UNSNAPPED_OPACITY};
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::hit_targets::{snap_axis_tolerance, snap_point_tolerance};
 
 use document_legacy::layers::layer_info::Layer;
 use document_legacy::layers::style::{self, Stroke};
@@ -34,14 +32,14 @@ impl SnapOverlays {
 					Operation::AddLine {
 						path: layer_path.clone(),
 						transform,
-						style: style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), 1.0)), style::Fill::None),
+						style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().snap_guides), 1.0)), style::Fill::None),
 						insert_index: -1,
 					}
 				} else {
 					Operation::AddEllipse {
 						path: layer_path.clone(),
 						transform,
-						style: style::PathStyle::new(None, style::Fill::Solid(COLOR_ACCENT)),
+						style: style::PathStyle::new(None, style::Fill::Solid(overlay_colors().snap_guides)),
 						insert_index: -1,
 					}
 				}
@@ -162,6 +160,8 @@ impl SnapOverlays {
 pub struct SnapManager {
 	point_targets: Option<Vec<DVec2>>,
 	bound_targets: Option<Vec<DVec2>>,
+	/// The transform from document space to viewport space and the spacing of the grid, when snapping to the grid
+	grid: Option<(DAffine2, f64)>,
 	snap_overlays: SnapOverlays,
 	snap_x: bool,
 	snap_y: bool,
@@ -185,22 +185,31 @@ impl SnapManager {
 		let y_axis = if self.snap_y { axis } else { &empty }
 			.iter()
 			.flat_map(|&pos| targets.clone().map(move |goal| (pos, goal, (pos - goal).y)));
-		let points = points.iter().flat_map(|&pos| targets.clone().map(move |goal| (pos, pos - goal, (pos - goal).length())));
+		// Grid intersections snap both axes at once, like other points
+		let grid = if snap_points { self.grid } else { None };
+		let grid_points = grid
+			.iter()
+			.flat_map(|&(transform, spacing)| targets.clone().map(move |goal| nearest_grid_point(goal, transform, spacing)))
+			.collect::<Vec<_>>();
+		let points = points
+			.iter()
+			.chain(&grid_points)
+			.flat_map(|&pos| targets.clone().map(move |goal| (pos, pos - goal, (pos - goal).length())));
 
 		let min_x = x_axis.clone().min_by(|a, b| a.2.abs().partial_cmp(&b.2.abs()).expect("Could not compare position."));
 		let min_y = y_axis.clone().min_by(|a, b| a.2.abs().partial_cmp(&b.2.abs()).expect("Could not compare position."));
 		let min_points = points.clone().min_by(|a, b| a.2.abs().partial_cmp(&b.2.abs()).expect("Could not compare position."));
 
 		// Snap to a point if possible
-		let (clamped_closest_distance, snapped_to_point) = if let Some(min_points) = min_points.filter(|&(_, _, dist)| dist <= SNAP_POINT_TOLERANCE) {
+		let (clamped_closest_distance, snapped_to_point) = if let Some(min_points) = min_points.filter(|&(_, _, dist)| dist <= snap_point_tolerance()) {
 			(min_points.1, true)
 		} else {
 			// Do not move if over snap tolerance
 			let closest_distance = DVec2::new(min_x.unwrap_or_default().2, min_y.unwrap_or_default().2);
 			(
 				DVec2::new(
-					if closest_distance.x.abs() > SNAP_AXIS_TOLERANCE { 0. } else { closest_distance.x },
-					if closest_distance.y.abs() > SNAP_AXIS_TOLERANCE { 0. } else { closest_distance.y },
+					if closest_distance.x.abs() > snap_axis_tolerance() { 0. } else { closest_distance.x },
+					if closest_distance.y.abs() > snap_axis_tolerance() { 0. } else { closest_distance.y },
 				),
 				false,
 			)
@@ -223,10 +232,16 @@ impl SnapManager {
 	) {
 		let snapping_enabled = document_message_handler.snapping_state.snapping_enabled;
 		let bounding_box_snapping = document_message_handler.snapping_state.bounding_box_snapping;
-		if snapping_enabled && bounding_box_snapping {
-			self.snap_x = snap_x;
-			self.snap_y = snap_y;
+		if !snapping_enabled {
+			return;
+		}
 
+		// Point targets, added afterwards, also rely on these being set
+		self.snap_x = snap_x;
+		self.snap_y = snap_y;
+		self.point_targets = None;
+
+		if bounding_box_snapping {
 			// Could be made into sorted Vec or a HashSet for more performant lookups.
 			self.bound_targets = Some(
 				bounding_boxes
@@ -234,8 +249,10 @@ impl SnapManager {
 					.filter(|&pos| pos.x >= 0. && pos.y >= 0. && pos.x < input.viewport_bounds.size().x && pos.y <= input.viewport_bounds.size().y)
 					.collect(),
 			);
-			self.point_targets = None;
 		}
+
+		let snapping_state = &document_message_handler.snapping_state;
+		self.grid = (snapping_state.grid_snapping && snapping_state.grid_spacing > 0.).then(|| (document_message_handler.document_legacy.root.transform, snapping_state.grid_spacing));
 	}
 
 	/// Add arbitrary snapping points
@@ -333,17 +350,27 @@ impl SnapManager {
 		self.snap_overlays.cleanup(responses);
 		self.bound_targets = None;
 		self.point_targets = None;
+		self.grid = None;
 	}
 }
 
+/// Finds the intersection of grid lines closest to a viewport position, returning it in viewport coords.
+/// The grid lines are spaced evenly in document space, so they follow the zoom and rotation of the canvas.
+fn nearest_grid_point(position_viewport: DVec2, document_to_viewport: DAffine2, spacing: f64) -> DVec2 {
+	let position_document = document_to_viewport.inverse().transform_point2(position_viewport);
+	let snapped_document = (position_document / spacing).round() * spacing;
+	document_to_viewport.transform_point2(snapped_document)
+}
+
 /// Converts a bounding box into a set of points for snapping
 ///
-/// Puts a point in the middle of each edge (top, bottom, left, right)
-pub fn expand_bounds([bound1, bound2]: [DVec2; 2]) -> [DVec2; 4] {
+/// Puts a point in the middle of each edge (top, bottom, left, right) and in the center
+pub fn expand_bounds([bound1, bound2]: [DVec2; 2]) -> [DVec2; 5] {
 	[
 		DVec2::new((bound1.x + bound2.x) / 2., bound1.y),
 		DVec2::new((bound1.x + bound2.x) / 2., bound2.y),
 		DVec2::new(bound1.x, (bound1.y + bound2.y) / 2.),
 		DVec2::new(bound2.x, (bound1.y + bound2.y) / 2.),
+		(bound1 + bound2) / 2.,
 	]
 }
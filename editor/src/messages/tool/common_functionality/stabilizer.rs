@@ -0,0 +1,170 @@
+//! Steadies the pointer positions of a drawing drag before they're turned into a line, smoothing out the wobble of a mouse or a shaky hand.
+
+use crate::consts::{STABILIZER_MAX_AVERAGED_SAMPLES, STABILIZER_MAX_STRING_LENGTH};
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::Message;
+
+use glam::DVec2;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default, Serialize, Deserialize, specta::Type)]
+pub enum StabilizerMode {
+	#[default]
+	Off,
+	/// Draws at the average of the latest pointer positions, which smooths the line while staying close behind the pointer.
+	MovingAverage,
+	/// Draws with a pen pulled along behind the pointer on a string, which only moves once the string is taut, so small wobbles are ignored.
+	PullString,
+}
+
+/// The stabilizer chosen in the options of a drawing tool, with a strength from 0 to 100.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StabilizerOptions {
+	pub mode: StabilizerMode,
+	pub strength: f64,
+}
+
+impl Default for StabilizerOptions {
+	fn default() -> Self {
+		Self {
+			mode: StabilizerMode::Off,
+			strength: 50.,
+		}
+	}
+}
+
+impl StabilizerOptions {
+	pub fn create_widgets(&self, mode_message: fn(StabilizerMode) -> Message, strength_message: fn(f64) -> Message) -> Vec<WidgetHolder> {
+		let entries = [
+			(StabilizerMode::Off, "Off", "Draw exactly where the pointer moves"),
+			(StabilizerMode::MovingAverage, "Average", "Draw at the average of the latest pointer positions, smoothing the line"),
+			(
+				StabilizerMode::PullString,
+				"String",
+				"Draw with a pen pulled behind the pointer on a string, ignoring wobbles shorter than the string",
+			),
+		];
+		let entries = entries
+			.into_iter()
+			.map(|(mode, label, tooltip)| RadioEntryData::new(label).tooltip(tooltip).on_update(move |_| mode_message(mode)))
+			.collect();
+
+		vec![
+			TextLabel::new("Stabilizer").widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			RadioInput::new(entries).selected_index(self.mode as u32).widget_holder(),
+			Separator::new(SeparatorType::Related).widget_holder(),
+			NumberInput::new(Some(self.strength))
+				.label("Strength")
+				.unit("%")
+				.min(0.)
+				.max(100.)
+				.disabled(self.mode == StabilizerMode::Off)
+				.tooltip("How many pointer positions are averaged, or how long the string is")
+				.on_update(move |number_input: &NumberInput| strength_message(number_input.value.unwrap()))
+				.widget_holder(),
+		]
+	}
+}
+
+/// Stabilizes the pointer positions of one drag, which are given in viewport space so the strength doesn't depend on the zoom.
+#[derive(Clone, Debug, Default)]
+pub struct Stabilizer {
+	options: StabilizerOptions,
+	/// The latest pointer positions, as many as are averaged
+	recent: VecDeque<DVec2>,
+	/// Where the line was last drawn to
+	pen: Option<DVec2>,
+}
+
+impl Stabilizer {
+	pub fn new(options: StabilizerOptions) -> Self {
+		Self {
+			options,
+			recent: VecDeque::new(),
+			pen: None,
+		}
+	}
+
+	/// Takes the next pointer position and returns where the line is drawn to, or `None` if the line doesn't move.
+	/// The line starts where the pointer was first given.
+	pub fn stabilize(&mut self, position: DVec2) -> Option<DVec2> {
+		let strength = self.options.strength.clamp(0., 100.) / 100.;
+
+		let count = match self.options.mode {
+			StabilizerMode::MovingAverage => 1 + (strength * (STABILIZER_MAX_AVERAGED_SAMPLES - 1) as f64).round() as usize,
+			_ => 1,
+		};
+		self.recent.push_back(position);
+		while self.recent.len() > count {
+			self.recent.pop_front();
+		}
+
+		let Some(pen) = self.pen else {
+			self.pen = Some(position);
+			return self.pen;
+		};
+
+		let drawn = match self.options.mode {
+			StabilizerMode::Off => position,
+			StabilizerMode::MovingAverage => self.recent.iter().sum::<DVec2>() / self.recent.len() as f64,
+			StabilizerMode::PullString => {
+				let length = strength * STABILIZER_MAX_STRING_LENGTH;
+				let distance = pen.distance(position);
+				if distance <= length {
+					return None;
+				}
+				position + (pen - position) * (length / distance)
+			}
+		};
+
+		if drawn == pen {
+			return None;
+		}
+		self.pen = Some(drawn);
+		self.pen
+	}
+
+	/// Where the line is drawn to once the pointer is released, which brings it the rest of the way to the pointer since it lags behind.
+	pub fn finish(&mut self) -> Option<DVec2> {
+		let pointer = *self.recent.back()?;
+		(self.pen != Some(pointer)).then(|| {
+			self.pen = Some(pointer);
+			pointer
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn stabilizer(mode: StabilizerMode, strength: f64) -> Stabilizer {
+		Stabilizer::new(StabilizerOptions { mode, strength })
+	}
+
+	#[test]
+	fn moving_average_smooths_a_zigzag() {
+		let mut stabilizer = stabilizer(StabilizerMode::MovingAverage, 100.);
+		let drawn = (0..40)
+			.filter_map(|index| stabilizer.stabilize(DVec2::new(index as f64, if index % 2 == 0 { 10. } else { -10. })))
+			.collect::<Vec<_>>();
+
+		assert_eq!(drawn[0], DVec2::new(0., 10.));
+		assert!(drawn.iter().skip(STABILIZER_MAX_AVERAGED_SAMPLES).all(|point| point.y.abs() < 1.));
+		assert_eq!(stabilizer.finish(), Some(DVec2::new(39., -10.)));
+		assert_eq!(stabilizer.finish(), None);
+	}
+
+	#[test]
+	fn pull_string_ignores_wobbles_shorter_than_the_string() {
+		let length = STABILIZER_MAX_STRING_LENGTH / 2.;
+		let mut stabilizer = stabilizer(StabilizerMode::PullString, 50.);
+
+		assert_eq!(stabilizer.stabilize(DVec2::ZERO), Some(DVec2::ZERO));
+		assert_eq!(stabilizer.stabilize(DVec2::new(0., length / 2.)), None);
+		assert_eq!(stabilizer.stabilize(DVec2::new(length + 5., 0.)), Some(DVec2::new(5., 0.)));
+		assert_eq!(stabilizer.finish(), Some(DVec2::new(length + 5., 0.)));
+	}
+}
@@ -0,0 +1,123 @@
+use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::prelude::Message;
+
+use graphene_core::vector::style::{LineCap, LineJoin, Stroke};
+
+use serde::{Deserialize, Serialize};
+
+#[remain::sorted]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize, specta::Type)]
+pub enum StrokeOptionsUpdate {
+	DashLengths(String),
+	DashOffset(f64),
+	LineCap(LineCap),
+	LineJoin(LineJoin),
+	MiterLimit(f64),
+}
+
+/// Dash pattern, cap, and join settings seen in the [`LayoutTarget::ToolOptions`] bar, given to the strokes of shapes drawn by the tool.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolStrokeOptions {
+	pub dash_lengths: Vec<f32>,
+	pub dash_offset: f64,
+	pub line_cap: LineCap,
+	pub line_join: LineJoin,
+	pub miter_limit: f64,
+}
+
+impl Default for ToolStrokeOptions {
+	fn default() -> Self {
+		let stroke = Stroke::default();
+		Self {
+			dash_lengths: stroke.dash_lengths,
+			dash_offset: stroke.dash_offset,
+			line_cap: stroke.line_cap,
+			line_join: stroke.line_join,
+			miter_limit: stroke.line_join_miter_limit,
+		}
+	}
+}
+
+impl ToolStrokeOptions {
+	/// Gives the stroke these dash, cap, and join settings, keeping its color and weight.
+	pub fn apply(&self, stroke: Stroke) -> Stroke {
+		Stroke {
+			dash_lengths: self.dash_lengths.clone(),
+			dash_offset: self.dash_offset,
+			line_cap: self.line_cap,
+			line_join: self.line_join,
+			line_join_miter_limit: self.miter_limit,
+			..stroke
+		}
+	}
+
+	/// Updates one of the settings. Dash lengths that can't be parsed are ignored, and the tool options are resent to show the previous ones.
+	pub fn update(&mut self, update: StrokeOptionsUpdate) {
+		match update {
+			StrokeOptionsUpdate::DashLengths(dash_lengths) => {
+				if let Some(stroke) = self.apply(Stroke::default()).with_dash_lengths(&dash_lengths) {
+					self.dash_lengths = stroke.dash_lengths;
+				}
+			}
+			StrokeOptionsUpdate::DashOffset(dash_offset) => self.dash_offset = dash_offset,
+			StrokeOptionsUpdate::LineCap(line_cap) => self.line_cap = line_cap,
+			StrokeOptionsUpdate::LineJoin(line_join) => self.line_join = line_join,
+			StrokeOptionsUpdate::MiterLimit(miter_limit) => self.miter_limit = miter_limit,
+		}
+	}
+
+	pub fn create_widgets(&self, update_callback: fn(StrokeOptionsUpdate) -> Message) -> Vec<WidgetHolder> {
+		let stroke = self.apply(Stroke::default());
+
+		let line_caps = [("Butt", LineCap::Butt), ("Round", LineCap::Round), ("Square", LineCap::Square)]
+			.into_iter()
+			.map(|(name, line_cap)| RadioEntryData::new(name).on_update(move |_| update_callback(StrokeOptionsUpdate::LineCap(line_cap))))
+			.collect();
+		let line_joins = [("Miter", LineJoin::Miter), ("Bevel", LineJoin::Bevel), ("Round", LineJoin::Round)]
+			.into_iter()
+			.map(|(name, line_join)| RadioEntryData::new(name).on_update(move |_| update_callback(StrokeOptionsUpdate::LineJoin(line_join))))
+			.collect();
+
+		let row = |label: &str, widget: WidgetHolder| LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new(label).table_align(true).min_width(80).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				widget,
+			],
+		};
+
+		let options = vec![
+			row(
+				"Dash Lengths",
+				TextInput::new(stroke.dash_lengths())
+					.centered(true)
+					.on_update(move |text_input: &TextInput| update_callback(StrokeOptionsUpdate::DashLengths(text_input.value.clone())))
+					.widget_holder(),
+			),
+			row(
+				"Dash Offset",
+				NumberInput::new(Some(self.dash_offset))
+					.unit(" px")
+					.on_update(move |number_input: &NumberInput| update_callback(StrokeOptionsUpdate::DashOffset(number_input.value.unwrap())))
+					.widget_holder(),
+			),
+			row("Line Cap", RadioInput::new(line_caps).selected_index(stroke.line_cap_index()).widget_holder()),
+			row("Line Join", RadioInput::new(line_joins).selected_index(stroke.line_join_index()).widget_holder()),
+			row(
+				"Miter Limit",
+				NumberInput::new(Some(self.miter_limit))
+					.min(0.)
+					.disabled(self.line_join != LineJoin::Miter)
+					.on_update(move |number_input: &NumberInput| update_callback(StrokeOptionsUpdate::MiterLimit(number_input.value.unwrap())))
+					.widget_holder(),
+			),
+		];
+
+		vec![
+			Separator::new(SeparatorType::Related).widget_holder(),
+			PopoverButton::new("Stroke Style", "Dash pattern, line caps, and line joins of the stroke")
+				.options_widget(options)
+				.widget_holder(),
+		]
+	}
+}
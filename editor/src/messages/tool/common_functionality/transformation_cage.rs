@@ -1,8 +1,9 @@
 use crate::application::generate_uuid;
-use crate::consts::{BOUNDS_ROTATE_THRESHOLD, BOUNDS_SELECT_THRESHOLD, COLOR_ACCENT, MANIPULATOR_GROUP_MARKER_SIZE, SELECTION_DRAG_ANGLE};
+use crate::consts::SELECTION_DRAG_ANGLE;
 use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::portfolio::document::utility_types::transformation::OriginalTransforms;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::hit_targets::{bounds_rotate_threshold, bounds_select_threshold, marker_size, viewport_distance_in_local_space};
 
 use document_legacy::layers::style::{self, Fill, Stroke};
 use document_legacy::LayerId;
@@ -128,6 +129,13 @@ impl SelectedEdges {
 		(min, max - min)
 	}
 
+	/// Scales the dragged axes by a typed factor around the opposite edge, returning the scale and its pivot.
+	pub fn typed_scale_transform(&self, factor: f64) -> (DAffine2, DVec2) {
+		let scale_x = if self.left || self.right { factor } else { 1. };
+		let scale_y = if self.top || self.bottom { factor } else { 1. };
+		(DAffine2::from_scale(DVec2::new(scale_x, scale_y)), self.calculate_pivot())
+	}
+
 	/// Calculates the required scaling to resize the bounding box
 	pub fn bounds_to_scale_transform(&self, position: DVec2, size: DVec2) -> (DAffine2, DVec2) {
 		let old_size = self.bounds[1] - self.bounds[0];
@@ -156,7 +164,7 @@ pub fn add_bounding_box(responses: &mut VecDeque<Message>) -> Vec<LayerId> {
 	let operation = Operation::AddRect {
 		path: path.clone(),
 		transform: DAffine2::ZERO.to_cols_array(),
-		style: style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), 1.0)), Fill::None),
+		style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().selection_outline), 1.0)), Fill::None),
 		insert_index: -1,
 	};
 	responses.add(DocumentMessage::Overlays(operation.into()));
@@ -175,7 +183,7 @@ fn add_transform_handles(responses: &mut VecDeque<Message>) -> [Vec<LayerId>; 8]
 		let operation = Operation::AddRect {
 			path: current_path.clone(),
 			transform: DAffine2::ZERO.to_cols_array(),
-			style: style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), 2.0)), Fill::solid(Color::WHITE)),
+			style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().handles), 2.0)), Fill::solid(Color::WHITE)),
 			insert_index: -1,
 		};
 		responses.add(DocumentMessage::Overlays(operation.into()));
@@ -259,7 +267,7 @@ impl BoundingBoxOverlays {
 		const BIAS: f64 = 0.0001;
 
 		for (position, path) in self.evaluate_transform_handle_positions().into_iter().zip(&self.transform_handles) {
-			let scale = DVec2::splat(MANIPULATOR_GROUP_MARKER_SIZE);
+			let scale = DVec2::splat(marker_size());
 			let translation = (position - (scale / 2.) - 0.5 + BIAS).round();
 			let transform = DAffine2::from_scale_angle_translation(scale, 0., translation).to_cols_array();
 			let path = path.clone();
@@ -270,22 +278,22 @@ impl BoundingBoxOverlays {
 	/// Check if the user has selected the edge for dragging (returns which edge in order top, bottom, left, right)
 	pub fn check_selected_edges(&self, cursor: DVec2) -> Option<(bool, bool, bool, bool)> {
 		let cursor = self.transform.inverse().transform_point2(cursor);
-		let select_threshold = self.transform.inverse().transform_vector2(DVec2::new(0., BOUNDS_SELECT_THRESHOLD)).length();
+		let select_threshold = viewport_distance_in_local_space(self.transform, bounds_select_threshold());
 
 		let min = self.bounds[0].min(self.bounds[1]);
 		let max = self.bounds[0].max(self.bounds[1]);
-		if min.x - cursor.x < select_threshold && min.y - cursor.y < select_threshold && cursor.x - max.x < select_threshold && cursor.y - max.y < select_threshold {
-			let mut top = (cursor.y - min.y).abs() < select_threshold;
-			let mut bottom = (max.y - cursor.y).abs() < select_threshold;
-			let mut left = (cursor.x - min.x).abs() < select_threshold;
-			let mut right = (max.x - cursor.x).abs() < select_threshold;
+		if min.x - cursor.x < select_threshold.x && min.y - cursor.y < select_threshold.y && cursor.x - max.x < select_threshold.x && cursor.y - max.y < select_threshold.y {
+			let mut top = (cursor.y - min.y).abs() < select_threshold.y;
+			let mut bottom = (max.y - cursor.y).abs() < select_threshold.y;
+			let mut left = (cursor.x - min.x).abs() < select_threshold.x;
+			let mut right = (max.x - cursor.x).abs() < select_threshold.x;
 
 			// Prioritise single axis transformations on very small bounds
-			if cursor.y - min.y + max.y - cursor.y < select_threshold * 2. && (left || right) {
+			if cursor.y - min.y + max.y - cursor.y < select_threshold.y * 2. && (left || right) {
 				top = false;
 				bottom = false;
 			}
-			if cursor.x - min.x + max.x - cursor.x < select_threshold * 2. && (top || bottom) {
+			if cursor.x - min.x + max.x - cursor.x < select_threshold.x * 2. && (top || bottom) {
 				left = false;
 				right = false;
 			}
@@ -311,13 +319,13 @@ impl BoundingBoxOverlays {
 	/// Check if the user is rotating with the bounds
 	pub fn check_rotate(&self, cursor: DVec2) -> bool {
 		let cursor = self.transform.inverse().transform_point2(cursor);
-		let rotate_threshold = self.transform.inverse().transform_vector2(DVec2::new(0., BOUNDS_ROTATE_THRESHOLD)).length();
+		let rotate_threshold = viewport_distance_in_local_space(self.transform, bounds_rotate_threshold());
 
 		let min = self.bounds[0].min(self.bounds[1]);
 		let max = self.bounds[0].max(self.bounds[1]);
 
 		let outside_bounds = (min.x > cursor.x || cursor.x > max.x) || (min.y > cursor.y || cursor.y > max.y);
-		let inside_extended_bounds = min.x - cursor.x < rotate_threshold && min.y - cursor.y < rotate_threshold && cursor.x - max.x < rotate_threshold && cursor.y - max.y < rotate_threshold;
+		let inside_extended_bounds = min.x - cursor.x < rotate_threshold.x && min.y - cursor.y < rotate_threshold.y && cursor.x - max.x < rotate_threshold.x && cursor.y - max.y < rotate_threshold.y;
 
 		outside_bounds & inside_extended_bounds
 	}
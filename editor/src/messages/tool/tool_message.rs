@@ -1,3 +1,4 @@
+use super::common_functionality::color_selector::ColorSlot;
 use super::utility_types::ToolType;
 use crate::messages::prelude::*;
 
@@ -126,13 +127,22 @@ pub enum ToolMessage {
 	#[remain::unsorted]
 	ActivateToolFrame,
 
+	AbortCurrent,
 	ActivateTool {
 		tool_type: ToolType,
 	},
+	ApplyPrimaryColorToSelection {
+		slot: ColorSlot,
+	},
+	ConfirmCurrent,
 	DeactivateTools,
 	InitTools,
 	RefreshToolOptions,
+	RefreshToolStatus,
 	ResetColors,
+	SelectColorSlot {
+		slot: ColorSlot,
+	},
 	SelectPrimaryColor {
 		color: Color,
 	},
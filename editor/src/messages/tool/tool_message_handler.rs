@@ -1,15 +1,18 @@
 use super::common_functionality::overlay_renderer::OverlayRenderer;
 use super::common_functionality::shape_editor::ShapeState;
-use super::utility_types::{tool_message_to_tool_type, ToolActionHandlerData, ToolFsmState};
+use super::utility_types::{tool_message_to_tool_type, ToolActionHandlerData, ToolFsmState, ToolMetadata, ToolStatus};
 use crate::application::generate_uuid;
+use crate::messages::input_mapper::utility_types::input_keyboard::Key;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::utility_types::PersistentData;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::color_selector::ColorSlot;
 use crate::messages::tool::utility_types::ToolType;
 use crate::node_graph_executor::NodeGraphExecutor;
 
 use document_legacy::layers::style::RenderData;
 use graphene_core::raster::color::Color;
+use graphene_core::vector::style::Fill;
 
 #[derive(Debug, Default)]
 pub struct ToolMessageHandler {
@@ -17,6 +20,8 @@ pub struct ToolMessageHandler {
 	pub transform_layer_handler: TransformLayerMessageHandler,
 	pub shape_overlay: OverlayRenderer,
 	pub shape_editor: ShapeState,
+	/// The status last sent to the frontend, so it is only sent again when something changes
+	tool_status: ToolStatus,
 }
 
 impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocessorMessageHandler, &PersistentData, &NodeGraphExecutor)> for ToolMessageHandler {
@@ -29,6 +34,11 @@ impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocess
 	) {
 		let render_data = RenderData::new(&persistent_data.font_cache, document.view_mode, None);
 
+		// Any tool message may change the tool status, which is refreshed once the messages resulting from the input are processed
+		if !matches!(message, ToolMessage::RefreshToolStatus | ToolMessage::DeactivateTools) {
+			responses.add(ToolMessage::RefreshToolStatus);
+		}
+
 		#[remain::sorted]
 		match message {
 			// Messages
@@ -74,6 +84,11 @@ impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocess
 			#[remain::unsorted]
 			ToolMessage::ActivateToolImaginate => responses.add_front(ToolMessage::ActivateTool { tool_type: ToolType::Imaginate }),
 
+			ToolMessage::AbortCurrent => {
+				if let Some(message) = self.tool_state.tool_data.active_tool().event_to_message_map().tool_abort {
+					responses.add_front(message);
+				}
+			}
 			ToolMessage::ActivateTool { tool_type } => {
 				let tool_data = &mut self.tool_state.tool_data;
 				let old_tool = tool_data.active_tool_type;
@@ -118,6 +133,7 @@ impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocess
 
 				// Store the new active tool
 				tool_data.active_tool_type = tool_type;
+				responses.add(WorkspaceMessage::SetActiveTool { tool_type });
 
 				// Subscribe new tool
 				tool_data.tools.get(&tool_type).unwrap().activate(responses);
@@ -137,6 +153,27 @@ impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocess
 				// Notify the frontend about the new active tool to be displayed
 				tool_data.send_layout(responses, LayoutTarget::ToolShelf);
 			}
+			ToolMessage::ApplyPrimaryColorToSelection { slot } => {
+				let color = self.tool_state.document_tool_data.primary_color;
+				let layers: Vec<_> = document.selected_layers().filter(|path| !document.is_raster_layer(path)).map(|path| path.to_vec()).collect();
+				if layers.is_empty() {
+					return;
+				}
+
+				responses.add(DocumentMessage::StartTransaction);
+				for layer in layers {
+					match slot {
+						ColorSlot::Fill => responses.add(GraphOperationMessage::FillSet { layer, fill: Fill::Solid(color) }),
+						ColorSlot::Stroke => responses.add(GraphOperationMessage::StrokeColorSet { layer, color: Some(color) }),
+					}
+				}
+				responses.add(DocumentMessage::CommitTransaction);
+			}
+			ToolMessage::ConfirmCurrent => {
+				if let Some(message) = self.tool_state.tool_data.active_tool().event_to_message_map().tool_confirm {
+					responses.add_front(message);
+				}
+			}
 			ToolMessage::DeactivateTools => {
 				let tool_data = &mut self.tool_state.tool_data;
 				tool_data.tools.get(&tool_data.active_tool_type).unwrap().deactivate(responses);
@@ -145,6 +182,13 @@ impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocess
 				let message = Box::new(TransformLayerMessage::SelectionChanged.into());
 				let on = BroadcastEvent::SelectionChanged;
 				responses.add(BroadcastMessage::UnsubscribeEvent { message, on });
+
+				// Stop refreshing the tool status and hide it
+				let message = Box::new(ToolMessage::RefreshToolStatus.into());
+				let on = BroadcastEvent::ModifierKeysChanged;
+				responses.add(BroadcastMessage::UnsubscribeEvent { message, on });
+				self.tool_status = ToolStatus::default();
+				responses.add(FrontendMessage::UpdateToolStatus { status: ToolStatus::default() });
 			}
 			ToolMessage::InitTools => {
 				// Subscribe the transform layer to selection change events
@@ -152,6 +196,11 @@ impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocess
 				let on = BroadcastEvent::SelectionChanged;
 				responses.add(BroadcastMessage::SubscribeEvent { send, on });
 
+				// Subscribe the tool status to modifier key changes, which don't otherwise reach the tools
+				let send = Box::new(ToolMessage::RefreshToolStatus.into());
+				let on = BroadcastEvent::ModifierKeysChanged;
+				responses.add(BroadcastMessage::SubscribeEvent { send, on });
+
 				let tool_data = &mut self.tool_state.tool_data;
 				let document_data = &self.tool_state.document_tool_data;
 				let active_tool = &tool_data.active_tool_type;
@@ -188,6 +237,27 @@ impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocess
 				let tool_data = &mut self.tool_state.tool_data;
 				tool_data.tools.get(&tool_data.active_tool_type).unwrap().send_layout(responses, LayoutTarget::ToolOptions);
 			}
+			ToolMessage::RefreshToolStatus => {
+				let tool_data = &self.tool_state.tool_data;
+				let modifiers = [Key::Shift, Key::Control, Key::Alt, Key::Meta, Key::Command];
+				let mut status = ToolStatus {
+					tool: tool_data.tools.get(&tool_data.active_tool_type).map(|tool| tool.tooltip()).unwrap_or_default(),
+					modifiers: modifiers.into_iter().filter(|&key| input.keyboard.get(key as usize)).map(|key| key.to_string()).collect(),
+					operation: self.transform_layer_handler.operation_label(),
+					typed_value: self.transform_layer_handler.typed_text(),
+					measurement: None,
+				};
+				if status.operation.is_none() {
+					if let Some(tool) = tool_data.tools.get(&tool_data.active_tool_type) {
+						tool.update_status(&mut status);
+					}
+				}
+
+				if status != self.tool_status {
+					self.tool_status = status.clone();
+					responses.add(FrontendMessage::UpdateToolStatus { status });
+				}
+			}
 			ToolMessage::ResetColors => {
 				let document_data = &mut self.tool_state.document_tool_data;
 
@@ -196,6 +266,12 @@ impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocess
 
 				document_data.update_working_colors(responses);
 			}
+			ToolMessage::SelectColorSlot { slot } => {
+				let document_data = &mut self.tool_state.document_tool_data;
+				document_data.active_slot = slot;
+
+				document_data.update_working_colors(responses);
+			}
 			ToolMessage::SelectPrimaryColor { color } => {
 				let document_data = &mut self.tool_state.document_tool_data;
 				document_data.primary_color = color;
@@ -291,7 +367,25 @@ impl MessageHandler<ToolMessage, (&DocumentMessageHandler, u64, &InputPreprocess
 			ResetColors,
 			SwapColors,
 		);
-		list.extend(self.tool_state.tool_data.active_tool().actions());
+
+		// Escape and Enter are only taken while the active tool can abort or confirm in its current state, so they are left to other handlers otherwise
+		let active_tool = self.tool_state.tool_data.active_tool();
+		let tool_actions = active_tool.actions();
+		let event_to_message_map = active_tool.event_to_message_map();
+		let available = |message: Option<ToolMessage>| {
+			message.map_or(false, |message| {
+				let discriminant = Message::from(message).to_discriminant();
+				tool_actions.iter().flatten().any(|action| *action == discriminant)
+			})
+		};
+		if available(event_to_message_map.tool_abort) {
+			list.extend(actions!(ToolMessageDiscriminant; AbortCurrent));
+		}
+		if available(event_to_message_map.tool_confirm) {
+			list.extend(actions!(ToolMessageDiscriminant; ConfirmCurrent));
+		}
+
+		list.extend(tool_actions);
 		list.extend(self.transform_layer_handler.actions());
 
 		list
@@ -1,16 +1,15 @@
 use crate::application::generate_uuid;
-use crate::consts::SELECTION_TOLERANCE;
 use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::utility_types::misc::TargetDocument;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::hit_targets::pointer_quad;
 use crate::messages::tool::common_functionality::snapping::SnapManager;
 use crate::messages::tool::common_functionality::transformation_cage::*;
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
-use document_legacy::intersection::Quad;
 use document_legacy::LayerId;
 
 use glam::{DVec2, IVec2, Vec2Swizzles};
@@ -183,8 +182,7 @@ impl Fsm for ArtboardToolFsmState {
 						ArtboardToolFsmState::ResizingBounds
 					} else {
 						responses.add(DocumentMessage::StartTransaction);
-						let tolerance = DVec2::splat(SELECTION_TOLERANCE);
-						let quad = Quad::from_box([input.mouse.position - tolerance, input.mouse.position + tolerance]);
+						let quad = pointer_quad(input.mouse.position);
 						let intersection = document.artboard_message_handler.artboards_document.intersects_quad_root(quad, render_data);
 
 						responses.add(BroadcastEvent::DocumentIsDirty);
@@ -1,10 +1,11 @@
 use crate::messages::frontend::utility_types::MouseCursorIcon;
-use crate::messages::input_mapper::utility_types::input_keyboard::MouseMotion;
+use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::node_graph::transform_utils::get_current_transform;
 use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
 use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::common_functionality::stabilizer::{Stabilizer, StabilizerMode, StabilizerOptions};
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
@@ -16,7 +17,7 @@ use graphene_core::raster::{BlendMode, ImageFrame};
 use graphene_core::vector::brush_stroke::{BrushInputSample, BrushStroke, BrushStyle};
 use graphene_core::Color;
 
-use glam::DAffine2;
+use glam::{DAffine2, DVec2};
 use serde::{Deserialize, Serialize};
 
 const EXPOSED_BLEND_MODES: &[&[BlendMode]] = {
@@ -72,6 +73,7 @@ pub struct BrushOptions {
 	color: ToolColorOptions,
 	blend_mode: BlendMode,
 	draw_mode: DrawMode,
+	stabilizer: StabilizerOptions,
 }
 
 impl Default for BrushOptions {
@@ -84,6 +86,7 @@ impl Default for BrushOptions {
 			color: ToolColorOptions::default(),
 			blend_mode: BlendMode::Normal,
 			draw_mode: DrawMode::Draw,
+			stabilizer: StabilizerOptions::default(),
 		}
 	}
 }
@@ -101,6 +104,8 @@ pub enum BrushToolMessage {
 	// Tool-specific messages
 	DragStart,
 	DragStop,
+	/// Starts a stroke that erases regardless of the draw mode, such as when drawing with the eraser end of a pen.
+	EraseStart,
 	PointerMove,
 	UpdateOptions(BrushToolMessageOptionsUpdate),
 }
@@ -117,6 +122,8 @@ pub enum BrushToolMessageOptionsUpdate {
 	Flow(f64),
 	Hardness(f64),
 	Spacing(f64),
+	Stabilizer(StabilizerMode),
+	StabilizerStrength(f64),
 	WorkingColors(Option<Color>, Option<Color>),
 }
 
@@ -215,6 +222,13 @@ impl LayoutHolder for BrushTool {
 				.widget_holder(),
 		);
 
+		widgets.push(Separator::new(SeparatorType::Section).widget_holder());
+
+		widgets.append(&mut self.options.stabilizer.create_widgets(
+			|mode| BrushToolMessage::UpdateOptions(BrushToolMessageOptionsUpdate::Stabilizer(mode)).into(),
+			|strength| BrushToolMessage::UpdateOptions(BrushToolMessageOptionsUpdate::StabilizerStrength(strength)).into(),
+		));
+
 		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
 }
@@ -241,6 +255,8 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for BrushTo
 				BrushToolMessageOptionsUpdate::Hardness(hardness) => self.options.hardness = hardness,
 				BrushToolMessageOptionsUpdate::Flow(flow) => self.options.flow = flow,
 				BrushToolMessageOptionsUpdate::Spacing(spacing) => self.options.spacing = spacing,
+				BrushToolMessageOptionsUpdate::Stabilizer(mode) => self.options.stabilizer.mode = mode,
+				BrushToolMessageOptionsUpdate::StabilizerStrength(strength) => self.options.stabilizer.strength = strength,
 				BrushToolMessageOptionsUpdate::Color(color) => {
 					self.options.color.custom_color = color;
 					self.options.color.color_type = ToolColorType::Custom;
@@ -267,6 +283,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for BrushTo
 			Ready => actions!(BrushToolMessageDiscriminant;
 				DragStart,
 				DragStop,
+				EraseStart,
 				Abort,
 				UpdateOptions,
 			),
@@ -294,10 +311,21 @@ impl ToolTransition for BrushTool {
 struct BrushToolData {
 	strokes: Vec<BrushStroke>,
 	layer_path: Vec<LayerId>,
+	/// Where a new layer for the strokes is inserted in its folder
+	insert_index: isize,
 	transform: DAffine2,
+	stabilizer: Stabilizer,
 }
 
 impl BrushToolData {
+	/// Continues the latest stroke to the position in viewport space.
+	fn extend_stroke(&mut self, viewport_position: DVec2, document_transform: DAffine2) {
+		let layer_position = (document_transform * self.transform).inverse().transform_point2(viewport_position);
+		if let Some(stroke) = self.strokes.last_mut() {
+			stroke.trace.push(BrushInputSample { position: layer_position })
+		}
+	}
+
 	fn load_existing_strokes(&mut self, document: &DocumentMessageHandler) -> Option<&Vec<LayerId>> {
 		self.transform = DAffine2::IDENTITY;
 		if document.selected_layers().count() != 1 {
@@ -351,19 +379,22 @@ impl Fsm for BrushToolFsmState {
 		responses: &mut VecDeque<Message>,
 	) -> Self {
 		let document_position = (document.document_legacy.root.transform).inverse().transform_point2(input.mouse.position);
-		let layer_position = tool_data.transform.inverse().transform_point2(document_position);
 
 		if let ToolMessage::Brush(event) = event {
 			match (self, event) {
-				(BrushToolFsmState::Ready, BrushToolMessage::DragStart) => {
+				(BrushToolFsmState::Ready, BrushToolMessage::DragStart | BrushToolMessage::EraseStart) => {
+					let erase = matches!(event, BrushToolMessage::EraseStart);
 					responses.add(DocumentMessage::StartTransaction);
 					let layer_path = tool_data.load_existing_strokes(document);
 					let new_layer = layer_path.is_none();
 					if new_layer {
 						responses.add(DocumentMessage::DeselectAllLayers);
 						tool_data.layer_path = document.get_path_for_new_layer();
+						tool_data.insert_index = document.get_insert_index_for_new_layer();
 					}
 					let layer_position = tool_data.transform.inverse().transform_point2(document_position);
+					tool_data.stabilizer = Stabilizer::new(tool_options.stabilizer);
+					tool_data.stabilizer.stabilize(input.mouse.position);
 					// TODO: Also scale it based on the input image ('Background' parameter).
 					// TODO: Resizing the input image results in a different brush size from the chosen diameter.
 					let layer_scale = 0.0001_f64 // Safety against division by zero
@@ -372,6 +403,7 @@ impl Fsm for BrushToolFsmState {
 
 					// Start a new stroke with a single sample
 					let blend_mode = match tool_options.draw_mode {
+						_ if erase => BlendMode::Erase,
 						DrawMode::Draw => tool_options.blend_mode,
 						DrawMode::Erase => BlendMode::Erase,
 						DrawMode::Restore => BlendMode::Restore,
@@ -397,8 +429,8 @@ impl Fsm for BrushToolFsmState {
 				}
 
 				(BrushToolFsmState::Drawing, BrushToolMessage::PointerMove) => {
-					if let Some(stroke) = tool_data.strokes.last_mut() {
-						stroke.trace.push(BrushInputSample { position: layer_position })
+					if let Some(stabilized) = tool_data.stabilizer.stabilize(input.mouse.position) {
+						tool_data.extend_stroke(stabilized, document.document_legacy.root.transform);
 					}
 					tool_data.update_strokes(responses);
 
@@ -406,6 +438,12 @@ impl Fsm for BrushToolFsmState {
 				}
 
 				(BrushToolFsmState::Drawing, BrushToolMessage::DragStop) | (BrushToolFsmState::Drawing, BrushToolMessage::Abort) => {
+					// The stabilized stroke lags behind the pointer, so it's brought the rest of the way to where the pointer was released
+					if let Some(stabilized) = tool_data.stabilizer.finish() {
+						tool_data.extend_stroke(stabilized, document.document_legacy.root.transform);
+						tool_data.update_strokes(responses);
+					}
+
 					if !tool_data.strokes.is_empty() {
 						responses.add(DocumentMessage::CommitTransaction);
 					} else {
@@ -433,7 +471,10 @@ impl Fsm for BrushToolFsmState {
 
 	fn update_hints(&self, responses: &mut VecDeque<Message>) {
 		let hint_data = match self {
-			BrushToolFsmState::Ready => HintData(vec![HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Draw Stroke")])]),
+			BrushToolFsmState::Ready => HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Draw Stroke")]),
+				HintGroup(vec![HintInfo::keys([Key::PenEraser], "Erase")]),
+			]),
 			BrushToolFsmState::Drawing => HintData(vec![]),
 		};
 
@@ -451,5 +492,5 @@ fn add_brush_render(_tool_options: &BrushOptions, data: &BrushToolData, response
 	if let Some(node) = network.nodes.get_mut(&output_node) {
 		node.inputs.push(NodeInput::value(TaggedValue::ImageFrame(ImageFrame::empty()), true))
 	}
-	graph_modification_utils::new_custom_layer(network, data.layer_path.clone(), responses);
+	graph_modification_utils::new_custom_layer_at(network, data.layer_path.clone(), data.insert_index, responses);
 }
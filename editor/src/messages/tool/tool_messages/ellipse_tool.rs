@@ -4,7 +4,10 @@ use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
 use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::common_functionality::hit_targets::drag_threshold;
 use crate::messages::tool::common_functionality::resize::Resize;
+use crate::messages::tool::common_functionality::shape_presets::ToolShapePreset;
+use crate::messages::tool::common_functionality::stroke_options::{StrokeOptionsUpdate, ToolStrokeOptions};
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
@@ -23,16 +26,22 @@ pub struct EllipseTool {
 
 pub struct EllipseToolOptions {
 	line_weight: f64,
+	lock_aspect_ratio: bool,
 	fill: ToolColorOptions,
 	stroke: ToolColorOptions,
+	stroke_style: ToolStrokeOptions,
+	preset: ToolShapePreset,
 }
 
 impl Default for EllipseToolOptions {
 	fn default() -> Self {
 		Self {
 			line_weight: 5.,
+			lock_aspect_ratio: false,
 			fill: ToolColorOptions::new_secondary(),
 			stroke: ToolColorOptions::new_primary(),
+			stroke_style: ToolStrokeOptions::default(),
+			preset: ToolShapePreset::default(),
 		}
 	}
 }
@@ -43,8 +52,11 @@ pub enum EllipseOptionsUpdate {
 	FillColor(Option<Color>),
 	FillColorType(ToolColorType),
 	LineWeight(f64),
+	LockAspectRatio(bool),
+	Preset(Option<String>),
 	StrokeColor(Option<Color>),
 	StrokeColorType(ToolColorType),
+	StrokeStyle(StrokeOptionsUpdate),
 	WorkingColors(Option<Color>, Option<Color>),
 }
 
@@ -89,6 +101,14 @@ fn create_weight_widget(line_weight: f64) -> WidgetHolder {
 		.widget_holder()
 }
 
+fn create_lock_aspect_ratio_widget(lock_aspect_ratio: bool) -> WidgetHolder {
+	CheckboxInput::new(lock_aspect_ratio)
+		.icon("Link")
+		.tooltip("Lock Aspect Ratio (instead of holding Shift)")
+		.on_update(|input: &CheckboxInput| EllipseToolMessage::UpdateOptions(EllipseOptionsUpdate::LockAspectRatio(input.checked)).into())
+		.widget_holder()
+}
+
 impl LayoutHolder for EllipseTool {
 	fn layout(&self) -> Layout {
 		let mut widgets = self.options.fill.create_widgets(
@@ -110,6 +130,16 @@ impl LayoutHolder for EllipseTool {
 		));
 		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
 		widgets.push(create_weight_widget(self.options.line_weight));
+		widgets.append(
+			&mut self
+				.options
+				.stroke_style
+				.create_widgets(|update| EllipseToolMessage::UpdateOptions(EllipseOptionsUpdate::StrokeStyle(update)).into()),
+		);
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		widgets.push(create_lock_aspect_ratio_widget(self.options.lock_aspect_ratio));
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		widgets.append(&mut self.options.preset.create_widgets(|name| EllipseToolMessage::UpdateOptions(EllipseOptionsUpdate::Preset(name)).into()));
 
 		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
@@ -118,6 +148,12 @@ impl LayoutHolder for EllipseTool {
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for EllipseTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
 		if let ToolMessage::Ellipse(EllipseToolMessage::UpdateOptions(action)) = message {
+			// Changing the style means it no longer matches the chosen preset
+			let changes_style = !matches!(
+				action,
+				EllipseOptionsUpdate::LockAspectRatio(_) | EllipseOptionsUpdate::Preset(_) | EllipseOptionsUpdate::WorkingColors(..)
+			);
+
 			match action {
 				EllipseOptionsUpdate::FillColor(color) => {
 					self.options.fill.custom_color = color;
@@ -125,11 +161,19 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Ellipse
 				}
 				EllipseOptionsUpdate::FillColorType(color_type) => self.options.fill.color_type = color_type,
 				EllipseOptionsUpdate::LineWeight(line_weight) => self.options.line_weight = line_weight,
+				EllipseOptionsUpdate::LockAspectRatio(lock_aspect_ratio) => self.options.lock_aspect_ratio = lock_aspect_ratio,
+				EllipseOptionsUpdate::Preset(name) => {
+					if let Some(preset) = self.options.preset.select(name) {
+						let options = &mut self.options;
+						preset.apply(&mut options.fill, &mut options.stroke, &mut options.line_weight, &mut options.stroke_style);
+					}
+				}
 				EllipseOptionsUpdate::StrokeColor(color) => {
 					self.options.stroke.custom_color = color;
 					self.options.stroke.color_type = ToolColorType::Custom;
 				}
 				EllipseOptionsUpdate::StrokeColorType(color_type) => self.options.stroke.color_type = color_type,
+				EllipseOptionsUpdate::StrokeStyle(update) => self.options.stroke_style.update(update),
 				EllipseOptionsUpdate::WorkingColors(primary, secondary) => {
 					self.options.stroke.primary_working_color = primary;
 					self.options.stroke.secondary_working_color = secondary;
@@ -137,6 +181,9 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Ellipse
 					self.options.fill.secondary_working_color = secondary;
 				}
 			}
+			if changes_style {
+				self.options.preset = ToolShapePreset::default();
+			}
 
 			self.send_layout(responses, LayoutTarget::ToolOptions);
 
@@ -215,12 +262,13 @@ impl Fsm for EllipseToolFsmState {
 
 					// Create a new layer path for this shape
 					let layer_path = document.get_path_for_new_layer();
+					let insert_index = document.get_insert_index_for_new_layer();
 					shape_data.path = Some(layer_path.clone());
 
 					// Create a new ellipse vector shape
 					let subpath = bezier_rs::Subpath::new_ellipse(DVec2::ZERO, DVec2::ONE);
 					let manipulator_groups = subpath.manipulator_groups().to_vec();
-					graph_modification_utils::new_vector_layer(vec![subpath], layer_path.clone(), responses);
+					graph_modification_utils::new_vector_layer_at(vec![subpath], layer_path.clone(), insert_index, responses);
 					graph_modification_utils::set_manipulator_mirror_angle(&manipulator_groups, &layer_path, true, responses);
 
 					let fill_color = tool_options.fill.active_color();
@@ -231,20 +279,28 @@ impl Fsm for EllipseToolFsmState {
 
 					responses.add(GraphOperationMessage::StrokeSet {
 						layer: layer_path,
-						stroke: Stroke::new(tool_options.stroke.active_color(), tool_options.line_weight),
+						stroke: tool_options.stroke_style.apply(Stroke::new(tool_options.stroke.active_color(), tool_options.line_weight)),
 					});
 
 					Drawing
 				}
 				(state, Resize { center, lock_ratio }) => {
-					if let Some(message) = shape_data.calculate_transform(responses, document, input, center, lock_ratio, false) {
+					if let Some(message) = shape_data.calculate_transform(responses, document, input, center, lock_ratio, tool_options.lock_aspect_ratio, false) {
 						responses.add(message);
 					}
 
 					state
 				}
 				(Drawing, DragStop) => {
-					input.mouse.finish_transaction(shape_data.viewport_drag_start(document), responses);
+					// A click places a shape at the size of the chosen preset, where it would otherwise be discarded for having no size
+					let clicked = shape_data.viewport_drag_start(document).distance(input.mouse.position) <= drag_threshold();
+					match tool_options.preset.size().filter(|_| clicked).and_then(|size| shape_data.place_with_size(document, size)) {
+						Some(message) => {
+							responses.add(message);
+							responses.add(DocumentMessage::CommitTransaction);
+						}
+						None => input.mouse.finish_transaction(shape_data.viewport_drag_start(document), responses),
+					}
 					shape_data.cleanup(responses);
 
 					Ready
@@ -2,6 +2,7 @@ use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::sampled_color::SampledColorSpace;
 use crate::messages::tool::utility_types::{DocumentToolData, EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
@@ -11,6 +12,13 @@ use serde::{Deserialize, Serialize};
 pub struct EyedropperTool {
 	fsm_state: EyedropperToolFsmState,
 	data: EyedropperToolData,
+	options: EyedropperOptions,
+}
+
+#[derive(Default)]
+pub struct EyedropperOptions {
+	/// Samples from a capture of the whole window, including the panels around the canvas, instead of from the rendered artwork.
+	sample_anywhere: bool,
 }
 
 #[remain::sorted]
@@ -24,9 +32,24 @@ pub enum EyedropperToolMessage {
 	// Tool-specific messages
 	LeftPointerDown,
 	LeftPointerUp,
+	PointerEnter,
+	PointerLeave,
 	PointerMove,
 	RightPointerDown,
 	RightPointerUp,
+	SampleForColorPicker,
+	UpdateOptions(EyedropperOptionsUpdate),
+	/// The frontend read the pixel clicked in its capture of the window, in the given color space.
+	WindowColorSampled {
+		pixel: [u8; 4],
+		color_space: SampledColorSpace,
+	},
+}
+
+#[remain::sorted]
+#[derive(PartialEq, Eq, Clone, Debug, Hash, Serialize, Deserialize, specta::Type)]
+pub enum EyedropperOptionsUpdate {
+	SampleAnywhere(bool),
 }
 
 impl ToolMetadata for EyedropperTool {
@@ -43,13 +66,34 @@ impl ToolMetadata for EyedropperTool {
 
 impl LayoutHolder for EyedropperTool {
 	fn layout(&self) -> Layout {
-		Layout::WidgetLayout(WidgetLayout::default())
+		let sample_anywhere = CheckboxInput::new(self.options.sample_anywhere)
+			.tooltip("Sample colors from anywhere in the window, including reference images in other panels, rather than only the artwork on the canvas")
+			.on_update(|input: &CheckboxInput| EyedropperToolMessage::UpdateOptions(EyedropperOptionsUpdate::SampleAnywhere(input.checked)).into())
+			.widget_holder();
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row {
+			widgets: vec![
+				sample_anywhere,
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextLabel::new("Sample Anywhere").widget_holder(),
+			],
+		}]))
 	}
 }
 
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for EyedropperTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
-		self.fsm_state.process_event(message, &mut self.data, tool_data, &(), responses, true);
+		if let ToolMessage::Eyedropper(EyedropperToolMessage::UpdateOptions(action)) = message {
+			match action {
+				EyedropperOptionsUpdate::SampleAnywhere(sample_anywhere) => self.options.sample_anywhere = sample_anywhere,
+			}
+
+			self.send_layout(responses, LayoutTarget::ToolOptions);
+
+			return;
+		}
+
+		self.fsm_state.process_event(message, &mut self.data, tool_data, &self.options, responses, true);
 	}
 
 	advertise_actions!(EyedropperToolMessageDiscriminant;
@@ -67,6 +111,8 @@ impl ToolTransition for EyedropperTool {
 		EventToMessageMap {
 			tool_abort: Some(EyedropperToolMessage::Abort.into()),
 			working_color_changed: Some(EyedropperToolMessage::PointerMove.into()),
+			pointer_enter: Some(EyedropperToolMessage::PointerEnter.into()),
+			pointer_leave: Some(EyedropperToolMessage::PointerLeave.into()),
 			..Default::default()
 		}
 	}
@@ -78,21 +124,28 @@ enum EyedropperToolFsmState {
 	Ready,
 	SamplingPrimary,
 	SamplingSecondary,
+	/// Waiting for the frontend to capture the window and for the user to click the color in it.
+	SamplingWindow,
 }
 
 #[derive(Clone, Debug, Default)]
-struct EyedropperToolData {}
+struct EyedropperToolData {
+	/// The next sampled color goes to the color picker instead of the working colors.
+	for_color_picker: bool,
+	/// The working color that the color sampled from the window goes to, which is the secondary one when `false`.
+	window_sample_to_primary: bool,
+}
 
 impl Fsm for EyedropperToolFsmState {
 	type ToolData = EyedropperToolData;
-	type ToolOptions = ();
+	type ToolOptions = EyedropperOptions;
 
 	fn transition(
 		self,
 		event: ToolMessage,
-		_tool_data: &mut Self::ToolData,
+		tool_data: &mut Self::ToolData,
 		ToolActionHandlerData { global_tool_data, input, .. }: &mut ToolActionHandlerData,
-		_tool_options: &Self::ToolOptions,
+		tool_options: &Self::ToolOptions,
 		responses: &mut VecDeque<Message>,
 	) -> Self {
 		use EyedropperToolFsmState::*;
@@ -100,6 +153,26 @@ impl Fsm for EyedropperToolFsmState {
 
 		if let ToolMessage::Eyedropper(event) = event {
 			match (self, event) {
+				// Ready -> SamplingWindow
+				(Ready, mouse_down) if tool_options.sample_anywhere && (mouse_down == LeftPointerDown || mouse_down == RightPointerDown) => {
+					tool_data.window_sample_to_primary = mouse_down == LeftPointerDown;
+					responses.add(FrontendMessage::TriggerSampleWindowColor);
+
+					SamplingWindow
+				}
+				// SamplingWindow -> Ready
+				(SamplingWindow, WindowColorSampled { pixel, color_space }) => {
+					let color = color_space.to_document_color(pixel);
+					if std::mem::take(&mut tool_data.for_color_picker) {
+						responses.add(ColorPickerMessage::FinishSampling { color });
+					} else if tool_data.window_sample_to_primary {
+						responses.add(ToolMessage::SelectPrimaryColor { color });
+					} else {
+						responses.add(ToolMessage::SelectSecondaryColor { color });
+					}
+
+					Ready
+				}
 				// Ready -> Sampling
 				(Ready, mouse_down) | (Ready, mouse_down) if mouse_down == LeftPointerDown || mouse_down == RightPointerDown => {
 					update_cursor_preview(responses, input, global_tool_data, None);
@@ -111,26 +184,45 @@ impl Fsm for EyedropperToolFsmState {
 					}
 				}
 				// Sampling -> Sampling
-				(SamplingPrimary, PointerMove) | (SamplingSecondary, PointerMove) => {
-					if input.viewport_bounds.in_bounds(input.mouse.position) {
+				(SamplingPrimary | SamplingSecondary, PointerMove | PointerEnter) => {
+					if input.pointer_in_viewport {
 						update_cursor_preview(responses, input, global_tool_data, None);
-					} else {
-						disable_cursor_preview(responses);
 					}
 
 					self
 				}
+				(SamplingPrimary | SamplingSecondary, PointerLeave) => {
+					disable_cursor_preview(responses);
+
+					self
+				}
 				// Sampling -> Ready
 				(SamplingPrimary, mouse_up) | (SamplingSecondary, mouse_up) if mouse_up == LeftPointerUp || mouse_up == RightPointerUp => {
-					let set_color_choice = if self == SamplingPrimary { "Primary".to_string() } else { "Secondary".to_string() };
+					let set_color_choice = match self {
+						_ if std::mem::take(&mut tool_data.for_color_picker) => "ColorPicker".to_string(),
+						SamplingPrimary => "Primary".to_string(),
+						_ => "Secondary".to_string(),
+					};
 					update_cursor_preview(responses, input, global_tool_data, Some(set_color_choice));
 					disable_cursor_preview(responses);
 
 					Ready
 				}
+				// Ready -> Ready
+				(Ready, SampleForColorPicker) => {
+					tool_data.for_color_picker = true;
+
+					self
+				}
 				// Any -> Ready
 				(_, Abort) => {
+					if self == SamplingWindow {
+						responses.add(FrontendMessage::TriggerCancelWindowColorSampling);
+					}
 					disable_cursor_preview(responses);
+					if std::mem::take(&mut tool_data.for_color_picker) {
+						responses.add(ColorPickerMessage::CancelSampling);
+					}
 
 					Ready
 				}
@@ -149,6 +241,10 @@ impl Fsm for EyedropperToolFsmState {
 				HintInfo::mouse(MouseMotion::Rmb, "Sample to Secondary"),
 			])]),
 			EyedropperToolFsmState::SamplingPrimary | EyedropperToolFsmState::SamplingSecondary => HintData(vec![HintGroup(vec![HintInfo::keys([Key::Escape], "Cancel")])]),
+			EyedropperToolFsmState::SamplingWindow => HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Sample Anywhere in the Window")]),
+				HintGroup(vec![HintInfo::keys([Key::Escape], "Cancel")]),
+			]),
 		};
 
 		responses.add(FrontendMessage::UpdateInputHints { hint_data });
@@ -156,7 +252,7 @@ impl Fsm for EyedropperToolFsmState {
 
 	fn update_cursor(&self, responses: &mut VecDeque<Message>) {
 		let cursor = match *self {
-			EyedropperToolFsmState::Ready => MouseCursorIcon::Default,
+			EyedropperToolFsmState::Ready | EyedropperToolFsmState::SamplingWindow => MouseCursorIcon::Default,
 			EyedropperToolFsmState::SamplingPrimary | EyedropperToolFsmState::SamplingSecondary => MouseCursorIcon::None,
 		};
 
@@ -1,22 +1,26 @@
-use crate::consts::SELECTION_TOLERANCE;
 use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::input_mapper::utility_types::input_keyboard::MouseMotion;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::color_selector::ColorSlot;
+use crate::messages::tool::common_functionality::hit_targets::pointer_quad;
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
-use document_legacy::intersection::Quad;
-use document_legacy::layers::layer_layer::CachedOutputData;
 use document_legacy::layers::style::Fill;
 
-use glam::DVec2;
 use serde::{Deserialize, Serialize};
 
 #[derive(Default)]
 pub struct FillTool {
 	fsm_state: FillToolFsmState,
 	data: FillToolData,
+	options: FillOptions,
+}
+
+#[derive(Default)]
+pub struct FillOptions {
+	slot: ColorSlot,
 }
 
 #[remain::sorted]
@@ -26,10 +30,19 @@ pub enum FillToolMessage {
 	// Standard messages
 	#[remain::unsorted]
 	Abort,
+	#[remain::unsorted]
+	WorkingColorChanged,
 
 	// Tool-specific messages
 	LeftPointerDown,
 	RightPointerDown,
+	UpdateOptions(FillOptionsUpdate),
+}
+
+#[remain::sorted]
+#[derive(PartialEq, Eq, Clone, Debug, Hash, Serialize, Deserialize, specta::Type)]
+pub enum FillOptionsUpdate {
+	Slot(ColorSlot),
 }
 
 impl ToolMetadata for FillTool {
@@ -46,12 +59,37 @@ impl ToolMetadata for FillTool {
 
 impl LayoutHolder for FillTool {
 	fn layout(&self) -> Layout {
-		Layout::WidgetLayout(WidgetLayout::default())
+		let slot = RadioInput::new(vec![
+			RadioEntryData::new("Fill")
+				.value("fill")
+				.tooltip("Clicking a shape changes its fill color")
+				.on_update(move |_| ToolMessage::SelectColorSlot { slot: ColorSlot::Fill }.into()),
+			RadioEntryData::new("Stroke")
+				.value("stroke")
+				.tooltip("Clicking a shape changes its stroke color")
+				.on_update(move |_| ToolMessage::SelectColorSlot { slot: ColorSlot::Stroke }.into()),
+		])
+		.selected_index((self.options.slot == ColorSlot::Stroke) as u32)
+		.widget_holder();
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row {
+			widgets: vec![TextLabel::new("Apply To").widget_holder(), Separator::new(SeparatorType::Related).widget_holder(), slot],
+		}]))
 	}
 }
 
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for FillTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
+		if let ToolMessage::Fill(FillToolMessage::UpdateOptions(action)) = message {
+			match action {
+				FillOptionsUpdate::Slot(slot) => self.options.slot = slot,
+			}
+
+			self.send_layout(responses, LayoutTarget::ToolOptions);
+
+			return;
+		}
+
 		self.fsm_state.process_event(message, &mut self.data, tool_data, &(), responses, true);
 	}
 
@@ -65,6 +103,7 @@ impl ToolTransition for FillTool {
 	fn event_to_message_map(&self) -> EventToMessageMap {
 		EventToMessageMap {
 			tool_abort: Some(FillToolMessage::Abort.into()),
+			working_color_changed: Some(FillToolMessage::WorkingColorChanged.into()),
 			..Default::default()
 		}
 	}
@@ -104,38 +143,37 @@ impl Fsm for FillToolFsmState {
 			match (self, event) {
 				(Ready, lmb_or_rmb) if lmb_or_rmb == LeftPointerDown || lmb_or_rmb == RightPointerDown => {
 					let mouse_pos = input.mouse.position;
-					let tolerance = DVec2::splat(SELECTION_TOLERANCE);
-					let quad = Quad::from_box([mouse_pos - tolerance, mouse_pos + tolerance]);
+					let quad = pointer_quad(mouse_pos);
 
 					if let Some(path) = document.document_legacy.intersects_quad_root(quad, render_data).last() {
-						let is_bitmap = document
-							.document_legacy
-							.layer(path)
-							.ok()
-							.and_then(|layer| layer.as_layer().ok())
-							.map_or(false, |layer| matches!(layer.cached_output_data, CachedOutputData::BlobURL(_) | CachedOutputData::SurfaceId(_)));
-
-						if is_bitmap {
+						if document.is_raster_layer(path) {
 							return self;
 						}
 
 						let color = match lmb_or_rmb {
 							LeftPointerDown => global_tool_data.primary_color,
 							RightPointerDown => global_tool_data.secondary_color,
-							Abort => unreachable!(),
+							_ => unreachable!(),
 						};
-						let fill = Fill::Solid(color);
+						let layer = path.to_vec();
 
 						responses.add(DocumentMessage::StartTransaction);
 						responses.add(DocumentMessage::SetSelectedLayers {
-							replacement_selected_layers: vec![path.to_vec()],
+							replacement_selected_layers: vec![layer.clone()],
 						});
-						responses.add(GraphOperationMessage::FillSet { layer: path.to_vec(), fill });
+						match global_tool_data.active_slot {
+							ColorSlot::Fill => responses.add(GraphOperationMessage::FillSet { layer, fill: Fill::Solid(color) }),
+							ColorSlot::Stroke => responses.add(GraphOperationMessage::StrokeColorSet { layer, color: Some(color) }),
+						}
 						responses.add(DocumentMessage::CommitTransaction);
 					}
 
 					Ready
 				}
+				(_, WorkingColorChanged) => {
+					responses.add(FillToolMessage::UpdateOptions(FillOptionsUpdate::Slot(global_tool_data.active_slot)));
+					self
+				}
 				_ => self,
 			}
 		} else {
@@ -146,8 +184,8 @@ impl Fsm for FillToolFsmState {
 	fn update_hints(&self, responses: &mut VecDeque<Message>) {
 		let hint_data = match self {
 			FillToolFsmState::Ready => HintData(vec![HintGroup(vec![
-				HintInfo::mouse(MouseMotion::Lmb, "Fill with Primary"),
-				HintInfo::mouse(MouseMotion::Rmb, "Fill with Secondary"),
+				HintInfo::mouse(MouseMotion::Lmb, "Apply Primary Color"),
+				HintInfo::mouse(MouseMotion::Rmb, "Apply Secondary Color"),
 			])]),
 		};
 
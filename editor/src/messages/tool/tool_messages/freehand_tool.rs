@@ -1,9 +1,12 @@
+use crate::consts::FREEHAND_SIMPLIFY_TOLERANCE;
 use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::input_mapper::utility_types::input_keyboard::MouseMotion;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
+use crate::messages::tool::common_functionality::freehand_path;
 use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::common_functionality::stabilizer::{Stabilizer, StabilizerMode, StabilizerOptions};
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
@@ -26,6 +29,12 @@ pub struct FreehandOptions {
 	line_weight: f64,
 	fill: ToolColorOptions,
 	stroke: ToolColorOptions,
+	pressure: bool,
+	/// Thins the line where the pointer moves faster, standing in for pressure when drawing with a mouse.
+	speed: bool,
+	/// How much the speed dynamic thins the fastest parts of the line, as a percentage of the weight.
+	speed_strength: f64,
+	stabilizer: StabilizerOptions,
 }
 
 impl Default for FreehandOptions {
@@ -34,6 +43,10 @@ impl Default for FreehandOptions {
 			line_weight: 5.,
 			fill: ToolColorOptions::new_none(),
 			stroke: ToolColorOptions::new_primary(),
+			pressure: false,
+			speed: false,
+			speed_strength: 50.,
+			stabilizer: StabilizerOptions::default(),
 		}
 	}
 }
@@ -61,6 +74,11 @@ pub enum FreehandOptionsUpdate {
 	FillColor(Option<Color>),
 	FillColorType(ToolColorType),
 	LineWeight(f64),
+	Pressure(bool),
+	Speed(bool),
+	SpeedStrength(f64),
+	Stabilizer(StabilizerMode),
+	StabilizerStrength(f64),
 	StrokeColor(Option<Color>),
 	StrokeColorType(ToolColorType),
 	WorkingColors(Option<Color>, Option<Color>),
@@ -94,6 +112,38 @@ fn create_weight_widget(line_weight: f64) -> WidgetHolder {
 		.widget_holder()
 }
 
+fn create_pressure_widgets(pressure: bool) -> Vec<WidgetHolder> {
+	vec![
+		TextLabel::new("Pressure").widget_holder(),
+		Separator::new(SeparatorType::Related).widget_holder(),
+		CheckboxInput::new(pressure)
+			.tooltip("Vary the width of the line with the pressure of the stylus, filling it with the stroke color")
+			.on_update(|input: &CheckboxInput| FreehandToolMessage::UpdateOptions(FreehandOptionsUpdate::Pressure(input.checked)).into())
+			.widget_holder(),
+	]
+}
+
+fn create_speed_widgets(speed: bool, speed_strength: f64) -> Vec<WidgetHolder> {
+	vec![
+		TextLabel::new("Speed").widget_holder(),
+		Separator::new(SeparatorType::Related).widget_holder(),
+		CheckboxInput::new(speed)
+			.tooltip("Thin the line where the pointer moves faster, like a pen pressed more lightly, filling it with the stroke color")
+			.on_update(|input: &CheckboxInput| FreehandToolMessage::UpdateOptions(FreehandOptionsUpdate::Speed(input.checked)).into())
+			.widget_holder(),
+		Separator::new(SeparatorType::Related).widget_holder(),
+		NumberInput::new(Some(speed_strength))
+			.label("Strength")
+			.unit("%")
+			.min(0.)
+			.max(100.)
+			.disabled(!speed)
+			.tooltip("How much the fastest parts of the line are thinned")
+			.on_update(|number_input: &NumberInput| FreehandToolMessage::UpdateOptions(FreehandOptionsUpdate::SpeedStrength(number_input.value.unwrap())).into())
+			.widget_holder(),
+	]
+}
+
 impl LayoutHolder for FreehandTool {
 	fn layout(&self) -> Layout {
 		let mut widgets = self.options.fill.create_widgets(
@@ -115,6 +165,15 @@ impl LayoutHolder for FreehandTool {
 		));
 		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
 		widgets.push(create_weight_widget(self.options.line_weight));
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		widgets.append(&mut create_pressure_widgets(self.options.pressure));
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		widgets.append(&mut create_speed_widgets(self.options.speed, self.options.speed_strength));
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		widgets.append(&mut self.options.stabilizer.create_widgets(
+			|mode| FreehandToolMessage::UpdateOptions(FreehandOptionsUpdate::Stabilizer(mode)).into(),
+			|strength| FreehandToolMessage::UpdateOptions(FreehandOptionsUpdate::StabilizerStrength(strength)).into(),
+		));
 
 		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
@@ -130,6 +189,11 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Freehan
 				}
 				FreehandOptionsUpdate::FillColorType(color_type) => self.options.fill.color_type = color_type,
 				FreehandOptionsUpdate::LineWeight(line_weight) => self.options.line_weight = line_weight,
+				FreehandOptionsUpdate::Pressure(pressure) => self.options.pressure = pressure,
+				FreehandOptionsUpdate::Speed(speed) => self.options.speed = speed,
+				FreehandOptionsUpdate::SpeedStrength(speed_strength) => self.options.speed_strength = speed_strength,
+				FreehandOptionsUpdate::Stabilizer(mode) => self.options.stabilizer.mode = mode,
+				FreehandOptionsUpdate::StabilizerStrength(strength) => self.options.stabilizer.strength = strength,
 				FreehandOptionsUpdate::StrokeColor(color) => {
 					self.options.stroke.custom_color = color;
 					self.options.stroke.color_type = ToolColorType::Custom;
@@ -182,8 +246,24 @@ impl ToolTransition for FreehandTool {
 #[derive(Clone, Debug, Default)]
 struct FreehandToolData {
 	points: Vec<DVec2>,
+	pressures: Vec<f64>,
+	/// The pointer speed at each sample, in viewport pixels per millisecond
+	speeds: Vec<f64>,
+	stabilizer: Stabilizer,
 	weight: f64,
 	path: Option<Vec<LayerId>>,
+	/// Where the layer is inserted in its folder once the stroke is finished
+	insert_index: isize,
+}
+
+impl FreehandToolData {
+	fn push_sample(&mut self, position: DVec2, pressure: f64, speed: f64) {
+		if self.points.last() != Some(&position) {
+			self.points.push(position);
+			self.pressures.push(pressure);
+			self.speeds.push(speed);
+		}
+	}
 }
 
 impl Fsm for FreehandToolFsmState {
@@ -211,32 +291,43 @@ impl Fsm for FreehandToolFsmState {
 					responses.add(DocumentMessage::StartTransaction);
 					responses.add(DocumentMessage::DeselectAllLayers);
 					tool_data.path = Some(document.get_path_for_new_layer());
+					tool_data.insert_index = document.get_insert_index_for_new_layer();
 
+					tool_data.stabilizer = Stabilizer::new(tool_options.stabilizer);
+					tool_data.stabilizer.stabilize(input.mouse.position);
 					let pos = transform.inverse().transform_point2(input.mouse.position);
 
 					tool_data.points.push(pos);
+					tool_data.pressures.push(input.mouse.pressure);
+					tool_data.speeds.push(input.pointer_speed);
 
 					tool_data.weight = tool_options.line_weight;
 
-					add_polyline(tool_data, tool_options.stroke.active_color(), tool_options.fill.active_color(), responses);
+					add_shape(tool_data, tool_options, None, responses);
 
 					Drawing
 				}
 				(Drawing, PointerMove) => {
-					let pos = transform.inverse().transform_point2(input.mouse.position);
-
-					if tool_data.points.last() != Some(&pos) {
-						tool_data.points.push(pos);
+					if let Some(stabilized) = tool_data.stabilizer.stabilize(input.mouse.position) {
+						tool_data.push_sample(transform.inverse().transform_point2(stabilized), input.mouse.pressure, input.pointer_speed);
 					}
 
-					add_polyline(tool_data, tool_options.stroke.active_color(), tool_options.fill.active_color(), responses);
+					add_shape(tool_data, tool_options, None, responses);
 
 					Drawing
 				}
 				(Drawing, DragStop) | (Drawing, Abort) => {
+					// The stabilized line lags behind the pointer, so it's brought the rest of the way to where the pointer was released
+					if let Some(stabilized) = tool_data.stabilizer.finish() {
+						let pressure = tool_data.pressures.last().copied().unwrap_or(input.mouse.pressure);
+						let speed = tool_data.speeds.last().copied().unwrap_or_default();
+						tool_data.push_sample(transform.inverse().transform_point2(stabilized), pressure, speed);
+					}
+
 					if tool_data.points.len() >= 2 {
 						responses.add(remove_preview(tool_data));
-						add_polyline(tool_data, tool_options.stroke.active_color(), tool_options.fill.active_color(), responses);
+						let tolerance = transform.inverse().transform_vector2(DVec2::X * FREEHAND_SIMPLIFY_TOLERANCE).length();
+						add_shape(tool_data, tool_options, Some(tolerance), responses);
 						responses.add(DocumentMessage::CommitTransaction);
 					} else {
 						responses.add(DocumentMessage::AbortTransaction);
@@ -244,6 +335,8 @@ impl Fsm for FreehandToolFsmState {
 
 					tool_data.path = None;
 					tool_data.points.clear();
+					tool_data.pressures.clear();
+					tool_data.speeds.clear();
 
 					Ready
 				}
@@ -279,19 +372,55 @@ fn remove_preview(data: &FreehandToolData) -> Message {
 	Operation::DeleteLayer { path: data.path.clone().unwrap() }.into()
 }
 
-fn add_polyline(data: &FreehandToolData, stroke_color: Option<Color>, fill_color: Option<Color>, responses: &mut VecDeque<Message>) {
-	let subpath = bezier_rs::Subpath::from_anchors(data.points.iter().copied(), false);
+/// Adds the layer drawn from the samples, which is simplified to within the tolerance and smoothed once the drag is finished.
+/// With pressure or speed, the line is drawn as an outline filled with the stroke color whose width follows the pressure of each sample and
+/// thins with its speed.
+fn add_shape(data: &FreehandToolData, tool_options: &FreehandOptions, simplify_tolerance: Option<f64>, responses: &mut VecDeque<Message>) {
+	let indices = match simplify_tolerance {
+		Some(tolerance) => freehand_path::simplify(&data.points, tolerance),
+		None => (0..data.points.len()).collect(),
+	};
+	let points = indices.iter().map(|&index| data.points[index]).collect::<Vec<_>>();
+	let widths = indices
+		.iter()
+		.map(|&index| {
+			let pressure = if tool_options.pressure { data.pressures[index] } else { 1. };
+			let speed = if tool_options.speed {
+				freehand_path::speed_width_factor(data.speeds[index], tool_options.speed_strength / 100.)
+			} else {
+				1.
+			};
+			pressure * speed * data.weight
+		})
+		.collect::<Vec<_>>();
+
+	let stroke_color = tool_options.stroke.active_color();
+	let outline = if tool_options.pressure || tool_options.speed {
+		freehand_path::variable_width_outline(&points, &widths)
+	} else {
+		Vec::new()
+	};
+	let (subpath, fill_color, stroke) = if !outline.is_empty() {
+		let subpath = match simplify_tolerance {
+			Some(_) => freehand_path::smooth_subpath(&outline, true),
+			None => bezier_rs::Subpath::from_anchors(outline, true),
+		};
+		(subpath, stroke_color, Stroke::new(None, 0.))
+	} else {
+		let subpath = match simplify_tolerance {
+			Some(_) => freehand_path::smooth_subpath(&points, false),
+			None => bezier_rs::Subpath::from_anchors(points, false),
+		};
+		(subpath, tool_options.fill.active_color(), Stroke::new(stroke_color, data.weight))
+	};
 
 	let layer_path = data.path.clone().unwrap();
-	graph_modification_utils::new_vector_layer(vec![subpath], layer_path.clone(), responses);
+	graph_modification_utils::new_vector_layer_at(vec![subpath], layer_path.clone(), data.insert_index, responses);
 
 	responses.add(GraphOperationMessage::FillSet {
 		layer: layer_path.clone(),
 		fill: if let Some(color) = fill_color { Fill::Solid(color) } else { Fill::None },
 	});
 
-	responses.add(GraphOperationMessage::StrokeSet {
-		layer: layer_path,
-		stroke: Stroke::new(stroke_color, data.weight),
-	});
+	responses.add(GraphOperationMessage::StrokeSet { layer: layer_path, stroke });
 }
@@ -1,14 +1,14 @@
 use crate::application::generate_uuid;
-use crate::consts::{COLOR_ACCENT, LINE_ROTATE_SNAP_ANGLE, MANIPULATOR_GROUP_MARKER_SIZE, SELECTION_THRESHOLD, SELECTION_TOLERANCE};
+use crate::consts::LINE_ROTATE_SNAP_ANGLE;
 use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::hit_targets::{marker_size, pointer_quad, selection_threshold};
 use crate::messages::tool::common_functionality::snapping::SnapManager;
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
-use document_legacy::intersection::Quad;
 use document_legacy::layers::layer_info::Layer;
 use document_legacy::layers::layer_layer::CachedOutputData;
 use document_legacy::layers::style::{Fill, Gradient, GradientType, PathStyle, RenderData, Stroke};
@@ -154,14 +154,15 @@ impl GradientOverlay {
 	fn generate_overlay_handle(translation: DVec2, responses: &mut VecDeque<Message>, selected: bool) -> Vec<LayerId> {
 		let path = vec![generate_uuid()];
 
-		let size = DVec2::splat(MANIPULATOR_GROUP_MARKER_SIZE);
+		let size = DVec2::splat(marker_size());
 
-		let fill = if selected { Fill::solid(COLOR_ACCENT) } else { Fill::solid(Color::WHITE) };
+		let handles = overlay_colors().handles;
+		let fill = if selected { Fill::solid(handles) } else { Fill::solid(Color::WHITE) };
 
 		let operation = Operation::AddEllipse {
 			path: path.clone(),
 			transform: DAffine2::from_scale_angle_translation(size, 0., translation - size / 2.).to_cols_array(),
-			style: PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), 1.0)), fill),
+			style: PathStyle::new(Some(Stroke::new(Some(handles), 1.0)), fill),
 			insert_index: -1,
 		};
 		responses.add(DocumentMessage::Overlays(operation.into()));
@@ -180,7 +181,7 @@ impl GradientOverlay {
 		let operation = Operation::AddLine {
 			path: path.clone(),
 			transform,
-			style: PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), 1.0)), Fill::None),
+			style: PathStyle::new(Some(Stroke::new(Some(overlay_colors().handles), 1.0)), Fill::None),
 			insert_index: -1,
 		};
 		responses.add(DocumentMessage::Overlays(operation.into()));
@@ -490,7 +491,7 @@ impl Fsm for GradientToolFsmState {
 						let distance = (end - start).angle_between(mouse - start).sin() * (mouse - start).length();
 
 						// If click is on the line then insert point
-						if distance < SELECTION_THRESHOLD {
+						if distance < selection_threshold() {
 							let mut gradient = overlay.gradient.clone();
 
 							// Try and insert the new stop
@@ -522,7 +523,7 @@ impl Fsm for GradientToolFsmState {
 
 					let mouse = input.mouse.position;
 					tool_data.drag_start = mouse;
-					let tolerance = MANIPULATOR_GROUP_MARKER_SIZE.powi(2);
+					let tolerance = marker_size().powi(2);
 
 					let mut dragging = false;
 					for overlay in &tool_data.gradient_overlays {
@@ -561,8 +562,7 @@ impl Fsm for GradientToolFsmState {
 						document.backup_nonmut(responses);
 						GradientToolFsmState::Drawing
 					} else {
-						let tolerance = DVec2::splat(SELECTION_TOLERANCE);
-						let quad = Quad::from_box([input.mouse.position - tolerance, input.mouse.position + tolerance]);
+						let quad = pointer_quad(input.mouse.position);
 						let intersection = document.document_legacy.intersects_quad_root(quad, render_data).pop();
 
 						// the intersection is the layer where the gradient is being applied
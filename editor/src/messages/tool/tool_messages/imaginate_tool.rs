@@ -17,6 +17,18 @@ use serde::{Deserialize, Serialize};
 pub struct ImaginateTool {
 	fsm_state: ImaginateToolFsmState,
 	tool_data: ImaginateToolData,
+	options: ImaginateToolOptions,
+}
+
+#[derive(Default)]
+pub struct ImaginateToolOptions {
+	lock_aspect_ratio: bool,
+}
+
+#[remain::sorted]
+#[derive(PartialEq, Eq, Clone, Debug, Hash, Serialize, Deserialize, specta::Type)]
+pub enum ImaginateOptionsUpdate {
+	LockAspectRatio(bool),
 }
 
 #[remain::sorted]
@@ -38,17 +50,38 @@ pub enum ImaginateToolMessage {
 		center: Key,
 		lock_ratio: Key,
 	},
+	UpdateOptions(ImaginateOptionsUpdate),
+}
+
+fn create_lock_aspect_ratio_widget(lock_aspect_ratio: bool) -> WidgetHolder {
+	CheckboxInput::new(lock_aspect_ratio)
+		.icon("Link")
+		.tooltip("Lock Aspect Ratio (instead of holding Shift)")
+		.on_update(|input: &CheckboxInput| ImaginateToolMessage::UpdateOptions(ImaginateOptionsUpdate::LockAspectRatio(input.checked)).into())
+		.widget_holder()
 }
 
 impl LayoutHolder for ImaginateTool {
 	fn layout(&self) -> Layout {
-		Layout::WidgetLayout(WidgetLayout::default())
+		let widgets = vec![create_lock_aspect_ratio_widget(self.options.lock_aspect_ratio)];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
 }
 
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for ImaginateTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
-		self.fsm_state.process_event(message, &mut self.tool_data, tool_data, &(), responses, true);
+		if let ToolMessage::Imaginate(ImaginateToolMessage::UpdateOptions(action)) = message {
+			match action {
+				ImaginateOptionsUpdate::LockAspectRatio(lock_aspect_ratio) => self.options.lock_aspect_ratio = lock_aspect_ratio,
+			}
+
+			self.send_layout(responses, LayoutTarget::ToolOptions);
+
+			return;
+		}
+
+		self.fsm_state.process_event(message, &mut self.tool_data, tool_data, &self.options, responses, true);
 	}
 
 	fn actions(&self) -> ActionList {
@@ -105,14 +138,14 @@ struct ImaginateToolData {
 
 impl Fsm for ImaginateToolFsmState {
 	type ToolData = ImaginateToolData;
-	type ToolOptions = ();
+	type ToolOptions = ImaginateToolOptions;
 
 	fn transition(
 		self,
 		event: ToolMessage,
 		tool_data: &mut Self::ToolData,
 		ToolActionHandlerData { document, input, render_data, .. }: &mut ToolActionHandlerData,
-		_tool_options: &Self::ToolOptions,
+		tool_options: &Self::ToolOptions,
 		responses: &mut VecDeque<Message>,
 	) -> Self {
 		use ImaginateToolFsmState::*;
@@ -124,7 +157,7 @@ impl Fsm for ImaginateToolFsmState {
 			match (self, event) {
 				(_, DocumentIsDirty | SelectionChanged) => {
 					tool_data.path_outlines.clear_selected(responses);
-					tool_data.path_outlines.update_selected(document.selected_visible_layers(), document, responses, render_data);
+					tool_data.path_outlines.update_selected(document.selected_visible_layers(), document, responses);
 
 					self
 				}
@@ -134,6 +167,7 @@ impl Fsm for ImaginateToolFsmState {
 					shape_data.start(responses, document, input, render_data);
 					responses.add(DocumentMessage::StartTransaction);
 					shape_data.path = Some(document.get_path_for_new_layer());
+					let insert_index = document.get_insert_index_for_new_layer();
 					responses.add(DocumentMessage::DeselectAllLayers);
 
 					use graph_craft::document::*;
@@ -170,7 +204,7 @@ impl Fsm for ImaginateToolFsmState {
 					// Add a layer with a frame to the document
 					responses.add(Operation::AddFrame {
 						path: shape_data.path.clone().unwrap(),
-						insert_index: -1,
+						insert_index,
 						transform: DAffine2::ZERO.to_cols_array(),
 						network,
 					});
@@ -179,7 +213,7 @@ impl Fsm for ImaginateToolFsmState {
 					Drawing
 				}
 				(state, Resize { center, lock_ratio }) => {
-					let message = shape_data.calculate_transform(responses, document, input, center, lock_ratio, true);
+					let message = shape_data.calculate_transform(responses, document, input, center, lock_ratio, tool_options.lock_aspect_ratio, true);
 					responses.try_add(message);
 
 					state
@@ -3,18 +3,21 @@ use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
 use crate::messages::input_mapper::utility_types::input_mouse::ViewportPosition;
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::transformation::Typing;
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::canvas_follower::CanvasFollower;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
 use crate::messages::tool::common_functionality::graph_modification_utils;
 use crate::messages::tool::common_functionality::snapping::SnapManager;
-use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
+use crate::messages::tool::common_functionality::stroke_options::{StrokeOptionsUpdate, ToolStrokeOptions};
+use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolStatus, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
 use document_legacy::LayerId;
 use graphene_core::vector::style::Stroke;
 use graphene_core::Color;
 
-use glam::DVec2;
+use glam::{DAffine2, DVec2};
 use serde::{Deserialize, Serialize};
 
 #[derive(Default)]
@@ -27,6 +30,7 @@ pub struct LineTool {
 pub struct LineOptions {
 	line_weight: f64,
 	stroke: ToolColorOptions,
+	stroke_style: ToolStrokeOptions,
 }
 
 impl Default for LineOptions {
@@ -34,6 +38,7 @@ impl Default for LineOptions {
 		Self {
 			line_weight: 5.,
 			stroke: ToolColorOptions::new_primary(),
+			stroke_style: ToolStrokeOptions::default(),
 		}
 	}
 }
@@ -49,6 +54,7 @@ pub enum LineToolMessage {
 	WorkingColorChanged,
 
 	// Tool-specific messages
+	CycleTypedField,
 	DragStart,
 	DragStop,
 	Redraw {
@@ -56,6 +62,12 @@ pub enum LineToolMessage {
 		lock_angle: Key,
 		snap_angle: Key,
 	},
+	TypeBackspace,
+	TypeDecimalPoint,
+	TypeDigit {
+		digit: u8,
+	},
+	TypeNegate,
 	UpdateOptions(LineOptionsUpdate),
 }
 
@@ -65,6 +77,7 @@ pub enum LineOptionsUpdate {
 	LineWeight(f64),
 	StrokeColor(Option<Color>),
 	StrokeColorType(ToolColorType),
+	StrokeStyle(StrokeOptionsUpdate),
 	WorkingColors(Option<Color>, Option<Color>),
 }
 
@@ -78,6 +91,24 @@ impl ToolMetadata for LineTool {
 	fn tool_type(&self) -> crate::messages::tool::utility_types::ToolType {
 		ToolType::Line
 	}
+
+	fn update_status(&self, status: &mut ToolStatus) {
+		if self.fsm_state != LineToolFsmState::Drawing {
+			return;
+		}
+
+		let data = &self.tool_data;
+		let locale = number_locale();
+		status.measurement = Some(format!(
+			"Length {}, Angle {}",
+			locale.format_with_unit(data.document_length, 2, "px"),
+			locale.format_with_unit(data.document_angle, 2, "°")
+		));
+		if let Some(field) = data.typed_field {
+			status.operation = Some(field.label().to_string());
+			status.typed_value = data.typing.text();
+		}
+	}
 }
 
 fn create_weight_widget(line_weight: f64) -> WidgetHolder {
@@ -100,6 +131,12 @@ impl LayoutHolder for LineTool {
 		);
 		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
 		widgets.push(create_weight_widget(self.options.line_weight));
+		widgets.append(
+			&mut self
+				.options
+				.stroke_style
+				.create_widgets(|update| LineToolMessage::UpdateOptions(LineOptionsUpdate::StrokeStyle(update)).into()),
+		);
 
 		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
@@ -115,6 +152,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for LineToo
 					self.options.stroke.color_type = ToolColorType::Custom;
 				}
 				LineOptionsUpdate::StrokeColorType(color_type) => self.options.stroke.color_type = color_type,
+				LineOptionsUpdate::StrokeStyle(update) => self.options.stroke_style.update(update),
 				LineOptionsUpdate::WorkingColors(primary, secondary) => {
 					self.options.stroke.primary_working_color = primary;
 					self.options.stroke.secondary_working_color = secondary;
@@ -132,7 +170,16 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for LineToo
 	fn actions(&self) -> ActionList {
 		match self.fsm_state {
 			LineToolFsmState::Ready => actions!(LineToolMessageDiscriminant; DragStart),
-			LineToolFsmState::Drawing => actions!(LineToolMessageDiscriminant; DragStop, Redraw, Abort),
+			LineToolFsmState::Drawing => actions!(LineToolMessageDiscriminant;
+				DragStop,
+				Redraw,
+				Abort,
+				CycleTypedField,
+				TypeBackspace,
+				TypeDecimalPoint,
+				TypeDigit,
+				TypeNegate,
+			),
 		}
 	}
 }
@@ -154,14 +201,64 @@ enum LineToolFsmState {
 	Drawing,
 }
 
+/// The measurement of the line which is being typed in precisely, switched between by pressing Tab.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum LineField {
+	Length,
+	Angle,
+}
+
+impl LineField {
+	fn label(self) -> &'static str {
+		match self {
+			LineField::Length => "Length",
+			LineField::Angle => "Angle",
+		}
+	}
+}
+
 #[derive(Clone, Debug, Default)]
 struct LineToolData {
 	drag_start: ViewportPosition,
 	drag_current: ViewportPosition,
+	canvas_follower: CanvasFollower,
 	angle: f64,
 	weight: f64,
 	path: Option<Vec<LayerId>>,
 	snap_manager: SnapManager,
+	/// The modifier keys held during the last redraw, which are reused when typing changes the line without the mouse moving.
+	center: bool,
+	lock_angle: bool,
+	snap_angle: bool,
+	/// The measurements of the line as last drawn, in document units and degrees counterclockwise from the document's X axis.
+	document_length: f64,
+	document_angle: f64,
+	/// The field which typed digits go into, if Tab has been pressed since the line was started.
+	typed_field: Option<LineField>,
+	typing: Typing,
+	/// Typed values which replace the measurements given by the mouse, in document units and degrees.
+	typed_length: Option<f64>,
+	typed_angle: Option<f64>,
+}
+
+impl LineToolData {
+	fn reset_typing(&mut self) {
+		self.typed_field = None;
+		self.typing.clear();
+		self.typed_length = None;
+		self.typed_angle = None;
+	}
+
+	/// Sets the measurement being typed to the typed value, or back to the one given by the mouse once the typed value is erased.
+	fn apply_typed_value(&mut self, value: Option<f64>, document_to_viewport: DAffine2, responses: &mut VecDeque<Message>) {
+		match self.typed_field {
+			Some(LineField::Length) => self.typed_length = value,
+			Some(LineField::Angle) => self.typed_angle = value,
+			None => return,
+		}
+
+		responses.add(generate_transform(self, document_to_viewport));
+	}
 }
 
 impl Fsm for LineToolFsmState {
@@ -191,34 +288,75 @@ impl Fsm for LineToolFsmState {
 					tool_data.snap_manager.start_snap(document, input, document.bounding_boxes(None, None, render_data), true, true);
 					tool_data.snap_manager.add_all_document_handles(document, input, &[], &[], &[]);
 					tool_data.drag_start = tool_data.snap_manager.snap_position(responses, document, input.mouse.position);
+					tool_data.canvas_follower.start(document);
 
 					let subpath = bezier_rs::Subpath::new_line(DVec2::ZERO, DVec2::X);
 
 					responses.add(DocumentMessage::StartTransaction);
 					let layer_path = document.get_path_for_new_layer();
+					let insert_index = document.get_insert_index_for_new_layer();
 					tool_data.path = Some(layer_path.clone());
-					graph_modification_utils::new_vector_layer(vec![subpath], layer_path.clone(), responses);
+					graph_modification_utils::new_vector_layer_at(vec![subpath], layer_path.clone(), insert_index, responses);
 					responses.add(GraphOperationMessage::StrokeSet {
 						layer: layer_path,
-						stroke: Stroke::new(tool_options.stroke.active_color(), tool_options.line_weight),
+						stroke: tool_options.stroke_style.apply(Stroke::new(tool_options.stroke.active_color(), tool_options.line_weight)),
 					});
 
 					tool_data.weight = tool_options.line_weight;
+					tool_data.reset_typing();
 
 					Drawing
 				}
 				(Drawing, Redraw { center, snap_angle, lock_angle }) => {
+					// Keep the start of the line over the same part of the document if the canvas moved, such as by auto-scrolling
+					tool_data.drag_start = tool_data.canvas_follower.follow(document).transform_point2(tool_data.drag_start);
 					tool_data.drag_current = tool_data.snap_manager.snap_position(responses, document, input.mouse.position);
 
 					let keyboard = &input.keyboard;
-					responses.add(generate_transform(tool_data, keyboard.key(lock_angle), keyboard.key(snap_angle), keyboard.key(center)));
+					if !keyboard.key(snap_angle) && !keyboard.key(lock_angle) {
+						tool_data.drag_current = document.snap_to_guide_grid(tool_data.drag_start, tool_data.drag_current);
+					}
+					tool_data.center = keyboard.key(center);
+					tool_data.lock_angle = keyboard.key(lock_angle);
+					tool_data.snap_angle = keyboard.key(snap_angle);
+					responses.add(generate_transform(tool_data, document.document_legacy.root.transform));
 
 					Drawing
 				}
+				(Drawing, CycleTypedField) => {
+					tool_data.typed_field = match tool_data.typed_field {
+						Some(LineField::Length) => Some(LineField::Angle),
+						_ => Some(LineField::Length),
+					};
+					tool_data.typing.clear();
+
+					Drawing
+				}
+				(Drawing, TypeBackspace) => {
+					let value = tool_data.typing.type_backspace();
+					tool_data.apply_typed_value(value, document.document_legacy.root.transform, responses);
+					Drawing
+				}
+				(Drawing, TypeDecimalPoint) => {
+					let value = tool_data.typing.type_decimal_point();
+					tool_data.apply_typed_value(value, document.document_legacy.root.transform, responses);
+					Drawing
+				}
+				(Drawing, TypeDigit { digit }) => {
+					let value = tool_data.typing.type_number(digit);
+					tool_data.apply_typed_value(value, document.document_legacy.root.transform, responses);
+					Drawing
+				}
+				(Drawing, TypeNegate) => {
+					let value = tool_data.typing.type_negate();
+					tool_data.apply_typed_value(value, document.document_legacy.root.transform, responses);
+					Drawing
+				}
 				(Drawing, DragStop) => {
 					tool_data.snap_manager.cleanup(responses);
 					input.mouse.finish_transaction(tool_data.drag_start, responses);
 					tool_data.path = None;
+					tool_data.reset_typing();
 
 					Ready
 				}
@@ -226,6 +364,7 @@ impl Fsm for LineToolFsmState {
 					tool_data.snap_manager.cleanup(responses);
 					responses.add(DocumentMessage::AbortTransaction);
 					tool_data.path = None;
+					tool_data.reset_typing();
 					Ready
 				}
 				(_, WorkingColorChanged) => {
@@ -250,11 +389,14 @@ impl Fsm for LineToolFsmState {
 				HintInfo::keys([Key::Alt], "From Center").prepend_plus(),
 				HintInfo::keys([Key::Control], "Lock Angle").prepend_plus(),
 			])]),
-			LineToolFsmState::Drawing => HintData(vec![HintGroup(vec![
-				HintInfo::keys([Key::Shift], "Snap 15°"),
-				HintInfo::keys([Key::Alt], "From Center"),
-				HintInfo::keys([Key::Control], "Lock Angle"),
-			])]),
+			LineToolFsmState::Drawing => HintData(vec![
+				HintGroup(vec![
+					HintInfo::keys([Key::Shift], "Snap 15°"),
+					HintInfo::keys([Key::Alt], "From Center"),
+					HintInfo::keys([Key::Control], "Lock Angle"),
+				]),
+				HintGroup(vec![HintInfo::keys([Key::Tab], "Type Length/Angle")]),
+			]),
 		};
 
 		responses.add(FrontendMessage::UpdateInputHints { hint_data });
@@ -265,38 +407,57 @@ impl Fsm for LineToolFsmState {
 	}
 }
 
-fn generate_transform(tool_data: &mut LineToolData, lock_angle: bool, snap_angle: bool, center: bool) -> Message {
+fn generate_transform(tool_data: &mut LineToolData, document_to_viewport: DAffine2) -> Message {
 	let mut start = tool_data.drag_start;
 	let line_vector = tool_data.drag_current - start;
 
 	let mut angle = -line_vector.angle_between(DVec2::X);
 
-	if lock_angle {
+	if tool_data.lock_angle {
 		angle = tool_data.angle;
 	}
 
-	if snap_angle {
+	if tool_data.snap_angle {
 		let snap_resolution = LINE_ROTATE_SNAP_ANGLE.to_radians();
 		angle = (angle / snap_resolution).round() * snap_resolution;
 	}
 
-	tool_data.angle = angle;
-
 	let mut line_length = line_vector.length();
 
-	if lock_angle {
+	if tool_data.lock_angle {
 		let angle_vec = DVec2::new(angle.cos(), angle.sin());
 		line_length = line_vector.dot(angle_vec);
 	}
 
-	if center {
+	// Drawing from the center makes the line twice as long as the drag
+	let length_factor = if tool_data.center { 2. } else { 1. };
+	let angle_or_zero = |vector: DVec2| if vector == DVec2::ZERO { 0. } else { -vector.angle_between(DVec2::X) };
+
+	// Typed values are measured in the document, so they keep their meaning however the canvas is zoomed or tilted
+	if tool_data.typed_length.is_some() || tool_data.typed_angle.is_some() {
+		let document_vector = document_to_viewport.inverse().transform_vector2(DVec2::from_angle(angle) * line_length * length_factor);
+		let length = tool_data.typed_length.unwrap_or(document_vector.length());
+		let document_angle = tool_data.typed_angle.map_or(angle_or_zero(document_vector), |degrees| -degrees.to_radians());
+
+		let viewport_vector = document_to_viewport.transform_vector2(DVec2::from_angle(document_angle) * length);
+		angle = angle_or_zero(viewport_vector);
+		line_length = viewport_vector.length() / length_factor;
+	}
+
+	tool_data.angle = angle;
+
+	if tool_data.center {
 		start -= line_length * DVec2::new(angle.cos(), angle.sin());
 		line_length *= 2.;
 	}
 
+	let document_vector = document_to_viewport.inverse().transform_vector2(DVec2::from_angle(angle) * line_length);
+	tool_data.document_length = document_vector.length();
+	tool_data.document_angle = -angle_or_zero(document_vector).to_degrees();
+
 	GraphOperationMessage::TransformSet {
 		layer: tool_data.path.clone().unwrap(),
-		transform: glam::DAffine2::from_scale_angle_translation(DVec2::new(line_length, 1.), angle, start),
+		transform: DAffine2::from_scale_angle_translation(DVec2::new(line_length, 1.), angle, start),
 		transform_in: TransformIn::Viewport,
 		skip_rerender: false,
 	}
@@ -1,10 +1,12 @@
 use std::vec;
 
-use crate::consts::{DRAG_THRESHOLD, SELECTION_THRESHOLD, SELECTION_TOLERANCE};
 use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
 use crate::messages::layout::utility_types::widget_prelude::*;
+use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis};
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::canvas_follower::CanvasFollower;
+use crate::messages::tool::common_functionality::hit_targets::{drag_threshold, selection_threshold, selection_tolerance};
 use crate::messages::tool::common_functionality::overlay_renderer::OverlayRenderer;
 use crate::messages::tool::common_functionality::shape_editor::{ManipulatorPointInfo, OpposingHandleLengths, ShapeState};
 use crate::messages::tool::common_functionality::snapping::SnapManager;
@@ -37,7 +39,16 @@ pub enum PathToolMessage {
 	SelectionChanged,
 
 	// Tool-specific messages
+	AlignSelectedPoints {
+		axis: AlignAxis,
+		aggregate: AlignAggregate,
+	},
+	AverageSelectedPoints,
+	BreakAtSelectedPoints,
 	Delete,
+	DistributeSelectedPoints {
+		axis: AlignAxis,
+	},
 	DragStart {
 		add_to_selection: Key,
 	},
@@ -47,7 +58,9 @@ pub enum PathToolMessage {
 	Enter {
 		add_to_selection: Key,
 	},
+	GrowPointSelection,
 	InsertPoint,
+	InvertPointSelection,
 	NudgeSelectedPoints {
 		delta_x: f64,
 		delta_y: f64,
@@ -56,6 +69,12 @@ pub enum PathToolMessage {
 		alt_mirror_angle: Key,
 		shift_mirror_distance: Key,
 	},
+	SelectAllPointsInShapes,
+	ShrinkPointSelection,
+	SmoothSelectedPoints,
+	StraightenSelectedSegments {
+		direction: AlignAxis,
+	},
 }
 
 impl ToolMetadata for PathTool {
@@ -72,7 +91,120 @@ impl ToolMetadata for PathTool {
 
 impl LayoutHolder for PathTool {
 	fn layout(&self) -> Layout {
-		Layout::WidgetLayout(WidgetLayout::default())
+		let align_button = |icon: &str, tooltip: &str, axis: AlignAxis, aggregate: AlignAggregate| {
+			IconButton::new(icon, 24)
+				.tooltip(tooltip)
+				.on_update(move |_| {
+					PathToolMessage::AlignSelectedPoints {
+						axis: axis.clone(),
+						aggregate: aggregate.clone(),
+					}
+					.into()
+				})
+				.widget_holder()
+		};
+
+		let row = |label: &str, message: fn(AlignAxis) -> Message| LayoutGroup::Row {
+			widgets: vec![
+				TextLabel::new(label).table_align(true).min_width(80).widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextButton::new("Horizontally").on_update(move |_| message(AlignAxis::X)).widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("Vertically").on_update(move |_| message(AlignAxis::Y)).widget_holder(),
+			],
+		};
+		let arrange_options = vec![
+			row("Distribute", |axis| PathToolMessage::DistributeSelectedPoints { axis }.into()),
+			row("Straighten", |direction| PathToolMessage::StraightenSelectedSegments { direction }.into()),
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Clean Up").table_align(true).min_width(80).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextButton::new("Average")
+						.tooltip("Merge the selected points together at their centroid")
+						.on_update(|_| PathToolMessage::AverageSelectedPoints.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextButton::new("Smooth")
+						.tooltip("Recompute the handles of the selected points so the path curves smoothly through them")
+						.on_update(|_| PathToolMessage::SmoothSelectedPoints.into())
+						.widget_holder(),
+				],
+			},
+		];
+		let selection_options = vec![
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Along Paths").table_align(true).min_width(80).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextButton::new("Grow")
+						.tooltip("Extend the selection to the neighboring points along the paths")
+						.on_update(|_| PathToolMessage::GrowPointSelection.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextButton::new("Shrink")
+						.tooltip("Deselect the points at the ends of each run of selected points along the paths")
+						.on_update(|_| PathToolMessage::ShrinkPointSelection.into())
+						.widget_holder(),
+				],
+			},
+			LayoutGroup::Row {
+				widgets: vec![
+					TextLabel::new("Select").table_align(true).min_width(80).widget_holder(),
+					Separator::new(SeparatorType::Unrelated).widget_holder(),
+					TextButton::new("Whole Shapes")
+						.tooltip("Select all the points of each shape with a point selected, or of every shape if none are")
+						.on_update(|_| PathToolMessage::SelectAllPointsInShapes.into())
+						.widget_holder(),
+					Separator::new(SeparatorType::Related).widget_holder(),
+					TextButton::new("Invert")
+						.tooltip("Select the unselected points and deselect the rest")
+						.on_update(|_| PathToolMessage::InvertPointSelection.into())
+						.widget_holder(),
+				],
+			},
+		];
+
+		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row {
+			widgets: vec![
+				align_button("AlignLeft", "Align Left", AlignAxis::X, AlignAggregate::Min),
+				align_button("AlignHorizontalCenter", "Align Horizontal Center", AlignAxis::X, AlignAggregate::Center),
+				align_button("AlignRight", "Align Right", AlignAxis::X, AlignAggregate::Max),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				align_button("AlignTop", "Align Top", AlignAxis::Y, AlignAggregate::Min),
+				align_button("AlignVerticalCenter", "Align Vertical Center", AlignAxis::Y, AlignAggregate::Center),
+				align_button("AlignBottom", "Align Bottom", AlignAxis::Y, AlignAggregate::Max),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				PopoverButton::new(
+					"Arrange Points",
+					"Distribute the selected points evenly, straighten the segments between them into horizontal or vertical lines, or clean up jittery points",
+				)
+				.options_widget(arrange_options)
+				.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				PopoverButton::new(
+					"Point Selection",
+					"Grow or shrink the selected points along the paths, select the whole shapes, or invert the selection",
+				)
+				.options_widget(selection_options)
+				.widget_holder(),
+				Separator::new(SeparatorType::Unrelated).widget_holder(),
+				TextButton::new("Join")
+					.tooltip("Join the nearest ends of two open paths in the selected shapes into one path")
+					.on_update(|_| DocumentMessage::JoinSelectedPaths.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("Close")
+					.tooltip("Close the open paths in the selected shapes, merging their ends if they are close together")
+					.on_update(|_| DocumentMessage::CloseSelectedPaths.into())
+					.widget_holder(),
+				Separator::new(SeparatorType::Related).widget_holder(),
+				TextButton::new("Break")
+					.tooltip("Break the paths at the selected points, opening closed paths and splitting open ones into separate shapes")
+					.on_update(|_| PathToolMessage::BreakAtSelectedPoints.into())
+					.widget_holder(),
+			],
+		}]))
 	}
 }
 
@@ -92,6 +224,9 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for PathToo
 				Delete,
 				NudgeSelectedPoints,
 				Enter,
+				GrowPointSelection,
+				ShrinkPointSelection,
+				InvertPointSelection,
 			),
 			Dragging => actions!(PathToolMessageDiscriminant;
 				InsertPoint,
@@ -115,6 +250,7 @@ impl ToolTransition for PathTool {
 		EventToMessageMap {
 			document_dirty: Some(PathToolMessage::DocumentIsDirty.into()),
 			tool_abort: Some(PathToolMessage::Abort.into()),
+			tool_confirm: Some(PathToolMessage::Enter { add_to_selection: Key::Shift }.into()),
 			selection_changed: Some(PathToolMessage::SelectionChanged.into()),
 			..Default::default()
 		}
@@ -134,12 +270,20 @@ struct PathToolData {
 	snap_manager: SnapManager,
 	drag_start_pos: DVec2,
 	previous_mouse_position: DVec2,
+	canvas_follower: CanvasFollower,
 	alt_debounce: bool,
 	opposing_handle_lengths: Option<OpposingHandleLengths>,
 	drag_box_overlay_layer: Option<Vec<LayerId>>,
 }
 
 impl PathToolData {
+	/// Keeps the drag anchored to the document while the canvas moves beneath it, such as when it auto-scrolls near the edge of the viewport.
+	fn follow_canvas(&mut self, document: &DocumentMessageHandler) {
+		let change = self.canvas_follower.follow(document);
+		self.drag_start_pos = change.transform_point2(self.drag_start_pos);
+		self.previous_mouse_position = change.transform_point2(self.previous_mouse_position);
+	}
+
 	fn refresh_overlays(&mut self, document: &DocumentMessageHandler, shape_editor: &mut ShapeState, shape_overlay: &mut OverlayRenderer, responses: &mut VecDeque<Message>) {
 		// Set the previously selected layers to invisible
 		for layer_path in document.all_layers() {
@@ -199,10 +343,11 @@ impl Fsm for PathToolFsmState {
 					let shift_pressed = input.keyboard.get(add_to_selection as usize);
 
 					tool_data.opposing_handle_lengths = None;
+					tool_data.canvas_follower.start(document);
 					let selected_layers = shape_editor.selected_layers().cloned().collect();
 
 					// Select the first point within the threshold (in pixels)
-					if let Some(mut selected_points) = shape_editor.select_point(&document.document_legacy, input.mouse.position, SELECTION_THRESHOLD, shift_pressed) {
+					if let Some(mut selected_points) = shape_editor.select_point(&document.document_legacy, input.mouse.position, selection_threshold(), shift_pressed) {
 						responses.add(DocumentMessage::StartTransaction);
 
 						tool_data
@@ -270,6 +415,7 @@ impl Fsm for PathToolFsmState {
 					}
 				}
 				(PathToolFsmState::DrawingBox, PathToolMessage::PointerMove { .. }) => {
+					tool_data.follow_canvas(document);
 					tool_data.previous_mouse_position = input.mouse.position;
 
 					responses.add_front(DocumentMessage::Overlays(
@@ -313,6 +459,7 @@ impl Fsm for PathToolFsmState {
 					}
 
 					// Move the selected points by the mouse position
+					tool_data.follow_canvas(document);
 					let snapped_position = tool_data.snap_manager.snap_position(responses, document, input.mouse.position);
 					shape_editor.move_selected_points(&document.document_legacy, snapped_position - tool_data.previous_mouse_position, shift_pressed, responses);
 					tool_data.previous_mouse_position = snapped_position;
@@ -361,16 +508,16 @@ impl Fsm for PathToolFsmState {
 					let shift_pressed = input.keyboard.get(shift_mirror_distance as usize);
 
 					let nearest_point = shape_editor
-						.find_nearest_point_indices(&document.document_legacy, input.mouse.position, SELECTION_THRESHOLD)
+						.find_nearest_point_indices(&document.document_legacy, input.mouse.position, selection_threshold())
 						.map(|(_, nearest_point)| nearest_point);
 
 					shape_editor.delete_selected_handles_with_zero_length(&document.document_legacy, &tool_data.opposing_handle_lengths, responses);
 
-					if tool_data.drag_start_pos.distance(input.mouse.position) <= DRAG_THRESHOLD && !shift_pressed {
+					if tool_data.drag_start_pos.distance(input.mouse.position) <= drag_threshold() && !shift_pressed {
 						let clicked_selected = shape_editor.selected_points().any(|&point| nearest_point == Some(point));
 						if clicked_selected {
 							shape_editor.deselect_all();
-							shape_editor.select_point(&document.document_legacy, input.mouse.position, SELECTION_THRESHOLD, false);
+							shape_editor.select_point(&document.document_legacy, input.mouse.position, selection_threshold(), false);
 						}
 					}
 
@@ -391,9 +538,9 @@ impl Fsm for PathToolFsmState {
 				}
 				(_, PathToolMessage::InsertPoint) => {
 					// First we try and flip the sharpness (if they have clicked on an anchor)
-					if !shape_editor.flip_sharp(&document.document_legacy, input.mouse.position, SELECTION_TOLERANCE, responses) {
+					if !shape_editor.flip_sharp(&document.document_legacy, input.mouse.position, selection_tolerance(), responses) {
 						// If not, then we try and split the path that may have been clicked upon
-						shape_editor.split(&document.document_legacy, input.mouse.position, SELECTION_TOLERANCE, responses);
+						shape_editor.split(&document.document_legacy, input.mouse.position, selection_tolerance(), responses);
 					}
 
 					self
@@ -412,6 +559,50 @@ impl Fsm for PathToolFsmState {
 						shift_mirror_distance: _,
 					},
 				) => self,
+				(_, PathToolMessage::AlignSelectedPoints { axis, aggregate }) => {
+					shape_editor.align_selected_anchors(&document.document_legacy, &axis, &aggregate, responses);
+					self
+				}
+				(_, PathToolMessage::AverageSelectedPoints) => {
+					shape_editor.average_selected_anchors(&document.document_legacy, responses);
+					self
+				}
+				(_, PathToolMessage::SmoothSelectedPoints) => {
+					shape_editor.smooth_selected_anchors(&document.document_legacy, responses);
+					self
+				}
+				(_, PathToolMessage::BreakAtSelectedPoints) => {
+					shape_editor.break_at_selected_anchors(&document.document_legacy, responses);
+					self
+				}
+				(_, PathToolMessage::DistributeSelectedPoints { axis }) => {
+					shape_editor.distribute_selected_anchors(&document.document_legacy, &axis, responses);
+					self
+				}
+				(_, PathToolMessage::StraightenSelectedSegments { direction }) => {
+					shape_editor.straighten_selected_segments(&document.document_legacy, &direction, responses);
+					self
+				}
+				(_, PathToolMessage::GrowPointSelection) => {
+					shape_editor.grow_anchor_selection(&document.document_legacy);
+					tool_data.refresh_overlays(document, shape_editor, shape_overlay, responses);
+					self
+				}
+				(_, PathToolMessage::ShrinkPointSelection) => {
+					shape_editor.shrink_anchor_selection(&document.document_legacy);
+					tool_data.refresh_overlays(document, shape_editor, shape_overlay, responses);
+					self
+				}
+				(_, PathToolMessage::SelectAllPointsInShapes) => {
+					shape_editor.select_all_anchors_in_shapes(&document.document_legacy);
+					tool_data.refresh_overlays(document, shape_editor, shape_overlay, responses);
+					self
+				}
+				(_, PathToolMessage::InvertPointSelection) => {
+					shape_editor.invert_anchor_selection(&document.document_legacy);
+					tool_data.refresh_overlays(document, shape_editor, shape_overlay, responses);
+					self
+				}
 				(_, PathToolMessage::NudgeSelectedPoints { delta_x, delta_y }) => {
 					shape_editor.move_selected_points(&document.document_legacy, (delta_x, delta_y).into(), true, responses);
 					PathToolFsmState::Ready
@@ -429,6 +620,7 @@ impl Fsm for PathToolFsmState {
 			HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Drag Selected")]),
 			HintGroup(vec![HintInfo::arrow_keys("Nudge Selected"), HintInfo::keys([Key::Shift], "10x").prepend_plus()]),
 			HintGroup(vec![HintInfo::keys([Key::KeyG, Key::KeyR, Key::KeyS], "Grab/Rotate/Scale Selected")]),
+			HintGroup(vec![HintInfo::keys([Key::BracketLeft, Key::BracketRight], "Shrink/Grow Selection")]),
 		]);
 
 		let hint_data = match self {
@@ -6,8 +6,10 @@ use crate::messages::portfolio::document::node_graph::VectorDataModification;
 use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
 use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::common_functionality::hit_targets::snap_point_tolerance;
 use crate::messages::tool::common_functionality::overlay_renderer::OverlayRenderer;
 use crate::messages::tool::common_functionality::snapping::SnapManager;
+use crate::messages::tool::common_functionality::stroke_options::{StrokeOptionsUpdate, ToolStrokeOptions};
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
@@ -34,6 +36,7 @@ pub struct PenOptions {
 	line_weight: f64,
 	fill: ToolColorOptions,
 	stroke: ToolColorOptions,
+	stroke_style: ToolStrokeOptions,
 }
 
 impl Default for PenOptions {
@@ -42,6 +45,7 @@ impl Default for PenOptions {
 			line_weight: 5.,
 			fill: ToolColorOptions::new_secondary(),
 			stroke: ToolColorOptions::new_primary(),
+			stroke_style: ToolStrokeOptions::default(),
 		}
 	}
 }
@@ -89,6 +93,7 @@ pub enum PenOptionsUpdate {
 	LineWeight(f64),
 	StrokeColor(Option<Color>),
 	StrokeColorType(ToolColorType),
+	StrokeStyle(StrokeOptionsUpdate),
 	WorkingColors(Option<Color>, Option<Color>),
 }
 
@@ -134,6 +139,12 @@ impl LayoutHolder for PenTool {
 		));
 		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
 		widgets.push(create_weight_widget(self.options.line_weight));
+		widgets.append(
+			&mut self
+				.options
+				.stroke_style
+				.create_widgets(|update| PenToolMessage::UpdateOptions(PenOptionsUpdate::StrokeStyle(update)).into()),
+		);
 
 		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
@@ -154,6 +165,7 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for PenTool
 					self.options.stroke.color_type = ToolColorType::Custom;
 				}
 				PenOptionsUpdate::StrokeColorType(color_type) => self.options.stroke.color_type = color_type,
+				PenOptionsUpdate::StrokeStyle(update) => self.options.stroke_style.update(update),
 				PenOptionsUpdate::WorkingColors(primary, secondary) => {
 					self.options.stroke.primary_working_color = primary;
 					self.options.stroke.secondary_working_color = secondary;
@@ -195,8 +207,10 @@ impl ToolTransition for PenTool {
 		EventToMessageMap {
 			document_dirty: Some(PenToolMessage::DocumentIsDirty.into()),
 			tool_abort: Some(PenToolMessage::Abort.into()),
+			tool_confirm: Some(PenToolMessage::Confirm.into()),
 			selection_changed: Some(PenToolMessage::SelectionChanged.into()),
 			working_color_changed: Some(PenToolMessage::WorkingColorChanged.into()),
+			..Default::default()
 		}
 	}
 }
@@ -238,39 +252,29 @@ impl PenToolData {
 		});
 	}
 
-	fn create_new_path(
-		&mut self,
-		document: &DocumentMessageHandler,
-		line_weight: f64,
-		stroke_color: Option<Color>,
-		fill_color: Option<Color>,
-		input: &InputPreprocessorMessageHandler,
-		responses: &mut VecDeque<Message>,
-	) {
+	fn create_new_path(&mut self, document: &DocumentMessageHandler, stroke: Stroke, fill_color: Option<Color>, input: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
 		// Deselect layers because we are now creating a new layer
 		responses.add(DocumentMessage::DeselectAllLayers);
 
 		let layer_path = document.get_path_for_new_layer();
+		let insert_index = document.get_insert_index_for_new_layer();
 
 		// Get the position and set properties
 		let transform = document.document_legacy.multiply_transforms(&layer_path[..layer_path.len() - 1]).unwrap_or_default();
 		let snapped_position = self.snap_manager.snap_position(responses, document, input.mouse.position);
 		let start_position = transform.inverse().transform_point2(snapped_position);
-		self.weight = line_weight;
+		self.weight = stroke.weight;
 
 		// Create the initial shape with a `bez_path` (only contains a moveto initially)
 		let subpath = bezier_rs::Subpath::new(vec![bezier_rs::ManipulatorGroup::new(start_position, Some(start_position), Some(start_position))], false);
-		graph_modification_utils::new_vector_layer(vec![subpath], layer_path.clone(), responses);
+		graph_modification_utils::new_vector_layer_at(vec![subpath], layer_path.clone(), insert_index, responses);
 
 		responses.add(GraphOperationMessage::FillSet {
 			layer: layer_path.clone(),
 			fill: if let Some(color) = fill_color { Fill::Solid(color) } else { Fill::None },
 		});
 
-		responses.add(GraphOperationMessage::StrokeSet {
-			layer: layer_path.clone(),
-			stroke: Stroke::new(stroke_color, line_weight),
-		});
+		responses.add(GraphOperationMessage::StrokeSet { layer: layer_path.clone(), stroke });
 
 		self.path = Some(layer_path);
 		self.from_start = false;
@@ -297,7 +301,7 @@ impl PenToolData {
 		let previous_anchor = previous_manipulator_group.anchor;
 
 		// Break the control
-		let on_top = transform.transform_point2(last_anchor).distance_squared(transform.transform_point2(previous_anchor)) < crate::consts::SNAP_POINT_TOLERANCE.powi(2);
+		let on_top = transform.transform_point2(last_anchor).distance_squared(transform.transform_point2(previous_anchor)) < snap_point_tolerance().powi(2);
 		if !on_top {
 			return None;
 		}
@@ -357,7 +361,7 @@ impl PenToolData {
 		let last_in = inwards_handle.get_position(last_manipulator_group)?;
 
 		let transformed_distance_between_squared = transform.transform_point2(last_anchor).distance_squared(transform.transform_point2(first_anchor));
-		let snap_point_tolerance_squared = crate::consts::SNAP_POINT_TOLERANCE.powi(2);
+		let snap_point_tolerance_squared = snap_point_tolerance().powi(2);
 		let should_close_path = transformed_distance_between_squared < snap_point_tolerance_squared && previous_manipulator_group.is_some();
 		if should_close_path {
 			// Move the in handle of the first point to where the user has placed it
@@ -482,17 +486,23 @@ impl PenToolData {
 		let mut pos = transform.inverse().transform_point2(mouse);
 
 		// Snap to the first point (to show close path)
-		let show_close_path = mouse.distance_squared(transform.transform_point2(first_anchor)) < crate::consts::SNAP_POINT_TOLERANCE.powi(2);
+		let show_close_path = mouse.distance_squared(transform.transform_point2(first_anchor)) < snap_point_tolerance().powi(2);
 		if show_close_path {
 			pos = first_anchor;
 		}
 
 		if let Some(relative_previous_anchor) = previous_manipulator_group.map(|group| group.anchor) {
 			// Snap to the previously placed point (to show break control)
-			if mouse.distance_squared(transform.transform_point2(relative_previous_anchor)) < crate::consts::SNAP_POINT_TOLERANCE.powi(2) {
+			if mouse.distance_squared(transform.transform_point2(relative_previous_anchor)) < snap_point_tolerance().powi(2) {
 				pos = relative_previous_anchor;
 			} else {
 				pos = compute_snapped_angle(&mut self.angle, modifiers.lock_angle, modifiers.snap_angle, pos, relative_previous_anchor);
+
+				// Follow the guide grid unless the angle is already snapped or locked
+				if !modifiers.snap_angle && !modifiers.lock_angle {
+					let snapped = document.snap_to_guide_grid(transform.transform_point2(relative_previous_anchor), transform.transform_point2(pos));
+					pos = transform.inverse().transform_point2(snapped);
+				}
 			}
 		}
 
@@ -627,13 +637,12 @@ impl Fsm for PenToolFsmState {
 					tool_data.should_mirror = false;
 
 					// Perform extension of an existing path
-					if let Some((layer, subpath_index, from_start)) = should_extend(document, input.mouse.position, crate::consts::SNAP_POINT_TOLERANCE) {
+					if let Some((layer, subpath_index, from_start)) = should_extend(document, input.mouse.position, snap_point_tolerance()) {
 						tool_data.extend_subpath(layer, subpath_index, from_start, document, responses);
 					} else {
 						tool_data.create_new_path(
 							document,
-							tool_options.line_weight,
-							tool_options.stroke.active_color(),
+							tool_options.stroke_style.apply(Stroke::new(tool_options.stroke.active_color(), tool_options.line_weight)),
 							tool_options.fill.active_color(),
 							input,
 							responses,
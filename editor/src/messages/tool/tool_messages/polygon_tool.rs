@@ -5,9 +5,12 @@ use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
 use crate::messages::tool::common_functionality::graph_modification_utils;
 use crate::messages::tool::common_functionality::resize::Resize;
+use crate::messages::tool::common_functionality::stroke_options::{StrokeOptionsUpdate, ToolStrokeOptions};
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
+use graph_craft::document::value::TaggedValue;
+use graph_craft::document::NodeInput;
 use graphene_core::vector::style::{Fill, Stroke};
 use graphene_core::Color;
 
@@ -23,8 +26,10 @@ pub struct PolygonTool {
 
 pub struct PolygonOptions {
 	line_weight: f64,
+	lock_aspect_ratio: bool,
 	fill: ToolColorOptions,
 	stroke: ToolColorOptions,
+	stroke_style: ToolStrokeOptions,
 	vertices: u32,
 	primitive_shape_type: PrimitiveShapeType,
 }
@@ -34,8 +39,10 @@ impl Default for PolygonOptions {
 		Self {
 			vertices: 5,
 			line_weight: 5.,
+			lock_aspect_ratio: false,
 			fill: ToolColorOptions::new_secondary(),
 			stroke: ToolColorOptions::new_primary(),
+			stroke_style: ToolStrokeOptions::default(),
 			primitive_shape_type: PrimitiveShapeType::Polygon,
 		}
 	}
@@ -65,6 +72,36 @@ pub enum PolygonToolMessage {
 pub enum PrimitiveShapeType {
 	Polygon = 0,
 	Star = 1,
+	// Shape library
+	Arrow = 2,
+	SpeechBubble = 3,
+	Gear = 4,
+	Heart = 5,
+	QrCode = 6,
+	Barcode = 7,
+	BarChart = 8,
+	LineChart = 9,
+	PieChart = 10,
+	PlaceholderImage = 11,
+}
+
+impl PrimitiveShapeType {
+	/// The generator node that draws a shape from the shape library, which is placed in the layer so its parameters can be edited later.
+	fn generator(&self) -> Option<&'static str> {
+		match self {
+			Self::Polygon | Self::Star => None,
+			Self::Arrow => Some("Arrow Generator"),
+			Self::SpeechBubble => Some("Speech Bubble Generator"),
+			Self::Gear => Some("Gear Generator"),
+			Self::Heart => Some("Heart Generator"),
+			Self::QrCode => Some("QR Code Generator"),
+			Self::Barcode => Some("Barcode Generator"),
+			Self::BarChart => Some("Bar Chart Generator"),
+			Self::LineChart => Some("Line Chart Generator"),
+			Self::PieChart => Some("Pie Chart Generator"),
+			Self::PlaceholderImage => Some("Placeholder Image Generator"),
+		}
+	}
 }
 
 #[remain::sorted]
@@ -73,9 +110,11 @@ pub enum PolygonOptionsUpdate {
 	FillColor(Option<Color>),
 	FillColorType(ToolColorType),
 	LineWeight(f64),
+	LockAspectRatio(bool),
 	PrimitiveShapeType(PrimitiveShapeType),
 	StrokeColor(Option<Color>),
 	StrokeColorType(ToolColorType),
+	StrokeStyle(StrokeOptionsUpdate),
 	Vertices(u32),
 	WorkingColors(Option<Color>, Option<Color>),
 }
@@ -92,9 +131,9 @@ impl ToolMetadata for PolygonTool {
 	}
 }
 
-fn create_sides_widget(vertices: u32) -> WidgetHolder {
+fn create_sides_widget(vertices: u32, label: &str) -> WidgetHolder {
 	NumberInput::new(Some(vertices as f64))
-		.label("Sides")
+		.label(label)
 		.int()
 		.min(3.)
 		.max(1000.)
@@ -103,12 +142,29 @@ fn create_sides_widget(vertices: u32) -> WidgetHolder {
 		.widget_holder()
 }
 
-fn create_star_option_widget(primitive_shape_type: PrimitiveShapeType) -> WidgetHolder {
+fn create_shape_type_widget(primitive_shape_type: PrimitiveShapeType) -> WidgetHolder {
+	let entry =
+		|label: &str, shape_type: PrimitiveShapeType| DropdownEntryData::new(label).on_update(move |_| PolygonToolMessage::UpdateOptions(PolygonOptionsUpdate::PrimitiveShapeType(shape_type)).into());
 	let entries = vec![
-		RadioEntryData::new("Polygon").on_update(move |_| PolygonToolMessage::UpdateOptions(PolygonOptionsUpdate::PrimitiveShapeType(PrimitiveShapeType::Polygon)).into()),
-		RadioEntryData::new("Star").on_update(move |_| PolygonToolMessage::UpdateOptions(PolygonOptionsUpdate::PrimitiveShapeType(PrimitiveShapeType::Star)).into()),
+		vec![entry("Polygon", PrimitiveShapeType::Polygon), entry("Star", PrimitiveShapeType::Star)],
+		vec![
+			entry("Arrow", PrimitiveShapeType::Arrow),
+			entry("Speech Bubble", PrimitiveShapeType::SpeechBubble),
+			entry("Gear", PrimitiveShapeType::Gear),
+			entry("Heart", PrimitiveShapeType::Heart),
+		],
+		vec![entry("QR Code", PrimitiveShapeType::QrCode), entry("Barcode", PrimitiveShapeType::Barcode)],
+		vec![
+			entry("Bar Chart", PrimitiveShapeType::BarChart),
+			entry("Line Chart", PrimitiveShapeType::LineChart),
+			entry("Pie Chart", PrimitiveShapeType::PieChart),
+		],
+		vec![entry("Placeholder Image", PrimitiveShapeType::PlaceholderImage)],
 	];
-	RadioInput::new(entries).selected_index(primitive_shape_type as u32).widget_holder()
+	DropdownInput::new(entries)
+		.selected_index(Some(primitive_shape_type as u32))
+		.tooltip("The shape to draw. Shapes from the library below the polygons keep their parameters editable in the Properties panel.")
+		.widget_holder()
 }
 
 fn create_weight_widget(line_weight: f64) -> WidgetHolder {
@@ -120,13 +176,26 @@ fn create_weight_widget(line_weight: f64) -> WidgetHolder {
 		.widget_holder()
 }
 
+fn create_lock_aspect_ratio_widget(lock_aspect_ratio: bool) -> WidgetHolder {
+	CheckboxInput::new(lock_aspect_ratio)
+		.icon("Link")
+		.tooltip("Lock Aspect Ratio (instead of holding Shift)")
+		.on_update(|input: &CheckboxInput| PolygonToolMessage::UpdateOptions(PolygonOptionsUpdate::LockAspectRatio(input.checked)).into())
+		.widget_holder()
+}
+
 impl LayoutHolder for PolygonTool {
 	fn layout(&self) -> Layout {
-		let mut widgets = vec![
-			create_star_option_widget(self.options.primitive_shape_type),
-			Separator::new(SeparatorType::Related).widget_holder(),
-			create_sides_widget(self.options.vertices),
-		];
+		let mut widgets = vec![create_shape_type_widget(self.options.primitive_shape_type)];
+		let sides_label = match self.options.primitive_shape_type {
+			PrimitiveShapeType::Polygon | PrimitiveShapeType::Star => Some("Sides"),
+			PrimitiveShapeType::Gear => Some("Teeth"),
+			_ => None,
+		};
+		if let Some(label) = sides_label {
+			widgets.push(Separator::new(SeparatorType::Related).widget_holder());
+			widgets.push(create_sides_widget(self.options.vertices, label));
+		}
 
 		widgets.push(Separator::new(SeparatorType::Section).widget_holder());
 
@@ -149,6 +218,14 @@ impl LayoutHolder for PolygonTool {
 		));
 		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
 		widgets.push(create_weight_widget(self.options.line_weight));
+		widgets.append(
+			&mut self
+				.options
+				.stroke_style
+				.create_widgets(|update| PolygonToolMessage::UpdateOptions(PolygonOptionsUpdate::StrokeStyle(update)).into()),
+		);
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		widgets.push(create_lock_aspect_ratio_widget(self.options.lock_aspect_ratio));
 
 		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
@@ -165,11 +242,13 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Polygon
 				}
 				PolygonOptionsUpdate::FillColorType(color_type) => self.options.fill.color_type = color_type,
 				PolygonOptionsUpdate::LineWeight(line_weight) => self.options.line_weight = line_weight,
+				PolygonOptionsUpdate::LockAspectRatio(lock_aspect_ratio) => self.options.lock_aspect_ratio = lock_aspect_ratio,
 				PolygonOptionsUpdate::StrokeColor(color) => {
 					self.options.stroke.custom_color = color;
 					self.options.stroke.color_type = ToolColorType::Custom;
 				}
 				PolygonOptionsUpdate::StrokeColorType(color_type) => self.options.stroke.color_type = color_type,
+				PolygonOptionsUpdate::StrokeStyle(update) => self.options.stroke_style.update(update),
 				PolygonOptionsUpdate::WorkingColors(primary, secondary) => {
 					self.options.stroke.primary_working_color = primary;
 					self.options.stroke.secondary_working_color = secondary;
@@ -253,15 +332,23 @@ impl Fsm for PolygonToolFsmState {
 					polygon_data.start(responses, document, input, render_data);
 					responses.add(DocumentMessage::StartTransaction);
 					let layer_path = document.get_path_for_new_layer();
+					let insert_index = document.get_insert_index_for_new_layer();
 					polygon_data.path = Some(layer_path.clone());
 
-					let subpath = match tool_options.primitive_shape_type {
-						PrimitiveShapeType::Polygon => bezier_rs::Subpath::new_regular_polygon(DVec2::ZERO, tool_options.vertices as u64, 1.),
-						PrimitiveShapeType::Star => bezier_rs::Subpath::new_regular_star_polygon(DVec2::ZERO, tool_options.vertices as u64, 1., 0.5),
-					};
-					graph_modification_utils::new_vector_layer(vec![subpath], layer_path.clone(), responses);
+					let shape_type = tool_options.primitive_shape_type;
+					if let Some(generator) = shape_type.generator() {
+						let teeth = (shape_type == PrimitiveShapeType::Gear).then(|| NodeInput::value(TaggedValue::U32(tool_options.vertices), false));
+						graph_modification_utils::new_generated_vector_layer_at(generator, [None, teeth], layer_path.clone(), insert_index, responses);
+					} else {
+						let subpath = match shape_type {
+							PrimitiveShapeType::Star => bezier_rs::Subpath::new_regular_star_polygon(DVec2::ZERO, tool_options.vertices as u64, 1., 0.5),
+							_ => bezier_rs::Subpath::new_regular_polygon(DVec2::ZERO, tool_options.vertices as u64, 1.),
+						};
+						graph_modification_utils::new_vector_layer_at(vec![subpath], layer_path.clone(), insert_index, responses);
+					}
 
-					let fill_color = tool_options.fill.active_color();
+					// A line chart is an open path, so filling it would only obscure the line
+					let fill_color = tool_options.fill.active_color().filter(|_| shape_type != PrimitiveShapeType::LineChart);
 					responses.add(GraphOperationMessage::FillSet {
 						layer: layer_path.clone(),
 						fill: if let Some(color) = fill_color { Fill::Solid(color) } else { Fill::None },
@@ -269,13 +356,13 @@ impl Fsm for PolygonToolFsmState {
 
 					responses.add(GraphOperationMessage::StrokeSet {
 						layer: layer_path,
-						stroke: Stroke::new(tool_options.stroke.active_color(), tool_options.line_weight),
+						stroke: tool_options.stroke_style.apply(Stroke::new(tool_options.stroke.active_color(), tool_options.line_weight)),
 					});
 
 					Drawing
 				}
 				(state, Resize { center, lock_ratio }) => {
-					if let Some(message) = polygon_data.calculate_transform(responses, document, input, center, lock_ratio, false) {
+					if let Some(message) = polygon_data.calculate_transform(responses, document, input, center, lock_ratio, tool_options.lock_aspect_ratio, false) {
 						responses.add(message);
 					}
 
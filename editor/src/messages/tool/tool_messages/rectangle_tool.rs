@@ -4,7 +4,10 @@ use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
 use crate::messages::tool::common_functionality::graph_modification_utils;
+use crate::messages::tool::common_functionality::hit_targets::drag_threshold;
 use crate::messages::tool::common_functionality::resize::Resize;
+use crate::messages::tool::common_functionality::shape_presets::ToolShapePreset;
+use crate::messages::tool::common_functionality::stroke_options::{StrokeOptionsUpdate, ToolStrokeOptions};
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
@@ -22,16 +25,25 @@ pub struct RectangleTool {
 
 pub struct RectangleToolOptions {
 	line_weight: f64,
+	lock_aspect_ratio: bool,
+	/// Draws rectangles with a "Rectangle Generator", whose corners can then be rounded on the canvas with the Select tool, rather than as paths
+	editable_corners: bool,
 	fill: ToolColorOptions,
 	stroke: ToolColorOptions,
+	stroke_style: ToolStrokeOptions,
+	preset: ToolShapePreset,
 }
 
 impl Default for RectangleToolOptions {
 	fn default() -> Self {
 		Self {
 			line_weight: 5.,
+			lock_aspect_ratio: false,
+			editable_corners: false,
 			fill: ToolColorOptions::new_secondary(),
 			stroke: ToolColorOptions::new_primary(),
+			stroke_style: ToolStrokeOptions::default(),
+			preset: ToolShapePreset::default(),
 		}
 	}
 }
@@ -39,11 +51,15 @@ impl Default for RectangleToolOptions {
 #[remain::sorted]
 #[derive(PartialEq, Clone, Debug, Serialize, Deserialize, specta::Type)]
 pub enum RectangleOptionsUpdate {
+	EditableCorners(bool),
 	FillColor(Option<Color>),
 	FillColorType(ToolColorType),
 	LineWeight(f64),
+	LockAspectRatio(bool),
+	Preset(Option<String>),
 	StrokeColor(Option<Color>),
 	StrokeColorType(ToolColorType),
+	StrokeStyle(StrokeOptionsUpdate),
 	WorkingColors(Option<Color>, Option<Color>),
 }
 
@@ -76,6 +92,25 @@ fn create_weight_widget(line_weight: f64) -> WidgetHolder {
 		.widget_holder()
 }
 
+fn create_lock_aspect_ratio_widget(lock_aspect_ratio: bool) -> WidgetHolder {
+	CheckboxInput::new(lock_aspect_ratio)
+		.icon("Link")
+		.tooltip("Lock Aspect Ratio (instead of holding Shift)")
+		.on_update(|input: &CheckboxInput| RectangleToolMessage::UpdateOptions(RectangleOptionsUpdate::LockAspectRatio(input.checked)).into())
+		.widget_holder()
+}
+
+fn create_editable_corners_widgets(editable_corners: bool) -> Vec<WidgetHolder> {
+	vec![
+		TextLabel::new("Editable Corners").widget_holder(),
+		Separator::new(SeparatorType::Related).widget_holder(),
+		CheckboxInput::new(editable_corners)
+			.tooltip("Keep the rectangle's shape parametric, so its corners can be rounded by dragging the handles inside them with the Select tool")
+			.on_update(|input: &CheckboxInput| RectangleToolMessage::UpdateOptions(RectangleOptionsUpdate::EditableCorners(input.checked)).into())
+			.widget_holder(),
+	]
+}
+
 impl LayoutHolder for RectangleTool {
 	fn layout(&self) -> Layout {
 		let mut widgets = self.options.fill.create_widgets(
@@ -97,6 +132,23 @@ impl LayoutHolder for RectangleTool {
 		));
 		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
 		widgets.push(create_weight_widget(self.options.line_weight));
+		widgets.append(
+			&mut self
+				.options
+				.stroke_style
+				.create_widgets(|update| RectangleToolMessage::UpdateOptions(RectangleOptionsUpdate::StrokeStyle(update)).into()),
+		);
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		widgets.push(create_lock_aspect_ratio_widget(self.options.lock_aspect_ratio));
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		widgets.append(&mut create_editable_corners_widgets(self.options.editable_corners));
+		widgets.push(Separator::new(SeparatorType::Unrelated).widget_holder());
+		widgets.append(
+			&mut self
+				.options
+				.preset
+				.create_widgets(|name| RectangleToolMessage::UpdateOptions(RectangleOptionsUpdate::Preset(name)).into()),
+		);
 
 		Layout::WidgetLayout(WidgetLayout::new(vec![LayoutGroup::Row { widgets }]))
 	}
@@ -105,18 +157,33 @@ impl LayoutHolder for RectangleTool {
 impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for RectangleTool {
 	fn process_message(&mut self, message: ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut ToolActionHandlerData<'a>) {
 		if let ToolMessage::Rectangle(RectangleToolMessage::UpdateOptions(action)) = message {
+			// Changing the style means it no longer matches the chosen preset
+			let changes_style = !matches!(
+				action,
+				RectangleOptionsUpdate::EditableCorners(_) | RectangleOptionsUpdate::LockAspectRatio(_) | RectangleOptionsUpdate::Preset(_) | RectangleOptionsUpdate::WorkingColors(..)
+			);
+
 			match action {
+				RectangleOptionsUpdate::EditableCorners(editable_corners) => self.options.editable_corners = editable_corners,
 				RectangleOptionsUpdate::FillColor(color) => {
 					self.options.fill.custom_color = color;
 					self.options.fill.color_type = ToolColorType::Custom;
 				}
 				RectangleOptionsUpdate::FillColorType(color_type) => self.options.fill.color_type = color_type,
 				RectangleOptionsUpdate::LineWeight(line_weight) => self.options.line_weight = line_weight,
+				RectangleOptionsUpdate::LockAspectRatio(lock_aspect_ratio) => self.options.lock_aspect_ratio = lock_aspect_ratio,
+				RectangleOptionsUpdate::Preset(name) => {
+					if let Some(preset) = self.options.preset.select(name) {
+						let options = &mut self.options;
+						preset.apply(&mut options.fill, &mut options.stroke, &mut options.line_weight, &mut options.stroke_style);
+					}
+				}
 				RectangleOptionsUpdate::StrokeColor(color) => {
 					self.options.stroke.custom_color = color;
 					self.options.stroke.color_type = ToolColorType::Custom;
 				}
 				RectangleOptionsUpdate::StrokeColorType(color_type) => self.options.stroke.color_type = color_type,
+				RectangleOptionsUpdate::StrokeStyle(update) => self.options.stroke_style.update(update),
 				RectangleOptionsUpdate::WorkingColors(primary, secondary) => {
 					self.options.stroke.primary_working_color = primary;
 					self.options.stroke.secondary_working_color = secondary;
@@ -124,6 +191,9 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for Rectang
 					self.options.fill.secondary_working_color = secondary;
 				}
 			}
+			if changes_style {
+				self.options.preset = ToolShapePreset::default();
+			}
 
 			self.send_layout(responses, LayoutTarget::ToolOptions);
 
@@ -211,12 +281,16 @@ impl Fsm for RectangleToolFsmState {
 				(Ready, DragStart) => {
 					shape_data.start(responses, document, input, render_data);
 
-					let subpath = bezier_rs::Subpath::new_rect(DVec2::ZERO, DVec2::ONE);
-
 					let layer_path = document.get_path_for_new_layer();
+					let insert_index = document.get_insert_index_for_new_layer();
 					responses.add(DocumentMessage::StartTransaction);
 					shape_data.path = Some(layer_path.clone());
-					graph_modification_utils::new_vector_layer(vec![subpath], layer_path.clone(), responses);
+					if tool_options.editable_corners {
+						graph_modification_utils::new_generated_vector_layer_at("Rectangle Generator", [None], layer_path.clone(), insert_index, responses);
+					} else {
+						let subpath = bezier_rs::Subpath::new_rect(DVec2::ZERO, DVec2::ONE);
+						graph_modification_utils::new_vector_layer_at(vec![subpath], layer_path.clone(), insert_index, responses);
+					}
 
 					let fill_color = tool_options.fill.active_color();
 					responses.add(GraphOperationMessage::FillSet {
@@ -226,20 +300,28 @@ impl Fsm for RectangleToolFsmState {
 
 					responses.add(GraphOperationMessage::StrokeSet {
 						layer: layer_path,
-						stroke: Stroke::new(tool_options.stroke.active_color(), tool_options.line_weight),
+						stroke: tool_options.stroke_style.apply(Stroke::new(tool_options.stroke.active_color(), tool_options.line_weight)),
 					});
 
 					Drawing
 				}
 				(state, Resize { center, lock_ratio }) => {
-					if let Some(message) = shape_data.calculate_transform(responses, document, input, center, lock_ratio, false) {
+					if let Some(message) = shape_data.calculate_transform(responses, document, input, center, lock_ratio, tool_options.lock_aspect_ratio, false) {
 						responses.add(message);
 					}
 
 					state
 				}
 				(Drawing, DragStop) => {
-					input.mouse.finish_transaction(shape_data.viewport_drag_start(document), responses);
+					// A click places a shape at the size of the chosen preset, where it would otherwise be discarded for having no size
+					let clicked = shape_data.viewport_drag_start(document).distance(input.mouse.position) <= drag_threshold();
+					match tool_options.preset.size().filter(|_| clicked).and_then(|size| shape_data.place_with_size(document, size)) {
+						Some(message) => {
+							responses.add(message);
+							responses.add(DocumentMessage::CommitTransaction);
+						}
+						None => input.mouse.finish_transaction(shape_data.viewport_drag_start(document), responses),
+					}
 					shape_data.cleanup(responses);
 
 					Ready
@@ -1,20 +1,25 @@
 #![allow(clippy::too_many_arguments)]
 use crate::application::generate_uuid;
-use crate::consts::{ROTATE_SNAP_ANGLE, SELECTION_TOLERANCE};
+use crate::consts::ROTATE_SNAP_ANGLE;
 use crate::messages::frontend::utility_types::MouseCursorIcon;
 use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
 use crate::messages::input_mapper::utility_types::input_mouse::ViewportPosition;
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::utility_types::misc::{AlignAggregate, AlignAxis, FlipAxis};
-use crate::messages::portfolio::document::utility_types::transformation::Selected;
+use crate::messages::portfolio::document::utility_types::transformation::{Selected, Typing};
 use crate::messages::prelude::*;
+use crate::messages::tool::common_functionality::canvas_follower::CanvasFollower;
+use crate::messages::tool::common_functionality::corner_radius::CornerRadiusHandles;
+use crate::messages::tool::common_functionality::hit_targets::{pointer_quad, selection_tolerance};
 use crate::messages::tool::common_functionality::path_outline::*;
 use crate::messages::tool::common_functionality::pivot::Pivot;
+use crate::messages::tool::common_functionality::quick_mask::QuickMask;
 use crate::messages::tool::common_functionality::snapping::{self, SnapManager};
 use crate::messages::tool::common_functionality::transformation_cage::*;
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
+use document_legacy::boolean_ops::BooleanOperation;
 use document_legacy::document::Document;
 use document_legacy::intersection::Quad;
 use document_legacy::layers::layer_info::{Layer, LayerDataType};
@@ -69,6 +74,8 @@ pub enum SelectToolMessage {
 	#[remain::unsorted]
 	DocumentIsDirty,
 	#[remain::unsorted]
+	PointerLeave,
+	#[remain::unsorted]
 	SelectionChanged,
 
 	// Tool-specific messages
@@ -93,10 +100,20 @@ pub enum SelectToolMessage {
 		center: Key,
 		duplicate: Key,
 	},
+	ResizeQuickMaskBrush {
+		grow: bool,
+	},
 	SelectOptions(SelectOptionsUpdate),
 	SetPivot {
 		position: PivotPosition,
 	},
+	ToggleQuickMask,
+	TypeBackspace,
+	TypeDecimalPoint,
+	TypeDigit {
+		digit: u8,
+	},
+	TypeNegate,
 }
 
 impl ToolMetadata for SelectTool {
@@ -111,6 +128,21 @@ impl ToolMetadata for SelectTool {
 	}
 }
 
+/// A button applying a boolean operation to the selected shapes, whose result is previewed on the canvas while the button is hovered.
+fn boolean_operation_button(icon: &str, tooltip: &str, operation: BooleanOperation) -> WidgetHolder {
+	IconButton::new(icon, 24)
+		.tooltip(tooltip)
+		.reports_hover(true)
+		.on_update(move |_| DocumentMessage::BooleanOperation(operation).into())
+		.on_hover(move |&hovered| {
+			DocumentMessage::PreviewBooleanOperation {
+				operation: hovered.then_some(operation),
+			}
+			.into()
+		})
+		.widget_holder()
+}
+
 impl LayoutHolder for SelectTool {
 	fn layout(&self) -> Layout {
 		let layer_selection_behavior_entries = [NestedSelectionBehavior::Deepest, NestedSelectionBehavior::Shallowest]
@@ -220,26 +252,11 @@ impl LayoutHolder for SelectTool {
 				Separator::new(SeparatorType::Related).widget_holder(),
 				PopoverButton::new("Flip", "Coming soon").widget_holder(),
 				Separator::new(SeparatorType::Section).widget_holder(),
-				IconButton::new("BooleanUnion", 24)
-					.tooltip("Boolean Union (coming soon)")
-					.on_update(|_| DialogMessage::RequestComingSoonDialog { issue: Some(1091) }.into())
-					.widget_holder(),
-				IconButton::new("BooleanSubtractFront", 24)
-					.tooltip("Boolean Subtract Front (coming soon)")
-					.on_update(|_| DialogMessage::RequestComingSoonDialog { issue: Some(1091) }.into())
-					.widget_holder(),
-				IconButton::new("BooleanSubtractBack", 24)
-					.tooltip("Boolean Subtract Back (coming soon)")
-					.on_update(|_| DialogMessage::RequestComingSoonDialog { issue: Some(1091) }.into())
-					.widget_holder(),
-				IconButton::new("BooleanIntersect", 24)
-					.tooltip("Boolean Intersect (coming soon)")
-					.on_update(|_| DialogMessage::RequestComingSoonDialog { issue: Some(1091) }.into())
-					.widget_holder(),
-				IconButton::new("BooleanDifference", 24)
-					.tooltip("Boolean Difference (coming soon)")
-					.on_update(|_| DialogMessage::RequestComingSoonDialog { issue: Some(1091) }.into())
-					.widget_holder(),
+				boolean_operation_button("BooleanUnion", "Boolean Union", BooleanOperation::Union),
+				boolean_operation_button("BooleanSubtractFront", "Boolean Subtract Front", BooleanOperation::SubtractFront),
+				boolean_operation_button("BooleanSubtractBack", "Boolean Subtract Back", BooleanOperation::SubtractBack),
+				boolean_operation_button("BooleanIntersect", "Boolean Intersect", BooleanOperation::Intersection),
+				boolean_operation_button("BooleanDifference", "Boolean Difference", BooleanOperation::Difference),
 				Separator::new(SeparatorType::Related).widget_holder(),
 				PopoverButton::new("Boolean", "Coming soon").widget_holder(),
 			],
@@ -273,6 +290,27 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for SelectT
 				Abort,
 				EditLayer,
 				Enter,
+				ToggleQuickMask,
+			),
+			QuickMask | PaintingQuickMask => actions!(SelectToolMessageDiscriminant;
+				DragStart,
+				DragStop,
+				PointerMove,
+				Abort,
+				Enter,
+				ResizeQuickMaskBrush,
+				ToggleQuickMask,
+			),
+			Dragging | ResizingBounds | RotatingBounds => actions!(SelectToolMessageDiscriminant;
+				DragStop,
+				PointerMove,
+				Abort,
+				EditLayer,
+				Enter,
+				TypeBackspace,
+				TypeDecimalPoint,
+				TypeDigit,
+				TypeNegate,
 			),
 			_ => actions!(SelectToolMessageDiscriminant;
 				DragStop,
@@ -290,7 +328,9 @@ impl ToolTransition for SelectTool {
 		EventToMessageMap {
 			document_dirty: Some(SelectToolMessage::DocumentIsDirty.into()),
 			tool_abort: Some(SelectToolMessage::Abort.into()),
+			tool_confirm: Some(SelectToolMessage::Enter.into()),
 			selection_changed: Some(SelectToolMessage::SelectionChanged.into()),
+			pointer_leave: Some(SelectToolMessage::PointerLeave.into()),
 			..Default::default()
 		}
 	}
@@ -305,12 +345,17 @@ enum SelectToolFsmState {
 	ResizingBounds,
 	RotatingBounds,
 	DraggingPivot,
+	DraggingCornerRadius,
+	/// Showing the quick mask, which is painted on by dragging
+	QuickMask,
+	PaintingQuickMask,
 }
 
 #[derive(Clone, Debug, Default)]
 struct SelectToolData {
 	drag_start: ViewportPosition,
 	drag_current: ViewportPosition,
+	canvas_follower: CanvasFollower,
 	layers_dragging: Vec<Vec<LayerId>>,
 	layer_selected_on_start: Option<Vec<LayerId>>,
 	is_dragging: bool,
@@ -321,12 +366,80 @@ struct SelectToolData {
 	snap_manager: SnapManager,
 	cursor: MouseCursorIcon,
 	pivot: Pivot,
+	corner_radius: CornerRadiusHandles,
 	nested_selection_behavior: NestedSelectionBehavior,
 	selected_layers_count: usize,
 	selected_layers_changed: bool,
+	/// A value typed mid-drag which overrides the mouse movement until the drag ends
+	typing: Typing,
+	/// The rotation or scale about its pivot that resizing or rotating the bounding box has applied so far, which "Transform Again" repeats once the drag ends
+	bounds_transform: DAffine2,
+	quick_mask: QuickMask,
 }
 
 impl SelectToolData {
+	/// Keeps the drag anchored to the document while the canvas moves beneath it, such as when it auto-scrolls near the edge of the viewport.
+	fn follow_canvas(&mut self, document: &DocumentMessageHandler) {
+		let change = self.canvas_follower.follow(document);
+		self.drag_start = change.transform_point2(self.drag_start);
+		self.drag_current = change.transform_point2(self.drag_current);
+	}
+
+	/// Sets the drag, rotation, or scale to exactly the typed value, or back to none of it once the typed value is erased.
+	/// Drags are measured in document units along the direction of the drag, rotations in degrees, and scales as a factor.
+	fn apply_typed_value(&mut self, state: SelectToolFsmState, value: Option<f64>, document: &DocumentMessageHandler, input: &InputPreprocessorMessageHandler, responses: &mut VecDeque<Message>) {
+		match state {
+			SelectToolFsmState::Dragging => {
+				let direction = (input.mouse.position - self.drag_start).try_normalize().unwrap_or(DVec2::X);
+				let zoom = document.document_legacy.root.transform.matrix2.x_axis.length();
+				let target = self.drag_start + direction * value.unwrap_or_default() * zoom;
+
+				for path in Document::shallowest_unique_layers(self.layers_dragging.iter()) {
+					responses.add(GraphOperationMessage::TransformChange {
+						layer: path.to_vec(),
+						transform: DAffine2::from_translation(target - self.drag_current),
+						transform_in: TransformIn::Viewport,
+						skip_rerender: false,
+					});
+				}
+				self.drag_current = target;
+			}
+			SelectToolFsmState::RotatingBounds => {
+				if let Some(bounds) = &mut self.bounding_box_overlays {
+					let selected = self.layers_dragging.iter().collect::<Vec<_>>();
+					let mut selected = Selected::new(
+						&mut bounds.original_transforms,
+						&mut bounds.center_of_transformation,
+						&selected,
+						responses,
+						&document.document_legacy,
+						None,
+						&ToolType::Select,
+					);
+
+					self.bounds_transform = DAffine2::from_angle(value.unwrap_or_default().to_radians());
+					selected.update_transforms(self.bounds_transform);
+				}
+			}
+			SelectToolFsmState::ResizingBounds => {
+				if let Some(bounds) = &mut self.bounding_box_overlays {
+					let Some((delta, mut pivot)) = bounds.selected_edges.as_ref().map(|movement| movement.typed_scale_transform(value.unwrap_or(1.))) else {
+						return;
+					};
+
+					let selected = self.layers_dragging.iter().collect::<Vec<_>>();
+					let mut selected = Selected::new(&mut bounds.original_transforms, &mut pivot, &selected, responses, &document.document_legacy, None, &ToolType::Select);
+
+					self.bounds_transform = delta;
+					selected.update_transforms(delta);
+				}
+			}
+			_ => return,
+		}
+
+		responses.add(BroadcastEvent::DocumentIsDirty);
+	}
+
 	fn selection_quad(&self) -> Quad {
 		let bbox = self.selection_box();
 		Quad::from_box(bbox)
@@ -334,7 +447,7 @@ impl SelectToolData {
 
 	fn selection_box(&self) -> [DVec2; 2] {
 		if self.drag_current == self.drag_start {
-			let tolerance = DVec2::splat(SELECTION_TOLERANCE);
+			let tolerance = DVec2::splat(selection_tolerance());
 			[self.drag_start - tolerance, self.drag_start + tolerance]
 		} else {
 			[self.drag_start, self.drag_current]
@@ -439,6 +552,39 @@ impl Fsm for SelectToolFsmState {
 
 		if let ToolMessage::Select(event) = event {
 			match (self, event) {
+				// The painted region is kept over the same artwork as the canvas moves
+				(QuickMask | PaintingQuickMask, DocumentIsDirty | SelectionChanged) => {
+					tool_data.quick_mask.update_overlay(&document.document_legacy, responses);
+					self
+				}
+				(Ready, ToggleQuickMask) => {
+					tool_data.path_outlines.clear_hovered(responses);
+					tool_data.quick_mask.show(responses);
+					QuickMask
+				}
+				(QuickMask, DragStart { .. }) => {
+					tool_data.quick_mask.start_stroke(input.mouse.position, &document.document_legacy, responses);
+					PaintingQuickMask
+				}
+				(PaintingQuickMask, PointerMove { .. }) => {
+					tool_data.quick_mask.extend_stroke(input.mouse.position, &document.document_legacy, responses);
+					PaintingQuickMask
+				}
+				(PaintingQuickMask, DragStop { .. }) => QuickMask,
+				(QuickMask | PaintingQuickMask, ResizeQuickMaskBrush { grow }) => {
+					tool_data.quick_mask.resize_brush(grow);
+					self
+				}
+				(QuickMask | PaintingQuickMask, ToggleQuickMask | Enter) => {
+					let replacement_selected_layers = tool_data.quick_mask.touched_layers(&document.document_legacy, render_data);
+					responses.add(DocumentMessage::SetSelectedLayers { replacement_selected_layers });
+					tool_data.quick_mask.clear(responses);
+					Ready
+				}
+				(QuickMask | PaintingQuickMask, Abort) => {
+					tool_data.quick_mask.clear(responses);
+					Ready
+				}
 				(_, DocumentIsDirty | SelectionChanged) => {
 					let selected_layers_count = document.selected_layers().count();
 					let selected_layers_changed = selected_layers_count != tool_data.selected_layers_count;
@@ -465,17 +611,17 @@ impl Fsm for SelectToolFsmState {
 						(_, _) => {}
 					};
 
-					tool_data.path_outlines.update_selected(document.selected_visible_layers(), document, responses, render_data);
+					tool_data.path_outlines.update_selected(document.selected_visible_layers(), document, responses);
 					tool_data.path_outlines.intersect_test_hovered(input, document, responses, render_data);
 					tool_data.pivot.update_pivot(document, render_data, responses);
+					tool_data.corner_radius.update(document, responses);
 
 					self
 				}
 				(_, EditLayer) => {
 					// Setup required data for checking the clicked layer
 					let mouse_pos = input.mouse.position;
-					let tolerance = DVec2::splat(SELECTION_TOLERANCE);
-					let quad = Quad::from_box([mouse_pos - tolerance, mouse_pos + tolerance]);
+					let quad = pointer_quad(mouse_pos);
 
 					// Check the last (topmost) intersection layer
 					if let Some(intersect_layer_path) = document.document_legacy.intersects_quad_root(quad, render_data).last() {
@@ -491,9 +637,11 @@ impl Fsm for SelectToolFsmState {
 				}
 				(Ready, DragStart { add_to_selection, select_deepest }) => {
 					tool_data.path_outlines.clear_hovered(responses);
+					tool_data.typing.clear();
 
 					tool_data.drag_start = input.mouse.position;
 					tool_data.drag_current = input.mouse.position;
+					tool_data.canvas_follower.start(document);
 
 					let dragging_bounds = tool_data.bounding_box_overlays.as_mut().and_then(|bounding_box| {
 						let edges = bounding_box.check_selected_edges(input.mouse.position);
@@ -522,7 +670,12 @@ impl Fsm for SelectToolFsmState {
 					// If the user clicks on a layer that is in their current selection, go into the dragging mode.
 					// If the user clicks on new shape, make that layer their new selection.
 					// Otherwise enter the box select mode
-					let state = if tool_data.pivot.is_over(input.mouse.position) {
+					let state = if let Some(corner) = tool_data.corner_radius.corner_under(input.mouse.position) {
+						responses.add(DocumentMessage::StartTransaction);
+						tool_data.corner_radius.start_drag(corner);
+
+						DraggingCornerRadius
+					} else if tool_data.pivot.is_over(input.mouse.position) {
 						responses.add(DocumentMessage::StartTransaction);
 
 						tool_data.snap_manager.start_snap(document, input, document.bounding_boxes(None, None, render_data), true, true);
@@ -626,10 +779,13 @@ impl Fsm for SelectToolFsmState {
 
 					state
 				}
+				// While a value is being typed, it takes precedence over the mouse position
+				(Dragging | ResizingBounds | RotatingBounds, PointerMove { .. }) if !tool_data.typing.digits.is_empty() => self,
 				(Dragging, PointerMove { axis_align, duplicate, .. }) => {
 					tool_data.is_dragging = true;
 					// TODO: This is a cheat. Break out the relevant functionality from the handler above and call it from there and here.
 					responses.add_front(SelectToolMessage::DocumentIsDirty);
+					tool_data.follow_canvas(document);
 
 					let mouse_position = axis_align_drag(input.keyboard.get(axis_align as usize), input.mouse.position, tool_data.drag_start);
 
@@ -666,6 +822,9 @@ impl Fsm for SelectToolFsmState {
 					if let Some(bounds) = &mut tool_data.bounding_box_overlays {
 						if let Some(movement) = &mut bounds.selected_edges {
 							let (center, axis_align) = (input.keyboard.get(center as usize), input.keyboard.get(axis_align as usize));
+							// Layers with a sticky aspect ratio lock always scale proportionally
+							let aspect_locked = tool_data.layers_dragging.iter().any(|path| document.layer_metadata.get(path).map_or(false, |data| data.aspect_locked));
+							let axis_align = axis_align || aspect_locked;
 
 							let mouse_position = input.mouse.position;
 
@@ -677,6 +836,7 @@ impl Fsm for SelectToolFsmState {
 							let selected = &tool_data.layers_dragging.iter().collect::<Vec<_>>();
 							let mut selected = Selected::new(&mut bounds.original_transforms, &mut _pivot, selected, responses, &document.document_legacy, None, &ToolType::Select);
 
+							tool_data.bounds_transform = delta;
 							selected.update_transforms(delta);
 						}
 					}
@@ -699,6 +859,7 @@ impl Fsm for SelectToolFsmState {
 						};
 
 						let delta = DAffine2::from_angle(snapped_angle);
+						tool_data.bounds_transform = delta;
 
 						let selected = tool_data.layers_dragging.iter().collect::<Vec<_>>();
 						let mut selected = Selected::new(
@@ -723,7 +884,13 @@ impl Fsm for SelectToolFsmState {
 
 					DraggingPivot
 				}
+				(DraggingCornerRadius, PointerMove { axis_align, .. }) => {
+					tool_data.corner_radius.drag(input.mouse.position, input.keyboard.get(axis_align as usize), responses);
+
+					DraggingCornerRadius
+				}
 				(DrawingBox, PointerMove { .. }) => {
+					tool_data.follow_canvas(document);
 					tool_data.drag_current = input.mouse.position;
 
 					responses.add_front(DocumentMessage::Overlays(
@@ -738,8 +905,8 @@ impl Fsm for SelectToolFsmState {
 				(Ready, PointerMove { .. }) => {
 					let mut cursor = tool_data.bounding_box_overlays.as_ref().map_or(MouseCursorIcon::Default, |bounds| bounds.get_cursor(input, true));
 
-					// Dragging the pivot overrules the other operations
-					if tool_data.pivot.is_over(input.mouse.position) {
+					// Dragging the pivot or a corner radius handle overrules the other operations
+					if tool_data.pivot.is_over(input.mouse.position) || tool_data.corner_radius.corner_under(input.mouse.position).is_some() {
 						cursor = MouseCursorIcon::Move;
 					}
 
@@ -760,11 +927,16 @@ impl Fsm for SelectToolFsmState {
 				(Dragging, Enter) => {
 					rerender_selected_layers(tool_data, responses);
 
-					let response = match input.mouse.position.distance(tool_data.drag_start) < 10. * f64::EPSILON {
+					let response = match tool_data.typing.digits.is_empty() && input.mouse.position.distance(tool_data.drag_start) < 10. * f64::EPSILON {
 						true => DocumentMessage::Undo,
-						false => DocumentMessage::CommitTransaction,
+						false => {
+							let viewport_transform = DAffine2::from_translation(tool_data.drag_current - tool_data.drag_start);
+							responses.add(DocumentMessage::RecordSelectionTransform { viewport_transform });
+							DocumentMessage::CommitTransaction
+						}
 					};
 					tool_data.snap_manager.cleanup(responses);
+					tool_data.typing.clear();
 					responses.add_front(response);
 
 					Ready
@@ -787,8 +959,14 @@ impl Fsm for SelectToolFsmState {
 						}
 					}
 
+					if tool_data.is_dragging {
+						let viewport_transform = DAffine2::from_translation(tool_data.drag_current - tool_data.drag_start);
+						responses.add(DocumentMessage::RecordSelectionTransform { viewport_transform });
+					}
+
 					tool_data.is_dragging = false;
 					tool_data.layer_selected_on_start = None;
+					tool_data.typing.clear();
 
 					responses.add(DocumentMessage::CommitTransaction);
 					tool_data.snap_manager.cleanup(responses);
@@ -798,13 +976,18 @@ impl Fsm for SelectToolFsmState {
 				(ResizingBounds, DragStop { .. } | Enter) => {
 					rerender_selected_layers(tool_data, responses);
 
-					let response = match input.mouse.position.distance(tool_data.drag_start) < 10. * f64::EPSILON {
+					let response = match tool_data.typing.digits.is_empty() && input.mouse.position.distance(tool_data.drag_start) < 10. * f64::EPSILON {
 						true => DocumentMessage::Undo,
-						false => DocumentMessage::CommitTransaction,
+						false => {
+							let viewport_transform = std::mem::replace(&mut tool_data.bounds_transform, DAffine2::IDENTITY);
+							responses.add(DocumentMessage::RecordSelectionTransform { viewport_transform });
+							DocumentMessage::CommitTransaction
+						}
 					};
 					responses.add(response);
 
 					tool_data.snap_manager.cleanup(responses);
+					tool_data.typing.clear();
 
 					if let Some(bounds) = &mut tool_data.bounding_box_overlays {
 						bounds.original_transforms.clear();
@@ -815,11 +998,16 @@ impl Fsm for SelectToolFsmState {
 				(RotatingBounds, DragStop { .. } | Enter) => {
 					rerender_selected_layers(tool_data, responses);
 
-					let response = match input.mouse.position.distance(tool_data.drag_start) < 10. * f64::EPSILON {
+					let response = match tool_data.typing.digits.is_empty() && input.mouse.position.distance(tool_data.drag_start) < 10. * f64::EPSILON {
 						true => DocumentMessage::Undo,
-						false => DocumentMessage::CommitTransaction,
+						false => {
+							let viewport_transform = std::mem::replace(&mut tool_data.bounds_transform, DAffine2::IDENTITY);
+							responses.add(DocumentMessage::RecordSelectionTransform { viewport_transform });
+							DocumentMessage::CommitTransaction
+						}
 					};
 					responses.add(response);
+					tool_data.typing.clear();
 
 					if let Some(bounds) = &mut tool_data.bounding_box_overlays {
 						bounds.original_transforms.clear();
@@ -838,6 +1026,17 @@ impl Fsm for SelectToolFsmState {
 
 					Ready
 				}
+				(DraggingCornerRadius, DragStop { .. } | Enter) => {
+					let response = match input.mouse.position.distance(tool_data.drag_start) < 10. * f64::EPSILON {
+						true => DocumentMessage::Undo,
+						false => DocumentMessage::CommitTransaction,
+					};
+					responses.add(response);
+
+					tool_data.corner_radius.stop_drag();
+
+					Ready
+				}
 				(DrawingBox, DragStop { .. } | Enter) => {
 					let quad = tool_data.selection_quad();
 					// For shallow select we don't update dragging layers until inside drag_start_shallowest_manipulation()
@@ -874,16 +1073,26 @@ impl Fsm for SelectToolFsmState {
 				}
 				(Dragging, Abort) => {
 					rerender_selected_layers(tool_data, responses);
+					tool_data.typing.clear();
 
 					tool_data.snap_manager.cleanup(responses);
 					responses.add(DocumentMessage::Undo);
 
 					tool_data.path_outlines.clear_selected(responses);
 					tool_data.pivot.clear_overlays(responses);
+					tool_data.corner_radius.clear_overlays(responses);
+
+					Ready
+				}
+				(DraggingCornerRadius, Abort) => {
+					tool_data.corner_radius.stop_drag();
+					tool_data.corner_radius.clear_overlays(responses);
+					responses.add(DocumentMessage::AbortTransaction);
 
 					Ready
 				}
 				(_, Abort) => {
+					tool_data.typing.clear();
 					if let Some(path) = tool_data.drag_box_overlay_layer.take() {
 						responses.add_front(DocumentMessage::Overlays(Operation::DeleteLayer { path }.into()))
 					};
@@ -907,10 +1116,17 @@ impl Fsm for SelectToolFsmState {
 					tool_data.path_outlines.clear_hovered(responses);
 					tool_data.path_outlines.clear_selected(responses);
 					tool_data.pivot.clear_overlays(responses);
+					tool_data.corner_radius.clear_overlays(responses);
 
 					tool_data.snap_manager.cleanup(responses);
 					Ready
 				}
+				// The layer under the pointer is no longer hovered once the pointer leaves for another panel
+				(Ready, PointerLeave) => {
+					tool_data.path_outlines.clear_hovered(responses);
+
+					self
+				}
 				(_, Align { axis, aggregate }) => {
 					responses.add(DocumentMessage::AlignSelectedLayers { axis, aggregate });
 
@@ -934,6 +1150,26 @@ impl Fsm for SelectToolFsmState {
 
 					self
 				}
+				(Dragging | ResizingBounds | RotatingBounds, TypeBackspace) => {
+					let value = tool_data.typing.type_backspace();
+					tool_data.apply_typed_value(self, value, document, input, responses);
+					self
+				}
+				(Dragging | ResizingBounds | RotatingBounds, TypeDecimalPoint) => {
+					let value = tool_data.typing.type_decimal_point();
+					tool_data.apply_typed_value(self, value, document, input, responses);
+					self
+				}
+				(Dragging | ResizingBounds | RotatingBounds, TypeDigit { digit }) => {
+					let value = tool_data.typing.type_number(digit);
+					tool_data.apply_typed_value(self, value, document, input, responses);
+					self
+				}
+				(Dragging | ResizingBounds | RotatingBounds, TypeNegate) => {
+					let value = tool_data.typing.type_negate();
+					tool_data.apply_typed_value(self, value, document, input, responses);
+					self
+				}
 				_ => self,
 			}
 		} else {
@@ -944,6 +1180,17 @@ impl Fsm for SelectToolFsmState {
 	fn standard_tool_messages(&self, message: &ToolMessage, responses: &mut VecDeque<Message>, tool_data: &mut Self::ToolData) -> bool {
 		// Check for standard hits or cursor events
 		match message {
+			ToolMessage::UpdateHints if matches!(self, SelectToolFsmState::QuickMask | SelectToolFsmState::PaintingQuickMask) => {
+				let hint_data = HintData(vec![
+					HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Paint Mask")]),
+					HintGroup(vec![HintInfo::keys([Key::BracketLeft, Key::BracketRight], "Shrink/Grow Brush")]),
+					HintGroup(vec![HintInfo::keys([Key::Enter], "Select Masked")]),
+					HintGroup(vec![HintInfo::keys([Key::Escape], "Discard Mask")]),
+				]);
+
+				responses.add(FrontendMessage::UpdateInputHints { hint_data });
+				true
+			}
 			ToolMessage::UpdateHints => {
 				let hint_data = HintData(vec![
 					HintGroup(vec![HintInfo::mouse(MouseMotion::LmbDrag, "Drag Selected")]),
@@ -969,6 +1216,7 @@ impl Fsm for SelectToolFsmState {
 						HintInfo::keys_and_mouse([Key::Alt], MouseMotion::LmbDrag, "Move Duplicate"),
 						HintInfo::keys([Key::Control, Key::KeyD], "Duplicate").add_mac_keys([Key::Command, Key::KeyD]),
 					]),
+					HintGroup(vec![HintInfo::keys([Key::KeyQ], "Quick Mask")]),
 				]);
 
 				responses.add(FrontendMessage::UpdateInputHints { hint_data });
@@ -983,7 +1231,10 @@ impl Fsm for SelectToolFsmState {
 		}
 	}
 
-	fn update_hints(&self, _responses: &mut VecDeque<Message>) {}
+	fn update_hints(&self, responses: &mut VecDeque<Message>) {
+		// Entering or leaving the quick mask changes the hints, which are otherwise made from the tool options in `standard_tool_messages`
+		responses.add(ToolMessage::UpdateHints);
+	}
 
 	fn update_cursor(&self, responses: &mut VecDeque<Message>) {
 		responses.add(FrontendMessage::UpdateMouseCursor { cursor: MouseCursorIcon::Default });
@@ -179,6 +179,7 @@ impl ToolTransition for SplineTool {
 	fn event_to_message_map(&self) -> EventToMessageMap {
 		EventToMessageMap {
 			tool_abort: Some(SplineToolMessage::Abort.into()),
+			tool_confirm: Some(SplineToolMessage::Confirm.into()),
 			working_color_changed: Some(SplineToolMessage::WorkingColorChanged.into()),
 			..Default::default()
 		}
@@ -191,6 +192,8 @@ struct SplineToolData {
 	next_point: DVec2,
 	weight: f64,
 	path: Option<Vec<LayerId>>,
+	/// Where the layer is inserted in its folder each time the spline is redrawn
+	insert_index: isize,
 	snap_manager: SnapManager,
 }
 
@@ -223,6 +226,7 @@ impl Fsm for SplineToolFsmState {
 					responses.add(DocumentMessage::StartTransaction);
 					responses.add(DocumentMessage::DeselectAllLayers);
 					tool_data.path = Some(document.get_path_for_new_layer());
+					tool_data.insert_index = document.get_insert_index_for_new_layer();
 
 					tool_data.snap_manager.start_snap(document, input, document.bounding_boxes(None, None, render_data), true, true);
 					tool_data.snap_manager.add_all_document_handles(document, input, &[], &[], &[]);
@@ -328,7 +332,7 @@ fn add_spline(tool_data: &SplineToolData, show_preview: bool, fill_color: Option
 
 	let layer_path = tool_data.path.clone().unwrap();
 	let manipulator_groups = subpath.manipulator_groups().to_vec();
-	graph_modification_utils::new_vector_layer(vec![subpath], layer_path.clone(), responses);
+	graph_modification_utils::new_vector_layer_at(vec![subpath], layer_path.clone(), tool_data.insert_index, responses);
 	graph_modification_utils::set_manipulator_mirror_angle(&manipulator_groups, &layer_path, true, responses);
 
 	responses.add(GraphOperationMessage::FillSet {
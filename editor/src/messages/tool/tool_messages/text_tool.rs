@@ -1,13 +1,13 @@
 #![allow(clippy::too_many_arguments)]
 
 use crate::application::generate_uuid;
-use crate::consts::{COLOR_ACCENT, SELECTION_TOLERANCE};
 use crate::messages::frontend::utility_types::MouseCursorIcon;
-use crate::messages::input_mapper::utility_types::input_keyboard::{Key, MouseMotion};
+use crate::messages::input_mapper::utility_types::input_keyboard::{Key, KeysGroup, MouseMotion};
 use crate::messages::layout::utility_types::widget_prelude::*;
 use crate::messages::portfolio::document::node_graph::new_text_network;
 use crate::messages::prelude::*;
 use crate::messages::tool::common_functionality::color_selector::{ToolColorOptions, ToolColorType};
+use crate::messages::tool::common_functionality::hit_targets::pointer_quad;
 use crate::messages::tool::utility_types::{EventToMessageMap, Fsm, ToolActionHandlerData, ToolMetadata, ToolTransition, ToolType};
 use crate::messages::tool::utility_types::{HintData, HintGroup, HintInfo};
 
@@ -62,9 +62,16 @@ pub enum TextToolMessage {
 	WorkingColorChanged,
 
 	// Tool-specific messages
+	AdjustKerning {
+		delta: f32,
+	},
 	CommitText,
 	EditSelected,
 	Interact,
+	SelectKerningPair {
+		delta: i32,
+	},
+	StartKerning,
 	TextChange {
 		new_text: String,
 	},
@@ -187,12 +194,19 @@ impl<'a> MessageHandler<ToolMessage, &mut ToolActionHandlerData<'a>> for TextToo
 		match self.fsm_state {
 			Ready => actions!(TextToolMessageDiscriminant;
 				Interact,
+				StartKerning,
 			),
 			Editing => actions!(TextToolMessageDiscriminant;
 				Interact,
 				Abort,
 				CommitText,
 			),
+			Kerning => actions!(TextToolMessageDiscriminant;
+				Interact,
+				Abort,
+				AdjustKerning,
+				SelectKerningPair,
+			),
 		}
 	}
 }
@@ -204,6 +218,7 @@ impl ToolTransition for TextTool {
 			tool_abort: Some(TextToolMessage::Abort.into()),
 			selection_changed: Some(TextToolMessage::DocumentIsDirty.into()),
 			working_color_changed: Some(TextToolMessage::WorkingColorChanged.into()),
+			..Default::default()
 		}
 	}
 }
@@ -213,12 +228,15 @@ enum TextToolFsmState {
 	#[default]
 	Ready,
 	Editing,
+	/// Adjusting the spacing between the pairs of characters of the selected text layer, one pair at a time.
+	Kerning,
 }
 #[derive(Clone, Debug)]
 pub struct EditingText {
 	text: String,
 	font: Font,
 	font_size: f64,
+	kerning: Vec<f32>,
 	color: Option<Color>,
 	transform: DAffine2,
 }
@@ -226,9 +244,15 @@ pub struct EditingText {
 #[derive(Clone, Debug, Default)]
 struct TextToolData {
 	layer_path: Vec<LayerId>,
+	/// Where a new text layer is inserted in its folder
+	insert_index: isize,
 	overlays: Vec<Vec<LayerId>>,
 	editing_text: Option<EditingText>,
 	new_text: String,
+	/// The character whose spacing to the next one is being kerned.
+	kerning_pair: usize,
+	/// The overlay marking the gap between the kerned pair of characters.
+	kerning_marker: Option<Vec<LayerId>>,
 }
 
 impl TextToolData {
@@ -263,11 +287,12 @@ impl TextToolData {
 		let node_id = get_text_node_id(network)?;
 		let node = network.nodes.get(&node_id)?;
 
-		let (text, font, font_size) = Self::extract_text_node_inputs(node)?;
+		let (text, font, font_size, kerning) = Self::extract_text_node_inputs(node)?;
 		self.editing_text = Some(EditingText {
 			text: text.clone(),
 			font: font.clone(),
 			font_size,
+			kerning: kerning.to_vec(),
 			color: Some(color),
 			transform,
 		});
@@ -291,7 +316,83 @@ impl TextToolData {
 		responses.add(DocumentMessage::SetSelectedLayers { replacement_selected_layers });
 	}
 
-	fn extract_text_node_inputs(node: &DocumentNode) -> Option<(&String, &Font, f64)> {
+	/// The gaps between each pair of characters on the same line of the layer's text, which can be kerned, in the layer's space.
+	fn kerning_gaps(&self, document: &DocumentMessageHandler, render_data: &RenderData) -> Option<Vec<(usize, DVec2, f64)>> {
+		let network = get_network(&self.layer_path, document)?;
+		let node = network.nodes.get(&get_text_node_id(network)?)?;
+		let (text, font, font_size, kerning) = Self::extract_text_node_inputs(node)?;
+		let buzz_face = render_data.font_cache.get(font).map(|data| load_face(data));
+
+		let characters = text.chars().collect::<Vec<_>>();
+		let mut gaps = graphene_core::text::kerning_gaps(text, buzz_face, font_size, kerning);
+		gaps.retain(|&(index, ..)| characters.get(index + 1).map_or(false, |&next| next != '\n'));
+		Some(gaps)
+	}
+
+	/// Draws the marker between the pair of characters being kerned, returning `None` if the text has no such pair.
+	fn update_kerning_marker(&mut self, document: &DocumentMessageHandler, render_data: &RenderData, responses: &mut VecDeque<Message>) -> Option<()> {
+		let gaps = self.kerning_gaps(document, render_data)?;
+		let &(_, position, line_height) = gaps.iter().find(|(index, ..)| *index == self.kerning_pair)?;
+		let transform = document.document_legacy.multiply_transforms(&self.layer_path).ok()?;
+
+		let marker = match &self.kerning_marker {
+			Some(marker) => marker.clone(),
+			None => {
+				let path = vec![generate_uuid()];
+				let operation = Operation::AddRect {
+					path: path.clone(),
+					transform: DAffine2::ZERO.to_cols_array(),
+					style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().selection_outline), 1.0)), Fill::None),
+					insert_index: -1,
+				};
+				responses.add(DocumentMessage::Overlays(operation.into()));
+				self.kerning_marker = Some(path.clone());
+				path
+			}
+		};
+
+		let bounds = (transform * Quad::from_box([position, position + DVec2::new(0., line_height)])).bounding_box();
+		let operation = Operation::SetLayerTransformInViewport {
+			path: marker,
+			transform: transform_from_box(bounds[0], bounds[1]),
+		};
+		responses.add(DocumentMessage::Overlays(operation.into()));
+		Some(())
+	}
+
+	fn remove_kerning_marker(&mut self, responses: &mut VecDeque<Message>) {
+		if let Some(path) = self.kerning_marker.take() {
+			responses.add(DocumentMessage::Overlays(Operation::DeleteLayer { path }.into()));
+		}
+	}
+
+	/// Widens (or with a negative amount, narrows) the gap between the pair of characters being kerned, in thousandths of an em.
+	fn adjust_kerning(&self, delta: f32, document: &DocumentMessageHandler, responses: &mut VecDeque<Message>) -> Option<()> {
+		let network = get_network(&self.layer_path, document)?;
+		let node_id = get_text_node_id(network)?;
+		let (_, _, _, kerning) = Self::extract_text_node_inputs(network.nodes.get(&node_id)?)?;
+
+		let mut kerning = kerning.to_vec();
+		if kerning.len() <= self.kerning_pair {
+			kerning.resize(self.kerning_pair + 1, 0.);
+		}
+		kerning[self.kerning_pair] += delta;
+		// The pairs after the last adjusted one don't need to be stored
+		while kerning.last() == Some(&0.) {
+			kerning.pop();
+		}
+
+		responses.add(DocumentMessage::StartTransaction);
+		responses.add(NodeGraphMessage::SetQualifiedInputValue {
+			layer_path: self.layer_path.clone(),
+			node_path: vec![node_id],
+			input_index: 4,
+			value: TaggedValue::VecF32(kerning),
+		});
+		Some(())
+	}
+
+	fn extract_text_node_inputs(node: &DocumentNode) -> Option<(&String, &Font, f64, &[f32])> {
 		let NodeInput::Value {
 			tagged_value: TaggedValue::String(text),
 			..
@@ -306,19 +407,29 @@ impl TextToolData {
 		else {
 			return None;
 		};
-		let NodeInput::Value {
-			tagged_value: TaggedValue::F64(font_size),
-			..
-		} = &node.inputs[3]
-		else {
-			return None;
+		let font_size = match &node.inputs[3] {
+			NodeInput::Value {
+				tagged_value: TaggedValue::F32(font_size),
+				..
+			} => *font_size as f64,
+			NodeInput::Value {
+				tagged_value: TaggedValue::F64(font_size),
+				..
+			} => *font_size,
+			_ => return None,
+		};
+		let kerning = match node.inputs.get(4) {
+			Some(NodeInput::Value {
+				tagged_value: TaggedValue::VecF32(kerning),
+				..
+			}) => kerning.as_slice(),
+			_ => &[],
 		};
-		Some((text, font, *font_size))
+		Some((text, font, font_size, kerning))
 	}
 
 	fn interact(&mut self, state: TextToolFsmState, mouse: DVec2, document: &DocumentMessageHandler, render_data: &RenderData, responses: &mut VecDeque<Message>) -> TextToolFsmState {
-		let tolerance = DVec2::splat(SELECTION_TOLERANCE);
-		let quad = Quad::from_box([mouse - tolerance, mouse + tolerance]);
+		let quad = pointer_quad(mouse);
 
 		// Check if the user has selected an existing text layer
 		if let Some(clicked_text_layer_path) = document.document_legacy.intersects_quad_root(quad, render_data).last().filter(|l| is_text_layer(document, l)) {
@@ -334,7 +445,7 @@ impl TextToolData {
 
 			responses.add(Operation::AddFrame {
 				path: self.layer_path.clone(),
-				insert_index: -1,
+				insert_index: self.insert_index,
 				transform: DAffine2::ZERO.to_cols_array(),
 				network,
 			});
@@ -371,7 +482,7 @@ impl TextToolData {
 
 		let editing_text = self.editing_text.as_ref()?;
 		let buzz_face = render_data.font_cache.get(&editing_text.font).map(|data| load_face(data));
-		let far = graphene_core::text::bounding_box(&self.new_text, buzz_face, editing_text.font_size, None);
+		let far = graphene_core::text::bounding_box(&self.new_text, buzz_face, editing_text.font_size, None, &editing_text.kerning);
 		let quad = Quad::from_box([DVec2::ZERO, far]);
 
 		let transformed_quad = document.document_legacy.multiply_transforms(&self.layer_path).ok()? * quad;
@@ -388,7 +499,7 @@ impl TextToolData {
 	fn get_bounds(&self, text: &str, render_data: &RenderData) -> Option<[DVec2; 2]> {
 		let editing_text = self.editing_text.as_ref()?;
 		let buzz_face = render_data.font_cache.get(&editing_text.font).map(|data| load_face(data));
-		let subpaths = graphene_core::text::to_path(text, buzz_face, editing_text.font_size, None);
+		let subpaths = graphene_core::text::to_path(text, buzz_face, editing_text.font_size, None, &editing_text.kerning);
 		let bounds = subpaths.iter().filter_map(|subpath| subpath.bounding_box());
 		let combined_bounds = bounds.reduce(|a, b| [a[0].min(b[0]), a[1].max(b[1])]).unwrap_or_default();
 		Some(combined_bounds)
@@ -420,7 +531,7 @@ fn resize_overlays(overlays: &mut Vec<Vec<LayerId>>, responses: &mut VecDeque<Me
 		let operation = Operation::AddRect {
 			path,
 			transform: DAffine2::ZERO.to_cols_array(),
-			style: style::PathStyle::new(Some(Stroke::new(Some(COLOR_ACCENT), 1.0)), Fill::None),
+			style: style::PathStyle::new(Some(Stroke::new(Some(overlay_colors().selection_outline), 1.0)), Fill::None),
 			insert_index: -1,
 		};
 		responses.add(DocumentMessage::Overlays(operation.into()));
@@ -432,9 +543,9 @@ fn update_overlays(document: &DocumentMessageHandler, tool_data: &mut TextToolDa
 		let node_graph = layer.as_layer_network().ok()?;
 		let node_id = get_text_node_id(node_graph)?;
 		let document_node = node_graph.nodes.get(&node_id)?;
-		let (text, font, font_size) = TextToolData::extract_text_node_inputs(document_node)?;
+		let (text, font, font_size, kerning) = TextToolData::extract_text_node_inputs(document_node)?;
 		let buzz_face = render_data.font_cache.get(font).map(|data| load_face(data));
-		let far = graphene_core::text::bounding_box(text, buzz_face, font_size, None);
+		let far = graphene_core::text::bounding_box(text, buzz_face, font_size, None, kerning);
 		let quad = Quad::from_box([DVec2::ZERO, far]);
 		let multiplied = document.document_legacy.multiply_transforms(path).ok()? * quad;
 		Some(multiplied.bounding_box())
@@ -510,21 +621,62 @@ impl Fsm for TextToolFsmState {
 					tool_data.update_bounds_overlay(document, render_data, responses);
 					TextToolFsmState::Editing
 				}
+				(TextToolFsmState::Kerning, TextToolMessage::DocumentIsDirty) => {
+					update_overlays(document, tool_data, responses, render_data);
+					if tool_data.update_kerning_marker(document, render_data, responses).is_none() {
+						// The text was changed or removed, leaving no pair to kern
+						tool_data.remove_kerning_marker(responses);
+						return TextToolFsmState::Ready;
+					}
+
+					TextToolFsmState::Kerning
+				}
 				(state, TextToolMessage::DocumentIsDirty) => {
 					update_overlays(document, tool_data, responses, render_data);
 
 					state
 				}
+				(TextToolFsmState::Ready, TextToolMessage::StartKerning) => {
+					let Some(layer_path) = can_edit_selected(document) else { return self };
+					tool_data.layer_path = layer_path;
+					tool_data.kerning_pair = 0;
+					if tool_data.update_kerning_marker(document, render_data, responses).is_none() {
+						return self;
+					}
+
+					TextToolFsmState::Kerning
+				}
+				(TextToolFsmState::Kerning, TextToolMessage::SelectKerningPair { delta }) => {
+					if let Some(gaps) = tool_data.kerning_gaps(document, render_data).filter(|gaps| !gaps.is_empty()) {
+						let current = gaps.iter().position(|&(index, ..)| index >= tool_data.kerning_pair).unwrap_or(0);
+						let next = (current as i32 + delta).clamp(0, gaps.len() as i32 - 1) as usize;
+						tool_data.kerning_pair = gaps[next].0;
+					}
+					tool_data.update_kerning_marker(document, render_data, responses);
+
+					TextToolFsmState::Kerning
+				}
+				(TextToolFsmState::Kerning, TextToolMessage::AdjustKerning { delta }) => {
+					tool_data.adjust_kerning(delta, document, responses);
+
+					TextToolFsmState::Kerning
+				}
 				(state, TextToolMessage::Interact) => {
+					// Clicking ends kerning, and otherwise works as it does before kerning was started
+					tool_data.remove_kerning_marker(responses);
+					let state = if state == TextToolFsmState::Kerning { TextToolFsmState::Ready } else { state };
+
 					tool_data.editing_text = Some(EditingText {
 						text: String::new(),
 						transform: DAffine2::from_translation(input.mouse.position),
 						font_size: tool_options.font_size as f64,
 						font: Font::new(tool_options.font_name.clone(), tool_options.font_style.clone()),
+						kerning: Vec::new(),
 						color: tool_options.fill.active_color(),
 					});
 					tool_data.new_text = String::new();
 					tool_data.layer_path = document.get_path_for_new_layer();
+					tool_data.insert_index = document.get_insert_index_for_new_layer();
 
 					tool_data.interact(state, input.mouse.position, document, render_data, responses)
 				}
@@ -540,6 +692,7 @@ impl Fsm for TextToolFsmState {
 					if state == TextToolFsmState::Editing {
 						tool_data.set_editing(false, render_data, responses);
 					}
+					tool_data.remove_kerning_marker(responses);
 
 					resize_overlays(&mut tool_data.overlays, responses, 0);
 
@@ -588,11 +741,25 @@ impl Fsm for TextToolFsmState {
 
 	fn update_hints(&self, responses: &mut VecDeque<Message>) {
 		let hint_data = match self {
-			TextToolFsmState::Ready => HintData(vec![HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Add Text"), HintInfo::mouse(MouseMotion::Lmb, "Edit Text")])]),
+			TextToolFsmState::Ready => HintData(vec![
+				HintGroup(vec![HintInfo::mouse(MouseMotion::Lmb, "Add Text"), HintInfo::mouse(MouseMotion::Lmb, "Edit Text")]),
+				HintGroup(vec![HintInfo::keys([Key::Alt, Key::KeyK], "Kern Selected Text")]),
+			]),
 			TextToolFsmState::Editing => HintData(vec![HintGroup(vec![
 				HintInfo::keys([Key::Control, Key::Enter], "Commit Edit").add_mac_keys([Key::Command, Key::Enter]),
 				HintInfo::keys([Key::Escape], "Discard Edit"),
 			])]),
+			TextToolFsmState::Kerning => HintData(vec![
+				HintGroup(vec![HintInfo {
+					key_groups: vec![KeysGroup(vec![Key::ArrowLeft]).into(), KeysGroup(vec![Key::ArrowRight]).into()],
+					..HintInfo::label("Select Pair")
+				}]),
+				HintGroup(vec![
+					HintInfo::keys([Key::Alt], "Tighten/Loosen Pair").prepend_plus(),
+					HintInfo::keys([Key::Shift], "Bigger Steps").prepend_plus(),
+				]),
+				HintGroup(vec![HintInfo::keys([Key::Escape], "Stop Kerning")]),
+			]),
 		};
 
 		responses.add(FrontendMessage::UpdateInputHints { hint_data });
@@ -36,6 +36,26 @@ impl TransformLayerMessageHandler {
 		};
 		self.transform_operation.hints(self.snap, axis_constraint, responses);
 	}
+
+	/// Names the transform in progress along with its axis constraint, like "Grab X", or `None` if not transforming.
+	pub fn operation_label(&self) -> Option<String> {
+		let (name, constraint) = match self.transform_operation {
+			TransformOperation::None => return None,
+			TransformOperation::Grabbing(grabbing) => ("Grab", grabbing.constraint),
+			TransformOperation::Rotating(_) => ("Rotate", Axis::Both),
+			TransformOperation::Scaling(scaling) => ("Scale", scaling.constraint),
+		};
+		Some(match constraint {
+			Axis::Both => name.to_string(),
+			Axis::X => format!("{name} X"),
+			Axis::Y => format!("{name} Y"),
+		})
+	}
+
+	/// The number typed so far to enter the transform in progress precisely.
+	pub fn typed_text(&self) -> Option<String> {
+		self.is_transforming().then(|| self.typing.text()).flatten()
+	}
 }
 
 type TransformData<'a> = (&'a DocumentMessageHandler, &'a InputPreprocessorMessageHandler, &'a RenderData<'a>, &'a ToolData, &'a mut ShapeState);
@@ -100,6 +120,12 @@ impl<'a> MessageHandler<TransformLayerMessage, TransformData<'a>> for TransformL
 			ApplyTransformOperation => {
 				selected.original_transforms.clear();
 
+				if !using_path_tool {
+					if let Some(viewport_transform) = self.transform_operation.transformation(self.snap) {
+						responses.add(DocumentMessage::RecordSelectionTransform { viewport_transform });
+					}
+				}
+
 				self.typing.clear();
 
 				self.transform_operation = TransformOperation::None;
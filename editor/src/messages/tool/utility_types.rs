@@ -1,4 +1,5 @@
 #![allow(clippy::too_many_arguments)]
+use super::common_functionality::color_selector::ColorSlot;
 use super::common_functionality::overlay_renderer::OverlayRenderer;
 use super::common_functionality::shape_editor::ShapeState;
 use super::tool_messages::*;
@@ -134,6 +135,8 @@ pub trait Fsm {
 pub struct DocumentToolData {
 	pub primary_color: Color,
 	pub secondary_color: Color,
+	/// Whether tools that recolor existing shapes, like the Fill tool, change their fill or their stroke.
+	pub active_slot: ColorSlot,
 }
 
 impl DocumentToolData {
@@ -170,8 +173,12 @@ impl DocumentToolData {
 #[derive(Clone, Debug, Default)]
 pub struct EventToMessageMap {
 	pub document_dirty: Option<ToolMessage>,
+	pub pointer_enter: Option<ToolMessage>,
+	pub pointer_leave: Option<ToolMessage>,
 	pub selection_changed: Option<ToolMessage>,
 	pub tool_abort: Option<ToolMessage>,
+	/// Sent when the user confirms the tool's current operation with Enter. Unlike the others, this isn't subscribed to a broadcast event.
+	pub tool_confirm: Option<ToolMessage>,
 	pub working_color_changed: Option<ToolMessage>,
 }
 
@@ -190,6 +197,8 @@ pub trait ToolTransition {
 
 		let event_to_tool_map = self.event_to_message_map();
 		subscribe_message(event_to_tool_map.document_dirty, BroadcastEvent::DocumentIsDirty);
+		subscribe_message(event_to_tool_map.pointer_enter, BroadcastEvent::PointerEnterViewport);
+		subscribe_message(event_to_tool_map.pointer_leave, BroadcastEvent::PointerLeaveViewport);
 		subscribe_message(event_to_tool_map.tool_abort, BroadcastEvent::ToolAbort);
 		subscribe_message(event_to_tool_map.selection_changed, BroadcastEvent::SelectionChanged);
 		subscribe_message(event_to_tool_map.working_color_changed, BroadcastEvent::WorkingColorChanged);
@@ -207,6 +216,8 @@ pub trait ToolTransition {
 
 		let event_to_tool_map = self.event_to_message_map();
 		unsubscribe_message(event_to_tool_map.document_dirty, BroadcastEvent::DocumentIsDirty);
+		unsubscribe_message(event_to_tool_map.pointer_enter, BroadcastEvent::PointerEnterViewport);
+		unsubscribe_message(event_to_tool_map.pointer_leave, BroadcastEvent::PointerLeaveViewport);
 		unsubscribe_message(event_to_tool_map.tool_abort, BroadcastEvent::ToolAbort);
 		unsubscribe_message(event_to_tool_map.selection_changed, BroadcastEvent::SelectionChanged);
 		unsubscribe_message(event_to_tool_map.working_color_changed, BroadcastEvent::WorkingColorChanged);
@@ -217,6 +228,9 @@ pub trait ToolMetadata {
 	fn icon_name(&self) -> String;
 	fn tooltip(&self) -> String;
 	fn tool_type(&self) -> ToolType;
+
+	/// Lets a tool describe the operation it has in progress, such as the measurements of a shape being drawn, in the tool status.
+	fn update_status(&self, _status: &mut ToolStatus) {}
 }
 
 pub struct ToolData {
@@ -317,6 +331,7 @@ impl Default for ToolFsmState {
 			document_tool_data: DocumentToolData {
 				primary_color: Color::BLACK,
 				secondary_color: Color::WHITE,
+				active_slot: ColorSlot::default(),
 			},
 		}
 	}
@@ -406,7 +421,17 @@ fn list_tools_in_groups() -> Vec<Vec<ToolAvailability>> {
 }
 
 pub fn tool_message_to_tool_type(tool_message: &ToolMessage) -> ToolType {
-	match tool_message {
+	try_tool_message_to_tool_type(tool_message).unwrap_or_else(|| {
+		panic!(
+			"Conversion from ToolMessage to ToolType impossible because the given ToolMessage does not have a matching ToolType. Got: {:?}",
+			tool_message
+		)
+	})
+}
+
+/// The tool that a message belongs to, or `None` for messages that aren't specific to one tool.
+pub fn try_tool_message_to_tool_type(tool_message: &ToolMessage) -> Option<ToolType> {
+	let tool_type = match tool_message {
 		// General tool group
 		ToolMessage::Select(_) => ToolType::Select,
 		ToolMessage::Artboard(_) => ToolType::Artboard,
@@ -435,11 +460,9 @@ pub fn tool_message_to_tool_type(tool_message: &ToolMessage) -> ToolType {
 		// ToolMessage::Relight(_) => ToolType::Relight,
 		ToolMessage::Imaginate(_) => ToolType::Imaginate,
 		ToolMessage::Frame(_) => ToolType::Frame,
-		_ => panic!(
-			"Conversion from ToolMessage to ToolType impossible because the given ToolMessage does not have a matching ToolType. Got: {:?}",
-			tool_message
-		),
-	}
+		_ => return None,
+	};
+	Some(tool_type)
 }
 
 pub fn tool_type_to_activate_tool_message(tool_type: ToolType) -> ToolMessageDiscriminant {
@@ -479,6 +502,22 @@ pub fn tool_type_to_activate_tool_message(tool_type: ToolType) -> ToolMessageDis
 	}
 }
 
+/// What the user is doing with the tools right now, shown in the corner of the viewport.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub struct ToolStatus {
+	/// The name of the active tool, which is empty when no tool is active.
+	pub tool: String,
+	/// The labels of the modifier keys being held down.
+	pub modifiers: Vec<String>,
+	/// The modal transform in progress along with its axis constraint, like "Grab X".
+	pub operation: Option<String>,
+	/// The number typed so far to enter the modal transform precisely.
+	#[serde(rename = "typedValue")]
+	pub typed_value: Option<String>,
+	/// The live measurements of what is being drawn, like the length and angle of a line.
+	pub measurement: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
 pub struct HintData(pub Vec<HintGroup>);
 
@@ -0,0 +1,9 @@
+mod tutorial_message;
+mod tutorial_message_handler;
+
+pub mod utility_types;
+
+#[doc(inline)]
+pub use tutorial_message::{TutorialMessage, TutorialMessageDiscriminant};
+#[doc(inline)]
+pub use tutorial_message_handler::TutorialMessageHandler;
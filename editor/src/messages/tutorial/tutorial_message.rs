@@ -0,0 +1,14 @@
+use super::utility_types::Tutorial;
+use crate::messages::prelude::*;
+
+use serde::{Deserialize, Serialize};
+
+#[remain::sorted]
+#[impl_message(Message, Tutorial)]
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub enum TutorialMessage {
+	// Messages
+	Exit,
+	NextStep,
+	Start { tutorial: Tutorial },
+}
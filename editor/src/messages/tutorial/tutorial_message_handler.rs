@@ -0,0 +1,103 @@
+use super::utility_types::{Tutorial, TutorialStep, TutorialStepDetails};
+use crate::messages::prelude::*;
+
+/// Guides the user through a tutorial one step at a time. While a tutorial is running, only the actions its current step needs can be triggered
+/// from the keyboard and mouse, and the dispatcher moves on to the next step once it processes the message completing the current one.
+#[derive(Debug, Clone, Default)]
+pub struct TutorialMessageHandler {
+	/// The running tutorial and the index of its current step
+	active: Option<(Tutorial, usize)>,
+}
+
+impl TutorialMessageHandler {
+	fn current_step(&self) -> Option<&'static TutorialStep> {
+		self.active.and_then(|(tutorial, step)| tutorial.steps().get(step))
+	}
+
+	/// Whether processing this message completes the current step of the running tutorial.
+	pub fn completes_step(&self, message: &Message) -> bool {
+		self.current_step().map_or(false, |step| (step.completed_by)(message))
+	}
+
+	/// Leaves out the actions the current step of the running tutorial doesn't allow, or passes them all through when no tutorial is running.
+	pub fn filter_actions(&self, actions: ActionList) -> ActionList {
+		let Some(step) = self.current_step() else { return actions };
+
+		actions
+			.into_iter()
+			.map(|group| group.into_iter().filter(|&action| step.allows(action)).collect::<Vec<_>>())
+			.filter(|group| !group.is_empty())
+			.collect()
+	}
+
+	fn step_details(&self) -> Option<TutorialStepDetails> {
+		let (tutorial, index) = self.active?;
+		let step = tutorial.steps().get(index)?;
+
+		Some(TutorialStepDetails {
+			tutorial: tutorial.name().into(),
+			title: step.title.into(),
+			instructions: step.instructions.into(),
+			step_number: index + 1,
+			step_count: tutorial.steps().len(),
+		})
+	}
+}
+
+impl MessageHandler<TutorialMessage, ()> for TutorialMessageHandler {
+	#[remain::check]
+	fn process_message(&mut self, message: TutorialMessage, responses: &mut VecDeque<Message>, _data: ()) {
+		#[remain::sorted]
+		match message {
+			TutorialMessage::Exit => {
+				self.active = None;
+			}
+			TutorialMessage::NextStep => {
+				if let Some((tutorial, step)) = self.active {
+					// Finishing the last step ends the tutorial
+					self.active = (step + 1 < tutorial.steps().len()).then_some((tutorial, step + 1));
+				}
+			}
+			TutorialMessage::Start { tutorial } => {
+				self.active = Some((tutorial, 0));
+			}
+		}
+
+		responses.add(FrontendMessage::UpdateTutorialStep { step: self.step_details() });
+	}
+
+	fn actions(&self) -> ActionList {
+		actions!(TutorialMessageDiscriminant;)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn steps_allow_only_their_actions() {
+		let mut handler = TutorialMessageHandler::default();
+		let actions: ActionList = vec![vec![
+			MessageDiscriminant::Tool(ToolMessageDiscriminant::ActivateToolRectangle),
+			MessageDiscriminant::Tool(ToolMessageDiscriminant::ActivateToolSelect),
+		]];
+		assert_eq!(handler.filter_actions(actions.clone()), actions);
+
+		handler.process_message(TutorialMessage::Start { tutorial: Tutorial::Basics }, &mut VecDeque::new(), ());
+		handler.process_message(TutorialMessage::NextStep, &mut VecDeque::new(), ());
+		assert_eq!(handler.filter_actions(actions), vec![vec![MessageDiscriminant::Tool(ToolMessageDiscriminant::ActivateToolRectangle)]]);
+	}
+
+	#[test]
+	fn finishing_the_last_step_ends_the_tutorial() {
+		let mut handler = TutorialMessageHandler::default();
+		handler.process_message(TutorialMessage::Start { tutorial: Tutorial::Basics }, &mut VecDeque::new(), ());
+		for _ in Tutorial::Basics.steps() {
+			assert!(handler.step_details().is_some());
+			handler.process_message(TutorialMessage::NextStep, &mut VecDeque::new(), ());
+		}
+		assert!(handler.step_details().is_none());
+		assert!(!handler.completes_step(&DocumentMessage::Undo.into()));
+	}
+}
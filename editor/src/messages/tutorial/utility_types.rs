@@ -0,0 +1,94 @@
+use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ToolType;
+
+use serde::{Deserialize, Serialize};
+
+/// Actions which stay available throughout every tutorial, so the user can still open dialogs, zoom and pan, and reach the debugging tools.
+/// These and the allowed actions of the steps are prefixes of the dotted names of the actions, like "Tool.Rectangle" for all of the Rectangle tool's.
+const ALWAYS_ALLOWED_ACTIONS: &[&str] = &["InputPreprocessor", "KeyMapping", "Dialog", "Debug", "Portfolio.Document.Navigation"];
+
+/// The guided tours which can be taken through the editor from the Help menu.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Tutorial {
+	Basics,
+}
+
+impl Tutorial {
+	pub fn name(self) -> &'static str {
+		match self {
+			Tutorial::Basics => "The Basics",
+		}
+	}
+
+	pub fn steps(self) -> &'static [TutorialStep] {
+		match self {
+			Tutorial::Basics => BASICS,
+		}
+	}
+}
+
+/// One step of a tutorial, which asks the user to do something and waits until the editor processes the message which does it.
+pub struct TutorialStep {
+	pub title: &'static str,
+	pub instructions: &'static str,
+	/// The actions the keyboard and mouse can trigger during this step, besides those which are always allowed.
+	pub allowed_actions: &'static [&'static str],
+	/// Whether the message, when processed, completes this step.
+	pub completed_by: fn(&Message) -> bool,
+}
+
+impl TutorialStep {
+	pub fn allows(&self, action: MessageDiscriminant) -> bool {
+		let name = action.local_name();
+		ALWAYS_ALLOWED_ACTIONS.iter().chain(self.allowed_actions).any(|&prefix| {
+			// Match whole segments of the name, so "Tool.Select" doesn't allow "Tool.SelectAll"
+			name == prefix || name.strip_prefix(prefix).map_or(false, |rest| rest.starts_with('.'))
+		})
+	}
+}
+
+const BASICS: &[TutorialStep] = &[
+	TutorialStep {
+		title: "Create a document",
+		instructions: "Everything is drawn in a document. Start a new one with File > New… or Ctrl+N, then confirm its size in the dialog.",
+		allowed_actions: &[],
+		completed_by: |message| matches!(message, Message::Portfolio(PortfolioMessage::NewDocumentWithName { .. })),
+	},
+	TutorialStep {
+		title: "Pick the Rectangle tool",
+		instructions: "Tools are chosen from the shelf on the left of the canvas. Click the Rectangle tool there, or press M.",
+		allowed_actions: &["Tool.ActivateToolRectangle"],
+		completed_by: |message| matches!(message, Message::Tool(ToolMessage::ActivateTool { tool_type: ToolType::Rectangle })),
+	},
+	TutorialStep {
+		title: "Draw a rectangle",
+		instructions: "Drag on the canvas to draw a rectangle. Hold Shift to make it a square or Alt to draw it from its center.",
+		allowed_actions: &["Tool.Rectangle"],
+		completed_by: |message| matches!(message, Message::Tool(ToolMessage::Rectangle(RectangleToolMessage::DragStop))),
+	},
+	TutorialStep {
+		title: "Move it",
+		instructions: "Switch to the Select tool by clicking it in the shelf or pressing V, then drag the rectangle somewhere else.",
+		allowed_actions: &["Tool.ActivateToolSelect", "Tool.Select"],
+		completed_by: |message| matches!(message, Message::Tool(ToolMessage::Select(SelectToolMessage::DragStop { .. }))),
+	},
+	TutorialStep {
+		title: "Undo",
+		instructions: "Changed your mind? Undo the move with Edit > Undo or Ctrl+Z. That's the basics covered!",
+		allowed_actions: &["Portfolio.Document.Undo"],
+		completed_by: |message| matches!(message, Message::Portfolio(PortfolioMessage::Document(DocumentMessage::Undo))),
+	},
+];
+
+/// The step of the active tutorial shown to the user.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize, specta::Type)]
+pub struct TutorialStepDetails {
+	pub tutorial: String,
+	pub title: String,
+	pub instructions: String,
+	/// Counted from 1.
+	#[serde(rename = "stepNumber")]
+	pub step_number: usize,
+	#[serde(rename = "stepCount")]
+	pub step_count: usize,
+}
@@ -1,6 +1,8 @@
 mod workspace_message;
 mod workspace_message_handler;
 
+pub mod utility_types;
+
 #[doc(inline)]
 pub use workspace_message::{WorkspaceMessage, WorkspaceMessageDiscriminant};
 #[doc(inline)]
@@ -0,0 +1,83 @@
+use crate::messages::tool::utility_types::ToolType;
+
+use serde::{Deserialize, Serialize};
+
+/// The relative sizes of the subdivisions of the workspace's panel grid, used as their flex-grow weights.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct PanelSizes {
+	pub content: f64,
+	pub document: f64,
+	pub graph: f64,
+	pub details: f64,
+	pub properties: f64,
+	pub layers: f64,
+}
+
+impl Default for PanelSizes {
+	fn default() -> Self {
+		Self {
+			content: 80.,
+			document: 80.,
+			graph: 20.,
+			details: 20.,
+			properties: 45.,
+			layers: 55.,
+		}
+	}
+}
+
+/// Which panels are open, how large they are, and which tool is active, so the workspace can be restored the next time the editor starts.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct WorkspaceLayout {
+	#[serde(rename = "nodeGraphVisible")]
+	pub node_graph_visible: bool,
+	#[serde(rename = "detailsVisible")]
+	pub details_visible: bool,
+	#[serde(rename = "panelSizes")]
+	pub panel_sizes: PanelSizes,
+	#[serde(rename = "activeTool")]
+	pub active_tool: ToolType,
+}
+
+impl Default for WorkspaceLayout {
+	fn default() -> Self {
+		WorkspacePreset::Design.layout(ToolType::default())
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, specta::Type)]
+pub enum WorkspacePreset {
+	/// The Properties and Layers panels alongside the document, with the node graph kept small
+	#[default]
+	Design,
+	/// Everything open, with extra room given to the node graph
+	Debug,
+	/// Only the document, to leave as much room as possible for the artwork
+	Minimal,
+}
+
+impl WorkspacePreset {
+	/// The panel arrangement of this preset, which keeps the current tool active.
+	pub fn layout(self, active_tool: ToolType) -> WorkspaceLayout {
+		let (node_graph_visible, details_visible, panel_sizes) = match self {
+			WorkspacePreset::Design => (true, true, PanelSizes::default()),
+			WorkspacePreset::Debug => (
+				true,
+				true,
+				PanelSizes {
+					document: 55.,
+					graph: 45.,
+					..Default::default()
+				},
+			),
+			WorkspacePreset::Minimal => (false, false, PanelSizes::default()),
+		};
+
+		WorkspaceLayout {
+			node_graph_visible,
+			details_visible,
+			panel_sizes,
+			active_tool,
+		}
+	}
+}
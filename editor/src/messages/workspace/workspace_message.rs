@@ -1,11 +1,17 @@
+use super::utility_types::{PanelSizes, WorkspaceLayout, WorkspacePreset};
 use crate::messages::prelude::*;
+use crate::messages::tool::utility_types::ToolType;
 
 use serde::{Deserialize, Serialize};
 
 #[remain::sorted]
 #[impl_message(Message, Workspace)]
-#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
 pub enum WorkspaceMessage {
 	// Messages
+	ApplyPreset { preset: WorkspacePreset },
 	NodeGraphToggleVisibility,
+	RestoreLayout { layout: WorkspaceLayout },
+	SetActiveTool { tool_type: ToolType },
+	SetPanelSizes { panel_sizes: PanelSizes },
 }
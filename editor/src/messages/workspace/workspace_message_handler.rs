@@ -1,26 +1,55 @@
+use super::utility_types::WorkspaceLayout;
 use crate::messages::prelude::*;
 
 #[derive(Debug, Clone, Default)]
 pub struct WorkspaceMessageHandler {
-	node_graph_visible: bool,
+	layout: WorkspaceLayout,
 }
 
 impl MessageHandler<WorkspaceMessage, ()> for WorkspaceMessageHandler {
 	#[remain::check]
-	fn process_message(&mut self, message: WorkspaceMessage, _responses: &mut VecDeque<Message>, _data: ()) {
+	fn process_message(&mut self, message: WorkspaceMessage, responses: &mut VecDeque<Message>, _data: ()) {
 		use WorkspaceMessage::*;
 
 		#[remain::sorted]
 		match message {
 			// Messages
+			ApplyPreset { preset } => {
+				self.layout = preset.layout(self.layout.active_tool);
+
+				responses.add(FrontendMessage::UpdateWorkspaceLayout { layout: self.layout });
+			}
 			NodeGraphToggleVisibility => {
-				self.node_graph_visible = !self.node_graph_visible;
+				self.layout.node_graph_visible = !self.layout.node_graph_visible;
+
+				responses.add(FrontendMessage::UpdateWorkspaceLayout { layout: self.layout });
+			}
+			RestoreLayout { layout } => {
+				self.layout = layout;
+
+				responses.add(FrontendMessage::UpdateWorkspaceLayout { layout });
+				responses.add(ToolMessage::ActivateTool { tool_type: layout.active_tool });
+
+				// The restored layout came from the preferences, so it doesn't need to be stored again
+				return;
+			}
+			SetActiveTool { tool_type } => {
+				if self.layout.active_tool == tool_type {
+					return;
+				}
+				self.layout.active_tool = tool_type;
+			}
+			SetPanelSizes { panel_sizes } => {
+				self.layout.panel_sizes = panel_sizes;
 			}
 		}
+
+		responses.add(PreferencesMessage::WorkspaceLayout { layout: self.layout });
 	}
 
 	fn actions(&self) -> ActionList {
 		actions!(WorkspaceMessageDiscriminant;
+			ApplyPreset,
 			NodeGraphToggleVisibility,
 		)
 	}
@@ -443,19 +443,25 @@ impl NodeGraphExecutor {
 					new_thumbnails,
 				}) => {
 					self.thumbnails = new_thumbnails;
-					let node_graph_output = result.map_err(|e| format!("Node graph evaluation failed: {:?}", e))?;
-					let execution_context = self.futures.remove(&generation_id).ok_or_else(|| "Invalid generation ID".to_string())?;
-					responses.extend(updates);
-					self.process_node_graph_output(node_graph_output, execution_context.layer_path.clone(), transform, responses, execution_context.document_id)?;
-					responses.add(DocumentMessage::LayerChanged {
-						affected_layer_path: execution_context.layer_path,
+					let ExecutionContext { layer_path, document_id } = self.futures.remove(&generation_id).ok_or_else(|| "Invalid generation ID".to_string())?;
+					let processed = result.map_err(|e| format!("Node graph evaluation failed: {:?}", e)).and_then(|node_graph_output| {
+						responses.extend(updates);
+						self.process_node_graph_output(node_graph_output, layer_path.clone(), transform, responses, document_id)
 					});
-					responses.add(DocumentMessage::RenderDocument);
-					responses.add(ArtboardMessage::RenderArtboards);
-					responses.add(DocumentMessage::DocumentStructureChanged);
-					responses.add(BroadcastEvent::DocumentIsDirty);
-					responses.add(DocumentMessage::DirtyRenderDocument);
-					responses.add(DocumentMessage::Overlays(OverlaysMessage::Rerender));
+					if processed.is_ok() {
+						responses.add(DocumentMessage::LayerChanged {
+							affected_layer_path: layer_path.clone(),
+						});
+						responses.add(DocumentMessage::RenderDocument);
+						responses.add(ArtboardMessage::RenderArtboards);
+						responses.add(DocumentMessage::DocumentStructureChanged);
+						responses.add(BroadcastEvent::DocumentIsDirty);
+						responses.add(DocumentMessage::DirtyRenderDocument);
+						responses.add(DocumentMessage::Overlays(OverlaysMessage::Rerender));
+					}
+					// Whatever waits on the evaluation, like a batch, goes on after its output, even when it failed and there's none
+					responses.add(PortfolioMessage::NodeGraphEvaluated { document_id, layer_path });
+					processed?;
 				}
 				NodeGraphUpdate::NodeGraphUpdateMessage(NodeGraphUpdateMessage::ImaginateStatusUpdate) => {
 					responses.add(DocumentMessage::PropertiesPanel(PropertiesPanelMessage::ResendActiveProperties))
@@ -69,6 +69,8 @@ impl EditorTestUtils for Editor {
 			editor_position: (x2, y2).into(),
 			mouse_keys: MouseKeys::empty(),
 			scroll_delta: ScrollDelta::default(),
+			pressure: 0.,
+			time: 0.,
 		});
 	}
 
@@ -94,6 +96,8 @@ impl EditorTestUtils for Editor {
 			editor_position: (x, y).into(),
 			mouse_keys: MouseKeys::LEFT,
 			scroll_delta: ScrollDelta::default(),
+			pressure: 0.,
+			time: 0.,
 		});
 	}
 
@@ -17,6 +17,9 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+#[cfg(feature = "remote-control")]
+mod remote_control;
+
 static IMAGES: Mutex<Option<HashMap<String, FrontendImageData>>> = Mutex::new(None);
 thread_local! {
 	static EDITOR: RefCell<Option<Editor>> = RefCell::new(None);
@@ -67,10 +70,12 @@ async fn main() {
 	});
 
 	tauri::Builder::default()
-		.invoke_handler(tauri::generate_handler![set_random_seed, handle_message])
+		.invoke_handler(tauri::generate_handler![set_random_seed, launch_document_paths, read_document_file, handle_message])
 		.setup(|_app| {
 			use tauri::Manager;
 			_app.get_window("main").unwrap().open_devtools();
+			#[cfg(feature = "remote-control")]
+			remote_control::serve(_app.handle());
 			Ok(())
 		})
 		.run(tauri::generate_context!())
@@ -82,51 +87,67 @@ fn set_random_seed(seed: f64) {
 	graphite_editor::application::set_uuid_seed(seed);
 }
 
+/// The documents given as command line arguments, such as by opening a file with Graphite from the operating system.
+#[tauri::command]
+fn launch_document_paths() -> Vec<String> {
+	std::env::args().skip(1).filter(|argument| !argument.starts_with('-')).collect()
+}
+
+#[tauri::command]
+fn read_document_file(path: String) -> Result<String, String> {
+	std::fs::read_to_string(&path).map_err(|error| error.to_string())
+}
+
 #[tauri::command]
 fn handle_message(message: String) -> String {
 	let Ok(message) = ron::from_str::<graphite_editor::messages::message::Message>(&message) else {
 		panic!("Error parsing message: {}", message)
 	};
-	let responses = EDITOR.with(|editor| {
-		let mut editor = editor.borrow_mut();
-		editor.as_mut().unwrap().handle_message(message)
-	});
-
-	// Sends a FrontendMessage to JavaScript
-	fn send_frontend_message_to_js(message: FrontendMessage) -> FrontendMessage {
-		// Special case for update image data to avoid serialization times.
-		if let FrontendMessage::UpdateImageData { document_id, image_data } = message {
-			let mut guard = IMAGES.lock().unwrap();
-			let images = (*guard).as_mut().unwrap();
-			let mut stub_data = Vec::with_capacity(image_data.len());
-			for image in image_data {
-				let path = image.path.clone();
-				let mime = image.mime.clone();
-				let transform = image.transform;
-				images.insert(format!("{:?}_{}", &image.path, document_id), image);
-				stub_data.push(FrontendImageData {
-					path,
-					node_id: None,
-					mime,
-					image_data: Arc::new(Vec::new()),
-					transform,
-				});
-			}
-			FrontendMessage::UpdateImageData { document_id, image_data: stub_data }
-		} else {
-			message
-		}
-	}
+	let result = dispatch(message);
 
-	for response in &responses {
-		let serialized = ron::to_string(&send_frontend_message_to_js(response.clone())).unwrap();
+	for response in &result {
+		let serialized = ron::to_string(response).unwrap();
 		if let Err(error) = ron::from_str::<FrontendMessage>(&serialized) {
 			log::error!("Error deserializing message: {}", error);
 		}
 	}
 
+	ron::to_string(&result).expect("Failed to serialize FrontendMessage")
+}
+
+/// Has the editor handle the message, which must be done on the main thread where the editor lives, returning the `FrontendMessage` responses.
+fn dispatch(message: Message) -> Vec<FrontendMessage> {
+	let responses = EDITOR.with(|editor| {
+		let mut editor = editor.borrow_mut();
+		editor.as_mut().unwrap().handle_message(message)
+	});
+
 	// Process any `FrontendMessage` responses resulting from the backend processing the dispatched message
-	let result: Vec<_> = responses.into_iter().map(send_frontend_message_to_js).collect();
+	responses.into_iter().map(send_frontend_message_to_js).collect()
+}
 
-	ron::to_string(&result).expect("Failed to serialize FrontendMessage")
+// Sends a FrontendMessage to JavaScript
+fn send_frontend_message_to_js(message: FrontendMessage) -> FrontendMessage {
+	// Special case for update image data to avoid serialization times.
+	if let FrontendMessage::UpdateImageData { document_id, image_data } = message {
+		let mut guard = IMAGES.lock().unwrap();
+		let images = (*guard).as_mut().unwrap();
+		let mut stub_data = Vec::with_capacity(image_data.len());
+		for image in image_data {
+			let path = image.path.clone();
+			let mime = image.mime.clone();
+			let transform = image.transform;
+			images.insert(format!("{:?}_{}", &image.path, document_id), image);
+			stub_data.push(FrontendImageData {
+				path,
+				node_id: None,
+				mime,
+				image_data: Arc::new(Vec::new()),
+				transform,
+			});
+		}
+		FrontendMessage::UpdateImageData { document_id, image_data: stub_data }
+	} else {
+		message
+	}
 }
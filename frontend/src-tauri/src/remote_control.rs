@@ -0,0 +1,137 @@
+//! A WebSocket server, enabled by the `remote-control` feature, letting external tools and tests drive the editor programmatically,
+//! like opening a document, running operations on it, and requesting exports.
+//!
+//! Each text frame sent to the server is an editor `Message` serialized as JSON, which the editor handles as if it came from its own frontend.
+//! It's answered by a text frame holding the JSON array of the `FrontendMessage`s that resulted, or an object with an `error` describing why
+//! the message couldn't be handled. The same responses are sent to the editor's window, which shows the changes as it does for its own messages.
+//!
+//! The server only listens on the local machine, since whatever connects to it can do anything the editor can. For the same reason,
+//! connecting requires the session's token, given as the `token` query parameter (`ws://127.0.0.1:3002/?token=...`), and connections
+//! from web pages, which send an `Origin` header, are refused so a website open in a browser can't take control of the editor.
+
+use crate::dispatch;
+
+use graphite_editor::messages::prelude::*;
+
+use axum::extract::ws::{Message as Frame, WebSocket, WebSocketUpgrade};
+use axum::extract::Query;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+
+/// The address the server listens on, which can be changed by setting the `GRAPHITE_REMOTE_CONTROL_ADDRESS` environment variable.
+const DEFAULT_ADDRESS: &str = "127.0.0.1:3002";
+
+/// The event the responses to remote messages are sent to the editor's window with, as the RON the window's own messages are answered with.
+const RESPONSES_EVENT: &str = "remote-control-responses";
+
+/// Starts the server in the background.
+pub fn serve(app: AppHandle) {
+	let address = std::env::var("GRAPHITE_REMOTE_CONTROL_ADDRESS").unwrap_or_else(|_| DEFAULT_ADDRESS.to_string());
+	let Ok(address) = address.parse::<std::net::SocketAddr>() else {
+		log::error!("The remote control address {address} isn't a valid socket address");
+		return;
+	};
+	if !address.ip().is_loopback() {
+		log::warn!("The remote control server is listening on {address}, which lets other machines control the editor");
+	}
+
+	// The token can be chosen with the `GRAPHITE_REMOTE_CONTROL_TOKEN` environment variable by the tool launching the editor
+	let token: Arc<str> = std::env::var("GRAPHITE_REMOTE_CONTROL_TOKEN").unwrap_or_else(|_| random_token()).into();
+	log::info!("The remote control token for this session is {token}");
+
+	let router = Router::new().route(
+		"/",
+		get(move |upgrade: WebSocketUpgrade, headers: HeaderMap, Query(query): Query<HashMap<String, String>>| {
+			let (app, token) = (app.clone(), token.clone());
+			async move {
+				if let Err(refusal) = authorize(&headers, &query, &token) {
+					return refusal;
+				}
+				upgrade.on_upgrade(move |socket| connection(socket, app))
+			}
+		}),
+	);
+
+	tauri::async_runtime::spawn(async move {
+		log::info!("Remote control server listening on ws://{address}");
+		if let Err(error) = axum::Server::bind(&address).serve(router.into_make_service()).await {
+			log::error!("The remote control server stopped: {error}");
+		}
+	});
+}
+
+/// Refuses connections from web pages and connections that don't give the session's token.
+fn authorize(headers: &HeaderMap, query: &HashMap<String, String>, token: &str) -> Result<(), Response> {
+	if headers.contains_key(header::ORIGIN) {
+		return Err((StatusCode::FORBIDDEN, "Connections from web pages aren't accepted").into_response());
+	}
+	if query.get("token").map(String::as_str) != Some(token) {
+		return Err((StatusCode::UNAUTHORIZED, "The remote control token is missing or wrong").into_response());
+	}
+	Ok(())
+}
+
+/// A token nobody else can guess, made from the random keys the standard library seeds its hash maps with.
+fn random_token() -> String {
+	(0..4).map(|_| format!("{:016x}", RandomState::new().build_hasher().finish())).collect()
+}
+
+/// Answers each message sent over the connection in turn, until it's closed.
+async fn connection(mut socket: WebSocket, app: AppHandle) {
+	while let Some(Ok(frame)) = socket.recv().await {
+		let reply = match frame {
+			Frame::Text(text) => handle_remote_message(&app, &text).await,
+			Frame::Close(_) => break,
+			_ => continue,
+		};
+
+		if socket.send(Frame::Text(reply)).await.is_err() {
+			break;
+		}
+	}
+}
+
+/// Has the editor handle the JSON message on the main thread, where it lives, and serializes the responses as JSON. The responses are also
+/// sent to the editor's window so it shows the changes the message made.
+async fn handle_remote_message(app: &AppHandle, text: &str) -> String {
+	let message = match serde_json::from_str::<Message>(text) {
+		Ok(message) => message,
+		Err(error) => return error_reply(format!("The message couldn't be read: {error}")),
+	};
+
+	let (sender, receiver) = oneshot::channel();
+	let window_app = app.clone();
+	let queued = app.run_on_main_thread(move || {
+		let responses = dispatch(message);
+		match ron::to_string(&responses) {
+			Ok(window_responses) => {
+				if let Err(error) = window_app.emit_all(RESPONSES_EVENT, window_responses) {
+					log::error!("The responses to a remote message couldn't be sent to the window: {error}");
+				}
+			}
+			Err(error) => log::error!("The responses to a remote message couldn't be serialized for the window: {error}"),
+		}
+		let _ = sender.send(serde_json::to_string(&responses));
+	});
+	if let Err(error) = queued {
+		return error_reply(format!("The message couldn't be passed to the editor: {error}"));
+	}
+
+	match receiver.await {
+		Ok(Ok(responses)) => responses,
+		Ok(Err(error)) => error_reply(format!("The responses couldn't be written: {error}")),
+		Err(_) => error_reply("The editor didn't handle the message".to_string()),
+	}
+}
+
+fn error_reply(error: String) -> String {
+	serde_json::json!({ "error": error }).to_string()
+}
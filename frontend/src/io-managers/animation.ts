@@ -0,0 +1,18 @@
+import { type Editor } from "@graphite/wasm-communication/editor";
+import { TriggerAnimationFrame } from "@graphite/wasm-communication/messages";
+
+export function createAnimationManager(editor: Editor): void {
+	let scheduled = false;
+
+	// Subscribe to process backend event
+	editor.subscriptions.subscribeJsMessage(TriggerAnimationFrame, () => {
+		// The backend asks for another frame after each step of an animation that isn't finished, so only one frame needs to be waiting at a time
+		if (scheduled) return;
+		scheduled = true;
+
+		requestAnimationFrame(() => {
+			scheduled = false;
+			editor.instance.animateCanvas();
+		});
+	});
+}
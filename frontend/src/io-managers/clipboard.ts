@@ -1,6 +1,6 @@
 import { type Editor } from "@graphite/wasm-communication/editor";
-import { TriggerTextCopy } from "@graphite/wasm-communication/messages";
-import { imageToPNG } from "~src/utility-functions/rasterization";
+import { TriggerCopyRaster, TriggerTextCopy } from "@graphite/wasm-communication/messages";
+import { imageToPNG, rasterizeSVG } from "~src/utility-functions/rasterization";
 
 export function createClipboardManager(editor: Editor): void {
 	// Subscribe to process backend event
@@ -8,6 +8,13 @@ export function createClipboardManager(editor: Editor): void {
 		// If the Clipboard API is supported in the browser, copy text to the clipboard
 		navigator.clipboard?.writeText?.(triggerTextCopy.copyText);
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerCopyRaster, async (triggerCopyRaster) => {
+		const { svg, size } = triggerCopyRaster;
+
+		// The Clipboard API only accepts PNG images, so the artwork is always rasterized to one
+		const blob = await rasterizeSVG(svg, size.x, size.y, "image/png");
+		navigator.clipboard?.write?.([new ClipboardItem({ [blob.type]: blob })]);
+	});
 }
 
 export async function copyToClipboardFileURL(url: string): Promise<void> {
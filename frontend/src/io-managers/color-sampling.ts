@@ -0,0 +1,75 @@
+import { type Editor } from "@graphite/wasm-communication/editor";
+import { TriggerCancelWindowColorSampling, TriggerSampleWindowColor } from "@graphite/wasm-communication/messages";
+
+export function createColorSamplingManager(editor: Editor): void {
+	let closeCapture: (() => void) | undefined;
+
+	// Subscribe to process backend event
+	editor.subscriptions.subscribeJsMessage(TriggerSampleWindowColor, async () => {
+		closeCapture?.();
+
+		// Read the pixels in the widest gamut the display shows, so colors outside of sRGB are converted by the backend rather than clipped by the browser
+		const colorSpace: PredefinedColorSpace = window.matchMedia("(color-gamut: p3)").matches ? "display-p3" : "srgb";
+
+		let canvas: HTMLCanvasElement;
+		try {
+			canvas = await captureWindow(colorSpace);
+		} catch (e) {
+			// The user declined to share the window, or the browser can't capture it
+			console.warn("Failed to capture the window for sampling a color:", e);
+			editor.instance.windowColorSamplingCanceled();
+			return;
+		}
+		const context = canvas.getContext("2d", { colorSpace, willReadFrequently: true });
+		if (!context) {
+			editor.instance.windowColorSamplingCanceled();
+			return;
+		}
+
+		// Show the capture over the whole window, where the color is sampled from wherever it's clicked
+		canvas.style.cssText = "position: fixed; inset: 0; width: 100vw; height: 100vh; z-index: 10000; cursor: crosshair;";
+		canvas.addEventListener("pointerdown", (e) => {
+			e.preventDefault();
+			e.stopPropagation();
+
+			const x = Math.floor((e.clientX / window.innerWidth) * canvas.width);
+			const y = Math.floor((e.clientY / window.innerHeight) * canvas.height);
+			const [red, green, blue, alpha] = context.getImageData(x, y, 1, 1, { colorSpace }).data;
+
+			closeCapture?.();
+			editor.instance.windowColorSampled(red, green, blue, alpha, colorSpace);
+		});
+		document.body.appendChild(canvas);
+		closeCapture = () => {
+			canvas.remove();
+			closeCapture = undefined;
+		};
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerCancelWindowColorSampling, () => {
+		closeCapture?.();
+	});
+}
+
+// Capture one frame of the browser tab, which the browser asks the user's permission for, and draw it onto a canvas in the given color space
+async function captureWindow(colorSpace: PredefinedColorSpace): Promise<HTMLCanvasElement> {
+	// `preferCurrentTab` isn't yet in the DOM types, but browsers supporting it offer this tab first instead of a list of screens and windows
+	const stream = await navigator.mediaDevices.getDisplayMedia({ video: true, audio: false, preferCurrentTab: true } as DisplayMediaStreamOptions);
+
+	try {
+		const video = document.createElement("video");
+		video.srcObject = stream;
+		video.muted = true;
+		await video.play();
+
+		const canvas = document.createElement("canvas");
+		canvas.width = video.videoWidth;
+		canvas.height = video.videoHeight;
+		const context = canvas.getContext("2d", { colorSpace, willReadFrequently: true });
+		if (!context) throw new Error("Can't create 2D context from canvas while capturing the window");
+		context.drawImage(video, 0, 0);
+
+		return canvas;
+	} finally {
+		stream.getTracks().forEach((track) => track.stop());
+	}
+}
@@ -0,0 +1,25 @@
+import { type Editor } from "@graphite/wasm-communication/editor";
+import { TriggerIdleWork } from "@graphite/wasm-communication/messages";
+
+// Browsers without `requestIdleCallback` get a short timeout and a fixed time budget instead
+const FALLBACK_IDLE_DELAY_MS = 50;
+const FALLBACK_IDLE_BUDGET_MS = 10;
+
+export function createIdleManager(editor: Editor): void {
+	let scheduled = false;
+
+	const performIdleWork = (timeRemaining: number): void => {
+		scheduled = false;
+		editor.instance.performIdleWork(timeRemaining);
+	};
+
+	// Subscribe to process backend event
+	editor.subscriptions.subscribeJsMessage(TriggerIdleWork, () => {
+		// The backend asks again whenever it has more to do, so only one callback needs to be waiting at a time
+		if (scheduled) return;
+		scheduled = true;
+
+		if ("requestIdleCallback" in window) window.requestIdleCallback((deadline) => performIdleWork(deadline.timeRemaining()));
+		else setTimeout(() => performIdleWork(FALLBACK_IDLE_BUDGET_MS), FALLBACK_IDLE_DELAY_MS);
+	});
+}
@@ -3,12 +3,12 @@ import { get } from "svelte/store";
 import { type DialogState } from "@graphite/state-providers/dialog";
 import { type FullscreenState } from "@graphite/state-providers/fullscreen";
 import { type PortfolioState } from "@graphite/state-providers/portfolio";
-import { makeKeyboardModifiersBitfield, textInputCleanup, getLocalizedScanCode } from "@graphite/utility-functions/keyboard-entry";
+import { makeKeyboardModifiersBitfield, makePointerButtonsBitfield, textInputCleanup, getLocalizedScanCode } from "@graphite/utility-functions/keyboard-entry";
 import { platformIsMac } from "@graphite/utility-functions/platform";
 import { extractPixelData } from "@graphite/utility-functions/rasterization";
 import { stripIndents } from "@graphite/utility-functions/strip-indents";
 import { type Editor } from "@graphite/wasm-communication/editor";
-import { TriggerPaste } from "@graphite/wasm-communication/messages";
+import { type PasteMode, TriggerKeyRepeat, TriggerPaste } from "@graphite/wasm-communication/messages";
 
 type EventName = keyof HTMLElementEventMap | keyof WindowEventHandlersEventMap | "modifyinputfield";
 type EventListenerTarget = {
@@ -35,6 +35,8 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 		{ target: window, eventName: "pointermove", action: (e: PointerEvent) => onPointerMove(e) },
 		{ target: window, eventName: "pointerdown", action: (e: PointerEvent) => onPointerDown(e) },
 		{ target: window, eventName: "pointerup", action: (e: PointerEvent) => onPointerUp(e) },
+		{ target: window, eventName: "pointercancel", action: (e: PointerEvent) => onPointerCancel(e) },
+		{ target: window, eventName: "blur", action: () => onWindowBlur() },
 		{ target: window, eventName: "dblclick", action: (e: PointerEvent) => onDoubleClick(e) },
 		{ target: window, eventName: "wheel", action: (e: WheelEvent) => onWheelScroll(e), options: { passive: false } },
 		{ target: window, eventName: "modifyinputfield", action: (e: CustomEvent) => onModifyInputField(e) },
@@ -53,6 +55,8 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 	function unbindListeners(): void {
 		// Remove event bindings after the lifetime of the application (or on hot-module replacement during development)
 		listeners.forEach(({ target, eventName, action, options }) => target.removeEventListener(eventName, action, options));
+		devicePixelRatioQuery?.removeEventListener("change", onDevicePixelRatioChange);
+		if (autoScrollFrame !== undefined) cancelAnimationFrame(autoScrollFrame);
 	}
 
 	// Keyboard events
@@ -97,11 +101,11 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 	async function onKeyDown(e: KeyboardEvent): Promise<void> {
 		const key = await getLocalizedScanCode(e);
 
-		const NO_KEY_REPEAT_MODIFIER_KEYS = ["ControlLeft", "ControlRight", "ShiftLeft", "ShiftRight", "MetaLeft", "MetaRight", "AltLeft", "AltRight", "AltGraph", "CapsLock", "Fn", "FnLock"];
-		if (e.repeat && NO_KEY_REPEAT_MODIFIER_KEYS.includes(key)) return;
-
 		if (await shouldRedirectKeyboardEventToBackend(e)) {
 			e.preventDefault();
+			// The editor repeats held keys itself, after the delay and at the interval chosen in the preferences, since browsers repeat inconsistently
+			if (e.repeat) return;
+
 			const modifiers = makeKeyboardModifiersBitfield(e);
 			editor.instance.onKeyDown(key, modifiers);
 			return;
@@ -143,7 +147,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 		}
 
 		const modifiers = makeKeyboardModifiersBitfield(e);
-		editor.instance.onMouseMove(e.clientX, e.clientY, e.buttons, modifiers);
+		editor.instance.onMouseMove(e.clientX, e.clientY, makePointerButtonsBitfield(e), e.pressure, e.timeStamp, modifiers);
 	}
 
 	function onPointerDown(e: PointerEvent): void {
@@ -164,12 +168,32 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 		}
 
 		if (viewportPointerInteractionOngoing) {
+			// Keep receiving the movement and release of this pointer if the drag leaves the canvas, or even the browser window
+			if (isTargetingCanvas instanceof Element && !isTargetingCanvas.hasPointerCapture(e.pointerId)) isTargetingCanvas.setPointerCapture(e.pointerId);
+
 			const modifiers = makeKeyboardModifiersBitfield(e);
-			editor.instance.onMouseDown(e.clientX, e.clientY, e.buttons, modifiers);
+			editor.instance.onMouseDown(e.clientX, e.clientY, makePointerButtonsBitfield(e), e.pressure, e.timeStamp, modifiers);
+
+			if (autoScrollFrame === undefined) autoScrollFrame = requestAnimationFrame(onAutoScrollFrame);
 		}
 
 		// Block middle mouse button auto-scroll mode (the circlar widget that appears and allows quick scrolling by moving the cursor above or below it)
 		if (e.button === 1) e.preventDefault();
+
+		// Block the back and forward mouse buttons from navigating away from the page, since they are mapped to editor actions
+		if (viewportPointerInteractionOngoing && (e.button === 3 || e.button === 4)) e.preventDefault();
+	}
+
+	// While a drag in the viewport is ongoing, give the backend a chance each frame to pan the canvas if the pointer is held near the edge
+	let autoScrollFrame: number | undefined;
+	function onAutoScrollFrame(): void {
+		if (!viewportPointerInteractionOngoing) {
+			autoScrollFrame = undefined;
+			return;
+		}
+
+		editor.instance.autoScrollCanvas();
+		autoScrollFrame = requestAnimationFrame(onAutoScrollFrame);
 	}
 
 	function onPointerUp(e: PointerEvent): void {
@@ -177,7 +201,17 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 
 		if (!textToolInteractiveInputElement) {
 			const modifiers = makeKeyboardModifiersBitfield(e);
-			editor.instance.onMouseUp(e.clientX, e.clientY, e.buttons, modifiers);
+			editor.instance.onMouseUp(e.clientX, e.clientY, makePointerButtonsBitfield(e), e.pressure, e.timeStamp, modifiers);
+		}
+	}
+
+	// The browser took over the pointer (such as for a touch scroll gesture), so its release will never be reported
+	function onPointerCancel(e: PointerEvent): void {
+		viewportPointerInteractionOngoing = false;
+
+		if (!textToolInteractiveInputElement) {
+			const modifiers = makeKeyboardModifiersBitfield(e);
+			editor.instance.onMouseUp(e.clientX, e.clientY, 0, 0, e.timeStamp, modifiers);
 		}
 	}
 
@@ -186,7 +220,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 
 		if (!textToolInteractiveInputElement) {
 			const modifiers = makeKeyboardModifiersBitfield(e);
-			editor.instance.onDoubleClick(e.clientX, e.clientY, e.buttons, modifiers);
+			editor.instance.onDoubleClick(e.clientX, e.clientY, makePointerButtonsBitfield(e), modifiers);
 		}
 	}
 
@@ -207,7 +241,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 		if (isTargetingCanvas) {
 			e.preventDefault();
 			const modifiers = makeKeyboardModifiersBitfield(e);
-			editor.instance.onWheelScroll(e.clientX, e.clientY, e.buttons, e.deltaX, e.deltaY, e.deltaZ, modifiers);
+			editor.instance.onWheelScroll(e.clientX, e.clientY, makePointerButtonsBitfield(e), e.deltaX, e.deltaY, e.deltaZ, modifiers);
 		}
 	}
 
@@ -225,6 +259,23 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 
 	// Window events
 
+	// Keys and buttons released in another window aren't reported, so the backend releases whatever it still considers held
+	function onWindowBlur(): void {
+		viewportPointerInteractionOngoing = false;
+		editor.instance.onFocusLost();
+	}
+
+	// The media query only matches the current device pixel ratio, so it's replaced after each change, which happens
+	// with browser zoom or when the window is moved to a display with a different pixel density (which doesn't always fire a resize)
+	let devicePixelRatioQuery: MediaQueryList | undefined;
+	function onDevicePixelRatioChange(): void {
+		devicePixelRatioQuery?.removeEventListener("change", onDevicePixelRatioChange);
+		devicePixelRatioQuery = window.matchMedia(`(resolution: ${window.devicePixelRatio}dppx)`);
+		devicePixelRatioQuery.addEventListener("change", onDevicePixelRatioChange);
+
+		editor.instance.devicePixelRatio(window.devicePixelRatio);
+	}
+
 	function onWindowResize(container: HTMLElement): void {
 		const viewports = Array.from(container.querySelectorAll("[data-canvas]"));
 		const boundsOfViewports = viewports.map((canvas) => {
@@ -264,7 +315,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 			if (item.type === "text/plain") {
 				item.getAsString((text) => {
 					if (text.startsWith("graphite/layer: ")) {
-						editor.instance.pasteSerializedData(text.substring(16, text.length));
+						editor.instance.pasteSerializedData(text.substring(16, text.length), "InPlace");
 					} else if (text.startsWith("graphite/nodes: ")) {
 						editor.instance.pasteSerializedNodes(text.substring(16, text.length));
 					}
@@ -282,7 +333,12 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 
 	// Frontend message subscriptions
 
-	editor.subscriptions.subscribeJsMessage(TriggerPaste, async () => {
+	editor.subscriptions.subscribeJsMessage(TriggerPaste, (data) => pasteFromClipboard(data.mode));
+	editor.subscriptions.subscribeJsMessage(TriggerKeyRepeat, (data) => {
+		setTimeout(() => editor.instance.onKeyRepeat(data.repeatId), data.delay);
+	});
+
+	async function pasteFromClipboard(mode: PasteMode): Promise<void> {
 		// In the try block, attempt to read from the Clipboard API, which may not have permission and may not be supported in all browsers
 		// In the catch block, explain to the user why the paste failed and how to fix or work around the problem
 		try {
@@ -307,7 +363,7 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 						const text = reader.result as string;
 
 						if (text.startsWith("graphite/layer: ")) {
-							editor.instance.pasteSerializedData(text.substring(16, text.length));
+							editor.instance.pasteSerializedData(text.substring(16, text.length), mode);
 						}
 					};
 					reader.readAsText(blob);
@@ -348,13 +404,14 @@ export function createInputManager(editor: Editor, dialog: DialogState, document
 
 			editor.instance.errorDialog("Cannot access clipboard", message);
 		}
-	});
+	}
 
 	// Initialization
 
 	// Bind the event listeners
 	bindListeners();
-	// Resize on creation
+	// Report the pixel density and resize on creation
+	onDevicePixelRatioChange();
 	onWindowResize(window.document.body);
 
 	// Return the destructor
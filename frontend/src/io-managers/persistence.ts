@@ -3,11 +3,39 @@ import { get as getFromStore } from "svelte/store";
 
 import { type PortfolioState } from "@graphite/state-providers/portfolio";
 import { type Editor } from "@graphite/wasm-communication/editor";
-import { TriggerIndexedDbWriteDocument, TriggerIndexedDbRemoveDocument, TriggerSavePreferences, TriggerLoadAutoSaveDocuments, TriggerLoadPreferences } from "@graphite/wasm-communication/messages";
+import {
+	TriggerIndexedDbWriteDocument,
+	TriggerIndexedDbRemoveDocument,
+	TriggerIndexedDbWriteBackup,
+	TriggerLoadBackups,
+	TriggerLoadSavedVersion,
+	TriggerOpenBackup,
+	TriggerSaveKeymap,
+	TriggerSavePreferences,
+	TriggerLoadAutoSaveDocuments,
+	TriggerLoadPreferences,
+	TriggerAcquireDocumentLock,
+	TriggerReleaseDocumentLock,
+} from "@graphite/wasm-communication/messages";
 
 const graphiteStore = createStore("graphite", "store");
 
+// Locks are kept in local storage rather than IndexedDB so they can be released synchronously while the window is closing
+const DOCUMENT_LOCKS_KEY = "graphite-document-locks";
+// Locks are kept alive by a heartbeat, so one that hasn't been refreshed in a while belongs to a window that closed without releasing it
+const DOCUMENT_LOCK_HEARTBEAT_INTERVAL_MS = 10_000;
+const DOCUMENT_LOCK_STALE_AFTER_MS = 30_000;
+
+type DocumentLock = { sessionId: string; heartbeat: number };
+// A save of a file, either the latest one or one of the backups of those before it, which are kept from the most recent to the oldest
+type DocumentBackup = { savedAt: number; document: string };
+
 export function createPersistenceManager(editor: Editor, portfolio: PortfolioState): void {
+	// Identifies this window so the locks it holds can be told apart from those of other windows or instances sharing the same storage
+	const sessionId = `${Date.now().toString(36)}-${Math.random().toString(36).slice(2)}`;
+	// IDs of the open documents locked by this window, along with the IDs of their files which the locks are kept under
+	const heldLocks = new Map<bigint, bigint>();
+
 	// DOCUMENTS
 
 	async function storeDocumentOrder(): Promise<void> {
@@ -62,6 +90,114 @@ export function createPersistenceManager(editor: Editor, portfolio: PortfolioSta
 		});
 	}
 
+	// DOCUMENT LOCKS
+
+	function readDocumentLocks(): Record<string, DocumentLock> {
+		try {
+			return JSON.parse(localStorage.getItem(DOCUMENT_LOCKS_KEY) || "{}");
+		} catch {
+			return {};
+		}
+	}
+
+	function writeDocumentLocks(locks: Record<string, DocumentLock>): void {
+		localStorage.setItem(DOCUMENT_LOCKS_KEY, JSON.stringify(locks));
+	}
+
+	// Locks are kept under the ID of the file rather than of the open document, which is new each time the file is opened
+	function acquireDocumentLock(documentId: bigint, fileId: bigint, force: boolean): void {
+		const locks = readDocumentLocks();
+		const existing = locks[String(fileId)];
+		const heldByOtherSession = existing && existing.sessionId !== sessionId && Date.now() - existing.heartbeat < DOCUMENT_LOCK_STALE_AFTER_MS;
+		const lockedElsewhere = Boolean(heldByOtherSession && !force);
+
+		if (!lockedElsewhere) {
+			locks[String(fileId)] = { sessionId, heartbeat: Date.now() };
+			writeDocumentLocks(locks);
+			heldLocks.set(documentId, fileId);
+		}
+		editor.instance.setDocumentLockStatus(documentId, lockedElsewhere);
+	}
+
+	function releaseDocumentLocks(documentIds: bigint[]): void {
+		const releasedFileIds = documentIds.flatMap((documentId) => {
+			const fileId = heldLocks.get(documentId);
+			heldLocks.delete(documentId);
+			return fileId === undefined ? [] : [fileId];
+		});
+		if (releasedFileIds.length === 0) return;
+
+		const locks = readDocumentLocks();
+		const stillHeld = new Set(heldLocks.values());
+		releasedFileIds.forEach((fileId) => {
+			// The file stays locked while this window still has it open as another document
+			if (!stillHeld.has(fileId) && locks[String(fileId)]?.sessionId === sessionId) delete locks[String(fileId)];
+		});
+		writeDocumentLocks(locks);
+	}
+
+	function refreshDocumentLocks(): void {
+		if (heldLocks.size === 0) return;
+
+		const locks = readDocumentLocks();
+		const lostLocks: bigint[] = [];
+		heldLocks.forEach((fileId, documentId) => {
+			// Another window overrode our lock, so the document becomes read-only here
+			const existing = locks[String(fileId)];
+			if (existing && existing.sessionId !== sessionId) lostLocks.push(documentId);
+			else locks[String(fileId)] = { sessionId, heartbeat: Date.now() };
+		});
+		writeDocumentLocks(locks);
+
+		lostLocks.forEach((documentId) => {
+			heldLocks.delete(documentId);
+			editor.instance.setDocumentLockStatus(documentId, true);
+		});
+	}
+
+	setInterval(refreshDocumentLocks, DOCUMENT_LOCK_HEARTBEAT_INTERVAL_MS);
+	// Local storage is written synchronously, so the locks are released before the window unloads
+	window.addEventListener("beforeunload", () => releaseDocumentLocks(Array.from(heldLocks.keys())));
+
+	// BACKUPS
+
+	async function storeBackup(fileName: string, document: string, keep: number): Promise<void> {
+		const savedVersions = await get<Record<string, DocumentBackup>>("saved_versions", graphiteStore);
+		const previous = savedVersions?.[fileName];
+
+		// The save this one replaces becomes the first backup, moving the rest back by one and removing those beyond the number to keep
+		await update<Record<string, DocumentBackup[]>>(
+			"backups",
+			(old) => {
+				const backups = old || {};
+				const rotated = previous ? [previous, ...(backups[fileName] || [])] : backups[fileName] || [];
+				backups[fileName] = rotated.slice(0, keep);
+				return backups;
+			},
+			graphiteStore
+		);
+
+		await update<Record<string, DocumentBackup>>(
+			"saved_versions",
+			(old) => {
+				const saved = old || {};
+				saved[fileName] = { savedAt: Date.now(), document };
+				return saved;
+			},
+			graphiteStore
+		);
+	}
+
+	async function loadSavedVersion(fileName: string): Promise<DocumentBackup | undefined> {
+		const savedVersions = await get<Record<string, DocumentBackup>>("saved_versions", graphiteStore);
+		return savedVersions?.[fileName];
+	}
+
+	async function loadBackups(fileName: string): Promise<DocumentBackup[]> {
+		const backups = await get<Record<string, DocumentBackup[]>>("backups", graphiteStore);
+		return backups?.[fileName] || [];
+	}
+
 	// PREFERENCES
 
 	async function savePreferences(preferences: TriggerSavePreferences["preferences"]): Promise<void> {
@@ -75,6 +211,19 @@ export function createPersistenceManager(editor: Editor, portfolio: PortfolioSta
 		editor.instance.loadPreferences(JSON.stringify(preferences));
 	}
 
+	// KEYBOARD SHORTCUTS
+
+	async function saveKeymap(keymap: string): Promise<void> {
+		await set("keymap", keymap, graphiteStore);
+	}
+
+	async function loadKeymap(): Promise<void> {
+		const keymap = await get<string>("keymap", graphiteStore);
+		if (!keymap) return;
+
+		editor.instance.setKeymap(keymap);
+	}
+
 	// FRONTEND MESSAGE SUBSCRIPTIONS
 
 	// Subscribe to process backend events
@@ -83,6 +232,10 @@ export function createPersistenceManager(editor: Editor, portfolio: PortfolioSta
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerLoadPreferences, async () => {
 		await loadPreferences();
+		await loadKeymap();
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerSaveKeymap, async (saveKeymapMessage) => {
+		await saveKeymap(saveKeymapMessage.keymap);
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerIndexedDbWriteDocument, async (autoSaveDocument) => {
 		await storeDocument(autoSaveDocument);
@@ -93,6 +246,29 @@ export function createPersistenceManager(editor: Editor, portfolio: PortfolioSta
 	editor.subscriptions.subscribeJsMessage(TriggerLoadAutoSaveDocuments, async () => {
 		await loadDocuments();
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerIndexedDbWriteBackup, async (writeBackup) => {
+		await storeBackup(writeBackup.fileName, writeBackup.document, writeBackup.keep);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerLoadBackups, async (loadBackupsMessage) => {
+		const backups = await loadBackups(loadBackupsMessage.fileName);
+		const saveTimes = backups.map((backup) => new Date(backup.savedAt).toLocaleString());
+		editor.instance.requestBackupsDialogWithSaveTimes(loadBackupsMessage.fileName, saveTimes);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerLoadSavedVersion, async (loadSavedVersion) => {
+		const latest = await loadSavedVersion(loadSavedVersion.fileName);
+		if (latest) editor.instance.compareWithSavedVersion(loadSavedVersion.documentId, new Date(latest.savedAt).toLocaleString(), latest.document);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerOpenBackup, async (openBackup) => {
+		const backups = await loadBackups(openBackup.fileName);
+		const backup = backups[openBackup.generation - 1];
+		if (backup) editor.instance.openDocumentFile(openBackup.documentName, backup.document);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerAcquireDocumentLock, (acquireDocumentLockMessage) => {
+		acquireDocumentLock(acquireDocumentLockMessage.documentId, acquireDocumentLockMessage.fileId, acquireDocumentLockMessage.force);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerReleaseDocumentLock, (releaseDocumentLockMessage) => {
+		releaseDocumentLocks([releaseDocumentLockMessage.documentId]);
+	});
 }
 
 export async function wipeDocuments(): Promise<void> {
@@ -0,0 +1,24 @@
+import { writable } from "svelte/store";
+
+import { type Editor } from "@graphite/wasm-communication/editor";
+import { type ColorPickerState, UpdateColorPicker } from "@graphite/wasm-communication/messages";
+
+// eslint-disable-next-line @typescript-eslint/explicit-function-return-type
+export function createColorPickerState(editor: Editor) {
+	const { subscribe, update } = writable({
+		picker: undefined as ColorPickerState | undefined,
+	});
+
+	// Set up message subscriptions on creation
+	editor.subscriptions.subscribeJsMessage(UpdateColorPicker, (updateColorPicker) => {
+		update((state) => {
+			state.picker = updateColorPicker.state;
+			return state;
+		});
+	});
+
+	return {
+		subscribe,
+	};
+}
+export type ColorPickerStateProvider = ReturnType<typeof createColorPickerState>;
@@ -1,8 +1,18 @@
 import {writable} from "svelte/store";
 
 import { type IconName } from "@graphite/utility-functions/icons";
+import { rasterizeSVG } from "@graphite/utility-functions/rasterization";
 import { type Editor } from "@graphite/wasm-communication/editor";
-import { type TextButtonWidget, type WidgetLayout, defaultWidgetLayout, DisplayDialog, DisplayDialogDismiss, UpdateDialogDetails, patchWidgetLayout } from "@graphite/wasm-communication/messages";
+import {
+	type TextButtonWidget,
+	type WidgetLayout,
+	defaultWidgetLayout,
+	DisplayDialog,
+	DisplayDialogDismiss,
+	UpdateDialogDetails,
+	UpdateExportPreview,
+	patchWidgetLayout,
+} from "@graphite/wasm-communication/messages";
 
 // eslint-disable-next-line @typescript-eslint/explicit-function-return-type
 export function createDialogState(editor: Editor) {
@@ -12,8 +22,18 @@ export function createDialogState(editor: Editor) {
 		widgets: defaultWidgetLayout(),
 		// Special case for the crash dialog because we cannot handle button widget callbacks from Rust once the editor instance has panicked
 		crashDialogButtons: undefined as undefined | TextButtonWidget[],
+		// Blob URL of the rasterized preview image shown in the export dialog
+		exportPreview: undefined as undefined | string,
 	});
 
+	// Each preview render is numbered so a slow rasterization can't replace the preview of a newer one
+	let exportPreviewRequest = 0;
+
+	function setExportPreview(state: { exportPreview: undefined | string }, exportPreview: undefined | string): void {
+		if (state.exportPreview) URL.revokeObjectURL(state.exportPreview);
+		state.exportPreview = exportPreview;
+	}
+
 	function dismissDialog(): void {
 		
 		update((state) => {
@@ -41,6 +61,7 @@ export function createDialogState(editor: Editor) {
 		update((state) => {
 			state.visible = true;
 			state.icon = displayDialog.icon;
+			setExportPreview(state, undefined);
 			return state;
 		});
 	});
@@ -53,6 +74,27 @@ export function createDialogState(editor: Editor) {
 		});
 	});
 	editor.subscriptions.subscribeJsMessage(DisplayDialogDismiss, dismissDialog);
+	editor.subscriptions.subscribeJsMessage(UpdateExportPreview, async (updateExportPreview) => {
+		const { svg, mime, size } = updateExportPreview;
+		const request = ++exportPreviewRequest;
+
+		// Rasterize the export at the preview size the same way as a raster export, filling with white for JPEGs which lack transparency
+		let exportPreview: undefined | string;
+		if (svg) {
+			const backgroundColor = mime.endsWith("jpeg") ? "white" : undefined;
+			const blob = await rasterizeSVG(svg, size.x, size.y, mime, backgroundColor);
+			exportPreview = URL.createObjectURL(blob);
+		}
+
+		if (request !== exportPreviewRequest) {
+			if (exportPreview) URL.revokeObjectURL(exportPreview);
+			return;
+		}
+		update((state) => {
+			setExportPreview(state, exportPreview);
+			return state;
+		});
+	});
 
 	return {
 		subscribe,
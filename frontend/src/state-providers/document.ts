@@ -4,10 +4,13 @@ import {writable} from "svelte/store";
 import { type Editor } from "@graphite/wasm-communication/editor";
 import {
 	defaultWidgetLayout,
+	type OutlineEntry,
 	patchWidgetLayout,
 	TriggerRefreshBoundsOfViewports,
+	UpdateCanvasZoom,
 	UpdateDocumentBarLayout,
 	UpdateDocumentModeLayout,
+	UpdateDocumentOutline,
 	UpdateToolOptionsLayout,
 	UpdateToolShelfLayout,
 	UpdateWorkingColorsLayout,
@@ -22,9 +25,33 @@ export function createDocumentState(editor: Editor) {
 		documentBarLayout: defaultWidgetLayout(),
 		toolShelfLayout: defaultWidgetLayout(),
 		workingColorsLayout: defaultWidgetLayout(),
+		// Document outline and text search results
+		outlineQuery: "",
+		outline: [] as OutlineEntry[],
+		// Canvas zoom percentage and the zoom levels offered in a zoom dropdown
+		zoomPercentage: 100,
+		zoomLevels: [] as number[],
 	});
 	const { subscribe, update } = state;
 
+	// Update the document outline
+	editor.subscriptions.subscribeJsMessage(UpdateDocumentOutline, (updateDocumentOutline) => {
+		update((state) => {
+			state.outlineQuery = updateDocumentOutline.query;
+			state.outline = updateDocumentOutline.entries;
+			return state;
+		});
+	});
+
+	// Update the canvas zoom
+	editor.subscriptions.subscribeJsMessage(UpdateCanvasZoom, (updateCanvasZoom) => {
+		update((state) => {
+			state.zoomPercentage = updateCanvasZoom.zoomPercentage;
+			state.zoomLevels = updateCanvasZoom.zoomLevels;
+			return state;
+		});
+	});
+
 	// Update layouts
 	editor.subscriptions.subscribeJsMessage(UpdateDocumentModeLayout, async (updateDocumentModeLayout) => {
 		await tick();
@@ -2,23 +2,36 @@
 
 import { writable } from "svelte/store";
 
-import { downloadFileText, downloadFileBlob, upload, downloadFileURL } from "@graphite/utility-functions/files";
-import { extractPixelData, imageToPNG, rasterizeSVG, rasterizeSVGCanvas } from "@graphite/utility-functions/rasterization";
-import { type Editor } from "@graphite/wasm-communication/editor";
+import { downloadFileText, downloadFileBlob, upload, uploadMultiple, downloadFileURL } from "@graphite/utility-functions/files";
+import { extractPixelData, imageToPNG, rasterizeSVG, rasterizeSVGCanvas, rasterizeSVGIcon } from "@graphite/utility-functions/rasterization";
+import { type Editor, isDesktopApp, tauriReadDocumentFile } from "@graphite/wasm-communication/editor";
 import {
+	type DiagnosticsReport,
 	type FrontendDocumentDetails,
+	type TextMatch,
+	type TextQuery,
+	TriggerBatchChooseFiles,
 	TriggerCopyToClipboardBlobUrl,
 	TriggerDownloadBlobUrl,
+	TriggerDownloadIcon,
 	TriggerDownloadRaster,
 	TriggerDownloadTextFile,
+	TriggerFetchDocument,
+	TriggerFetchMergedImage,
 	TriggerImaginateCheckServerStatus,
 	TriggerImport,
 	TriggerOpenDocument,
+	TriggerRasterizeFileThumbnail,
+	TriggerRasterizeLayerEffects,
 	TriggerRasterizeRegionBelowLayer,
 	TriggerRevokeBlobUrl,
 	UpdateActiveDocument,
+	UpdateDiagnostics,
+	UpdateDocumentLoadingProgress,
+	UpdateDocumentThumbnail,
 	UpdateImageData,
 	UpdateOpenDocumentsList,
+	UpdateTextSearchResults,
 } from "@graphite/wasm-communication/messages";
 import { copyToClipboardFileURL } from "~src/io-managers/clipboard";
 
@@ -28,12 +41,29 @@ export function createPortfolioState(editor: Editor) {
 		unsaved: false,
 		documents: [] as FrontendDocumentDetails[],
 		activeDocumentIndex: 0,
+		diagnostics: undefined as DiagnosticsReport | undefined,
+		// Composite previews of each document's artwork, keyed by document ID, shown in the document tabs
+		thumbnails: {} as Record<string, string>,
+		// How much of each very large document is loaded, from 0 to 1, keyed by document ID, while the rest of it is still being loaded
+		loadingProgress: {} as Record<string, number>,
+		// Text layers matching the find query across all open documents
+		textSearch: {
+			query: undefined as TextQuery | undefined,
+			matches: [] as TextMatch[],
+			error: undefined as string | undefined,
+		},
 	});
 
 	// Set up message subscriptions on creation
 	editor.subscriptions.subscribeJsMessage(UpdateOpenDocumentsList, (updateOpenDocumentList) => {
 		update((state) => {
 			state.documents = updateOpenDocumentList.openDocuments;
+
+			// Forget the thumbnails of closed documents
+			const openIds = new Set(state.documents.map((doc) => String(doc.id)));
+			Object.keys(state.thumbnails).forEach((id) => {
+				if (!openIds.has(id)) delete state.thumbnails[id];
+			});
 			return state;
 		})
 	});
@@ -45,10 +75,72 @@ export function createPortfolioState(editor: Editor) {
 			return state;
 		})
 	});
+	editor.subscriptions.subscribeJsMessage(UpdateDocumentThumbnail, (updateDocumentThumbnail) => {
+		update((state) => {
+			state.thumbnails[String(updateDocumentThumbnail.documentId)] = updateDocumentThumbnail.thumbnail;
+			return state;
+		});
+	});
+	editor.subscriptions.subscribeJsMessage(UpdateDocumentLoadingProgress, (updateDocumentLoadingProgress) => {
+		update((state) => {
+			const { documentId, loaded, total } = updateDocumentLoadingProgress;
+			if (loaded < total) state.loadingProgress[String(documentId)] = loaded / total;
+			else delete state.loadingProgress[String(documentId)];
+			return state;
+		});
+	});
+	editor.subscriptions.subscribeJsMessage(UpdateTextSearchResults, (updateTextSearchResults) => {
+		update((state) => {
+			state.textSearch = {
+				query: updateTextSearchResults.query,
+				matches: updateTextSearchResults.matches,
+				error: updateTextSearchResults.error,
+			};
+			return state;
+		});
+	});
+	editor.subscriptions.subscribeJsMessage(UpdateDiagnostics, (updateDiagnostics) => {
+		update((state) => {
+			state.diagnostics = updateDiagnostics.diagnostics;
+			return state;
+		});
+	});
 	editor.subscriptions.subscribeJsMessage(TriggerOpenDocument, async () => {
 		const extension = editor.instance.fileSaveSuffix();
-		const data = await upload(extension, "text");
-		editor.instance.openDocumentFile(data.filename, data.content);
+		const data = await upload(`${extension},.svg,.json`, "text");
+		const filename = data.filename.toLowerCase();
+		if (filename.endsWith(".svg")) editor.instance.openSvgDocument(data.filename, data.content);
+		else if (filename.endsWith(".json")) editor.instance.openDesignFile(data.filename, data.content);
+		else editor.instance.openDocumentFile(data.filename, data.content);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerBatchChooseFiles, async () => {
+		const files = await uploadMultiple(editor.instance.fileSaveSuffix());
+		if (files.length === 0) return;
+
+		editor.instance.batchFilesChosen(
+			files.map((file) => file.filename),
+			files.map((file) => file.content)
+		);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerFetchDocument, async (triggerFetchDocument) => {
+		const { source } = triggerFetchDocument;
+		// The desktop app is given file paths, while the web has nothing but URLs, which may be relative to the page
+		const isUrl = /^[a-z][a-z0-9+.-]*:\/\//i.test(source) || !isDesktopApp();
+
+		try {
+			let content: string;
+			if (isUrl) {
+				const response = await fetch(source);
+				if (!response.ok) throw new Error(`The server responded with ${response.status} ${response.statusText}`);
+				content = await response.text();
+			} else {
+				content = await tauriReadDocumentFile(source);
+			}
+
+			editor.instance.openDocumentSource(source, content);
+		} catch (e) {
+			editor.instance.fetchDocumentFailed(source, e instanceof Error ? e.message : String(e));
+		}
 	});
 	editor.subscriptions.subscribeJsMessage(TriggerImport, async () => {
 		const data = await upload("image/*", "data");
@@ -58,6 +150,18 @@ export function createPortfolioState(editor: Editor) {
 	editor.subscriptions.subscribeJsMessage(TriggerDownloadTextFile, (triggerFileDownload) => {
 		downloadFileText(triggerFileDownload.name, triggerFileDownload.document);
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerFetchMergedImage, async (triggerFetchMergedImage) => {
+		const { documentId, layerPath, url } = triggerFetchMergedImage;
+
+		try {
+			const response = await fetch(url);
+			const imageData = await extractPixelData(await response.blob());
+
+			editor.instance.setMergedImage(documentId, layerPath, new Uint8Array(imageData.data), imageData.width, imageData.height);
+		} catch (e) {
+			console.error(`Failed to load the image for a data merge from "${url}":`, e);
+		}
+	});
 	editor.subscriptions.subscribeJsMessage(TriggerDownloadBlobUrl, async (triggerDownloadBlobUrl) => {
 		const data = await fetch(triggerDownloadBlobUrl.blobUrl);
 		const blob = await data.blob();
@@ -70,6 +174,15 @@ export function createPortfolioState(editor: Editor) {
 	editor.subscriptions.subscribeJsMessage(TriggerCopyToClipboardBlobUrl, (triggerDownloadBlobUrl) => {
 		copyToClipboardFileURL(triggerDownloadBlobUrl.blobUrl);
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerDownloadIcon, async (triggerIconDownload) => {
+		const { svg, name, mime, sizes } = triggerIconDownload;
+
+		// Rasterize the SVG once per icon size and bundle the images into the icon container file
+		const blob = await rasterizeSVGIcon(svg, sizes, mime);
+
+		// Have the browser download the file to the user's disk
+		downloadFileBlob(name, blob);
+	});
 	editor.subscriptions.subscribeJsMessage(TriggerDownloadRaster, async (triggerRasterDownload) => {
 		const { svg, name, mime, size } = triggerRasterDownload;
 
@@ -97,6 +210,32 @@ export function createPortfolioState(editor: Editor) {
 			editor.instance.setImageBlobURL(updateImageData.documentId, element.path, element.nodeId, blobURL, image.naturalWidth, image.naturalHeight, element.transform);
 		});
 	});
+	editor.subscriptions.subscribeJsMessage(TriggerRasterizeFileThumbnail, async (triggerRasterizeFileThumbnail) => {
+		const { documentId, svg, size } = triggerRasterizeFileThumbnail;
+
+		// The document is still saved without a thumbnail if it can't be rasterized
+		let thumbnail: string | undefined;
+		try {
+			thumbnail = (await rasterizeSVGCanvas(svg, size[0], size[1])).toDataURL("image/png");
+		} catch (e) {
+			console.error("Failed to rasterize the thumbnail of the saved document:", e);
+		}
+
+		editor.instance.downloadDocumentWithThumbnail(documentId, thumbnail);
+	});
+	editor.subscriptions.subscribeJsMessage(TriggerRasterizeLayerEffects, async (triggerRasterizeLayerEffects) => {
+		const { documentId, layerPath, svg, size } = triggerRasterizeLayerEffects;
+
+		try {
+			const imageData = (await rasterizeSVGCanvas(svg, size[0], size[1])).getContext("2d")?.getImageData(0, 0, size[0], size[1]);
+			if (!imageData) return;
+
+			editor.instance.flattenedLayerEffectsRasterized(documentId, layerPath, new Uint8Array(imageData.data), imageData.width, imageData.height);
+		} catch (e) {
+			// getImageData may throw an exception if the resolution is too high
+			console.error("Failed to rasterize the layer's effects:", e);
+		}
+	});
 	editor.subscriptions.subscribeJsMessage(TriggerRasterizeRegionBelowLayer, async (triggerRasterizeRegionBelowLayer) => {
 		const { documentId, layerPath, svg, size } = triggerRasterizeRegionBelowLayer;
 
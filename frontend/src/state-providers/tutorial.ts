@@ -0,0 +1,24 @@
+import { writable } from "svelte/store";
+
+import { type Editor } from "@graphite/wasm-communication/editor";
+import { type TutorialStepDetails, UpdateTutorialStep } from "@graphite/wasm-communication/messages";
+
+// eslint-disable-next-line @typescript-eslint/explicit-function-return-type
+export function createTutorialState(editor: Editor) {
+	const { subscribe, update } = writable({
+		step: undefined as TutorialStepDetails | undefined,
+	});
+
+	// Set up message subscriptions on creation
+	editor.subscriptions.subscribeJsMessage(UpdateTutorialStep, (updateTutorialStep) => {
+		update((state) => {
+			state.step = updateTutorialStep.step || undefined;
+			return state;
+		});
+	});
+
+	return {
+		subscribe,
+	};
+}
+export type TutorialState = ReturnType<typeof createTutorialState>;
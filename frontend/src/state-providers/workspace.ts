@@ -1,15 +1,30 @@
 /* eslint-disable max-classes-per-file */
 
-import {writable} from "svelte/store";
+import { writable } from "svelte/store";
 
 import { type Editor } from "@graphite/wasm-communication/editor";
+import { type WorkspaceLayout, UpdatePresentationMode, UpdateWorkspaceLayout } from "@graphite/wasm-communication/messages";
 
 // eslint-disable-next-line @typescript-eslint/explicit-function-return-type
 export function createWorkspaceState(editor: Editor) {
-	const { subscribe, update } = writable({});
+	const { subscribe, update } = writable({
+		layout: undefined as WorkspaceLayout | undefined,
+		presenting: false,
+	});
 
 	// Set up message subscriptions on creation
-
+	editor.subscriptions.subscribeJsMessage(UpdateWorkspaceLayout, (updateWorkspaceLayout) => {
+		update((state) => {
+			state.layout = updateWorkspaceLayout.layout;
+			return state;
+		});
+	});
+	editor.subscriptions.subscribeJsMessage(UpdatePresentationMode, (updatePresentationMode) => {
+		update((state) => {
+			state.presenting = updatePresentationMode.presenting;
+			return state;
+		});
+	});
 
 	return {
 		subscribe,
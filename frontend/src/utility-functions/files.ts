@@ -49,6 +49,27 @@ export async function upload<T extends "text" | "data">(acceptedExtensions: stri
 		// Once `element` goes out of scope, it has no references so it gets garbage collected along with its event listener, so `removeEventListener` is not needed
 	});
 }
+export async function uploadMultiple(acceptedExtensions: string): Promise<UploadResult<"text">[]> {
+	return new Promise<UploadResult<"text">[]>((resolve, _) => {
+		const element = document.createElement("input");
+		element.type = "file";
+		element.accept = acceptedExtensions;
+		element.multiple = true;
+
+		element.addEventListener(
+			"change",
+			async () => {
+				const files = Array.from(element.files || []);
+				const results = await Promise.all(files.map(async (file) => ({ filename: file.name, type: file.type, content: await file.text() })));
+
+				resolve(results);
+			},
+			{ capture: false, once: true }
+		);
+
+		element.click();
+	});
+}
 export type UploadResult<T> = { filename: string; type: string; content: UploadResultType<T> };
 type UploadResultType<T> = T extends "text" ? string : T extends "data" ? Uint8Array : never;
 
@@ -11,6 +11,23 @@ export function makeKeyboardModifiersBitfield(e: WheelEvent | PointerEvent | Key
 	);
 }
 
+export function makePointerButtonsBitfield(e: PointerEvent | WheelEvent): number {
+	// A stylus reports its barrel button as the right button and its eraser end as button 5, which are given their own bits so they can be mapped separately
+	if (e instanceof PointerEvent && e.pointerType === "pen") {
+		return (
+			// Pen tip
+			(Number(Boolean(e.buttons & 1)) << 0) |
+			// Eraser end
+			(Number(Boolean(e.buttons & 32)) << 3) |
+			// Barrel button
+			(Number(Boolean(e.buttons & 2)) << 4)
+		);
+	}
+
+	// Left, right, and middle buttons, followed by the back and forward buttons moved past the bits used by the pen
+	return (e.buttons & 0b111) | ((e.buttons & 0b11000) << 2);
+}
+
 // Necessary because innerText puts an extra newline character at the end when the text is more than one line.
 export function textInputCleanup(text: string): string {
 	if (text[text.length - 1] === "\n") return text.slice(0, -1);
@@ -54,6 +54,65 @@ export async function rasterizeSVG(svg: string, width: number, height: number, m
 	return blob;
 }
 
+// The ICNS element types that hold PNG data, by icon size
+const ICNS_PNG_TYPES: Record<number, string> = { 16: "icp4", 32: "icp5", 64: "icp6", 128: "ic07", 256: "ic08", 512: "ic09", 1024: "ic10" };
+
+// Rasterize the string of a square SVG document as a PNG at each of the given sizes and bundle them into an ICO (`image/x-icon`) or ICNS (`image/icns`) file
+export async function rasterizeSVGIcon(svg: string, sizes: number[], mime: string): Promise<Blob> {
+	const images = await Promise.all(
+		sizes.map(async (size) => {
+			const png = await rasterizeSVG(svg, size, size, "image/png");
+			return { size, data: new Uint8Array(await png.arrayBuffer()) };
+		})
+	);
+
+	if (mime === "image/icns") {
+		const entries = images.filter(({ size }) => size in ICNS_PNG_TYPES);
+		const totalLength = 8 + entries.reduce((length, { data }) => length + 8 + data.length, 0);
+
+		// ICNS files are a big-endian header followed by a series of typed, length-prefixed elements
+		const bytes = new Uint8Array(totalLength);
+		const view = new DataView(bytes.buffer);
+		const writeType = (offset: number, type: string): void => [...type].forEach((char, index) => view.setUint8(offset + index, char.charCodeAt(0)));
+
+		writeType(0, "icns");
+		view.setUint32(4, totalLength);
+		let offset = 8;
+		entries.forEach(({ size, data }) => {
+			writeType(offset, ICNS_PNG_TYPES[size]);
+			view.setUint32(offset + 4, 8 + data.length);
+			bytes.set(data, offset + 8);
+			offset += 8 + data.length;
+		});
+
+		return new Blob([bytes], { type: mime });
+	}
+
+	// ICO files are a little-endian header and directory of 16-byte entries, followed by the PNG data of each image
+	const headerLength = 6 + 16 * images.length;
+	const totalLength = headerLength + images.reduce((length, { data }) => length + data.length, 0);
+	const bytes = new Uint8Array(totalLength);
+	const view = new DataView(bytes.buffer);
+
+	view.setUint16(2, 1, true);
+	view.setUint16(4, images.length, true);
+	let offset = headerLength;
+	images.forEach(({ size, data }, index) => {
+		const entry = 6 + 16 * index;
+		// A dimension of 0 means 256 pixels
+		view.setUint8(entry, size >= 256 ? 0 : size);
+		view.setUint8(entry + 1, size >= 256 ? 0 : size);
+		view.setUint16(entry + 4, 1, true);
+		view.setUint16(entry + 6, 32, true);
+		view.setUint32(entry + 8, data.length, true);
+		view.setUint32(entry + 12, offset, true);
+		bytes.set(data, offset);
+		offset += data.length;
+	});
+
+	return new Blob([bytes], { type: mime });
+}
+
 /// Convert an image source (e.g. PNG document) into pixel data, a width, and a height
 export async function extractPixelData(imageData: ImageBitmapSource): Promise<ImageData> {
 	const canvasContext = await imageToCanvasContext(imageData);
@@ -1,5 +1,5 @@
 import type WasmBindgenPackage from "@graphite-frontend/wasm/pkg";
-import init, { setRandomSeed, wasmMemory, JsEditorHandle } from "@graphite-frontend/wasm/pkg/graphite_wasm.js";
+import init, { setRandomSeed, wasmMemory, parseNumber as wasmParseNumber, readDocumentThumbnail as wasmReadDocumentThumbnail, JsEditorHandle } from "@graphite-frontend/wasm/pkg/graphite_wasm.js";
 import { panicProxy } from "@graphite/utility-functions/panic-proxy";
 import { type JsMessageType } from "@graphite/wasm-communication/messages";
 import { createSubscriptionRouter, type SubscriptionRouter } from "@graphite/wasm-communication/subscription-router";
@@ -51,6 +51,34 @@ export async function dispatchTauri(message: unknown): Promise<void> {
 	}
 }
 
+// Reads a typed number with either a decimal point or a decimal comma, whichever the number locale, or `undefined` if it isn't a number
+export function parseNumber(text: string): number | undefined {
+	return wasmParseNumber(text);
+}
+
+// Reads the PNG thumbnail, as a data URL, embedded in the contents of a saved document without opening it, or `undefined` if it has none
+export function readDocumentThumbnail(serializedContent: string): string | undefined {
+	return wasmReadDocumentThumbnail(serializedContent);
+}
+
+export function isDesktopApp(): boolean {
+	return Boolean(tauri);
+}
+
+// The file paths given on the command line of the desktop app, which has no URL to read them from
+export async function tauriLaunchDocumentPaths(): Promise<string[]> {
+	if (!tauri) return [];
+
+	return (await tauri).invoke("launch_document_paths");
+}
+
+// Reads a document from disk in the desktop app, rejecting with the reason if it couldn't be read
+export async function tauriReadDocumentFile(path: string): Promise<string> {
+	if (!tauri) throw new Error("Files can only be read from disk in the desktop app");
+
+	return (await tauri).invoke("read_document_file", { path });
+}
+
 // Should be called asynchronously before `createEditor()`
 export async function initWasm(): Promise<void> {
 	// Skip if the WASM module is already initialized
@@ -69,6 +97,9 @@ export async function initWasm(): Promise<void> {
 	setRandomSeed(randomSeed);
 	if (!tauri) return;
 	await (await tauri).invoke("set_random_seed", { seed: randomSeedFloat });
+
+	// The responses to messages sent by the remote control server are handled like the responses to the window's own messages
+	await (await tauri).event.listen<string>("remote-control-responses", (event) => window["editorInstance"]?.tauriResponse(event.payload));
 }
 
 // Should be called after running `initWasm()` and its promise resolving
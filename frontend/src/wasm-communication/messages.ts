@@ -51,6 +51,40 @@ export class UpdateZoomWithScroll extends JsMessage {
 	readonly zoomWithScroll!: boolean;
 }
 
+export class PanelSizes {
+	readonly content!: number;
+
+	readonly document!: number;
+
+	readonly graph!: number;
+
+	readonly details!: number;
+
+	readonly properties!: number;
+
+	readonly layers!: number;
+}
+
+export class WorkspaceLayout {
+	readonly nodeGraphVisible!: boolean;
+
+	readonly detailsVisible!: boolean;
+
+	@Type(() => PanelSizes)
+	readonly panelSizes!: PanelSizes;
+
+	readonly activeTool!: string;
+}
+
+export class UpdateWorkspaceLayout extends JsMessage {
+	@Type(() => WorkspaceLayout)
+	readonly layout!: WorkspaceLayout;
+}
+
+export class UpdatePresentationMode extends JsMessage {
+	readonly presenting!: boolean;
+}
+
 // Allows the auto save system to use a string for the id rather than a BigInt.
 // IndexedDb does not allow for BigInts as primary keys.
 // TypeScript does not allow subclasses to change the type of class variables in subclasses.
@@ -62,10 +96,12 @@ export abstract class DocumentDetails {
 
 	readonly isSaved!: boolean;
 
+	readonly isReadOnly!: boolean;
+
 	readonly id!: bigint | string;
 
 	get displayName(): string {
-		return `${this.name}${this.isSaved ? "" : "*"}`;
+		return `${this.name}${this.isSaved ? "" : "*"}${this.isReadOnly ? " (Read Only)" : ""}`;
 	}
 }
 
@@ -138,6 +174,44 @@ export class TriggerIndexedDbWriteDocument extends JsMessage {
 	version!: string;
 }
 
+export class TriggerIndexedDbWriteBackup extends JsMessage {
+	readonly fileName!: string;
+
+	readonly document!: string;
+
+	readonly keep!: number;
+}
+
+export class TriggerLoadBackups extends JsMessage {
+	readonly fileName!: string;
+}
+
+export class TriggerLoadSavedVersion extends JsMessage {
+	readonly documentId!: bigint;
+
+	readonly fileName!: string;
+}
+
+export class TriggerOpenBackup extends JsMessage {
+	readonly fileName!: string;
+
+	readonly generation!: number;
+
+	readonly documentName!: string;
+}
+
+export class TriggerAcquireDocumentLock extends JsMessage {
+	readonly documentId!: bigint;
+
+	readonly fileId!: bigint;
+
+	readonly force!: boolean;
+}
+
+export class TriggerReleaseDocumentLock extends JsMessage {
+	readonly documentId!: bigint;
+}
+
 export class TriggerIndexedDbRemoveDocument extends JsMessage {
 	// Use a string since IndexedDB can not use BigInts for keys
 	@Transform(({ value }: { value: bigint }) => value.toString())
@@ -149,6 +223,14 @@ export class UpdateInputHints extends JsMessage {
 	readonly hintData!: HintData;
 }
 
+export class UpdateKeymap extends JsMessage {
+	readonly keymap!: string;
+
+	readonly errors!: string[];
+
+	readonly conflicts!: string[];
+}
+
 export type HintData = HintGroup[];
 
 export type HintGroup = HintInfo[];
@@ -421,6 +503,66 @@ export class UpdateActiveDocument extends JsMessage {
 	readonly documentId!: bigint;
 }
 
+export class BatchResult {
+	readonly name!: string;
+
+	readonly error!: string | undefined;
+}
+
+export class UpdateBatchProgress extends JsMessage {
+	readonly completed!: number;
+
+	readonly total!: number;
+
+	@Type(() => BatchResult)
+	readonly results!: BatchResult[];
+}
+
+export type ColorPickerTarget = "PrimaryWorkingColor" | "SecondaryWorkingColor" | { Widget: { layoutTarget: unknown; widgetId: bigint } };
+
+export type HarmonyRule = "Complementary" | "Analogous" | "Triadic" | "Tetradic";
+
+const colorOrNone = ({ value }: { value: { red: number; green: number; blue: number; alpha: number } | undefined }): Color =>
+	value ? new Color(value.red, value.green, value.blue, value.alpha) : new Color("none");
+
+export class ColorPalette {
+	readonly name!: string;
+
+	@Type(() => Color)
+	readonly colors!: Color[];
+}
+
+export class ColorPickerState {
+	readonly target!: ColorPickerTarget | undefined;
+
+	@Transform(colorOrNone)
+	readonly current!: Color;
+
+	@Transform(colorOrNone)
+	readonly previous!: Color;
+
+	@Type(() => Color)
+	readonly recentColors!: Color[];
+
+	@Type(() => ColorPalette)
+	readonly palettes!: ColorPalette[];
+
+	readonly activePalette!: number;
+
+	readonly sampling!: boolean;
+}
+
+export class UpdateCanvasZoom extends JsMessage {
+	readonly zoomPercentage!: number;
+
+	readonly zoomLevels!: number[];
+}
+
+export class UpdateColorPicker extends JsMessage {
+	@Type(() => ColorPickerState)
+	readonly state!: ColorPickerState;
+}
+
 export class DisplayDialogPanic extends JsMessage {
 	readonly panicInfo!: string;
 
@@ -437,6 +579,84 @@ export class UpdateDocumentArtwork extends JsMessage {
 	readonly svg!: string;
 }
 
+export class UpdateDocumentLayerRender extends JsMessage {
+	@Transform(({ value }: { value: bigint[] }) => new BigUint64Array(value))
+	readonly layerPath!: BigUint64Array;
+
+	readonly svg!: string;
+}
+
+export class OutlineEntry {
+	readonly name!: string;
+
+	@Transform(({ value }: { value: bigint[] }) => new BigUint64Array(value))
+	readonly layerPath!: BigUint64Array;
+
+	readonly depth!: number;
+
+	readonly isFolder!: boolean;
+
+	readonly text!: string | undefined;
+
+	@TupleToVec2
+	readonly position!: XY | undefined;
+
+	readonly matchRanges!: [number, number][];
+}
+
+export class UpdateDocumentOutline extends JsMessage {
+	readonly query!: string;
+
+	@Type(() => OutlineEntry)
+	readonly entries!: OutlineEntry[];
+}
+
+export class MessageTimingReport {
+	readonly name!: string;
+
+	readonly count!: number;
+
+	readonly totalMilliseconds!: number;
+
+	readonly meanMilliseconds!: number;
+
+	readonly maxMilliseconds!: number;
+}
+
+export class SampleSummary {
+	readonly count!: number;
+
+	readonly meanMilliseconds!: number;
+
+	readonly medianMilliseconds!: number;
+
+	readonly p95Milliseconds!: number;
+
+	readonly maxMilliseconds!: number;
+}
+
+export class DiagnosticsReport {
+	readonly recording!: boolean;
+
+	readonly durationMilliseconds!: number;
+
+	@Type(() => MessageTimingReport)
+	readonly messages!: MessageTimingReport[];
+
+	@Type(() => SampleSummary)
+	readonly inputLatency!: SampleSummary;
+
+	@Type(() => SampleSummary)
+	readonly frameIntervals!: SampleSummary;
+
+	readonly framesPerSecond!: number;
+}
+
+export class UpdateDiagnostics extends JsMessage {
+	@Type(() => DiagnosticsReport)
+	readonly diagnostics!: DiagnosticsReport;
+}
+
 export class UpdateDocumentOverlays extends JsMessage {
 	readonly svg!: string;
 }
@@ -449,6 +669,20 @@ export class UpdateDocumentNodeRender extends JsMessage {
 	readonly svg!: string;
 }
 
+export class UpdateDocumentLoadingProgress extends JsMessage {
+	readonly documentId!: bigint;
+
+	readonly loaded!: number;
+
+	readonly total!: number;
+}
+
+export class UpdateDocumentThumbnail extends JsMessage {
+	readonly documentId!: bigint;
+
+	readonly thumbnail!: string;
+}
+
 export class UpdateDocumentTransform extends JsMessage {
 	readonly transform!: string;
 }
@@ -471,6 +705,10 @@ export class UpdateDocumentRulers extends JsMessage {
 	readonly spacing!: number;
 
 	readonly interval!: number;
+
+	readonly flipped!: boolean;
+
+	readonly decimalSeparator!: string;
 }
 
 export class UpdateEyedropperSamplingState extends JsMessage {
@@ -481,7 +719,66 @@ export class UpdateEyedropperSamplingState extends JsMessage {
 
 	readonly secondaryColor!: string;
 
-	readonly setColorChoice!: "Primary" | "Secondary" | undefined;
+	readonly setColorChoice!: "Primary" | "Secondary" | "ColorPicker" | undefined;
+}
+
+export type ToolStatus = {
+	tool: string;
+	modifiers: string[];
+	operation: string | undefined;
+	typedValue: string | undefined;
+	measurement: string | undefined;
+};
+
+export class UpdateToolStatus extends JsMessage {
+	readonly status!: ToolStatus;
+}
+
+export class TutorialStepDetails {
+	readonly tutorial!: string;
+
+	readonly title!: string;
+
+	readonly instructions!: string;
+
+	readonly stepNumber!: number;
+
+	readonly stepCount!: number;
+}
+
+export class UpdateTutorialStep extends JsMessage {
+	@Type(() => TutorialStepDetails)
+	readonly step!: TutorialStepDetails | undefined;
+}
+
+export type TextQuery = {
+	pattern: string;
+	caseSensitive: boolean;
+	regex: boolean;
+};
+
+export class TextMatch {
+	readonly documentId!: bigint;
+
+	readonly documentName!: string;
+
+	@Transform(({ value }: { value: bigint[] }) => new BigUint64Array(value))
+	readonly layerPath!: BigUint64Array;
+
+	readonly layerName!: string;
+
+	readonly text!: string;
+
+	readonly matchRanges!: [number, number][];
+}
+
+export class UpdateTextSearchResults extends JsMessage {
+	readonly query!: TextQuery;
+
+	@Type(() => TextMatch)
+	readonly matches!: TextMatch[];
+
+	readonly error!: string | undefined;
 }
 
 const mouseCursorIconCSSNames = {
@@ -513,9 +810,34 @@ export class TriggerLoadPreferences extends JsMessage { }
 
 export class TriggerOpenDocument extends JsMessage { }
 
+export class TriggerIdleWork extends JsMessage { }
+
+export class TriggerAnimationFrame extends JsMessage { }
+
+export class TriggerBatchChooseFiles extends JsMessage { }
+
+export class TriggerCancelWindowColorSampling extends JsMessage { }
+
 export class TriggerImport extends JsMessage { }
 
-export class TriggerPaste extends JsMessage { }
+export class TriggerKeyRepeat extends JsMessage {
+	readonly repeatId!: bigint;
+
+	readonly delay!: number;
+}
+
+export type PasteMode = "InPlace" | "IntoFolder" | "AtPointer";
+
+export class TriggerPaste extends JsMessage {
+	readonly mode!: PasteMode;
+}
+
+export class TriggerCopyRaster extends JsMessage {
+	readonly svg!: string;
+
+	@TupleToVec2
+	readonly size!: XY;
+}
 
 export class TriggerCopyToClipboardBlobUrl extends JsMessage {
 	readonly blobUrl!: string;
@@ -527,6 +849,16 @@ export class TriggerDownloadBlobUrl extends JsMessage {
 	readonly blobUrl!: string;
 }
 
+export class TriggerDownloadIcon extends JsMessage {
+	readonly svg!: string;
+
+	readonly name!: string;
+
+	readonly mime!: string;
+
+	readonly sizes!: number[];
+}
+
 export class TriggerDownloadRaster extends JsMessage {
 	readonly svg!: string;
 
@@ -544,6 +876,36 @@ export class TriggerDownloadTextFile extends JsMessage {
 	readonly name!: string;
 }
 
+export class TriggerFetchDocument extends JsMessage {
+	readonly source!: string;
+}
+
+export class TriggerFetchMergedImage extends JsMessage {
+	readonly documentId!: bigint;
+
+	readonly layerPath!: BigUint64Array;
+
+	readonly url!: string;
+}
+
+export class TriggerRasterizeFileThumbnail extends JsMessage {
+	readonly documentId!: bigint;
+
+	readonly svg!: string;
+
+	readonly size!: [number, number];
+}
+
+export class TriggerRasterizeLayerEffects extends JsMessage {
+	readonly documentId!: bigint;
+
+	readonly layerPath!: BigUint64Array;
+
+	readonly svg!: string;
+
+	readonly size!: [number, number];
+}
+
 export class TriggerRasterizeRegionBelowLayer extends JsMessage {
 	readonly documentId!: bigint;
 
@@ -560,6 +922,12 @@ export class TriggerRevokeBlobUrl extends JsMessage {
 	readonly url!: string;
 }
 
+export class TriggerSampleWindowColor extends JsMessage { }
+
+export class TriggerSaveKeymap extends JsMessage {
+	readonly keymap!: string;
+}
+
 export class TriggerSavePreferences extends JsMessage {
 	readonly preferences!: Record<string, unknown>;
 }
@@ -650,6 +1018,15 @@ export class DisplayEditableTextboxTransform extends JsMessage {
 	readonly transform!: number[];
 }
 
+export class UpdateExportPreview extends JsMessage {
+	readonly svg!: string;
+
+	readonly mime!: string;
+
+	@TupleToVec2
+	readonly size!: XY;
+}
+
 export class UpdateImageData extends JsMessage {
 	readonly documentId!: bigint;
 
@@ -687,6 +1064,8 @@ export class LayerMetadata {
 	expanded!: boolean;
 
 	selected!: boolean;
+
+	aspectLocked!: boolean;
 }
 
 export type LayerType = "Folder" | "Layer";
@@ -837,6 +1216,8 @@ export class IconButton extends WidgetProps {
 
 	@Transform(({ value }: { value: string }) => value || undefined)
 	tooltip!: string | undefined;
+
+	reportsHover!: boolean;
 }
 
 export class IconLabel extends WidgetProps {
@@ -897,6 +1278,8 @@ export class NumberInput extends WidgetProps {
 
 	unit!: string;
 
+	decimalSeparator!: string;
+
 	unitIsHiddenWhenEditing!: boolean;
 
 	// Mode behavior
@@ -1336,42 +1719,71 @@ export const messageMakers: Record<string, MessageMaker> = {
 	DisplayEditableTextboxTransform,
 	DisplayRemoveEditableTextbox,
 	TriggerAboutGraphiteLocalizedCommitDate,
+	TriggerAcquireDocumentLock,
+	TriggerAnimationFrame,
+	TriggerBatchChooseFiles,
+	TriggerCancelWindowColorSampling,
+	TriggerCopyRaster,
 	TriggerCopyToClipboardBlobUrl,
 	TriggerDownloadBlobUrl,
+	TriggerDownloadIcon,
 	TriggerDownloadRaster,
 	TriggerDownloadTextFile,
+	TriggerFetchDocument,
+	TriggerFetchMergedImage,
 	TriggerFontLoad,
+	TriggerIdleWork,
 	TriggerImport,
 	TriggerIndexedDbRemoveDocument,
+	TriggerIndexedDbWriteBackup,
 	TriggerIndexedDbWriteDocument,
+	TriggerKeyRepeat,
 	TriggerLoadAutoSaveDocuments,
+	TriggerLoadBackups,
 	TriggerLoadPreferences,
+	TriggerLoadSavedVersion,
+	TriggerOpenBackup,
 	TriggerOpenDocument,
 	TriggerPaste,
+	TriggerRasterizeFileThumbnail,
+	TriggerRasterizeLayerEffects,
 	TriggerRasterizeRegionBelowLayer,
 	TriggerRefreshBoundsOfViewports,
+	TriggerReleaseDocumentLock,
 	TriggerRevokeBlobUrl,
+	TriggerSampleWindowColor,
+	TriggerSaveKeymap,
 	TriggerSavePreferences,
 	TriggerTextCommit,
 	TriggerTextCopy,
 	TriggerViewportResize,
 	TriggerVisitLink,
 	UpdateActiveDocument,
+	UpdateBatchProgress,
+	UpdateCanvasZoom,
+	UpdateColorPicker,
+	UpdateDiagnostics,
 	UpdateDialogDetails,
 	UpdateDocumentArtboards,
 	UpdateDocumentNodeRender,
 	UpdateDocumentArtwork,
+	UpdateDocumentLayerRender,
 	UpdateDocumentBarLayout,
 	UpdateDocumentLayerDetails,
 	UpdateDocumentLayerTreeStructureJs: newUpdateDocumentLayerTreeStructure,
 	UpdateDocumentModeLayout,
+	UpdateDocumentOutline,
 	UpdateDocumentOverlays,
 	UpdateDocumentRulers,
 	UpdateDocumentScrollbars,
+	UpdateDocumentLoadingProgress,
+	UpdateDocumentThumbnail,
 	UpdateDocumentTransform,
 	UpdateEyedropperSamplingState,
+	UpdateExportPreview,
 	UpdateImageData,
 	UpdateInputHints,
+	UpdateKeymap,
 	UpdateLayerTreeOptionsLayout,
 	UpdateMenuBarLayout,
 	UpdateMouseCursor,
@@ -1380,11 +1792,16 @@ export const messageMakers: Record<string, MessageMaker> = {
 	UpdateNodeGraphSelection,
 	UpdateNodeTypes,
 	UpdateOpenDocumentsList,
+	UpdatePresentationMode,
 	UpdatePropertyPanelOptionsLayout,
 	UpdatePropertyPanelSectionsLayout,
+	UpdateTextSearchResults,
 	UpdateToolOptionsLayout,
 	UpdateToolShelfLayout,
+	UpdateToolStatus,
+	UpdateTutorialStep,
 	UpdateWorkingColorsLayout,
+	UpdateWorkspaceLayout,
 	UpdateZoomWithScroll,
 } as const;
 export type JsMessageType = keyof typeof messageMakers;
@@ -11,8 +11,13 @@ use editor::application::Editor;
 use editor::consts::{FILE_SAVE_SUFFIX, GRAPHITE_DOCUMENT_VERSION};
 use editor::messages::input_mapper::utility_types::input_keyboard::ModifierKeys;
 use editor::messages::input_mapper::utility_types::input_mouse::{EditorMouseState, ScrollDelta, ViewportBounds};
+use editor::messages::input_mapper::utility_types::mapping_definition::MappingDefinition;
+use editor::messages::portfolio::batch::BatchSource;
+use editor::messages::portfolio::document::utility_types::find_replace::TextQuery;
 use editor::messages::portfolio::utility_types::Platform;
 use editor::messages::prelude::*;
+use editor::messages::tool::common_functionality::sampled_color::SampledColorSpace;
+use editor::messages::workspace::utility_types::PanelSizes;
 use graph_craft::document::NodeId;
 use graphene_core::raster::color::Color;
 
@@ -45,6 +50,18 @@ pub fn wasm_memory() -> JsValue {
 	wasm_bindgen::memory()
 }
 
+/// Reads a number typed into a number input, with either a decimal point or a decimal comma, in the same way as numbers typed into the editor.
+#[wasm_bindgen(js_name = parseNumber)]
+pub fn parse_number(text: String) -> Option<f64> {
+	editor::messages::preferences::utility_types::parse_number(&text)
+}
+
+/// Reads the thumbnail embedded in the contents of a saved document, a PNG data URL, without the cost of opening the whole document.
+#[wasm_bindgen(js_name = readDocumentThumbnail)]
+pub fn read_document_thumbnail(serialized_content: String) -> Option<String> {
+	editor::messages::portfolio::document::utility_types::file_thumbnail::read_document_thumbnail(&serialized_content)
+}
+
 // To avoid wasm-bindgen from checking mutable reference issues using WasmRefCell we must make all methods take a non mutable reference to self.
 // Not doing this creates an issue when rust calls into JS which calls back to rust in the same call stack.
 #[wasm_bindgen]
@@ -63,6 +80,14 @@ fn request_animation_frame(f: &Closure<dyn FnMut()>) {
 	window().request_animation_frame(f.as_ref().unchecked_ref()).expect("should register `requestAnimationFrame` OK");
 }
 
+/// Reads a color sent by the frontend's `Color` class, which can also represent the absence of a color.
+fn color_or_none(red: f32, green: f32, blue: f32, alpha: f32, none: bool) -> Result<Option<Color>, Error> {
+	if none {
+		return Ok(None);
+	}
+	Color::from_rgbaf32(red, green, blue, alpha).map(Some).ok_or_else(|| Error::new("Invalid color"))
+}
+
 // Sends a message to the dispatcher in the Editor Backend
 async fn poll_node_graph_evaluation() {
 	// Process no further messages after a crash to avoid spamming the console
@@ -215,6 +240,8 @@ impl JsEditorHandle {
 		};
 
 		self.dispatch(GlobalsMessage::SetPlatform { platform });
+		let _ = GLOBAL_CLOCK.set(|| window().performance().map(|performance| performance.now()).unwrap_or_default());
+		let _ = GLOBAL_DATE.set(js_sys::Date::now);
 		self.dispatch(Message::Init);
 
 		let f = std::rc::Rc::new(RefCell::new(None));
@@ -245,6 +272,13 @@ impl JsEditorHandle {
 		}
 	}
 
+	/// Lets the editor do deferred background work, like rendering layer thumbnails, while the browser is idle
+	#[wasm_bindgen(js_name = performIdleWork)]
+	pub fn perform_idle_work(&self, time_remaining: f64) {
+		let message = PortfolioMessage::PerformIdleWork { time_remaining };
+		self.dispatch(message);
+	}
+
 	/// Displays a dialog with an error message
 	#[wasm_bindgen(js_name = errorDialog)]
 	pub fn error_dialog(&self, title: String, description: String) {
@@ -296,6 +330,24 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Replaces the user's changes to the keyboard shortcuts with a keymap serialized as JSON
+	#[wasm_bindgen(js_name = setKeymap)]
+	pub fn set_keymap(&self, keymap: String) -> Result<(), JsValue> {
+		let definition = MappingDefinition::from_json(&keymap).map_err(|error| Error::new(&format!("Invalid keymap: {error}")))?;
+
+		let message = InputMapperMessage::SetMapping(definition);
+		self.dispatch(message);
+
+		Ok(())
+	}
+
+	/// Sends the user's current changes to the keyboard shortcuts, along with any problems with them, to the frontend
+	#[wasm_bindgen(js_name = reloadKeymap)]
+	pub fn reload_keymap(&self) {
+		let message = InputMapperMessage::ReloadMapping;
+		self.dispatch(message);
+	}
+
 	#[wasm_bindgen(js_name = selectDocument)]
 	pub fn select_document(&self, document_id: u64) {
 		let message = PortfolioMessage::SelectDocument { document_id };
@@ -323,6 +375,45 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Opens the file paths or URLs given at launch, once the frontend has read them from the command line or the page's URL
+	#[wasm_bindgen(js_name = openLaunchDocuments)]
+	pub fn open_launch_documents(&self, sources: Vec<String>) {
+		let message = PortfolioMessage::OpenLaunchDocuments { sources };
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = openDocumentSource)]
+	pub fn open_document_source(&self, source: String, content: String) {
+		let message = PortfolioMessage::OpenDocumentSource { source, content };
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = fetchDocumentFailed)]
+	pub fn fetch_document_failed(&self, source: String, error: String) {
+		let message = PortfolioMessage::FetchDocumentFailed { source, error };
+		self.dispatch(message);
+	}
+
+	/// Processes the documents chosen for the batch dialog, given by their file names and contents in the same order
+	#[wasm_bindgen(js_name = batchFilesChosen)]
+	pub fn batch_files_chosen(&self, names: Vec<String>, contents: Vec<String>) {
+		let sources = names.into_iter().zip(contents).map(|(name, content)| BatchSource::Content { name, content }).collect();
+		let message = BatchDialogMessage::FilesChosen { sources };
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = openDesignFile)]
+	pub fn open_design_file(&self, name: String, content: String) {
+		let message = PortfolioMessage::OpenDesignFile { name, content };
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = openSvgDocument)]
+	pub fn open_svg_document(&self, name: String, content: String) {
+		let message = PortfolioMessage::OpenSvgDocument { name, content };
+		self.dispatch(message);
+	}
+
 	#[wasm_bindgen(js_name = openAutoSavedDocument)]
 	pub fn open_auto_saved_document(&self, document_id: u64, document_name: String, document_is_saved: bool, document_serialized_content: String) {
 		let message = PortfolioMessage::OpenDocumentFileWithId {
@@ -335,6 +426,37 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	#[wasm_bindgen(js_name = compareWithSavedVersion)]
+	pub fn compare_with_saved_version(&self, document_id: u64, saved_at: String, saved_serialized_content: String) {
+		let message = PortfolioMessage::CompareWithSavedVersion {
+			document_id,
+			saved_at,
+			saved_serialized_content,
+		};
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = setDocumentLockStatus)]
+	pub fn set_document_lock_status(&self, document_id: u64, locked_elsewhere: bool) {
+		let message = PortfolioMessage::SetDocumentLockStatus { document_id, locked_elsewhere };
+		self.dispatch(message);
+	}
+
+	/// Stores the panel sizes after the user finishes resizing the workspace's panels
+	#[wasm_bindgen(js_name = setWorkspacePanelSizes)]
+	pub fn set_workspace_panel_sizes(&self, content: f64, document: f64, graph: f64, details: f64, properties: f64, layers: f64) {
+		let panel_sizes = PanelSizes {
+			content,
+			document,
+			graph,
+			details,
+			properties,
+			layers,
+		};
+		let message = WorkspaceMessage::SetPanelSizes { panel_sizes };
+		self.dispatch(message);
+	}
+
 	#[wasm_bindgen(js_name = triggerAutoSave)]
 	pub fn trigger_auto_save(&self, document_id: u64) {
 		let message = PortfolioMessage::AutoSaveDocument { document_id };
@@ -353,6 +475,16 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	#[wasm_bindgen(js_name = requestBackupsDialogWithSaveTimes)]
+	pub fn request_backups_dialog_with_save_times(&self, file_name: String, save_times: JsValue) -> Result<(), JsValue> {
+		let save_times = from_value(save_times).map_err(|error| Error::new(&format!("Invalid backup save times: {error}")))?;
+
+		let message = DialogMessage::RequestBackupsDialogWithSaveTimes { file_name, save_times };
+		self.dispatch(message);
+
+		Ok(())
+	}
+
 	/// Send new bounds when document panel viewports get resized or moved within the editor
 	/// [left, top, right, bottom]...
 	#[wasm_bindgen(js_name = boundsOfViewports)]
@@ -363,10 +495,19 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Send the number of physical screen pixels per CSS pixel when it changes, such as from browser zoom or moving to another display
+	#[wasm_bindgen(js_name = devicePixelRatio)]
+	pub fn device_pixel_ratio(&self, ratio: f64) {
+		let message = InputPreprocessorMessage::DevicePixelRatio { ratio };
+		self.dispatch(message);
+	}
+
 	/// Mouse movement within the screenspace bounds of the viewport
 	#[wasm_bindgen(js_name = onMouseMove)]
-	pub fn on_mouse_move(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
-		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+	pub fn on_mouse_move(&self, x: f64, y: f64, mouse_keys: u8, pressure: f64, time: f64, modifiers: u8) {
+		let mut editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+		editor_mouse_state.pressure = pressure;
+		editor_mouse_state.time = time;
 
 		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
 
@@ -388,8 +529,10 @@ impl JsEditorHandle {
 
 	/// A mouse button depressed within screenspace the bounds of the viewport
 	#[wasm_bindgen(js_name = onMouseDown)]
-	pub fn on_mouse_down(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
-		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+	pub fn on_mouse_down(&self, x: f64, y: f64, mouse_keys: u8, pressure: f64, time: f64, modifiers: u8) {
+		let mut editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+		editor_mouse_state.pressure = pressure;
+		editor_mouse_state.time = time;
 
 		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
 
@@ -399,8 +542,10 @@ impl JsEditorHandle {
 
 	/// A mouse button released
 	#[wasm_bindgen(js_name = onMouseUp)]
-	pub fn on_mouse_up(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
-		let editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+	pub fn on_mouse_up(&self, x: f64, y: f64, mouse_keys: u8, pressure: f64, time: f64, modifiers: u8) {
+		let mut editor_mouse_state = EditorMouseState::from_keys_and_editor_position(mouse_keys, (x, y).into());
+		editor_mouse_state.pressure = pressure;
+		editor_mouse_state.time = time;
 
 		let modifier_keys = ModifierKeys::from_bits(modifiers).expect("Invalid modifier keys");
 
@@ -408,6 +553,13 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	/// The editor lost focus to another window while keys or mouse buttons may have been held
+	#[wasm_bindgen(js_name = onFocusLost)]
+	pub fn on_focus_lost(&self) {
+		let message = InputPreprocessorMessage::FocusLost;
+		self.dispatch(message);
+	}
+
 	/// Mouse double clicked
 	#[wasm_bindgen(js_name = onDoubleClick)]
 	pub fn on_double_click(&self, x: f64, y: f64, mouse_keys: u8, modifiers: u8) {
@@ -430,6 +582,13 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	/// The time the editor asked to wait before repeating a held key has passed
+	#[wasm_bindgen(js_name = onKeyRepeat)]
+	pub fn on_key_repeat(&self, repeat_id: u64) {
+		let message = InputPreprocessorMessage::KeyRepeat { repeat_id };
+		self.dispatch(message);
+	}
+
 	/// A keyboard button released
 	#[wasm_bindgen(js_name = onKeyUp)]
 	pub fn on_key_up(&self, name: String, modifiers: u8) {
@@ -478,12 +637,141 @@ impl JsEditorHandle {
 	/// Begin sampling a pixel color from the document by entering eyedropper sampling mode
 	#[wasm_bindgen(js_name = eyedropperSampleForColorPicker)]
 	pub fn eyedropper_sample_for_color_picker(&self) -> Result<(), JsValue> {
-		let message = DialogMessage::RequestComingSoonDialog { issue: Some(832) };
+		let message = ColorPickerMessage::StartSampling;
+		self.dispatch(message);
+
+		Ok(())
+	}
+
+	/// The Eyedropper tool sampled a color for the color picker, with values on a scale from 0 to 1.
+	#[wasm_bindgen(js_name = finishColorPickerSampling)]
+	pub fn finish_color_picker_sampling(&self, red: f32, green: f32, blue: f32, alpha: f32) -> Result<(), JsValue> {
+		let Some(color) = Color::from_rgbaf32(red, green, blue, alpha) else {
+			return Err(Error::new("Invalid color").into());
+		};
+
+		let message = ColorPickerMessage::FinishSampling { color };
+		self.dispatch(message);
+
+		Ok(())
+	}
+
+	/// The user clicked a pixel in the capture of the window to sample its color, which is in the named canvas color space.
+	#[wasm_bindgen(js_name = windowColorSampled)]
+	pub fn window_color_sampled(&self, red: u8, green: u8, blue: u8, alpha: u8, color_space: String) -> Result<(), JsValue> {
+		let Some(color_space) = SampledColorSpace::from_canvas_name(&color_space) else {
+			return Err(Error::new(&format!("Unsupported color space: {color_space}")).into());
+		};
+
+		let message = EyedropperToolMessage::WindowColorSampled {
+			pixel: [red, green, blue, alpha],
+			color_space,
+		};
+		self.dispatch(message);
+
+		Ok(())
+	}
+
+	/// The window couldn't be captured for sampling a color from it, or the user closed the capture without sampling.
+	#[wasm_bindgen(js_name = windowColorSamplingCanceled)]
+	pub fn window_color_sampling_canceled(&self) -> Result<(), JsValue> {
+		let message = EyedropperToolMessage::Abort;
 		self.dispatch(message);
 
 		Ok(())
 	}
 
+	/// The color picker was opened for the given target, showing a color with values on a scale from 0 to 1.
+	#[wasm_bindgen(js_name = openColorPicker)]
+	pub fn open_color_picker(&self, target: JsValue, red: f32, green: f32, blue: f32, alpha: f32, none: bool) -> Result<(), JsValue> {
+		let target = from_value(target).map_err(|error| Error::new(&format!("Invalid color picker target: {error}")))?;
+		let color = color_or_none(red, green, blue, alpha, none)?;
+
+		let message = ColorPickerMessage::Open { target, color };
+		self.dispatch(message);
+
+		Ok(())
+	}
+
+	/// The color picker's color was changed, with values on a scale from 0 to 1.
+	#[wasm_bindgen(js_name = setColorPickerColor)]
+	pub fn set_color_picker_color(&self, red: f32, green: f32, blue: f32, alpha: f32, none: bool) -> Result<(), JsValue> {
+		let color = color_or_none(red, green, blue, alpha, none)?;
+
+		let message = ColorPickerMessage::SetColor { color };
+		self.dispatch(message);
+
+		Ok(())
+	}
+
+	/// Swap the color picker's color with the one it was opened with
+	#[wasm_bindgen(js_name = swapColorPickerWithPrevious)]
+	pub fn swap_color_picker_with_previous(&self) {
+		let message = ColorPickerMessage::SwapWithPrevious;
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = closeColorPicker)]
+	pub fn close_color_picker(&self) {
+		let message = ColorPickerMessage::Close;
+		self.dispatch(message);
+	}
+
+	/// Move on to the next step of the running tutorial without doing what the current one asks for
+	#[wasm_bindgen(js_name = skipTutorialStep)]
+	pub fn skip_tutorial_step(&self) {
+		let message = TutorialMessage::NextStep;
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = exitTutorial)]
+	pub fn exit_tutorial(&self) {
+		let message = TutorialMessage::Exit;
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = selectRecentColor)]
+	pub fn select_recent_color(&self, index: usize) {
+		let message = ColorPickerMessage::SelectRecentColor { index };
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = selectColorPalette)]
+	pub fn select_color_palette(&self, index: usize) {
+		let message = ColorPickerMessage::SelectPalette { index };
+		self.dispatch(message);
+	}
+
+	#[wasm_bindgen(js_name = selectPaletteColor)]
+	pub fn select_palette_color(&self, index: usize) {
+		let message = ColorPickerMessage::SelectPaletteColor { index };
+		self.dispatch(message);
+	}
+
+	/// Add the color picker's color to the active palette
+	#[wasm_bindgen(js_name = addColorToPalette)]
+	pub fn add_color_to_palette(&self) {
+		let message = ColorPickerMessage::AddToPalette;
+		self.dispatch(message);
+	}
+
+	/// Add a palette of colors in harmony with the primary working color, following a rule like "Complementary" or "Triadic"
+	#[wasm_bindgen(js_name = generateHarmonyPalette)]
+	pub fn generate_harmony_palette(&self, rule: JsValue, hue_spread: f64, lightness_steps: u32) -> Result<(), JsValue> {
+		let rule = from_value(rule).map_err(|error| Error::new(&format!("Invalid color harmony rule: {error}")))?;
+
+		let message = ColorPickerMessage::GenerateHarmonyPalette { rule, hue_spread, lightness_steps };
+		self.dispatch(message);
+
+		Ok(())
+	}
+
+	#[wasm_bindgen(js_name = removeColorFromPalette)]
+	pub fn remove_color_from_palette(&self, index: usize) {
+		let message = ColorPickerMessage::RemoveFromPalette { index };
+		self.dispatch(message);
+	}
+
 	/// Update primary color with values on a scale from 0 to 1.
 	#[wasm_bindgen(js_name = updatePrimaryColor)]
 	pub fn update_primary_color(&self, red: f32, green: f32, blue: f32, alpha: f32) -> Result<(), JsValue> {
@@ -512,11 +800,15 @@ impl JsEditorHandle {
 		Ok(())
 	}
 
-	/// Paste layers from a serialized json representation
+	/// Paste layers from a serialized json representation, placed according to the given paste mode
 	#[wasm_bindgen(js_name = pasteSerializedData)]
-	pub fn paste_serialized_data(&self, data: String) {
-		let message = PortfolioMessage::PasteSerializedData { data };
+	pub fn paste_serialized_data(&self, data: String, mode: JsValue) -> Result<(), JsValue> {
+		let mode = from_value(mode).map_err(|error| Error::new(&format!("Invalid paste mode: {error}")))?;
+
+		let message = PortfolioMessage::PasteSerializedData { data, mode };
 		self.dispatch(message);
+
+		Ok(())
 	}
 
 	/// Modify the layer selection based on the layer which is clicked while holding down the <kbd>Ctrl</kbd> and/or <kbd>Shift</kbd> modifier keys used for range selection behavior
@@ -526,6 +818,43 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Select a layer and move the viewport to show it, such as when picking a result of the document text search
+	#[wasm_bindgen(js_name = revealLayer)]
+	pub fn reveal_layer(&self, layer_path: Vec<LayerId>) {
+		let message = DocumentMessage::RevealLayer { layer_path };
+		self.dispatch(message);
+	}
+
+	/// List the folders and text layers of the document, keeping only the text layers that contain the query if it isn't empty
+	#[wasm_bindgen(js_name = searchDocumentOutline)]
+	pub fn search_document_outline(&self, query: String) {
+		let message = DocumentMessage::SearchOutline { query };
+		self.dispatch(message);
+	}
+
+	/// Search the text layers of every open document for the pattern, matched literally unless it is a regular expression
+	#[wasm_bindgen(js_name = findText)]
+	pub fn find_text(&self, pattern: String, case_sensitive: bool, regex: bool) {
+		let query = TextQuery { pattern, case_sensitive, regex };
+		let message = PortfolioMessage::FindText { query };
+		self.dispatch(message);
+	}
+
+	/// Replace every match of the pattern in the text layers of every open document, as one undo step per document
+	#[wasm_bindgen(js_name = replaceAllText)]
+	pub fn replace_all_text(&self, pattern: String, case_sensitive: bool, regex: bool, replacement: String) {
+		let query = TextQuery { pattern, case_sensitive, regex };
+		let message = PortfolioMessage::ReplaceAllText { query, replacement };
+		self.dispatch(message);
+	}
+
+	/// Switch to the document of a text search result and select its layer, moving the viewport to show it
+	#[wasm_bindgen(js_name = selectTextMatch)]
+	pub fn select_text_match(&self, document_id: u64, layer_path: Vec<LayerId>) {
+		let message = PortfolioMessage::SelectTextMatch { document_id, layer_path };
+		self.dispatch(message);
+	}
+
 	/// Deselect all layers
 	#[wasm_bindgen(js_name = deselectAllLayers)]
 	pub fn deselect_all_layers(&self) {
@@ -551,6 +880,27 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Sends the whole artwork to the viewport, such as when it was recreated or a changed layer couldn't be found in it
+	#[wasm_bindgen(js_name = renderFullDocument)]
+	pub fn render_full_document(&self) {
+		let message = DocumentMessage::RenderFullDocument;
+		self.dispatch(message);
+	}
+
+	/// Pans the canvas while a drag is held near the edge of the viewport, called on every animation frame of the drag
+	#[wasm_bindgen(js_name = autoScrollCanvas)]
+	pub fn auto_scroll_canvas(&self) {
+		let message = NavigationMessage::AutoScroll;
+		self.dispatch(message);
+	}
+
+	/// Advances the animated view of the canvas, called on the animation frame requested by the backend while an animation is running
+	#[wasm_bindgen(js_name = animateCanvas)]
+	pub fn animate_canvas(&self) {
+		let message = NavigationMessage::AnimationFrame;
+		self.dispatch(message);
+	}
+
 	/// Translates document (in viewport coords)
 	#[wasm_bindgen(js_name = translateCanvas)]
 	pub fn translate_canvas(&self, delta_x: f64, delta_y: f64) {
@@ -598,6 +948,27 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Downloads the document being saved along with the thumbnail JS rasterized for it, or without one if that failed
+	#[wasm_bindgen(js_name = downloadDocumentWithThumbnail)]
+	pub fn download_document_with_thumbnail(&self, document_id: u64, thumbnail: Option<String>) {
+		let message = PortfolioMessage::DocumentPassMessage {
+			document_id,
+			message: DocumentMessage::DownloadDocument { thumbnail }.into(),
+		};
+		self.dispatch(message);
+	}
+
+	/// Replaces the layer with the image of its rasterized effects, which JS rasterized from the SVG given to it
+	#[wasm_bindgen(js_name = flattenedLayerEffectsRasterized)]
+	pub fn flattened_layer_effects_rasterized(&self, document_id: u64, layer_path: Vec<LayerId>, image_data: Vec<u8>, width: u32, height: u32) {
+		let image = graphene_core::raster::Image::from_image_data(&image_data, width, height);
+		let message = PortfolioMessage::DocumentPassMessage {
+			document_id,
+			message: DocumentMessage::FlattenedLayerEffectsRasterized { layer_path, image }.into(),
+		};
+		self.dispatch(message);
+	}
+
 	/// Notifies the backend that the user connected a node's primary output to one of another node's inputs
 	#[wasm_bindgen(js_name = connectNodesByLink)]
 	pub fn connect_nodes_by_link(&self, output_node: u64, output_node_connector_index: usize, input_node: u64, input_node_connector_index: usize) {
@@ -691,6 +1062,17 @@ impl JsEditorHandle {
 		self.dispatch(message);
 	}
 
+	/// Fills in an image placeholder of a data merge with the image fetched from the URL in its row of data
+	#[wasm_bindgen(js_name = setMergedImage)]
+	pub fn set_merged_image(&self, document_id: u64, layer_path: Vec<LayerId>, image_data: Vec<u8>, width: u32, height: u32) {
+		let image = graphene_core::raster::Image::from_image_data(&image_data, width, height);
+		let message = PortfolioMessage::DocumentPassMessage {
+			document_id,
+			message: GraphOperationMessage::ImageSet { layer: layer_path, image }.into(),
+		};
+		self.dispatch(message);
+	}
+
 	/// Toggle visibility of a layer from the layer list
 	#[wasm_bindgen(js_name = toggleLayerVisibility)]
 	pub fn toggle_layer_visibility(&self, layer_path: Vec<LayerId>) {
@@ -0,0 +1,25 @@
+//! Opens arbitrary bytes as a Graphite document, which must fail with an error when they aren't a valid document rather than panic or
+//! run out of memory. Valid documents are also rendered, since a document that opens must be usable.
+//!
+//! Run with `cargo +nightly fuzz run document_deserialization -- -rss_limit_mb=2048` from the `fuzz` directory, optionally seeded with
+//! saved documents like `editor/graphite-test-document.graphite`.
+
+#![no_main]
+
+use document_legacy::layers::style::{RenderData, ViewMode};
+use editor::messages::portfolio::document::DocumentMessageHandler;
+use graphene_std::text::FontCache;
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+	let Ok(content) = std::str::from_utf8(data) else { return };
+	let Ok((mut document, _)) = DocumentMessageHandler::with_name_and_content("Fuzzed Document".to_string(), content.to_string()) else {
+		return;
+	};
+
+	let font_cache = FontCache::default();
+	let render_data = RenderData::new(&font_cache, ViewMode::Normal, None);
+	document.document_legacy.render_root(&render_data);
+	document.artboard_message_handler.artboards_document.render_root(&render_data);
+});
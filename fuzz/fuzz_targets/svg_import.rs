@@ -0,0 +1,26 @@
+//! Imports arbitrary bytes as an SVG file, which must fail with an error when they aren't valid SVG rather than panic or run out of memory.
+//! The imported layers are also added to a document and rendered, since whatever is imported must be usable.
+//!
+//! Run with `cargo +nightly fuzz run svg_import -- -rss_limit_mb=2048` from the `fuzz` directory, optionally seeded with SVG files.
+
+#![no_main]
+
+use document_legacy::document::Document;
+use document_legacy::layers::style::{RenderData, ViewMode};
+use document_legacy::svg_import::import_svg;
+use graphene_std::text::FontCache;
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+	let Ok(svg) = std::str::from_utf8(data) else { return };
+	let Ok(import) = import_svg(svg) else { return };
+
+	let mut document = Document::default();
+	for layer in import.layers {
+		let _ = document.add_layer(&[], layer, -1);
+	}
+
+	let font_cache = FontCache::default();
+	document.render_root(&RenderData::new(&font_cache, ViewMode::Normal, None));
+});
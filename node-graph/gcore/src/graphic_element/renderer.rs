@@ -136,6 +136,16 @@ impl GraphicElementRendered for VectorData {
 			let style = self.style.render(render_params.view_mode, &mut render.svg_defs, render.transform, layer_bounds, transformed_bounds);
 			attributes.push_val(style);
 		});
+
+		if let Some(stroke) = self.style.stroke().filter(|_| render_params.view_mode != crate::vector::style::ViewMode::Outline) {
+			if let Some(outline) = stroke.width_profile_outline(&self.subpaths, self.transform * render.transform) {
+				render.leaf_tag("path", |attributes| {
+					attributes.push("class", "vector-data-width-profile");
+					attributes.push("d", outline);
+					attributes.push_val(stroke.render_outline_fill());
+				});
+			}
+		}
 	}
 	fn bounding_box(&self, transform: DAffine2) -> Option<[DVec2; 2]> {
 		self.bounding_box_with_transform(self.transform * transform)
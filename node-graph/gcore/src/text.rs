@@ -8,14 +8,16 @@ pub use to_path::*;
 
 use crate::Node;
 
-pub struct TextGenerator<Text, FontName, Size> {
+pub struct TextGenerator<Text, FontName, Size, Kerning> {
 	text: Text,
 	font_name: FontName,
 	font_size: Size,
+	kerning: Kerning,
 }
 
+/// The kerning is the manual adjustment added after each character of the text, in thousandths of an em.
 #[node_fn(TextGenerator)]
-fn generate_text<'a: 'input, T>(editor: EditorApi<'a, T>, text: String, font_name: Font, font_size: f32) -> crate::vector::VectorData {
+fn generate_text<'a: 'input, T>(editor: EditorApi<'a, T>, text: String, font_name: Font, font_size: f32, kerning: Vec<f32>) -> crate::vector::VectorData {
 	let buzz_face = editor.font_cache.get(&font_name).map(|data| load_face(data));
-	crate::vector::VectorData::from_subpaths(to_path(&text, buzz_face, font_size as f64, None))
+	crate::vector::VectorData::from_subpaths(to_path(&text, buzz_face, font_size as f64, None, &kerning))
 }
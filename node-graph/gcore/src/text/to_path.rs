@@ -52,6 +52,48 @@ impl OutlineBuilder for Builder {
 	}
 }
 
+/// Kerning adjustments are in thousandths of an em, the usual unit for manual kerning, so they scale along with the font size.
+pub const KERNING_UNITS_PER_EM: f64 = 1000.;
+
+/// Looks up the manual kerning after each character of a text, given as the offset added between every character and the one following it.
+struct Kerning<'a> {
+	adjustments: &'a [f32],
+	/// The byte offsets where each character starts, which shaping refers to the characters by.
+	character_starts: Vec<usize>,
+	font_size: f64,
+}
+
+impl<'a> Kerning<'a> {
+	fn new(str: &str, adjustments: &'a [f32], font_size: f64) -> Self {
+		let character_starts = str.char_indices().map(|(start, _)| start).collect();
+		Self {
+			adjustments,
+			character_starts,
+			font_size,
+		}
+	}
+
+	/// The index of the character starting at (or containing) the byte offset.
+	fn character_index(&self, byte: usize) -> usize {
+		self.character_starts.binary_search(&byte).unwrap_or_else(|index| index.saturating_sub(1))
+	}
+
+	/// The extra distance after the glyph of the character at the byte offset, if it's the last glyph drawn for that character.
+	fn after(&self, byte: usize, next_byte: Option<usize>) -> f64 {
+		if self.adjustments.is_empty() || next_byte == Some(byte) {
+			return 0.;
+		}
+		let adjustment = self.adjustments.get(self.character_index(byte)).copied().unwrap_or_default();
+		adjustment as f64 * self.font_size / KERNING_UNITS_PER_EM
+	}
+}
+
+/// The byte offsets of the clusters of the shaped glyphs within the whole text, given where the shaped word starts, paired with that of the following glyph.
+fn glyph_clusters(glyph_buffer: &GlyphBuffer, word_start: usize) -> Vec<(usize, Option<usize>)> {
+	let clusters = glyph_buffer.glyph_infos().iter().map(|info| word_start + info.cluster as usize).collect::<Vec<_>>();
+	clusters.iter().enumerate().map(|(index, &cluster)| (cluster, clusters.get(index + 1).copied())).collect()
+}
+
 fn font_properties(buzz_face: &rustybuzz::Face, font_size: f64) -> (f64, f64, UnicodeBuffer) {
 	let scale = (buzz_face.units_per_em() as f64).recip() * font_size;
 	let line_height = font_size;
@@ -79,7 +121,8 @@ fn wrap_word(line_width: Option<f64>, glyph_buffer: &GlyphBuffer, scale: f64, x_
 	false
 }
 
-pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, line_width: Option<f64>) -> Vec<Subpath<ManipulatorGroupId>> {
+/// Converts the text to the outlines of its glyphs. The kerning is the manual adjustment (in thousandths of an em) added after each character.
+pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, line_width: Option<f64>, kerning: &[f32]) -> Vec<Subpath<ManipulatorGroupId>> {
 	let buzz_face = match buzz_face {
 		Some(face) => face,
 		// Show blank layer if font has not loaded
@@ -97,7 +140,9 @@ pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, li
 		scale,
 		id: ManipulatorGroupId::ZERO,
 	};
+	let kerning = Kerning::new(str, kerning, font_size);
 
+	let mut word_start = 0;
 	for line in str.split('\n') {
 		let length = line.split(' ').count();
 		for (index, word) in line.split(' ').enumerate() {
@@ -108,7 +153,8 @@ pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, li
 				builder.pos = DVec2::new(0., builder.pos.y + line_height);
 			}
 
-			for (glyph_position, glyph_info) in glyph_buffer.glyph_positions().iter().zip(glyph_buffer.glyph_infos()) {
+			let clusters = glyph_clusters(&glyph_buffer, word_start);
+			for ((glyph_position, glyph_info), (cluster, next_cluster)) in glyph_buffer.glyph_positions().iter().zip(glyph_buffer.glyph_infos()).zip(clusters) {
 				if let Some(line_width) = line_width {
 					if builder.pos.x + (glyph_position.x_advance as f64 * builder.scale) >= line_width {
 						builder.pos = DVec2::new(0., builder.pos.y + line_height);
@@ -121,16 +167,19 @@ pub fn to_path(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, li
 				}
 
 				builder.pos += DVec2::new(glyph_position.x_advance as f64, glyph_position.y_advance as f64) * builder.scale;
+				builder.pos.x += kerning.after(cluster, next_cluster);
 			}
 
 			buffer = glyph_buffer.clear();
+			// Past the word and the space or line break after it
+			word_start += word.len() + 1;
 		}
 		builder.pos = DVec2::new(0., builder.pos.y + line_height);
 	}
 	builder.other_subpaths
 }
 
-pub fn bounding_box(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, line_width: Option<f64>) -> DVec2 {
+pub fn bounding_box(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, line_width: Option<f64>, kerning: &[f32]) -> DVec2 {
 	let buzz_face = match buzz_face {
 		Some(face) => face,
 		// Show blank layer if font has not loaded
@@ -141,7 +190,9 @@ pub fn bounding_box(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f6
 
 	let mut pos = DVec2::ZERO;
 	let mut bounds = DVec2::ZERO;
+	let kerning = Kerning::new(str, kerning, font_size);
 
+	let mut word_start = 0;
 	for line in str.split('\n') {
 		let length = line.split(' ').count();
 		for (index, word) in line.split(' ').enumerate() {
@@ -153,17 +204,19 @@ pub fn bounding_box(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f6
 				pos = DVec2::new(0., pos.y + line_height);
 			}
 
-			for glyph_position in glyph_buffer.glyph_positions() {
+			for (glyph_position, (cluster, next_cluster)) in glyph_buffer.glyph_positions().iter().zip(glyph_clusters(&glyph_buffer, word_start)) {
 				if let Some(line_width) = line_width {
 					if pos.x + (glyph_position.x_advance as f64 * scale) >= line_width {
 						pos = DVec2::new(0., pos.y + line_height);
 					}
 				}
 				pos += DVec2::new(glyph_position.x_advance as f64, glyph_position.y_advance as f64) * scale;
+				pos.x += kerning.after(cluster, next_cluster);
 			}
 			bounds = bounds.max(pos + DVec2::new(0., line_height));
 
 			buffer = glyph_buffer.clear();
+			word_start += word.len() + 1;
 		}
 		pos = DVec2::new(0., pos.y + line_height);
 	}
@@ -171,6 +224,57 @@ pub fn bounding_box(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f6
 	bounds
 }
 
+/// Where the gap after each character of the text begins, at the top of its line, along with the height of the line. The gaps are where
+/// manual kerning is added, so these mark the pairs of characters being kerned. Characters drawn without any glyph of their own, like
+/// line breaks, have no gap.
+pub fn kerning_gaps(str: &str, buzz_face: Option<rustybuzz::Face>, font_size: f64, kerning: &[f32]) -> Vec<(usize, DVec2, f64)> {
+	let Some(buzz_face) = buzz_face else { return Vec::new() };
+	let (scale, line_height, mut buffer) = font_properties(&buzz_face, font_size);
+	let kerning = Kerning::new(str, kerning, font_size);
+
+	let mut gaps = Vec::new();
+	let mut pos = DVec2::ZERO;
+	let mut word_start = 0;
+	for line in str.split('\n') {
+		let length = line.split(' ').count();
+		for (index, word) in line.split(' ').enumerate() {
+			push_str(&mut buffer, word, index != length - 1);
+			let glyph_buffer = rustybuzz::shape(&buzz_face, &[], buffer);
+
+			for (glyph_position, (cluster, next_cluster)) in glyph_buffer.glyph_positions().iter().zip(glyph_clusters(&glyph_buffer, word_start)) {
+				pos += DVec2::new(glyph_position.x_advance as f64, glyph_position.y_advance as f64) * scale;
+				if next_cluster != Some(cluster) {
+					gaps.push((kerning.character_index(cluster), pos, line_height));
+				}
+				pos.x += kerning.after(cluster, next_cluster);
+			}
+
+			buffer = glyph_buffer.clear();
+			word_start += word.len() + 1;
+		}
+		pos = DVec2::new(0., pos.y + line_height);
+	}
+
+	gaps
+}
+
+/// The IDs of every glyph needed to draw the text, which are those chosen when shaping it along with the glyph each of its characters maps to.
+pub fn glyph_ids(str: &str, buzz_face: &rustybuzz::Face) -> Vec<u16> {
+	let mut glyph_ids: Vec<u16> = str.chars().filter_map(|character| buzz_face.glyph_index(character)).map(|glyph_id| glyph_id.0).collect();
+
+	let mut buffer = UnicodeBuffer::new();
+	for line in str.split('\n') {
+		buffer.push_str(line);
+		let glyph_buffer = rustybuzz::shape(buzz_face, &[], buffer);
+		glyph_ids.extend(glyph_buffer.glyph_infos().iter().map(|glyph_info| glyph_info.glyph_id as u16));
+		buffer = glyph_buffer.clear();
+	}
+
+	glyph_ids.sort_unstable();
+	glyph_ids.dedup();
+	glyph_ids
+}
+
 pub fn load_face(data: &[u8]) -> rustybuzz::Face {
 	rustybuzz::Face::from_slice(data, 0).expect("Loading font failed")
 }
@@ -0,0 +1,478 @@
+//! Encoders turning text into the dark and light modules of scannable codes, which the generator nodes then draw as vector shapes.
+
+/// A QR code symbol, encoded in byte mode with the medium error correction level (recovering from up to 15% damage).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrCode {
+	size: usize,
+	modules: Vec<bool>,
+	/// Whether each module belongs to the finder, timing, alignment, format, or version patterns rather than the data.
+	function: Vec<bool>,
+}
+
+/// Error correction codewords per block for each version at the medium error correction level, indexed by version.
+const QR_ECC_CODEWORDS_PER_BLOCK: [usize; 41] = [
+	0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26, 30, 22, 22, 24, 24, 28, 28, 26, 26, 26, 26, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28, 28,
+];
+/// Number of error correction blocks the codewords are split into for each version at the medium error correction level, indexed by version.
+const QR_ERROR_CORRECTION_BLOCKS: [usize; 41] = [
+	0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5, 5, 8, 9, 9, 10, 10, 11, 13, 14, 16, 17, 17, 18, 20, 21, 23, 25, 26, 28, 29, 31, 33, 35, 37, 38, 40, 43, 45, 47, 49,
+];
+/// The two bits identifying the medium error correction level in the format information.
+const QR_MEDIUM_FORMAT_BITS: u32 = 0;
+
+impl QrCode {
+	/// Encodes the bytes in the smallest QR code version that fits them, or returns `None` if they exceed the capacity of the largest version.
+	pub fn encode(data: &[u8]) -> Option<Self> {
+		let version = (1..=40).find(|&version| 4 + Self::character_count_bits(version) + data.len() * 8 <= Self::data_codewords(version) * 8)?;
+
+		// Byte mode indicator, character count, and the data itself
+		let mut bits = BitBuffer::default();
+		bits.append(0b0100, 4);
+		bits.append(data.len() as u32, Self::character_count_bits(version));
+		for &byte in data {
+			bits.append(byte as u32, 8);
+		}
+
+		// Terminator, padding to a whole byte, then alternating pad bytes to fill the capacity
+		let capacity = Self::data_codewords(version) * 8;
+		bits.append(0, (capacity - bits.len()).min(4));
+		bits.append(0, (8 - bits.len() % 8) % 8);
+		for &pad in [0xEC, 0x11].iter().cycle() {
+			if bits.len() >= capacity {
+				break;
+			}
+			bits.append(pad, 8);
+		}
+
+		let codewords = Self::add_error_correction(version, &bits.into_bytes());
+
+		let size = version * 4 + 17;
+		let mut qr_code = Self {
+			size,
+			modules: vec![false; size * size],
+			function: vec![false; size * size],
+		};
+		qr_code.draw_function_patterns(version);
+		qr_code.draw_codewords(&codewords);
+
+		// Keep the mask that leaves the fewest patterns confusing to scanners
+		let best_mask = (0..8)
+			.min_by_key(|&mask| {
+				let mut candidate = qr_code.clone();
+				candidate.apply_mask(mask);
+				candidate.draw_format_bits(mask);
+				candidate.penalty_score()
+			})
+			.unwrap_or_default();
+		qr_code.apply_mask(best_mask);
+		qr_code.draw_format_bits(best_mask);
+
+		Some(qr_code)
+	}
+
+	/// The number of modules along each side, not including the quiet zone that should be left around the symbol.
+	pub fn size(&self) -> usize {
+		self.size
+	}
+
+	/// Whether the module at column `x` and row `y` is dark.
+	pub fn module(&self, x: usize, y: usize) -> bool {
+		self.modules[y * self.size + x]
+	}
+
+	fn character_count_bits(version: usize) -> usize {
+		if version <= 9 {
+			8
+		} else {
+			16
+		}
+	}
+
+	/// The number of modules available for data and error correction codewords after all function patterns are placed.
+	fn raw_data_modules(version: usize) -> usize {
+		let mut result = (16 * version + 128) * version + 64;
+		if version >= 2 {
+			let alignment_count = version / 7 + 2;
+			result -= (25 * alignment_count - 10) * alignment_count - 55;
+			if version >= 7 {
+				result -= 36;
+			}
+		}
+		result
+	}
+
+	fn data_codewords(version: usize) -> usize {
+		Self::raw_data_modules(version) / 8 - QR_ECC_CODEWORDS_PER_BLOCK[version] * QR_ERROR_CORRECTION_BLOCKS[version]
+	}
+
+	/// Splits the data into blocks, appends the Reed-Solomon error correction codewords to each, and interleaves the blocks.
+	fn add_error_correction(version: usize, data: &[u8]) -> Vec<u8> {
+		let block_count = QR_ERROR_CORRECTION_BLOCKS[version];
+		let ecc_length = QR_ECC_CODEWORDS_PER_BLOCK[version];
+		let raw_codewords = Self::raw_data_modules(version) / 8;
+		let short_block_count = block_count - raw_codewords % block_count;
+		let short_block_length = raw_codewords / block_count;
+		let divisor = reed_solomon_divisor(ecc_length);
+
+		let mut blocks = Vec::with_capacity(block_count);
+		let mut start = 0;
+		for index in 0..block_count {
+			let data_length = short_block_length - ecc_length + usize::from(index >= short_block_count);
+			let block_data = &data[start..start + data_length];
+			start += data_length;
+
+			let mut block = block_data.to_vec();
+			// Short blocks get a placeholder so every block lines up for interleaving
+			if index < short_block_count {
+				block.push(0);
+			}
+			block.extend(reed_solomon_remainder(block_data, &divisor));
+			blocks.push(block);
+		}
+
+		let mut result = Vec::with_capacity(raw_codewords);
+		for position in 0..blocks[0].len() {
+			for (index, block) in blocks.iter().enumerate() {
+				if position != short_block_length - ecc_length || index >= short_block_count {
+					result.push(block[position]);
+				}
+			}
+		}
+		result
+	}
+
+	fn set_function_module(&mut self, x: usize, y: usize, dark: bool) {
+		let index = y * self.size + x;
+		self.modules[index] = dark;
+		self.function[index] = true;
+	}
+
+	fn draw_function_patterns(&mut self, version: usize) {
+		let size = self.size;
+
+		// Timing patterns
+		for i in 0..size {
+			self.set_function_module(6, i, i % 2 == 0);
+			self.set_function_module(i, 6, i % 2 == 0);
+		}
+
+		// Finder patterns and their separators in three corners
+		for (x, y) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+			for dy in -4_isize..=4 {
+				for dx in -4_isize..=4 {
+					let (module_x, module_y) = (x as isize + dx, y as isize + dy);
+					if (0..size as isize).contains(&module_x) && (0..size as isize).contains(&module_y) {
+						let distance = dx.abs().max(dy.abs());
+						self.set_function_module(module_x as usize, module_y as usize, distance != 2 && distance != 4);
+					}
+				}
+			}
+		}
+
+		// Alignment patterns, except where they would overlap the finder patterns
+		let positions = Self::alignment_pattern_positions(version);
+		let last = positions.len().saturating_sub(1);
+		for (i, &x) in positions.iter().enumerate() {
+			for (j, &y) in positions.iter().enumerate() {
+				if (i == 0 && j == 0) || (i == 0 && j == last) || (i == last && j == 0) {
+					continue;
+				}
+				for dy in -2_isize..=2 {
+					for dx in -2_isize..=2 {
+						self.set_function_module((x as isize + dx) as usize, (y as isize + dy) as usize, dx.abs().max(dy.abs()) != 1);
+					}
+				}
+			}
+		}
+
+		// Reserve the format areas until the mask is chosen
+		self.draw_format_bits(0);
+
+		// Version information in the two 6x3 blocks beside the finder patterns
+		if version >= 7 {
+			let mut remainder = version as u32;
+			for _ in 0..12 {
+				remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+			}
+			let bits = (version as u32) << 12 | remainder;
+			for i in 0..18 {
+				let dark = (bits >> i) & 1 != 0;
+				let (a, b) = (size - 11 + i % 3, i / 3);
+				self.set_function_module(a, b, dark);
+				self.set_function_module(b, a, dark);
+			}
+		}
+	}
+
+	fn alignment_pattern_positions(version: usize) -> Vec<usize> {
+		if version == 1 {
+			return Vec::new();
+		}
+		let count = version / 7 + 2;
+		let step = (version * 8 + count * 3 + 5) / (count * 4 - 4) * 2;
+		let mut positions: Vec<usize> = (0..count - 1).map(|i| version * 4 + 17 - 7 - i * step).collect();
+		positions.push(6);
+		positions.reverse();
+		positions
+	}
+
+	/// Writes the error correction level and mask, with their error correcting code, into both copies of the format information.
+	fn draw_format_bits(&mut self, mask: u32) {
+		let data = QR_MEDIUM_FORMAT_BITS << 3 | mask;
+		let mut remainder = data;
+		for _ in 0..10 {
+			remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+		}
+		let bits = (data << 10 | remainder) ^ 0x5412;
+		let bit = |i: usize| (bits >> i) & 1 != 0;
+		let size = self.size;
+
+		// Around the top left finder pattern
+		for i in 0..=5 {
+			self.set_function_module(8, i, bit(i));
+		}
+		self.set_function_module(8, 7, bit(6));
+		self.set_function_module(8, 8, bit(7));
+		self.set_function_module(7, 8, bit(8));
+		for i in 9..15 {
+			self.set_function_module(14 - i, 8, bit(i));
+		}
+
+		// Split between the other two finder patterns
+		for i in 0..8 {
+			self.set_function_module(size - 1 - i, 8, bit(i));
+		}
+		for i in 8..15 {
+			self.set_function_module(8, size - 15 + i, bit(i));
+		}
+		self.set_function_module(8, size - 8, true);
+	}
+
+	/// Fills the data area in the zigzag order of two-module-wide columns, moving upwards and downwards in turn from the right edge.
+	fn draw_codewords(&mut self, codewords: &[u8]) {
+		let size = self.size as isize;
+		let total_bits = codewords.len() * 8;
+		let mut bit_index = 0;
+
+		let mut right = size - 1;
+		while right >= 1 {
+			// Skip the vertical timing pattern
+			if right == 6 {
+				right = 5;
+			}
+			let upward = (right + 1) & 2 == 0;
+			for vertical in 0..size {
+				for offset in 0..2 {
+					let x = (right - offset) as usize;
+					let y = if upward { size - 1 - vertical } else { vertical } as usize;
+					let index = y * self.size + x;
+					if !self.function[index] && bit_index < total_bits {
+						self.modules[index] = (codewords[bit_index / 8] >> (7 - bit_index % 8)) & 1 != 0;
+						bit_index += 1;
+					}
+				}
+			}
+			right -= 2;
+		}
+	}
+
+	fn apply_mask(&mut self, mask: u32) {
+		for y in 0..self.size {
+			for x in 0..self.size {
+				let invert = match mask {
+					0 => (x + y) % 2 == 0,
+					1 => y % 2 == 0,
+					2 => x % 3 == 0,
+					3 => (x + y) % 3 == 0,
+					4 => (x / 3 + y / 2) % 2 == 0,
+					5 => x * y % 2 + x * y % 3 == 0,
+					6 => (x * y % 2 + x * y % 3) % 2 == 0,
+					_ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+				};
+				let index = y * self.size + x;
+				if invert && !self.function[index] {
+					self.modules[index] ^= true;
+				}
+			}
+		}
+	}
+
+	/// Scores the patterns that make a symbol harder to scan: long runs, solid blocks, finder lookalikes, and unbalanced darkness.
+	fn penalty_score(&self) -> usize {
+		let size = self.size;
+		let mut score = 0;
+
+		for horizontal in [true, false] {
+			let line = |outer: usize, inner: usize| if horizontal { self.module(inner, outer) } else { self.module(outer, inner) };
+			for outer in 0..size {
+				// Runs of five or more modules of the same color
+				let mut run_length = 1;
+				for inner in 1..size {
+					if line(outer, inner) == line(outer, inner - 1) {
+						run_length += 1;
+					} else {
+						if run_length >= 5 {
+							score += run_length - 2;
+						}
+						run_length = 1;
+					}
+				}
+				if run_length >= 5 {
+					score += run_length - 2;
+				}
+
+				// The 1:1:3:1:1 ratio of the finder patterns with four light modules on either side
+				const FINDER_LIKE: [bool; 11] = [true, false, true, true, true, false, true, false, false, false, false];
+				for inner in 0..size.saturating_sub(10) {
+					let matches = |reversed: bool| (0..11).all(|i| line(outer, inner + i) == FINDER_LIKE[if reversed { 10 - i } else { i }]);
+					if matches(false) || matches(true) {
+						score += 40;
+					}
+				}
+			}
+		}
+
+		// Blocks of 2x2 modules of the same color
+		for y in 0..size - 1 {
+			for x in 0..size - 1 {
+				let color = self.module(x, y);
+				if color == self.module(x + 1, y) && color == self.module(x, y + 1) && color == self.module(x + 1, y + 1) {
+					score += 3;
+				}
+			}
+		}
+
+		// Every 5% the proportion of dark modules strays from half
+		let dark = self.modules.iter().filter(|&&dark| dark).count();
+		let total = size * size;
+		let deviation = (dark * 20).abs_diff(total * 10);
+		score += deviation / total * 10;
+
+		score
+	}
+}
+
+#[derive(Default)]
+struct BitBuffer {
+	bits: Vec<bool>,
+}
+
+impl BitBuffer {
+	fn len(&self) -> usize {
+		self.bits.len()
+	}
+
+	fn append(&mut self, value: u32, length: usize) {
+		self.bits.extend((0..length).rev().map(|i| (value >> i) & 1 != 0));
+	}
+
+	fn into_bytes(self) -> Vec<u8> {
+		self.bits.chunks(8).map(|byte| byte.iter().fold(0, |result, &bit| result << 1 | u8::from(bit))).collect()
+	}
+}
+
+/// Multiplies two elements of the Galois field GF(2^8) used by QR codes, with the reducing polynomial x^8 + x^4 + x^3 + x^2 + 1.
+fn galois_multiply(x: u8, y: u8) -> u8 {
+	let mut z: u32 = 0;
+	for i in (0..8).rev() {
+		z = (z << 1) ^ ((z >> 7) * 0x11D);
+		z ^= ((y as u32 >> i) & 1) * x as u32;
+	}
+	z as u8
+}
+
+/// The coefficients of the Reed-Solomon generator polynomial of the given degree, from the highest power down, without the leading 1.
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+	let mut result = vec![0; degree];
+	result[degree - 1] = 1;
+
+	let mut root = 1;
+	for _ in 0..degree {
+		for j in 0..degree {
+			result[j] = galois_multiply(result[j], root);
+			if j + 1 < degree {
+				result[j] ^= result[j + 1];
+			}
+		}
+		root = galois_multiply(root, 0x02);
+	}
+	result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+	let mut result = vec![0; divisor.len()];
+	for &byte in data {
+		let factor = byte ^ result.remove(0);
+		result.push(0);
+		for (remainder, &coefficient) in result.iter_mut().zip(divisor) {
+			*remainder ^= galois_multiply(coefficient, factor);
+		}
+	}
+	result
+}
+
+/// The bar and space widths, in modules, of each Code 128 symbol value, starting with a bar.
+const CODE_128_PATTERNS: [&str; 107] = [
+	"212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212", "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221", "223211",
+	"221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221", "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321", "112313", "132113",
+	"132311", "211313", "231113", "231311", "112133", "112331", "132131", "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131", "311123", "311321", "331121",
+	"312113", "312311", "332111", "314111", "221411", "431111", "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114", "122411", "142112", "142211", "241211",
+	"221114", "413111", "241112", "134111", "111242", "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141", "214121", "412121", "111143", "111341", "131141",
+	"114113", "114311", "411113", "411311", "113141", "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+const CODE_128_START_B: usize = 104;
+const CODE_128_STOP: usize = 106;
+
+/// Encodes printable ASCII text as a Code 128 barcode using code set B, returning the widths of alternating bars and spaces in modules.
+/// Returns `None` if the text contains any other characters.
+pub fn code_128_widths(text: &str) -> Option<Vec<u8>> {
+	let values = text
+		.chars()
+		.map(|character| (' '..='~').contains(&character).then(|| character as usize - ' ' as usize))
+		.collect::<Option<Vec<_>>>()?;
+
+	let checksum = values.iter().enumerate().fold(CODE_128_START_B, |sum, (index, value)| sum + (index + 1) * value) % 103;
+
+	let symbols = std::iter::once(CODE_128_START_B).chain(values).chain([checksum, CODE_128_STOP]);
+	Some(symbols.flat_map(|symbol| CODE_128_PATTERNS[symbol].bytes().map(|width| width - b'0')).collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reed_solomon_matches_reference() {
+		// The "HELLO WORLD" example of a version 1 symbol with the quartile error correction level
+		let data = [32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236];
+		let ecc = reed_solomon_remainder(&data, &reed_solomon_divisor(13));
+		assert_eq!(ecc, [168, 72, 22, 82, 217, 54, 156, 0, 46, 15, 180, 122, 16]);
+	}
+
+	#[test]
+	fn byte_capacity_matches_specification() {
+		let capacities = [
+			14, 26, 42, 62, 84, 106, 122, 152, 180, 213, 251, 287, 331, 362, 412, 450, 504, 560, 624, 666, 711, 779, 857, 911, 997, 1059, 1125, 1190, 1264, 1370, 1452, 1538, 1628, 1722, 1809, 1911,
+			1989, 2099, 2213, 2331,
+		];
+		for (version, capacity) in (1..=40).zip(capacities) {
+			assert_eq!((QrCode::data_codewords(version) * 8 - 4 - QrCode::character_count_bits(version)) / 8, capacity, "version {version}");
+		}
+	}
+
+	#[test]
+	fn qr_code_picks_smallest_version() {
+		assert_eq!(QrCode::encode(b"https://graphite.rs").unwrap().size(), 25);
+		assert!(QrCode::encode(&[b'a'; 2331]).is_some());
+		assert!(QrCode::encode(&[b'a'; 2332]).is_none());
+	}
+
+	#[test]
+	fn code_128_patterns_are_eleven_modules_wide() {
+		assert!(CODE_128_PATTERNS[..CODE_128_STOP]
+			.iter()
+			.all(|pattern| pattern.bytes().map(|width| (width - b'0') as usize).sum::<usize>() == 11));
+		assert_eq!(code_128_widths("Wikipedia").unwrap().len(), (1 + 9 + 1) * 6 + 7);
+		assert!(code_128_widths("ü").is_none());
+	}
+}
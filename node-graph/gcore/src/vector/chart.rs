@@ -0,0 +1,82 @@
+//! Reading the values plotted by the chart generator nodes from pasted CSV or JSON data.
+
+/// Reads the numbers to chart from CSV or JSON text.
+///
+/// JSON is recognized by a leading `[` and may be an array of numbers or of objects, in which case every number outside a string is taken in order.
+/// In CSV, fields may be separated by commas, semicolons, or tabs. A row starting with a label contributes the first number after it,
+/// while a row of only numbers contributes all of them. Rows without any numbers, like a header, are skipped.
+pub fn parse_chart_values(data: &str) -> Vec<f64> {
+	let data = data.trim();
+	if data.starts_with('[') {
+		return json_numbers(data);
+	}
+
+	let mut values = Vec::new();
+	for line in data.lines() {
+		let fields: Vec<&str> = line.split([',', ';', '\t']).map(|field| field.trim().trim_matches('"')).collect();
+		let numbers = fields.iter().filter_map(|field| field.parse::<f64>().ok()).filter(|value| value.is_finite());
+		match fields.first().map(|first| first.parse::<f64>().is_ok()) {
+			Some(false) => values.extend(numbers.take(1)),
+			_ => values.extend(numbers),
+		}
+	}
+	values
+}
+
+fn json_numbers(data: &str) -> Vec<f64> {
+	let mut values = Vec::new();
+	let mut chars = data.char_indices().peekable();
+	while let Some((start, character)) = chars.next() {
+		match character {
+			// Skip over strings, including any escaped quotes inside them
+			'"' => {
+				let mut escaped = false;
+				for (_, character) in chars.by_ref() {
+					match character {
+						'"' if !escaped => break,
+						'\\' => escaped = !escaped,
+						_ => escaped = false,
+					}
+				}
+			}
+			'-' | '0'..='9' => {
+				let mut end = start + character.len_utf8();
+				while let Some(&(index, next)) = chars.peek() {
+					if !matches!(next, '0'..='9' | '.' | 'e' | 'E' | '+' | '-') {
+						break;
+					}
+					end = index + next.len_utf8();
+					chars.next();
+				}
+				if let Ok(value) = data[start..end].parse::<f64>() {
+					values.push(value);
+				}
+			}
+			_ => {}
+		}
+	}
+	values
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn csv_with_labels_and_header() {
+		assert_eq!(parse_chart_values("Month,Sales\nJan,10\nFeb,12.5\nMar,-3"), [10., 12.5, -3.]);
+		assert_eq!(parse_chart_values("\"A\";4\n\"B\";6"), [4., 6.]);
+	}
+
+	#[test]
+	fn csv_with_only_numbers() {
+		assert_eq!(parse_chart_values("3, 5, 2"), [3., 5., 2.]);
+		assert_eq!(parse_chart_values("1\n2\n\n3"), [1., 2., 3.]);
+	}
+
+	#[test]
+	fn json_arrays() {
+		assert_eq!(parse_chart_values("[1, 2.5, -4e1]"), [1., 2.5, -40.]);
+		assert_eq!(parse_chart_values(r#"[{"label": "Q1 \"2023\"", "value": 7}, {"label": "Q2", "value": 9}]"#), [7., 9.]);
+	}
+}
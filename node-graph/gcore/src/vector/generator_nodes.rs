@@ -1,8 +1,10 @@
 use crate::uuid::ManipulatorGroupId;
+use crate::vector::barcode::{code_128_widths, QrCode};
+use crate::vector::chart::parse_chart_values;
 use crate::vector::VectorData;
 use crate::Node;
 
-use bezier_rs::Subpath;
+use bezier_rs::{ManipulatorGroup, Subpath};
 use glam::DVec2;
 
 pub struct UnitCircleGenerator;
@@ -20,6 +22,363 @@ fn unit_square(_input: ()) -> VectorData {
 	super::VectorData::from_subpaths(vec![Subpath::new_ellipse(DVec2::ZERO, DVec2::ONE)])
 }
 
+/// Based on <https://pomax.github.io/bezierinfo/#circles_cubic>
+const CIRCULAR_HANDLE_FACTOR: f64 = 0.551784777779014;
+
+// The shape library generators below all fill the unit square, leaving the layer's transform to size and place them.
+// Their parameters are fractions of that square, so they keep their proportions when the layer is resized.
+
+#[derive(Debug, Clone, Copy)]
+pub struct ArrowGenerator<HeadLength, HeadWidth, ShaftWidth> {
+	head_length: HeadLength,
+	head_width: HeadWidth,
+	shaft_width: ShaftWidth,
+}
+
+/// An arrow pointing right, with a rectangular shaft and a triangular head.
+#[node_macro::node_fn(ArrowGenerator)]
+fn arrow(_input: (), head_length: f64, head_width: f64, shaft_width: f64) -> VectorData {
+	let neck = 1. - head_length.clamp(0., 1.);
+	let head = head_width.clamp(0., 1.) / 2.;
+	let shaft = shaft_width.clamp(0., 1.) / 2.;
+
+	let anchors = [
+		DVec2::new(0., 0.5 - shaft),
+		DVec2::new(neck, 0.5 - shaft),
+		DVec2::new(neck, 0.5 - head),
+		DVec2::new(1., 0.5),
+		DVec2::new(neck, 0.5 + head),
+		DVec2::new(neck, 0.5 + shaft),
+		DVec2::new(0., 0.5 + shaft),
+	];
+	super::VectorData::from_subpath(Subpath::from_anchors(anchors, true))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SpeechBubbleGenerator<CornerRadius, TailPosition, TailLength> {
+	corner_radius: CornerRadius,
+	tail_position: TailPosition,
+	tail_length: TailLength,
+}
+
+/// A rounded rectangle with a tail hanging down from its bottom edge at `tail_position` across the width.
+#[node_macro::node_fn(SpeechBubbleGenerator)]
+fn speech_bubble(_input: (), corner_radius: f64, tail_position: f64, tail_length: f64) -> VectorData {
+	let bottom = 1. - tail_length.clamp(0., 0.9);
+	let radius = corner_radius.clamp(0., 0.4).min(bottom / 2.);
+	let handle = radius * (1. - CIRCULAR_HANDLE_FACTOR);
+
+	const TAIL_HALF_WIDTH: f64 = 0.1;
+	let tail_center = tail_position.clamp(radius + TAIL_HALF_WIDTH, 1. - radius - TAIL_HALF_WIDTH);
+	let (tail_start, tail_end) = (tail_center + TAIL_HALF_WIDTH, tail_center - TAIL_HALF_WIDTH);
+
+	let point = |x: f64, y: f64| DVec2::new(x, y);
+	let manipulator_groups = vec![
+		// Top edge and top right corner
+		ManipulatorGroup::new(point(radius, 0.), Some(point(handle, 0.)), None),
+		ManipulatorGroup::new(point(1. - radius, 0.), None, Some(point(1. - handle, 0.))),
+		ManipulatorGroup::new(point(1., radius), Some(point(1., handle)), None),
+		// Right edge and bottom right corner
+		ManipulatorGroup::new(point(1., bottom - radius), None, Some(point(1., bottom - handle))),
+		ManipulatorGroup::new(point(1. - radius, bottom), Some(point(1. - handle, bottom)), None),
+		// Tail, leaning towards the left
+		ManipulatorGroup::new(point(tail_start, bottom), None, None),
+		ManipulatorGroup::new(point(tail_end, 1.), None, None),
+		ManipulatorGroup::new(point(tail_end, bottom), None, None),
+		// Bottom left corner and left edge
+		ManipulatorGroup::new(point(radius, bottom), None, Some(point(handle, bottom))),
+		ManipulatorGroup::new(point(0., bottom - radius), Some(point(0., bottom - handle)), None),
+		ManipulatorGroup::new(point(0., radius), None, Some(point(0., handle))),
+	];
+	super::VectorData::from_subpath(Subpath::new(manipulator_groups, true))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RectangleGenerator<Size, TopLeft, TopRight, BottomRight, BottomLeft> {
+	size: Size,
+	top_left: TopLeft,
+	top_right: TopRight,
+	bottom_right: BottomRight,
+	bottom_left: BottomLeft,
+}
+
+/// Shrinks the corner radii of a rectangle of the given size in proportion, so neighboring corners don't overlap along any edge.
+/// The radii are given clockwise from the top left corner, and negative ones are taken as zero.
+pub fn fit_corner_radii(size: DVec2, radii: [f64; 4]) -> [f64; 4] {
+	let radii = radii.map(|radius| radius.max(0.));
+	let [top_left, top_right, bottom_right, bottom_left] = radii;
+	let edges = [
+		(size.x, top_left + top_right),
+		(size.y, top_right + bottom_right),
+		(size.x, bottom_right + bottom_left),
+		(size.y, bottom_left + top_left),
+	];
+
+	let scale = edges.into_iter().filter(|&(_, radii)| radii > 0.).map(|(length, radii)| length.abs() / radii).fold(1_f64, f64::min);
+	radii.map(|radius| radius * scale)
+}
+
+/// A rectangle with separately rounded corners. Its corner radii are in the units of `size`, which is how large the layer's transform
+/// stretches the unit square, so the corners stay circular rather than being stretched along with it.
+#[node_macro::node_fn(RectangleGenerator)]
+fn rectangle(_input: (), size: DVec2, top_left: f64, top_right: f64, bottom_right: f64, bottom_left: f64) -> VectorData {
+	if size.x <= 0. || size.y <= 0. {
+		return super::VectorData::from_subpath(Subpath::new_rect(DVec2::ZERO, DVec2::ONE));
+	}
+
+	// Each corner, its radius, and the directions along the edges leading into and out of it, going clockwise
+	let radii = fit_corner_radii(size, [top_left, top_right, bottom_right, bottom_left]);
+	let corners = [
+		(DVec2::new(0., 0.), radii[0], -DVec2::Y, DVec2::X),
+		(DVec2::new(1., 0.), radii[1], DVec2::X, DVec2::Y),
+		(DVec2::new(1., 1.), radii[2], DVec2::Y, -DVec2::X),
+		(DVec2::new(0., 1.), radii[3], -DVec2::X, -DVec2::Y),
+	];
+
+	let mut manipulator_groups = Vec::with_capacity(8);
+	for (corner, radius, incoming, outgoing) in corners {
+		if radius <= 0. {
+			manipulator_groups.push(ManipulatorGroup::new(corner, None, None));
+			continue;
+		}
+
+		// The radius is converted from the units of `size` into the unit square along each edge
+		let (before, after) = (incoming.abs() * radius / size, outgoing.abs() * radius / size);
+		let start = corner - incoming * before;
+		let end = corner + outgoing * after;
+		let start_handle = start + incoming * before * CIRCULAR_HANDLE_FACTOR;
+		let end_handle = end - outgoing * after * CIRCULAR_HANDLE_FACTOR;
+		manipulator_groups.push(ManipulatorGroup::new(start, None, Some(start_handle)));
+		manipulator_groups.push(ManipulatorGroup::new(end, Some(end_handle), None));
+	}
+	super::VectorData::from_subpath(Subpath::new(manipulator_groups, true))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GearGenerator<Teeth, ToothDepth> {
+	teeth: Teeth,
+	tooth_depth: ToothDepth,
+}
+
+/// A gear outline with `teeth` evenly spaced trapezoidal teeth, each reaching `tooth_depth` of the radius inwards from the rim.
+#[node_macro::node_fn(GearGenerator)]
+fn gear(_input: (), teeth: u32, tooth_depth: f64) -> VectorData {
+	let teeth = teeth.max(3);
+	let center = DVec2::splat(0.5);
+	let outer_radius = 0.5;
+	let inner_radius = outer_radius * (1. - tooth_depth.clamp(0., 0.9));
+
+	// Where the root and the tip of each tooth start and end, as fractions of the angle taken up by one tooth and its gap
+	const TOOTH_PROFILE: [(f64, bool); 4] = [(0., false), (0.15, true), (0.4, true), (0.55, false)];
+
+	let step = std::f64::consts::TAU / teeth as f64;
+	let anchors = (0..teeth).flat_map(|tooth| {
+		TOOTH_PROFILE.iter().map(move |&(fraction, tip)| {
+			let radius = if tip { outer_radius } else { inner_radius };
+			center + DVec2::from_angle((tooth as f64 + fraction) * step) * radius
+		})
+	});
+	super::VectorData::from_subpath(Subpath::from_anchors(anchors, true))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HeartGenerator;
+
+#[node_macro::node_fn(HeartGenerator)]
+fn heart(_input: ()) -> VectorData {
+	let point = |x: f64, y: f64| DVec2::new(x, y);
+	let manipulator_groups = vec![
+		// The dip between the two lobes
+		ManipulatorGroup::new(point(0.5, 0.3), Some(point(0.45, -0.1)), Some(point(0.55, -0.1))),
+		ManipulatorGroup::new(point(1., 0.3), Some(point(1., -0.1)), Some(point(1., 0.6))),
+		// The point at the bottom
+		ManipulatorGroup::new(point(0.5, 1.), Some(point(0.75, 0.8)), Some(point(0.25, 0.8))),
+		ManipulatorGroup::new(point(0., 0.3), Some(point(0., 0.6)), Some(point(0., -0.1))),
+	];
+	super::VectorData::from_subpath(Subpath::new(manipulator_groups, true))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PlaceholderImageGenerator;
+
+/// A frame crossed by both of its diagonals, standing in for an image that hasn't been chosen yet.
+#[node_macro::node_fn(PlaceholderImageGenerator)]
+fn placeholder_image(_input: ()) -> VectorData {
+	let subpaths = vec![
+		Subpath::new_rect(DVec2::ZERO, DVec2::ONE),
+		Subpath::from_anchors([DVec2::ZERO, DVec2::ONE], false),
+		Subpath::from_anchors([DVec2::X, DVec2::Y], false),
+	];
+	super::VectorData::from_subpaths(subpaths)
+}
+
+#[derive(Debug, Clone)]
+pub struct QrCodeGenerator<Text> {
+	text: Text,
+}
+
+/// A QR code of the text, drawn as one rectangle per horizontal run of dark modules.
+/// Text too long for the largest QR code produces no shape.
+#[node_macro::node_fn(QrCodeGenerator)]
+fn qr_code(_input: (), text: String) -> VectorData {
+	let Some(qr_code) = QrCode::encode(text.as_bytes()) else { return VectorData::empty() };
+
+	let size = qr_code.size();
+	let module = 1. / size as f64;
+	let mut subpaths = Vec::new();
+	for y in 0..size {
+		let mut x = 0;
+		while x < size {
+			if !qr_code.module(x, y) {
+				x += 1;
+				continue;
+			}
+			let start = x;
+			while x < size && qr_code.module(x, y) {
+				x += 1;
+			}
+			subpaths.push(Subpath::new_rect(DVec2::new(start as f64, y as f64) * module, DVec2::new(x as f64, (y + 1) as f64) * module));
+		}
+	}
+	super::VectorData::from_subpaths(subpaths)
+}
+
+#[derive(Debug, Clone)]
+pub struct BarcodeGenerator<Text> {
+	text: Text,
+}
+
+/// A Code 128 barcode of the text, with bars spanning the full height.
+/// Text with characters other than printable ASCII produces no shape.
+#[node_macro::node_fn(BarcodeGenerator)]
+fn barcode(_input: (), text: String) -> VectorData {
+	let Some(widths) = code_128_widths(&text) else { return VectorData::empty() };
+
+	let total_width = widths.iter().map(|&width| width as f64).sum::<f64>();
+	let mut subpaths = Vec::new();
+	let mut x = 0.;
+	// Bars and spaces alternate, starting with a bar
+	for (index, &width) in widths.iter().enumerate() {
+		let width = width as f64 / total_width;
+		if index % 2 == 0 {
+			subpaths.push(Subpath::new_rect(DVec2::new(x, 0.), DVec2::new(x + width, 1.)));
+		}
+		x += width;
+	}
+	super::VectorData::from_subpaths(subpaths)
+}
+
+/// The lowest and highest values a chart's scale spans, which always includes zero so the bars grow from a baseline.
+fn chart_range(values: &[f64]) -> (f64, f64) {
+	values.iter().fold((0_f64, 0_f64), |(low, high), &value| (low.min(value), high.max(value)))
+}
+
+#[derive(Debug, Clone)]
+pub struct BarChartGenerator<Data, Gap> {
+	data: Data,
+	gap: Gap,
+}
+
+/// A column for each value in the CSV or JSON data, rising from the baseline at zero (or hanging below it for negative values).
+/// The `gap` between columns is a fraction of the space each column is given.
+#[node_macro::node_fn(BarChartGenerator)]
+fn bar_chart(_input: (), data: String, gap: f64) -> VectorData {
+	let values = parse_chart_values(&data);
+	let (low, high) = chart_range(&values);
+	if values.is_empty() || high - low <= 0. {
+		return VectorData::empty();
+	}
+
+	let height = |value: f64| (high - value) / (high - low);
+	let slot = 1. / values.len() as f64;
+	let margin = slot * gap.clamp(0., 0.95) / 2.;
+	let subpaths = values
+		.iter()
+		.enumerate()
+		.map(|(index, &value)| {
+			let left = index as f64 * slot + margin;
+			Subpath::new_rect(DVec2::new(left, height(value)), DVec2::new(left + slot - margin * 2., height(0.)))
+		})
+		.collect();
+	super::VectorData::from_subpaths(subpaths)
+}
+
+#[derive(Debug, Clone)]
+pub struct LineChartGenerator<Data> {
+	data: Data,
+}
+
+/// An open polyline through the values in the CSV or JSON data, spaced evenly across the width and scaled to fill the height.
+#[node_macro::node_fn(LineChartGenerator)]
+fn line_chart(_input: (), data: String) -> VectorData {
+	let values = parse_chart_values(&data);
+	if values.len() < 2 {
+		return VectorData::empty();
+	}
+
+	let low = values.iter().copied().fold(f64::INFINITY, f64::min);
+	let high = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+	let height = |value: f64| if high > low { (high - value) / (high - low) } else { 0.5 };
+	let step = 1. / (values.len() - 1) as f64;
+	let anchors = values.iter().enumerate().map(|(index, &value)| DVec2::new(index as f64 * step, height(value)));
+	super::VectorData::from_subpath(Subpath::from_anchors(anchors, false))
+}
+
+#[derive(Debug, Clone)]
+pub struct PieChartGenerator<Data, InnerRadius> {
+	data: Data,
+	inner_radius: InnerRadius,
+}
+
+/// A slice for each positive value in the CSV or JSON data, going clockwise from the top.
+/// A nonzero `inner_radius`, as a fraction of the outer radius, cuts a hole in the middle to make a donut chart.
+#[node_macro::node_fn(PieChartGenerator)]
+fn pie_chart(_input: (), data: String, inner_radius: f64) -> VectorData {
+	let values: Vec<f64> = parse_chart_values(&data).into_iter().filter(|&value| value > 0.).collect();
+	let total = values.iter().sum::<f64>();
+	if total <= 0. {
+		return VectorData::empty();
+	}
+
+	let center = DVec2::splat(0.5);
+	let radius = 0.5;
+	let inner_radius = radius * inner_radius.clamp(0., 0.95);
+
+	let mut start_angle = -std::f64::consts::FRAC_PI_2;
+	let subpaths = values
+		.iter()
+		.map(|&value| {
+			let end_angle = start_angle + value / total * std::f64::consts::TAU;
+			let mut manipulator_groups = arc(center, radius, start_angle, end_angle);
+			if inner_radius > 0. {
+				manipulator_groups.extend(arc(center, inner_radius, end_angle, start_angle));
+			} else {
+				manipulator_groups.push(ManipulatorGroup::new(center, None, None));
+			}
+			start_angle = end_angle;
+			Subpath::new(manipulator_groups, true)
+		})
+		.collect();
+	super::VectorData::from_subpaths(subpaths)
+}
+
+/// Anchors along a circular arc, with handles approximating it by cubic Bézier segments that each span at most a quarter turn.
+fn arc(center: DVec2, radius: f64, start_angle: f64, end_angle: f64) -> Vec<ManipulatorGroup<ManipulatorGroupId>> {
+	let sweep = end_angle - start_angle;
+	let segments = (sweep.abs() / std::f64::consts::FRAC_PI_2).ceil().max(1.) as usize;
+	let step = sweep / segments as f64;
+	let handle_length = 4. / 3. * (step / 4.).tan() * radius;
+
+	(0..=segments)
+		.map(|index| {
+			let direction = DVec2::from_angle(start_angle + step * index as f64);
+			let anchor = center + direction * radius;
+			let tangent = direction.perp() * handle_length;
+			ManipulatorGroup::new(anchor, (index > 0).then(|| anchor - tangent), (index < segments).then(|| anchor + tangent))
+		})
+		.collect()
+}
+
 // TODO(TrueDoctor): I removed the Arc requirement we should think about when it makes sense to use it vs making a generic value node
 #[derive(Debug, Clone)]
 pub struct PathGenerator<Mirror> {
@@ -56,3 +415,45 @@ fn generate_path(path_data: Vec<Subpath<ManipulatorGroupId>>, mirror: Vec<Manipu
 
 // 	base_image
 // }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::value::ClonedNode;
+
+	#[test]
+	fn corner_radii_shrink_in_proportion_to_fit() {
+		let size = DVec2::new(10., 6.);
+		// Radii which fit along every edge are kept, and negative ones are taken as zero
+		assert_eq!(fit_corner_radii(size, [2., -1., 3., 1.]), [2., 0., 3., 1.]);
+		// Two radii of 4 don't fit along an edge 6 long, so every radius is scaled by 6 / 8
+		assert_eq!(fit_corner_radii(size, [4.; 4]), [3.; 4]);
+		assert_eq!(fit_corner_radii(size, [8., 0., 0., 0.]), [6., 0., 0., 0.]);
+		assert_eq!(fit_corner_radii(size, [0.; 4]), [0.; 4]);
+	}
+
+	fn rectangle_anchors(size: DVec2, [top_left, top_right, bottom_right, bottom_left]: [f64; 4]) -> Vec<DVec2> {
+		let generator = RectangleGenerator::new(
+			ClonedNode::new(size),
+			ClonedNode::new(top_left),
+			ClonedNode::new(top_right),
+			ClonedNode::new(bottom_right),
+			ClonedNode::new(bottom_left),
+		);
+		let vector_data = generator.eval(());
+		vector_data.subpaths[0].manipulator_groups().iter().map(|group| group.anchor).collect()
+	}
+
+	#[test]
+	fn rectangle_rounds_only_corners_with_a_radius() {
+		let square = [DVec2::new(0., 0.), DVec2::new(1., 0.), DVec2::new(1., 1.), DVec2::new(0., 1.)];
+		assert_eq!(rectangle_anchors(DVec2::new(100., 50.), [0.; 4]), square);
+
+		// A radius of 10 is a tenth of the width and a fifth of the height, so the corner stays circular once the layer stretches the unit square
+		let rounded = rectangle_anchors(DVec2::new(100., 50.), [10., 0., 0., 0.]);
+		assert_eq!(rounded, [DVec2::new(0., 0.2), DVec2::new(0.1, 0.), DVec2::new(1., 0.), DVec2::new(1., 1.), DVec2::new(0., 1.)]);
+
+		// Without a size to measure the radii in, the corners are left sharp
+		assert_eq!(rectangle_anchors(DVec2::ZERO, [10.; 4]), square);
+	}
+}
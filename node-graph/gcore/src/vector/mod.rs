@@ -1,4 +1,6 @@
+pub mod barcode;
 pub mod brush_stroke;
+pub mod chart;
 pub mod consts;
 pub mod generator_nodes;
 pub mod manipulator_group;
@@ -7,6 +9,8 @@ pub mod manipulator_point;
 pub mod style;
 pub use style::PathStyle;
 
+pub mod width_profile;
+
 pub mod subpath;
 pub use subpath::Subpath;
 
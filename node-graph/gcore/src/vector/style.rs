@@ -1,6 +1,8 @@
 //! Contains stylistic options for SVG elements.
 
+use super::width_profile::{self, WidthPoint};
 use crate::consts::{LAYER_OUTLINE_STROKE_COLOR, LAYER_OUTLINE_STROKE_WEIGHT};
+use crate::uuid::ManipulatorGroupId;
 use crate::Color;
 
 use dyn_any::{DynAny, StaticType};
@@ -262,9 +264,16 @@ pub struct Stroke {
 	pub weight: f64,
 	pub dash_lengths: Vec<f32>,
 	pub dash_offset: f64,
+	/// How fast the dashes march along the path, in pixels per second, or backwards if negative. The animation plays live in the viewport
+	/// and is kept in exported SVG files as a CSS animation.
+	#[serde(default)]
+	pub dash_speed: f64,
 	pub line_cap: LineCap,
 	pub line_join: LineJoin,
 	pub line_join_miter_limit: f64,
+	/// The control points varying the width along the path, sorted by their position. A stroke without any keeps its weight everywhere.
+	#[serde(default)]
+	pub width_profile: Vec<WidthPoint>,
 }
 
 impl core::hash::Hash for Stroke {
@@ -274,9 +283,15 @@ impl core::hash::Hash for Stroke {
 		self.dash_lengths.len().hash(state);
 		self.dash_lengths.iter().for_each(|length| length.to_bits().hash(state));
 		self.dash_offset.to_bits().hash(state);
+		self.dash_speed.to_bits().hash(state);
 		self.line_cap.hash(state);
 		self.line_join.hash(state);
 		self.line_join_miter_limit.to_bits().hash(state);
+		self.width_profile.len().hash(state);
+		for point in &self.width_profile {
+			point.position.to_bits().hash(state);
+			point.width.to_bits().hash(state);
+		}
 	}
 }
 
@@ -287,9 +302,11 @@ impl Stroke {
 			weight,
 			dash_lengths: Vec::new(),
 			dash_offset: 0.,
+			dash_speed: 0.,
 			line_cap: LineCap::Butt,
 			line_join: LineJoin::Miter,
 			line_join_miter_limit: 4.,
+			width_profile: Vec::new(),
 		}
 	}
 
@@ -315,6 +332,20 @@ impl Stroke {
 		self.dash_offset
 	}
 
+	pub fn dash_speed(&self) -> f64 {
+		self.dash_speed
+	}
+
+	/// The length after which the dash pattern repeats. SVG repeats a list of odd length twice to pair up every dash with a gap.
+	pub fn dash_cycle_length(&self) -> f64 {
+		let length = self.dash_lengths.iter().map(|&length| length as f64).sum::<f64>();
+		if self.dash_lengths.len() % 2 == 1 {
+			length * 2.
+		} else {
+			length
+		}
+	}
+
 	pub fn line_cap_index(&self) -> u32 {
 		self.line_cap as u32
 	}
@@ -327,6 +358,24 @@ impl Stroke {
 		self.line_join_miter_limit as f32
 	}
 
+	pub fn has_width_profile(&self) -> bool {
+		!self.width_profile.is_empty()
+	}
+
+	/// The path data of the filled outline drawn in place of a variable-width stroke, or `None` if the stroke has a constant width.
+	pub fn width_profile_outline(&self, subpaths: &[bezier_rs::Subpath<ManipulatorGroupId>], transform: DAffine2) -> Option<String> {
+		self.has_width_profile()
+			.then(|| width_profile::outline_path_data(subpaths, transform, self.weight, &self.width_profile))
+	}
+
+	/// Provide the SVG attributes filling the outline of a variable-width stroke with the stroke's color.
+	pub fn render_outline_fill(&self) -> String {
+		match self.color {
+			Some(color) => format!(r##" fill="#{}"{}"##, color.rgb_hex(), format_opacity("fill", color.a())),
+			None => r#" fill="none""#.to_string(),
+		}
+	}
+
 	/// Provide the SVG attributes for the stroke.
 	pub fn render(&self) -> String {
 		if let Some(color) = self.color {
@@ -346,6 +395,25 @@ impl Stroke {
 		}
 	}
 
+	/// Adds the keyframes marching the dashes along the path to the defs, and provides the SVG attribute playing them.
+	/// Solid strokes and still dashes have no animation.
+	pub fn render_dash_animation(&self, svg_defs: &mut String) -> String {
+		let cycle = self.dash_cycle_length();
+		if self.color.is_none() || self.dash_speed == 0. || cycle <= 0. {
+			return String::new();
+		}
+
+		// Moving the offset back by one whole cycle brings the pattern to where it started, so the animation loops seamlessly
+		let end = self.dash_offset - cycle * self.dash_speed.signum();
+		let name = format!("dash-march-{:x}-{:x}", self.dash_offset.to_bits(), end.to_bits());
+		let _ = write!(
+			svg_defs,
+			"<style>@keyframes {name} {{ from {{ stroke-dashoffset: {}px; }} to {{ stroke-dashoffset: {end}px; }} }}</style>",
+			self.dash_offset
+		);
+		format!(r#" style="animation: {name} {}s linear infinite""#, cycle / self.dash_speed.abs())
+	}
+
 	pub fn with_color(mut self, color: &Option<Color>) -> Option<Self> {
 		self.color = *color;
 
@@ -375,6 +443,11 @@ impl Stroke {
 		self
 	}
 
+	pub fn with_dash_speed(mut self, dash_speed: f64) -> Self {
+		self.dash_speed = dash_speed;
+		self
+	}
+
 	pub fn with_line_cap(mut self, line_cap: LineCap) -> Self {
 		self.line_cap = line_cap;
 		self
@@ -399,9 +472,11 @@ impl Default for Stroke {
 			color: Some(Color::from_rgba8_srgb(0, 0, 0, 255)),
 			dash_lengths: Vec::new(),
 			dash_offset: 0.,
+			dash_speed: 0.,
 			line_cap: LineCap::Butt,
 			line_join: LineJoin::Miter,
 			line_join_miter_limit: 4.,
+			width_profile: Vec::new(),
 		}
 	}
 }
@@ -529,12 +604,22 @@ impl PathStyle {
 		};
 		let stroke_attribute = match (view_mode, &self.stroke) {
 			(ViewMode::Outline, _) => Stroke::new(Some(LAYER_OUTLINE_STROKE_COLOR), LAYER_OUTLINE_STROKE_WEIGHT).render(),
-			(_, Some(stroke)) => stroke.render(),
+			// A variable-width stroke is drawn as its own filled outline instead, see `render_width_profile`
+			(_, Some(stroke)) if stroke.has_width_profile() => String::new(),
+			(_, Some(stroke)) => stroke.render() + &stroke.render_dash_animation(svg_defs),
 			(_, None) => String::new(),
 		};
 
 		format!("{}{}", fill_attribute, stroke_attribute)
 	}
+
+	/// Provide a `<path>` element for the outline of a variable-width stroke, drawn on top of the path itself. Outline view mode draws
+	/// every stroke as a thin line, so there it has none.
+	pub fn render_width_profile(&self, view_mode: ViewMode, subpaths: &[bezier_rs::Subpath<ManipulatorGroupId>], transform: DAffine2) -> Option<String> {
+		let stroke = self.stroke.as_ref().filter(|_| view_mode != ViewMode::Outline)?;
+		let outline = stroke.width_profile_outline(subpaths, transform)?;
+		Some(format!(r#"<path d="{outline}"{} />"#, stroke.render_outline_fill()))
+	}
 }
 
 /// Represents different ways of rendering an object
@@ -548,3 +633,60 @@ pub enum ViewMode {
 	/// Render with normal coloration at the document resolution, showing the pixels when the current viewport resolution is higher
 	Pixels,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn dashed_stroke(dash_lengths: Vec<f32>, dash_offset: f64, dash_speed: f64) -> Stroke {
+		Stroke {
+			dash_lengths,
+			dash_offset,
+			dash_speed,
+			..Stroke::new(Some(Color::BLACK), 2.)
+		}
+	}
+
+	#[test]
+	fn still_and_solid_strokes_are_not_animated() {
+		let mut svg_defs = String::new();
+		assert!(dashed_stroke(vec![4., 2.], 0., 0.).render_dash_animation(&mut svg_defs).is_empty());
+		assert!(dashed_stroke(Vec::new(), 0., 10.).render_dash_animation(&mut svg_defs).is_empty());
+		let uncolored = Stroke {
+			color: None,
+			..dashed_stroke(vec![4., 2.], 0., 10.)
+		};
+		assert!(uncolored.render_dash_animation(&mut svg_defs).is_empty());
+		assert!(svg_defs.is_empty());
+	}
+
+	#[test]
+	fn dashes_march_one_cycle_per_loop() {
+		let mut svg_defs = String::new();
+		let attribute = dashed_stroke(vec![4., 2.], 1., 3.).render_dash_animation(&mut svg_defs);
+
+		let name = format!("dash-march-{:x}-{:x}", 1_f64.to_bits(), (-5_f64).to_bits());
+		assert_eq!(attribute, format!(r#" style="animation: {name} 2s linear infinite""#));
+		assert_eq!(
+			svg_defs,
+			format!("<style>@keyframes {name} {{ from {{ stroke-dashoffset: 1px; }} to {{ stroke-dashoffset: -5px; }} }}</style>")
+		);
+	}
+
+	#[test]
+	fn odd_dash_lists_march_backwards_over_the_doubled_cycle() {
+		let mut svg_defs = String::new();
+		let attribute = dashed_stroke(vec![3.], 0., -4.).render_dash_animation(&mut svg_defs);
+
+		assert!(attribute.ends_with(r#" 1.5s linear infinite""#));
+		assert!(svg_defs.contains("from { stroke-dashoffset: 0px; } to { stroke-dashoffset: 6px; }"));
+
+		// The animation is added after the stroke's other attributes when the path is rendered
+		let style = PathStyle::new(Some(dashed_stroke(vec![3.], 0., -4.)), Fill::None);
+		let bounds = [DVec2::ZERO, DVec2::ONE];
+		let rendered = style.render(ViewMode::Normal, &mut String::new(), DAffine2::IDENTITY, bounds, bounds);
+		assert!(rendered.contains(r#"stroke-dasharray="3""#));
+		assert!(rendered.ends_with(&attribute));
+		assert!(!style.render(ViewMode::Outline, &mut String::new(), DAffine2::IDENTITY, bounds, bounds).contains("animation"));
+	}
+}
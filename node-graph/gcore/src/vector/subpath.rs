@@ -60,6 +60,34 @@ impl Subpath {
 		Self(groups)
 	}
 
+	/// Convert from the legacy Subpath to a `bezier_rs::Subpath` for each of its contours.
+	pub fn to_bezier_rs(&self) -> Vec<bezier_rs::Subpath<ManipulatorGroupId>> {
+		fn finish(groups: &mut Vec<bezier_rs::ManipulatorGroup<ManipulatorGroupId>>, closed: bool, subpaths: &mut Vec<bezier_rs::Subpath<ManipulatorGroupId>>) {
+			let mut groups = core::mem::take(groups);
+			// A contour that is closed after returning to its first anchor would otherwise end with a segment of zero length
+			if closed && groups.len() > 2 && groups[0].anchor.distance_squared(groups[groups.len() - 1].anchor) < 1e-12 {
+				let last = groups.pop().unwrap();
+				groups[0].in_handle = last.in_handle;
+			}
+			if groups.len() > 1 {
+				subpaths.push(bezier_rs::Subpath::new(groups, closed));
+			}
+		}
+
+		let mut subpaths = Vec::new();
+		let mut groups = Vec::new();
+		for group in self.manipulator_groups().iter() {
+			let position = |manipulator_type: ManipulatorType| group.points[manipulator_type].as_ref().map(|point| point.position);
+			match position(ManipulatorType::Anchor) {
+				Some(anchor) => groups.push(bezier_rs::ManipulatorGroup::new(anchor, position(ManipulatorType::InHandle), position(ManipulatorType::OutHandle))),
+				None => finish(&mut groups, true, &mut subpaths),
+			}
+		}
+		finish(&mut groups, false, &mut subpaths);
+
+		subpaths
+	}
+
 	// ** PRIMITIVE CONSTRUCTION **
 
 	/// constructs a rectangle with `p1` as the lower left and `p2` as the top right
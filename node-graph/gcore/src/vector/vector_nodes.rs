@@ -65,9 +65,54 @@ fn set_vector_data_stroke(
 		weight: weight as f64,
 		dash_lengths,
 		dash_offset: dash_offset as f64,
+		dash_speed: 0.,
 		line_cap,
 		line_join,
 		line_join_miter_limit: miter_limit as f64,
+		width_profile: Vec::new(),
 	});
 	vector_data
 }
+
+#[derive(Debug, Clone, Copy)]
+pub struct SetWidthProfileNode<Positions, Widths> {
+	positions: Positions,
+	widths: Widths,
+}
+
+/// Varies the width of the stroke along the path, pairing up each position (from 0 at the start of the path to 1 at its end) with a width
+/// (as a multiple of the stroke's weight). It goes after the "Stroke" node, since that one sets a stroke of constant width.
+#[node_macro::node_fn(SetWidthProfileNode)]
+fn set_vector_data_width_profile(mut vector_data: VectorData, positions: Vec<f32>, widths: Vec<f32>) -> VectorData {
+	if let Some(mut stroke) = vector_data.style.stroke() {
+		stroke.width_profile = super::width_profile::WidthPoint::from_lists(&positions, &widths);
+		vector_data.style.set_stroke(stroke);
+	}
+	vector_data
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RadialArrayNode<Count, Angle, Center> {
+	count: Count,
+	angle: Angle,
+	center: Center,
+}
+
+/// Repeats the shape `count` times, rotating each copy further about `center` so the copies are spread over `angle` degrees.
+/// A full turn spaces the copies evenly around the circle, while a partial sweep places the last copy at its end.
+#[node_macro::node_fn(RadialArrayNode)]
+fn radial_array(mut vector_data: VectorData, count: u32, angle: f64, center: DVec2) -> VectorData {
+	let count = count.max(1);
+	let step = if angle.abs() >= 360. { angle / count as f64 } else { angle / (count - 1).max(1) as f64 };
+
+	let original = core::mem::take(&mut vector_data.subpaths);
+	for index in 0..count {
+		let rotation = DAffine2::from_translation(center) * DAffine2::from_angle((step * index as f64).to_radians()) * DAffine2::from_translation(-center);
+		vector_data.subpaths.extend(original.iter().map(|subpath| {
+			let mut copy = subpath.clone();
+			copy.apply_transform(rotation);
+			copy
+		}));
+	}
+	vector_data
+}
@@ -0,0 +1,186 @@
+//! Variable-width strokes, which thin and thicken along their path like a brush stroke instead of keeping one constant weight.
+//!
+//! The widths are kept as control points alongside the stroke, leaving the path itself untouched so it can still be edited. SVG strokes only
+//! have a single width, so such strokes are rendered (and exported) as a filled outline traced around the path at the interpolated widths.
+
+use crate::uuid::ManipulatorGroupId;
+
+use bezier_rs::{Subpath, SubpathTValue};
+use dyn_any::{DynAny, StaticType};
+use glam::{DAffine2, DVec2};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// The distance in pixels between the samples the outline of a variable-width stroke is traced through.
+const OUTLINE_SAMPLE_SPACING: f64 = 2.;
+/// The fewest and most samples traced along each subpath, keeping short paths smooth and long ones affordable.
+const OUTLINE_SAMPLE_RANGE: (usize, usize) = (16, 2000);
+
+/// A control point of a variable-width stroke.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, DynAny, specta::Type)]
+pub struct WidthPoint {
+	/// How far along the path the point is, from 0 at its start to 1 at its end.
+	pub position: f64,
+	/// The stroke width at the point, as a multiple of the stroke's weight.
+	pub width: f64,
+}
+
+impl WidthPoint {
+	pub fn new(position: f64, width: f64) -> Self {
+		Self { position, width }
+	}
+
+	/// Pairs up the positions with the widths, as they are given to the "Width Profile" node. Extra values without a pair are ignored.
+	pub fn from_lists(positions: &[f32], widths: &[f32]) -> Vec<Self> {
+		let mut points = positions
+			.iter()
+			.zip(widths)
+			.map(|(&position, &width)| Self::new((position as f64).clamp(0., 1.), (width as f64).max(0.)))
+			.collect::<Vec<_>>();
+		points.sort_by(|a, b| a.position.total_cmp(&b.position));
+		points
+	}
+}
+
+/// The width multiplier at a position along the path, linearly interpolated between the surrounding points of the (sorted) profile.
+/// Before the first point and after the last one, the width of that point continues to the end of the path.
+pub fn width_at(profile: &[WidthPoint], position: f64) -> f64 {
+	let (Some(first), Some(last)) = (profile.first(), profile.last()) else { return 1. };
+	if position <= first.position {
+		return first.width;
+	}
+	if position >= last.position {
+		return last.width;
+	}
+
+	let next = profile.iter().position(|point| point.position > position).unwrap_or(profile.len() - 1);
+	let (start, end) = (profile[next - 1], profile[next]);
+	let span = end.position - start.position;
+	if span <= f64::EPSILON {
+		return end.width;
+	}
+	start.width + (end.width - start.width) * (position - start.position) / span
+}
+
+/// Writes the SVG path data of the filled outline of a variable-width stroke of the given weight around the subpaths.
+/// The outline is traced in the transformed space, so like a regular SVG stroke its width isn't affected by the transform's scale.
+pub fn outline_path_data(subpaths: &[Subpath<ManipulatorGroupId>], transform: DAffine2, weight: f64, profile: &[WidthPoint]) -> String {
+	let mut path = String::new();
+
+	for subpath in subpaths {
+		let mut transformed = subpath.clone();
+		transformed.apply_transform(transform);
+		if transformed.len() < 2 {
+			continue;
+		}
+		let length = transformed.length(None);
+		if length <= f64::EPSILON {
+			continue;
+		}
+
+		let (min_samples, max_samples) = OUTLINE_SAMPLE_RANGE;
+		let samples = ((length / OUTLINE_SAMPLE_SPACING).ceil() as usize).clamp(min_samples, max_samples);
+		let closed = transformed.closed();
+		// A closed subpath ends where it starts, so its last sample would repeat the first one
+		let sample_count = if closed { samples } else { samples + 1 };
+
+		let (mut left, mut right) = (Vec::with_capacity(sample_count), Vec::with_capacity(sample_count));
+		for index in 0..sample_count {
+			let position = index as f64 / samples as f64;
+			let t = SubpathTValue::GlobalEuclidean(position);
+			let point = transformed.evaluate(t);
+			let normal = transformed.normal(t).try_normalize().unwrap_or(DVec2::Y);
+			let half_width = weight * width_at(profile, position) / 2.;
+
+			left.push(point + normal * half_width);
+			right.push(point - normal * half_width);
+		}
+
+		if closed {
+			// The two sides are separate loops wound in opposite directions, so the nonzero fill rule leaves the inside of the path unfilled
+			write_polygon(&mut path, left.iter());
+			write_polygon(&mut path, right.iter().rev());
+		} else {
+			write_polygon(&mut path, left.iter().chain(right.iter().rev()));
+		}
+	}
+
+	path
+}
+
+fn write_polygon<'a>(path: &mut String, mut points: impl Iterator<Item = &'a DVec2>) {
+	let Some(first) = points.next() else { return };
+	let _ = write!(path, "M{},{}", first.x, first.y);
+	for point in points {
+		let _ = write!(path, " L{},{}", point.x, point.y);
+	}
+	path.push_str(" Z ");
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// The points the outline passes through, and how many closed polygons it's made of.
+	fn outline_points(path: &str) -> (Vec<DVec2>, usize) {
+		let points = path
+			.split_whitespace()
+			.filter(|command| *command != "Z")
+			.map(|command| {
+				let (x, y) = command[1..].split_once(',').unwrap();
+				DVec2::new(x.parse().unwrap(), y.parse().unwrap())
+			})
+			.collect();
+		(points, path.matches('Z').count())
+	}
+
+	#[test]
+	fn lists_are_paired_up_sorted_and_clamped() {
+		let points = WidthPoint::from_lists(&[0.8, 1.5, -0.2, 0.5], &[2., 1., -3.]);
+		assert_eq!(points, [WidthPoint::new(0., 0.), WidthPoint::new(0.8, 2.), WidthPoint::new(1., 1.)]);
+		assert!(WidthPoint::from_lists(&[], &[1.]).is_empty());
+	}
+
+	#[test]
+	fn width_is_interpolated_between_points() {
+		let profile = [WidthPoint::new(0.2, 0.), WidthPoint::new(0.6, 2.), WidthPoint::new(0.6, 4.), WidthPoint::new(0.8, 1.)];
+		assert_eq!(width_at(&profile, 0.), 0.);
+		assert_eq!(width_at(&profile, 0.4), 1.);
+		assert_eq!(width_at(&profile, 0.7), 2.5);
+		assert_eq!(width_at(&profile, 1.), 1.);
+		// Without any points, the stroke keeps its weight all along the path
+		assert_eq!(width_at(&[], 0.5), 1.);
+	}
+
+	#[test]
+	fn open_outline_tapers_along_the_path() {
+		let subpaths = [Subpath::new_line(DVec2::ZERO, DVec2::new(50., 0.))];
+		let profile = [WidthPoint::new(0., 0.), WidthPoint::new(1., 2.)];
+		// The transform's scale lengthens the path but leaves the width as it is
+		let path = outline_path_data(&subpaths, DAffine2::from_scale(DVec2::splat(2.)), 10., &profile);
+
+		let (points, polygons) = outline_points(&path);
+		assert_eq!(polygons, 1);
+		// Both sides start at the pointed end and are 20 apart at the other
+		assert!(points[0].abs_diff_eq(DVec2::ZERO, 1e-6));
+		assert!(points.last().unwrap().abs_diff_eq(DVec2::ZERO, 1e-6));
+		let widest = points.iter().map(|point| point.y.abs()).fold(0., f64::max);
+		assert!((widest - 10.).abs() < 1e-6);
+		assert!(points.iter().all(|point| point.x > -1e-6 && point.x < 100. + 1e-6));
+	}
+
+	#[test]
+	fn closed_outline_is_two_loops() {
+		let subpaths = [Subpath::new_rect(DVec2::ZERO, DVec2::splat(100.))];
+		let (points, polygons) = outline_points(&outline_path_data(&subpaths, DAffine2::IDENTITY, 4., &[]));
+		assert_eq!(polygons, 2);
+		// One loop runs half the weight outside the edge of the square and the other half the weight inside it
+		assert!(points.iter().all(|point| point.cmpge(DVec2::splat(-2. - 1e-6)).all() && point.cmple(DVec2::splat(102. + 1e-6)).all()));
+		assert!(points.iter().any(|point| point.abs_diff_eq(DVec2::new(50., -2.), 1e-6)));
+		assert!(points.iter().any(|point| point.abs_diff_eq(DVec2::new(50., 2.), 1e-6)));
+
+		// Subpaths with a single point have no outline
+		let point = [Subpath::from_anchors([DVec2::ZERO], false)];
+		assert!(outline_path_data(&point, DAffine2::IDENTITY, 4., &[]).is_empty());
+	}
+}
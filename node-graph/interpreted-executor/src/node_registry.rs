@@ -526,13 +526,26 @@ fn node_registry() -> HashMap<NodeIdentifier, HashMap<NodeIOTypes, NodeConstruct
 		register_node!(graphene_core::transform::SetTransformNode<_>, input: ImageFrame<Color>, params: [DAffine2]),
 		register_node!(graphene_core::vector::SetFillNode<_, _, _, _, _, _, _>, input: VectorData, params: [graphene_core::vector::style::FillType, Option<graphene_core::Color>, graphene_core::vector::style::GradientType, DVec2, DVec2, DAffine2, Vec<(f64, Option<graphene_core::Color>)>]),
 		register_node!(graphene_core::vector::SetStrokeNode<_, _, _, _, _, _, _>, input: VectorData, params: [Option<graphene_core::Color>, f32, Vec<f32>, f32, graphene_core::vector::style::LineCap, graphene_core::vector::style::LineJoin, f32]),
+		register_node!(graphene_core::vector::SetWidthProfileNode<_, _>, input: VectorData, params: [Vec<f32>, Vec<f32>]),
+		register_node!(graphene_core::vector::RadialArrayNode<_, _, _>, input: VectorData, params: [u32, f64, DVec2]),
 		register_node!(graphene_core::vector::generator_nodes::UnitCircleGenerator, input: (), params: []),
+		register_node!(graphene_core::vector::generator_nodes::ArrowGenerator<_, _, _>, input: (), params: [f64, f64, f64]),
+		register_node!(graphene_core::vector::generator_nodes::SpeechBubbleGenerator<_, _, _>, input: (), params: [f64, f64, f64]),
+		register_node!(graphene_core::vector::generator_nodes::RectangleGenerator<_, _, _, _, _>, input: (), params: [DVec2, f64, f64, f64, f64]),
+		register_node!(graphene_core::vector::generator_nodes::GearGenerator<_, _>, input: (), params: [u32, f64]),
+		register_node!(graphene_core::vector::generator_nodes::HeartGenerator, input: (), params: []),
+		register_node!(graphene_core::vector::generator_nodes::PlaceholderImageGenerator, input: (), params: []),
+		register_node!(graphene_core::vector::generator_nodes::QrCodeGenerator<_>, input: (), params: [String]),
+		register_node!(graphene_core::vector::generator_nodes::BarcodeGenerator<_>, input: (), params: [String]),
+		register_node!(graphene_core::vector::generator_nodes::BarChartGenerator<_, _>, input: (), params: [String, f64]),
+		register_node!(graphene_core::vector::generator_nodes::LineChartGenerator<_>, input: (), params: [String]),
+		register_node!(graphene_core::vector::generator_nodes::PieChartGenerator<_, _>, input: (), params: [String, f64]),
 		register_node!(
 			graphene_core::vector::generator_nodes::PathGenerator<_>,
 			input: Vec<graphene_core::vector::bezier_rs::Subpath<graphene_core::uuid::ManipulatorGroupId>>,
 			params: [Vec<graphene_core::uuid::ManipulatorGroupId>]
 		),
-		register_node!(graphene_core::text::TextGenerator<_, _, _>, input: WasmEditorApi, params: [String, graphene_core::text::Font, f32]),
+		register_node!(graphene_core::text::TextGenerator<_, _, _, _>, input: WasmEditorApi, params: [String, graphene_core::text::Font, f32, Vec<f32>]),
 		register_node!(graphene_std::brush::VectorPointsNode, input: VectorData, params: []),
 		register_node!(graphene_core::ExtractImageFrame, input: WasmEditorApi, params: []),
 		register_node!(graphene_core::ConstructLayerNode<_, _, _, _, _, _, _>, input: graphene_core::vector::VectorData, params: [String, BlendMode, f32, bool, bool, bool, graphene_core::GraphicGroup]),